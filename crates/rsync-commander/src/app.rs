@@ -1,24 +1,46 @@
 use std::sync::Arc;
 
+use chrono::{DateTime, Local, Utc};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use uuid::Uuid;
 
+use rsync_core::file_system::real_file_system::RealFileSystem;
+use rsync_core::rsync_client::process_rsync_client::ProcessRsyncClient;
 use rsync_core::models::backup::{BackupInvocation, InvocationTrigger};
-use rsync_core::models::job::JobDefinition;
-use rsync_core::models::progress::{JobStatusEvent, LogLine, ProgressUpdate};
+use rsync_core::models::job::{JobAppearance, JobDefinition, JobRunPolicy, JobSafety};
+use rsync_core::models::progress::{AggregateProgress, JobStatusEvent, LogLine, ProgressUpdate};
 use rsync_core::models::statistics::AggregatedStats;
 use rsync_core::models::command::CommandExplanation;
+use rsync_core::models::pattern::PatternTestEntry;
+use rsync_core::models::schedule::{ScheduleConfig, ScheduleType};
+use rsync_core::services::audit::AuditLog;
 use rsync_core::services::command_explainer;
 use rsync_core::services::command_parser;
 use rsync_core::services::job_executor::JobExecutor;
 use rsync_core::services::job_service::JobService;
+use rsync_core::services::log_relocation;
+use rsync_core::services::pattern_tester;
+use rsync_core::services::rsync_glob;
+use rsync_core::services::scheduler;
+use rsync_core::services::scheduler_events::SchedulerEventLog;
 use rsync_core::services::settings_service::SettingsService;
+use rsync_core::services::snapshot_diff;
 use rsync_core::services::statistics_service::StatisticsService;
+use rsync_core::services::test_mode::command_was_dry_run;
 
 use crate::handler::{TuiEvent, TuiEventHandler};
 use crate::theme::{self, Theme};
 use crate::ui::text_input::TextInput;
 
+/// How many upcoming runs the schedule preview tool lists.
+const SCHEDULE_PREVIEW_COUNT: usize = 10;
+
+/// How many audit entries the History page's audit popup shows.
+const AUDIT_LOG_POPUP_LIMIT: usize = 100;
+
+/// Setting that makes `r`/`d` show the rsync command for confirmation first.
+const REVIEW_BEFORE_RUN_KEY: &str = "tui_review_before_run";
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Page {
     Jobs,
@@ -71,6 +93,50 @@ pub enum PopupKind {
         action: ConfirmAction,
     },
     Error(String),
+    /// Recent scheduler decisions for one job, already formatted.
+    SchedulerEvents {
+        title: String,
+        lines: Vec<String>,
+    },
+    /// Recent destructive actions, already formatted.
+    AuditLog {
+        lines: Vec<String>,
+    },
+    /// Changes between a job's two latest snapshots, already formatted.
+    SnapshotDiff {
+        title: String,
+        lines: Vec<String>,
+    },
+    /// A job's snapshots, newest first, with one selected for pinning.
+    Snapshots {
+        title: String,
+        rows: Vec<SnapshotRow>,
+        selected: usize,
+    },
+    /// Other jobs to copy rsync options from into the job form, plus whether
+    /// their SSH settings and schedule come along.
+    CopyJobSettings {
+        rows: Vec<JobPickRow>,
+        selected: usize,
+        include_ssh: bool,
+        include_schedule: bool,
+    },
+}
+
+/// One job in the copy-settings picker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobPickRow {
+    pub id: Uuid,
+    pub name: String,
+}
+
+/// One snapshot in the snapshots popup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotRow {
+    pub id: Uuid,
+    /// Creation time and path, already formatted.
+    pub label: String,
+    pub pinned: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -80,6 +146,9 @@ pub enum ConfirmAction {
     DeleteAllHistory(Uuid),
     ResetStatistics,
     ResetStatisticsForJob(Uuid),
+    RecomputeStatistics,
+    CancelAllJobs,
+    RunJob { job_id: Uuid, dry_run: bool },
 }
 
 /// Mode for the job form
@@ -109,6 +178,30 @@ impl Default for JobsState {
     }
 }
 
+impl JobsState {
+    /// Jobs whose name matches the search text.
+    pub fn filtered_jobs(&self) -> Vec<&JobDefinition> {
+        let query = self.search_input.value();
+        self.jobs
+            .iter()
+            .filter(|j| job_name_matches(query, &j.name))
+            .collect()
+    }
+}
+
+/// Case-insensitive match of a job name against the search text: a glob over
+/// the whole name when it contains `*` or `?` (e.g. `docs-*`, `*offsite`),
+/// otherwise a substring. An empty query matches every job.
+pub fn job_name_matches(query: &str, name: &str) -> bool {
+    let query = query.to_lowercase();
+    let name = name.to_lowercase();
+    if query.contains(['*', '?']) {
+        rsync_glob::matches(&query, &name, false)
+    } else {
+        name.contains(&query)
+    }
+}
+
 /// State for viewing live job output
 #[derive(Debug)]
 pub struct JobOutputState {
@@ -131,6 +224,25 @@ pub struct JobFormState {
     pub field_inputs: Vec<TextInput>,
 }
 
+impl JobFormState {
+    /// Adopt `source`'s rsync options, and optionally its SSH settings and
+    /// schedule. Name, description, source, destination and backup mode stay.
+    pub fn copy_settings_from(
+        &mut self,
+        source: &JobDefinition,
+        include_ssh: bool,
+        include_schedule: bool,
+    ) {
+        self.job.options = source.options.clone();
+        if include_ssh {
+            self.job.ssh_config = source.ssh_config.clone();
+        }
+        if include_schedule {
+            self.job.schedule = source.schedule.clone();
+        }
+    }
+}
+
 /// State for the history page
 #[derive(Debug)]
 pub struct HistoryState {
@@ -160,20 +272,73 @@ impl Default for HistoryState {
 }
 
 /// State for the statistics page
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct StatisticsState {
     pub aggregated: Option<AggregatedStats>,
     pub per_job: Vec<(String, AggregatedStats)>,
+    /// Index into `filtered_per_job()`, not `per_job`.
     pub selected: usize,
+    pub search_active: bool,
+    pub search_input: TextInput,
+}
+
+impl Default for StatisticsState {
+    fn default() -> Self {
+        Self {
+            aggregated: None,
+            per_job: Vec::new(),
+            selected: 0,
+            search_active: false,
+            search_input: TextInput::new(),
+        }
+    }
+}
+
+impl StatisticsState {
+    /// Per-job rows whose job name contains the search text (case-insensitive).
+    pub fn filtered_per_job(&self) -> Vec<&(String, AggregatedStats)> {
+        let query = self.search_input.value().to_lowercase();
+        if query.is_empty() {
+            self.per_job.iter().collect()
+        } else {
+            self.per_job
+                .iter()
+                .filter(|(name, _)| name.to_lowercase().contains(&query))
+                .collect()
+        }
+    }
+
+    /// Keep the selection on a visible row after the filter or data changes.
+    pub fn clamp_selection(&mut self) {
+        let len = self.filtered_per_job().len();
+        self.selected = self.selected.min(len.saturating_sub(1));
+    }
 }
 
 /// State for the tools page
 pub struct ToolsState {
-    pub active_tab: usize, // 0 = explainer, 1 = scrubber
+    pub active_tab: usize, // 0 = explainer, 1 = scrubber, 2 = pattern tester, 3 = schedule preview
     pub command_input: TextInput,
     pub explanation: Option<CommandExplanation>,
     pub explanation_error: Option<String>,
     pub scrub_input: TextInput,
+    pub pattern_source_input: TextInput,
+    pub pattern_filter_input: TextInput,
+    pub pattern_results: Vec<PatternTestEntry>,
+    pub pattern_error: Option<String>,
+    pub schedule_input: TextInput,
+    pub schedule_preview: Vec<DateTime<Local>>,
+    pub schedule_error: Option<String>,
+}
+
+impl ToolsState {
+    fn has_focused_input(&self) -> bool {
+        self.command_input.is_focused
+            || self.scrub_input.is_focused
+            || self.pattern_source_input.is_focused
+            || self.pattern_filter_input.is_focused
+            || self.schedule_input.is_focused
+    }
 }
 
 impl Default for ToolsState {
@@ -184,6 +349,13 @@ impl Default for ToolsState {
             explanation: None,
             explanation_error: None,
             scrub_input: TextInput::new(),
+            pattern_source_input: TextInput::new(),
+            pattern_filter_input: TextInput::new(),
+            pattern_results: Vec::new(),
+            pattern_error: None,
+            schedule_input: TextInput::new(),
+            schedule_preview: Vec::new(),
+            schedule_error: None,
         }
     }
 }
@@ -199,6 +371,9 @@ pub struct SettingsState {
     pub max_history_per_job: usize,
     pub auto_trailing_slash: bool,
     pub tui_theme: String,
+    pub test_mode: bool,
+    pub relative_paths: bool,
+    pub review_before_run: bool,
 }
 
 impl Default for SettingsState {
@@ -212,6 +387,9 @@ impl Default for SettingsState {
             max_history_per_job: 15,
             auto_trailing_slash: true,
             tui_theme: "Default".to_string(),
+            test_mode: false,
+            relative_paths: false,
+            review_before_run: false,
         }
     }
 }
@@ -221,6 +399,8 @@ pub struct AppServices {
     pub job_service: Arc<JobService>,
     pub statistics_service: Arc<StatisticsService>,
     pub settings_service: Arc<SettingsService>,
+    pub scheduler_events: Arc<SchedulerEventLog>,
+    pub audit_log: Arc<AuditLog>,
 }
 
 pub struct PageStates {
@@ -253,6 +433,8 @@ impl App {
         job_service: Arc<JobService>,
         statistics_service: Arc<StatisticsService>,
         settings_service: Arc<SettingsService>,
+        scheduler_events: Arc<SchedulerEventLog>,
+        audit_log: Arc<AuditLog>,
         job_sender: std::sync::mpsc::Sender<TuiEvent>,
     ) -> Self {
         // Load theme from settings
@@ -271,6 +453,8 @@ impl App {
                 job_service,
                 statistics_service,
                 settings_service,
+                scheduler_events,
+                audit_log,
             },
             theme,
             job_sender,
@@ -340,6 +524,7 @@ impl App {
                 }
             }
             self.pages.statistics.per_job = per_job;
+            self.pages.statistics.clamp_selection();
         }
     }
 
@@ -356,11 +541,18 @@ impl App {
             self.pages.settings.max_history_per_job = ret.max_history_per_job;
         }
         self.pages.settings.auto_trailing_slash = ss.get_auto_trailing_slash().unwrap_or(true);
+        self.pages.settings.test_mode = ss.get_test_mode().unwrap_or(false);
+        self.pages.settings.relative_paths = ss.get_relative_paths().unwrap_or(false);
         self.pages.settings.tui_theme = ss
             .get_setting("tui_theme")
             .ok()
             .flatten()
             .unwrap_or_else(|| "Default".to_string());
+        self.pages.settings.review_before_run = ss
+            .get_setting(REVIEW_BEFORE_RUN_KEY)
+            .ok()
+            .flatten()
+            .is_some_and(|v| v == "true");
     }
 
     /// Handle a job execution event from the background thread.
@@ -406,7 +598,11 @@ impl App {
         // Popup handling takes priority
         if let Some(popup) = &self.overlays.popup {
             match popup {
-                PopupKind::Help | PopupKind::Error(_) => {
+                PopupKind::Help
+                | PopupKind::Error(_)
+                | PopupKind::SchedulerEvents { .. }
+                | PopupKind::AuditLog { .. }
+                | PopupKind::SnapshotDiff { .. } => {
                     match key.code {
                         KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char('?') => {
                             self.overlays.popup = None;
@@ -415,6 +611,14 @@ impl App {
                     }
                     return;
                 }
+                PopupKind::Snapshots { .. } => {
+                    self.handle_snapshots_key(key);
+                    return;
+                }
+                PopupKind::CopyJobSettings { .. } => {
+                    self.handle_copy_settings_key(key);
+                    return;
+                }
                 PopupKind::Confirm { action, .. } => {
                     match key.code {
                         KeyCode::Char('y') | KeyCode::Enter => {
@@ -456,10 +660,14 @@ impl App {
             return;
         }
 
+        // Search mode in statistics
+        if self.pages.statistics.search_active {
+            self.handle_statistics_search_key(key);
+            return;
+        }
+
         // Text input mode in tools
-        if self.current_page == Page::Tools
-            && (self.pages.tools.command_input.is_focused || self.pages.tools.scrub_input.is_focused)
-        {
+        if self.current_page == Page::Tools && self.pages.tools.has_focused_input() {
             self.handle_tools_input_key(key);
             return;
         }
@@ -554,6 +762,33 @@ impl App {
                     self.refresh_statistics();
                 }
             }
+            ConfirmAction::RecomputeStatistics => {
+                match self
+                    .services
+                    .statistics_service
+                    .recompute_from_invocations(&self.services.job_service)
+                {
+                    Ok(_) => self.refresh_statistics(),
+                    Err(e) => {
+                        self.overlays.popup = Some(PopupKind::Error(format!(
+                            "Failed to recompute statistics: {}",
+                            e
+                        )));
+                    }
+                }
+            }
+            ConfirmAction::CancelAllJobs => {
+                self.services.job_executor.cancel_all();
+            }
+            ConfirmAction::RunJob { job_id, dry_run } => {
+                match self.services.job_service.get_job(&job_id) {
+                    Ok(job) => self.run_job(&job, dry_run),
+                    Err(e) => {
+                        self.overlays.popup =
+                            Some(PopupKind::Error(format!("Failed to execute job: {}", e)));
+                    }
+                }
+            }
         }
     }
 
@@ -581,12 +816,12 @@ impl App {
             }
             KeyCode::Char('r') => {
                 if let Some(job) = self.selected_job() {
-                    self.run_job(&job.clone(), false);
+                    self.request_run(&job, false);
                 }
             }
             KeyCode::Char('d') => {
                 if let Some(job) = self.selected_job() {
-                    self.run_job(&job.clone(), true);
+                    self.request_run(&job, true);
                 }
             }
             KeyCode::Char('c') => {
@@ -595,6 +830,16 @@ impl App {
                     self.services.job_executor.cancel(&job_id);
                 }
             }
+            KeyCode::Char('C') => {
+                let running = self.services.job_executor.running_job_ids().len();
+                if running > 0 {
+                    self.overlays.popup = Some(PopupKind::Confirm {
+                        title: "Cancel All Jobs".to_string(),
+                        message: format!("Cancel {} running job(s)?", running),
+                        action: ConfirmAction::CancelAllJobs,
+                    });
+                }
+            }
             KeyCode::Char('x') => {
                 if let Some(job) = self.selected_job() {
                     self.overlays.popup = Some(PopupKind::Confirm {
@@ -609,6 +854,24 @@ impl App {
                     self.open_job_output(job.id, job.name.clone());
                 }
             }
+            KeyCode::Char('e') => {
+                if let Some(job) = self.selected_job() {
+                    let (job_id, name) = (job.id, job.name.clone());
+                    self.open_scheduler_events(job_id, &name);
+                }
+            }
+            KeyCode::Char('s') => {
+                if let Some(job) = self.selected_job() {
+                    let (job_id, name) = (job.id, job.name.clone());
+                    self.open_snapshot_diff(job_id, &name);
+                }
+            }
+            KeyCode::Char('S') => {
+                if let Some(job) = self.selected_job() {
+                    let (job_id, name) = (job.id, job.name.clone());
+                    self.open_snapshots(job_id, &name);
+                }
+            }
             KeyCode::Char('/') => {
                 self.pages.jobs.search_active = true;
                 self.pages.jobs.search_input.clear();
@@ -618,6 +881,141 @@ impl App {
         }
     }
 
+    fn open_scheduler_events(&mut self, job_id: Uuid, name: &str) {
+        let events = self.services.scheduler_events.scheduler_events(&job_id);
+        let lines = if events.is_empty() {
+            vec!["No scheduler decisions recorded since startup".to_string()]
+        } else {
+            events
+                .iter()
+                .map(|e| {
+                    format!(
+                        "{}  {}",
+                        e.timestamp
+                            .with_timezone(&chrono::Local)
+                            .format("%Y-%m-%d %H:%M:%S"),
+                        e.decision
+                    )
+                })
+                .collect()
+        };
+        self.overlays.popup = Some(PopupKind::SchedulerEvents {
+            title: format!("Scheduler: {}", name),
+            lines,
+        });
+    }
+
+    /// Show what changed between the job's two most recent snapshots.
+    fn open_snapshot_diff(&mut self, job_id: Uuid, name: &str) {
+        let snapshots = match self.services.job_service.list_snapshots(&job_id) {
+            Ok(snapshots) => snapshots,
+            Err(e) => {
+                self.overlays.popup = Some(PopupKind::Error(e.to_string()));
+                return;
+            }
+        };
+        let Some((older, newer)) = snapshot_diff::latest_snapshot_pair(&snapshots) else {
+            self.overlays.popup = Some(PopupKind::Error(
+                "Comparing needs at least two snapshots".to_string(),
+            ));
+            return;
+        };
+        let diff = match snapshot_diff::diff_snapshots(older, newer, &ProcessRsyncClient::new()) {
+            Ok(diff) => diff,
+            Err(e) => {
+                self.overlays.popup = Some(PopupKind::Error(e.to_string()));
+                return;
+            }
+        };
+
+        let mut lines = vec![
+            format!("{} -> {}", diff.from_path, diff.to_path),
+            format!(
+                "{} created, {} updated, {} deleted",
+                diff.created.len(),
+                diff.updated.len(),
+                diff.deleted.len()
+            ),
+            String::new(),
+        ];
+        let groups = [
+            ("+", &diff.created),
+            ("~", &diff.updated),
+            ("-", &diff.deleted),
+        ];
+        for (marker, changes) in groups {
+            lines.extend(changes.iter().map(|c| format!("{} {}", marker, c.path)));
+        }
+        self.overlays.popup = Some(PopupKind::SnapshotDiff {
+            title: format!("Snapshot diff: {}", name),
+            lines,
+        });
+    }
+
+    /// List the job's snapshots so one can be pinned or unpinned.
+    fn open_snapshots(&mut self, job_id: Uuid, name: &str) {
+        let snapshots = match self.services.job_service.list_snapshots(&job_id) {
+            Ok(snapshots) => snapshots,
+            Err(e) => {
+                self.overlays.popup = Some(PopupKind::Error(e.to_string()));
+                return;
+            }
+        };
+        if snapshots.is_empty() {
+            self.overlays.popup = Some(PopupKind::Error("This job has no snapshots".to_string()));
+            return;
+        }
+        let rows = snapshots
+            .iter()
+            .map(|snap| SnapshotRow {
+                id: snap.id,
+                label: format!(
+                    "{}  {}",
+                    snap.created_at
+                        .with_timezone(&Local)
+                        .format("%Y-%m-%d %H:%M"),
+                    snap.snapshot_path
+                ),
+                pinned: snap.pinned,
+            })
+            .collect();
+        self.overlays.popup = Some(PopupKind::Snapshots {
+            title: format!("Snapshots: {}", name),
+            rows,
+            selected: 0,
+        });
+    }
+
+    fn handle_snapshots_key(&mut self, key: KeyEvent) {
+        let Some(PopupKind::Snapshots { rows, selected, .. }) = &mut self.overlays.popup else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.overlays.popup = None;
+            }
+            KeyCode::Char('j') | KeyCode::Down if *selected + 1 < rows.len() => {
+                *selected += 1;
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                *selected = selected.saturating_sub(1);
+            }
+            KeyCode::Char('p') => {
+                if let Some(row) = rows.get_mut(*selected) {
+                    match self
+                        .services
+                        .job_service
+                        .set_snapshot_pinned(&row.id, !row.pinned)
+                    {
+                        Ok(()) => row.pinned = !row.pinned,
+                        Err(e) => self.overlays.popup = Some(PopupKind::Error(e.to_string())),
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
     fn handle_jobs_search_key(&mut self, key: KeyEvent) {
         match key.code {
             KeyCode::Esc => {
@@ -635,16 +1033,7 @@ impl App {
     }
 
     pub fn filtered_jobs(&self) -> Vec<&JobDefinition> {
-        let query = self.pages.jobs.search_input.value().to_lowercase();
-        if query.is_empty() {
-            self.pages.jobs.jobs.iter().collect()
-        } else {
-            self.pages.jobs
-                .jobs
-                .iter()
-                .filter(|j| j.name.to_lowercase().contains(&query))
-                .collect()
-        }
+        self.pages.jobs.filtered_jobs()
     }
 
     fn selected_job(&self) -> Option<JobDefinition> {
@@ -652,6 +1041,35 @@ impl App {
         jobs.get(self.pages.jobs.selected).map(|j| (*j).clone())
     }
 
+    /// Run the job, or with "Review Before Run" on, first show the command it
+    /// will run and run it only once confirmed.
+    fn request_run(&mut self, job: &JobDefinition, dry_run: bool) {
+        if !self.pages.settings.review_before_run {
+            self.run_job(job, dry_run);
+            return;
+        }
+        let mut reviewed = job.clone();
+        if dry_run {
+            reviewed.options.core_transfer.dry_run = true;
+        }
+        let command = self.services.job_executor.preview_command(&reviewed);
+        let yes_no = |on: bool| if on { "yes" } else { "no" };
+        let message = format!(
+            "{}\n\nDelete extraneous files: {}   Dry run: {}",
+            command,
+            yes_no(reviewed.options.file_handling.delete),
+            yes_no(command_was_dry_run(&command)),
+        );
+        self.overlays.popup = Some(PopupKind::Confirm {
+            title: format!("Run '{}'?", job.name),
+            message,
+            action: ConfirmAction::RunJob {
+                job_id: job.id,
+                dry_run,
+            },
+        });
+    }
+
     fn run_job(&mut self, job: &JobDefinition, dry_run: bool) {
         let mut job = job.clone();
         if dry_run {
@@ -686,31 +1104,9 @@ impl App {
     }
 
     fn open_job_form_create(&mut self) {
-        let now = chrono::Utc::now();
-        let job = JobDefinition {
-            id: uuid::Uuid::new_v4(),
-            name: String::new(),
-            description: None,
-            transfer: rsync_core::models::job::TransferConfig {
-                source: rsync_core::models::job::StorageLocation::Local {
-                    path: String::new(),
-                },
-                destination: rsync_core::models::job::StorageLocation::Local {
-                    path: String::new(),
-                },
-                backup_mode: rsync_core::models::job::BackupMode::Mirror,
-            },
-            options: rsync_core::models::job::RsyncOptions::default(),
-            ssh_config: None,
-            schedule: None,
-            enabled: true,
-            created_at: now,
-            updated_at: now,
-        };
-
         self.overlays.job_form = Some(JobFormState {
             mode: JobFormMode::Create,
-            job,
+            job: blank_job(),
             field_index: 0,
             editing: false,
             field_inputs: (0..20).map(|_| TextInput::new()).collect(),
@@ -858,6 +1254,71 @@ impl App {
             KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                 self.save_job_form();
             }
+            KeyCode::Char('c') => self.open_copy_settings_picker(),
+            _ => {}
+        }
+    }
+
+    /// Offer every other job as a source of settings for the open form.
+    fn open_copy_settings_picker(&mut self) {
+        let Some(form) = &self.overlays.job_form else {
+            return;
+        };
+        let rows: Vec<JobPickRow> = self
+            .pages
+            .jobs
+            .jobs
+            .iter()
+            .filter(|job| job.id != form.job.id)
+            .map(|job| JobPickRow {
+                id: job.id,
+                name: job.name.clone(),
+            })
+            .collect();
+        self.overlays.popup = Some(if rows.is_empty() {
+            PopupKind::Error("No other jobs to copy settings from".to_string())
+        } else {
+            PopupKind::CopyJobSettings {
+                rows,
+                selected: 0,
+                include_ssh: false,
+                include_schedule: false,
+            }
+        });
+    }
+
+    fn handle_copy_settings_key(&mut self, key: KeyEvent) {
+        let Some(PopupKind::CopyJobSettings {
+            rows,
+            selected,
+            include_ssh,
+            include_schedule,
+        }) = &mut self.overlays.popup
+        else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.overlays.popup = None;
+            }
+            KeyCode::Char('j') | KeyCode::Down if *selected + 1 < rows.len() => {
+                *selected += 1;
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                *selected = selected.saturating_sub(1);
+            }
+            KeyCode::Char('s') => *include_ssh = !*include_ssh,
+            KeyCode::Char('t') => *include_schedule = !*include_schedule,
+            KeyCode::Enter => {
+                let picked = rows.get(*selected).map(|row| row.id);
+                let (include_ssh, include_schedule) = (*include_ssh, *include_schedule);
+                self.overlays.popup = None;
+                let source =
+                    picked.and_then(|id| self.pages.jobs.jobs.iter().find(|job| job.id == id));
+                if let (Some(source), Some(form)) = (source, self.overlays.job_form.as_mut()) {
+                    form.copy_settings_from(source, include_ssh, include_schedule);
+                }
+            }
             _ => {}
         }
     }
@@ -908,6 +1369,7 @@ impl App {
                     }
                 }
             }
+            KeyCode::Char('a') => self.open_audit_log(),
             KeyCode::Char('d') => {
                 if let Some(inv) = self.pages.history.invocations.get(self.pages.history.selected) {
                     self.overlays.popup = Some(PopupKind::Confirm {
@@ -921,6 +1383,30 @@ impl App {
         }
     }
 
+    fn open_audit_log(&mut self) {
+        let lines = match self.services.audit_log.list(AUDIT_LOG_POPUP_LIMIT) {
+            Ok(entries) if entries.is_empty() => {
+                vec!["No destructive actions recorded".to_string()]
+            }
+            Ok(entries) => entries
+                .iter()
+                .map(|e| {
+                    format!(
+                        "{}  [{}] {}: {}",
+                        e.timestamp
+                            .with_timezone(&Local)
+                            .format("%Y-%m-%d %H:%M:%S"),
+                        e.source.as_deref().unwrap_or("-"),
+                        e.action,
+                        e.detail
+                    )
+                })
+                .collect(),
+            Err(e) => vec![format!("Failed to read audit log: {}", e)],
+        };
+        self.overlays.popup = Some(PopupKind::AuditLog { lines });
+    }
+
     fn handle_log_viewer_key(&mut self, key: KeyEvent) {
         match key.code {
             KeyCode::Esc | KeyCode::Char('q') => {
@@ -962,6 +1448,13 @@ impl App {
                     action: ConfirmAction::ResetStatistics,
                 });
             }
+            KeyCode::Char('c') => {
+                self.overlays.popup = Some(PopupKind::Confirm {
+                    title: "Recompute Statistics".to_string(),
+                    message: "Rebuild all statistics from stored run history?".to_string(),
+                    action: ConfirmAction::RecomputeStatistics,
+                });
+            }
             KeyCode::Char('e') => {
                 if let Ok(json) = self.services.statistics_service.export() {
                     let path = format!(
@@ -974,7 +1467,7 @@ impl App {
                 }
             }
             KeyCode::Char('j') | KeyCode::Down => {
-                let len = self.pages.statistics.per_job.len();
+                let len = self.pages.statistics.filtered_per_job().len();
                 if len > 0 {
                     self.pages.statistics.selected =
                         (self.pages.statistics.selected + 1).min(len - 1);
@@ -983,24 +1476,45 @@ impl App {
             KeyCode::Char('k') | KeyCode::Up => {
                 self.pages.statistics.selected = self.pages.statistics.selected.saturating_sub(1);
             }
+            KeyCode::Char('/') => {
+                self.pages.statistics.search_active = true;
+                self.pages.statistics.search_input.clear();
+                self.pages.statistics.search_input.is_focused = true;
+            }
             _ => {}
         }
     }
 
+    fn handle_statistics_search_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                self.pages.statistics.search_active = false;
+                self.pages.statistics.search_input.is_focused = false;
+                self.pages.statistics.search_input.clear();
+            }
+            KeyCode::Enter => {
+                self.pages.statistics.search_input.is_focused = false;
+            }
+            _ => {
+                self.pages.statistics.search_input.handle_key(key);
+            }
+        }
+        self.pages.statistics.clamp_selection();
+    }
+
     // --- Tools page keys ---
 
     fn handle_tools_key(&mut self, key: KeyEvent) {
         match key.code {
             KeyCode::Tab => {
-                self.pages.tools.active_tab = (self.pages.tools.active_tab + 1) % 2;
-            }
-            KeyCode::Enter | KeyCode::Char('i') => {
-                if self.pages.tools.active_tab == 0 {
-                    self.pages.tools.command_input.is_focused = true;
-                } else {
-                    self.pages.tools.scrub_input.is_focused = true;
-                }
+                self.pages.tools.active_tab = (self.pages.tools.active_tab + 1) % 4;
             }
+            KeyCode::Enter | KeyCode::Char('i') => match self.pages.tools.active_tab {
+                0 => self.pages.tools.command_input.is_focused = true,
+                1 => self.pages.tools.scrub_input.is_focused = true,
+                2 => self.pages.tools.pattern_source_input.is_focused = true,
+                _ => self.pages.tools.schedule_input.is_focused = true,
+            },
             _ => {}
         }
     }
@@ -1038,13 +1552,144 @@ impl App {
                     self.pages.tools.scrub_input.handle_key(key);
                 }
             }
+        } else if self.pages.tools.pattern_source_input.is_focused {
+            match key.code {
+                KeyCode::Esc => {
+                    self.pages.tools.pattern_source_input.is_focused = false;
+                }
+                KeyCode::Enter | KeyCode::Tab => {
+                    // Move on to the filter rules
+                    self.pages.tools.pattern_source_input.is_focused = false;
+                    self.pages.tools.pattern_filter_input.is_focused = true;
+                }
+                _ => {
+                    self.pages.tools.pattern_source_input.handle_key(key);
+                }
+            }
+        } else if self.pages.tools.pattern_filter_input.is_focused {
+            match key.code {
+                KeyCode::Esc => {
+                    self.pages.tools.pattern_filter_input.is_focused = false;
+                }
+                KeyCode::Enter => {
+                    self.pages.tools.pattern_filter_input.is_focused = false;
+                    self.run_pattern_test();
+                }
+                _ => {
+                    self.pages.tools.pattern_filter_input.handle_key(key);
+                }
+            }
+        } else if self.pages.tools.schedule_input.is_focused {
+            match key.code {
+                KeyCode::Esc => {
+                    self.pages.tools.schedule_input.is_focused = false;
+                }
+                KeyCode::Enter => {
+                    self.pages.tools.schedule_input.is_focused = false;
+                    self.run_schedule_preview();
+                }
+                _ => {
+                    self.pages.tools.schedule_input.handle_key(key);
+                }
+            }
+        }
+    }
+
+    /// Preview the next runs of the schedule entered on the Tools page: a
+    /// number of minutes for an interval, anything else as a cron expression.
+    /// Runs inside quiet hours are shown where the scheduler catches up.
+    fn run_schedule_preview(&mut self) {
+        let input = self.pages.tools.schedule_input.value().trim().to_string();
+        if input.is_empty() {
+            self.pages.tools.schedule_preview.clear();
+            self.pages.tools.schedule_error = None;
+            return;
+        }
+        let schedule_type = match input.parse::<u64>() {
+            Ok(0) => {
+                self.pages.tools.schedule_preview.clear();
+                self.pages.tools.schedule_error =
+                    Some("Interval must be at least 1 minute".to_string());
+                return;
+            }
+            Ok(minutes) => ScheduleType::Interval { minutes },
+            Err(_) => ScheduleType::Cron { expression: input },
+        };
+        let schedule = ScheduleConfig {
+            schedule_type,
+            enabled: true,
+        };
+        let quiet_hours = self
+            .services
+            .settings_service
+            .get_quiet_hours()
+            .ok()
+            .flatten();
+        let runs = scheduler::upcoming_runs_around_quiet_hours(
+            &schedule,
+            Utc::now(),
+            SCHEDULE_PREVIEW_COUNT,
+            quiet_hours.as_ref(),
+            &Local,
+        );
+        if runs.is_empty() {
+            self.pages.tools.schedule_preview.clear();
+            self.pages.tools.schedule_error = Some("Invalid cron expression".to_string());
+        } else {
+            self.pages.tools.schedule_preview =
+                runs.iter().map(|r| r.with_timezone(&Local)).collect();
+            self.pages.tools.schedule_error = None;
+        }
+    }
+
+    /// Run the pattern tester with the source dir and filter rules entered on the
+    /// Tools page. Rules are comma separated; `+ pat` includes, `- pat` or a bare
+    /// pattern excludes.
+    fn run_pattern_test(&mut self) {
+        let source = self
+            .pages
+            .tools
+            .pattern_source_input
+            .value()
+            .trim()
+            .to_string();
+        let mut includes = Vec::new();
+        let mut excludes = Vec::new();
+        for rule in self.pages.tools.pattern_filter_input.value().split(',') {
+            let rule = rule.trim();
+            if let Some(pattern) = rule.strip_prefix("+ ") {
+                includes.push(pattern.trim().to_string());
+            } else if let Some(pattern) = rule.strip_prefix("- ") {
+                excludes.push(pattern.trim().to_string());
+            } else if !rule.is_empty() {
+                excludes.push(rule.to_string());
+            }
+        }
+
+        let fs = RealFileSystem::new();
+        match pattern_tester::test_pattern_entries(
+            &fs,
+            std::path::Path::new(&source),
+            &includes,
+            &excludes,
+        ) {
+            Ok(entries) => {
+                self.pages.tools.pattern_results = entries;
+                self.pages.tools.pattern_error = None;
+            }
+            Err(e) => {
+                self.pages.tools.pattern_results.clear();
+                self.pages.tools.pattern_error = Some(e.to_string());
+            }
         }
     }
 
     // --- Settings page keys ---
 
     fn handle_settings_key(&mut self, key: KeyEvent) {
-        let settings_count = 5; // log_dir, max_age, max_per_job, auto_slash, theme
+        // log_dir, max_age, max_per_job, auto_slash, theme, test_mode, relative_paths,
+        // review_before_run
+        let settings_count = 8;
         match key.code {
             KeyCode::Char('j') | KeyCode::Down => {
                 self.pages.settings.selected =
@@ -1083,6 +1728,33 @@ impl App {
                         self.pages.settings.editing = false;
                         return;
                     }
+                    5 => {
+                        // Toggle boolean
+                        let new_val = !self.pages.settings.test_mode;
+                        let _ = self.services.settings_service.set_test_mode(new_val);
+                        self.pages.settings.test_mode = new_val;
+                        self.pages.settings.editing = false;
+                        return;
+                    }
+                    6 => {
+                        // Toggle boolean
+                        let new_val = !self.pages.settings.relative_paths;
+                        let _ = self.services.settings_service.set_relative_paths(new_val);
+                        self.pages.settings.relative_paths = new_val;
+                        self.pages.settings.editing = false;
+                        return;
+                    }
+                    7 => {
+                        // Toggle boolean
+                        let new_val = !self.pages.settings.review_before_run;
+                        let _ = self.services.settings_service.set_setting(
+                            REVIEW_BEFORE_RUN_KEY,
+                            if new_val { "true" } else { "false" },
+                        );
+                        self.pages.settings.review_before_run = new_val;
+                        self.pages.settings.editing = false;
+                        return;
+                    }
                     _ => String::new(),
                 };
                 self.pages.settings.edit_input.set_value(&val);
@@ -1119,28 +1791,40 @@ impl App {
 
                 match self.pages.settings.selected {
                     0 => {
-                        let _ = self.services.settings_service.set_log_directory(&val);
-                        self.pages.settings.log_directory = val;
+                        // Move existing logs along with the setting
+                        match log_relocation::relocate_logs(
+                            &RealFileSystem::new(),
+                            &self.services.job_service,
+                            &self.services.settings_service,
+                            self.services.job_executor.default_log_dir(),
+                            &val,
+                        ) {
+                            Ok(_) => self.pages.settings.log_directory = val,
+                            Err(e) => {
+                                self.overlays.popup =
+                                    Some(PopupKind::Error(format!("Failed to move logs: {}", e)));
+                            }
+                        }
                     }
                     1 => {
                         if let Ok(days) = val.parse::<u32>() {
-                            let _ = self.services.settings_service.set_retention_settings(
-                                &rsync_core::models::settings::RetentionSettings {
-                                    max_log_age_days: days,
-                                    max_history_per_job: self.pages.settings.max_history_per_job,
-                                },
-                            );
+                            if let Ok(mut ret) =
+                                self.services.settings_service.get_retention_settings()
+                            {
+                                ret.max_log_age_days = days;
+                                let _ = self.services.settings_service.set_retention_settings(&ret);
+                            }
                             self.pages.settings.max_log_age_days = days;
                         }
                     }
                     2 => {
                         if let Ok(max) = val.parse::<usize>() {
-                            let _ = self.services.settings_service.set_retention_settings(
-                                &rsync_core::models::settings::RetentionSettings {
-                                    max_log_age_days: self.pages.settings.max_log_age_days,
-                                    max_history_per_job: max,
-                                },
-                            );
+                            if let Ok(mut ret) =
+                                self.services.settings_service.get_retention_settings()
+                            {
+                                ret.max_history_per_job = max;
+                                let _ = self.services.settings_service.set_retention_settings(&ret);
+                            }
                             self.pages.settings.max_history_per_job = max;
                         }
                     }
@@ -1157,6 +1841,39 @@ impl App {
     pub fn running_count(&self) -> usize {
         self.services.job_executor.running_job_ids().len()
     }
+
+    /// Get combined progress across all running jobs.
+    pub fn aggregate_progress(&self) -> AggregateProgress {
+        self.services.job_executor.aggregate_progress()
+    }
+}
+
+/// An empty local mirror job for the create form.
+fn blank_job() -> JobDefinition {
+    let now = chrono::Utc::now();
+    JobDefinition {
+        id: uuid::Uuid::new_v4(),
+        name: String::new(),
+        description: None,
+        transfer: rsync_core::models::job::TransferConfig {
+            source: rsync_core::models::job::StorageLocation::Local {
+                path: String::new(),
+            },
+            destination: rsync_core::models::job::StorageLocation::Local {
+                path: String::new(),
+            },
+            backup_mode: rsync_core::models::job::BackupMode::Mirror,
+        },
+        options: rsync_core::models::job::RsyncOptions::default(),
+        ssh_config: None,
+        appearance: JobAppearance::default(),
+        run_policy: JobRunPolicy::default(),
+        safety: JobSafety::default(),
+        schedule: None,
+        enabled: true,
+        created_at: now,
+        updated_at: now,
+    }
 }
 
 fn apply_form_field(form: &mut JobFormState) {
@@ -1174,3 +1891,280 @@ fn apply_form_field(form: &mut JobFormState) {
         _ => {}
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats_state(names: &[&str]) -> StatisticsState {
+        let stats = AggregatedStats {
+            total_jobs_run: 1,
+            total_files_transferred: 0,
+            total_bytes_transferred: 0,
+            total_files_deleted: 0,
+            total_duration_secs: 0.0,
+            total_time_saved_secs: 0.0,
+        };
+        StatisticsState {
+            per_job: names
+                .iter()
+                .map(|name| (name.to_string(), stats.clone()))
+                .collect(),
+            ..StatisticsState::default()
+        }
+    }
+
+    fn visible(state: &StatisticsState) -> Vec<&str> {
+        state
+            .filtered_per_job()
+            .into_iter()
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_statistics_filter_narrows_per_job_rows() {
+        let mut state = stats_state(&["Photos", "Documents", "Photo Archive", "Music"]);
+        assert_eq!(visible(&state).len(), 4);
+
+        state.search_input.set_value("PHOTO");
+        assert_eq!(visible(&state), vec!["Photos", "Photo Archive"]);
+
+        state.search_input.set_value("nothing");
+        assert!(visible(&state).is_empty());
+    }
+
+    #[test]
+    fn test_statistics_filter_clamps_selection() {
+        let mut state = stats_state(&["Photos", "Documents", "Photo Archive", "Music"]);
+        state.selected = 3;
+
+        state.search_input.set_value("photo");
+        state.clamp_selection();
+        assert_eq!(state.selected, 1);
+
+        state.search_input.set_value("nothing");
+        state.clamp_selection();
+        assert_eq!(state.selected, 0);
+
+        // Clearing the filter keeps the clamped index
+        state.search_input.clear();
+        state.clamp_selection();
+        assert_eq!(state.selected, 0);
+    }
+
+    const JOB_NAMES: &[&str] = &[
+        "docs-daily",
+        "docs-offsite",
+        "Photos",
+        "photos-offsite",
+        "Music",
+    ];
+
+    fn matching(query: &str) -> Vec<&'static str> {
+        JOB_NAMES
+            .iter()
+            .copied()
+            .filter(|name| job_name_matches(query, name))
+            .collect()
+    }
+
+    fn job_form(job: JobDefinition) -> JobFormState {
+        JobFormState {
+            mode: JobFormMode::Edit(job.id),
+            job,
+            field_index: 0,
+            editing: false,
+            field_inputs: (0..20).map(|_| TextInput::new()).collect(),
+        }
+    }
+
+    fn local_job(source: &str, destination: &str) -> JobDefinition {
+        let mut job = blank_job();
+        job.transfer.source = rsync_core::models::job::StorageLocation::Local {
+            path: source.to_string(),
+        };
+        job.transfer.destination = rsync_core::models::job::StorageLocation::Local {
+            path: destination.to_string(),
+        };
+        job
+    }
+
+    fn tuned_job() -> JobDefinition {
+        let mut job = local_job("/other/src/", "/other/dst/");
+        job.name = "Tuned".to_string();
+        job.options.core_transfer.compress = true;
+        job.options.file_handling.delete = true;
+        job.options.advanced.custom_args = vec!["--bwlimit=500".to_string()];
+        job.ssh_config = Some(rsync_core::models::job::SshConfig::default());
+        job.schedule = Some(rsync_core::models::schedule::ScheduleConfig {
+            schedule_type: rsync_core::models::schedule::ScheduleType::Interval { minutes: 60 },
+            enabled: true,
+        });
+        job
+    }
+
+    #[test]
+    fn test_copy_settings_takes_options_and_keeps_paths() {
+        let mut form = job_form(local_job("/home/me/", "/backups/me/"));
+        form.job.name = "Mine".to_string();
+        let source = tuned_job();
+
+        form.copy_settings_from(&source, false, false);
+
+        assert_eq!(form.job.options, source.options);
+        assert_eq!(form.job.name, "Mine");
+        assert_eq!(
+            form.job.transfer,
+            local_job("/home/me/", "/backups/me/").transfer
+        );
+        assert_eq!(form.job.ssh_config, None);
+        assert_eq!(form.job.schedule, None);
+    }
+
+    #[test]
+    fn test_copy_settings_optionally_takes_ssh_and_schedule() {
+        let mut form = job_form(local_job("/home/me/", "/backups/me/"));
+        let source = tuned_job();
+
+        form.copy_settings_from(&source, true, true);
+
+        assert_eq!(form.job.ssh_config, source.ssh_config);
+        assert_eq!(form.job.schedule, source.schedule);
+        assert_eq!(
+            form.job.transfer,
+            local_job("/home/me/", "/backups/me/").transfer
+        );
+    }
+
+    #[test]
+    fn test_job_search_substring() {
+        assert_eq!(matching("photo"), vec!["Photos", "photos-offsite"]);
+        assert_eq!(matching("").len(), JOB_NAMES.len());
+    }
+
+    #[test]
+    fn test_job_search_prefix_glob() {
+        assert_eq!(matching("docs-*"), vec!["docs-daily", "docs-offsite"]);
+        assert_eq!(matching("PHOTOS*"), vec!["Photos", "photos-offsite"]);
+    }
+
+    #[test]
+    fn test_job_search_suffix_glob() {
+        assert_eq!(matching("*offsite"), vec!["docs-offsite", "photos-offsite"]);
+        assert_eq!(matching("mus?c"), vec!["Music"]);
+        // A glob covers the whole name, unlike a substring
+        assert!(matching("*daily-").is_empty());
+    }
+
+    /// An app over an in-memory database holding `job`, and the job as saved.
+    fn app_with_job(job: JobDefinition) -> (App, JobDefinition) {
+        use rsync_core::database::sqlite::Database;
+        use rsync_core::repository::sqlite::audit::SqliteAuditLogRepository;
+        use rsync_core::repository::sqlite::invocation::SqliteInvocationRepository;
+        use rsync_core::repository::sqlite::job::SqliteJobRepository;
+        use rsync_core::repository::sqlite::settings::SqliteSettingsRepository;
+        use rsync_core::repository::sqlite::snapshot::SqliteSnapshotRepository;
+        use rsync_core::repository::sqlite::statistics::SqliteStatisticsRepository;
+        use rsync_core::services::running_jobs::RunningJobs;
+
+        let conn = Database::in_memory().unwrap().conn();
+        let job_service = Arc::new(JobService::new(
+            Arc::new(SqliteJobRepository::new(conn.clone())),
+            Arc::new(SqliteInvocationRepository::new(conn.clone())),
+            Arc::new(SqliteSnapshotRepository::new(conn.clone())),
+        ));
+        let statistics_service = Arc::new(StatisticsService::new(Arc::new(
+            SqliteStatisticsRepository::new(conn.clone()),
+        )));
+        let settings_service = Arc::new(SettingsService::new(Arc::new(
+            SqliteSettingsRepository::new(conn.clone()),
+        )));
+        let job_executor = Arc::new(JobExecutor::new(
+            Arc::clone(&job_service),
+            Arc::clone(&statistics_service),
+            Arc::clone(&settings_service),
+            Arc::new(RunningJobs::new()),
+            std::env::temp_dir().to_string_lossy().to_string(),
+        ));
+        let job = job_service.create_job(job).unwrap();
+        let (job_sender, _) = std::sync::mpsc::channel();
+        let app = App::new(
+            job_executor,
+            job_service,
+            statistics_service,
+            settings_service,
+            Arc::new(SchedulerEventLog::default()),
+            Arc::new(AuditLog::new(Arc::new(SqliteAuditLogRepository::new(conn)))),
+            job_sender,
+        );
+        (app, job)
+    }
+
+    fn press(app: &mut App, c: char) {
+        app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn test_review_before_run_shows_command_and_waits() {
+        let mut job = local_job("/home/me/", "/backups/me/");
+        job.name = "Home".to_string();
+        job.options.core_transfer.archive = true;
+        job.options.file_handling.delete = true;
+        let (mut app, job) = app_with_job(job);
+
+        // Turn the setting on from the Settings page, as a user would
+        app.switch_page(Page::Settings);
+        app.pages.settings.selected = 7;
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(app.pages.settings.review_before_run);
+
+        app.switch_page(Page::Jobs);
+        press(&mut app, 'r');
+
+        let Some(PopupKind::Confirm {
+            title,
+            message,
+            action,
+        }) = app.overlays.popup.clone()
+        else {
+            panic!("expected a review popup, got {:?}", app.overlays.popup);
+        };
+        assert_eq!(title, "Run 'Home'?");
+        let command = message.lines().next().unwrap();
+        assert!(command.starts_with("rsync -a "), "{}", command);
+        assert!(command.contains("--delete"));
+        assert!(command.ends_with("/home/me/ /backups/me/"), "{}", command);
+        assert!(message.contains("Delete extraneous files: yes   Dry run: no"));
+        assert_eq!(
+            action,
+            ConfirmAction::RunJob {
+                job_id: job.id,
+                dry_run: false
+            }
+        );
+        // Nothing runs until the popup is confirmed
+        assert!(app.overlays.job_output.is_none());
+
+        press(&mut app, 'n');
+        assert!(app.overlays.popup.is_none());
+        assert!(app.overlays.job_output.is_none());
+
+        press(&mut app, 'd');
+        let Some(PopupKind::Confirm {
+            message, action, ..
+        }) = app.overlays.popup.clone()
+        else {
+            panic!("expected a review popup, got {:?}", app.overlays.popup);
+        };
+        assert!(message.contains("--dry-run"));
+        assert!(message.contains("Dry run: yes"));
+        assert_eq!(
+            action,
+            ConfirmAction::RunJob {
+                job_id: job.id,
+                dry_run: true
+            }
+        );
+    }
+}
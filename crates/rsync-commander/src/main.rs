@@ -18,19 +18,32 @@ use ratatui::backend::CrosstermBackend;
 use ratatui::Terminal;
 
 use rsync_core::database::sqlite::Database;
-use rsync_core::models::backup::InvocationTrigger;
+use rsync_core::models::backup::{InvocationStatus, InvocationTrigger};
+use rsync_core::models::backup_set::SetJobOutcome;
+use rsync_core::repository::sqlite::audit::SqliteAuditLogRepository;
+use rsync_core::repository::sqlite::backup_set::SqliteBackupSetRepository;
 use rsync_core::repository::sqlite::invocation::SqliteInvocationRepository;
 use rsync_core::repository::sqlite::job::SqliteJobRepository;
 use rsync_core::repository::sqlite::settings::SqliteSettingsRepository;
 use rsync_core::repository::sqlite::snapshot::SqliteSnapshotRepository;
 use rsync_core::repository::sqlite::statistics::SqliteStatisticsRepository;
+use rsync_core::services::audit::AuditLog;
+use rsync_core::services::backup_set_service::BackupSetService;
+use rsync_core::services::data_budget::DataBudget;
+use rsync_core::services::source_change::SourceUnchanged;
 use rsync_core::services::execution_handler::ExecutionEventHandler;
+use rsync_core::services::history_export;
 use rsync_core::services::job_executor::JobExecutor;
 use rsync_core::services::job_service::JobService;
+use rsync_core::services::log_follow::{running_invocation, LogFollower};
+use rsync_core::services::metrics;
 use rsync_core::services::retention_runner;
 use rsync_core::services::running_jobs::RunningJobs;
 use rsync_core::models::schedule::SchedulerConfig;
+use rsync_core::models::status::StatusDisplay;
 use rsync_core::services::scheduler_backend::{InProcessScheduler, SchedulerBackend};
+use rsync_core::services::startup_runner;
+use rsync_core::services::scheduler_events::SchedulerEventLog;
 use rsync_core::services::settings_service::SettingsService;
 use rsync_core::services::statistics_service::StatisticsService;
 
@@ -60,8 +73,47 @@ enum Commands {
         /// Job ID to execute
         job_id: String,
     },
+    /// Follow the log of a job that is running in another process
+    Tail {
+        /// Job ID whose running invocation to follow
+        job_id: String,
+        /// Give up once the log has not grown for this many minutes
+        #[arg(long, default_value_t = 30)]
+        idle_minutes: u64,
+    },
     /// List all jobs
     List,
+    /// List backup sets
+    Sets,
+    /// Create a backup set from job IDs, in run order
+    CreateSet {
+        /// Name of the set
+        name: String,
+        /// Job IDs to run, in order
+        #[arg(required = true)]
+        job_ids: Vec<String>,
+    },
+    /// Run the jobs of a backup set one after another
+    RunSet {
+        /// Backup set ID to execute
+        set_id: String,
+        /// Keep running the remaining jobs after one fails
+        #[arg(long)]
+        continue_on_failure: bool,
+    },
+    /// Print run history as newline-delimited JSON, oldest first, for log pipelines
+    ExportHistory {
+        /// Only runs started at or after this time (RFC 3339, or YYYY-MM-DD in UTC)
+        #[arg(long)]
+        since: Option<String>,
+    },
+    /// Print Prometheus metrics for all jobs
+    Metrics {
+        /// Serve them on this address (e.g. 127.0.0.1:9187) instead of printing once
+        #[cfg(feature = "metrics-server")]
+        #[arg(long)]
+        listen: Option<String>,
+    },
 }
 
 fn main() -> io::Result<()> {
@@ -96,20 +148,35 @@ fn main() -> io::Result<()> {
     let invocations = Arc::new(SqliteInvocationRepository::new(conn.clone()));
     let snapshots = Arc::new(SqliteSnapshotRepository::new(conn.clone()));
     let statistics_repo = Arc::new(SqliteStatisticsRepository::new(conn.clone()));
+    let backup_set_repo = Arc::new(SqliteBackupSetRepository::new(conn.clone()));
+    let audit_repo = Arc::new(SqliteAuditLogRepository::new(conn.clone()));
     let settings_repo = Arc::new(SqliteSettingsRepository::new(conn));
 
-    let job_service = Arc::new(JobService::new(jobs, invocations, snapshots));
-    let statistics_service = Arc::new(StatisticsService::new(statistics_repo));
+    let audit_source = if cli.command.is_some() { "cli" } else { "tui" };
+    let audit_log = Arc::new(AuditLog::new(audit_repo).with_source(audit_source));
+    let job_service = Arc::new(
+        JobService::new(jobs, invocations, snapshots).with_audit_log(Arc::clone(&audit_log)),
+    );
+    let statistics_service =
+        Arc::new(StatisticsService::new(statistics_repo).with_audit_log(Arc::clone(&audit_log)));
     let settings_service = Arc::new(SettingsService::new(settings_repo));
+    let backup_set_service = BackupSetService::new(backup_set_repo);
     let running_jobs = Arc::new(RunningJobs::new());
 
-    let job_executor = Arc::new(JobExecutor::new(
-        Arc::clone(&job_service),
-        Arc::clone(&statistics_service),
-        Arc::clone(&settings_service),
-        Arc::clone(&running_jobs),
-        default_log_dir,
-    ));
+    let job_executor = Arc::new(
+        JobExecutor::new(
+            Arc::clone(&job_service),
+            Arc::clone(&statistics_service),
+            Arc::clone(&settings_service),
+            Arc::clone(&running_jobs),
+            default_log_dir,
+        )
+        .with_run_condition(Arc::new(DataBudget::new(
+            Arc::clone(&job_service),
+            Arc::clone(&settings_service),
+        )))
+        .with_run_condition(Arc::new(SourceUnchanged::new(Arc::clone(&job_service)))),
+    );
 
     // Run retention on startup
     retention_runner::run_history_retention(&job_service, &settings_service);
@@ -119,15 +186,57 @@ fn main() -> io::Result<()> {
         Some(Commands::Run { job_id }) => {
             run_single_job(&job_id, &job_executor, &job_service)?;
         }
+        Some(Commands::Tail {
+            job_id,
+            idle_minutes,
+        }) => {
+            tail_job(
+                &job_id,
+                &job_service,
+                Duration::from_secs(idle_minutes * 60),
+            )?;
+        }
         Some(Commands::List) => {
             list_jobs(&job_service)?;
         }
+        Some(Commands::Sets) => {
+            list_sets(&backup_set_service)?;
+        }
+        Some(Commands::CreateSet { name, job_ids }) => {
+            create_set(&backup_set_service, &name, &job_ids)?;
+        }
+        Some(Commands::RunSet {
+            set_id,
+            continue_on_failure,
+        }) => {
+            run_set(
+                &set_id,
+                !continue_on_failure,
+                &job_executor,
+                &backup_set_service,
+            )?;
+        }
+        Some(Commands::ExportHistory { since }) => {
+            let since = since.as_deref().map(parse_since).transpose()?;
+            let mut out = io::BufWriter::new(io::stdout().lock());
+            history_export::export_history_ndjson(&job_service, since, &mut out)?;
+        }
+        #[cfg(feature = "metrics-server")]
+        Some(Commands::Metrics { listen: Some(addr) }) => {
+            metrics::serve_metrics(&addr, job_service, statistics_service)?;
+        }
+        Some(Commands::Metrics { .. }) => {
+            let text = metrics::render_metrics(&job_service, &statistics_service)
+                .map_err(|e| io::Error::other(e.to_string()))?;
+            print!("{}", text);
+        }
         None => {
             run_tui(
                 job_executor,
                 job_service,
                 statistics_service,
                 settings_service,
+                audit_log,
             )?;
         }
     }
@@ -205,6 +314,69 @@ fn run_single_job(
     Ok(())
 }
 
+/// Print a running invocation's log as it grows, until the database shows the
+/// invocation has finished.
+fn tail_job(
+    job_id_str: &str,
+    job_service: &Arc<JobService>,
+    idle_limit: Duration,
+) -> io::Result<()> {
+    let job_uuid = job_id_str.parse::<uuid::Uuid>().map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Invalid job ID: {}", e),
+        )
+    })?;
+
+    let job = job_service
+        .get_job(&job_uuid)
+        .map_err(|e| io::Error::new(io::ErrorKind::NotFound, format!("Job not found: {}", e)))?;
+    let invocation = running_invocation(job_service, &job.id)
+        .map_err(|e| io::Error::other(e.to_string()))?
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Job is not running"))?;
+    let log_path = invocation
+        .execution_output
+        .log_file_path
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Running job has no log file"))?;
+
+    println!(
+        "Following '{}' ({}) — {}",
+        job.name, invocation.id, log_path
+    );
+
+    let mut follower = LogFollower::new(std::path::Path::new(&log_path));
+    loop {
+        // Read the status before the log so lines written just before the run
+        // finished are still printed
+        let status = job_service
+            .get_invocation(&invocation.id)
+            .map(|inv| inv.status)
+            .map_err(|e| io::Error::other(e.to_string()))?;
+        for line in follower.read_new_lines()? {
+            println!("{}", line);
+        }
+        if status != InvocationStatus::Running {
+            if let Some(line) = follower.finish() {
+                println!("{}", line);
+            }
+            println!("Run finished: {}", status.status_label());
+            if status != InvocationStatus::Succeeded {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        // A run whose process died stays Running in the database forever
+        if follower.idle_for() >= idle_limit {
+            eprintln!(
+                "No log output for {} minutes; the run may have been interrupted",
+                idle_limit.as_secs() / 60
+            );
+            std::process::exit(1);
+        }
+        std::thread::sleep(Duration::from_millis(500));
+    }
+}
+
 fn list_jobs(job_service: &Arc<JobService>) -> io::Result<()> {
     let jobs = job_service
         .list_jobs()
@@ -236,6 +408,140 @@ fn list_jobs(job_service: &Arc<JobService>) -> io::Result<()> {
     Ok(())
 }
 
+fn list_sets(backup_set_service: &BackupSetService) -> io::Result<()> {
+    let sets = backup_set_service
+        .list_sets()
+        .map_err(|e| io::Error::other(e.to_string()))?;
+
+    if sets.is_empty() {
+        println!("No backup sets configured.");
+        return Ok(());
+    }
+
+    println!("{:<38} {:<30} Jobs", "ID", "Name");
+    println!("{}", "-".repeat(76));
+    for set in &sets {
+        println!(
+            "{:<38} {:<30} {}",
+            set.id,
+            truncate(&set.name, 28),
+            set.job_ids.len()
+        );
+    }
+
+    Ok(())
+}
+
+fn create_set(
+    backup_set_service: &BackupSetService,
+    name: &str,
+    job_ids: &[String],
+) -> io::Result<()> {
+    let job_ids = job_ids
+        .iter()
+        .map(|id| {
+            id.parse::<uuid::Uuid>().map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Invalid job ID: {}", e),
+                )
+            })
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+    let set = backup_set_service
+        .create_set(name, job_ids)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
+    println!("Created backup set '{}' ({})", set.name, set.id);
+    Ok(())
+}
+
+fn run_set(
+    set_id_str: &str,
+    stop_on_failure: bool,
+    job_executor: &Arc<JobExecutor>,
+    backup_set_service: &BackupSetService,
+) -> io::Result<()> {
+    let set_uuid = set_id_str.parse::<uuid::Uuid>().map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Invalid backup set ID: {}", e),
+        )
+    })?;
+    let set = backup_set_service.get_set(&set_uuid).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Backup set not found: {}", e),
+        )
+    })?;
+
+    println!(
+        "Running backup set '{}' ({} jobs)",
+        set.name,
+        set.job_ids.len()
+    );
+
+    // The set runs on its own thread so this one can print its jobs' output
+    let (tx, rx) = std::sync::mpsc::channel();
+    let handler: Arc<dyn ExecutionEventHandler> = Arc::new(TuiEventHandler::new(tx));
+    let executor = Arc::clone(job_executor);
+    let runner = std::thread::spawn(move || executor.run_set(&set, stop_on_failure, handler));
+
+    use handler::TuiEvent;
+    for event in rx {
+        match event {
+            TuiEvent::LogLine(ll) if ll.is_stderr => eprintln!("{}", ll.line),
+            TuiEvent::LogLine(ll) => println!("{}", ll.line),
+            _ => {}
+        }
+    }
+    let run = runner
+        .join()
+        .map_err(|_| io::Error::other("Backup set runner panicked"))?;
+
+    println!();
+    for result in &run.results {
+        let name = if result.job_name.is_empty() {
+            result.job_id.to_string()
+        } else {
+            result.job_name.clone()
+        };
+        let outcome = match &result.outcome {
+            SetJobOutcome::Finished {
+                status,
+                error_message,
+                ..
+            } => match error_message {
+                Some(msg) => format!("{}: {}", status.status_label(), msg),
+                None => status.status_label().to_string(),
+            },
+            SetJobOutcome::NotStarted { message } => format!("Not started: {}", message),
+            SetJobOutcome::Skipped => "Skipped".to_string(),
+        };
+        println!("{:<30} {}", truncate(&name, 28), outcome);
+    }
+
+    if !run.succeeded() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// A `--since` value: a full RFC 3339 timestamp, or a date taken as
+/// midnight UTC.
+fn parse_since(value: &str) -> io::Result<chrono::DateTime<chrono::Utc>> {
+    if let Ok(time) = chrono::DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&chrono::Utc));
+    }
+    chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map(|date| date.and_time(chrono::NaiveTime::MIN).and_utc())
+        .map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Invalid --since '{}': use RFC 3339 or YYYY-MM-DD", value),
+            )
+        })
+}
+
 fn truncate(s: &str, max: usize) -> String {
     if s.len() > max {
         format!("{}...", &s[..max.saturating_sub(3)])
@@ -249,6 +555,7 @@ fn run_tui(
     job_service: Arc<JobService>,
     statistics_service: Arc<StatisticsService>,
     settings_service: Arc<SettingsService>,
+    audit_log: Arc<AuditLog>,
 ) -> io::Result<()> {
     // Terminal setup
     enable_raw_mode()?;
@@ -266,14 +573,24 @@ fn run_tui(
     let handler_factory: Arc<dyn Fn() -> Arc<dyn ExecutionEventHandler> + Send + Sync> =
         Arc::new(move || Arc::new(TuiEventHandler::new(scheduler_sender.clone())));
 
+    let scheduler_events = Arc::new(SchedulerEventLog::default());
     let scheduler = InProcessScheduler::new(
         SchedulerConfig::default(),
         Arc::clone(&job_executor),
         Arc::clone(&job_service),
         Arc::clone(&settings_service),
+        Arc::clone(&handler_factory),
+    )
+    .with_event_log(Arc::clone(&scheduler_events));
+    let _scheduler_handle = scheduler.start();
+
+    // Jobs flagged to run on startup start in the background
+    startup_runner::spawn_startup_runs(
+        Arc::clone(&job_executor),
+        Arc::clone(&job_service),
         handler_factory,
+        None,
     );
-    let _scheduler_handle = scheduler.start();
 
     // App state
     let mut app = App::new(
@@ -281,6 +598,8 @@ fn run_tui(
         job_service,
         statistics_service,
         settings_service,
+        scheduler_events,
+        audit_log,
         job_sender,
     );
 
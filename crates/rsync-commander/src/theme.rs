@@ -1,5 +1,7 @@
 use ratatui::style::Color;
 
+use rsync_core::models::status::StatusColor;
+
 #[derive(Debug, Clone)]
 pub struct Theme {
     pub name: &'static str,
@@ -60,6 +62,19 @@ pub const THEMES: &[Theme] = &[
     },
 ];
 
+impl Theme {
+    /// The theme's color for a core status color hint.
+    pub fn status_color(&self, hint: StatusColor) -> Color {
+        match hint {
+            StatusColor::Success => self.success,
+            StatusColor::Failure => self.error,
+            StatusColor::Warning => Color::Yellow,
+            StatusColor::Active => self.highlight,
+            StatusColor::Neutral => self.muted,
+        }
+    }
+}
+
 pub fn get_theme(name: &str) -> &'static Theme {
     THEMES
         .iter()
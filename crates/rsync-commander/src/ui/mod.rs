@@ -36,7 +36,11 @@ pub fn draw(f: &mut Frame, app: &App) {
 
     // Popup takes priority over everything
     if let Some(ref popup_kind) = app.overlays.popup {
-        draw_page(f, app, chunks[1]);
+        if app.overlays.job_form.is_some() {
+            job_form::draw_job_form(f, app, chunks[1]);
+        } else {
+            draw_page(f, app, chunks[1]);
+        }
         popup::draw_popup(f, popup_kind, chunks[1]);
         return;
     }
@@ -4,7 +4,7 @@ use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph, Row, Table};
 
-use rsync_core::models::backup::InvocationStatus;
+use rsync_core::models::status::StatusDisplay;
 
 use crate::app::App;
 
@@ -19,12 +19,14 @@ pub fn draw_history(f: &mut Frame, app: &App, area: Rect) {
         .constraints([Constraint::Min(0), Constraint::Length(2)])
         .split(area);
 
-    let header = Row::new(vec!["Job", "Started", "Status", "Exit", "Files", "Trigger"])
-        .style(
-            Style::default()
-                .fg(app.theme.highlight)
-                .add_modifier(Modifier::BOLD),
-        );
+    let header = Row::new(vec![
+        "Job", "Started", "Status", "Exit", "Files", "Deleted", "Trigger",
+    ])
+    .style(
+        Style::default()
+            .fg(app.theme.highlight)
+            .add_modifier(Modifier::BOLD),
+    );
 
     let rows: Vec<Row> = app
         .pages.history
@@ -39,42 +41,46 @@ pub fn draw_history(f: &mut Frame, app: &App, area: Rect) {
                 .unwrap_or_else(|_| inv.job_id.to_string()[..8].to_string());
 
             let started = inv.started_at.format("%Y-%m-%d %H:%M").to_string();
-            let status = format!("{:?}", inv.status);
+            let status = match inv.warning {
+                Some(_) => format!("{} (alarm)", inv.status.status_label()),
+                None => inv.status.status_label().to_string(),
+            };
             let exit_code = inv
                 .execution_output
                 .exit_code
                 .map(|c| c.to_string())
                 .unwrap_or_else(|| "-".to_string());
             let files = inv.transfer_stats.files_transferred.to_string();
+            let deleted = inv.transfer_stats.files_deleted.to_string();
             let trigger = format!("{:?}", inv.trigger);
 
             let style = if i == app.pages.history.selected {
                 Style::default()
                     .fg(app.theme.selected)
                     .add_modifier(Modifier::BOLD)
+            } else if inv.warning.is_some() {
+                Style::default().fg(ratatui::style::Color::Yellow)
             } else {
-                let color = match inv.status {
-                    InvocationStatus::Succeeded => app.theme.success,
-                    InvocationStatus::Failed => app.theme.error,
-                    InvocationStatus::Cancelled => ratatui::style::Color::Yellow,
-                    InvocationStatus::Running => app.theme.highlight,
-                };
-                Style::default().fg(color)
+                Style::default().fg(app.theme.status_color(inv.status.status_color_hint()))
             };
 
-            Row::new(vec![job_name, started, status, exit_code, files, trigger]).style(style)
+            Row::new(vec![
+                job_name, started, status, exit_code, files, deleted, trigger,
+            ])
+            .style(style)
         })
         .collect();
 
     let table = Table::new(
         rows,
         [
-            Constraint::Percentage(25),
-            Constraint::Percentage(20),
+            Constraint::Percentage(23),
+            Constraint::Percentage(18),
             Constraint::Percentage(15),
-            Constraint::Percentage(10),
-            Constraint::Percentage(10),
-            Constraint::Percentage(20),
+            Constraint::Percentage(8),
+            Constraint::Percentage(9),
+            Constraint::Percentage(9),
+            Constraint::Percentage(18),
         ],
     )
     .header(header)
@@ -96,7 +102,9 @@ pub fn draw_history(f: &mut Frame, app: &App, area: Rect) {
         Span::styled("Enter", Style::default().fg(app.theme.highlight)),
         Span::styled(":view log ", Style::default().fg(app.theme.muted)),
         Span::styled("d", Style::default().fg(app.theme.highlight)),
-        Span::styled(":delete", Style::default().fg(app.theme.muted)),
+        Span::styled(":delete ", Style::default().fg(app.theme.muted)),
+        Span::styled("a", Style::default().fg(app.theme.highlight)),
+        Span::styled(":audit log", Style::default().fg(app.theme.muted)),
     ]);
 
     f.render_widget(Paragraph::new(help), chunks[1]);
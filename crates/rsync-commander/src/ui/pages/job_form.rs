@@ -1,7 +1,8 @@
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Modifier, Style};
-use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use rsync_core::services::command_explainer::explain_trailing_slash;
 
 use crate::app::{App, JobFormMode};
 use crate::ui::text_input::TextInputWidget;
@@ -13,8 +14,8 @@ pub fn draw_job_form(f: &mut Frame, app: &App, area: Rect) {
     };
 
     let title = match &form.mode {
-        JobFormMode::Create => " New Job (Esc to cancel, Ctrl+S to save) ",
-        JobFormMode::Edit(_) => " Edit Job (Esc to cancel, Ctrl+S to save) ",
+        JobFormMode::Create => " New Job (Esc to cancel, Ctrl+S to save, c to copy settings) ",
+        JobFormMode::Edit(_) => " Edit Job (Esc to cancel, Ctrl+S to save, c to copy settings) ",
     };
 
     let block = Block::default()
@@ -25,6 +26,30 @@ pub fn draw_job_form(f: &mut Frame, app: &App, area: Rect) {
     let inner = block.inner(area);
     f.render_widget(block, area);
 
+    // Where the source ends up, given the trailing-slash rule
+    let sections = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(2)])
+        .split(inner);
+    let inner = sections[0];
+    let transfer = &form.job.transfer;
+    if !format_location(&transfer.source).is_empty()
+        && !format_location(&transfer.destination).is_empty()
+    {
+        let slash_effect = explain_trailing_slash(
+            &transfer.source,
+            &transfer.destination,
+            &transfer.backup_mode,
+            app.pages.settings.auto_trailing_slash,
+        );
+        f.render_widget(
+            Paragraph::new(slash_effect)
+                .style(Style::default().fg(app.theme.muted))
+                .wrap(Wrap { trim: true }),
+            sections[1],
+        );
+    }
+
     // Field definitions
     let fields: Vec<(&str, String)> = vec![
         ("Name", form.job.name.clone()),
@@ -117,9 +142,12 @@ fn format_location(loc: &rsync_core::models::job::StorageLocation) -> String {
         rsync_core::models::job::StorageLocation::RemoteSsh { user, host, path, .. } => {
             format!("{}@{}:{}", user, host, path)
         }
-        rsync_core::models::job::StorageLocation::RemoteRsync { host, module, path } => {
+        rsync_core::models::job::StorageLocation::RemoteRsync {
+            host, module, path, ..
+        } => {
             format!("{}::{}:{}", host, module, path)
         }
+        rsync_core::models::job::StorageLocation::Mount { path, .. } => path.clone(),
     }
 }
 
@@ -1,10 +1,11 @@
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
 
 use rsync_core::models::job::JobStatus;
+use rsync_core::models::status::StatusDisplay;
 
 use crate::app::App;
 
@@ -25,36 +26,42 @@ pub fn draw_job_output(f: &mut Frame, app: &App, area: Rect) {
 
     // Status / Progress bar
     let status_text = if let Some(ref status) = output.status {
+        let label = status.status.status_label();
         match status.status {
-            JobStatus::Completed => format!("Completed (exit code: {})", status.exit_code.unwrap_or(0)),
+            JobStatus::Completed => {
+                format!("{} (exit code: {})", label, status.exit_code.unwrap_or(0))
+            }
             JobStatus::Failed => format!(
-                "Failed: {}",
+                "{}: {}",
+                label,
                 status.error_message.as_deref().unwrap_or("unknown error")
             ),
-            JobStatus::Cancelled => "Cancelled".to_string(),
-            JobStatus::Running => "Running...".to_string(),
-            _ => "Unknown".to_string(),
+            _ => label.to_string(),
         }
     } else {
         "Starting...".to_string()
     };
 
     let progress_text = if let Some(ref prog) = output.progress {
+        let eta = prog
+            .eta_secs
+            .map(|secs| format!(" | ETA {}", format_eta(secs)))
+            .unwrap_or_default();
+        let file = prog
+            .current_file
+            .as_deref()
+            .map(|name| format!(" | {}", name))
+            .unwrap_or_default();
         format!(
-            " {:.1}% | {} files | {} | {}",
-            prog.percentage, prog.files_transferred, prog.transfer_rate, prog.elapsed
+            " {:.1}% | {} files | {} | {}{}{}",
+            prog.percentage, prog.files_transferred, prog.transfer_rate, prog.elapsed, eta, file
         )
     } else {
         String::new()
     };
 
     let status_color = if let Some(ref status) = output.status {
-        match status.status {
-            JobStatus::Completed => app.theme.success,
-            JobStatus::Failed => app.theme.error,
-            JobStatus::Cancelled => Color::Yellow,
-            _ => app.theme.highlight,
-        }
+        app.theme.status_color(status.status.status_color_hint())
     } else {
         app.theme.highlight
     };
@@ -128,3 +135,8 @@ pub fn draw_job_output(f: &mut Frame, app: &App, area: Rect) {
 
     f.render_widget(Paragraph::new(help), chunks[2]);
 }
+
+/// Format an ETA in the same H:MM:SS shape rsync uses for elapsed time.
+fn format_eta(secs: u64) -> String {
+    format!("{}:{:02}:{:02}", secs / 3600, (secs % 3600) / 60, secs % 60)
+}
@@ -1,10 +1,14 @@
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
-use ratatui::style::{Modifier, Style};
+use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph, Row, Table};
 
+use rsync_core::models::job::JobDefinition;
+use rsync_core::models::validation::parse_hex_color;
+
 use crate::app::App;
+use crate::theme::Theme;
 use crate::ui::text_input::TextInputWidget;
 
 pub fn draw_jobs(f: &mut Frame, app: &App, area: Rect) {
@@ -59,13 +63,7 @@ pub fn draw_jobs(f: &mut Frame, app: &App, area: Rect) {
             let mode = format_mode(&job.transfer.backup_mode);
             let enabled = if job.enabled { "Yes" } else { "No" };
 
-            let style = if i == app.pages.jobs.selected {
-                Style::default()
-                    .fg(app.theme.selected)
-                    .add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(app.theme.fg)
-            };
+            let style = job_row_style(job, i == app.pages.jobs.selected, app.theme);
 
             Row::new(vec![
                 job.name.clone(),
@@ -112,10 +110,18 @@ pub fn draw_jobs(f: &mut Frame, app: &App, area: Rect) {
         Span::styled(":dry-run ", Style::default().fg(app.theme.muted)),
         Span::styled("c", Style::default().fg(app.theme.highlight)),
         Span::styled(":cancel ", Style::default().fg(app.theme.muted)),
+        Span::styled("C", Style::default().fg(app.theme.highlight)),
+        Span::styled(":cancel all ", Style::default().fg(app.theme.muted)),
         Span::styled("x", Style::default().fg(app.theme.highlight)),
         Span::styled(":delete ", Style::default().fg(app.theme.muted)),
         Span::styled("o", Style::default().fg(app.theme.highlight)),
         Span::styled(":output ", Style::default().fg(app.theme.muted)),
+        Span::styled("e", Style::default().fg(app.theme.highlight)),
+        Span::styled(":scheduler ", Style::default().fg(app.theme.muted)),
+        Span::styled("s", Style::default().fg(app.theme.highlight)),
+        Span::styled(":snapshot diff ", Style::default().fg(app.theme.muted)),
+        Span::styled("S", Style::default().fg(app.theme.highlight)),
+        Span::styled(":snapshots ", Style::default().fg(app.theme.muted)),
         Span::styled("/", Style::default().fg(app.theme.highlight)),
         Span::styled(":search", Style::default().fg(app.theme.muted)),
     ]);
@@ -123,15 +129,34 @@ pub fn draw_jobs(f: &mut Frame, app: &App, area: Rect) {
     f.render_widget(Paragraph::new(help), chunks[2]);
 }
 
+/// The selection highlight for the selected row; otherwise the job's own
+/// color, falling back to the theme's text color.
+fn job_row_style(job: &JobDefinition, selected: bool, theme: &Theme) -> Style {
+    if selected {
+        return Style::default()
+            .fg(theme.selected)
+            .add_modifier(Modifier::BOLD);
+    }
+    let fg = job
+        .appearance
+        .color
+        .as_deref()
+        .and_then(parse_hex_color)
+        .map(|(r, g, b)| Color::Rgb(r, g, b))
+        .unwrap_or(theme.fg);
+    Style::default().fg(fg)
+}
+
 fn format_location(loc: &rsync_core::models::job::StorageLocation) -> String {
     match loc {
         rsync_core::models::job::StorageLocation::Local { path } => truncate(path, 25),
-        rsync_core::models::job::StorageLocation::RemoteSsh { user, host, path, .. } => {
-            truncate(&format!("{}@{}:{}", user, host, path), 25)
-        }
-        rsync_core::models::job::StorageLocation::RemoteRsync { host, module, path } => {
-            truncate(&format!("{}::{}:{}", host, module, path), 25)
-        }
+        rsync_core::models::job::StorageLocation::RemoteSsh {
+            user, host, path, ..
+        } => truncate(&format!("{}@{}:{}", user, host, path), 25),
+        rsync_core::models::job::StorageLocation::RemoteRsync {
+            host, module, path, ..
+        } => truncate(&format!("{}::{}:{}", host, module, path), 25),
+        rsync_core::models::job::StorageLocation::Mount { path, .. } => truncate(path, 25),
     }
 }
 
@@ -150,3 +175,73 @@ fn truncate(s: &str, max: usize) -> String {
         s.to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use rsync_core::models::job::{
+        BackupMode, JobAppearance, JobRunPolicy, JobSafety, RsyncOptions, StorageLocation,
+        TransferConfig,
+    };
+
+    use super::*;
+    use crate::theme::THEMES;
+
+    fn job_with_color(color: Option<&str>) -> JobDefinition {
+        let now = Utc::now();
+        JobDefinition {
+            id: uuid::Uuid::new_v4(),
+            name: "Photos".to_string(),
+            description: None,
+            transfer: TransferConfig {
+                source: StorageLocation::Local {
+                    path: "/src/".to_string(),
+                },
+                destination: StorageLocation::Local {
+                    path: "/dst/".to_string(),
+                },
+                backup_mode: BackupMode::Mirror,
+            },
+            options: RsyncOptions::default(),
+            ssh_config: None,
+            appearance: JobAppearance {
+                color: color.map(str::to_string),
+                icon: None,
+            },
+            run_policy: JobRunPolicy::default(),
+            safety: JobSafety::default(),
+            schedule: None,
+            enabled: true,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_row_is_tinted_with_job_color() {
+        let theme = &THEMES[0];
+        let style = job_row_style(&job_with_color(Some("#22c55e")), false, theme);
+        assert_eq!(style.fg, Some(Color::Rgb(0x22, 0xc5, 0x5e)));
+    }
+
+    #[test]
+    fn test_row_without_color_uses_theme() {
+        let theme = &THEMES[1];
+        assert_eq!(
+            job_row_style(&job_with_color(None), false, theme).fg,
+            Some(theme.fg)
+        );
+        assert_eq!(
+            job_row_style(&job_with_color(Some("not-a-color")), false, theme).fg,
+            Some(theme.fg)
+        );
+    }
+
+    #[test]
+    fn test_selected_row_keeps_selection_highlight() {
+        let theme = &THEMES[0];
+        let style = job_row_style(&job_with_color(Some("#22c55e")), true, theme);
+        assert_eq!(style.fg, Some(theme.selected));
+        assert!(style.add_modifier.contains(Modifier::BOLD));
+    }
+}
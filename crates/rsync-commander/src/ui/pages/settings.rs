@@ -39,6 +39,30 @@ pub fn draw_settings(f: &mut Frame, app: &App, area: Rect) {
             },
         ),
         ("TUI Theme", app.pages.settings.tui_theme.clone()),
+        (
+            "Test Mode (dry runs)",
+            if app.pages.settings.test_mode {
+                "Yes".to_string()
+            } else {
+                "No".to_string()
+            },
+        ),
+        (
+            "Relative Paths (-R)",
+            if app.pages.settings.relative_paths {
+                "Yes".to_string()
+            } else {
+                "No".to_string()
+            },
+        ),
+        (
+            "Review Before Run",
+            if app.pages.settings.review_before_run {
+                "Yes".to_string()
+            } else {
+                "No".to_string()
+            },
+        ),
     ];
 
     let row_constraints: Vec<Constraint> = settings.iter().map(|_| Constraint::Length(2)).collect();
@@ -3,15 +3,22 @@ use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph, Row, Table};
+use rsync_core::format::{format_bytes, format_duration};
 
 use crate::app::App;
+use crate::ui::text_input::TextInputWidget;
 
 pub fn draw_statistics(f: &mut Frame, app: &App, area: Rect) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(8), // Summary
-            Constraint::Min(0),   // Per-job table
+            Constraint::Length(if app.pages.statistics.search_active {
+                3
+            } else {
+                0
+            }),
+            Constraint::Min(0),    // Per-job table
             Constraint::Length(2), // Help
         ])
         .split(area);
@@ -41,6 +48,16 @@ pub fn draw_statistics(f: &mut Frame, app: &App, area: Rect) {
                     Style::default().fg(app.theme.fg),
                 ),
             ]),
+            Line::from(vec![
+                Span::styled(
+                    "  Files Deleted:     ",
+                    Style::default().fg(app.theme.muted),
+                ),
+                Span::styled(
+                    agg.total_files_deleted.to_string(),
+                    Style::default().fg(app.theme.fg),
+                ),
+            ]),
             Line::from(vec![
                 Span::styled("  Bytes Transferred: ", Style::default().fg(app.theme.muted)),
                 Span::styled(
@@ -72,6 +89,22 @@ pub fn draw_statistics(f: &mut Frame, app: &App, area: Rect) {
         );
     }
 
+    // Search bar
+    if app.pages.statistics.search_active {
+        let search_block = Block::default()
+            .title(" Search (Esc to close) ")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(app.theme.border));
+        let inner = search_block.inner(chunks[1]);
+        f.render_widget(search_block, chunks[1]);
+        f.render_widget(
+            TextInputWidget::new(&app.pages.statistics.search_input)
+                .focused_style(Style::default().fg(app.theme.fg))
+                .unfocused_style(Style::default().fg(app.theme.muted)),
+            inner,
+        );
+    }
+
     // Per-job table
     let header = Row::new(vec!["Job", "Runs", "Files", "Bytes", "Duration", "Time Saved"])
         .style(
@@ -82,8 +115,8 @@ pub fn draw_statistics(f: &mut Frame, app: &App, area: Rect) {
 
     let rows: Vec<Row> = app
         .pages.statistics
-        .per_job
-        .iter()
+        .filtered_per_job()
+        .into_iter()
         .enumerate()
         .map(|(i, (name, stats))| {
             let style = if i == app.pages.statistics.selected {
@@ -125,41 +158,21 @@ pub fn draw_statistics(f: &mut Frame, app: &App, area: Rect) {
             .style(Style::default().fg(app.theme.border)),
     );
 
-    f.render_widget(table, chunks[1]);
+    f.render_widget(table, chunks[2]);
 
     // Help
     let help = Line::from(vec![
         Span::styled(" r", Style::default().fg(app.theme.highlight)),
         Span::styled(":reset ", Style::default().fg(app.theme.muted)),
+        Span::styled("c", Style::default().fg(app.theme.highlight)),
+        Span::styled(":recompute ", Style::default().fg(app.theme.muted)),
         Span::styled("e", Style::default().fg(app.theme.highlight)),
         Span::styled(":export ", Style::default().fg(app.theme.muted)),
         Span::styled("j/k", Style::default().fg(app.theme.highlight)),
-        Span::styled(":navigate", Style::default().fg(app.theme.muted)),
+        Span::styled(":navigate ", Style::default().fg(app.theme.muted)),
+        Span::styled("/", Style::default().fg(app.theme.highlight)),
+        Span::styled(":search", Style::default().fg(app.theme.muted)),
     ]);
 
-    f.render_widget(Paragraph::new(help), chunks[2]);
-}
-
-fn format_bytes(bytes: u64) -> String {
-    if bytes < 1024 {
-        format!("{} B", bytes)
-    } else if bytes < 1024 * 1024 {
-        format!("{:.1} KB", bytes as f64 / 1024.0)
-    } else if bytes < 1024 * 1024 * 1024 {
-        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
-    } else {
-        format!("{:.2} GB", bytes as f64 / (1024.0 * 1024.0 * 1024.0))
-    }
-}
-
-fn format_duration(secs: f64) -> String {
-    if secs < 60.0 {
-        format!("{:.1}s", secs)
-    } else if secs < 3600.0 {
-        format!("{:.0}m {:.0}s", secs / 60.0, secs % 60.0)
-    } else {
-        let hours = secs / 3600.0;
-        let mins = (secs % 3600.0) / 60.0;
-        format!("{:.0}h {:.0}m", hours, mins)
-    }
+    f.render_widget(Paragraph::new(help), chunks[3]);
 }
@@ -4,6 +4,8 @@ use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
 
+use rsync_core::models::pattern::Matched;
+
 use crate::app::App;
 use crate::ui::text_input::TextInputWidget;
 
@@ -18,7 +20,12 @@ pub fn draw_tools(f: &mut Frame, app: &App, area: Rect) {
         .split(area);
 
     // Sub-tabs
-    let tabs = vec!["Explainer", "Log Scrubber"];
+    let tabs = [
+        "Explainer",
+        "Log Scrubber",
+        "Pattern Tester",
+        "Schedule Preview",
+    ];
     let tab_line = Line::from(
         tabs.iter()
             .enumerate()
@@ -39,6 +46,8 @@ pub fn draw_tools(f: &mut Frame, app: &App, area: Rect) {
     match app.pages.tools.active_tab {
         0 => draw_explainer(f, app, chunks[1]),
         1 => draw_scrubber(f, app, chunks[1]),
+        2 => draw_pattern_tester(f, app, chunks[1]),
+        3 => draw_schedule_preview(f, app, chunks[1]),
         _ => {}
     }
 
@@ -159,3 +168,160 @@ fn draw_scrubber(f: &mut Frame, app: &App, area: Rect) {
 
     f.render_widget(instructions, chunks[1]);
 }
+
+fn draw_pattern_tester(f: &mut Frame, app: &App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Source dir
+            Constraint::Length(3), // Filter rules
+            Constraint::Min(0),    // Results
+        ])
+        .split(area);
+
+    let inputs = [
+        (" Source directory ", &app.pages.tools.pattern_source_input),
+        (
+            " Filter rules (comma separated, '+ pat' include, '- pat' exclude; Enter to test) ",
+            &app.pages.tools.pattern_filter_input,
+        ),
+    ];
+    for (i, (title, input)) in inputs.iter().enumerate() {
+        let block = Block::default()
+            .title(*title)
+            .borders(Borders::ALL)
+            .style(Style::default().fg(app.theme.border));
+        let inner = block.inner(chunks[i]);
+        f.render_widget(block, chunks[i]);
+        f.render_widget(
+            TextInputWidget::new(input)
+                .focused_style(Style::default().fg(app.theme.fg))
+                .unfocused_style(Style::default().fg(app.theme.muted)),
+            inner,
+        );
+    }
+
+    let results_block = Block::default()
+        .title(" Matches ")
+        .borders(Borders::ALL)
+        .style(Style::default().fg(app.theme.border));
+    let results_inner = results_block.inner(chunks[2]);
+    f.render_widget(results_block, chunks[2]);
+
+    if let Some(ref err) = app.pages.tools.pattern_error {
+        f.render_widget(
+            Paragraph::new(format!("Error: {}", err))
+                .style(Style::default().fg(app.theme.error))
+                .wrap(Wrap { trim: false }),
+            results_inner,
+        );
+        return;
+    }
+    if app.pages.tools.pattern_results.is_empty() {
+        f.render_widget(
+            Paragraph::new("Enter a source directory and filter rules to preview what they match.")
+                .style(Style::default().fg(app.theme.muted)),
+            results_inner,
+        );
+        return;
+    }
+
+    let lines: Vec<Line> = app
+        .pages
+        .tools
+        .pattern_results
+        .iter()
+        .map(|entry| {
+            let path = if entry.is_dir {
+                format!("{}/", entry.path)
+            } else {
+                entry.path.clone()
+            };
+            let (label, color) = match &entry.matched {
+                Matched::Excluded { pattern } => {
+                    (format!("excluded by {}", pattern), app.theme.error)
+                }
+                Matched::Included { pattern } => {
+                    (format!("included by {}", pattern), app.theme.success)
+                }
+                Matched::Unmatched => ("transferred".to_string(), app.theme.muted),
+            };
+            Line::from(vec![
+                Span::styled(format!("  {:40} ", path), Style::default().fg(app.theme.fg)),
+                Span::styled(label, Style::default().fg(color)),
+            ])
+        })
+        .collect();
+
+    f.render_widget(Paragraph::new(lines), results_inner);
+}
+
+fn draw_schedule_preview(f: &mut Frame, app: &App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Input
+            Constraint::Min(0),    // Upcoming runs
+        ])
+        .split(area);
+
+    let input_block = Block::default()
+        .title(" Interval in minutes or cron expression (Enter to preview) ")
+        .borders(Borders::ALL)
+        .style(Style::default().fg(app.theme.border));
+    let input_inner = input_block.inner(chunks[0]);
+    f.render_widget(input_block, chunks[0]);
+    f.render_widget(
+        TextInputWidget::new(&app.pages.tools.schedule_input)
+            .focused_style(Style::default().fg(app.theme.fg))
+            .unfocused_style(Style::default().fg(app.theme.muted)),
+        input_inner,
+    );
+
+    let output_block = Block::default()
+        .title(" Upcoming runs ")
+        .borders(Borders::ALL)
+        .style(Style::default().fg(app.theme.border));
+    let output_inner = output_block.inner(chunks[1]);
+    f.render_widget(output_block, chunks[1]);
+
+    if let Some(ref err) = app.pages.tools.schedule_error {
+        f.render_widget(
+            Paragraph::new(format!("Error: {}", err))
+                .style(Style::default().fg(app.theme.error))
+                .wrap(Wrap { trim: false }),
+            output_inner,
+        );
+        return;
+    }
+    if app.pages.tools.schedule_preview.is_empty() {
+        f.render_widget(
+            Paragraph::new("Enter a schedule above to see when it would run next.")
+                .style(Style::default().fg(app.theme.muted)),
+            output_inner,
+        );
+        return;
+    }
+
+    let lines: Vec<Line> = app
+        .pages
+        .tools
+        .schedule_preview
+        .iter()
+        .enumerate()
+        .map(|(i, run)| {
+            Line::from(vec![
+                Span::styled(
+                    format!("  {:>2}. ", i + 1),
+                    Style::default().fg(app.theme.muted),
+                ),
+                Span::styled(
+                    run.format("%a %Y-%m-%d %H:%M").to_string(),
+                    Style::default().fg(app.theme.fg),
+                ),
+            ])
+        })
+        .collect();
+
+    f.render_widget(Paragraph::new(lines), output_inner);
+}
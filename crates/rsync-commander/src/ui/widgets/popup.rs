@@ -4,13 +4,27 @@ use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Text};
 use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
 
-use crate::app::PopupKind;
+use crate::app::{JobPickRow, PopupKind, SnapshotRow};
 
 pub fn draw_popup(f: &mut Frame, popup: &PopupKind, area: Rect) {
     match popup {
         PopupKind::Help => draw_help(f, area),
         PopupKind::Confirm { title, message, .. } => draw_confirm(f, title, message, area),
         PopupKind::Error(msg) => draw_error(f, msg, area),
+        PopupKind::SchedulerEvents { title, lines } => draw_line_list(f, title, lines, 70, area),
+        PopupKind::AuditLog { lines } => draw_line_list(f, "Audit Log", lines, 100, area),
+        PopupKind::SnapshotDiff { title, lines } => draw_line_list(f, title, lines, 90, area),
+        PopupKind::Snapshots {
+            title,
+            rows,
+            selected,
+        } => draw_snapshots(f, title, rows, *selected, area),
+        PopupKind::CopyJobSettings {
+            rows,
+            selected,
+            include_ssh,
+            include_schedule,
+        } => draw_copy_settings(f, rows, *selected, *include_ssh, *include_schedule, area),
     }
 }
 
@@ -31,9 +45,13 @@ fn draw_help(f: &mut Frame, area: Rect) {
         Line::from("  r            Run job"),
         Line::from("  d            Dry-run"),
         Line::from("  c            Cancel running job"),
+        Line::from("  C            Cancel all running jobs"),
         Line::from("  x            Delete job"),
         Line::from("  o            View output"),
-        Line::from("  /            Search"),
+        Line::from("  e            Scheduler decisions"),
+        Line::from("  s            Diff latest snapshots"),
+        Line::from("  S            Snapshots (p to pin)"),
+        Line::from("  /            Search (* and ? glob)"),
         Line::from(""),
         Line::from("Output Viewer").style(Style::default().add_modifier(Modifier::BOLD)),
         Line::from(""),
@@ -49,12 +67,15 @@ fn draw_help(f: &mut Frame, area: Rect) {
         Line::from("  j/k          Navigate"),
         Line::from("  Enter        View log"),
         Line::from("  d            Delete invocation"),
+        Line::from("  a            Audit log"),
         Line::from(""),
         Line::from("Statistics").style(Style::default().add_modifier(Modifier::BOLD)),
         Line::from(""),
         Line::from("  r            Reset all"),
+        Line::from("  c            Recompute from history"),
         Line::from("  e            Export"),
         Line::from("  j/k          Navigate per-job"),
+        Line::from("  /            Search per-job"),
         Line::from(""),
         Line::from("Press Esc/q/? to close"),
     ]);
@@ -78,14 +99,20 @@ fn draw_help(f: &mut Frame, area: Rect) {
 }
 
 fn draw_confirm(f: &mut Frame, title: &str, message: &str, area: Rect) {
-    let text = Text::from(vec![
-        Line::from(message.to_string()),
-        Line::from(""),
-        Line::from("  [y] Yes   [n] No"),
-    ]);
+    let mut lines: Vec<Line> = message.lines().map(|l| Line::from(l.to_string())).collect();
+    lines.push(Line::from(""));
+    lines.push(Line::from("  [y] Yes   [n] No"));
 
-    let height = 6.min(area.height);
-    let width = 50.min(area.width);
+    // Short questions keep the small box; longer ones (a command to review)
+    // get a wider one tall enough for their wrapped lines
+    let width = if message.len() > 48 { 90 } else { 50 }.min(area.width);
+    let inner_width = width.saturating_sub(2).max(1) as usize;
+    let rows: usize = message
+        .lines()
+        .map(|l| l.chars().count().div_ceil(inner_width).max(1))
+        .sum();
+    let height = ((rows + 4) as u16).max(6).min(area.height);
+    let text = Text::from(lines);
     let popup_area = crate::ui::centered_rect(width, height, area);
 
     let block = Block::default()
@@ -126,3 +153,114 @@ fn draw_error(f: &mut Frame, msg: &str, area: Rect) {
         popup_area,
     );
 }
+
+/// A dismissable list of preformatted lines.
+fn draw_line_list(f: &mut Frame, title: &str, lines: &[String], width: u16, area: Rect) {
+    let mut text: Vec<Line> = lines.iter().map(|l| Line::from(l.clone())).collect();
+    text.push(Line::from(""));
+    text.push(Line::from("Press Enter/Esc to dismiss"));
+
+    let height = (text.len() + 2).min(area.height as usize) as u16;
+    let width = width.min(area.width);
+    let popup_area = crate::ui::centered_rect(width, height, area);
+
+    let block = Block::default()
+        .title(format!(" {} ", title))
+        .borders(Borders::ALL)
+        .style(Style::default().fg(ratatui::style::Color::Cyan));
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(
+        Paragraph::new(Text::from(text))
+            .block(block)
+            .wrap(Wrap { trim: false }),
+        popup_area,
+    );
+}
+
+/// A job's snapshots with the selected one highlighted; pinned ones are marked.
+fn draw_snapshots(f: &mut Frame, title: &str, rows: &[SnapshotRow], selected: usize, area: Rect) {
+    let mut text: Vec<Line> = rows
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let marker = if row.pinned { "[pinned] " } else { "         " };
+            let line = Line::from(format!("{}{}", marker, row.label));
+            if i == selected {
+                line.style(Style::default().add_modifier(Modifier::REVERSED))
+            } else {
+                line
+            }
+        })
+        .collect();
+    text.push(Line::from(""));
+    text.push(Line::from("j/k: select   p: pin/unpin   Esc: close"));
+
+    let height = (text.len() + 2).min(area.height as usize) as u16;
+    let width = 90.min(area.width);
+    let popup_area = crate::ui::centered_rect(width, height, area);
+
+    let block = Block::default()
+        .title(format!(" {} ", title))
+        .borders(Borders::ALL)
+        .style(Style::default().fg(ratatui::style::Color::Cyan));
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(
+        Paragraph::new(Text::from(text))
+            .block(block)
+            .wrap(Wrap { trim: false }),
+        popup_area,
+    );
+}
+
+/// Jobs to copy settings from, with the selected one highlighted and what
+/// comes along besides the rsync options.
+fn draw_copy_settings(
+    f: &mut Frame,
+    rows: &[JobPickRow],
+    selected: usize,
+    include_ssh: bool,
+    include_schedule: bool,
+    area: Rect,
+) {
+    let check = |on: bool| if on { "[x]" } else { "[ ]" };
+    let mut text: Vec<Line> = rows
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let line = Line::from(format!("  {}", row.name));
+            if i == selected {
+                line.style(Style::default().add_modifier(Modifier::REVERSED))
+            } else {
+                line
+            }
+        })
+        .collect();
+    text.push(Line::from(""));
+    text.push(Line::from(format!(
+        "{} SSH settings (s)   {} Schedule (t)",
+        check(include_ssh),
+        check(include_schedule)
+    )));
+    text.push(Line::from(
+        "j/k: select   Enter: copy options   Esc: cancel",
+    ));
+
+    let height = (text.len() + 2).min(area.height as usize) as u16;
+    let width = 60.min(area.width);
+    let popup_area = crate::ui::centered_rect(width, height, area);
+
+    let block = Block::default()
+        .title(" Copy Settings From ")
+        .borders(Borders::ALL)
+        .style(Style::default().fg(ratatui::style::Color::Cyan));
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(
+        Paragraph::new(Text::from(text))
+            .block(block)
+            .wrap(Wrap { trim: false }),
+        popup_area,
+    );
+}
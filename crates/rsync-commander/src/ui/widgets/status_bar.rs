@@ -3,6 +3,7 @@ use ratatui::layout::Rect;
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::Paragraph;
+use rsync_core::format::format_rate;
 
 use crate::app::App;
 
@@ -24,11 +25,30 @@ pub fn draw_status_bar(f: &mut Frame, app: &App, area: Rect) {
 
     let help_text = " q:quit ?:help ";
 
-    let line = Line::from(vec![
+    let mut spans = vec![
         Span::styled(running_text, running_style),
         Span::styled("│", Style::default().fg(app.theme.border)),
-        Span::styled(help_text, Style::default().fg(app.theme.muted)),
-    ]);
+    ];
+
+    let progress = app.aggregate_progress();
+    if running > 0 && progress.active_jobs > 0 {
+        let mut text = format!(" {} ", format_rate(progress.bytes_per_sec));
+        if progress.files_total > 0 {
+            text.push_str(&format!(
+                "· {} of {} files ",
+                progress.files_done, progress.files_total
+            ));
+        }
+        text.push_str(&format!("({:.0}%) ", progress.percentage));
+        spans.push(Span::styled(text, Style::default().fg(app.theme.fg)));
+        spans.push(Span::styled("│", Style::default().fg(app.theme.border)));
+    }
+
+    spans.push(Span::styled(
+        help_text,
+        Style::default().fg(app.theme.muted),
+    ));
+    let line = Line::from(spans);
 
     let bar = Paragraph::new(line)
         .style(Style::default().bg(app.theme.bg));
@@ -1,9 +1,20 @@
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
-use rusqlite::Connection;
+use rusqlite::{Connection, ErrorCode};
 
 use crate::error::AppError;
 
+/// How long SQLite waits on a lock held by another connection (the TUI, the
+/// GUI or the scheduler of another process) before reporting it busy.
+pub const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Further attempts a write gets after SQLite reports the database busy.
+pub const BUSY_RETRIES: u32 = 4;
+
+/// Pause before the first retry; doubled before each one after it.
+const BUSY_BACKOFF: Duration = Duration::from_millis(50);
+
 pub struct Database {
     conn: Arc<Mutex<Connection>>,
 }
@@ -37,6 +48,8 @@ impl Database {
 
     fn init_pragmas(&self) -> Result<(), AppError> {
         let conn = self.conn.lock().map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        conn.busy_timeout(BUSY_TIMEOUT)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
         conn.execute_batch(
             "PRAGMA journal_mode=WAL;
              PRAGMA foreign_keys=ON;"
@@ -106,6 +119,248 @@ impl Database {
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
         }
 
+        if current_version < 5 {
+            let sql = include_str!("../migrations/v005_job_env.sql");
+            conn.execute_batch(sql)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            conn.execute(
+                "INSERT INTO schema_version (version, applied_at) VALUES (5, datetime('now'))",
+                [],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current_version < 6 {
+            let sql = include_str!("../migrations/v006_job_max_runtime.sql");
+            conn.execute_batch(sql)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            conn.execute(
+                "INSERT INTO schema_version (version, applied_at) VALUES (6, datetime('now'))",
+                [],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current_version < 7 {
+            let sql = include_str!("../migrations/v007_itemized_changes.sql");
+            conn.execute_batch(sql)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            conn.execute(
+                "INSERT INTO schema_version (version, applied_at) VALUES (7, datetime('now'))",
+                [],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current_version < 8 {
+            let sql = include_str!("../migrations/v008_job_appearance.sql");
+            conn.execute_batch(sql)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            conn.execute(
+                "INSERT INTO schema_version (version, applied_at) VALUES (8, datetime('now'))",
+                [],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current_version < 9 {
+            let sql = include_str!("../migrations/v009_job_success_exit_codes.sql");
+            conn.execute_batch(sql)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            conn.execute(
+                "INSERT INTO schema_version (version, applied_at) VALUES (9, datetime('now'))",
+                [],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current_version < 10 {
+            let sql = include_str!("../migrations/v010_invocation_rerun_of.sql");
+            conn.execute_batch(sql)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            conn.execute(
+                "INSERT INTO schema_version (version, applied_at) VALUES (10, datetime('now'))",
+                [],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current_version < 11 {
+            let sql = include_str!("../migrations/v011_backup_sets.sql");
+            conn.execute_batch(sql)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            conn.execute(
+                "INSERT INTO schema_version (version, applied_at) VALUES (11, datetime('now'))",
+                [],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current_version < 12 {
+            let sql = include_str!("../migrations/v012_job_notify_on.sql");
+            conn.execute_batch(sql)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            conn.execute(
+                "INSERT INTO schema_version (version, applied_at) VALUES (12, datetime('now'))",
+                [],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current_version < 13 {
+            let sql = include_str!("../migrations/v013_audit_log.sql");
+            conn.execute_batch(sql)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            conn.execute(
+                "INSERT INTO schema_version (version, applied_at) VALUES (13, datetime('now'))",
+                [],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current_version < 14 {
+            let sql = include_str!("../migrations/v014_job_process_priority.sql");
+            conn.execute_batch(sql)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            conn.execute(
+                "INSERT INTO schema_version (version, applied_at) VALUES (14, datetime('now'))",
+                [],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current_version < 15 {
+            let sql = include_str!("../migrations/v015_job_create_destination.sql");
+            conn.execute_batch(sql)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            conn.execute(
+                "INSERT INTO schema_version (version, applied_at) VALUES (15, datetime('now'))",
+                [],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current_version < 16 {
+            let sql = include_str!("../migrations/v016_files_deleted.sql");
+            conn.execute_batch(sql)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            conn.execute(
+                "INSERT INTO schema_version (version, applied_at) VALUES (16, datetime('now'))",
+                [],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current_version < 17 {
+            let sql = include_str!("../migrations/v017_job_run_on_startup.sql");
+            conn.execute_batch(sql)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            conn.execute(
+                "INSERT INTO schema_version (version, applied_at) VALUES (17, datetime('now'))",
+                [],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current_version < 18 {
+            let sql = include_str!("../migrations/v018_change_alarm.sql");
+            conn.execute_batch(sql)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            conn.execute(
+                "INSERT INTO schema_version (version, applied_at) VALUES (18, datetime('now'))",
+                [],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current_version < 19 {
+            let sql = include_str!("../migrations/v019_job_skip_if_unchanged.sql");
+            conn.execute_batch(sql)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            conn.execute(
+                "INSERT INTO schema_version (version, applied_at) VALUES (19, datetime('now'))",
+                [],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current_version < 20 {
+            let sql = include_str!("../migrations/v020_snapshot_pinned.sql");
+            conn.execute_batch(sql)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            conn.execute(
+                "INSERT INTO schema_version (version, applied_at) VALUES (20, datetime('now'))",
+                [],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current_version < 21 {
+            let sql = include_str!("../migrations/v021_invocation_resumed_from.sql");
+            conn.execute_batch(sql)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            conn.execute(
+                "INSERT INTO schema_version (version, applied_at) VALUES (21, datetime('now'))",
+                [],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current_version < 22 {
+            let sql = include_str!("../migrations/v022_job_expect_nonempty_destination.sql");
+            conn.execute_batch(sql)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            conn.execute(
+                "INSERT INTO schema_version (version, applied_at) VALUES (22, datetime('now'))",
+                [],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current_version < 23 {
+            let sql = include_str!("../migrations/v023_invocation_detailed_stats.sql");
+            conn.execute_batch(sql)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            conn.execute(
+                "INSERT INTO schema_version (version, applied_at) VALUES (23, datetime('now'))",
+                [],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current_version < 24 {
+            let sql = include_str!("../migrations/v024_job_parallel_split.sql");
+            conn.execute_batch(sql)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            conn.execute(
+                "INSERT INTO schema_version (version, applied_at) VALUES (24, datetime('now'))",
+                [],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current_version < 25 {
+            let sql = include_str!("../migrations/v025_snapshot_verification.sql");
+            conn.execute_batch(sql)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            conn.execute(
+                "INSERT INTO schema_version (version, applied_at) VALUES (25, datetime('now'))",
+                [],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current_version < 26 {
+            let sql = include_str!("../migrations/v026_prune_records.sql");
+            conn.execute_batch(sql)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            conn.execute(
+                "INSERT INTO schema_version (version, applied_at) VALUES (26, datetime('now'))",
+                [],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
         Ok(())
     }
 
@@ -198,6 +453,70 @@ impl Database {
 }
 
 // Helper functions reused by all repository implementations
+
+/// Convert a SQLite error, keeping a lock held by another connection
+/// distinct (`AppError::DatabaseBusy`) so writes can retry it, and a
+/// duplicate key (`AppError::Conflict`) so callers can report the clash.
+pub fn db_error(e: rusqlite::Error) -> AppError {
+    match &e {
+        rusqlite::Error::SqliteFailure(failure, _)
+            if matches!(
+                failure.code,
+                ErrorCode::DatabaseBusy | ErrorCode::DatabaseLocked
+            ) =>
+        {
+            AppError::DatabaseBusy(e.to_string())
+        }
+        rusqlite::Error::SqliteFailure(failure, _)
+            if matches!(
+                failure.extended_code,
+                rusqlite::ffi::SQLITE_CONSTRAINT_PRIMARYKEY
+                    | rusqlite::ffi::SQLITE_CONSTRAINT_UNIQUE
+            ) =>
+        {
+            AppError::Conflict(e.to_string())
+        }
+        _ => AppError::DatabaseError(e.to_string()),
+    }
+}
+
+/// Run a write against the connection, retrying with exponential backoff
+/// while the database is busy. The mutex is released between attempts so
+/// other threads are not held up. Only `AppError::DatabaseBusy` (see
+/// [`db_error`]) is retried; once the retries are used up it is returned,
+/// and any other error is returned as is.
+pub fn with_busy_retry<T>(
+    conn: &Mutex<Connection>,
+    mut op: impl FnMut(&mut Connection) -> Result<T, AppError>,
+) -> Result<T, AppError> {
+    let mut backoff = BUSY_BACKOFF;
+    let mut attempt = 0;
+    loop {
+        let result = {
+            let mut guard = conn
+                .lock()
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            op(&mut guard)
+        };
+        match result {
+            Err(AppError::DatabaseBusy(msg)) => {
+                if attempt == BUSY_RETRIES {
+                    return Err(AppError::DatabaseBusy(msg));
+                }
+                attempt += 1;
+                log::warn!(
+                    "Database busy, retrying write ({}/{})",
+                    attempt,
+                    BUSY_RETRIES
+                );
+                std::thread::sleep(backoff);
+                backoff *= 2;
+            }
+            other => return other,
+        }
+    }
+}
+
 pub fn to_json<T: serde::Serialize>(value: &T) -> Result<String, AppError> {
     serde_json::to_string(value).map_err(|e| AppError::SerializationError(e.to_string()))
 }
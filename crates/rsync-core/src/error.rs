@@ -2,6 +2,7 @@ use serde::Serialize;
 use thiserror::Error;
 
 use crate::file_system::FsError;
+use crate::models::validation::{CheckSeverity, CheckType, ValidationCheck};
 use crate::rsync_client::RsyncError;
 
 #[derive(Debug, Error)]
@@ -9,6 +10,11 @@ pub enum AppError {
     #[error("Database error: {0}")]
     DatabaseError(String),
 
+    /// Another connection held the database locked (for writes, through
+    /// every retry).
+    #[error("Database busy: {0}")]
+    DatabaseBusy(String),
+
     #[error("Rsync error: {0}")]
     RsyncError(#[from] RsyncError),
 
@@ -24,11 +30,80 @@ pub enum AppError {
     #[error("Not found: {0}")]
     NotFound(String),
 
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
     #[error("Scheduler error: {0}")]
     SchedulerError(String),
 
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
+
+    #[error("{0}")]
+    JobService(#[from] JobServiceError),
+}
+
+/// Errors from `JobService`, kept distinct so callers can tell a missing
+/// record from a storage failure (e.g. to retry a busy database).
+#[derive(Debug, Error)]
+pub enum JobServiceError {
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    /// The record clashes with an existing one (e.g. a duplicate id).
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    #[error("Database error: {0}")]
+    Database(String),
+
+    /// The database stayed locked by another connection through every retry.
+    #[error("Database busy: {0}")]
+    Busy(String),
+
+    #[error("Validation failed: {}", failed_messages(.0))]
+    Validation(Vec<ValidationCheck>),
+
+    /// A failure outside storage, e.g. reading a file or running rsync.
+    #[error("{0}")]
+    Other(String),
+}
+
+fn failed_messages(checks: &[ValidationCheck]) -> String {
+    checks
+        .iter()
+        .filter(|c| !c.passed)
+        .map(|c| c.message.as_str())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+impl From<AppError> for JobServiceError {
+    fn from(e: AppError) -> Self {
+        match e {
+            AppError::NotFound(msg) => JobServiceError::NotFound(msg),
+            AppError::Conflict(msg) => JobServiceError::Conflict(msg),
+            AppError::DatabaseError(msg) => JobServiceError::Database(msg),
+            AppError::DatabaseBusy(msg) => JobServiceError::Busy(msg),
+            AppError::ValidationError(msg) => JobServiceError::Validation(vec![ValidationCheck {
+                check_type: CheckType::Input,
+                passed: false,
+                message: msg,
+                severity: CheckSeverity::Error,
+            }]),
+            AppError::JobService(e) => e,
+            other => JobServiceError::Other(other.to_string()),
+        }
+    }
+}
+
+impl Serialize for JobServiceError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
 }
 
 impl Serialize for AppError {
@@ -1,5 +1,6 @@
 pub mod real_file_system;
 
+use chrono::{DateTime, Utc};
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
@@ -16,6 +17,9 @@ pub enum FsError {
 
     #[error("Not a directory: {0}")]
     NotADirectory(String),
+
+    #[error("Not on the same filesystem: {0}")]
+    CrossDevice(String),
 }
 
 pub trait FileSystem {
@@ -23,9 +27,12 @@ pub trait FileSystem {
     fn is_dir(&self, path: &Path) -> bool;
     fn is_file(&self, path: &Path) -> bool;
     fn is_symlink(&self, path: &Path) -> bool;
+    /// Last modification time of `path` itself; symlinks are not followed.
+    fn modified(&self, path: &Path) -> Result<DateTime<Utc>, FsError>;
 
     fn create_dir_all(&self, path: &Path) -> Result<(), FsError>;
     fn remove_dir_all(&self, path: &Path) -> Result<(), FsError>;
+    fn remove_file(&self, path: &Path) -> Result<(), FsError>;
 
     fn read_dir(&self, path: &Path) -> Result<Vec<PathBuf>, FsError>;
     fn read_to_string(&self, path: &Path) -> Result<String, FsError>;
@@ -37,10 +44,19 @@ pub trait FileSystem {
 
     fn available_space(&self, path: &Path) -> Result<u64, FsError>;
     fn dir_size(&self, path: &Path) -> Result<u64, FsError>;
+    /// Like `dir_size`, but a file hard-linked several times under `path`
+    /// is counted once: the space the tree actually occupies.
+    fn unique_dir_size(&self, path: &Path) -> Result<u64, FsError>;
 
     fn copy_file(&self, from: &Path, to: &Path) -> Result<(), FsError>;
+    fn rename(&self, from: &Path, to: &Path) -> Result<(), FsError>;
     fn hard_link(&self, original: &Path, link: &Path) -> Result<(), FsError>;
     fn walk_dir(&self, path: &Path) -> Result<Vec<PathBuf>, FsError>;
 
     fn filesystem_type(&self, path: &Path) -> Option<String>;
+
+    /// Mount point of the filesystem that contains `path`.
+    fn mount_point(&self, path: &Path) -> Option<PathBuf>;
+    /// Label of the volume that contains `path`, if it has one.
+    fn volume_label(&self, path: &Path) -> Option<String>;
 }
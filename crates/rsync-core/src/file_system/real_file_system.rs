@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -46,6 +47,13 @@ impl FileSystem for RealFileSystem {
             .unwrap_or(false)
     }
 
+    fn modified(&self, path: &Path) -> Result<DateTime<Utc>, FsError> {
+        fs::symlink_metadata(path)
+            .and_then(|meta| meta.modified())
+            .map(DateTime::<Utc>::from)
+            .map_err(|e| Self::map_io_error(e, path))
+    }
+
     fn create_dir_all(&self, path: &Path) -> Result<(), FsError> {
         fs::create_dir_all(path).map_err(|e| Self::map_io_error(e, path))
     }
@@ -54,6 +62,10 @@ impl FileSystem for RealFileSystem {
         fs::remove_dir_all(path).map_err(|e| Self::map_io_error(e, path))
     }
 
+    fn remove_file(&self, path: &Path) -> Result<(), FsError> {
+        fs::remove_file(path).map_err(|e| Self::map_io_error(e, path))
+    }
+
     fn read_dir(&self, path: &Path) -> Result<Vec<PathBuf>, FsError> {
         if !path.is_dir() {
             return Err(FsError::NotADirectory(path.display().to_string()));
@@ -96,10 +108,10 @@ impl FileSystem for RealFileSystem {
         fs::remove_file(path).map_err(|e| Self::map_io_error(e, path))
     }
 
-    fn available_space(&self, _path: &Path) -> Result<u64, FsError> {
-        // A full implementation would use platform-specific APIs (statvfs on Unix)
-        // For now, return a large default
-        Ok(u64::MAX)
+    fn available_space(&self, path: &Path) -> Result<u64, FsError> {
+        // A destination that does not exist yet lands on its nearest existing parent
+        let existing = path.ancestors().find(|p| p.exists()).unwrap_or(path);
+        available_space_impl(existing)
     }
 
     fn dir_size(&self, path: &Path) -> Result<u64, FsError> {
@@ -117,6 +129,32 @@ impl FileSystem for RealFileSystem {
         Ok(total)
     }
 
+    #[cfg(unix)]
+    fn unique_dir_size(&self, path: &Path) -> Result<u64, FsError> {
+        use std::collections::HashSet;
+        use std::os::unix::fs::MetadataExt;
+
+        if !path.is_dir() {
+            return Err(FsError::NotADirectory(path.display().to_string()));
+        }
+        let mut seen = HashSet::new();
+        let mut total = 0u64;
+        for entry in self.walk_dir(path)? {
+            let Ok(meta) = fs::symlink_metadata(&entry) else {
+                continue;
+            };
+            if meta.is_file() && seen.insert((meta.dev(), meta.ino())) {
+                total += meta.len();
+            }
+        }
+        Ok(total)
+    }
+
+    #[cfg(not(unix))]
+    fn unique_dir_size(&self, path: &Path) -> Result<u64, FsError> {
+        self.dir_size(path)
+    }
+
     fn copy_file(&self, from: &Path, to: &Path) -> Result<(), FsError> {
         if let Some(parent) = to.parent() {
             fs::create_dir_all(parent).map_err(|e| Self::map_io_error(e, parent))?;
@@ -126,6 +164,18 @@ impl FileSystem for RealFileSystem {
         Ok(())
     }
 
+    fn rename(&self, from: &Path, to: &Path) -> Result<(), FsError> {
+        if let Some(parent) = to.parent() {
+            fs::create_dir_all(parent).map_err(|e| Self::map_io_error(e, parent))?;
+        }
+        if fs::rename(from, to).is_ok() {
+            return Ok(());
+        }
+        // rename fails across filesystems; fall back to copy + remove
+        fs::copy(from, to).map_err(|e| Self::map_io_error(e, from))?;
+        fs::remove_file(from).map_err(|e| Self::map_io_error(e, from))
+    }
+
     fn hard_link(&self, original: &Path, link: &Path) -> Result<(), FsError> {
         if let Some(parent) = link.parent() {
             fs::create_dir_all(parent).map_err(|e| Self::map_io_error(e, parent))?;
@@ -159,6 +209,39 @@ impl FileSystem for RealFileSystem {
     fn filesystem_type(&self, path: &Path) -> Option<String> {
         filesystem_type_impl(path)
     }
+
+    fn mount_point(&self, path: &Path) -> Option<PathBuf> {
+        mount_point_impl(path)
+    }
+
+    fn volume_label(&self, path: &Path) -> Option<String> {
+        volume_label_impl(path)
+    }
+}
+
+/// Bytes an unprivileged user can still write on the filesystem holding `path`.
+#[cfg(unix)]
+fn available_space_impl(path: &Path) -> Result<u64, FsError> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|e| FsError::IoError(format!("{}: {}", path.display(), e)))?;
+    unsafe {
+        let mut stat: libc::statvfs = std::mem::zeroed();
+        if libc::statvfs(c_path.as_ptr(), &mut stat) != 0 {
+            let err = std::io::Error::last_os_error();
+            return Err(RealFileSystem::map_io_error(err, path));
+        }
+        // The field types differ between platforms
+        #[allow(clippy::unnecessary_cast)]
+        Ok((stat.f_bavail as u64).saturating_mul(stat.f_frsize as u64))
+    }
+}
+
+#[cfg(not(unix))]
+fn available_space_impl(_path: &Path) -> Result<u64, FsError> {
+    Ok(u64::MAX)
 }
 
 #[cfg(target_os = "macos")]
@@ -216,3 +299,89 @@ fn filesystem_type_impl(path: &Path) -> Option<String> {
 fn filesystem_type_impl(_path: &Path) -> Option<String> {
     None
 }
+
+#[cfg(target_os = "macos")]
+fn mount_point_impl(path: &Path) -> Option<PathBuf> {
+    use std::ffi::CString;
+    let c_path = CString::new(path.to_str()?).ok()?;
+    unsafe {
+        let mut stat: libc::statfs = std::mem::zeroed();
+        if libc::statfs(c_path.as_ptr(), &mut stat) != 0 {
+            return None;
+        }
+        let name_bytes: Vec<u8> = stat
+            .f_mntonname
+            .iter()
+            .map(|&b| b as u8)
+            .take_while(|&b| b != 0)
+            .collect();
+        String::from_utf8(name_bytes).ok().map(PathBuf::from)
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn volume_label_impl(path: &Path) -> Option<String> {
+    // External volumes mount at /Volumes/<label>
+    let mount = mount_point_impl(path)?;
+    let rest = mount.strip_prefix("/Volumes").ok()?;
+    rest.to_str().filter(|s| !s.is_empty()).map(String::from)
+}
+
+/// The `/proc/mounts` entry (device, mount point) that contains `path`.
+#[cfg(target_os = "linux")]
+fn linux_mount_entry(path: &Path) -> Option<(String, PathBuf)> {
+    use std::io::BufRead;
+
+    let canonical = path.canonicalize().ok()?;
+    let file = fs::File::open("/proc/mounts").ok()?;
+    let reader = std::io::BufReader::new(file);
+
+    let mut best: Option<(String, PathBuf)> = None;
+    for line in reader.lines() {
+        let line = line.ok()?;
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 3 {
+            continue;
+        }
+        // Spaces in mount points are escaped as \040
+        let mount_point = PathBuf::from(parts[1].replace("\\040", " "));
+        if canonical.starts_with(&mount_point)
+            && best
+                .as_ref()
+                .is_none_or(|(_, b)| mount_point.as_os_str().len() >= b.as_os_str().len())
+        {
+            best = Some((parts[0].to_string(), mount_point));
+        }
+    }
+    best
+}
+
+#[cfg(target_os = "linux")]
+fn mount_point_impl(path: &Path) -> Option<PathBuf> {
+    linux_mount_entry(path).map(|(_, mount_point)| mount_point)
+}
+
+#[cfg(target_os = "linux")]
+fn volume_label_impl(path: &Path) -> Option<String> {
+    let (device, _) = linux_mount_entry(path)?;
+    let device = Path::new(&device).canonicalize().ok()?;
+    for entry in fs::read_dir("/dev/disk/by-label").ok()? {
+        let entry = entry.ok()?;
+        if entry.path().canonicalize().ok().as_ref() == Some(&device) {
+            // udev escapes spaces and slashes in labels as \x20 and \x2f
+            let name = entry.file_name().to_string_lossy().to_string();
+            return Some(name.replace("\\x20", " ").replace("\\x2f", "/"));
+        }
+    }
+    None
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+fn mount_point_impl(_path: &Path) -> Option<PathBuf> {
+    None
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+fn volume_label_impl(_path: &Path) -> Option<String> {
+    None
+}
@@ -0,0 +1,117 @@
+//! Human-readable byte counts, transfer rates and durations, shared by the
+//! GUI, the TUI and service messages so every frontend prints the same text.
+
+const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+
+/// Format a byte count in binary units (1 KB = 1024 B), e.g. "1.5 MB".
+/// Plain bytes have no decimals; larger units have one.
+pub fn format_bytes(bytes: u64) -> String {
+    scaled(bytes as f64)
+}
+
+/// Format a transfer rate in bytes per second, e.g. "2.3 MB/s".
+/// Negative or non-finite rates are shown as "0 B/s".
+pub fn format_rate(bytes_per_sec: f64) -> String {
+    let rate = if bytes_per_sec.is_finite() {
+        bytes_per_sec.max(0.0)
+    } else {
+        0.0
+    };
+    format!("{}/s", scaled(rate))
+}
+
+/// Format a duration in seconds: "250ms" below a second, "12.5s" below a
+/// minute, then "4m 5s" and "2h 3m 4s".
+pub fn format_duration(secs: f64) -> String {
+    if !secs.is_finite() || secs <= 0.0 {
+        return "0s".to_string();
+    }
+
+    let millis = (secs * 1000.0).round();
+    if millis < 1000.0 {
+        return format!("{}ms", millis as u64);
+    }
+    let tenths = (secs * 10.0).round();
+    if tenths < 600.0 {
+        return format!("{:.1}s", tenths / 10.0);
+    }
+
+    let total = secs.round() as u64;
+    let (hours, mins, rem) = (total / 3600, (total % 3600) / 60, total % 60);
+    if hours > 0 {
+        format!("{}h {}m {}s", hours, mins, rem)
+    } else {
+        format!("{}m {}s", mins, rem)
+    }
+}
+
+fn scaled(value: f64) -> String {
+    let mut value = value;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{:.0} {}", value, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KB: u64 = 1024;
+    const MB: u64 = KB * 1024;
+    const GB: u64 = MB * 1024;
+
+    #[test]
+    fn bytes_below_one_kilobyte_have_no_decimals() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(500), "500 B");
+        assert_eq!(format_bytes(KB - 1), "1023 B");
+    }
+
+    #[test]
+    fn bytes_switch_units_at_each_threshold() {
+        assert_eq!(format_bytes(KB), "1.0 KB");
+        assert_eq!(format_bytes(MB - 1), "1024.0 KB");
+        assert_eq!(format_bytes(MB), "1.0 MB");
+        assert_eq!(format_bytes(GB), "1.0 GB");
+        assert_eq!(format_bytes(2 * GB), "2.0 GB");
+        assert_eq!(format_bytes(GB * 1024), "1.0 TB");
+        assert_eq!(format_bytes(GB * 1024 * 2048), "2048.0 TB");
+    }
+
+    #[test]
+    fn rate_uses_byte_units_per_second() {
+        assert_eq!(format_rate(512.0), "512 B/s");
+        assert_eq!(format_rate(1.5 * MB as f64), "1.5 MB/s");
+        assert_eq!(format_rate(-3.0), "0 B/s");
+        assert_eq!(format_rate(f64::NAN), "0 B/s");
+    }
+
+    #[test]
+    fn sub_second_durations_show_milliseconds() {
+        assert_eq!(format_duration(0.0), "0s");
+        assert_eq!(format_duration(0.25), "250ms");
+        assert_eq!(format_duration(0.0004), "0ms");
+        // Rounds up into the seconds form rather than printing "1000ms"
+        assert_eq!(format_duration(0.9996), "1.0s");
+    }
+
+    #[test]
+    fn durations_under_a_minute_show_tenths() {
+        assert_eq!(format_duration(12.34), "12.3s");
+        assert_eq!(format_duration(59.96), "1m 0s");
+    }
+
+    #[test]
+    fn multi_hour_durations_show_hours_minutes_seconds() {
+        assert_eq!(format_duration(90.0), "1m 30s");
+        assert_eq!(format_duration(2.0 * 3600.0 + 3.0 * 60.0 + 4.0), "2h 3m 4s");
+        assert_eq!(format_duration(30.0 * 3600.0), "30h 0m 0s");
+    }
+}
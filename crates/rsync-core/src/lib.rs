@@ -1,10 +1,12 @@
 pub mod database;
 pub mod error;
 pub mod file_system;
+pub mod format;
 pub mod models;
 pub mod repository;
 pub mod rsync_client;
 pub mod services;
+pub mod ssh_client;
 
 #[cfg(test)]
 pub mod tests;
@@ -0,0 +1 @@
+ALTER TABLE jobs ADD COLUMN env TEXT NOT NULL DEFAULT '{}';
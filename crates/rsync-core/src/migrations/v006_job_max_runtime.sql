@@ -0,0 +1 @@
+ALTER TABLE jobs ADD COLUMN max_runtime_mins INTEGER;
@@ -0,0 +1,16 @@
+CREATE TABLE itemize_summaries (
+    invocation_id TEXT PRIMARY KEY NOT NULL REFERENCES invocations(id) ON DELETE CASCADE,
+    total         INTEGER NOT NULL DEFAULT 0,
+    created       INTEGER NOT NULL DEFAULT 0,
+    updated       INTEGER NOT NULL DEFAULT 0,
+    deleted       INTEGER NOT NULL DEFAULT 0,
+    stored        INTEGER NOT NULL DEFAULT 0,
+    truncated     INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE TABLE itemized_changes (
+    invocation_id TEXT NOT NULL REFERENCES invocations(id) ON DELETE CASCADE,
+    seq           INTEGER NOT NULL,
+    change        TEXT NOT NULL,
+    PRIMARY KEY (invocation_id, seq)
+);
@@ -0,0 +1,2 @@
+ALTER TABLE jobs ADD COLUMN color TEXT;
+ALTER TABLE jobs ADD COLUMN icon TEXT;
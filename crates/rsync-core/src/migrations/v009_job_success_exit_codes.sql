@@ -0,0 +1 @@
+ALTER TABLE jobs ADD COLUMN success_exit_codes TEXT NOT NULL DEFAULT '[0]';
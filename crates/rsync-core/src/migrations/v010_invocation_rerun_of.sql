@@ -0,0 +1 @@
+ALTER TABLE invocations ADD COLUMN rerun_of TEXT;
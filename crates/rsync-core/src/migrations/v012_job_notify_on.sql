@@ -0,0 +1 @@
+ALTER TABLE jobs ADD COLUMN notify_on TEXT NOT NULL DEFAULT '"Failure"';
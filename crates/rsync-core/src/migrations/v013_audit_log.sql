@@ -0,0 +1,8 @@
+CREATE TABLE audit_log (
+    id        TEXT PRIMARY KEY NOT NULL,
+    timestamp TEXT NOT NULL,
+    action    TEXT NOT NULL,
+    target_id TEXT,
+    detail    TEXT NOT NULL,
+    source    TEXT
+);
@@ -0,0 +1 @@
+ALTER TABLE jobs ADD COLUMN create_destination INTEGER NOT NULL DEFAULT 0;
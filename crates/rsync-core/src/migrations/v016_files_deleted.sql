@@ -0,0 +1,2 @@
+ALTER TABLE invocations ADD COLUMN files_deleted INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE run_statistics ADD COLUMN files_deleted INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1 @@
+ALTER TABLE jobs ADD COLUMN run_on_startup INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,2 @@
+ALTER TABLE jobs ADD COLUMN change_alarm TEXT;
+ALTER TABLE invocations ADD COLUMN warning TEXT;
@@ -0,0 +1 @@
+ALTER TABLE jobs ADD COLUMN skip_if_unchanged INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1 @@
+ALTER TABLE invocations ADD COLUMN resumed_from TEXT;
@@ -0,0 +1 @@
+ALTER TABLE jobs ADD COLUMN expect_nonempty_destination INTEGER NOT NULL DEFAULT 0;
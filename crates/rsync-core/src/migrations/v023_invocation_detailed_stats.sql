@@ -0,0 +1 @@
+ALTER TABLE invocations ADD COLUMN detailed_stats TEXT;
@@ -0,0 +1,10 @@
+ALTER TABLE jobs ADD COLUMN verify_after_snapshot INTEGER NOT NULL DEFAULT 0;
+
+CREATE TABLE snapshot_verifications (
+    invocation_id TEXT PRIMARY KEY NOT NULL REFERENCES invocations(id) ON DELETE CASCADE,
+    job_id        TEXT NOT NULL REFERENCES jobs(id) ON DELETE CASCADE,
+    verified_at   TEXT NOT NULL,
+    passed        INTEGER NOT NULL,
+    discrepancies INTEGER NOT NULL DEFAULT 0,
+    error         TEXT
+);
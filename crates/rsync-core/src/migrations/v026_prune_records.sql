@@ -0,0 +1,11 @@
+CREATE TABLE prune_records (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    pruned_at   TEXT NOT NULL,
+    invocations TEXT NOT NULL,
+    snapshots   TEXT NOT NULL
+);
+
+CREATE INDEX idx_prune_records_pruned_at ON prune_records(pruned_at);
+
+-- The old recovery log only ever covered the last day of prunes
+DELETE FROM settings WHERE key = 'prune_recovery_log';
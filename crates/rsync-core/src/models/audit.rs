@@ -0,0 +1,45 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A destructive action worth keeping a record of.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[ts(export_to = "audit/")]
+pub enum AuditAction {
+    JobDeleted,
+    /// All invocation history of one job was deleted.
+    HistoryPurged,
+    /// Run statistics were deleted, for every job or for one.
+    StatisticsReset,
+    SnapshotDeleted,
+    /// A duplicate job was folded into another and deleted.
+    JobsMerged,
+}
+
+impl std::fmt::Display for AuditAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuditAction::JobDeleted => write!(f, "Job deleted"),
+            AuditAction::HistoryPurged => write!(f, "History purged"),
+            AuditAction::StatisticsReset => write!(f, "Statistics reset"),
+            AuditAction::SnapshotDeleted => write!(f, "Snapshot deleted"),
+            AuditAction::JobsMerged => write!(f, "Jobs merged"),
+        }
+    }
+}
+
+/// One recorded destructive action.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "audit/")]
+pub struct AuditEntry {
+    pub id: Uuid,
+    pub timestamp: DateTime<Utc>,
+    pub action: AuditAction,
+    /// The job, snapshot, etc. acted on; `None` for actions across all jobs.
+    pub target_id: Option<Uuid>,
+    /// Human-readable description, e.g. the deleted job's name.
+    pub detail: String,
+    /// Where the action came from (`gui`, `tui`, `cli`), when known.
+    pub source: Option<String>,
+}
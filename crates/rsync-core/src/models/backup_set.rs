@@ -0,0 +1,83 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use crate::models::job::JobStatus;
+
+/// A named, ordered group of jobs that run one after another as a unit.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "backup_set/")]
+pub struct BackupSet {
+    pub id: Uuid,
+    pub name: String,
+    /// Jobs in run order.
+    pub job_ids: Vec<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// What happened to one job of a set run.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "backup_set/")]
+#[serde(tag = "type")]
+pub enum SetJobOutcome {
+    /// The job ran to the end with this status.
+    Finished {
+        invocation_id: Uuid,
+        status: JobStatus,
+        error_message: Option<String>,
+    },
+    /// The job could not be started (missing, already running, refused).
+    NotStarted { message: String },
+    /// An earlier job was cancelled, or failed and the set stops on failure.
+    Skipped,
+}
+
+impl SetJobOutcome {
+    pub fn is_success(&self) -> bool {
+        matches!(
+            self,
+            SetJobOutcome::Finished {
+                status: JobStatus::Completed,
+                ..
+            }
+        )
+    }
+
+    /// Whether the job was cancelled, which stops the rest of the set
+    /// whether or not it stops on failure.
+    pub fn is_cancelled(&self) -> bool {
+        matches!(
+            self,
+            SetJobOutcome::Finished {
+                status: JobStatus::Cancelled,
+                ..
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "backup_set/")]
+pub struct SetJobResult {
+    pub job_id: Uuid,
+    /// Empty when the job no longer exists.
+    pub job_name: String,
+    pub outcome: SetJobOutcome,
+}
+
+/// Per-job results of running a backup set, in set order.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "backup_set/")]
+pub struct BackupSetRun {
+    pub set_id: Uuid,
+    pub results: Vec<SetJobResult>,
+}
+
+impl BackupSetRun {
+    /// True when every job in the set finished successfully.
+    pub fn succeeded(&self) -> bool {
+        self.results.iter().all(|r| r.outcome.is_success())
+    }
+}
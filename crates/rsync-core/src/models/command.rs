@@ -55,6 +55,24 @@ pub struct ParsedCommand {
     /// Bandwidth limit if specified
     #[ts(type = "number | null")]
     pub bandwidth_limit: Option<u64>,
+    /// Compression algorithm from --compress-choice / --zc
+    #[serde(default)]
+    pub compress_choice: Option<String>,
+    /// Compression level from --compress-level / --zl
+    #[serde(default)]
+    pub compress_level: Option<i32>,
+    /// Deletion cap from --max-delete
+    #[serde(default)]
+    pub max_delete: Option<u32>,
+    /// Timestamp tolerance in seconds from --modify-window
+    #[serde(default)]
+    pub modify_window: Option<u32>,
+    /// Checksum algorithm from --checksum-choice / --cc
+    #[serde(default)]
+    pub checksum_choice: Option<String>,
+    /// Directory for partial files from --partial-dir
+    #[serde(default)]
+    pub partial_dir: Option<String>,
     /// SSH command string if -e was used
     pub ssh_command: Option<String>,
     /// Link-dest path if specified
@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use crate::models::progress::RunningJobInfo;
+
+/// Everything the home screen shows, gathered in one call so the numbers
+/// agree with each other.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "dashboard/")]
+pub struct DashboardSummary {
+    pub total_jobs: usize,
+    pub enabled_jobs: usize,
+    pub running: Vec<RunningJobInfo>,
+    /// Scheduled jobs past their next run, most overdue first.
+    pub overdue: Vec<OverdueJob>,
+    /// Runs started in the last 24 hours that succeeded.
+    pub succeeded_last_24h: usize,
+    /// Runs started in the last 24 hours that failed or hit their time limit.
+    pub failed_last_24h: usize,
+    /// Bytes transferred by real (non-dry) runs recorded in the last 7 days.
+    #[ts(type = "number")]
+    pub bytes_last_7d: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "dashboard/")]
+pub struct OverdueJob {
+    pub job_id: Uuid,
+    pub name: String,
+    #[ts(type = "number")]
+    pub overdue_secs: i64,
+}
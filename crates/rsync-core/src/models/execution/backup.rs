@@ -12,6 +12,46 @@ pub struct TransferStats {
     pub files_transferred: u64,
     #[ts(type = "number")]
     pub total_files: u64,
+    /// Files removed from the destination (`--delete`).
+    #[serde(default)]
+    #[ts(type = "number")]
+    pub files_deleted: u64,
+    /// The fuller breakdown rsync prints with `--stats` (`--info=stats2` or
+    /// higher), when the run printed one.
+    #[serde(default)]
+    pub detailed: Option<DetailedStats>,
+}
+
+/// Figures from rsync's `--stats` block beyond the counts in
+/// [`TransferStats`]. Each is `None` when rsync did not print it; older
+/// versions print fewer lines.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default, TS)]
+#[ts(export_to = "execution/")]
+pub struct DetailedStats {
+    /// "Number of created files"
+    #[ts(type = "number | null")]
+    pub created_files: Option<u64>,
+    /// "Literal data": bytes sent as-is
+    #[ts(type = "number | null")]
+    pub literal_data: Option<u64>,
+    /// "Matched data": bytes the delta algorithm found already at the destination
+    #[ts(type = "number | null")]
+    pub matched_data: Option<u64>,
+    /// "File list size"
+    #[ts(type = "number | null")]
+    pub file_list_size: Option<u64>,
+    /// "File list generation time"
+    pub file_list_generation_secs: Option<f64>,
+    /// "File list transfer time"
+    pub file_list_transfer_secs: Option<f64>,
+    /// "Total bytes sent", protocol overhead included
+    #[ts(type = "number | null")]
+    pub total_bytes_sent: Option<u64>,
+    /// "Total bytes received"
+    #[ts(type = "number | null")]
+    pub total_bytes_received: Option<u64>,
+    /// Average speed from the "sent … bytes  received … bytes  N bytes/sec" line
+    pub bytes_per_sec: Option<f64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
@@ -43,6 +83,15 @@ pub struct BackupInvocation {
     pub finished_at: Option<DateTime<Utc>>,
     pub status: InvocationStatus,
     pub trigger: InvocationTrigger,
+    /// The invocation whose stored command this run repeated, if it was a rerun.
+    #[serde(default)]
+    pub rerun_of: Option<Uuid>,
+    /// The interrupted invocation whose snapshot directory this run resumed into.
+    #[serde(default)]
+    pub resumed_from: Option<Uuid>,
+    /// Set when a successful run tripped the job's change alarm.
+    #[serde(default)]
+    pub warning: Option<String>,
     pub transfer_stats: TransferStats,
     pub execution_output: ExecutionOutput,
 }
@@ -54,6 +103,8 @@ pub enum InvocationStatus {
     Succeeded,
     Failed,
     Cancelled,
+    /// Stopped because the job's `max_runtime_mins` limit was reached.
+    StoppedByLimit,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
@@ -77,4 +128,24 @@ pub struct SnapshotRecord {
     #[ts(type = "number")]
     pub file_count: u64,
     pub is_latest: bool,
+    /// Pinned snapshots are never pruned by the retention policy.
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+/// The result of comparing a finished snapshot with its source, for jobs
+/// with `verify_after_snapshot` set.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "execution/")]
+pub struct SnapshotVerification {
+    /// The run that wrote the snapshot.
+    pub invocation_id: Uuid,
+    pub job_id: Uuid,
+    pub verified_at: DateTime<Utc>,
+    pub passed: bool,
+    /// Files that differ between source and snapshot, or exist on one side only.
+    #[ts(type = "number")]
+    pub discrepancies: u64,
+    /// Why the comparison itself could not be made, if it failed to run.
+    pub error: Option<String>,
 }
@@ -0,0 +1,61 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::backup::{BackupInvocation, InvocationStatus, InvocationTrigger, TransferStats};
+
+/// One invocation as a line of the history feed: flat on the wire, so log
+/// aggregators can index each field without unpacking nested objects.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HistoryEvent {
+    pub invocation_id: Uuid,
+    pub job_id: Uuid,
+    pub job_name: String,
+    pub trigger: InvocationTrigger,
+    pub status: InvocationStatus,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub duration_secs: Option<f64>,
+    pub exit_code: Option<i32>,
+    #[serde(flatten)]
+    pub stats: HistoryEventStats,
+}
+
+/// The transfer counts of a [`HistoryEvent`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HistoryEventStats {
+    pub bytes_transferred: u64,
+    pub files_transferred: u64,
+    pub total_files: u64,
+    pub files_deleted: u64,
+}
+
+impl HistoryEvent {
+    pub fn new(inv: &BackupInvocation, job_name: String) -> Self {
+        Self {
+            invocation_id: inv.id,
+            job_id: inv.job_id,
+            job_name,
+            trigger: inv.trigger.clone(),
+            status: inv.status.clone(),
+            started_at: inv.started_at,
+            finished_at: inv.finished_at,
+            duration_secs: inv
+                .finished_at
+                .map(|end| (end - inv.started_at).num_milliseconds() as f64 / 1000.0),
+            exit_code: inv.execution_output.exit_code,
+            stats: HistoryEventStats::from(&inv.transfer_stats),
+        }
+    }
+}
+
+impl From<&TransferStats> for HistoryEventStats {
+    fn from(stats: &TransferStats) -> Self {
+        Self {
+            bytes_transferred: stats.bytes_transferred,
+            files_transferred: stats.files_transferred,
+            total_files: stats.total_files,
+            files_deleted: stats.files_deleted,
+        }
+    }
+}
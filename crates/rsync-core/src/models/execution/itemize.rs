@@ -43,3 +43,66 @@ pub enum DifferenceKind {
     ExtendedAttributes, // x (position 10)
     NewlyCreated,       // + in any position
 }
+
+/// Counts over every itemized change in a run, including any that were
+/// dropped once the per-invocation storage cap was reached.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "execution/")]
+pub struct ItemizeSummary {
+    #[ts(type = "number")]
+    pub total: u64,
+    #[ts(type = "number")]
+    pub created: u64,
+    #[ts(type = "number")]
+    pub updated: u64,
+    #[ts(type = "number")]
+    pub deleted: u64,
+    /// Changes kept in the database.
+    #[ts(type = "number")]
+    pub stored: u64,
+    /// Changes past the cap that were counted but not stored.
+    #[ts(type = "number")]
+    pub truncated: u64,
+}
+
+/// The itemized changes stored for one invocation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "execution/")]
+pub struct StoredItemizedChanges {
+    pub summary: ItemizeSummary,
+    pub changes: Vec<ItemizedChange>,
+}
+
+/// What changed between two snapshots of a job, from `from_path` (the older
+/// one) to `to_path`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "execution/")]
+pub struct SnapshotDiff {
+    pub from_path: String,
+    pub to_path: String,
+    /// Files only in the newer snapshot.
+    pub created: Vec<ItemizedChange>,
+    /// Files in both whose content or attributes differ.
+    pub updated: Vec<ItemizedChange>,
+    /// Files only in the older snapshot.
+    pub deleted: Vec<ItemizedChange>,
+}
+
+/// What restoring a job's backup onto its source would write, from a dry run
+/// of the reversed transfer.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "execution/")]
+pub struct RestorePreview {
+    /// The restore command, for running once the user has confirmed.
+    pub command: String,
+    /// Files and directories the restore would create or update, up to the
+    /// preview cap.
+    pub changes: Vec<ItemizedChange>,
+    /// Changes past the cap that were counted but not listed.
+    #[ts(type = "number")]
+    pub truncated: u64,
+    #[ts(type = "number")]
+    pub files_to_restore: u64,
+    #[ts(type = "number")]
+    pub bytes_to_restore: u64,
+}
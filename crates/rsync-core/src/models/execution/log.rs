@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 use uuid::Uuid;
 
+use crate::models::execution::progress::ProgressUpdate;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
 #[ts(export_to = "execution/")]
 pub enum LogLevel {
@@ -22,3 +24,23 @@ pub struct LogEntry {
     pub level: LogLevel,
     pub message: String,
 }
+
+/// One event in an invocation's JSON Lines log (`<log>.jsonl`), tagged by
+/// the `stream` it came from.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "stream", rename_all = "snake_case")]
+pub enum StructuredLogRecord {
+    Stdout {
+        timestamp: DateTime<Utc>,
+        message: String,
+    },
+    Stderr {
+        timestamp: DateTime<Utc>,
+        message: String,
+    },
+    /// A parsed `--progress` line; the raw line follows as a `Stdout` record.
+    Progress {
+        timestamp: DateTime<Utc>,
+        progress: ProgressUpdate,
+    },
+}
@@ -1,5 +1,6 @@
 pub mod backup;
 pub mod event;
+pub mod history_event;
 pub mod itemize;
 pub mod log;
 pub mod progress;
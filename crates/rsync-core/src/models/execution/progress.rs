@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 use uuid::Uuid;
 
+use crate::models::execution::backup::{DetailedStats, InvocationStatus};
 use crate::models::job::JobStatus;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
@@ -20,6 +21,45 @@ pub struct ProgressUpdate {
     pub files_remaining: u64,
     #[ts(type = "number")]
     pub files_total: u64,
+    /// Estimated seconds until the transfer finishes, if it can be estimated.
+    #[serde(default)]
+    #[ts(type = "number | null")]
+    pub eta_secs: Option<u64>,
+    /// The file rsync is copying, when its name preceded the progress line.
+    #[serde(default)]
+    pub current_file: Option<String>,
+}
+
+/// Combined progress across all running jobs that have reported progress.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "execution/")]
+pub struct AggregateProgress {
+    /// Number of jobs contributing a progress snapshot.
+    pub active_jobs: usize,
+    /// Sum of each job's current transfer rate.
+    pub bytes_per_sec: f64,
+    #[ts(type = "number")]
+    pub bytes_transferred: u64,
+    /// Files checked so far (total minus remaining), summed across jobs.
+    #[ts(type = "number")]
+    pub files_done: u64,
+    #[ts(type = "number")]
+    pub files_total: u64,
+    /// `files_done / files_total`, or the mean per-job percentage when no job
+    /// reports file counts yet.
+    pub percentage: f64,
+}
+
+/// A running job and how far along it is, for the tray and secondary windows.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "execution/")]
+pub struct RunningJobInfo {
+    pub id: Uuid,
+    pub name: String,
+    /// Percentage of the current run, 0 until rsync reports progress.
+    pub percentage: f64,
+    pub current_file: Option<String>,
+    pub bytes_per_sec: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
@@ -41,9 +81,46 @@ pub struct JobStatusEvent {
     pub error_message: Option<String>,
 }
 
+/// A finished run worth telling the user about.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JobNotification {
+    pub job_id: Uuid,
+    pub job_name: String,
+    pub invocation_id: Uuid,
+    pub status: InvocationStatus,
+    pub error_message: Option<String>,
+}
+
 /// Parsed rsync transfer summary (from the "sent X bytes  received Y bytes" line).
 #[derive(Debug, Clone, PartialEq)]
 pub struct TransferSummary {
     pub sent_bytes: u64,
     pub received_bytes: u64,
+    /// The average rate at the end of the line, if present.
+    pub bytes_per_sec: Option<f64>,
+}
+
+/// Counts from rsync's `--stats` block. Fields are `None` when the line is
+/// missing (older rsync versions print fewer lines).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatsBlock {
+    /// "Number of files"
+    pub total_files: Option<u64>,
+    /// "Number of regular files transferred" ("Number of files transferred" before 3.1)
+    pub files_transferred: Option<u64>,
+    /// "Total file size"
+    pub total_file_size: Option<u64>,
+    /// "Total transferred file size"
+    pub transferred_file_size: Option<u64>,
+    /// "Number of deleted files" (3.1+)
+    pub deleted_files: Option<u64>,
+    /// Everything else the block reports (`--info=stats2` and up).
+    pub detailed: DetailedStats,
+}
+
+impl StatsBlock {
+    /// The detailed figures, or `None` when rsync printed none of them.
+    pub fn detailed_stats(&self) -> Option<DetailedStats> {
+        (self.detailed != DetailedStats::default()).then(|| self.detailed.clone())
+    }
 }
@@ -14,6 +14,9 @@ pub struct RunStatistic {
     pub files_transferred: u64,
     #[ts(type = "number")]
     pub bytes_transferred: u64,
+    #[serde(default)]
+    #[ts(type = "number")]
+    pub files_deleted: u64,
     pub duration_secs: f64,
     pub speedup: Option<f64>,
 }
@@ -27,6 +30,53 @@ pub struct AggregatedStats {
     pub total_files_transferred: u64,
     #[ts(type = "number")]
     pub total_bytes_transferred: u64,
+    #[ts(type = "number")]
+    pub total_files_deleted: u64,
     pub total_duration_secs: f64,
     pub total_time_saved_secs: f64,
 }
+
+/// How one run's transfer compares to the average of the job's recent runs.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "execution/")]
+pub struct RunAnomaly {
+    pub invocation_id: Uuid,
+    /// Previous runs the averages were taken over.
+    pub baseline_runs: usize,
+    pub avg_bytes_transferred: f64,
+    pub avg_files_transferred: f64,
+    /// This run's bytes divided by the average (the average is floored at 1).
+    pub bytes_ratio: f64,
+    pub files_ratio: f64,
+    /// Either ratio exceeded the configured factor.
+    pub is_anomaly: bool,
+}
+
+/// Space a job's destination currently occupies.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "execution/")]
+pub struct DestinationUsage {
+    pub path: String,
+    /// Sum of every file's size, counting hard-linked files once per link.
+    #[ts(type = "number")]
+    pub logical_bytes: u64,
+    /// Space actually used: each hard-linked file is counted once, so
+    /// unchanged files shared between snapshots add nothing.
+    #[ts(type = "number")]
+    pub physical_bytes: u64,
+}
+
+/// What a job's next run would transfer, from a `--dry-run --stats` pass.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "execution/")]
+pub struct TransferEstimate {
+    #[ts(type = "number")]
+    pub bytes_to_transfer: u64,
+    #[ts(type = "number")]
+    pub files_to_transfer: u64,
+    /// Size of everything in the source, transferred or not.
+    #[ts(type = "number")]
+    pub total_size_bytes: u64,
+    #[ts(type = "number")]
+    pub total_files: u64,
+}
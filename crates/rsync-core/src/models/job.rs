@@ -1,9 +1,15 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 use uuid::Uuid;
 
+use super::execution::backup::{BackupInvocation, SnapshotRecord};
+use super::execution::statistics::RunStatistic;
 use super::schedule::ScheduleConfig;
+use super::validation::ValidationCheck;
 
 pub use super::rsync_options::{
     AdvancedOptions, CoreTransferOptions, FileHandlingOptions, MetadataOptions, OutputOptions,
@@ -73,9 +79,33 @@ pub enum StorageLocation {
         host: String,
         module: String,
         path: String,
+        /// User to authenticate to the daemon as, for modules with `auth users`.
+        #[serde(default)]
+        username: Option<String>,
+        /// Name of a stored daemon password, written to a temporary
+        /// `--password-file` for each run.
+        #[serde(default)]
+        password_secret: Option<String>,
+    },
+    /// A local path on an external or network volume. The job refuses to run
+    /// unless a filesystem is actually mounted at `mountpoint`.
+    Mount {
+        mountpoint: String,
+        path: String,
+        /// Volume label the mount must carry, to catch the wrong drive.
+        expected_fs_label: Option<String>,
     },
 }
 
+/// How a job with `create_destination` gets its remote destination created.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DestinationPrep {
+    /// Run `ssh` with these arguments to `mkdir -p` the path before rsync.
+    RemoteMkdir(Vec<String>),
+    /// Pass `--mkpath` and let rsync create the path.
+    Mkpath,
+}
+
 fn default_ssh_port() -> u16 {
     22
 }
@@ -88,8 +118,32 @@ impl StorageLocation {
                 user, host, path, ..
             } => format!("{}@{}:{}", user, host, path),
             StorageLocation::RemoteRsync {
-                host, module, path, ..
-            } => format!("rsync://{}/{}/{}", host, module, path),
+                host,
+                module,
+                path,
+                username,
+                ..
+            } => match username {
+                Some(user) => format!("rsync://{}@{}/{}/{}", user, host, module, path),
+                None => format!("rsync://{}/{}/{}", host, module, path),
+            },
+            StorageLocation::Mount { path, .. } => path.clone(),
+        }
+    }
+
+    /// The path on this machine, for locations rsync reads or writes locally.
+    pub fn local_path(&self) -> Option<&str> {
+        match self {
+            StorageLocation::Local { path } | StorageLocation::Mount { path, .. } => Some(path),
+            _ => None,
+        }
+    }
+
+    /// The host `ssh` connects to, for locations reached over SSH.
+    pub fn ssh_host(&self) -> Option<&str> {
+        match self {
+            StorageLocation::RemoteSsh { host, .. } => Some(host),
+            _ => None,
         }
     }
 }
@@ -102,6 +156,15 @@ pub struct SshConfig {
     pub identity_file: Option<String>,
     #[serde(default = "default_true")]
     pub strict_host_key_checking: bool,
+    /// Seconds to wait for the connection (`-o ConnectTimeout=N`).
+    #[serde(default)]
+    pub connect_timeout: Option<u32>,
+    /// Seconds between keepalive probes (`-o ServerAliveInterval=N`).
+    #[serde(default)]
+    pub server_alive_interval: Option<u32>,
+    /// Bastion to connect through (`-J [user@]host[:port]`).
+    #[serde(default)]
+    pub jump_host: Option<String>,
     pub custom_ssh_command: Option<String>,
 }
 
@@ -115,11 +178,42 @@ impl Default for SshConfig {
             port: 22,
             identity_file: None,
             strict_host_key_checking: true,
+            connect_timeout: None,
+            server_alive_interval: None,
+            jump_host: None,
             custom_ssh_command: None,
         }
     }
 }
 
+/// Outcome of pointing a job at a new destination.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "job/")]
+pub struct RetargetResult {
+    pub job: JobDefinition,
+    /// Checks on the new destination, including warnings to show the user.
+    pub checks: Vec<ValidationCheck>,
+    /// Snapshots recorded under the old destination. Their records are
+    /// dropped so the next run doesn't `--link-dest` against them; the
+    /// directories themselves are left in place.
+    pub forgotten_snapshots: Vec<String>,
+}
+
+/// A host key offered by a remote server, for the user to confirm before
+/// it is added to `known_hosts`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "job/")]
+pub struct HostKey {
+    pub host: String,
+    pub port: u16,
+    /// Key algorithm, e.g. `ssh-ed25519`.
+    pub key_type: String,
+    /// OpenSSH-style fingerprint, e.g. `SHA256:abc...`.
+    pub fingerprint: String,
+    /// The line that would be appended to `known_hosts`.
+    pub known_hosts_line: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
 #[ts(export_to = "job/")]
 pub struct TransferConfig {
@@ -137,12 +231,164 @@ pub struct JobDefinition {
     pub transfer: TransferConfig,
     pub options: RsyncOptions,
     pub ssh_config: Option<SshConfig>,
+    #[serde(default)]
+    pub appearance: JobAppearance,
+    #[serde(default)]
+    pub run_policy: JobRunPolicy,
+    #[serde(default)]
+    pub safety: JobSafety,
     pub schedule: Option<ScheduleConfig>,
     pub enabled: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+fn default_success_exit_codes() -> Vec<i32> {
+    vec![0]
+}
+
+/// How a job is shown in job lists.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "job/")]
+pub struct JobAppearance {
+    /// Accent color for the job in job lists, as `#rgb` or `#rrggbb`.
+    #[serde(default)]
+    pub color: Option<String>,
+    /// Icon name shown next to the job (a lucide icon name in the GUI).
+    #[serde(default)]
+    pub icon: Option<String>,
+}
+
+/// How a job's rsync process is started, limited and judged.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "job/")]
+pub struct JobRunPolicy {
+    /// Extra environment variables for the rsync process. Empty inherits the
+    /// app's environment unchanged.
+    #[serde(default)]
+    #[ts(type = "Record<string, string>")]
+    pub env: HashMap<String, String>,
+    /// Stop the run after this many minutes. Uses rsync's `--stop-after` when
+    /// available; otherwise the executor kills the process.
+    #[serde(default)]
+    pub max_runtime_mins: Option<u32>,
+    /// rsync exit codes that count as a successful run, e.g. adding 24 to
+    /// accept files that vanished during the transfer.
+    #[serde(default = "default_success_exit_codes")]
+    pub success_exit_codes: Vec<i32>,
+    /// Which finished runs are reported through the executor's notifier.
+    #[serde(default)]
+    pub notify_on: NotifyOn,
+    /// CPU and I/O priority of the rsync process. `None` leaves it unchanged.
+    #[serde(default)]
+    pub process_priority: Option<ProcessPriority>,
+    /// Create a missing remote destination directory before transferring.
+    #[serde(default)]
+    pub create_destination: bool,
+    /// Start this job once when the app launches.
+    #[serde(default)]
+    pub run_on_startup: bool,
+    /// Split a local source's top-level entries across up to this many rsync
+    /// processes run at once. `None` or 1 runs a single rsync.
+    #[serde(default)]
+    pub parallel_split: Option<u32>,
+}
+
+impl Default for JobRunPolicy {
+    fn default() -> Self {
+        Self {
+            env: HashMap::new(),
+            max_runtime_mins: None,
+            success_exit_codes: default_success_exit_codes(),
+            notify_on: NotifyOn::default(),
+            process_priority: None,
+            create_destination: false,
+            run_on_startup: false,
+            parallel_split: None,
+        }
+    }
+}
+
+/// Guards against runs that would do damage or go unnoticed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "job/")]
+pub struct JobSafety {
+    /// Refuse to run when the local destination holds nothing, as it would
+    /// after a failed mount, instead of repopulating it from scratch.
+    #[serde(default)]
+    pub expect_nonempty_destination: bool,
+    /// Flag successful runs that changed or deleted an unusual share of files.
+    #[serde(default)]
+    pub change_alarm: Option<ChangeAlarm>,
+    /// Skip scheduled runs while nothing in the source changed since the
+    /// last successful run.
+    #[serde(default)]
+    pub skip_if_unchanged: bool,
+    /// After each successful snapshot run, compare the new snapshot with the
+    /// source by checksum and record the result.
+    #[serde(default)]
+    pub verify_after_snapshot: bool,
+}
+
+/// Scheduling priority for a job's rsync process, applied with `nice` and
+/// `ionice` on Unix and a priority class on Windows.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[ts(export_to = "job/")]
+pub enum ProcessPriority {
+    /// Yield CPU and disk to interactive programs.
+    Low,
+    Normal,
+    /// Raising priority usually needs elevated privileges; without them the
+    /// run continues at normal priority.
+    High,
+}
+
+/// Which finished runs of a job send a notification.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[ts(export_to = "job/")]
+pub enum NotifyOn {
+    Never,
+    /// Failed runs and runs stopped by the run-time limit.
+    #[default]
+    Failure,
+    /// Every finished run, including successes and cancellations.
+    Always,
+}
+
+/// Thresholds on how much of the tree one run may change before it is
+/// flagged, e.g. after a ransomware event or a wrong mount. A `None`
+/// threshold is not checked.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "job/")]
+pub struct ChangeAlarm {
+    /// Percent of files created or updated above which the run is flagged.
+    pub max_changed_percent: Option<f64>,
+    /// Percent of destination files deleted above which the run is flagged.
+    pub max_deleted_percent: Option<f64>,
+    /// Send a notification when the alarm trips, whatever `notify_on` says.
+    #[serde(default)]
+    pub notify: bool,
+}
+
+/// How much of the tree one run touched, in percent.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChangeProportions {
+    /// Created and updated entries over the files the run considered.
+    pub changed_percent: f64,
+    /// Deleted entries over what the destination held before the run:
+    /// everything not newly created, plus the deletions.
+    pub deleted_percent: f64,
+}
+
+impl JobDefinition {
+    /// The configured run-time limit, if any.
+    pub fn max_runtime(&self) -> Option<Duration> {
+        self.run_policy
+            .max_runtime_mins
+            .map(|mins| Duration::from_secs(u64::from(mins) * 60))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
 #[ts(export_to = "job/")]
 pub enum JobStatus {
@@ -160,3 +406,45 @@ pub struct ExportData {
     pub exported_at: DateTime<Utc>,
     pub jobs: Vec<JobDefinition>,
 }
+
+/// What importing a file would do, worked out without writing anything.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "job/")]
+pub struct ImportPreview {
+    /// Previewed as a full backup restore (ids kept) rather than a job import.
+    pub full_backup: bool,
+    pub jobs: Vec<ImportPreviewJob>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "job/")]
+pub struct ImportPreviewJob {
+    pub name: String,
+    pub action: ImportAction,
+    /// Validation checks the job fails; errors stop the import.
+    pub failed_checks: Vec<ValidationCheck>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[ts(export_to = "job/")]
+pub enum ImportAction {
+    Create,
+    /// A full backup overwrites the existing job with the same id.
+    Update,
+}
+
+/// Full backup of the app's data: jobs plus their run history, snapshots and
+/// statistics. Ids are preserved so a bundle can be re-imported safely.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "job/")]
+pub struct FullExportData {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub jobs: Vec<JobDefinition>,
+    #[serde(default)]
+    pub invocations: Vec<BackupInvocation>,
+    #[serde(default)]
+    pub snapshots: Vec<SnapshotRecord>,
+    #[serde(default)]
+    pub statistics: Vec<RunStatistic>,
+}
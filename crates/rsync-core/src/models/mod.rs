@@ -2,12 +2,17 @@
 pub mod execution;
 
 // Root modules
+pub mod audit;
+pub mod backup_set;
 pub mod command;
+pub mod dashboard;
 pub mod job;
+pub mod pattern;
 pub mod rsync_options;
 pub mod schedule;
 pub mod scrubber;
 pub mod settings;
+pub mod status;
 pub mod validation;
 
 // Re-exports for API stability
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// How an entry under the source is classified by a job's filter patterns.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "pattern/")]
+#[serde(tag = "type")]
+pub enum Matched {
+    /// Excluded by this pattern (or by an excluded parent directory).
+    Excluded { pattern: String },
+    /// Explicitly included by this pattern.
+    Included { pattern: String },
+    /// No pattern matched; transferred by default.
+    Unmatched,
+}
+
+/// A single entry from a pattern test, relative to the source directory.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "pattern/")]
+pub struct PatternTestEntry {
+    pub path: String,
+    pub is_dir: bool,
+    pub matched: Matched,
+}
@@ -14,6 +14,10 @@ pub struct CoreTransferOptions {
     pub compress: bool,
     #[serde(default)]
     pub partial: bool,
+    /// Keep partially transferred files in this directory (`--partial-dir`)
+    /// instead of in place. Implies `--partial`.
+    #[serde(default)]
+    pub partial_dir: Option<String>,
     #[serde(default)]
     pub dry_run: bool,
 }
@@ -24,6 +28,7 @@ impl Default for CoreTransferOptions {
             archive: true,
             compress: false,
             partial: false,
+            partial_dir: None,
             dry_run: false,
         }
     }
@@ -46,6 +51,17 @@ pub struct FileHandlingOptions {
     pub ignore_existing: bool,
     #[serde(default)]
     pub one_file_system: bool,
+    /// Stop deleting after this many files (`--max-delete`).
+    #[serde(default)]
+    pub max_delete: Option<u32>,
+    /// Treat modification times this many seconds apart as equal
+    /// (`--modify-window`). FAT/exFAT and SMB targets need 1 or 2.
+    #[serde(default)]
+    pub modify_window: Option<u32>,
+    /// Checksum algorithm for transfers and `--checksum` comparisons
+    /// (`--checksum-choice`), e.g. `xxh64` or `md5`. `None` lets rsync pick.
+    #[serde(default)]
+    pub checksum_choice: Option<String>,
 }
 
 impl Default for FileHandlingOptions {
@@ -58,6 +74,9 @@ impl Default for FileHandlingOptions {
             whole_file: false,
             ignore_existing: false,
             one_file_system: false,
+            max_delete: None,
+            modify_window: None,
+            checksum_choice: None,
         }
     }
 }
@@ -113,15 +132,43 @@ impl Default for OutputOptions {
     }
 }
 
+/// A daily period, in local time, with its own bandwidth limit.
+///
+/// Times are minutes after midnight, as in quiet hours: a window whose end is
+/// before its start wraps past midnight, and equal start and end is empty.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "job/")]
+pub struct BandwidthWindow {
+    pub start_minute: u16,
+    pub end_minute: u16,
+    /// KB/s for `--bwlimit`; `None` lifts the limit during the window.
+    #[ts(type = "number | null")]
+    pub limit: Option<u64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
 #[ts(export_to = "job/")]
 pub struct AdvancedOptions {
     #[serde(default)]
     pub exclude_patterns: Vec<String>,
+    /// Also exclude common OS/NAS junk files (`.DS_Store`, `Thumbs.db`,
+    /// `@eaDir`, ...). Added at build time; not stored in `exclude_patterns`.
+    #[serde(default)]
+    pub exclude_system_files: bool,
     #[serde(default)]
     pub include_patterns: Vec<String>,
     #[ts(type = "number | null")]
     pub bandwidth_limit: Option<u64>,
+    /// Limits that replace `bandwidth_limit` at certain times of day. The
+    /// first window covering a run's start time wins.
+    #[serde(default)]
+    pub bandwidth_schedule: Vec<BandwidthWindow>,
+    /// Compression algorithm for `--compress-choice` (e.g. `zstd`, `lz4`, `zlib`).
+    #[serde(default)]
+    pub compress_choice: Option<String>,
+    /// Compression level for `--compress-level`.
+    #[serde(default)]
+    pub compress_level: Option<i32>,
     #[serde(default)]
     pub custom_args: Vec<String>,
 }
@@ -130,8 +177,12 @@ impl Default for AdvancedOptions {
     fn default() -> Self {
         Self {
             exclude_patterns: Vec::new(),
+            exclude_system_files: false,
             include_patterns: Vec::new(),
             bandwidth_limit: None,
+            bandwidth_schedule: Vec::new(),
+            compress_choice: None,
+            compress_level: None,
             custom_args: Vec::new(),
         }
     }
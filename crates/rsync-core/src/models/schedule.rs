@@ -1,5 +1,7 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
 #[ts(export_to = "schedule/")]
@@ -20,6 +22,47 @@ pub enum ScheduleType {
     },
 }
 
+/// Why the scheduler did or didn't start a job on one of its cycles.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "schedule/")]
+#[serde(tag = "type")]
+pub enum SchedulerDecision {
+    /// The job was due and the executor started it.
+    Executed,
+    /// The schedule says the job isn't due yet.
+    NotDue,
+    /// A previous run of the job was still going.
+    AlreadyRunning,
+    /// The job or its schedule is disabled.
+    Disabled,
+    /// The job was due but quiet hours were in effect.
+    QuietHours,
+    /// The job was due but the executor refused it (e.g. metered network).
+    Refused { message: String },
+}
+
+impl std::fmt::Display for SchedulerDecision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchedulerDecision::Executed => write!(f, "Started"),
+            SchedulerDecision::NotDue => write!(f, "Not due yet"),
+            SchedulerDecision::AlreadyRunning => write!(f, "Skipped: already running"),
+            SchedulerDecision::Disabled => write!(f, "Skipped: disabled"),
+            SchedulerDecision::QuietHours => write!(f, "Held back: quiet hours"),
+            SchedulerDecision::Refused { message } => write!(f, "Refused: {}", message),
+        }
+    }
+}
+
+/// One scheduler decision about one job.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "schedule/")]
+pub struct SchedulerEvent {
+    pub timestamp: DateTime<Utc>,
+    pub job_id: Uuid,
+    pub decision: SchedulerDecision,
+}
+
 pub struct SchedulerConfig {
     /// How often the scheduler checks for due jobs (in seconds).
     pub check_interval_secs: u64,
@@ -1,11 +1,27 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use uuid::Uuid;
+
+use crate::models::backup::{BackupInvocation, SnapshotRecord};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
 #[ts(export_to = "settings/")]
 pub struct RetentionSettings {
     pub max_log_age_days: u32,
     pub max_history_per_job: usize,
+    /// Successful runs to keep per job; `None` uses `max_history_per_job`.
+    #[serde(default)]
+    #[ts(type = "number | null")]
+    pub max_success_per_job: Option<usize>,
+    /// Failed or cancelled runs to keep per job; `None` uses `max_history_per_job`.
+    #[serde(default)]
+    #[ts(type = "number | null")]
+    pub max_failure_per_job: Option<usize>,
+    /// Keep run records (without logs) this many days; `None` deletes them
+    /// together with their logs after `max_log_age_days`.
+    #[serde(default)]
+    pub max_record_age_days: Option<u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
@@ -15,8 +31,224 @@ pub struct DryModeSettings {
     pub checksum: bool,
 }
 
+/// Layout and rotation of per-invocation log files.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "settings/")]
+pub struct LogFileSettings {
+    /// Path relative to the log directory; supports `{job}`, `{date}` and `{id}`.
+    pub path_template: String,
+    /// Rotate a log file once it grows past this many bytes.
+    #[ts(type = "number | null")]
+    pub max_size_bytes: Option<u64>,
+    /// Also write each event as JSON to a `.jsonl` file next to the log.
+    #[serde(default)]
+    pub structured: bool,
+    /// Longer stdout/stderr lines are truncated before being logged or shown.
+    #[serde(default = "default_max_line_bytes")]
+    #[ts(type = "number")]
+    pub max_line_bytes: usize,
+    /// Also have rsync write its own `--log-file` next to the captured log.
+    #[serde(default)]
+    pub rsync_log_file: bool,
+    /// `--log-file-format` passed to rsync when `rsync_log_file` is set.
+    #[serde(default = "default_rsync_log_format")]
+    pub rsync_log_format: String,
+}
+
+fn default_max_line_bytes() -> usize {
+    DEFAULT_MAX_LOG_LINE_BYTES
+}
+
+fn default_rsync_log_format() -> String {
+    DEFAULT_RSYNC_LOG_FORMAT.to_string()
+}
+
+/// One file per invocation, directly in the log directory.
+pub const DEFAULT_LOG_PATH_TEMPLATE: &str = "{id}.log";
+
+/// Longest output line kept in full unless configured otherwise.
+pub const DEFAULT_MAX_LOG_LINE_BYTES: usize = 64 * 1024;
+
+/// rsync's own default `--log-file-format`: itemized change and file name.
+pub const DEFAULT_RSYNC_LOG_FORMAT: &str = "%i %n%L";
+
+/// Itemized changes stored per invocation unless configured otherwise.
+pub const DEFAULT_ITEMIZE_STORE_LIMIT: usize = 5000;
+
+/// Recent snapshots a snapshot run links against unless configured otherwise.
+pub const DEFAULT_LINK_DEST_COUNT: usize = 1;
+
+impl Default for LogFileSettings {
+    fn default() -> Self {
+        Self {
+            path_template: DEFAULT_LOG_PATH_TEMPLATE.to_string(),
+            max_size_bytes: None,
+            structured: false,
+            max_line_bytes: DEFAULT_MAX_LOG_LINE_BYTES,
+            rsync_log_file: false,
+            rsync_log_format: DEFAULT_RSYNC_LOG_FORMAT.to_string(),
+        }
+    }
+}
+
+/// When a run's transfer size is flagged as unusual.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "settings/")]
+pub struct AnomalySettings {
+    /// Number of previous successful runs averaged into the baseline.
+    pub baseline_runs: usize,
+    /// Flag a run that transfers more than this multiple of the baseline.
+    pub factor: f64,
+}
+
+impl Default for AnomalySettings {
+    fn default() -> Self {
+        Self {
+            baseline_runs: 10,
+            factor: 10.0,
+        }
+    }
+}
+
+/// Optional preflight estimate of how much a mirror run would delete.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "settings/")]
+pub struct SafeMirrorSettings {
+    /// Dry-run mirror jobs with `--delete` during preflight to count deletions.
+    pub enabled: bool,
+    /// Warn when a run would delete more than this percentage of the
+    /// destination's entries.
+    pub max_delete_percent: f64,
+}
+
+impl Default for SafeMirrorSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_delete_percent: 10.0,
+        }
+    }
+}
+
+/// The optional deletion estimate for mirror jobs (see `SafeMirrorSettings`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct MirrorDeletionCheck {
+    /// Warn above this percentage of the destination's entries.
+    pub max_delete_percent: f64,
+    /// The auto trailing slash setting, which changes what `--delete` removes.
+    pub auto_trailing_slash: bool,
+    /// The relative paths setting, which changes where the source lands.
+    pub relative_paths: bool,
+}
+
+/// Outcome of moving the log directory.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "settings/")]
+pub struct LogRelocationResult {
+    /// Log files moved to the new directory.
+    pub moved: usize,
+    /// Log files that no longer existed; their records were updated anyway.
+    pub missing: usize,
+    /// Invocation records pointed at the new directory.
+    pub updated: usize,
+}
+
+/// What one retention pass removed, kept in the recovery log so the prune
+/// can be undone for a while.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PruneRecord {
+    pub pruned_at: DateTime<Utc>,
+    pub invocations: Vec<BackupInvocation>,
+    pub snapshots: Vec<PrunedSnapshot>,
+}
+
+/// A snapshot record removed by retention.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PrunedSnapshot {
+    pub record: SnapshotRecord,
+    /// Where the directory was moved to, when a trash directory was set.
+    pub trashed_to: Option<String>,
+}
+
+/// Outcome of undoing the most recent retention prune.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "settings/")]
+pub struct UndoPruneResult {
+    /// Invocation records put back. Their log files stay deleted.
+    pub invocations_restored: usize,
+    /// Snapshot records put back.
+    pub snapshots_restored: usize,
+    /// Snapshot directories moved back out of the trash.
+    pub directories_restored: usize,
+    /// Restored snapshots whose directory is gone (deleted outright, or the
+    /// trash was emptied).
+    pub missing_directories: Vec<String>,
+}
+
+/// A daily period, in local time, during which scheduled runs are suppressed.
+///
+/// Times are minutes after midnight. A window whose end is before its start
+/// wraps past midnight (e.g. 22:00–06:00); equal start and end is empty.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export_to = "settings/")]
+pub struct QuietHours {
+    pub start_minute: u16,
+    pub end_minute: u16,
+}
+
+/// Minutes in a day; quiet-hours times must be below this.
+pub const MINUTES_PER_DAY: u16 = 24 * 60;
+
+impl QuietHours {
+    /// Parse the stored `HH:MM-HH:MM` form.
+    pub fn parse(value: &str) -> Option<Self> {
+        let (start, end) = value.split_once('-')?;
+        Some(Self {
+            start_minute: parse_hh_mm(start)?,
+            end_minute: parse_hh_mm(end)?,
+        })
+    }
+
+    /// Format as `HH:MM-HH:MM`.
+    pub fn to_setting_value(&self) -> String {
+        format!(
+            "{:02}:{:02}-{:02}:{:02}",
+            self.start_minute / 60,
+            self.start_minute % 60,
+            self.end_minute / 60,
+            self.end_minute % 60
+        )
+    }
+}
+
+fn parse_hh_mm(value: &str) -> Option<u16> {
+    let (h, m) = value.trim().split_once(':')?;
+    let (h, m) = (h.parse::<u16>().ok()?, m.parse::<u16>().ok()?);
+    (h < 24 && m < 60).then_some(h * 60 + m)
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct HistoryRetentionConfig {
     pub max_age_days: u32,
     pub max_per_job: usize,
+    /// When either split limit is set, successes and failures are counted
+    /// separately instead of sharing `max_per_job`.
+    pub max_success_per_job: Option<usize>,
+    pub max_failure_per_job: Option<usize>,
+    /// When set, records older than `max_age_days` only lose their log file;
+    /// the record itself is deleted after this many days.
+    pub max_record_age_days: Option<u32>,
+}
+
+/// Invocations to delete and logs to drop, as computed by
+/// `history_retention::compute_retention_plan`.
+///
+/// Each entry is `(invocation_id, Option<log_file_path>)`; the path is only
+/// `Some` when the log file itself should be deleted.
+#[derive(Debug, Default, PartialEq)]
+pub struct RetentionPlan {
+    /// Invocation records to delete.
+    pub prune: Vec<(Uuid, Option<String>)>,
+    /// Invocations that keep their record but lose their log file.
+    pub clear_logs: Vec<(Uuid, Option<String>)>,
 }
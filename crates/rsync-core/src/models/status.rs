@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::models::backup::InvocationStatus;
+use crate::models::job::JobStatus;
+
+/// How a status should be colored. Each frontend maps these onto its own
+/// palette, so the same state looks alike in the TUI and the GUI.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[ts(export_to = "execution/")]
+pub enum StatusColor {
+    Success,
+    Failure,
+    Warning,
+    Active,
+    Neutral,
+}
+
+/// The user-facing wording and color of a run or job state.
+///
+/// A finished job and a successful run read the same ("Succeeded"), whichever
+/// enum the frontend happens to hold.
+pub trait StatusDisplay {
+    fn status_label(&self) -> &'static str;
+    fn status_color_hint(&self) -> StatusColor;
+}
+
+impl StatusDisplay for InvocationStatus {
+    fn status_label(&self) -> &'static str {
+        match self {
+            InvocationStatus::Running => "Running",
+            InvocationStatus::Succeeded => "Succeeded",
+            InvocationStatus::Failed => "Failed",
+            InvocationStatus::Cancelled => "Cancelled",
+            InvocationStatus::StoppedByLimit => "Stopped at time limit",
+        }
+    }
+
+    fn status_color_hint(&self) -> StatusColor {
+        match self {
+            InvocationStatus::Running => StatusColor::Active,
+            InvocationStatus::Succeeded => StatusColor::Success,
+            InvocationStatus::Failed => StatusColor::Failure,
+            InvocationStatus::Cancelled | InvocationStatus::StoppedByLimit => StatusColor::Warning,
+        }
+    }
+}
+
+impl StatusDisplay for JobStatus {
+    fn status_label(&self) -> &'static str {
+        match self {
+            JobStatus::Idle => "Idle",
+            JobStatus::Running => "Running",
+            JobStatus::Completed => "Succeeded",
+            JobStatus::Failed => "Failed",
+            JobStatus::Cancelled => "Cancelled",
+        }
+    }
+
+    fn status_color_hint(&self) -> StatusColor {
+        match self {
+            JobStatus::Idle => StatusColor::Neutral,
+            JobStatus::Running => StatusColor::Active,
+            JobStatus::Completed => StatusColor::Success,
+            JobStatus::Failed => StatusColor::Failure,
+            JobStatus::Cancelled => StatusColor::Warning,
+        }
+    }
+}
@@ -1,7 +1,17 @@
+use std::path::Path;
+use std::str::FromStr;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use croner::Cron;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 use uuid::Uuid;
 
+use super::job::{JobDefinition, SshConfig, StorageLocation};
+use super::schedule::ScheduleType;
+use super::settings::QuietHours;
+use crate::services::quiet_hours::is_quiet_time;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
 #[ts(export_to = "validation/")]
 pub struct PreflightResult {
@@ -27,6 +37,18 @@ pub enum CheckType {
     DiskSpace,
     SshConnectivity,
     RsyncInstalled,
+    MountPresent,
+    JobName,
+    JobColor,
+    DestinationConfig,
+    PreflightScope,
+    SnapshotFilesystem,
+    DeletionEstimate,
+    PartialDir,
+    ScheduleHealth,
+    ChecksumChoice,
+    RemoteHost,
+    Input,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
@@ -35,3 +57,274 @@ pub enum CheckSeverity {
     Error,
     Warning,
 }
+
+/// Parse a `#rgb` or `#rrggbb` hex color into its red, green and blue parts.
+pub fn parse_hex_color(value: &str) -> Option<(u8, u8, u8)> {
+    let hex = value.strip_prefix('#')?;
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |s: &str| u8::from_str_radix(s, 16).ok();
+    match hex.len() {
+        // #rgb is shorthand for #rrggbb
+        3 => Some((
+            channel(&hex[0..1].repeat(2))?,
+            channel(&hex[1..2].repeat(2))?,
+            channel(&hex[2..3].repeat(2))?,
+        )),
+        6 => Some((
+            channel(&hex[0..2])?,
+            channel(&hex[2..4])?,
+            channel(&hex[4..6])?,
+        )),
+        _ => None,
+    }
+}
+
+pub fn is_valid_hex_color(value: &str) -> bool {
+    parse_hex_color(value).is_some()
+}
+
+/// Structured checks on a job definition before it is saved or imported.
+pub fn validate_job(job: &JobDefinition) -> Vec<ValidationCheck> {
+    let named = !job.name.trim().is_empty();
+    let mut checks = vec![ValidationCheck {
+        check_type: CheckType::JobName,
+        passed: named,
+        message: if named {
+            "Job name is set".to_string()
+        } else {
+            "Job name must not be empty".to_string()
+        },
+        severity: CheckSeverity::Error,
+    }];
+    if let Some(schedule) = job.schedule.as_ref().filter(|s| s.enabled) {
+        checks.push(schedule_check(
+            &schedule.schedule_type,
+            None,
+            Utc::now(),
+            &Utc,
+        ));
+    }
+    if let Some(dir) = &job.options.core_transfer.partial_dir {
+        checks.push(partial_dir_check(dir, &job.transfer.destination));
+    }
+    if let Some(color) = &job.appearance.color {
+        let valid = is_valid_hex_color(color);
+        checks.push(ValidationCheck {
+            check_type: CheckType::JobColor,
+            passed: valid,
+            message: if valid {
+                "Job color is a valid hex color".to_string()
+            } else {
+                format!("Job color '{}' must be a hex color like #3b82f6", color)
+            },
+            severity: CheckSeverity::Error,
+        });
+    }
+    for (role, location) in [
+        ("Source", &job.transfer.source),
+        ("Destination", &job.transfer.destination),
+    ] {
+        if let Some(host) = location.ssh_host() {
+            checks.push(ssh_host_check(role, host));
+        }
+    }
+    checks
+}
+
+/// An SSH host must not start with `-`, or `ssh` would read it as an option.
+fn ssh_host_check(role: &str, host: &str) -> ValidationCheck {
+    let valid = !host.trim_start().starts_with('-');
+    ValidationCheck {
+        check_type: CheckType::RemoteHost,
+        passed: valid,
+        message: if valid {
+            format!("{} host is a host name", role)
+        } else {
+            format!("{} host '{}' must not start with '-'", role, host)
+        },
+        severity: CheckSeverity::Error,
+    }
+}
+
+/// A `--partial-dir` must be non-empty, and an absolute one must not overlap
+/// the destination: partial files inside the destination would be treated
+/// as transferred data (and removed by `--delete`).
+fn partial_dir_check(dir: &str, dest: &StorageLocation) -> ValidationCheck {
+    let dest_path = match dest {
+        StorageLocation::Local { path }
+        | StorageLocation::Mount { path, .. }
+        | StorageLocation::RemoteSsh { path, .. } => Some(Path::new(path)),
+        StorageLocation::RemoteRsync { .. } => None,
+    }
+    .filter(|d| !d.as_os_str().is_empty());
+    let partial = Path::new(dir.trim());
+    let problem = if dir.trim().is_empty() {
+        Some("Partial directory must not be empty".to_string())
+    } else if partial.is_absolute()
+        && dest_path.is_some_and(|d| partial.starts_with(d) || d.starts_with(partial))
+    {
+        Some(format!(
+            "Partial directory '{}' overlaps the destination; use a relative name like .rsync-partial",
+            dir
+        ))
+    } else {
+        None
+    };
+
+    ValidationCheck {
+        check_type: CheckType::PartialDir,
+        passed: problem.is_none(),
+        message: problem.unwrap_or_else(|| "Partial directory is valid".to_string()),
+        severity: CheckSeverity::Error,
+    }
+}
+
+/// How far ahead a cron expression must match to count as firing.
+const SCHEDULE_HORIZON_DAYS: i64 = 366;
+
+/// Most cron occurrences compared against quiet hours; more than a day of
+/// every-minute runs, so any time of day the expression matches is seen.
+const QUIET_HOURS_SAMPLE: usize = 2000;
+
+/// Check that a schedule will ever start its job.
+///
+/// A zero interval, an invalid cron expression, or one with no occurrence
+/// within a year is an error. With `quiet_hours` (read in `tz`), a cron whose
+/// every run falls inside the window is a warning: the scheduler holds those
+/// runs back and catches up when the window ends, never at the chosen time.
+pub fn schedule_check<Tz: TimeZone>(
+    schedule_type: &ScheduleType,
+    quiet_hours: Option<&QuietHours>,
+    from: DateTime<Utc>,
+    tz: &Tz,
+) -> ValidationCheck {
+    let failed = |message: String, severity| ValidationCheck {
+        check_type: CheckType::ScheduleHealth,
+        passed: false,
+        message,
+        severity,
+    };
+
+    let expression = match schedule_type {
+        ScheduleType::Interval { minutes: 0 } => {
+            return failed(
+                "Schedule interval must be at least 1 minute".to_string(),
+                CheckSeverity::Error,
+            );
+        }
+        ScheduleType::Interval { .. } => None,
+        ScheduleType::Cron { expression } => Some(expression),
+    };
+
+    if let Some(expression) = expression {
+        let cron = match Cron::from_str(expression) {
+            Ok(cron) => cron,
+            Err(e) => {
+                return failed(
+                    format!("Cron expression '{}' is invalid: {}", expression, e),
+                    CheckSeverity::Error,
+                );
+            }
+        };
+        let horizon = from + Duration::days(SCHEDULE_HORIZON_DAYS);
+        let mut runs = Vec::new();
+        let mut last = from;
+        while runs.len() < QUIET_HOURS_SAMPLE {
+            match cron.find_next_occurrence(&last, false) {
+                Ok(next) if next <= horizon => {
+                    runs.push(next);
+                    last = next;
+                }
+                _ => break,
+            }
+            // Without quiet hours, one occurrence is enough
+            if quiet_hours.is_none() {
+                break;
+            }
+        }
+
+        if runs.is_empty() {
+            return failed(
+                format!(
+                    "Cron expression '{}' never matches within the next year",
+                    expression
+                ),
+                CheckSeverity::Error,
+            );
+        }
+        if let Some(quiet) = quiet_hours {
+            if runs
+                .iter()
+                .all(|run| is_quiet_time(quiet, run.with_timezone(tz).time()))
+            {
+                return failed(
+                    format!(
+                        "Every run of '{}' falls inside quiet hours ({}); runs are held back until the window ends",
+                        expression,
+                        quiet.to_setting_value()
+                    ),
+                    CheckSeverity::Warning,
+                );
+            }
+        }
+    }
+
+    ValidationCheck {
+        check_type: CheckType::ScheduleHealth,
+        passed: true,
+        message: "Schedule will run".to_string(),
+        severity: CheckSeverity::Error,
+    }
+}
+
+/// Check that a destination is complete enough to run against.
+///
+/// An SSH destination without SSH settings is flagged as a warning: rsync
+/// falls back to plain `ssh`, ignoring the port, key and host key options.
+pub fn validate_destination(
+    dest: &StorageLocation,
+    ssh_config: Option<&SshConfig>,
+) -> Vec<ValidationCheck> {
+    let required = |field: &str, value: &str| {
+        let set = !value.trim().is_empty();
+        ValidationCheck {
+            check_type: CheckType::DestinationConfig,
+            passed: set,
+            message: if set {
+                format!("Destination {} is set", field)
+            } else {
+                format!("Destination {} must not be empty", field)
+            },
+            severity: CheckSeverity::Error,
+        }
+    };
+
+    match dest {
+        StorageLocation::Local { path } | StorageLocation::Mount { path, .. } => {
+            vec![required("path", path)]
+        }
+        StorageLocation::RemoteSsh { host, path, .. } => {
+            let configured = ssh_config.is_some();
+            vec![
+                required("host", host),
+                ssh_host_check("Destination", host),
+                required("path", path),
+                ValidationCheck {
+                    check_type: CheckType::DestinationConfig,
+                    passed: configured,
+                    message: if configured {
+                        "SSH settings are configured".to_string()
+                    } else {
+                        "SSH destination has no SSH settings; add them to set the port, key and host key checking".to_string()
+                    },
+                    severity: CheckSeverity::Warning,
+                },
+            ]
+        }
+        StorageLocation::RemoteRsync { host, module, .. } => {
+            vec![required("host", host), required("module", module)]
+        }
+    }
+}
@@ -0,0 +1,8 @@
+use crate::error::AppError;
+use crate::models::audit::AuditEntry;
+
+pub trait AuditLogRepository: Send + Sync {
+    fn record_entry(&self, entry: &AuditEntry) -> Result<(), AppError>;
+    /// Most recent entries first.
+    fn list_entries(&self, limit: usize) -> Result<Vec<AuditEntry>, AppError>;
+}
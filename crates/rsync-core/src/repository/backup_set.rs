@@ -0,0 +1,12 @@
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::backup_set::BackupSet;
+
+pub trait BackupSetRepository: Send + Sync {
+    fn create_set(&self, set: &BackupSet) -> Result<(), AppError>;
+    fn get_set(&self, id: &Uuid) -> Result<BackupSet, AppError>;
+    fn list_sets(&self) -> Result<Vec<BackupSet>, AppError>;
+    fn update_set(&self, set: &BackupSet) -> Result<(), AppError>;
+    fn delete_set(&self, id: &Uuid) -> Result<(), AppError>;
+}
@@ -1,14 +1,57 @@
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
 use crate::error::AppError;
 use crate::models::backup::BackupInvocation;
+use crate::models::itemize::StoredItemizedChanges;
+use crate::models::settings::PruneRecord;
 
 pub trait InvocationRepository: Send + Sync {
     fn create_invocation(&self, inv: &BackupInvocation) -> Result<(), AppError>;
     fn get_invocation(&self, id: &Uuid) -> Result<BackupInvocation, AppError>;
     fn list_invocations_for_job(&self, job_id: &Uuid) -> Result<Vec<BackupInvocation>, AppError>;
     fn list_all_invocations(&self) -> Result<Vec<BackupInvocation>, AppError>;
+    /// Invocations started at or after `since`, oldest first.
+    fn list_invocations_since(
+        &self,
+        since: &DateTime<Utc>,
+    ) -> Result<Vec<BackupInvocation>, AppError>;
+    /// Up to `limit` invocations started at or after `since` (all when
+    /// `None`), oldest first, resuming after `after`: the `started_at` and id
+    /// of the last invocation of the previous page.
+    fn list_invocations_page(
+        &self,
+        since: Option<&DateTime<Utc>>,
+        after: Option<(&DateTime<Utc>, &Uuid)>,
+        limit: usize,
+    ) -> Result<Vec<BackupInvocation>, AppError>;
     fn update_invocation(&self, inv: &BackupInvocation) -> Result<(), AppError>;
     fn delete_invocation(&self, id: &Uuid) -> Result<(), AppError>;
     fn delete_invocations_for_job(&self, job_id: &Uuid) -> Result<(), AppError>;
+    /// Set `log_file_path` for several invocations in a single transaction.
+    fn update_log_file_paths(&self, updates: &[(Uuid, Option<String>)]) -> Result<(), AppError>;
+    /// Set `log_file_path` to NULL, keeping the rest of the record.
+    fn clear_log_file_path(&self, id: &Uuid) -> Result<(), AppError>;
+    /// Insert the invocation, or overwrite the existing row with the same id.
+    fn upsert_invocation(&self, inv: &BackupInvocation) -> Result<(), AppError>;
+    /// Replace the itemized changes and summary stored for an invocation.
+    fn save_itemized_changes(
+        &self,
+        invocation_id: &Uuid,
+        itemized: &StoredItemizedChanges,
+    ) -> Result<(), AppError>;
+    /// Stored itemized changes in output order, or `None` if none were recorded.
+    fn get_itemized_changes(
+        &self,
+        invocation_id: &Uuid,
+    ) -> Result<Option<StoredItemizedChanges>, AppError>;
+    /// Keep what a retention prune removed so it can be restored later.
+    fn insert_prune_record(&self, record: &PruneRecord) -> Result<(), AppError>;
+    /// Remove and return the most recent prune record newer than `cutoff`.
+    fn take_latest_prune_record(
+        &self,
+        cutoff: &DateTime<Utc>,
+    ) -> Result<Option<PruneRecord>, AppError>;
+    /// Drop prune records at or before `cutoff`, returning how many went.
+    fn delete_prune_records_before(&self, cutoff: &DateTime<Utc>) -> Result<usize, AppError>;
 }
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
 use crate::error::AppError;
@@ -8,5 +9,22 @@ pub trait JobRepository: Send + Sync {
     fn get_job(&self, id: &Uuid) -> Result<JobDefinition, AppError>;
     fn list_jobs(&self) -> Result<Vec<JobDefinition>, AppError>;
     fn update_job(&self, job: &JobDefinition) -> Result<(), AppError>;
+    /// Update the job unless the stored `updated_at` is newer than
+    /// `seen_updated_at`, in which case it fails with `AppError::Conflict`.
+    fn update_job_if_unchanged(
+        &self,
+        job: &JobDefinition,
+        seen_updated_at: &DateTime<Utc>,
+    ) -> Result<(), AppError>;
     fn delete_job(&self, id: &Uuid) -> Result<(), AppError>;
+    /// Insert the job, or overwrite the existing row with the same id.
+    fn upsert_job(&self, job: &JobDefinition) -> Result<(), AppError>;
+    /// Make `jobs` the complete job set in one transaction: jobs with other
+    /// ids are deleted, the rest are upserted. Nothing changes on error.
+    fn replace_all_jobs(&self, jobs: &[JobDefinition]) -> Result<(), AppError>;
+    /// Move the invocations, snapshot records and verifications of
+    /// `remove_ids` to `keep_id`, then delete those jobs, in one transaction.
+    /// Fails with `AppError::NotFound` if any job is missing; nothing changes
+    /// on error.
+    fn merge_jobs(&self, keep_id: &Uuid, remove_ids: &[Uuid]) -> Result<(), AppError>;
 }
@@ -1,11 +1,21 @@
 use uuid::Uuid;
 
 use crate::error::AppError;
-use crate::models::backup::SnapshotRecord;
+use crate::models::backup::{SnapshotRecord, SnapshotVerification};
 
 pub trait SnapshotRepository: Send + Sync {
     fn create_snapshot(&self, snapshot: &SnapshotRecord) -> Result<(), AppError>;
     fn get_latest_snapshot_for_job(&self, job_id: &Uuid) -> Result<Option<SnapshotRecord>, AppError>;
     fn list_snapshots_for_job(&self, job_id: &Uuid) -> Result<Vec<SnapshotRecord>, AppError>;
+    fn list_all_snapshots(&self) -> Result<Vec<SnapshotRecord>, AppError>;
     fn delete_snapshot(&self, id: &Uuid) -> Result<(), AppError>;
+    fn set_snapshot_pinned(&self, id: &Uuid, pinned: bool) -> Result<(), AppError>;
+    /// Insert the snapshot, or overwrite the existing row with the same id.
+    fn upsert_snapshot(&self, snapshot: &SnapshotRecord) -> Result<(), AppError>;
+    /// Store the verification of a run's snapshot, replacing any earlier one.
+    fn record_verification(&self, verification: &SnapshotVerification) -> Result<(), AppError>;
+    fn get_verification(
+        &self,
+        invocation_id: &Uuid,
+    ) -> Result<Option<SnapshotVerification>, AppError>;
 }
@@ -0,0 +1,83 @@
+use std::sync::{Arc, Mutex};
+
+use rusqlite::Connection;
+
+use crate::database::sqlite::{
+    db_error, from_json, parse_datetime, parse_uuid, to_json, with_busy_retry,
+};
+use crate::error::AppError;
+use crate::models::audit::AuditEntry;
+use crate::repository::audit::AuditLogRepository;
+
+pub struct SqliteAuditLogRepository {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl SqliteAuditLogRepository {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+}
+
+impl AuditLogRepository for SqliteAuditLogRepository {
+    fn record_entry(&self, entry: &AuditEntry) -> Result<(), AppError> {
+        with_busy_retry(&self.conn, |conn| {
+            conn.execute(
+                "INSERT INTO audit_log (id, timestamp, action, target_id, detail, source)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                rusqlite::params![
+                    entry.id.to_string(),
+                    entry.timestamp.to_rfc3339(),
+                    to_json(&entry.action)?,
+                    entry.target_id.map(|id| id.to_string()),
+                    entry.detail,
+                    entry.source,
+                ],
+            )
+            .map_err(db_error)?;
+            Ok(())
+        })
+    }
+
+    fn list_entries(&self, limit: usize) -> Result<Vec<AuditEntry>, AppError> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        // Entries are only ever appended, so insertion order is time order
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, timestamp, action, target_id, detail, source
+                 FROM audit_log ORDER BY rowid DESC LIMIT ?1",
+            )
+            .map_err(db_error)?;
+
+        let rows = stmt
+            .query_map(rusqlite::params![limit as i64], |row| Ok(row_to_entry(row)))
+            .map_err(db_error)?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row.map_err(db_error)??);
+        }
+        Ok(entries)
+    }
+}
+
+fn row_to_entry(row: &rusqlite::Row) -> Result<AuditEntry, AppError> {
+    let id_str: String = row.get(0).map_err(db_error)?;
+    let timestamp_str: String = row.get(1).map_err(db_error)?;
+    let action_json: String = row.get(2).map_err(db_error)?;
+    let target_str: Option<String> = row.get(3).map_err(db_error)?;
+    let detail: String = row.get(4).map_err(db_error)?;
+    let source: Option<String> = row.get(5).map_err(db_error)?;
+
+    Ok(AuditEntry {
+        id: parse_uuid(&id_str)?,
+        timestamp: parse_datetime(&timestamp_str)?,
+        action: from_json(&action_json)?,
+        target_id: target_str.as_deref().map(parse_uuid).transpose()?,
+        detail,
+        source,
+    })
+}
@@ -0,0 +1,141 @@
+use std::sync::{Arc, Mutex};
+
+use rusqlite::Connection;
+use uuid::Uuid;
+
+use crate::database::sqlite::{
+    db_error, from_json, parse_datetime, parse_uuid, to_json, with_busy_retry,
+};
+use crate::error::AppError;
+use crate::models::backup_set::BackupSet;
+use crate::repository::backup_set::BackupSetRepository;
+
+pub struct SqliteBackupSetRepository {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl SqliteBackupSetRepository {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+}
+
+impl BackupSetRepository for SqliteBackupSetRepository {
+    fn create_set(&self, set: &BackupSet) -> Result<(), AppError> {
+        with_busy_retry(&self.conn, |conn| {
+            conn.execute(
+                "INSERT INTO backup_sets (id, name, job_ids, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                rusqlite::params![
+                    set.id.to_string(),
+                    set.name,
+                    to_json(&set.job_ids)?,
+                    set.created_at.to_rfc3339(),
+                    set.updated_at.to_rfc3339(),
+                ],
+            )
+            .map_err(db_error)?;
+            Ok(())
+        })
+    }
+
+    fn get_set(&self, id: &Uuid) -> Result<BackupSet, AppError> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, name, job_ids, created_at, updated_at
+                 FROM backup_sets WHERE id = ?1",
+            )
+            .map_err(db_error)?;
+
+        stmt.query_row(rusqlite::params![id.to_string()], |row| Ok(row_to_set(row)))
+            .map_err(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => {
+                    AppError::NotFound(format!("Backup set {} not found", id))
+                }
+                _ => AppError::DatabaseError(e.to_string()),
+            })?
+    }
+
+    fn list_sets(&self) -> Result<Vec<BackupSet>, AppError> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, name, job_ids, created_at, updated_at
+                 FROM backup_sets ORDER BY name",
+            )
+            .map_err(db_error)?;
+
+        let rows = stmt
+            .query_map([], |row| Ok(row_to_set(row)))
+            .map_err(db_error)?;
+
+        let mut sets = Vec::new();
+        for row in rows {
+            sets.push(row.map_err(db_error)??);
+        }
+        Ok(sets)
+    }
+
+    fn update_set(&self, set: &BackupSet) -> Result<(), AppError> {
+        with_busy_retry(&self.conn, |conn| {
+            let rows = conn
+                .execute(
+                    "UPDATE backup_sets SET name = ?1, job_ids = ?2, updated_at = ?3 WHERE id = ?4",
+                    rusqlite::params![
+                        set.name,
+                        to_json(&set.job_ids)?,
+                        set.updated_at.to_rfc3339(),
+                        set.id.to_string(),
+                    ],
+                )
+                .map_err(db_error)?;
+
+            if rows == 0 {
+                return Err(AppError::NotFound(format!(
+                    "Backup set {} not found",
+                    set.id
+                )));
+            }
+            Ok(())
+        })
+    }
+
+    fn delete_set(&self, id: &Uuid) -> Result<(), AppError> {
+        with_busy_retry(&self.conn, |conn| {
+            let rows = conn
+                .execute(
+                    "DELETE FROM backup_sets WHERE id = ?1",
+                    rusqlite::params![id.to_string()],
+                )
+                .map_err(db_error)?;
+
+            if rows == 0 {
+                return Err(AppError::NotFound(format!("Backup set {} not found", id)));
+            }
+            Ok(())
+        })
+    }
+}
+
+fn row_to_set(row: &rusqlite::Row) -> Result<BackupSet, AppError> {
+    let id_str: String = row.get(0).map_err(db_error)?;
+    let name: String = row.get(1).map_err(db_error)?;
+    let job_ids_json: String = row.get(2).map_err(db_error)?;
+    let created_str: String = row.get(3).map_err(db_error)?;
+    let updated_str: String = row.get(4).map_err(db_error)?;
+
+    Ok(BackupSet {
+        id: parse_uuid(&id_str)?,
+        name,
+        job_ids: from_json(&job_ids_json)?,
+        created_at: parse_datetime(&created_str)?,
+        updated_at: parse_datetime(&updated_str)?,
+    })
+}
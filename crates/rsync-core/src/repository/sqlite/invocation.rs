@@ -1,11 +1,16 @@
 use std::sync::{Arc, Mutex};
 
-use rusqlite::Connection;
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, OptionalExtension};
 use uuid::Uuid;
 
-use crate::database::sqlite::{from_json, parse_datetime, parse_uuid, to_json};
+use crate::database::sqlite::{
+    db_error, from_json, parse_datetime, parse_uuid, to_json, with_busy_retry,
+};
 use crate::error::AppError;
 use crate::models::backup::{BackupInvocation, ExecutionOutput, TransferStats};
+use crate::models::itemize::{ItemizeSummary, ItemizedChange, StoredItemizedChanges};
+use crate::models::settings::PruneRecord;
 use crate::repository::invocation::InvocationRepository;
 
 pub struct SqliteInvocationRepository {
@@ -20,38 +25,44 @@ impl SqliteInvocationRepository {
 
 impl InvocationRepository for SqliteInvocationRepository {
     fn create_invocation(&self, inv: &BackupInvocation) -> Result<(), AppError> {
-        let conn = self.conn.lock().map_err(|e| AppError::DatabaseError(e.to_string()))?;
-        conn.execute(
-            "INSERT INTO invocations (id, job_id, started_at, finished_at, status, bytes_transferred, files_transferred, total_files, snapshot_path, command_executed, exit_code, trigger, log_file_path)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
-            rusqlite::params![
-                inv.id.to_string(),
-                inv.job_id.to_string(),
-                inv.started_at.to_rfc3339(),
-                inv.finished_at.map(|dt| dt.to_rfc3339()),
-                to_json(&inv.status)?,
-                inv.transfer_stats.bytes_transferred as i64,
-                inv.transfer_stats.files_transferred as i64,
-                inv.transfer_stats.total_files as i64,
-                inv.execution_output.snapshot_path,
-                inv.execution_output.command_executed,
-                inv.execution_output.exit_code,
-                to_json(&inv.trigger)?,
-                inv.execution_output.log_file_path,
-            ],
-        )
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-        Ok(())
+        with_busy_retry(&self.conn, |conn| {
+            conn.execute(
+                "INSERT INTO invocations (id, job_id, started_at, finished_at, status, bytes_transferred, files_transferred, total_files, snapshot_path, command_executed, exit_code, trigger, log_file_path, rerun_of, files_deleted, warning, resumed_from, detailed_stats)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)",
+                rusqlite::params![
+                    inv.id.to_string(),
+                    inv.job_id.to_string(),
+                    inv.started_at.to_rfc3339(),
+                    inv.finished_at.map(|dt| dt.to_rfc3339()),
+                    to_json(&inv.status)?,
+                    inv.transfer_stats.bytes_transferred as i64,
+                    inv.transfer_stats.files_transferred as i64,
+                    inv.transfer_stats.total_files as i64,
+                    inv.execution_output.snapshot_path,
+                    inv.execution_output.command_executed,
+                    inv.execution_output.exit_code,
+                    to_json(&inv.trigger)?,
+                    inv.execution_output.log_file_path,
+                    inv.rerun_of.map(|id| id.to_string()),
+                    inv.transfer_stats.files_deleted as i64,
+                    inv.warning,
+                    inv.resumed_from.map(|id| id.to_string()),
+                    inv.transfer_stats.detailed.as_ref().map(to_json).transpose()?,
+                ],
+            )
+            .map_err(db_error)?;
+            Ok(())
+        })
     }
 
     fn get_invocation(&self, id: &Uuid) -> Result<BackupInvocation, AppError> {
         let conn = self.conn.lock().map_err(|e| AppError::DatabaseError(e.to_string()))?;
         let mut stmt = conn
             .prepare(
-                "SELECT id, job_id, started_at, finished_at, status, bytes_transferred, files_transferred, total_files, snapshot_path, command_executed, exit_code, trigger, log_file_path
+                "SELECT id, job_id, started_at, finished_at, status, bytes_transferred, files_transferred, total_files, snapshot_path, command_executed, exit_code, trigger, log_file_path, rerun_of, files_deleted, warning, resumed_from, detailed_stats
                  FROM invocations WHERE id = ?1",
             )
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            .map_err(db_error)?;
 
         stmt.query_row(rusqlite::params![id.to_string()], |row| {
             Ok(row_to_invocation(row))
@@ -68,20 +79,20 @@ impl InvocationRepository for SqliteInvocationRepository {
         let conn = self.conn.lock().map_err(|e| AppError::DatabaseError(e.to_string()))?;
         let mut stmt = conn
             .prepare(
-                "SELECT id, job_id, started_at, finished_at, status, bytes_transferred, files_transferred, total_files, snapshot_path, command_executed, exit_code, trigger, log_file_path
+                "SELECT id, job_id, started_at, finished_at, status, bytes_transferred, files_transferred, total_files, snapshot_path, command_executed, exit_code, trigger, log_file_path, rerun_of, files_deleted, warning, resumed_from, detailed_stats
                  FROM invocations WHERE job_id = ?1 ORDER BY started_at DESC",
             )
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            .map_err(db_error)?;
 
         let rows = stmt
             .query_map(rusqlite::params![job_id.to_string()], |row| {
                 Ok(row_to_invocation(row))
             })
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            .map_err(db_error)?;
 
         let mut invocations = Vec::new();
         for row in rows {
-            let inv = row.map_err(|e| AppError::DatabaseError(e.to_string()))??;
+            let inv = row.map_err(db_error)??;
             invocations.push(inv);
         }
         Ok(invocations)
@@ -91,95 +102,393 @@ impl InvocationRepository for SqliteInvocationRepository {
         let conn = self.conn.lock().map_err(|e| AppError::DatabaseError(e.to_string()))?;
         let mut stmt = conn
             .prepare(
-                "SELECT id, job_id, started_at, finished_at, status, bytes_transferred, files_transferred, total_files, snapshot_path, command_executed, exit_code, trigger, log_file_path
+                "SELECT id, job_id, started_at, finished_at, status, bytes_transferred, files_transferred, total_files, snapshot_path, command_executed, exit_code, trigger, log_file_path, rerun_of, files_deleted, warning, resumed_from, detailed_stats
                  FROM invocations ORDER BY started_at DESC",
             )
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            .map_err(db_error)?;
 
         let rows = stmt
             .query_map([], |row| Ok(row_to_invocation(row)))
+            .map_err(db_error)?;
+
+        let mut invocations = Vec::new();
+        for row in rows {
+            let inv = row.map_err(db_error)??;
+            invocations.push(inv);
+        }
+        Ok(invocations)
+    }
+
+    fn list_invocations_since(
+        &self,
+        since: &DateTime<Utc>,
+    ) -> Result<Vec<BackupInvocation>, AppError> {
+        let conn = self
+            .conn
+            .lock()
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, job_id, started_at, finished_at, status, bytes_transferred, files_transferred, total_files, snapshot_path, command_executed, exit_code, trigger, log_file_path, rerun_of, files_deleted, warning, resumed_from, detailed_stats
+                 FROM invocations WHERE started_at >= ?1 ORDER BY started_at ASC",
+            )
+            .map_err(db_error)?;
+
+        let rows = stmt
+            .query_map(rusqlite::params![since.to_rfc3339()], |row| {
+                Ok(row_to_invocation(row))
+            })
+            .map_err(db_error)?;
 
         let mut invocations = Vec::new();
         for row in rows {
-            let inv = row.map_err(|e| AppError::DatabaseError(e.to_string()))??;
+            let inv = row.map_err(db_error)??;
             invocations.push(inv);
         }
         Ok(invocations)
     }
 
+    fn list_invocations_page(
+        &self,
+        since: Option<&DateTime<Utc>>,
+        after: Option<(&DateTime<Utc>, &Uuid)>,
+        limit: usize,
+    ) -> Result<Vec<BackupInvocation>, AppError> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        // Ordering by id as well keeps runs that started together on one side
+        // of a page boundary
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, job_id, started_at, finished_at, status, bytes_transferred, files_transferred, total_files, snapshot_path, command_executed, exit_code, trigger, log_file_path, rerun_of, files_deleted, warning, resumed_from, detailed_stats
+                 FROM invocations
+                 WHERE (?1 IS NULL OR started_at >= ?1)
+                   AND (?2 IS NULL OR started_at > ?2 OR (started_at = ?2 AND id > ?3))
+                 ORDER BY started_at ASC, id ASC LIMIT ?4",
+            )
+            .map_err(db_error)?;
+
+        let rows = stmt
+            .query_map(
+                rusqlite::params![
+                    since.map(|dt| dt.to_rfc3339()),
+                    after.map(|(started_at, _)| started_at.to_rfc3339()),
+                    after.map(|(_, id)| id.to_string()),
+                    limit as i64,
+                ],
+                |row| Ok(row_to_invocation(row)),
+            )
+            .map_err(db_error)?;
+
+        let mut invocations = Vec::new();
+        for row in rows {
+            invocations.push(row.map_err(db_error)??);
+        }
+        Ok(invocations)
+    }
+
     fn update_invocation(&self, inv: &BackupInvocation) -> Result<(), AppError> {
-        let conn = self.conn.lock().map_err(|e| AppError::DatabaseError(e.to_string()))?;
-        let rows = conn
-            .execute(
-                "UPDATE invocations SET finished_at = ?1, status = ?2, bytes_transferred = ?3, files_transferred = ?4, total_files = ?5, snapshot_path = ?6, exit_code = ?7, log_file_path = ?8
-                 WHERE id = ?9",
+        with_busy_retry(&self.conn, |conn| {
+            let rows = conn
+                .execute(
+                    "UPDATE invocations SET finished_at = ?1, status = ?2, bytes_transferred = ?3, files_transferred = ?4, total_files = ?5, snapshot_path = ?6, exit_code = ?7, log_file_path = ?8, files_deleted = ?9, warning = ?10, detailed_stats = ?11
+                     WHERE id = ?12",
+                    rusqlite::params![
+                        inv.finished_at.map(|dt| dt.to_rfc3339()),
+                        to_json(&inv.status)?,
+                        inv.transfer_stats.bytes_transferred as i64,
+                        inv.transfer_stats.files_transferred as i64,
+                        inv.transfer_stats.total_files as i64,
+                        inv.execution_output.snapshot_path,
+                        inv.execution_output.exit_code,
+                        inv.execution_output.log_file_path,
+                        inv.transfer_stats.files_deleted as i64,
+                        inv.warning,
+                        inv.transfer_stats.detailed.as_ref().map(to_json).transpose()?,
+                        inv.id.to_string(),
+                    ],
+                )
+                .map_err(db_error)?;
+
+            if rows == 0 {
+                return Err(AppError::NotFound(format!(
+                    "Invocation {} not found",
+                    inv.id
+                )));
+            }
+            Ok(())
+        })
+    }
+
+    fn delete_invocation(&self, id: &Uuid) -> Result<(), AppError> {
+        with_busy_retry(&self.conn, |conn| {
+            let rows = conn
+                .execute(
+                    "DELETE FROM invocations WHERE id = ?1",
+                    rusqlite::params![id.to_string()],
+                )
+                .map_err(db_error)?;
+
+            if rows == 0 {
+                return Err(AppError::NotFound(format!("Invocation {} not found", id)));
+            }
+            Ok(())
+        })
+    }
+
+    fn clear_log_file_path(&self, id: &Uuid) -> Result<(), AppError> {
+        with_busy_retry(&self.conn, |conn| {
+            let rows = conn
+                .execute(
+                    "UPDATE invocations SET log_file_path = NULL WHERE id = ?1",
+                    rusqlite::params![id.to_string()],
+                )
+                .map_err(db_error)?;
+
+            if rows == 0 {
+                return Err(AppError::NotFound(format!("Invocation {} not found", id)));
+            }
+            Ok(())
+        })
+    }
+
+    fn delete_invocations_for_job(&self, job_id: &Uuid) -> Result<(), AppError> {
+        with_busy_retry(&self.conn, |conn| {
+            conn.execute(
+                "DELETE FROM invocations WHERE job_id = ?1",
+                rusqlite::params![job_id.to_string()],
+            )
+            .map_err(db_error)?;
+            Ok(())
+        })
+    }
+
+    fn upsert_invocation(&self, inv: &BackupInvocation) -> Result<(), AppError> {
+        with_busy_retry(&self.conn, |conn| {
+            conn.execute(
+                "INSERT INTO invocations (id, job_id, started_at, finished_at, status, bytes_transferred, files_transferred, total_files, snapshot_path, command_executed, exit_code, trigger, log_file_path, rerun_of, files_deleted, warning, resumed_from, detailed_stats)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)
+                 ON CONFLICT(id) DO UPDATE SET job_id = excluded.job_id, started_at = excluded.started_at, finished_at = excluded.finished_at, status = excluded.status, bytes_transferred = excluded.bytes_transferred, files_transferred = excluded.files_transferred, total_files = excluded.total_files, snapshot_path = excluded.snapshot_path, command_executed = excluded.command_executed, exit_code = excluded.exit_code, trigger = excluded.trigger, log_file_path = excluded.log_file_path, rerun_of = excluded.rerun_of, files_deleted = excluded.files_deleted, warning = excluded.warning, resumed_from = excluded.resumed_from, detailed_stats = excluded.detailed_stats",
                 rusqlite::params![
+                    inv.id.to_string(),
+                    inv.job_id.to_string(),
+                    inv.started_at.to_rfc3339(),
                     inv.finished_at.map(|dt| dt.to_rfc3339()),
                     to_json(&inv.status)?,
                     inv.transfer_stats.bytes_transferred as i64,
                     inv.transfer_stats.files_transferred as i64,
                     inv.transfer_stats.total_files as i64,
                     inv.execution_output.snapshot_path,
+                    inv.execution_output.command_executed,
                     inv.execution_output.exit_code,
+                    to_json(&inv.trigger)?,
                     inv.execution_output.log_file_path,
-                    inv.id.to_string(),
+                    inv.rerun_of.map(|id| id.to_string()),
+                    inv.transfer_stats.files_deleted as i64,
+                    inv.warning,
+                    inv.resumed_from.map(|id| id.to_string()),
+                    inv.transfer_stats.detailed.as_ref().map(to_json).transpose()?,
                 ],
             )
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            .map_err(db_error)?;
+            Ok(())
+        })
+    }
 
-        if rows == 0 {
-            return Err(AppError::NotFound(format!(
-                "Invocation {} not found",
-                inv.id
-            )));
-        }
-        Ok(())
+    fn update_log_file_paths(&self, updates: &[(Uuid, Option<String>)]) -> Result<(), AppError> {
+        with_busy_retry(&self.conn, |conn| {
+            let tx = conn.transaction().map_err(db_error)?;
+            for (id, path) in updates {
+                let rows = tx
+                    .execute(
+                        "UPDATE invocations SET log_file_path = ?1 WHERE id = ?2",
+                        rusqlite::params![path, id.to_string()],
+                    )
+                    .map_err(db_error)?;
+                if rows == 0 {
+                    // Dropping the transaction rolls back the earlier updates
+                    return Err(AppError::NotFound(format!("Invocation {} not found", id)));
+                }
+            }
+            tx.commit().map_err(db_error)
+        })
     }
 
-    fn delete_invocation(&self, id: &Uuid) -> Result<(), AppError> {
-        let conn = self.conn.lock().map_err(|e| AppError::DatabaseError(e.to_string()))?;
-        let rows = conn
-            .execute(
-                "DELETE FROM invocations WHERE id = ?1",
-                rusqlite::params![id.to_string()],
+    fn save_itemized_changes(
+        &self,
+        invocation_id: &Uuid,
+        itemized: &StoredItemizedChanges,
+    ) -> Result<(), AppError> {
+        with_busy_retry(&self.conn, |conn| {
+            let tx = conn.transaction().map_err(db_error)?;
+            let id = invocation_id.to_string();
+            tx.execute(
+                "DELETE FROM itemized_changes WHERE invocation_id = ?1",
+                rusqlite::params![id],
             )
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            .map_err(db_error)?;
 
-        if rows == 0 {
-            return Err(AppError::NotFound(format!(
-                "Invocation {} not found",
-                id
-            )));
-        }
-        Ok(())
+            let summary = &itemized.summary;
+            tx.execute(
+                "INSERT OR REPLACE INTO itemize_summaries (invocation_id, total, created, updated, deleted, stored, truncated)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                rusqlite::params![
+                    id,
+                    summary.total as i64,
+                    summary.created as i64,
+                    summary.updated as i64,
+                    summary.deleted as i64,
+                    summary.stored as i64,
+                    summary.truncated as i64,
+                ],
+            )
+            .map_err(db_error)?;
+
+            {
+                let mut stmt = tx
+                    .prepare(
+                        "INSERT INTO itemized_changes (invocation_id, seq, change) VALUES (?1, ?2, ?3)",
+                    )
+                    .map_err(db_error)?;
+                for (seq, change) in itemized.changes.iter().enumerate() {
+                    stmt.execute(rusqlite::params![id, seq as i64, to_json(change)?])
+                        .map_err(db_error)?;
+                }
+            }
+
+            tx.commit().map_err(db_error)
+        })
     }
 
-    fn delete_invocations_for_job(&self, job_id: &Uuid) -> Result<(), AppError> {
+    fn get_itemized_changes(
+        &self,
+        invocation_id: &Uuid,
+    ) -> Result<Option<StoredItemizedChanges>, AppError> {
         let conn = self.conn.lock().map_err(|e| AppError::DatabaseError(e.to_string()))?;
-        conn.execute(
-            "DELETE FROM invocations WHERE job_id = ?1",
-            rusqlite::params![job_id.to_string()],
-        )
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-        Ok(())
+        let id = invocation_id.to_string();
+        let summary = conn.query_row(
+            "SELECT total, created, updated, deleted, stored, truncated
+                 FROM itemize_summaries WHERE invocation_id = ?1",
+            rusqlite::params![id],
+            |row| {
+                Ok(ItemizeSummary {
+                    total: row.get::<_, i64>(0)? as u64,
+                    created: row.get::<_, i64>(1)? as u64,
+                    updated: row.get::<_, i64>(2)? as u64,
+                    deleted: row.get::<_, i64>(3)? as u64,
+                    stored: row.get::<_, i64>(4)? as u64,
+                    truncated: row.get::<_, i64>(5)? as u64,
+                })
+            },
+        );
+        let summary = match summary {
+            Ok(summary) => summary,
+            Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(None),
+            Err(e) => return Err(AppError::DatabaseError(e.to_string())),
+        };
+
+        let mut stmt = conn
+            .prepare("SELECT change FROM itemized_changes WHERE invocation_id = ?1 ORDER BY seq")
+            .map_err(db_error)?;
+        let rows = stmt
+            .query_map(rusqlite::params![id], |row| row.get::<_, String>(0))
+            .map_err(db_error)?;
+
+        let mut changes = Vec::new();
+        for row in rows {
+            let json = row.map_err(db_error)?;
+            changes.push(from_json::<ItemizedChange>(&json)?);
+        }
+        Ok(Some(StoredItemizedChanges { summary, changes }))
+    }
+    fn insert_prune_record(&self, record: &PruneRecord) -> Result<(), AppError> {
+        with_busy_retry(&self.conn, |conn| {
+            conn.execute(
+                "INSERT INTO prune_records (pruned_at, invocations, snapshots)
+                 VALUES (?1, ?2, ?3)",
+                rusqlite::params![
+                    record.pruned_at.to_rfc3339(),
+                    to_json(&record.invocations)?,
+                    to_json(&record.snapshots)?,
+                ],
+            )
+            .map_err(db_error)?;
+            Ok(())
+        })
+    }
+
+    fn take_latest_prune_record(
+        &self,
+        cutoff: &DateTime<Utc>,
+    ) -> Result<Option<PruneRecord>, AppError> {
+        with_busy_retry(&self.conn, |conn| {
+            let tx = conn.transaction().map_err(db_error)?;
+            let row = tx
+                .query_row(
+                    "SELECT id, pruned_at, invocations, snapshots FROM prune_records
+                     WHERE pruned_at > ?1 ORDER BY pruned_at DESC, id DESC LIMIT 1",
+                    rusqlite::params![cutoff.to_rfc3339()],
+                    |row| {
+                        Ok((
+                            row.get::<_, i64>(0)?,
+                            row.get::<_, String>(1)?,
+                            row.get::<_, String>(2)?,
+                            row.get::<_, String>(3)?,
+                        ))
+                    },
+                )
+                .optional()
+                .map_err(db_error)?;
+            let Some((id, pruned_at, invocations, snapshots)) = row else {
+                return Ok(None);
+            };
+            tx.execute(
+                "DELETE FROM prune_records WHERE id = ?1",
+                rusqlite::params![id],
+            )
+            .map_err(db_error)?;
+            let record = PruneRecord {
+                pruned_at: parse_datetime(&pruned_at)?,
+                invocations: from_json(&invocations)?,
+                snapshots: from_json(&snapshots)?,
+            };
+            tx.commit().map_err(db_error)?;
+            Ok(Some(record))
+        })
+    }
+
+    fn delete_prune_records_before(&self, cutoff: &DateTime<Utc>) -> Result<usize, AppError> {
+        with_busy_retry(&self.conn, |conn| {
+            conn.execute(
+                "DELETE FROM prune_records WHERE pruned_at <= ?1",
+                rusqlite::params![cutoff.to_rfc3339()],
+            )
+            .map_err(db_error)
+        })
     }
 }
 
 fn row_to_invocation(row: &rusqlite::Row) -> Result<BackupInvocation, AppError> {
-    let id_str: String = row.get(0).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let job_id_str: String = row.get(1).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let started_str: String = row.get(2).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let finished_str: Option<String> = row.get(3).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let status_json: String = row.get(4).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let bytes: i64 = row.get(5).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let files: i64 = row.get(6).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let total: i64 = row.get(7).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let snapshot_path: Option<String> = row.get(8).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let command: String = row.get(9).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let exit_code: Option<i32> = row.get(10).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let trigger_json: String = row.get(11).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let log_file_path: Option<String> = row.get(12).map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let id_str: String = row.get(0).map_err(db_error)?;
+    let job_id_str: String = row.get(1).map_err(db_error)?;
+    let started_str: String = row.get(2).map_err(db_error)?;
+    let finished_str: Option<String> = row.get(3).map_err(db_error)?;
+    let status_json: String = row.get(4).map_err(db_error)?;
+    let bytes: i64 = row.get(5).map_err(db_error)?;
+    let files: i64 = row.get(6).map_err(db_error)?;
+    let total: i64 = row.get(7).map_err(db_error)?;
+    let snapshot_path: Option<String> = row.get(8).map_err(db_error)?;
+    let command: String = row.get(9).map_err(db_error)?;
+    let exit_code: Option<i32> = row.get(10).map_err(db_error)?;
+    let trigger_json: String = row.get(11).map_err(db_error)?;
+    let log_file_path: Option<String> = row.get(12).map_err(db_error)?;
+    let rerun_of: Option<String> = row.get(13).map_err(db_error)?;
+    let deleted: i64 = row.get(14).map_err(db_error)?;
+    let warning: Option<String> = row.get(15).map_err(db_error)?;
+    let resumed_from: Option<String> = row.get(16).map_err(db_error)?;
+    let detailed_json: Option<String> = row.get(17).map_err(db_error)?;
 
     Ok(BackupInvocation {
         id: parse_uuid(&id_str)?,
@@ -188,10 +497,15 @@ fn row_to_invocation(row: &rusqlite::Row) -> Result<BackupInvocation, AppError>
         finished_at: finished_str.as_deref().map(parse_datetime).transpose()?,
         status: from_json(&status_json)?,
         trigger: from_json(&trigger_json)?,
+        rerun_of: rerun_of.as_deref().map(parse_uuid).transpose()?,
+        resumed_from: resumed_from.as_deref().map(parse_uuid).transpose()?,
+        warning,
         transfer_stats: TransferStats {
             bytes_transferred: bytes as u64,
             files_transferred: files as u64,
             total_files: total as u64,
+            files_deleted: deleted as u64,
+            detailed: detailed_json.as_deref().map(from_json).transpose()?,
         },
         execution_output: ExecutionOutput {
             command_executed: command,
@@ -1,11 +1,15 @@
+use std::collections::HashSet;
 use std::sync::{Arc, Mutex};
 
-use rusqlite::Connection;
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, TransactionBehavior};
 use uuid::Uuid;
 
-use crate::database::sqlite::{from_json, parse_datetime, parse_uuid, to_json};
+use crate::database::sqlite::{
+    db_error, from_json, parse_datetime, parse_uuid, to_json, with_busy_retry,
+};
 use crate::error::AppError;
-use crate::models::job::{JobDefinition, TransferConfig};
+use crate::models::job::{JobAppearance, JobDefinition, JobRunPolicy, JobSafety, TransferConfig};
 use crate::repository::job::JobRepository;
 
 pub struct SqliteJobRepository {
@@ -20,37 +24,52 @@ impl SqliteJobRepository {
 
 impl JobRepository for SqliteJobRepository {
     fn create_job(&self, job: &JobDefinition) -> Result<(), AppError> {
-        let conn = self.conn.lock().map_err(|e| AppError::DatabaseError(e.to_string()))?;
-        conn.execute(
-            "INSERT INTO jobs (id, name, description, source, destination, backup_mode, options, ssh_config, schedule, enabled, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
-            rusqlite::params![
-                job.id.to_string(),
-                job.name,
-                job.description,
-                to_json(&job.transfer.source)?,
-                to_json(&job.transfer.destination)?,
-                to_json(&job.transfer.backup_mode)?,
-                to_json(&job.options)?,
-                job.ssh_config.as_ref().map(|s| to_json(s)).transpose()?,
-                job.schedule.as_ref().map(|s| to_json(s)).transpose()?,
-                job.enabled as i32,
-                job.created_at.to_rfc3339(),
-                job.updated_at.to_rfc3339(),
-            ],
-        )
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-        Ok(())
+        with_busy_retry(&self.conn, |conn| {
+            conn.execute(
+                "INSERT INTO jobs (id, name, description, source, destination, backup_mode, options, ssh_config, schedule, enabled, created_at, updated_at, env, max_runtime_mins, color, icon, success_exit_codes, notify_on, process_priority, create_destination, run_on_startup, change_alarm, skip_if_unchanged, expect_nonempty_destination, parallel_split, verify_after_snapshot)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26)",
+                rusqlite::params![
+                    job.id.to_string(),
+                    job.name,
+                    job.description,
+                    to_json(&job.transfer.source)?,
+                    to_json(&job.transfer.destination)?,
+                    to_json(&job.transfer.backup_mode)?,
+                    to_json(&job.options)?,
+                    job.ssh_config.as_ref().map(|s| to_json(s)).transpose()?,
+                    job.schedule.as_ref().map(|s| to_json(s)).transpose()?,
+                    job.enabled as i32,
+                    job.created_at.to_rfc3339(),
+                    job.updated_at.to_rfc3339(),
+                    to_json(&job.run_policy.env)?,
+                    job.run_policy.max_runtime_mins,
+                    job.appearance.color,
+                    job.appearance.icon,
+                    to_json(&job.run_policy.success_exit_codes)?,
+                    to_json(&job.run_policy.notify_on)?,
+                    job.run_policy.process_priority.as_ref().map(to_json).transpose()?,
+                    job.run_policy.create_destination as i32,
+                    job.run_policy.run_on_startup as i32,
+                    job.safety.change_alarm.as_ref().map(to_json).transpose()?,
+                    job.safety.skip_if_unchanged as i32,
+                    job.safety.expect_nonempty_destination as i32,
+                job.run_policy.parallel_split,
+                job.safety.verify_after_snapshot as i32,
+                ],
+            )
+            .map_err(db_error)?;
+            Ok(())
+        })
     }
 
     fn get_job(&self, id: &Uuid) -> Result<JobDefinition, AppError> {
         let conn = self.conn.lock().map_err(|e| AppError::DatabaseError(e.to_string()))?;
         let mut stmt = conn
             .prepare(
-                "SELECT id, name, description, source, destination, backup_mode, options, ssh_config, schedule, enabled, created_at, updated_at
+                "SELECT id, name, description, source, destination, backup_mode, options, ssh_config, schedule, enabled, created_at, updated_at, env, max_runtime_mins, color, icon, success_exit_codes, notify_on, process_priority, create_destination, run_on_startup, change_alarm, skip_if_unchanged, expect_nonempty_destination, parallel_split, verify_after_snapshot
                  FROM jobs WHERE id = ?1",
             )
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            .map_err(db_error)?;
 
         stmt.query_row(rusqlite::params![id.to_string()], |row| {
             Ok(row_to_job(row))
@@ -67,77 +86,167 @@ impl JobRepository for SqliteJobRepository {
         let conn = self.conn.lock().map_err(|e| AppError::DatabaseError(e.to_string()))?;
         let mut stmt = conn
             .prepare(
-                "SELECT id, name, description, source, destination, backup_mode, options, ssh_config, schedule, enabled, created_at, updated_at
+                "SELECT id, name, description, source, destination, backup_mode, options, ssh_config, schedule, enabled, created_at, updated_at, env, max_runtime_mins, color, icon, success_exit_codes, notify_on, process_priority, create_destination, run_on_startup, change_alarm, skip_if_unchanged, expect_nonempty_destination, parallel_split, verify_after_snapshot
                  FROM jobs ORDER BY name",
             )
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            .map_err(db_error)?;
 
         let rows = stmt
             .query_map([], |row| Ok(row_to_job(row)))
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            .map_err(db_error)?;
 
         let mut jobs = Vec::new();
         for row in rows {
-            let job = row.map_err(|e| AppError::DatabaseError(e.to_string()))??;
+            let job = row.map_err(db_error)??;
             jobs.push(job);
         }
         Ok(jobs)
     }
 
     fn update_job(&self, job: &JobDefinition) -> Result<(), AppError> {
-        let conn = self.conn.lock().map_err(|e| AppError::DatabaseError(e.to_string()))?;
-        let rows = conn
-            .execute(
-                "UPDATE jobs SET name = ?1, description = ?2, source = ?3, destination = ?4, backup_mode = ?5, options = ?6, ssh_config = ?7, schedule = ?8, enabled = ?9, updated_at = ?10
-                 WHERE id = ?11",
-                rusqlite::params![
-                    job.name,
-                    job.description,
-                    to_json(&job.transfer.source)?,
-                    to_json(&job.transfer.destination)?,
-                    to_json(&job.transfer.backup_mode)?,
-                    to_json(&job.options)?,
-                    job.ssh_config.as_ref().map(|s| to_json(s)).transpose()?,
-                    job.schedule.as_ref().map(|s| to_json(s)).transpose()?,
-                    job.enabled as i32,
-                    job.updated_at.to_rfc3339(),
-                    job.id.to_string(),
-                ],
-            )
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        with_busy_retry(&self.conn, |conn| update_job_row(conn, job))
+    }
 
-        if rows == 0 {
-            return Err(AppError::NotFound(format!("Job {} not found", job.id)));
-        }
-        Ok(())
+    fn update_job_if_unchanged(
+        &self,
+        job: &JobDefinition,
+        seen_updated_at: &DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        with_busy_retry(&self.conn, |conn| {
+            // IMMEDIATE takes the write lock up front, so another process cannot
+            // update the job between the version check and the write
+            let tx = conn
+                .transaction_with_behavior(TransactionBehavior::Immediate)
+                .map_err(db_error)?;
+            let stored: String = tx
+                .query_row(
+                    "SELECT updated_at FROM jobs WHERE id = ?1",
+                    rusqlite::params![job.id.to_string()],
+                    |row| row.get(0),
+                )
+                .map_err(|e| match e {
+                    rusqlite::Error::QueryReturnedNoRows => {
+                        AppError::NotFound(format!("Job {} not found", job.id))
+                    }
+                    _ => AppError::DatabaseError(e.to_string()),
+                })?;
+            if parse_datetime(&stored)? > *seen_updated_at {
+                return Err(AppError::Conflict(format!(
+                    "Job '{}' was changed elsewhere; reload it and try again",
+                    job.name
+                )));
+            }
+            update_job_row(&tx, job)?;
+            tx.commit().map_err(db_error)
+        })
     }
 
     fn delete_job(&self, id: &Uuid) -> Result<(), AppError> {
-        let conn = self.conn.lock().map_err(|e| AppError::DatabaseError(e.to_string()))?;
-        let rows = conn
-            .execute("DELETE FROM jobs WHERE id = ?1", rusqlite::params![id.to_string()])
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        with_busy_retry(&self.conn, |conn| {
+            let rows = conn
+                .execute(
+                    "DELETE FROM jobs WHERE id = ?1",
+                    rusqlite::params![id.to_string()],
+                )
+                .map_err(db_error)?;
 
-        if rows == 0 {
-            return Err(AppError::NotFound(format!("Job {} not found", id)));
-        }
-        Ok(())
+            if rows == 0 {
+                return Err(AppError::NotFound(format!("Job {} not found", id)));
+            }
+            Ok(())
+        })
+    }
+
+    fn upsert_job(&self, job: &JobDefinition) -> Result<(), AppError> {
+        with_busy_retry(&self.conn, |conn| upsert_job_row(conn, job))
+    }
+
+    fn replace_all_jobs(&self, jobs: &[JobDefinition]) -> Result<(), AppError> {
+        with_busy_retry(&self.conn, |conn| {
+            let tx = conn.transaction().map_err(db_error)?;
+
+            // Only jobs leaving the set are deleted, so kept ids keep their
+            // history and snapshots
+            let keep: HashSet<String> = jobs.iter().map(|job| job.id.to_string()).collect();
+            let existing: Vec<String> = {
+                let mut stmt = tx.prepare("SELECT id FROM jobs").map_err(db_error)?;
+                let rows = stmt.query_map([], |row| row.get(0)).map_err(db_error)?;
+                rows.collect::<Result<_, _>>().map_err(db_error)?
+            };
+            for id in existing.iter().filter(|id| !keep.contains(*id)) {
+                tx.execute("DELETE FROM jobs WHERE id = ?1", rusqlite::params![id])
+                    .map_err(db_error)?;
+            }
+            for job in jobs {
+                // Dropping the transaction on error rolls everything back
+                upsert_job_row(&tx, job)?;
+            }
+            tx.commit().map_err(db_error)
+        })
+    }
+    fn merge_jobs(&self, keep_id: &Uuid, remove_ids: &[Uuid]) -> Result<(), AppError> {
+        with_busy_retry(&self.conn, |conn| {
+            let tx = conn
+                .transaction_with_behavior(TransactionBehavior::Immediate)
+                .map_err(db_error)?;
+            for id in std::iter::once(keep_id).chain(remove_ids) {
+                let exists: bool = tx
+                    .query_row(
+                        "SELECT EXISTS(SELECT 1 FROM jobs WHERE id = ?1)",
+                        rusqlite::params![id.to_string()],
+                        |row| row.get(0),
+                    )
+                    .map_err(db_error)?;
+                if !exists {
+                    return Err(AppError::NotFound(format!("Job {} not found", id)));
+                }
+            }
+            let keep = keep_id.to_string();
+            for id in remove_ids {
+                let removed = id.to_string();
+                for table in ["invocations", "snapshots", "snapshot_verifications"] {
+                    tx.execute(
+                        &format!("UPDATE {} SET job_id = ?1 WHERE job_id = ?2", table),
+                        rusqlite::params![keep, removed],
+                    )
+                    .map_err(db_error)?;
+                }
+                // Run statistics stay with the removed job and go with it
+                tx.execute("DELETE FROM jobs WHERE id = ?1", rusqlite::params![removed])
+                    .map_err(db_error)?;
+            }
+            tx.commit().map_err(db_error)
+        })
     }
 }
 
 fn row_to_job(row: &rusqlite::Row) -> Result<JobDefinition, AppError> {
-    let id_str: String = row.get(0).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let name: String = row.get(1).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let description: Option<String> = row.get(2).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let source_json: String = row.get(3).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let dest_json: String = row.get(4).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let mode_json: String = row.get(5).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let options_json: String = row.get(6).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let ssh_json: Option<String> = row.get(7).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let schedule_json: Option<String> = row.get(8).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let enabled: i32 = row.get(9).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let created_str: String = row.get(10).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let updated_str: String = row.get(11).map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let id_str: String = row.get(0).map_err(db_error)?;
+    let name: String = row.get(1).map_err(db_error)?;
+    let description: Option<String> = row.get(2).map_err(db_error)?;
+    let source_json: String = row.get(3).map_err(db_error)?;
+    let dest_json: String = row.get(4).map_err(db_error)?;
+    let mode_json: String = row.get(5).map_err(db_error)?;
+    let options_json: String = row.get(6).map_err(db_error)?;
+    let ssh_json: Option<String> = row.get(7).map_err(db_error)?;
+    let schedule_json: Option<String> = row.get(8).map_err(db_error)?;
+    let enabled: i32 = row.get(9).map_err(db_error)?;
+    let created_str: String = row.get(10).map_err(db_error)?;
+    let updated_str: String = row.get(11).map_err(db_error)?;
+    let env_json: String = row.get(12).map_err(db_error)?;
+    let max_runtime_mins: Option<u32> = row.get(13).map_err(db_error)?;
+    let color: Option<String> = row.get(14).map_err(db_error)?;
+    let icon: Option<String> = row.get(15).map_err(db_error)?;
+    let success_codes_json: String = row.get(16).map_err(db_error)?;
+    let notify_on_json: String = row.get(17).map_err(db_error)?;
+    let priority_json: Option<String> = row.get(18).map_err(db_error)?;
+    let create_destination: i32 = row.get(19).map_err(db_error)?;
+    let run_on_startup: i32 = row.get(20).map_err(db_error)?;
+    let alarm_json: Option<String> = row.get(21).map_err(db_error)?;
+    let skip_if_unchanged: i32 = row.get(22).map_err(db_error)?;
+    let expect_nonempty_destination: i32 = row.get(23).map_err(db_error)?;
+    let parallel_split: Option<u32> = row.get(24).map_err(db_error)?;
+    let verify_after_snapshot: i32 = row.get(25).map_err(db_error)?;
 
     Ok(JobDefinition {
         id: parse_uuid(&id_str)?,
@@ -150,9 +259,107 @@ fn row_to_job(row: &rusqlite::Row) -> Result<JobDefinition, AppError> {
         },
         options: from_json(&options_json)?,
         ssh_config: ssh_json.as_deref().map(from_json).transpose()?,
+        appearance: JobAppearance { color, icon },
+        run_policy: JobRunPolicy {
+            env: from_json(&env_json)?,
+            max_runtime_mins,
+            success_exit_codes: from_json(&success_codes_json)?,
+            notify_on: from_json(&notify_on_json)?,
+            process_priority: priority_json.as_deref().map(from_json).transpose()?,
+            create_destination: create_destination != 0,
+            run_on_startup: run_on_startup != 0,
+            parallel_split,
+        },
+        safety: JobSafety {
+            expect_nonempty_destination: expect_nonempty_destination != 0,
+            change_alarm: alarm_json.as_deref().map(from_json).transpose()?,
+            skip_if_unchanged: skip_if_unchanged != 0,
+            verify_after_snapshot: verify_after_snapshot != 0,
+        },
         schedule: schedule_json.as_deref().map(from_json).transpose()?,
         enabled: enabled != 0,
         created_at: parse_datetime(&created_str)?,
         updated_at: parse_datetime(&updated_str)?,
     })
 }
+
+fn update_job_row(conn: &Connection, job: &JobDefinition) -> Result<(), AppError> {
+    let rows = conn
+        .execute(
+            "UPDATE jobs SET name = ?1, description = ?2, source = ?3, destination = ?4, backup_mode = ?5, options = ?6, ssh_config = ?7, schedule = ?8, enabled = ?9, updated_at = ?10, env = ?11, max_runtime_mins = ?12, color = ?13, icon = ?14, success_exit_codes = ?15, notify_on = ?16, process_priority = ?17, create_destination = ?18, run_on_startup = ?19, change_alarm = ?20, skip_if_unchanged = ?21, expect_nonempty_destination = ?22, parallel_split = ?23, verify_after_snapshot = ?24
+             WHERE id = ?25",
+            rusqlite::params![
+                job.name,
+                job.description,
+                to_json(&job.transfer.source)?,
+                to_json(&job.transfer.destination)?,
+                to_json(&job.transfer.backup_mode)?,
+                to_json(&job.options)?,
+                job.ssh_config.as_ref().map(|s| to_json(s)).transpose()?,
+                job.schedule.as_ref().map(|s| to_json(s)).transpose()?,
+                job.enabled as i32,
+                job.updated_at.to_rfc3339(),
+                to_json(&job.run_policy.env)?,
+                job.run_policy.max_runtime_mins,
+                job.appearance.color,
+                job.appearance.icon,
+                to_json(&job.run_policy.success_exit_codes)?,
+                to_json(&job.run_policy.notify_on)?,
+                job.run_policy.process_priority.as_ref().map(to_json).transpose()?,
+                job.run_policy.create_destination as i32,
+                job.run_policy.run_on_startup as i32,
+                job.safety.change_alarm.as_ref().map(to_json).transpose()?,
+                job.safety.skip_if_unchanged as i32,
+                job.safety.expect_nonempty_destination as i32,
+                job.run_policy.parallel_split,
+                job.safety.verify_after_snapshot as i32,
+                job.id.to_string(),
+            ],
+        )
+        .map_err(db_error)?;
+
+    if rows == 0 {
+        return Err(AppError::NotFound(format!("Job {} not found", job.id)));
+    }
+    Ok(())
+}
+
+fn upsert_job_row(conn: &Connection, job: &JobDefinition) -> Result<(), AppError> {
+    // ON CONFLICT DO UPDATE rather than INSERT OR REPLACE: a replace deletes
+    // the row first, which would cascade to the job's history.
+    conn.execute(
+        "INSERT INTO jobs (id, name, description, source, destination, backup_mode, options, ssh_config, schedule, enabled, created_at, updated_at, env, max_runtime_mins, color, icon, success_exit_codes, notify_on, process_priority, create_destination, run_on_startup, change_alarm, skip_if_unchanged, expect_nonempty_destination, parallel_split, verify_after_snapshot)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26)
+         ON CONFLICT(id) DO UPDATE SET name = excluded.name, description = excluded.description, source = excluded.source, destination = excluded.destination, backup_mode = excluded.backup_mode, options = excluded.options, ssh_config = excluded.ssh_config, schedule = excluded.schedule, enabled = excluded.enabled, created_at = excluded.created_at, updated_at = excluded.updated_at, env = excluded.env, max_runtime_mins = excluded.max_runtime_mins, color = excluded.color, icon = excluded.icon, success_exit_codes = excluded.success_exit_codes, notify_on = excluded.notify_on, process_priority = excluded.process_priority, create_destination = excluded.create_destination, run_on_startup = excluded.run_on_startup, change_alarm = excluded.change_alarm, skip_if_unchanged = excluded.skip_if_unchanged, expect_nonempty_destination = excluded.expect_nonempty_destination, parallel_split = excluded.parallel_split, verify_after_snapshot = excluded.verify_after_snapshot",
+        rusqlite::params![
+            job.id.to_string(),
+            job.name,
+            job.description,
+            to_json(&job.transfer.source)?,
+            to_json(&job.transfer.destination)?,
+            to_json(&job.transfer.backup_mode)?,
+            to_json(&job.options)?,
+            job.ssh_config.as_ref().map(to_json).transpose()?,
+            job.schedule.as_ref().map(to_json).transpose()?,
+            job.enabled as i32,
+            job.created_at.to_rfc3339(),
+            job.updated_at.to_rfc3339(),
+            to_json(&job.run_policy.env)?,
+            job.run_policy.max_runtime_mins,
+            job.appearance.color,
+            job.appearance.icon,
+            to_json(&job.run_policy.success_exit_codes)?,
+            to_json(&job.run_policy.notify_on)?,
+            job.run_policy.process_priority.as_ref().map(to_json).transpose()?,
+            job.run_policy.create_destination as i32,
+            job.run_policy.run_on_startup as i32,
+            job.safety.change_alarm.as_ref().map(to_json).transpose()?,
+            job.safety.skip_if_unchanged as i32,
+            job.safety.expect_nonempty_destination as i32,
+        job.run_policy.parallel_split,
+        job.safety.verify_after_snapshot as i32,
+        ],
+    )
+    .map_err(db_error)?;
+    Ok(())
+}
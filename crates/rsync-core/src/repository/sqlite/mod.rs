@@ -1,3 +1,5 @@
+pub mod audit;
+pub mod backup_set;
 pub mod invocation;
 pub mod job;
 pub mod settings;
@@ -2,6 +2,7 @@ use std::sync::{Arc, Mutex};
 
 use rusqlite::Connection;
 
+use crate::database::sqlite::{db_error, with_busy_retry};
 use crate::error::AppError;
 use crate::repository::settings::SettingsRepository;
 
@@ -23,40 +24,36 @@ impl SettingsRepository for SqliteSettingsRepository {
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
         let mut stmt = conn
             .prepare("SELECT value FROM settings WHERE key = ?1")
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            .map_err(db_error)?;
 
         let result = stmt
             .query_row(rusqlite::params![key], |row| row.get::<_, String>(0))
             .optional()
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            .map_err(db_error)?;
 
         Ok(result)
     }
 
     fn set_setting(&self, key: &str, value: &str) -> Result<(), AppError> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-        conn.execute(
-            "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
-            rusqlite::params![key, value],
-        )
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-        Ok(())
+        with_busy_retry(&self.conn, |conn| {
+            conn.execute(
+                "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+                rusqlite::params![key, value],
+            )
+            .map_err(db_error)?;
+            Ok(())
+        })
     }
 
     fn delete_setting(&self, key: &str) -> Result<(), AppError> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-        conn.execute(
-            "DELETE FROM settings WHERE key = ?1",
-            rusqlite::params![key],
-        )
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-        Ok(())
+        with_busy_retry(&self.conn, |conn| {
+            conn.execute(
+                "DELETE FROM settings WHERE key = ?1",
+                rusqlite::params![key],
+            )
+            .map_err(db_error)?;
+            Ok(())
+        })
     }
 }
 
@@ -3,9 +3,9 @@ use std::sync::{Arc, Mutex};
 use rusqlite::Connection;
 use uuid::Uuid;
 
-use crate::database::sqlite::{parse_datetime, parse_uuid};
+use crate::database::sqlite::{db_error, parse_datetime, parse_uuid, with_busy_retry};
 use crate::error::AppError;
-use crate::models::backup::SnapshotRecord;
+use crate::models::backup::{SnapshotRecord, SnapshotVerification};
 use crate::repository::snapshot::SnapshotRepository;
 
 pub struct SqliteSnapshotRepository {
@@ -20,24 +20,26 @@ impl SqliteSnapshotRepository {
 
 impl SnapshotRepository for SqliteSnapshotRepository {
     fn create_snapshot(&self, snapshot: &SnapshotRecord) -> Result<(), AppError> {
-        let conn = self.conn.lock().map_err(|e| AppError::DatabaseError(e.to_string()))?;
-        conn.execute(
-            "INSERT INTO snapshots (id, job_id, invocation_id, snapshot_path, link_dest_path, created_at, size_bytes, file_count, is_latest)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
-            rusqlite::params![
-                snapshot.id.to_string(),
-                snapshot.job_id.to_string(),
-                snapshot.invocation_id.to_string(),
-                snapshot.snapshot_path,
-                snapshot.link_dest_path,
-                snapshot.created_at.to_rfc3339(),
-                snapshot.size_bytes as i64,
-                snapshot.file_count as i64,
-                snapshot.is_latest as i32,
-            ],
-        )
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-        Ok(())
+        with_busy_retry(&self.conn, |conn| {
+            conn.execute(
+                "INSERT INTO snapshots (id, job_id, invocation_id, snapshot_path, link_dest_path, created_at, size_bytes, file_count, is_latest, pinned)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                rusqlite::params![
+                    snapshot.id.to_string(),
+                    snapshot.job_id.to_string(),
+                    snapshot.invocation_id.to_string(),
+                    snapshot.snapshot_path,
+                    snapshot.link_dest_path,
+                    snapshot.created_at.to_rfc3339(),
+                    snapshot.size_bytes as i64,
+                    snapshot.file_count as i64,
+                    snapshot.is_latest as i32,
+                    snapshot.pinned as i32,
+                ],
+            )
+            .map_err(db_error)?;
+            Ok(())
+        })
     }
 
     fn get_latest_snapshot_for_job(
@@ -47,10 +49,10 @@ impl SnapshotRepository for SqliteSnapshotRepository {
         let conn = self.conn.lock().map_err(|e| AppError::DatabaseError(e.to_string()))?;
         let mut stmt = conn
             .prepare(
-                "SELECT id, job_id, invocation_id, snapshot_path, link_dest_path, created_at, size_bytes, file_count, is_latest
+                "SELECT id, job_id, invocation_id, snapshot_path, link_dest_path, created_at, size_bytes, file_count, is_latest, pinned
                  FROM snapshots WHERE job_id = ?1 ORDER BY created_at DESC LIMIT 1",
             )
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            .map_err(db_error)?;
 
         let result = stmt
             .query_row(rusqlite::params![job_id.to_string()], |row| {
@@ -68,51 +70,178 @@ impl SnapshotRepository for SqliteSnapshotRepository {
         let conn = self.conn.lock().map_err(|e| AppError::DatabaseError(e.to_string()))?;
         let mut stmt = conn
             .prepare(
-                "SELECT id, job_id, invocation_id, snapshot_path, link_dest_path, created_at, size_bytes, file_count, is_latest
+                "SELECT id, job_id, invocation_id, snapshot_path, link_dest_path, created_at, size_bytes, file_count, is_latest, pinned
                  FROM snapshots WHERE job_id = ?1 ORDER BY created_at DESC",
             )
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            .map_err(db_error)?;
 
         let rows = stmt
             .query_map(rusqlite::params![job_id.to_string()], |row| {
                 Ok(row_to_snapshot(row))
             })
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            .map_err(db_error)?;
 
         let mut snapshots = Vec::new();
         for row in rows {
-            let snapshot = row.map_err(|e| AppError::DatabaseError(e.to_string()))??;
+            let snapshot = row.map_err(db_error)??;
             snapshots.push(snapshot);
         }
         Ok(snapshots)
     }
 
     fn delete_snapshot(&self, id: &Uuid) -> Result<(), AppError> {
+        with_busy_retry(&self.conn, |conn| {
+            let rows = conn
+                .execute(
+                    "DELETE FROM snapshots WHERE id = ?1",
+                    rusqlite::params![id.to_string()],
+                )
+                .map_err(db_error)?;
+
+            if rows == 0 {
+                return Err(AppError::NotFound(format!("Snapshot {} not found", id)));
+            }
+            Ok(())
+        })
+    }
+
+    fn set_snapshot_pinned(&self, id: &Uuid, pinned: bool) -> Result<(), AppError> {
+        with_busy_retry(&self.conn, |conn| {
+            let rows = conn
+                .execute(
+                    "UPDATE snapshots SET pinned = ?1 WHERE id = ?2",
+                    rusqlite::params![pinned as i32, id.to_string()],
+                )
+                .map_err(db_error)?;
+
+            if rows == 0 {
+                return Err(AppError::NotFound(format!("Snapshot {} not found", id)));
+            }
+            Ok(())
+        })
+    }
+
+    fn list_all_snapshots(&self) -> Result<Vec<SnapshotRecord>, AppError> {
         let conn = self.conn.lock().map_err(|e| AppError::DatabaseError(e.to_string()))?;
-        let rows = conn
-            .execute(
-                "DELETE FROM snapshots WHERE id = ?1",
-                rusqlite::params![id.to_string()],
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, job_id, invocation_id, snapshot_path, link_dest_path, created_at, size_bytes, file_count, is_latest, pinned
+                 FROM snapshots ORDER BY created_at DESC",
             )
+            .map_err(db_error)?;
+
+        let rows = stmt
+            .query_map([], |row| Ok(row_to_snapshot(row)))
+            .map_err(db_error)?;
+
+        let mut snapshots = Vec::new();
+        for row in rows {
+            let snapshot = row.map_err(db_error)??;
+            snapshots.push(snapshot);
+        }
+        Ok(snapshots)
+    }
+
+    fn upsert_snapshot(&self, snapshot: &SnapshotRecord) -> Result<(), AppError> {
+        with_busy_retry(&self.conn, |conn| {
+            conn.execute(
+                "INSERT INTO snapshots (id, job_id, invocation_id, snapshot_path, link_dest_path, created_at, size_bytes, file_count, is_latest, pinned)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+                 ON CONFLICT(id) DO UPDATE SET job_id = excluded.job_id, invocation_id = excluded.invocation_id, snapshot_path = excluded.snapshot_path, link_dest_path = excluded.link_dest_path, created_at = excluded.created_at, size_bytes = excluded.size_bytes, file_count = excluded.file_count, is_latest = excluded.is_latest, pinned = excluded.pinned",
+                rusqlite::params![
+                    snapshot.id.to_string(),
+                    snapshot.job_id.to_string(),
+                    snapshot.invocation_id.to_string(),
+                    snapshot.snapshot_path,
+                    snapshot.link_dest_path,
+                    snapshot.created_at.to_rfc3339(),
+                    snapshot.size_bytes as i64,
+                    snapshot.file_count as i64,
+                    snapshot.is_latest as i32,
+                    snapshot.pinned as i32,
+                ],
+            )
+            .map_err(db_error)?;
+            Ok(())
+        })
+    }
+
+    fn record_verification(&self, verification: &SnapshotVerification) -> Result<(), AppError> {
+        with_busy_retry(&self.conn, |conn| {
+            conn.execute(
+                "INSERT INTO snapshot_verifications (invocation_id, job_id, verified_at, passed, discrepancies, error)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                 ON CONFLICT(invocation_id) DO UPDATE SET job_id = excluded.job_id, verified_at = excluded.verified_at, passed = excluded.passed, discrepancies = excluded.discrepancies, error = excluded.error",
+                rusqlite::params![
+                    verification.invocation_id.to_string(),
+                    verification.job_id.to_string(),
+                    verification.verified_at.to_rfc3339(),
+                    verification.passed as i32,
+                    verification.discrepancies as i64,
+                    verification.error,
+                ],
+            )
+            .map_err(db_error)?;
+            Ok(())
+        })
+    }
+
+    fn get_verification(
+        &self,
+        invocation_id: &Uuid,
+    ) -> Result<Option<SnapshotVerification>, AppError> {
+        let conn = self
+            .conn
+            .lock()
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT invocation_id, job_id, verified_at, passed, discrepancies, error
+                 FROM snapshot_verifications WHERE invocation_id = ?1",
+            )
+            .map_err(db_error)?;
 
-        if rows == 0 {
-            return Err(AppError::NotFound(format!("Snapshot {} not found", id)));
+        let result = stmt.query_row(rusqlite::params![invocation_id.to_string()], |row| {
+            Ok(row_to_verification(row))
+        });
+
+        match result {
+            Ok(verification) => Ok(Some(verification?)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(AppError::DatabaseError(e.to_string())),
         }
-        Ok(())
     }
 }
 
+fn row_to_verification(row: &rusqlite::Row) -> Result<SnapshotVerification, AppError> {
+    let inv_id_str: String = row.get(0).map_err(db_error)?;
+    let job_id_str: String = row.get(1).map_err(db_error)?;
+    let verified_str: String = row.get(2).map_err(db_error)?;
+    let passed: i32 = row.get(3).map_err(db_error)?;
+    let discrepancies: i64 = row.get(4).map_err(db_error)?;
+    let error: Option<String> = row.get(5).map_err(db_error)?;
+
+    Ok(SnapshotVerification {
+        invocation_id: parse_uuid(&inv_id_str)?,
+        job_id: parse_uuid(&job_id_str)?,
+        verified_at: parse_datetime(&verified_str)?,
+        passed: passed != 0,
+        discrepancies: discrepancies as u64,
+        error,
+    })
+}
+
 fn row_to_snapshot(row: &rusqlite::Row) -> Result<SnapshotRecord, AppError> {
-    let id_str: String = row.get(0).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let job_id_str: String = row.get(1).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let inv_id_str: String = row.get(2).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let snapshot_path: String = row.get(3).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let link_dest_path: Option<String> = row.get(4).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let created_str: String = row.get(5).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let size_bytes: i64 = row.get(6).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let file_count: i64 = row.get(7).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let is_latest: i32 = row.get(8).map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let id_str: String = row.get(0).map_err(db_error)?;
+    let job_id_str: String = row.get(1).map_err(db_error)?;
+    let inv_id_str: String = row.get(2).map_err(db_error)?;
+    let snapshot_path: String = row.get(3).map_err(db_error)?;
+    let link_dest_path: Option<String> = row.get(4).map_err(db_error)?;
+    let created_str: String = row.get(5).map_err(db_error)?;
+    let size_bytes: i64 = row.get(6).map_err(db_error)?;
+    let file_count: i64 = row.get(7).map_err(db_error)?;
+    let is_latest: i32 = row.get(8).map_err(db_error)?;
+    let pinned: i32 = row.get(9).map_err(db_error)?;
 
     Ok(SnapshotRecord {
         id: parse_uuid(&id_str)?,
@@ -124,5 +253,6 @@ fn row_to_snapshot(row: &rusqlite::Row) -> Result<SnapshotRecord, AppError> {
         size_bytes: size_bytes as u64,
         file_count: file_count as u64,
         is_latest: is_latest != 0,
+        pinned: pinned != 0,
     })
 }
@@ -3,7 +3,7 @@ use std::sync::{Arc, Mutex};
 use rusqlite::Connection;
 use uuid::Uuid;
 
-use crate::database::sqlite::{parse_datetime, parse_uuid};
+use crate::database::sqlite::{db_error, parse_datetime, parse_uuid, with_busy_retry};
 use crate::error::AppError;
 use crate::models::statistics::RunStatistic;
 use crate::repository::statistics::StatisticsRepository;
@@ -20,43 +20,45 @@ impl SqliteStatisticsRepository {
 
 impl StatisticsRepository for SqliteStatisticsRepository {
     fn record_statistic(&self, stat: &RunStatistic) -> Result<(), AppError> {
-        let conn = self.conn.lock().map_err(|e| AppError::DatabaseError(e.to_string()))?;
-        conn.execute(
-            "INSERT INTO run_statistics (id, job_id, invocation_id, recorded_at, files_transferred, bytes_transferred, duration_secs, speedup)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
-            rusqlite::params![
-                stat.id.to_string(),
-                stat.job_id.to_string(),
-                stat.invocation_id.to_string(),
-                stat.recorded_at.to_rfc3339(),
-                stat.files_transferred as i64,
-                stat.bytes_transferred as i64,
-                stat.duration_secs,
-                stat.speedup,
-            ],
-        )
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-        Ok(())
+        with_busy_retry(&self.conn, |conn| {
+            conn.execute(
+                "INSERT INTO run_statistics (id, job_id, invocation_id, recorded_at, files_transferred, bytes_transferred, duration_secs, speedup, files_deleted)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                rusqlite::params![
+                    stat.id.to_string(),
+                    stat.job_id.to_string(),
+                    stat.invocation_id.to_string(),
+                    stat.recorded_at.to_rfc3339(),
+                    stat.files_transferred as i64,
+                    stat.bytes_transferred as i64,
+                    stat.duration_secs,
+                    stat.speedup,
+                    stat.files_deleted as i64,
+                ],
+            )
+            .map_err(db_error)?;
+            Ok(())
+        })
     }
 
     fn get_statistics_for_job(&self, job_id: &Uuid) -> Result<Vec<RunStatistic>, AppError> {
         let conn = self.conn.lock().map_err(|e| AppError::DatabaseError(e.to_string()))?;
         let mut stmt = conn
             .prepare(
-                "SELECT id, job_id, invocation_id, recorded_at, files_transferred, bytes_transferred, duration_secs, speedup
+                "SELECT id, job_id, invocation_id, recorded_at, files_transferred, bytes_transferred, duration_secs, speedup, files_deleted
                  FROM run_statistics WHERE job_id = ?1 ORDER BY recorded_at DESC",
             )
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            .map_err(db_error)?;
 
         let rows = stmt
             .query_map(rusqlite::params![job_id.to_string()], |row| {
                 Ok(row_to_statistic(row))
             })
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            .map_err(db_error)?;
 
         let mut stats = Vec::new();
         for row in rows {
-            let stat = row.map_err(|e| AppError::DatabaseError(e.to_string()))??;
+            let stat = row.map_err(db_error)??;
             stats.push(stat);
         }
         Ok(stats)
@@ -66,50 +68,76 @@ impl StatisticsRepository for SqliteStatisticsRepository {
         let conn = self.conn.lock().map_err(|e| AppError::DatabaseError(e.to_string()))?;
         let mut stmt = conn
             .prepare(
-                "SELECT id, job_id, invocation_id, recorded_at, files_transferred, bytes_transferred, duration_secs, speedup
+                "SELECT id, job_id, invocation_id, recorded_at, files_transferred, bytes_transferred, duration_secs, speedup, files_deleted
                  FROM run_statistics ORDER BY recorded_at DESC",
             )
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            .map_err(db_error)?;
 
         let rows = stmt
             .query_map([], |row| Ok(row_to_statistic(row)))
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            .map_err(db_error)?;
 
         let mut stats = Vec::new();
         for row in rows {
-            let stat = row.map_err(|e| AppError::DatabaseError(e.to_string()))??;
+            let stat = row.map_err(db_error)??;
             stats.push(stat);
         }
         Ok(stats)
     }
 
     fn delete_statistics_for_job(&self, job_id: &Uuid) -> Result<(), AppError> {
-        let conn = self.conn.lock().map_err(|e| AppError::DatabaseError(e.to_string()))?;
-        conn.execute(
-            "DELETE FROM run_statistics WHERE job_id = ?1",
-            rusqlite::params![job_id.to_string()],
-        )
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-        Ok(())
+        with_busy_retry(&self.conn, |conn| {
+            conn.execute(
+                "DELETE FROM run_statistics WHERE job_id = ?1",
+                rusqlite::params![job_id.to_string()],
+            )
+            .map_err(db_error)?;
+            Ok(())
+        })
     }
 
     fn delete_all_statistics(&self) -> Result<(), AppError> {
-        let conn = self.conn.lock().map_err(|e| AppError::DatabaseError(e.to_string()))?;
-        conn.execute("DELETE FROM run_statistics", [])
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-        Ok(())
+        with_busy_retry(&self.conn, |conn| {
+            conn.execute("DELETE FROM run_statistics", [])
+                .map_err(db_error)?;
+            Ok(())
+        })
+    }
+
+    fn upsert_statistic(&self, stat: &RunStatistic) -> Result<(), AppError> {
+        with_busy_retry(&self.conn, |conn| {
+            conn.execute(
+                "INSERT INTO run_statistics (id, job_id, invocation_id, recorded_at, files_transferred, bytes_transferred, duration_secs, speedup, files_deleted)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+                 ON CONFLICT(id) DO UPDATE SET job_id = excluded.job_id, invocation_id = excluded.invocation_id, recorded_at = excluded.recorded_at, files_transferred = excluded.files_transferred, bytes_transferred = excluded.bytes_transferred, duration_secs = excluded.duration_secs, speedup = excluded.speedup, files_deleted = excluded.files_deleted",
+                rusqlite::params![
+                    stat.id.to_string(),
+                    stat.job_id.to_string(),
+                    stat.invocation_id.to_string(),
+                    stat.recorded_at.to_rfc3339(),
+                    stat.files_transferred as i64,
+                    stat.bytes_transferred as i64,
+                    stat.duration_secs,
+                    stat.speedup,
+                    stat.files_deleted as i64,
+                ],
+            )
+            .map_err(db_error)?;
+            Ok(())
+        })
     }
 }
 
 fn row_to_statistic(row: &rusqlite::Row) -> Result<RunStatistic, AppError> {
-    let id_str: String = row.get(0).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let job_id_str: String = row.get(1).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let inv_id_str: String = row.get(2).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let recorded_str: String = row.get(3).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let files: i64 = row.get(4).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let bytes: i64 = row.get(5).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let duration: f64 = row.get(6).map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let speedup: Option<f64> = row.get(7).map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let id_str: String = row.get(0).map_err(db_error)?;
+    let job_id_str: String = row.get(1).map_err(db_error)?;
+    let inv_id_str: String = row.get(2).map_err(db_error)?;
+    let recorded_str: String = row.get(3).map_err(db_error)?;
+    let files: i64 = row.get(4).map_err(db_error)?;
+    let bytes: i64 = row.get(5).map_err(db_error)?;
+    let duration: f64 = row.get(6).map_err(db_error)?;
+    let speedup: Option<f64> = row.get(7).map_err(db_error)?;
+    let deleted: i64 = row.get(8).map_err(db_error)?;
 
     Ok(RunStatistic {
         id: parse_uuid(&id_str)?,
@@ -118,6 +146,7 @@ fn row_to_statistic(row: &rusqlite::Row) -> Result<RunStatistic, AppError> {
         recorded_at: parse_datetime(&recorded_str)?,
         files_transferred: files as u64,
         bytes_transferred: bytes as u64,
+        files_deleted: deleted as u64,
         duration_secs: duration,
         speedup,
     })
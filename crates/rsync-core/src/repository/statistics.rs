@@ -9,4 +9,6 @@ pub trait StatisticsRepository: Send + Sync {
     fn get_all_statistics(&self) -> Result<Vec<RunStatistic>, AppError>;
     fn delete_statistics_for_job(&self, job_id: &Uuid) -> Result<(), AppError>;
     fn delete_all_statistics(&self) -> Result<(), AppError>;
+    /// Insert the statistic, or overwrite the existing row with the same id.
+    fn upsert_statistic(&self, stat: &RunStatistic) -> Result<(), AppError>;
 }
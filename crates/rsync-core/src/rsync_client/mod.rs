@@ -18,6 +18,17 @@ pub enum RsyncError {
         exit_code: Option<i32>,
     },
 
+    #[error(
+        "rsync protocol version mismatch: {detail}. The two ends could not agree on a \
+         protocol; upgrade rsync on the remote, or add --protocol=N (e.g. --protocol=29) to \
+         the job's custom arguments to use an older one. If it asks whether your shell is \
+         clean, a remote login script is printing output"
+    )]
+    ProtocolMismatch {
+        detail: String,
+        exit_code: Option<i32>,
+    },
+
     #[error("rsync not found on system")]
     RsyncNotFound,
 
@@ -31,6 +42,29 @@ pub enum RsyncError {
     Cancelled,
 }
 
+/// Whether an rsync stderr line reports that client and server could not
+/// agree on a protocol version, e.g. a 3.x client talking to a 2.x daemon.
+pub fn is_protocol_mismatch(line: &str) -> bool {
+    let lower = line.to_lowercase();
+    lower.contains("protocol version mismatch") || lower.contains("protocol incompatibility")
+}
+
+/// The error for an rsync process that exited with `exit_code`: a
+/// [`RsyncError::ProtocolMismatch`] when `stderr` shows one, otherwise a
+/// plain [`RsyncError::ProcessError`] carrying the stderr text.
+pub fn classify_failure(stderr: &str, exit_code: Option<i32>) -> RsyncError {
+    match stderr.lines().find(|line| is_protocol_mismatch(line)) {
+        Some(line) => RsyncError::ProtocolMismatch {
+            detail: line.trim().to_string(),
+            exit_code,
+        },
+        None => RsyncError::ProcessError {
+            message: stderr.to_string(),
+            exit_code,
+        },
+    }
+}
+
 pub trait RsyncClient {
     fn execute(&self, args: &[String]) -> Result<RsyncResult, RsyncError>;
 
@@ -1,7 +1,7 @@
 use std::io::{BufRead, BufReader};
 use std::process::{Command, Stdio};
 
-use super::{RsyncClient, RsyncError, RsyncResult};
+use super::{classify_failure, RsyncClient, RsyncError, RsyncResult};
 
 pub struct ProcessRsyncClient {
     rsync_binary: String,
@@ -21,6 +21,19 @@ impl ProcessRsyncClient {
     }
 }
 
+/// Lines of `reader` like `BufRead::lines`, but invalid UTF-8 (a file name
+/// in another encoding) is replaced with U+FFFD instead of failing the read.
+fn lossy_lines<R: BufRead>(reader: R) -> impl Iterator<Item = std::io::Result<String>> {
+    reader.split(b'\n').map(|line| {
+        line.map(|mut bytes| {
+            if bytes.last() == Some(&b'\r') {
+                bytes.pop();
+            }
+            String::from_utf8_lossy(&bytes).into_owned()
+        })
+    })
+}
+
 impl Default for ProcessRsyncClient {
     fn default() -> Self {
         Self::new()
@@ -48,7 +61,7 @@ impl RsyncClient for ProcessRsyncClient {
 
         if let Some(stdout) = child.stdout.take() {
             let reader = BufReader::new(stdout);
-            for line in reader.lines() {
+            for line in lossy_lines(reader) {
                 let line = line.map_err(|e| RsyncError::IoError(e.to_string()))?;
                 stdout_lines.push(line);
             }
@@ -56,8 +69,7 @@ impl RsyncClient for ProcessRsyncClient {
 
         let stderr_output = if let Some(stderr) = child.stderr.take() {
             let reader = BufReader::new(stderr);
-            reader
-                .lines()
+            lossy_lines(reader)
                 .filter_map(|l| l.ok())
                 .collect::<Vec<_>>()
                 .join("\n")
@@ -72,10 +84,7 @@ impl RsyncClient for ProcessRsyncClient {
         let exit_code = status.code().unwrap_or(-1);
 
         if exit_code != 0 {
-            return Err(RsyncError::ProcessError {
-                message: stderr_output.clone(),
-                exit_code: Some(exit_code),
-            });
+            return Err(classify_failure(&stderr_output, Some(exit_code)));
         }
 
         Ok(RsyncResult {
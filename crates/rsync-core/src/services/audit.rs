@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::audit::{AuditAction, AuditEntry};
+use crate::repository::audit::AuditLogRepository;
+
+/// Records destructive actions taken through the services.
+///
+/// `JobService` and `StatisticsService` write to it once registered with
+/// their `with_audit_log`. Each frontend creates one with its own `source`.
+pub struct AuditLog {
+    repo: Arc<dyn AuditLogRepository>,
+    source: Option<String>,
+}
+
+impl AuditLog {
+    pub fn new(repo: Arc<dyn AuditLogRepository>) -> Self {
+        Self { repo, source: None }
+    }
+
+    /// Tag every entry with where the actions come from, e.g. `gui`.
+    pub fn with_source(mut self, source: &str) -> Self {
+        self.source = Some(source.to_string());
+        self
+    }
+
+    /// Record an action that has already happened. A failed write is logged
+    /// rather than returned, so it never masks the action's own result.
+    pub fn record(&self, action: AuditAction, target_id: Option<Uuid>, detail: String) {
+        let entry = AuditEntry {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            action,
+            target_id,
+            detail,
+            source: self.source.clone(),
+        };
+        if let Err(e) = self.repo.record_entry(&entry) {
+            log::error!("Failed to write audit entry for {}: {}", action, e);
+        }
+    }
+
+    /// Most recent entries first.
+    pub fn list(&self, limit: usize) -> Result<Vec<AuditEntry>, AppError> {
+        self.repo.list_entries(limit)
+    }
+}
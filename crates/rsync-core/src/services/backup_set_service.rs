@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::backup_set::BackupSet;
+use crate::repository::backup_set::BackupSetRepository;
+
+pub struct BackupSetService {
+    sets: Arc<dyn BackupSetRepository>,
+}
+
+impl BackupSetService {
+    pub fn new(sets: Arc<dyn BackupSetRepository>) -> Self {
+        Self { sets }
+    }
+
+    /// Create a set with a fresh id and timestamps.
+    pub fn create_set(&self, name: &str, job_ids: Vec<Uuid>) -> Result<BackupSet, AppError> {
+        validate_set(name, &job_ids)?;
+        let now = Utc::now();
+        let set = BackupSet {
+            id: Uuid::new_v4(),
+            name: name.trim().to_string(),
+            job_ids,
+            created_at: now,
+            updated_at: now,
+        };
+        self.sets.create_set(&set)?;
+        Ok(set)
+    }
+
+    pub fn update_set(&self, mut set: BackupSet) -> Result<BackupSet, AppError> {
+        validate_set(&set.name, &set.job_ids)?;
+        set.name = set.name.trim().to_string();
+        set.updated_at = Utc::now();
+        self.sets.update_set(&set)?;
+        Ok(set)
+    }
+
+    pub fn get_set(&self, id: &Uuid) -> Result<BackupSet, AppError> {
+        self.sets.get_set(id)
+    }
+
+    pub fn list_sets(&self) -> Result<Vec<BackupSet>, AppError> {
+        self.sets.list_sets()
+    }
+
+    pub fn delete_set(&self, id: &Uuid) -> Result<(), AppError> {
+        self.sets.delete_set(id)
+    }
+}
+
+fn validate_set(name: &str, job_ids: &[Uuid]) -> Result<(), AppError> {
+    if name.trim().is_empty() {
+        return Err(AppError::ValidationError(
+            "Backup set name must not be empty".to_string(),
+        ));
+    }
+    if let Some(dup) = job_ids
+        .iter()
+        .enumerate()
+        .find(|(i, id)| job_ids[..*i].contains(id))
+        .map(|(_, id)| id)
+    {
+        return Err(AppError::ValidationError(format!(
+            "Job {} appears more than once in the set",
+            dup
+        )));
+    }
+    Ok(())
+}
@@ -1,6 +1,34 @@
 use crate::models::job::{RsyncOptions, SshConfig, StorageLocation};
 
-fn ensure_trailing_slash(path: &str) -> String {
+/// Junk files that operating systems and NAS devices leave behind, excluded
+/// when `exclude_system_files` is set.
+pub const SYSTEM_FILE_EXCLUDES: &[&str] = &[
+    ".DS_Store",
+    "._*",
+    ".AppleDouble",
+    ".Spotlight-V100",
+    ".Trashes",
+    ".fseventsd",
+    ".TemporaryItems",
+    "Thumbs.db",
+    "desktop.ini",
+    "$RECYCLE.BIN",
+    "System Volume Information",
+    "@eaDir",
+    "#recycle",
+];
+
+/// Most `--link-dest` directories rsync accepts in one run.
+pub const MAX_LINK_DEST_DIRS: usize = 20;
+
+/// Whether `patterns` contains every system-file exclude.
+pub fn has_system_file_excludes(patterns: &[String]) -> bool {
+    SYSTEM_FILE_EXCLUDES
+        .iter()
+        .all(|junk| patterns.iter().any(|p| p == junk))
+}
+
+pub(crate) fn ensure_trailing_slash(path: &str) -> String {
     if path.ends_with('/') {
         path.to_string()
     } else {
@@ -8,13 +36,36 @@ fn ensure_trailing_slash(path: &str) -> String {
     }
 }
 
+/// Anchor `path` for `--relative` by inserting `/./` before its last
+/// component, so the destination receives `<name>/...` rather than the full
+/// source path. Paths that already carry a `/./` anchor, or have no parent
+/// directory, are left as they are.
+pub fn relative_source_path(path: &str) -> String {
+    if path.contains("/./") {
+        return path.to_string();
+    }
+    let trimmed = path.trim_end_matches('/');
+    match trimmed.rfind('/') {
+        Some(idx) if idx + 1 < trimmed.len() => {
+            format!("{}/./{}", &trimmed[..idx], &trimmed[idx + 1..])
+        }
+        _ => path.to_string(),
+    }
+}
+
+/// Build the rsync argument list for a transfer.
+///
+/// With `relative_paths`, `--relative` is emitted and the source is anchored
+/// by [`relative_source_path`]; the source's trailing slash then no longer
+/// matters, so `auto_trailing_slash` only affects the destination.
 pub fn build_rsync_args(
     source: &StorageLocation,
     destination: &StorageLocation,
     options: &RsyncOptions,
     ssh_config: Option<&SshConfig>,
-    link_dest: Option<&str>,
+    link_dests: &[&str],
     auto_trailing_slash: bool,
+    relative_paths: bool,
 ) -> Vec<String> {
     let mut args = Vec::new();
 
@@ -25,8 +76,11 @@ pub fn build_rsync_args(
     if options.core_transfer.compress {
         args.push("-z".to_string());
     }
-    if options.core_transfer.partial {
-        args.push("--partial".to_string());
+    // --partial-dir implies --partial
+    match options.core_transfer.partial_dir {
+        Some(ref dir) => args.push(format!("--partial-dir={}", dir)),
+        None if options.core_transfer.partial => args.push("--partial".to_string()),
+        None => {}
     }
     if options.core_transfer.dry_run {
         args.push("--dry-run".to_string());
@@ -53,6 +107,15 @@ pub fn build_rsync_args(
     if options.file_handling.one_file_system {
         args.push("--one-file-system".to_string());
     }
+    if let Some(max) = options.file_handling.max_delete {
+        args.push(format!("--max-delete={}", max));
+    }
+    if let Some(secs) = options.file_handling.modify_window {
+        args.push(format!("--modify-window={}", secs));
+    }
+    if let Some(ref choice) = options.file_handling.checksum_choice {
+        args.push(format!("--checksum-choice={}", choice));
+    }
     // Metadata
     if options.metadata.hard_links {
         args.push("--hard-links".to_string());
@@ -86,6 +149,11 @@ pub fn build_rsync_args(
     for pattern in &options.advanced.exclude_patterns {
         args.push(format!("--exclude={}", pattern));
     }
+    if options.advanced.exclude_system_files {
+        for pattern in SYSTEM_FILE_EXCLUDES {
+            args.push(format!("--exclude={}", pattern));
+        }
+    }
 
     for pattern in &options.advanced.include_patterns {
         args.push(format!("--include={}", pattern));
@@ -95,7 +163,16 @@ pub fn build_rsync_args(
         args.push(format!("--bwlimit={}", limit));
     }
 
-    if let Some(link) = link_dest {
+    if let Some(ref choice) = options.advanced.compress_choice {
+        args.push(format!("--compress-choice={}", choice));
+    }
+
+    if let Some(level) = options.advanced.compress_level {
+        args.push(format!("--compress-level={}", level));
+    }
+
+    // rsync tries each reference directory in the order given
+    for link in link_dests.iter().take(MAX_LINK_DEST_DIRS) {
         args.push(format!("--link-dest={}", link));
     }
 
@@ -114,10 +191,22 @@ pub fn build_rsync_args(
                 ssh_parts.push(format!("-i {}", key));
             }
 
+            if let Some(ref jump) = ssh.jump_host {
+                ssh_parts.push(format!("-J {}", jump));
+            }
+
             if !ssh.strict_host_key_checking {
                 ssh_parts.push("-o StrictHostKeyChecking=no".to_string());
             }
 
+            if let Some(secs) = ssh.connect_timeout {
+                ssh_parts.push(format!("-o ConnectTimeout={}", secs));
+            }
+
+            if let Some(secs) = ssh.server_alive_interval {
+                ssh_parts.push(format!("-o ServerAliveInterval={}", secs));
+            }
+
             if ssh_parts.len() > 1 {
                 args.push("-e".to_string());
                 args.push(ssh_parts.join(" "));
@@ -125,6 +214,10 @@ pub fn build_rsync_args(
         }
     }
 
+    if relative_paths {
+        args.push("--relative".to_string());
+    }
+
     for arg in &options.advanced.custom_args {
         args.push(arg.clone());
     }
@@ -132,11 +225,17 @@ pub fn build_rsync_args(
     let source_path = source.to_rsync_path();
     let dest_path = destination.to_rsync_path();
 
-    if auto_trailing_slash {
+    if relative_paths {
+        args.push(relative_source_path(&source_path));
+    } else if auto_trailing_slash {
         args.push(ensure_trailing_slash(&source_path));
-        args.push(ensure_trailing_slash(&dest_path));
     } else {
         args.push(source_path);
+    }
+
+    if auto_trailing_slash {
+        args.push(ensure_trailing_slash(&dest_path));
+    } else {
         args.push(dest_path);
     }
 
@@ -1,4 +1,14 @@
+use chrono::NaiveTime;
+
+use crate::format::format_rate;
 use crate::models::command::{ArgCategory, ArgumentExplanation, CommandExplanation, ParsedCommand};
+use crate::models::job::{BackupMode, StorageLocation};
+use crate::models::rsync_options::AdvancedOptions;
+use crate::services::bandwidth_schedule::{bandwidth_limit_at, next_bandwidth_change};
+use crate::services::command_builder::{has_system_file_excludes, SYSTEM_FILE_EXCLUDES};
+
+const UNRECOGNIZED: &str =
+    "Argument not recognized. Please check the rsync manual for more details.";
 
 /// Get a human-readable description for a recognized flag name.
 pub fn explain_flag(flag: &str) -> &'static str {
@@ -19,6 +29,7 @@ pub fn explain_flag(flag: &str) -> &'static str {
         "compress" => "Compress (-z): compresses data during transfer to reduce bandwidth usage.",
         "dry_run" => "Dry run (-n/--dry-run): simulates the transfer without making any changes. Useful for previewing what would happen.",
         "partial" => "Partial (--partial): keeps partially transferred files so interrupted transfers can resume.",
+        "partial_dir" => "Partial dir (--partial-dir=DIR): keeps partially transferred files in DIR instead of in place, so a half-copied file is never mistaken for a complete one. Implies --partial.",
         "update" => "Update (-u): skips files that are newer on the destination than the source.",
         "checksum" => "Checksum (-c): uses checksums instead of file size and modification time to decide whether to transfer a file.",
         "whole_file" => "Whole file (-W/--whole-file): disables rsync's delta-transfer algorithm and transfers whole files. Faster on fast networks.",
@@ -31,7 +42,7 @@ pub fn explain_flag(flag: &str) -> &'static str {
         "sparse" => "Sparse (-S/--sparse): handles sparse files efficiently, preserving their sparse nature on the destination.",
         "existing" => "Existing (--existing): only updates files that already exist on the destination; does not create new files.",
         "delay_updates" => "Delay updates (--delay-updates): puts updated files into a temporary directory first, then moves them into place at the end for more atomic updates.",
-        "relative" => "Relative (-R/--relative): preserves full path information by sending implied directories.",
+        "relative" => "Relative (-R/--relative): preserves full path information by sending implied directories. A '/./' in the source marks where the preserved path starts, and the source's trailing slash no longer matters.",
         "no_relative" => "No relative (--no-relative): disables --relative, sending only the final component of the source path.",
 
         // Symlink handling
@@ -104,6 +115,7 @@ pub fn explain_flag(flag: &str) -> &'static str {
         "timeout" => "Timeout (--timeout=SECONDS): sets the I/O timeout in seconds.",
         "address" => "Address (--address=ADDRESS): binds to the specified address for outgoing connections.",
         "port" => "Port (--port=PORT): specifies a non-default port for the rsync daemon.",
+        "password_file" => "Password file (--password-file=FILE): reads the rsync daemon password from the specified file.",
         "ipv4" => "IPv4 (-4/--ipv4): prefers IPv4 for connections.",
         "ipv6" => "IPv6 (-6/--ipv6): prefers IPv6 for connections.",
 
@@ -114,10 +126,75 @@ pub fn explain_flag(flag: &str) -> &'static str {
         "rsync_path" => "Rsync path (--rsync-path=PROGRAM): specifies the path to rsync on the remote machine.",
         "iconv" => "Iconv (--iconv=CONVERT_SPEC): converts filenames between character sets.",
 
-        _ => "Argument not recognized. Please check the rsync manual for more details.",
+        _ => UNRECOGNIZED,
     }
 }
 
+/// Descriptions for value-carrying flags the parser passes through as custom
+/// args without a dedicated field or flag name.
+fn explain_custom_flag(flag: &str) -> Option<(&'static str, ArgCategory)> {
+    let entry = match flag {
+        // File handling
+        "modify_window" => ("Modify window (--modify-window=NUM): treats modification times that differ by up to NUM seconds as equal. Useful for FAT and SMB filesystems with coarse timestamps.", ArgCategory::FileHandling),
+        "block_size" => ("Block size (-B/--block-size=SIZE): forces the block size used by the delta-transfer algorithm.", ArgCategory::FileHandling),
+        "checksum_choice" | "cc" => ("Checksum choice (--checksum-choice=ALGO): selects the checksum algorithm used for transfers and --checksum comparisons.", ArgCategory::FileHandling),
+        "mkpath" => ("Mkpath (--mkpath): creates missing parent directories of the destination path.", ArgCategory::FileHandling),
+        "exclude" => ("Exclude (--exclude=PATTERN): excludes files matching PATTERN from the transfer.", ArgCategory::Pattern),
+        "include" => ("Include (--include=PATTERN): includes files matching PATTERN, overriding later excludes.", ArgCategory::Pattern),
+        "link_dest" => ("Link dest (--link-dest=DIR): hard-links files that are unchanged relative to DIR instead of copying them.", ArgCategory::Performance),
+
+        // Metadata
+        "usermap" => ("User map (--usermap=STRING): maps source user names or IDs to different ones on the destination.", ArgCategory::Metadata),
+        "groupmap" => ("Group map (--groupmap=STRING): maps source group names or IDs to different ones on the destination.", ArgCategory::Metadata),
+
+        // Output
+        "log_file_format" => ("Log file format (--log-file-format=FMT): sets the format of the lines written to --log-file.", ArgCategory::Output),
+        "outbuf" => ("Output buffering (--outbuf=N|L|B): sets stdout buffering to none, line or block.", ArgCategory::Output),
+
+        // Performance / limits
+        "bwlimit" => ("Bandwidth limit (--bwlimit=RATE): caps the transfer rate. A plain number is KiB per second; suffixes such as K, M or G set other units.", ArgCategory::Performance),
+        "stop_after" | "time_limit" => ("Stop after (--stop-after=MINS): stops rsync after the given number of minutes.", ArgCategory::Performance),
+        "stop_at" => ("Stop at (--stop-at=y-m-dTh:m): stops rsync at the given point in time.", ArgCategory::Performance),
+        "max_alloc" => ("Max alloc (--max-alloc=SIZE): limits the memory a single allocation may request.", ArgCategory::Performance),
+        "sockopts" => ("Socket options (--sockopts=OPTIONS): sets custom TCP socket options.", ArgCategory::Performance),
+        "protocol" => ("Protocol (--protocol=NUM): forces an older rsync protocol version.", ArgCategory::Performance),
+
+        // Remote shell
+        "rsh" => ("Remote shell (-e/--rsh=COMMAND): sets the remote shell command used to reach remote hosts.", ArgCategory::Ssh),
+        "remote_option" => ("Remote option (-M/--remote-option=OPTION): passes OPTION to the remote rsync only.", ArgCategory::Ssh),
+
+        // Batch mode
+        "write_batch" | "only_write_batch" => ("Write batch (--write-batch=FILE): records the transfer in a batch file that can be replayed with --read-batch.", ArgCategory::Flag),
+        "read_batch" => ("Read batch (--read-batch=FILE): replays a transfer recorded with --write-batch.", ArgCategory::Flag),
+        _ => return None,
+    };
+    Some(entry)
+}
+
+/// Second-pass recognition of a long option the parser passed through in
+/// `custom_args`, such as `--modify-window=2`.
+///
+/// Checks the extended table first and then the regular flag descriptions.
+/// Returns `None` for options neither knows about.
+fn explain_custom_arg(arg: &str) -> Option<(String, ArgCategory)> {
+    let option = arg.strip_prefix("--")?;
+    let (key, value) = match option.split_once('=') {
+        Some((key, value)) => (key, Some(value)),
+        None => (option, None),
+    };
+    let flag = key.replace('-', "_");
+    let (description, category) = match explain_custom_flag(&flag) {
+        Some(entry) => entry,
+        None if explain_flag(&flag) != UNRECOGNIZED => (explain_flag(&flag), flag_category(&flag)),
+        None => return None,
+    };
+    let description = match value {
+        Some(value) => format!("{} Set to '{}'.", description, value),
+        None => description.to_string(),
+    };
+    Some((description, category))
+}
+
 /// Determine the category for a flag based on its name.
 fn flag_category(flag: &str) -> ArgCategory {
     match flag {
@@ -126,11 +203,11 @@ fn flag_category(flag: &str) -> ArgCategory {
         | "delete_excluded" | "force" | "max_delete" | "ignore_errors" => ArgCategory::Deletion,
 
         "checksum" | "update" | "whole_file" | "ignore_existing" | "one_file_system"
-        | "size_only" | "inplace" | "append" | "append_verify" | "sparse"
-        | "existing" | "delay_updates" | "relative" | "no_relative"
-        | "copy_links" | "copy_dirlinks" | "keep_dirlinks" | "safe_links"
-        | "files_from" | "filter" | "exclude_from" | "include_from"
-        | "prune_empty_dirs" | "max_size" | "min_size" | "fuzzy" => ArgCategory::FileHandling,
+        | "size_only" | "inplace" | "append" | "append_verify" | "sparse" | "existing"
+        | "delay_updates" | "relative" | "no_relative" | "copy_links" | "copy_dirlinks"
+        | "keep_dirlinks" | "safe_links" | "files_from" | "filter" | "exclude_from"
+        | "include_from" | "prune_empty_dirs" | "max_size" | "min_size" | "fuzzy"
+        | "partial_dir" => ArgCategory::FileHandling,
 
         // Metadata
         "hard_links" | "acls" | "xattrs" | "numeric_ids"
@@ -143,9 +220,10 @@ fn flag_category(flag: &str) -> ArgCategory {
         | "info" | "debug" | "msgs2stderr" => ArgCategory::Output,
 
         // Performance / networking
-        "compress" | "compress_level" | "skip_compress"
-        | "blocking_io" | "contimeout" | "timeout" | "address" | "port"
-        | "ipv4" | "ipv6" => ArgCategory::Performance,
+        "compress" | "compress_level" | "skip_compress" | "blocking_io" | "contimeout"
+        | "timeout" | "address" | "port" | "password_file" | "ipv4" | "ipv6" => {
+            ArgCategory::Performance
+        }
 
         // Backup
         "backup" | "backup_dir" | "suffix" => ArgCategory::Flag,
@@ -172,13 +250,22 @@ pub fn explain_command(parsed: &ParsedCommand) -> CommandExplanation {
     }
 
     // Explain exclude patterns
+    let system_files = has_system_file_excludes(&parsed.exclude_patterns);
     for pattern in &parsed.exclude_patterns {
-        arguments.push(ArgumentExplanation {
-            argument: format!("--exclude={}", pattern),
-            description: format!(
+        let description = if system_files && SYSTEM_FILE_EXCLUDES.contains(&pattern.as_str()) {
+            format!(
+                "Exclude '{}' — part of the built-in system/junk file list.",
+                pattern
+            )
+        } else {
+            format!(
                 "Exclude files matching the pattern '{}' from the transfer.",
                 pattern
-            ),
+            )
+        };
+        arguments.push(ArgumentExplanation {
+            argument: format!("--exclude={}", pattern),
+            description,
             category: ArgCategory::Pattern,
         });
     }
@@ -207,6 +294,77 @@ pub fn explain_command(parsed: &ParsedCommand) -> CommandExplanation {
         });
     }
 
+    // Explain compression choice and level
+    if let Some(ref choice) = parsed.compress_choice {
+        arguments.push(ArgumentExplanation {
+            argument: format!("--compress-choice={}", choice),
+            description: format!(
+                "Compresses data with the '{}' algorithm when compression is enabled. zstd and lz4 are much faster than the default zlib.",
+                choice
+            ),
+            category: ArgCategory::Performance,
+        });
+    }
+
+    if let Some(level) = parsed.compress_level {
+        arguments.push(ArgumentExplanation {
+            argument: format!("--compress-level={}", level),
+            description: format!(
+                "Sets the compression level to {}. Higher levels save bandwidth at the cost of CPU time.",
+                level
+            ),
+            category: ArgCategory::Performance,
+        });
+    }
+
+    // Explain partial dir
+    if let Some(ref dir) = parsed.partial_dir {
+        arguments.push(ArgumentExplanation {
+            argument: format!("--partial-dir={}", dir),
+            description: format!(
+                "Keeps partially transferred files in '{}' until they are complete, then moves them into place. Implies --partial, so interrupted transfers resume without leaving half-files among the finished ones.",
+                dir
+            ),
+            category: ArgCategory::FileHandling,
+        });
+    }
+
+    // Explain deletion cap
+    if let Some(max) = parsed.max_delete {
+        arguments.push(ArgumentExplanation {
+            argument: format!("--max-delete={}", max),
+            description: format!(
+                "Deletes at most {} file(s) on the destination. Once the limit is reached rsync skips further deletions and exits with code 25, guarding against accidental mass deletion.",
+                max
+            ),
+            category: ArgCategory::Deletion,
+        });
+    }
+
+    // Explain timestamp tolerance
+    if let Some(secs) = parsed.modify_window {
+        arguments.push(ArgumentExplanation {
+            argument: format!("--modify-window={}", secs),
+            description: format!(
+                "Treats modification times up to {} second(s) apart as equal. Essential for FAT/exFAT and SMB destinations, which store times with 2-second (or coarser) precision: without it rsync sees every file as changed and transfers it again on each run.",
+                secs
+            ),
+            category: ArgCategory::FileHandling,
+        });
+    }
+
+    // Explain checksum algorithm
+    if let Some(ref choice) = parsed.checksum_choice {
+        arguments.push(ArgumentExplanation {
+            argument: format!("--checksum-choice={}", choice),
+            description: format!(
+                "Uses the '{}' checksum algorithm for delta transfers and --checksum comparisons. xxh64/xxh3 are much faster than md5; md4 and md5 work with older rsync versions, which only know those. Both ends must support the algorithm.",
+                choice
+            ),
+            category: ArgCategory::FileHandling,
+        });
+    }
+
     // Explain link-dest
     if let Some(ref link_dest) = parsed.link_dest {
         arguments.push(ArgumentExplanation {
@@ -235,7 +393,7 @@ pub fn explain_command(parsed: &ParsedCommand) -> CommandExplanation {
     if let Some(ref source) = parsed.source {
         arguments.push(ArgumentExplanation {
             argument: source.clone(),
-            description: format!("Source: files will be read from '{}'.", source),
+            description: describe_source(source, parsed.flags.contains(&"relative".to_string())),
             category: ArgCategory::Path,
         });
     }
@@ -249,21 +407,40 @@ pub fn explain_command(parsed: &ParsedCommand) -> CommandExplanation {
         });
     }
 
-    // Explain custom/unknown args
+    // Explain custom args, recognizing well-known options the parser passed through
     for arg in &parsed.custom_args {
+        let (description, category) = explain_custom_arg(arg)
+            .unwrap_or_else(|| (UNRECOGNIZED.to_string(), ArgCategory::Unknown));
         arguments.push(ArgumentExplanation {
             argument: arg.clone(),
-            description: "Argument not recognized. Please check the rsync manual for more details.".to_string(),
-            category: ArgCategory::Unknown,
+            description,
+            category,
         });
     }
 
     // Build summary
     let summary = build_summary(parsed);
 
-    CommandExplanation {
-        arguments,
-        summary,
+    CommandExplanation { arguments, summary }
+}
+
+/// Describe the source path, including which part of it `--relative`
+/// recreates under the destination.
+fn describe_source(source: &str, relative: bool) -> String {
+    let read_from = format!("Source: files will be read from '{}'.", source);
+    if !relative {
+        return read_from;
+    }
+    match source.split_once("/./") {
+        Some((_, kept)) => format!(
+            "{} With --relative, '{}' is recreated under the destination.",
+            read_from, kept
+        ),
+        None => format!(
+            "{} With --relative, the full path '{}' is recreated under the destination.",
+            read_from,
+            source.trim_end_matches('/')
+        ),
     }
 }
 
@@ -287,11 +464,34 @@ fn build_summary(parsed: &ParsedCommand) -> String {
         parts.push("Transfers files from source to destination.".to_string());
     }
 
+    if let Some(max) = parsed.max_delete {
+        parts.push(format!("No more than {} file(s) will be deleted.", max));
+    }
+
     if has_compress {
-        parts.push("Data is compressed during transfer.".to_string());
+        match parsed.compress_choice {
+            Some(ref choice) => parts.push(format!(
+                "Data is compressed with {} during transfer.",
+                choice
+            )),
+            None => parts.push("Data is compressed during transfer.".to_string()),
+        }
     }
 
-    if !parsed.exclude_patterns.is_empty() {
+    if has_system_file_excludes(&parsed.exclude_patterns) {
+        let others = parsed
+            .exclude_patterns
+            .iter()
+            .filter(|p| !SYSTEM_FILE_EXCLUDES.contains(&p.as_str()))
+            .count();
+        parts.push(
+            "System and NAS junk files (.DS_Store, Thumbs.db, @eaDir, ...) are excluded."
+                .to_string(),
+        );
+        if others > 0 {
+            parts.push(format!("{} other pattern(s) are excluded.", others));
+        }
+    } else if !parsed.exclude_patterns.is_empty() {
         parts.push(format!(
             "{} pattern(s) are excluded.",
             parsed.exclude_patterns.len()
@@ -312,3 +512,83 @@ fn build_summary(parsed: &ParsedCommand) -> String {
 
     parts.join(" ")
 }
+
+/// Describe in plain language where the source ends up, following rsync's
+/// trailing-slash rule: `src/` copies the contents of `src`, while `src`
+/// copies the folder itself. `auto_slash` mirrors the auto trailing slash
+/// setting, which [`build_rsync_args`](crate::services::command_builder::build_rsync_args)
+/// applies to a source typed without one.
+pub fn explain_trailing_slash(
+    source: &StorageLocation,
+    destination: &StorageLocation,
+    mode: &BackupMode,
+    auto_slash: bool,
+) -> String {
+    let source_path = source.to_rsync_path();
+    let source_dir = without_trailing_slash(&source_path);
+    let name = source_dir.rsplit(['/', ':']).next().unwrap_or_default();
+
+    let dest_path = destination.to_rsync_path();
+    let target = match mode {
+        BackupMode::Snapshot { .. } => format!("{}/<snapshot>", dest_path.trim_end_matches('/')),
+        _ => without_trailing_slash(&dest_path).to_string(),
+    };
+
+    let typed_slash = source_path.ends_with('/');
+    if typed_slash || name.is_empty() {
+        format!(
+            "The contents of {} will be placed directly into {}.",
+            source_dir, target
+        )
+    } else if auto_slash {
+        format!(
+            "The contents of {} will be placed directly into {}. A trailing slash is added \
+             automatically; turn off auto trailing slash in Settings to copy the {} folder itself.",
+            source_dir, target, name
+        )
+    } else {
+        format!(
+            "A {}/{} folder will be created, holding {}. Add a trailing slash to the source \
+             to copy only its contents.",
+            target.trim_end_matches('/'),
+            name,
+            source_dir
+        )
+    }
+}
+
+/// Describe a job's bandwidth schedule as of `now`: the limit a run started
+/// now would get, and when and to what it next changes. `None` when the job
+/// has no schedule.
+pub fn explain_bandwidth_schedule(advanced: &AdvancedOptions, now: NaiveTime) -> Option<String> {
+    if advanced.bandwidth_schedule.is_empty() {
+        return None;
+    }
+    let current = describe_bwlimit(bandwidth_limit_at(advanced, now));
+    Some(match next_bandwidth_change(advanced, now) {
+        Some((at, limit)) => format!(
+            "Bandwidth right now: {}. From {} it changes to {}. A run keeps the limit it \
+             started with.",
+            current,
+            at.format("%H:%M"),
+            describe_bwlimit(limit)
+        ),
+        None => format!("Bandwidth right now: {}, all day.", current),
+    })
+}
+
+/// A `--bwlimit` value (KB/s) for display, or "unlimited".
+fn describe_bwlimit(limit: Option<u64>) -> String {
+    match limit {
+        Some(kb) => format_rate(kb as f64 * 1024.0),
+        None => "unlimited".to_string(),
+    }
+}
+
+/// `path` without its trailing slashes, keeping a bare `/` as is.
+fn without_trailing_slash(path: &str) -> &str {
+    match path.trim_end_matches('/') {
+        "" if path.starts_with('/') => "/",
+        trimmed => trimmed,
+    }
+}
@@ -1,8 +1,10 @@
 use crate::models::command::ParsedCommand;
 use crate::models::job::{
-    AdvancedOptions, CoreTransferOptions, FileHandlingOptions, JobDefinition, MetadataOptions,
-    OutputOptions, RsyncOptions, SshConfig, StorageLocation,
+    AdvancedOptions, CoreTransferOptions, FileHandlingOptions, JobAppearance, JobDefinition,
+    JobRunPolicy, JobSafety, MetadataOptions, OutputOptions, RsyncOptions, SshConfig,
+    StorageLocation,
 };
+use crate::services::command_builder::{has_system_file_excludes, SYSTEM_FILE_EXCLUDES};
 
 /// Parse an rsync command string into its component parts.
 ///
@@ -26,6 +28,12 @@ pub fn parse_rsync_command(command: &str) -> Result<ParsedCommand, String> {
     let mut exclude_patterns = Vec::new();
     let mut include_patterns = Vec::new();
     let mut bandwidth_limit: Option<u64> = None;
+    let mut compress_choice: Option<String> = None;
+    let mut compress_level: Option<i32> = None;
+    let mut max_delete: Option<u32> = None;
+    let mut modify_window: Option<u32> = None;
+    let mut checksum_choice: Option<String> = None;
+    let mut partial_dir: Option<String> = None;
     let mut ssh_command: Option<String> = None;
     let mut link_dest: Option<String> = None;
     let mut custom_args = Vec::new();
@@ -50,6 +58,12 @@ pub fn parse_rsync_command(command: &str) -> Result<ParsedCommand, String> {
                     &mut exclude_patterns,
                     &mut include_patterns,
                     &mut bandwidth_limit,
+                    &mut compress_choice,
+                    &mut compress_level,
+                    &mut max_delete,
+                    &mut modify_window,
+                    &mut checksum_choice,
+                    &mut partial_dir,
                     &mut link_dest,
                     &mut custom_args,
                 );
@@ -107,6 +121,12 @@ pub fn parse_rsync_command(command: &str) -> Result<ParsedCommand, String> {
         exclude_patterns,
         include_patterns,
         bandwidth_limit,
+        compress_choice,
+        compress_level,
+        max_delete,
+        modify_window,
+        checksum_choice,
+        partial_dir,
         ssh_command,
         link_dest,
         custom_args,
@@ -247,6 +267,12 @@ fn handle_long_with_value(
     exclude_patterns: &mut Vec<String>,
     include_patterns: &mut Vec<String>,
     bandwidth_limit: &mut Option<u64>,
+    compress_choice: &mut Option<String>,
+    compress_level: &mut Option<i32>,
+    max_delete: &mut Option<u32>,
+    modify_window: &mut Option<u32>,
+    checksum_choice: &mut Option<String>,
+    partial_dir: &mut Option<String>,
     link_dest: &mut Option<String>,
     custom_args: &mut Vec<String>,
 ) {
@@ -259,7 +285,16 @@ fn handle_long_with_value(
                 custom_args.push(format!("--bwlimit={}", value));
             }
         }
+        "compress-choice" | "zc" => *compress_choice = Some(value.to_string()),
+        "checksum-choice" | "cc" => *checksum_choice = Some(value.to_string()),
+        "compress-level" | "zl" => {
+            *compress_level = value.parse().ok();
+            if compress_level.is_none() {
+                custom_args.push(format!("--{}={}", key, value));
+            }
+        }
         "link-dest" => *link_dest = Some(value.to_string()),
+        "partial-dir" => *partial_dir = Some(value.to_string()),
         "backup-dir" => {
             flags.push("backup_dir".to_string());
             custom_args.push(format!("--backup-dir={}", value));
@@ -290,8 +325,17 @@ fn handle_long_with_value(
             custom_args.push(format!("--min-size={}", value));
         }
         "max-delete" => {
-            flags.push("max_delete".to_string());
-            custom_args.push(format!("--max-delete={}", value));
+            *max_delete = value.parse().ok();
+            if max_delete.is_none() {
+                custom_args.push(format!("--max-delete={}", value));
+            }
+        }
+        // A negative window (nanosecond-exact, -1) has no field and stays custom
+        "modify-window" => {
+            *modify_window = value.parse().ok();
+            if modify_window.is_none() {
+                custom_args.push(format!("--modify-window={}", value));
+            }
         }
         "timeout" => {
             flags.push("timeout".to_string());
@@ -341,10 +385,6 @@ fn handle_long_with_value(
             flags.push("chown".to_string());
             custom_args.push(format!("--chown={}", value));
         }
-        "compress-level" => {
-            flags.push("compress_level".to_string());
-            custom_args.push(format!("--compress-level={}", value));
-        }
         "skip-compress" => {
             flags.push("skip_compress".to_string());
             custom_args.push(format!("--skip-compress={}", value));
@@ -365,6 +405,10 @@ fn handle_long_with_value(
             flags.push("iconv".to_string());
             custom_args.push(format!("--iconv={}", value));
         }
+        "password-file" => {
+            flags.push("password_file".to_string());
+            custom_args.push(format!("--password-file={}", value));
+        }
         "rsh" | "log-file-format" => {
             custom_args.push(format!("--{}={}", key, value));
         }
@@ -372,16 +416,35 @@ fn handle_long_with_value(
     }
 }
 
+/// Parse `[user@]host/module/path` into a daemon location.
+fn parse_daemon_location(rest: &str) -> StorageLocation {
+    let parts: Vec<&str> = rest.splitn(3, '/').collect();
+    let authority = parts.first().unwrap_or(&"");
+    let (username, host) = match authority.rsplit_once('@') {
+        Some((user, host)) => (Some(user.to_string()), host),
+        None => (None, *authority),
+    };
+    StorageLocation::RemoteRsync {
+        host: host.to_string(),
+        module: parts.get(1).unwrap_or(&"").to_string(),
+        path: parts.get(2).unwrap_or(&"").to_string(),
+        username,
+        password_secret: None,
+    }
+}
+
 /// Parse a path string into a StorageLocation.
 pub fn parse_storage_location(path: &str) -> StorageLocation {
-    // rsync://host/module/path
+    // rsync://[user@]host/module/path
     if let Some(rest) = path.strip_prefix("rsync://") {
-        let parts: Vec<&str> = rest.splitn(3, '/').collect();
-        return StorageLocation::RemoteRsync {
-            host: parts.first().unwrap_or(&"").to_string(),
-            module: parts.get(1).unwrap_or(&"").to_string(),
-            path: parts.get(2).unwrap_or(&"").to_string(),
-        };
+        return parse_daemon_location(rest);
+    }
+
+    // [user@]host::module/path (daemon, double colon)
+    if let Some((host, rest)) = path.split_once("::") {
+        if !host.contains('/') {
+            return parse_daemon_location(&format!("{}/{}", host, rest));
+        }
     }
 
     // user@host:path (SSH)
@@ -446,11 +509,25 @@ pub fn to_job_definition(parsed: &ParsedCommand) -> Result<JobDefinition, String
 
     let has = |name: &str| parsed.flags.contains(&name.to_string());
 
+    // A command carrying the whole system-file list maps back to the toggle
+    let exclude_system_files = has_system_file_excludes(&parsed.exclude_patterns);
+    let exclude_patterns = if exclude_system_files {
+        parsed
+            .exclude_patterns
+            .iter()
+            .filter(|p| !SYSTEM_FILE_EXCLUDES.contains(&p.as_str()))
+            .cloned()
+            .collect()
+    } else {
+        parsed.exclude_patterns.clone()
+    };
+
     let options = RsyncOptions {
         core_transfer: CoreTransferOptions {
             archive: has("archive"),
             compress: has("compress"),
             partial: has("partial"),
+            partial_dir: parsed.partial_dir.clone(),
             dry_run: has("dry_run"),
         },
         file_handling: FileHandlingOptions {
@@ -461,6 +538,9 @@ pub fn to_job_definition(parsed: &ParsedCommand) -> Result<JobDefinition, String
             whole_file: has("whole_file"),
             ignore_existing: has("ignore_existing"),
             one_file_system: has("one_file_system"),
+            max_delete: parsed.max_delete,
+            modify_window: parsed.modify_window,
+            checksum_choice: parsed.checksum_choice.clone(),
         },
         metadata: MetadataOptions {
             hard_links: has("hard_links"),
@@ -476,9 +556,13 @@ pub fn to_job_definition(parsed: &ParsedCommand) -> Result<JobDefinition, String
             itemize_changes: has("itemize_changes"),
         },
         advanced: AdvancedOptions {
-            exclude_patterns: parsed.exclude_patterns.clone(),
+            exclude_patterns,
+            exclude_system_files,
             include_patterns: parsed.include_patterns.clone(),
             bandwidth_limit: parsed.bandwidth_limit,
+            bandwidth_schedule: Vec::new(),
+            compress_choice: parsed.compress_choice.clone(),
+            compress_level: parsed.compress_level,
             custom_args: parsed.custom_args.clone(),
         },
     };
@@ -508,6 +592,9 @@ pub fn to_job_definition(parsed: &ParsedCommand) -> Result<JobDefinition, String
         },
         options,
         ssh_config,
+        appearance: JobAppearance::default(),
+        run_policy: JobRunPolicy::default(),
+        safety: JobSafety::default(),
         schedule: None,
         enabled: true,
         created_at: now,
@@ -516,6 +603,34 @@ pub fn to_job_definition(parsed: &ParsedCommand) -> Result<JobDefinition, String
 }
 
 /// Parse an SSH command string (from -e flag) into an SshConfig.
+/// Apply a `-o Key=Value` option that `SshConfig` models. Returns false for
+/// anything else so the caller can fall back to a custom command.
+fn apply_ssh_option(config: &mut SshConfig, opt: &str) -> bool {
+    let (key, value) = match opt.split_once('=') {
+        Some(kv) => kv,
+        None => return false,
+    };
+    match key {
+        "StrictHostKeyChecking" if value == "no" => {
+            config.strict_host_key_checking = false;
+            true
+        }
+        "ConnectTimeout" => value
+            .parse()
+            .map(|secs| config.connect_timeout = Some(secs))
+            .is_ok(),
+        "ServerAliveInterval" => value
+            .parse()
+            .map(|secs| config.server_alive_interval = Some(secs))
+            .is_ok(),
+        "ProxyJump" => {
+            config.jump_host = Some(value.to_string());
+            true
+        }
+        _ => false,
+    }
+}
+
 pub(crate) fn parse_ssh_command(cmd: &str) -> Option<SshConfig> {
     let parts = shell_words::split(cmd).ok()?;
     let mut config = SshConfig::default();
@@ -542,11 +657,11 @@ pub(crate) fn parse_ssh_command(cmd: &str) -> Option<SshConfig> {
             }
         } else if part == "-i" {
             config.identity_file = iter.next().cloned();
+        } else if part == "-J" {
+            config.jump_host = iter.next().cloned();
         } else if part == "-o" {
             if let Some(opt) = iter.next() {
-                if opt == "StrictHostKeyChecking=no" {
-                    config.strict_host_key_checking = false;
-                } else {
+                if !apply_ssh_option(&mut config, opt) {
                     has_custom = true;
                 }
             }
@@ -2,3 +2,5 @@ pub mod command_builder;
 pub mod command_explainer;
 pub mod command_parser;
 pub mod itemize_parser;
+pub mod pattern_tester;
+pub mod rsync_glob;
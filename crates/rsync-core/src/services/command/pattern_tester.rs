@@ -0,0 +1,85 @@
+use std::path::{Path, PathBuf};
+
+use crate::file_system::{FileSystem, FsError};
+use crate::models::pattern::{Matched, PatternTestEntry};
+use crate::services::command::rsync_glob;
+
+/// Classify a single path (relative to the transfer root) against the filter
+/// rules, in the order the command builder emits them: excludes, then includes.
+/// The first matching rule wins, as in rsync.
+fn classify(rel_path: &str, is_dir: bool, excludes: &[String], includes: &[String]) -> Matched {
+    if let Some(p) = excludes
+        .iter()
+        .find(|p| rsync_glob::matches(p, rel_path, is_dir))
+    {
+        return Matched::Excluded { pattern: p.clone() };
+    }
+    if let Some(p) = includes
+        .iter()
+        .find(|p| rsync_glob::matches(p, rel_path, is_dir))
+    {
+        return Matched::Included { pattern: p.clone() };
+    }
+    Matched::Unmatched
+}
+
+/// Walk `source_dir` and classify every entry against the include/exclude
+/// patterns, returning entries with paths relative to `source_dir`.
+///
+/// Entries inside an excluded directory are reported as excluded by the
+/// directory's pattern, since rsync never descends into it.
+pub fn test_pattern_entries(
+    fs: &dyn FileSystem,
+    source_dir: &Path,
+    includes: &[String],
+    excludes: &[String],
+) -> Result<Vec<PatternTestEntry>, FsError> {
+    let mut entries = Vec::new();
+    let mut excluded_dirs: Vec<(String, String)> = Vec::new();
+
+    for path in fs.walk_dir(source_dir)? {
+        let rel = match path.strip_prefix(source_dir) {
+            Ok(r) => r.to_string_lossy().replace('\\', "/"),
+            Err(_) => continue,
+        };
+        let is_dir = fs.is_dir(&path);
+
+        let inherited = excluded_dirs
+            .iter()
+            .find(|(dir, _)| rel.starts_with(&format!("{}/", dir)));
+        let matched = match inherited {
+            Some((_, pattern)) => Matched::Excluded {
+                pattern: pattern.clone(),
+            },
+            None => classify(&rel, is_dir, excludes, includes),
+        };
+
+        if is_dir {
+            if let Matched::Excluded { ref pattern } = matched {
+                excluded_dirs.push((rel.clone(), pattern.clone()));
+            }
+        }
+
+        entries.push(PatternTestEntry {
+            path: rel,
+            is_dir,
+            matched,
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Walk `source_dir` and classify each entry against the patterns.
+/// See [`test_pattern_entries`] for the matching rules.
+pub fn test_patterns(
+    fs: &dyn FileSystem,
+    source_dir: &Path,
+    includes: &[String],
+    excludes: &[String],
+) -> Result<Vec<(PathBuf, Matched)>, FsError> {
+    Ok(test_pattern_entries(fs, source_dir, includes, excludes)?
+        .into_iter()
+        .map(|e| (PathBuf::from(e.path), e.matched))
+        .collect())
+}
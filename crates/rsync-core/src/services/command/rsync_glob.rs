@@ -0,0 +1,156 @@
+/// Returns true if the rsync filter `pattern` matches `path`, a path relative to
+/// the transfer root using `/` separators.
+///
+/// Follows the rsync man page rules rather than shell globbing: a leading `/`
+/// anchors to the transfer root, a trailing `/` matches directories only, `*`
+/// stops at slashes while `**` does not, and `dir/***` matches both `dir` and
+/// everything inside it. Patterns containing `/` or `**` are matched against
+/// the full path, others only against the final component.
+pub fn matches(pattern: &str, path: &str, is_dir: bool) -> bool {
+    let (pattern, dir_only) = match pattern.strip_suffix('/') {
+        Some(stripped) => (stripped, true),
+        None => (pattern, false),
+    };
+    if dir_only && !is_dir {
+        return false;
+    }
+    let (pattern, anchored) = match pattern.strip_prefix('/') {
+        Some(stripped) => (stripped, true),
+        None => (pattern, false),
+    };
+    if pattern.is_empty() {
+        return false;
+    }
+
+    let path = path.trim_start_matches('/').trim_end_matches('/');
+    let full_path = anchored || pattern.contains('/') || pattern.contains("**");
+    let target = if full_path {
+        path
+    } else {
+        path.rsplit('/').next().unwrap_or(path)
+    };
+
+    // Backslashes only escape when the pattern actually uses wildcards;
+    // otherwise the pattern is compared literally.
+    if !pattern.contains(['*', '?', '[']) {
+        return if full_path && !anchored {
+            target == pattern || target.ends_with(&format!("/{}", pattern))
+        } else {
+            target == pattern
+        };
+    }
+
+    let pat: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = target.chars().collect();
+    if !full_path || anchored {
+        return wildmatch(&pat, &text);
+    }
+
+    // Floating path patterns may start at any directory boundary.
+    if wildmatch(&pat, &text) {
+        return true;
+    }
+    text.iter()
+        .enumerate()
+        .filter(|(_, c)| **c == '/')
+        .any(|(i, _)| wildmatch(&pat, &text[i + 1..]))
+}
+
+fn wildmatch(pat: &[char], text: &[char]) -> bool {
+    match pat.first() {
+        None => text.is_empty(),
+        // `dir/***` matches the directory itself as well as its contents.
+        Some('/') if pat[1..] == ['*', '*', '*'] => text.is_empty() || text[0] == '/',
+        Some('*') => {
+            if pat.get(1) == Some(&'*') {
+                let rest = &pat[pat.iter().take_while(|c| **c == '*').count()..];
+                (0..=text.len()).any(|i| wildmatch(rest, &text[i..]))
+            } else {
+                let rest = &pat[1..];
+                for i in 0..=text.len() {
+                    if wildmatch(rest, &text[i..]) {
+                        return true;
+                    }
+                    if i < text.len() && text[i] == '/' {
+                        break;
+                    }
+                }
+                false
+            }
+        }
+        Some('?') => match text.first() {
+            Some(c) if *c != '/' => wildmatch(&pat[1..], &text[1..]),
+            _ => false,
+        },
+        Some('[') => match text.first() {
+            Some(c) => match match_class(&pat[1..], *c) {
+                Some((matched, len)) => matched && wildmatch(&pat[1 + len..], &text[1..]),
+                // No closing bracket: treat `[` literally.
+                None => *c == '[' && wildmatch(&pat[1..], &text[1..]),
+            },
+            None => false,
+        },
+        Some('\\') if pat.len() > 1 => {
+            text.first() == Some(&pat[1]) && wildmatch(&pat[2..], &text[1..])
+        }
+        Some(p) => text.first() == Some(p) && wildmatch(&pat[1..], &text[1..]),
+    }
+}
+
+/// Match `c` against a character class body (the part after `[`).
+///
+/// Returns whether it matched and how many pattern characters the class
+/// consumed, including the closing `]`, or `None` if the class is unterminated.
+fn match_class(pat: &[char], c: char) -> Option<(bool, usize)> {
+    let mut i = 0;
+    let negate = matches!(pat.first(), Some('!') | Some('^'));
+    if negate {
+        i += 1;
+    }
+    let mut matched = false;
+    let mut first = true;
+
+    while i < pat.len() {
+        let ch = pat[i];
+        if ch == ']' && !first {
+            return Some((matched != negate && c != '/', i + 1));
+        }
+        first = false;
+
+        if ch == '[' && pat.get(i + 1) == Some(&':') {
+            let rest: String = pat[i + 2..].iter().collect();
+            if let Some(end) = rest.find(":]") {
+                let name = &rest[..end];
+                matched |= match name {
+                    "alnum" => c.is_alphanumeric(),
+                    "alpha" => c.is_alphabetic(),
+                    "digit" => c.is_ascii_digit(),
+                    "lower" => c.is_lowercase(),
+                    "upper" => c.is_uppercase(),
+                    "space" => c.is_whitespace(),
+                    "punct" => c.is_ascii_punctuation(),
+                    "xdigit" => c.is_ascii_hexdigit(),
+                    _ => false,
+                };
+                i += 2 + name.chars().count() + 2;
+                continue;
+            }
+        }
+
+        let lo = if ch == '\\' && i + 1 < pat.len() {
+            i += 1;
+            pat[i]
+        } else {
+            ch
+        };
+        if i + 2 < pat.len() && pat[i + 1] == '-' && pat[i + 2] != ']' {
+            let hi = pat[i + 2];
+            matched |= lo <= c && c <= hi;
+            i += 3;
+        } else {
+            matched |= lo == c;
+            i += 1;
+        }
+    }
+    None
+}
@@ -0,0 +1,62 @@
+use chrono::{DateTime, Duration, Utc};
+
+use crate::error::JobServiceError;
+use crate::models::backup::InvocationStatus;
+use crate::models::dashboard::{DashboardSummary, OverdueJob};
+use crate::models::progress::RunningJobInfo;
+use crate::services::job_service::JobService;
+use crate::services::statistics_service::StatisticsService;
+
+/// How late a scheduled run must be before the dashboard calls it overdue.
+pub const OVERDUE_GRACE: Duration = Duration::hours(1);
+
+/// Assemble the dashboard at `now`. `running` comes from the executor, which
+/// is the only place that knows live progress.
+pub fn dashboard_summary(
+    job_service: &JobService,
+    statistics: &StatisticsService,
+    running: Vec<RunningJobInfo>,
+    now: DateTime<Utc>,
+) -> Result<DashboardSummary, JobServiceError> {
+    let jobs = job_service.list_jobs()?;
+    let overdue = job_service
+        .overdue_jobs(now, OVERDUE_GRACE)?
+        .into_iter()
+        .map(|(job, late_by)| OverdueJob {
+            job_id: job.id,
+            name: job.name,
+            overdue_secs: late_by.num_seconds(),
+        })
+        .collect();
+
+    let day_ago = now - Duration::hours(24);
+    let (mut succeeded_last_24h, mut failed_last_24h) = (0, 0);
+    for inv in job_service.list_all_invocations()? {
+        if inv.started_at <= day_ago {
+            continue;
+        }
+        match inv.status {
+            InvocationStatus::Succeeded => succeeded_last_24h += 1,
+            InvocationStatus::Failed | InvocationStatus::StoppedByLimit => failed_last_24h += 1,
+            InvocationStatus::Running | InvocationStatus::Cancelled => {}
+        }
+    }
+
+    let week_ago = now - Duration::days(7);
+    let bytes_last_7d = statistics
+        .list_all()?
+        .iter()
+        .filter(|stat| stat.recorded_at > week_ago)
+        .map(|stat| stat.bytes_transferred)
+        .sum();
+
+    Ok(DashboardSummary {
+        total_jobs: jobs.len(),
+        enabled_jobs: jobs.iter().filter(|job| job.enabled).count(),
+        running,
+        overdue,
+        succeeded_last_24h,
+        failed_last_24h,
+        bytes_last_7d,
+    })
+}
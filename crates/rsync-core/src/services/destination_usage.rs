@@ -0,0 +1,32 @@
+use std::path::Path;
+
+use crate::error::AppError;
+use crate::file_system::FileSystem;
+use crate::models::job::JobDefinition;
+use crate::models::statistics::DestinationUsage;
+use crate::services::preflight::verify_mount;
+
+/// Measure how much space `job`'s destination occupies on disk.
+///
+/// For snapshot jobs this is the whole snapshot root, so `physical_bytes`
+/// shows what the hard-linked snapshots really cost. Returns `None` when the
+/// size is unknown: remote destinations, unmounted volumes and local paths
+/// that do not exist yet.
+pub fn destination_usage(
+    job: &JobDefinition,
+    fs: &dyn FileSystem,
+) -> Result<Option<DestinationUsage>, AppError> {
+    let destination = &job.transfer.destination;
+    let Some(path) = destination.local_path() else {
+        return Ok(None);
+    };
+    let dir = Path::new(path);
+    if verify_mount(destination, fs).is_err() || !fs.is_dir(dir) {
+        return Ok(None);
+    }
+    Ok(Some(DestinationUsage {
+        path: path.to_string(),
+        logical_bytes: fs.dir_size(dir)?,
+        physical_bytes: fs.unique_dir_size(dir)?,
+    }))
+}
@@ -0,0 +1,79 @@
+use uuid::Uuid;
+
+use crate::models::backup_set::{BackupSet, BackupSetRun, SetJobOutcome, SetJobResult};
+use crate::models::job::JobDefinition;
+use crate::models::progress::JobStatusEvent;
+use crate::services::job_service::JobService;
+
+/// Runs one job of a set to completion. `JobExecutor` implements it; tests
+/// substitute a fake so no rsync process is needed.
+pub trait SetJobRunner {
+    /// Start `job` and block until it finishes, returning its final status.
+    fn run_to_completion(&self, job: &JobDefinition) -> Result<JobStatusEvent, String>;
+}
+
+/// Run the jobs of `set` one after another, in order.
+///
+/// With `stop_on_failure`, the first job that fails, is cancelled or can't
+/// start marks every later job `Skipped`; otherwise each job runs regardless
+/// of failures. A cancelled job always stops the set, so cancelling (or
+/// `cancel_all` on shutdown) doesn't just move on to the next job.
+pub fn run_backup_set(
+    set: &BackupSet,
+    job_service: &JobService,
+    runner: &dyn SetJobRunner,
+    stop_on_failure: bool,
+) -> BackupSetRun {
+    let mut results = Vec::with_capacity(set.job_ids.len());
+    let mut stopped = false;
+
+    for job_id in &set.job_ids {
+        if stopped {
+            results.push(SetJobResult {
+                job_id: *job_id,
+                job_name: job_name(job_service, job_id),
+                outcome: SetJobOutcome::Skipped,
+            });
+            continue;
+        }
+
+        let (job_name, outcome) = match job_service.get_job(job_id) {
+            Ok(job) => {
+                let outcome = match runner.run_to_completion(&job) {
+                    Ok(event) => SetJobOutcome::Finished {
+                        invocation_id: event.invocation_id,
+                        status: event.status,
+                        error_message: event.error_message,
+                    },
+                    Err(message) => SetJobOutcome::NotStarted { message },
+                };
+                (job.name, outcome)
+            }
+            Err(e) => (
+                String::new(),
+                SetJobOutcome::NotStarted {
+                    message: e.to_string(),
+                },
+            ),
+        };
+
+        stopped = outcome.is_cancelled() || (stop_on_failure && !outcome.is_success());
+        results.push(SetJobResult {
+            job_id: *job_id,
+            job_name,
+            outcome,
+        });
+    }
+
+    BackupSetRun {
+        set_id: set.id,
+        results,
+    }
+}
+
+fn job_name(job_service: &JobService, job_id: &Uuid) -> String {
+    job_service
+        .get_job(job_id)
+        .map(|job| job.name)
+        .unwrap_or_default()
+}
@@ -0,0 +1,61 @@
+use crate::models::itemize::ItemizeSummary;
+use crate::models::job::{ChangeAlarm, ChangeProportions};
+
+/// Work out a run's change proportions from its itemize summary and the
+/// number of files rsync considered. `None` when there was nothing to
+/// compare against.
+pub fn change_proportions(summary: &ItemizeSummary, total_files: u64) -> Option<ChangeProportions> {
+    let existing = total_files.saturating_sub(summary.created) + summary.deleted;
+    if total_files == 0 && existing == 0 {
+        return None;
+    }
+    Some(ChangeProportions {
+        changed_percent: percent(summary.created + summary.updated, total_files),
+        deleted_percent: percent(summary.deleted, existing),
+    })
+}
+
+/// A summary for runs without itemized output: every transferred file
+/// counts as updated.
+pub fn summary_from_stats(files_transferred: u64, files_deleted: u64) -> ItemizeSummary {
+    ItemizeSummary {
+        total: files_transferred + files_deleted,
+        updated: files_transferred,
+        deleted: files_deleted,
+        ..Default::default()
+    }
+}
+
+/// The warning to attach to the run when a threshold is exceeded, or
+/// `None` when the run stayed within the alarm.
+pub fn check_change_alarm(alarm: &ChangeAlarm, proportions: &ChangeProportions) -> Option<String> {
+    let mut reasons = Vec::new();
+    if let Some(max) = alarm.max_changed_percent {
+        if proportions.changed_percent > max {
+            reasons.push(format!(
+                "{:.1}% of files changed (limit {}%)",
+                proportions.changed_percent, max
+            ));
+        }
+    }
+    if let Some(max) = alarm.max_deleted_percent {
+        if proportions.deleted_percent > max {
+            reasons.push(format!(
+                "{:.1}% of destination files deleted (limit {}%)",
+                proportions.deleted_percent, max
+            ));
+        }
+    }
+    if reasons.is_empty() {
+        None
+    } else {
+        Some(format!("Change alarm: {}", reasons.join(", ")))
+    }
+}
+
+fn percent(part: u64, whole: u64) -> f64 {
+    if whole == 0 {
+        return 0.0;
+    }
+    part as f64 * 100.0 / whole as f64
+}
@@ -0,0 +1,61 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use uuid::Uuid;
+
+use crate::models::job::{JobDefinition, StorageLocation};
+
+/// Name of the stored daemon password the job's rsync daemon location
+/// authenticates with, if any.
+pub fn daemon_secret_name(job: &JobDefinition) -> Option<&str> {
+    [&job.transfer.source, &job.transfer.destination]
+        .into_iter()
+        .find_map(|location| match location {
+            StorageLocation::RemoteRsync {
+                password_secret: Some(name),
+                ..
+            } => Some(name.as_str()),
+            _ => None,
+        })
+}
+
+/// A daemon password written to a private file for one run, passed to rsync
+/// as `--password-file` and deleted when dropped.
+pub struct PasswordFile {
+    path: PathBuf,
+}
+
+impl PasswordFile {
+    pub fn create(dir: &Path, invocation_id: Uuid, password: &str) -> std::io::Result<Self> {
+        let path = dir.join(format!("rsync-studio-{}.pass", invocation_id));
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            // rsync refuses a password file that other users can read
+            options.mode(0o600);
+        }
+        let mut file = options.open(&path)?;
+        // Own the path before writing so a failed write still cleans up
+        let password_file = Self { path };
+        writeln!(file, "{}", password)?;
+        Ok(password_file)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The `--password-file=PATH` argument for rsync.
+    pub fn arg(&self) -> String {
+        format!("--password-file={}", self.path.display())
+    }
+}
+
+impl Drop for PasswordFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
@@ -0,0 +1,119 @@
+use crate::error::AppError;
+use crate::models::job::{DestinationPrep, JobDefinition, RsyncOptions, SshConfig, StorageLocation};
+use crate::services::runtime_limit::parse_rsync_version;
+use crate::ssh_client::SshClient;
+
+/// Makes rsync create missing destination directories itself (rsync 3.2.3+).
+pub const MKPATH_ARG: &str = "--mkpath";
+
+/// First rsync release with `--mkpath`.
+const MKPATH_MIN_VERSION: (u32, u32, u32) = (3, 2, 3);
+
+/// Whether the rsync that printed `version_output` understands `--mkpath`.
+pub fn supports_mkpath(version_output: &str) -> bool {
+    parse_rsync_version(version_output).is_some_and(|v| v >= MKPATH_MIN_VERSION)
+}
+
+/// What to do before running `job` against `destination` (the effective
+/// destination, which for snapshot jobs is the new snapshot directory).
+///
+/// Local destinations are covered by preflight, so only remote ones get a
+/// step. SSH destinations are created with `mkdir -p` over SSH; daemon
+/// destinations, custom SSH commands and dry runs (which must not create
+/// anything) fall back to `--mkpath`.
+pub fn destination_prep(
+    job: &JobDefinition,
+    destination: &StorageLocation,
+) -> Option<DestinationPrep> {
+    if !job.run_policy.create_destination {
+        return None;
+    }
+    match destination {
+        StorageLocation::RemoteSsh {
+            user, host, path, ..
+        } => {
+            let custom_ssh = job
+                .ssh_config
+                .as_ref()
+                .is_some_and(|ssh| ssh.custom_ssh_command.is_some());
+            if job.options.core_transfer.dry_run || custom_ssh {
+                Some(DestinationPrep::Mkpath)
+            } else {
+                Some(DestinationPrep::RemoteMkdir(remote_mkdir_args(
+                    user,
+                    host,
+                    path,
+                    job.ssh_config.as_ref(),
+                )))
+            }
+        }
+        StorageLocation::RemoteRsync { .. } => Some(DestinationPrep::Mkpath),
+        StorageLocation::Local { .. } | StorageLocation::Mount { .. } => None,
+    }
+}
+
+/// `ssh` arguments that run `mkdir -p` on `path` at `user@host`, connecting
+/// with the same options `build_rsync_args` puts in rsync's `-e ssh ...`.
+/// `BatchMode` makes ssh fail instead of waiting for a password nobody can
+/// type, and `--` keeps the target from being read as an option.
+pub fn remote_mkdir_args(
+    user: &str,
+    host: &str,
+    path: &str,
+    ssh: Option<&SshConfig>,
+) -> Vec<String> {
+    let mut args = vec!["-o".to_string(), "BatchMode=yes".to_string()];
+    if let Some(ssh) = ssh {
+        if ssh.port != 22 {
+            args.extend(["-p".to_string(), ssh.port.to_string()]);
+        }
+        if let Some(key) = &ssh.identity_file {
+            args.extend(["-i".to_string(), key.clone()]);
+        }
+        if let Some(jump) = &ssh.jump_host {
+            args.extend(["-J".to_string(), jump.clone()]);
+        }
+        if !ssh.strict_host_key_checking {
+            args.extend(["-o".to_string(), "StrictHostKeyChecking=no".to_string()]);
+        }
+        if let Some(secs) = ssh.connect_timeout {
+            args.extend(["-o".to_string(), format!("ConnectTimeout={}", secs)]);
+        }
+        if let Some(secs) = ssh.server_alive_interval {
+            args.extend(["-o".to_string(), format!("ServerAliveInterval={}", secs)]);
+        }
+    }
+    args.push("--".to_string());
+    args.push(if user.is_empty() {
+        host.to_string()
+    } else {
+        format!("{}@{}", user, host)
+    });
+    args.push(format!("mkdir -p -- {}", remote_shell_path(path)));
+    args
+}
+
+/// `path` quoted for the remote shell, leaving a leading `~/` outside the
+/// quotes so the shell still expands it to the home directory.
+fn remote_shell_path(path: &str) -> String {
+    match path.strip_prefix("~/") {
+        Some(rest) => format!("~/{}", shell_words::quote(rest)),
+        None => shell_words::quote(path).into_owned(),
+    }
+}
+
+/// `options` with `--mkpath` added to the custom arguments once.
+pub fn options_with_mkpath(options: &RsyncOptions) -> RsyncOptions {
+    let mut options = options.clone();
+    let custom_args = &mut options.advanced.custom_args;
+    if !custom_args.iter().any(|arg| arg == MKPATH_ARG) {
+        custom_args.push(MKPATH_ARG.to_string());
+    }
+    options
+}
+
+/// Run the `mkdir -p` prepared by [`destination_prep`].
+pub fn create_remote_destination(args: &[String], ssh: &dyn SshClient) -> Result<(), AppError> {
+    ssh.run(args)?;
+    Ok(())
+}
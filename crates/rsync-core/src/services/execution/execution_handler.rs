@@ -9,6 +9,13 @@ use crate::models::progress::{JobStatusEvent, LogLine, ProgressUpdate};
 /// The TUI implements this with `mpsc::Sender`.
 pub trait ExecutionEventHandler: Send + Sync {
     fn on_log_line(&self, log_line: LogLine);
+    /// Several lines at once, as batched by `ThrottledHandler`. Handlers
+    /// that can deliver a batch in one message should override this.
+    fn on_log_lines(&self, log_lines: Vec<LogLine>) {
+        for log_line in log_lines {
+            self.on_log_line(log_line);
+        }
+    }
     fn on_progress(&self, progress: &ProgressUpdate);
     fn on_status_change(&self, status: JobStatusEvent);
     fn on_itemized_change(&self, invocation_id: Uuid, change: &ItemizedChange);
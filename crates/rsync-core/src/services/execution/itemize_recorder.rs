@@ -0,0 +1,61 @@
+use crate::models::itemize::{
+    DifferenceKind, ItemizeSummary, ItemizedChange, StoredItemizedChanges, TransferType,
+};
+
+/// Collects a run's itemized changes for storage, keeping at most `limit`
+/// of them while counting every change in the summary.
+pub struct ItemizeRecorder {
+    limit: usize,
+    changes: Vec<ItemizedChange>,
+    summary: ItemizeSummary,
+}
+
+impl ItemizeRecorder {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            changes: Vec::new(),
+            summary: ItemizeSummary::default(),
+        }
+    }
+
+    pub fn push(&mut self, change: &ItemizedChange) {
+        self.summary.total += 1;
+        if change.transfer_type == TransferType::Message {
+            // rsync only emits `*deleting` message lines in itemize output
+            self.summary.deleted += 1;
+        } else if change.differences.contains(&DifferenceKind::NewlyCreated) {
+            self.summary.created += 1;
+        } else {
+            self.summary.updated += 1;
+        }
+
+        if self.changes.len() < self.limit {
+            self.changes.push(change.clone());
+            self.summary.stored += 1;
+        } else {
+            self.summary.truncated += 1;
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.summary.total == 0
+    }
+
+    /// Counts over every change seen so far.
+    pub fn summary(&self) -> &ItemizeSummary {
+        &self.summary
+    }
+
+    /// `*deleting` lines seen so far, including ones past the storage limit.
+    pub fn deleted(&self) -> u64 {
+        self.summary.deleted
+    }
+
+    pub fn finish(self) -> StoredItemizedChanges {
+        StoredItemizedChanges {
+            summary: self.summary,
+            changes: self.changes,
+        }
+    }
+}
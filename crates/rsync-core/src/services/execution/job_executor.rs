@@ -1,26 +1,85 @@
-use std::io::Write;
-use std::sync::Arc;
+use std::path::Path;
+use std::process::Child;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
 
-use chrono::Utc;
+use chrono::{Local, Utc};
 use regex::Regex;
 use uuid::Uuid;
 
+use crate::file_system::real_file_system::RealFileSystem;
+use crate::file_system::FileSystem;
+use crate::rsync_client::process_rsync_client::ProcessRsyncClient;
+use crate::rsync_client::{is_protocol_mismatch, RsyncClient};
 use crate::models::backup::{
     BackupInvocation, ExecutionOutput, InvocationStatus, InvocationTrigger, SnapshotRecord,
     TransferStats,
 };
-use crate::models::job::{BackupMode, JobDefinition, JobStatus, StorageLocation};
-use crate::models::progress::{JobStatusEvent, LogLine};
+use crate::models::backup_set::{BackupSet, BackupSetRun};
+use crate::models::itemize::ItemizedChange;
+use crate::models::job::{BackupMode, DestinationPrep, JobDefinition, JobStatus, StorageLocation};
+use crate::models::progress::{
+    AggregateProgress, JobStatusEvent, LogLine, ProgressUpdate, RunningJobInfo,
+};
+use crate::models::settings::{
+    LogFileSettings, PruneRecord, PrunedSnapshot, DEFAULT_ITEMIZE_STORE_LIMIT,
+    DEFAULT_LINK_DEST_COUNT,
+};
+use crate::services::backup_set_runner::{run_backup_set, SetJobRunner};
+use crate::services::bandwidth_schedule::apply_bandwidth_schedule;
+use crate::services::change_alarm::{change_proportions, check_change_alarm, summary_from_stats};
 use crate::services::command_builder::build_rsync_args;
+use crate::services::daemon_auth::{daemon_secret_name, PasswordFile};
+use crate::services::destination_prep::{
+    create_remote_destination, destination_prep, options_with_mkpath, supports_mkpath,
+};
 use crate::services::execution_handler::ExecutionEventHandler;
+use crate::services::itemize_recorder::ItemizeRecorder;
 use crate::models::execution::event::ExecutionEvent;
-use crate::services::job_runner::run_job;
+use crate::models::execution::progress::StatsBlock;
+use crate::services::job_runner::{
+    describe_env, is_success_exit, run_failure_message, run_job, StderrTail,
+};
+use crate::models::log::StructuredLogRecord;
+use crate::services::log_writer::{
+    ensure_log_parent, log_path_for, rsync_log_args, structured_log_path, RotatingLogWriter,
+    StructuredLogWriter, LOG_FLUSH_INTERVAL,
+};
+use crate::services::notifier::{
+    notify_change_alarm, notify_finished, notify_verification_failed, Notifier,
+};
+use crate::services::parallel_split::{
+    combined_exit_code, combined_progress, combined_stats, merge_part_events, part_arg_sets,
+    split_parallelism, split_source_entries,
+};
 use crate::services::job_service::JobService;
-use crate::services::progress_parser::parse_summary_line;
+use crate::services::prune_recovery;
+use crate::services::preflight::{insufficient_space, missing_mount, unexpectedly_empty_destination};
+use crate::services::rerun::{
+    job_for_retry, stored_command_args, uses_password_file, without_run_files,
+};
+use crate::services::progress_parser::{
+    estimate_eta_secs, estimate_remaining_bytes, feed_stats_line, parse_summary_line,
+    parse_transfer_rate,
+};
+use crate::services::snapshot_trash::{dispose_pruned_snapshot, prepare_trash};
+use crate::services::run_conditions::{check_run_conditions, manual_run_warnings, RunCondition};
+use crate::ssh_client::process_ssh_client::ProcessSshClient;
+use crate::ssh_client::SshClient;
+use crate::services::snapshot_resume::resumable_snapshot;
+use crate::services::snapshot_verify::{verification_args, verification_summary, verify_and_record};
 use crate::services::snapshot_retention;
-use crate::services::running_jobs::RunningJobs;
+use crate::services::running_jobs::{running_job_info, RunningJobs};
+use crate::services::runtime_limit::{
+    stop_after_arg, stopped_by_limit, supports_stop_after, RuntimeWatchdog, STOP_AFTER_GRACE,
+};
 use crate::services::settings_service::SettingsService;
 use crate::services::statistics_service::StatisticsService;
+use crate::services::test_mode::{
+    command_was_dry_run, job_for_run, records_run_results, TEST_MODE_BANNER,
+};
+use crate::services::throttled_handler::ThrottledHandler;
 
 /// For snapshot-mode jobs, compute the destination subdir and link-dest path.
 struct SnapshotContext {
@@ -28,13 +87,17 @@ struct SnapshotContext {
     snapshot_path: String,
     /// The destination StorageLocation overridden with the snapshot subdir
     effective_destination: StorageLocation,
-    /// Path to the previous snapshot for --link-dest, if any
-    link_dest: Option<String>,
+    /// Recent snapshots for --link-dest, newest first
+    link_dests: Vec<String>,
+    /// The interrupted invocation whose snapshot directory is being resumed
+    resumed_from: Option<Uuid>,
 }
 
 fn prepare_snapshot_context(
     job: &JobDefinition,
     job_service: &JobService,
+    link_dest_count: usize,
+    fs: &dyn FileSystem,
 ) -> Result<Option<SnapshotContext>, String> {
     match &job.transfer.backup_mode {
         BackupMode::Snapshot { .. } => {}
@@ -49,10 +112,24 @@ fn prepare_snapshot_context(
         StorageLocation::Local { path } => path.clone(),
         StorageLocation::RemoteSsh { path, .. } => path.clone(),
         StorageLocation::RemoteRsync { path, .. } => path.clone(),
+        StorageLocation::Mount { path, .. } => path.clone(),
     };
 
     let base = base_path.trim_end_matches('/');
-    let snapshot_path = format!("{}/{}", base, dir_name);
+
+    // Resume into the directory an interrupted run left behind, if any
+    let history = job_service
+        .get_job_history(&job.id, 1)
+        .map_err(|e| e.to_string())?;
+    let snapshots = job_service
+        .list_snapshots(&job.id)
+        .map_err(|e| e.to_string())?;
+    let resumed = resumable_snapshot(&job.transfer.destination, &history, &snapshots, fs);
+    let resumed_from = resumed.map(|inv| inv.id);
+    let snapshot_path = match resumed.and_then(|inv| inv.execution_output.snapshot_path.clone()) {
+        Some(path) => path,
+        None => format!("{}/{}", base, dir_name),
+    };
 
     // Override destination with snapshot subdir
     let effective_destination = match &job.transfer.destination {
@@ -73,27 +150,302 @@ fn prepare_snapshot_context(
             identity_file: identity_file.clone(),
         },
         StorageLocation::RemoteRsync {
-            host, module, ..
+            host,
+            module,
+            username,
+            password_secret,
+            ..
         } => StorageLocation::RemoteRsync {
             host: host.clone(),
             module: module.clone(),
             path: format!("{}/", snapshot_path),
+            username: username.clone(),
+            password_secret: password_secret.clone(),
+        },
+        StorageLocation::Mount {
+            mountpoint,
+            expected_fs_label,
+            ..
+        } => StorageLocation::Mount {
+            mountpoint: mountpoint.clone(),
+            path: format!("{}/", snapshot_path),
+            expected_fs_label: expected_fs_label.clone(),
         },
     };
 
-    // Get the latest snapshot for --link-dest
-    let link_dest = job_service
-        .get_latest_snapshot(&job.id)
-        .map_err(|e| e.to_string())?
-        .map(|snap| snap.snapshot_path);
+    // Link against the most recent snapshots, newest first
+    let link_dests = job_service
+        .recent_snapshot_paths(&job.id, link_dest_count)
+        .map_err(|e| e.to_string())?;
 
     Ok(Some(SnapshotContext {
         snapshot_path,
         effective_destination,
-        link_dest,
+        link_dests,
+        resumed_from,
     }))
 }
 
+/// Per-process counters of a run, kept apart while its parts run and added
+/// up once they finish. A run that isn't split has a single part.
+struct PartTally {
+    last_bytes: Vec<u64>,
+    last_files: Vec<u64>,
+    last_total: Vec<u64>,
+    summary_sent_bytes: Vec<Option<u64>>,
+    /// Running state for ETA estimation across per-file progress lines
+    files_remaining: Vec<Option<u64>>,
+    completed_file_bytes: Vec<u64>,
+    completed_file_count: Vec<u64>,
+    latest_progress: Vec<Option<ProgressUpdate>>,
+    stats_blocks: Vec<StatsBlock>,
+}
+
+impl PartTally {
+    fn new(parts: usize) -> Self {
+        Self {
+            last_bytes: vec![0; parts],
+            last_files: vec![0; parts],
+            last_total: vec![0; parts],
+            summary_sent_bytes: vec![None; parts],
+            files_remaining: vec![None; parts],
+            completed_file_bytes: vec![0; parts],
+            completed_file_count: vec![0; parts],
+            latest_progress: vec![None; parts],
+            stats_blocks: vec![StatsBlock::default(); parts],
+        }
+    }
+
+    /// Pick the transfer summary ("sent X bytes  received Y bytes") and the
+    /// `--stats` figures out of a part's stdout.
+    fn stdout_line(&mut self, part: usize, line: &str) {
+        if let Some(summary) = parse_summary_line(line) {
+            self.summary_sent_bytes[part] = Some(summary.sent_bytes);
+            self.stats_blocks[part].detailed.bytes_per_sec = summary.bytes_per_sec;
+        }
+        feed_stats_line(&mut self.stats_blocks[part], line);
+    }
+
+    /// Record a part's progress and return the update to report, with its ETA
+    /// refined; a split run reports all of its processes as one.
+    fn progress(&mut self, part: usize, mut progress: ProgressUpdate) -> ProgressUpdate {
+        self.last_bytes[part] = progress.bytes_transferred;
+        self.last_files[part] = progress.files_transferred;
+        self.last_total[part] = progress.files_total;
+
+        // Refine the ETA with the remaining-file count from the latest
+        // completion line and the average file size so far
+        if progress.files_total > 0 {
+            self.files_remaining[part] = Some(progress.files_remaining);
+        }
+        if progress.percentage >= 100.0 {
+            self.completed_file_bytes[part] += progress.bytes_transferred;
+            self.completed_file_count[part] += 1;
+        }
+        let avg_file_size = (self.completed_file_count[part] > 0)
+            .then(|| self.completed_file_bytes[part] / self.completed_file_count[part]);
+        progress.eta_secs = estimate_eta_secs(
+            parse_transfer_rate(&progress.transfer_rate).unwrap_or(0.0),
+            estimate_remaining_bytes(&progress, self.files_remaining[part], avg_file_size),
+        );
+
+        if self.latest_progress.len() > 1 {
+            self.latest_progress[part] = Some(progress.clone());
+            let latest: Vec<ProgressUpdate> =
+                self.latest_progress.iter().flatten().cloned().collect();
+            progress = combined_progress(&latest, &progress);
+        }
+        progress
+    }
+
+    /// Bytes sent, from each part's rsync summary when available (an
+    /// accurate total), falling back to its last per-file progress value.
+    fn sent_bytes(&self) -> u64 {
+        self.summary_sent_bytes
+            .iter()
+            .zip(&self.last_bytes)
+            .map(|(summary, last)| summary.unwrap_or(*last))
+            .sum()
+    }
+
+    fn last_bytes(&self) -> u64 {
+        self.last_bytes.iter().sum()
+    }
+
+    fn files_transferred(&self) -> u64 {
+        self.last_files.iter().sum()
+    }
+
+    fn total_files(&self) -> u64 {
+        self.last_total.iter().sum()
+    }
+
+    fn stats(&self) -> StatsBlock {
+        combined_stats(&self.stats_blocks)
+    }
+}
+
+/// What a snapshot run does once rsync has finished.
+struct SnapshotFollowUp {
+    /// The snapshot the new one was hard-linked against, if any
+    link_dest: Option<String>,
+    /// The rsync arguments that compare the new snapshot with the source
+    verify_args: Option<Vec<String>>,
+}
+
+/// Everything a recorded invocation needs to run its rsync processes.
+struct RunPlan {
+    job: JobDefinition,
+    /// The Running record the run completes
+    invocation: BackupInvocation,
+    /// The arguments of each rsync process, one per part of a split source
+    part_args: Vec<Vec<String>>,
+    test_mode: bool,
+    /// The run-time limit, and whether rsync enforces it with --stop-after
+    runtime_limit: Option<(Duration, bool)>,
+    snapshot: Option<SnapshotFollowUp>,
+    /// Kept until the run, and any verification after it, is done
+    password_file: Option<PasswordFile>,
+    log_settings: LogFileSettings,
+}
+
+/// A run's rsync processes, and their events merged onto one channel.
+type StartedParts = (Vec<Arc<Mutex<Child>>>, Receiver<(usize, ExecutionEvent)>);
+
+/// Drop a job from the running set and wait for each of its processes,
+/// returning their exit codes. On Unix a killed process has none.
+fn wait_for_parts(running_jobs: &RunningJobs, job_id: &Uuid) -> Vec<Option<i32>> {
+    running_jobs
+        .remove_parts(job_id)
+        .iter()
+        .map(|child_arc| match child_arc.lock() {
+            Ok(mut child) => child.wait().ok().and_then(|status| status.code()),
+            Err(_) => None,
+        })
+        .collect()
+}
+
+/// Prune the snapshots a job's retention policy no longer keeps: drop their
+/// records, then delete each directory or move it into `trash_dir`. The
+/// prune is remembered so it can be undone. Nothing is pruned when the trash
+/// is not on the filesystem of `snapshot_path`, the snapshot just recorded.
+fn prune_job_snapshots(
+    job_service: &JobService,
+    fs: &dyn FileSystem,
+    job_id: &Uuid,
+    snapshot_path: &Path,
+    trash_dir: Option<&Path>,
+) {
+    // Check the trash first: a failed move would leave a directory whose row is gone
+    if let Some(trash_dir) = trash_dir {
+        if let Err(e) = prepare_trash(fs, snapshot_path, trash_dir) {
+            log::error!("Retention skipped for job {}: {}", job_id, e);
+            return;
+        }
+    }
+    let pruned_records = match job_service.prune_snapshots_by_policy(job_id) {
+        Ok(records) => records,
+        Err(e) => {
+            log::error!("Failed to apply retention policy: {}", e);
+            return;
+        }
+    };
+    let snapshots = pruned_records
+        .into_iter()
+        .map(|record| {
+            log::info!("Retention: pruned snapshot {}", record.snapshot_path);
+            let trashed_to = dispose_pruned_directory(fs, &record.snapshot_path, trash_dir);
+            PrunedSnapshot { record, trashed_to }
+        })
+        .collect();
+    prune_recovery::record_prune(
+        job_service,
+        PruneRecord {
+            pruned_at: Utc::now(),
+            invocations: Vec::new(),
+            snapshots,
+        },
+    );
+}
+
+/// Delete a pruned snapshot's directory, or move it into `trash_dir`,
+/// returning where it was moved to.
+fn dispose_pruned_directory(
+    fs: &dyn FileSystem,
+    path: &str,
+    trash_dir: Option<&Path>,
+) -> Option<String> {
+    match dispose_pruned_snapshot(fs, Path::new(path), trash_dir) {
+        Ok(Some(trashed)) => {
+            log::info!("Retention: moved {} to {}", path, trashed.display());
+            Some(trashed.to_string_lossy().to_string())
+        }
+        Ok(None) => None,
+        Err(e) => {
+            log::error!("Failed to remove pruned snapshot dir {}: {}", path, e);
+            None
+        }
+    }
+}
+
+/// Compare the snapshot a run just recorded with its source, reporting the
+/// result in the run's log and notifying when it does not match.
+fn verify_recorded_snapshot(
+    rsync: &dyn RsyncClient,
+    job_service: &JobService,
+    notifier: Option<&dyn Notifier>,
+    job: &JobDefinition,
+    invocation_id: Uuid,
+    args: &[String],
+    handler: &dyn ExecutionEventHandler,
+) {
+    let verification = verify_and_record(rsync, job_service, job.id, invocation_id, args);
+    let summary = verification_summary(&verification);
+    log::info!("Job {}: {}", job.id, summary);
+    let line = if verification.passed {
+        summary
+    } else {
+        format!("WARNING: {}", summary)
+    };
+    log_line(handler, invocation_id, line, !verification.passed);
+    notify_verification_failed(notifier, job, &verification);
+}
+
+/// Send one line of a run's output to `handler`.
+fn log_line(
+    handler: &dyn ExecutionEventHandler,
+    invocation_id: Uuid,
+    line: String,
+    is_stderr: bool,
+) {
+    handler.on_log_line(LogLine {
+        invocation_id,
+        timestamp: Utc::now(),
+        line,
+        is_stderr,
+    });
+}
+
+/// The rsync program runs start, and the client for the calls made around a
+/// run: the version probe and snapshot verification.
+struct RsyncProgram {
+    binary: String,
+    client: Arc<dyn RsyncClient + Send + Sync>,
+    /// The client's `--version` output, probed on first use.
+    version: OnceLock<Option<String>>,
+}
+
+impl RsyncProgram {
+    fn new(binary: String) -> Self {
+        let client = Arc::new(ProcessRsyncClient::with_binary(binary.clone()));
+        Self {
+            binary,
+            client,
+            version: OnceLock::new(),
+        }
+    }
+}
+
 /// Orchestrates job execution, including snapshot context, log writing,
 /// statistics recording, and event emission through a pluggable handler.
 pub struct JobExecutor {
@@ -102,6 +454,16 @@ pub struct JobExecutor {
     settings_service: Arc<SettingsService>,
     running_jobs: Arc<RunningJobs>,
     default_log_dir: String,
+    /// Guards consulted before scheduled runs (window, metered, battery).
+    run_conditions: Vec<Arc<dyn RunCondition>>,
+    /// Told about finished runs, filtered by each job's `notify_on`.
+    notifier: Option<Arc<dyn Notifier>>,
+    /// The rsync program to run, `rsync` from the PATH unless overridden.
+    rsync: RsyncProgram,
+    /// Checks mounts, free space and sources, and removes pruned snapshots.
+    file_system: Arc<dyn FileSystem + Send + Sync>,
+    /// Creates missing remote destinations before a run.
+    ssh: Arc<dyn SshClient + Send + Sync>,
 }
 
 impl JobExecutor {
@@ -118,9 +480,54 @@ impl JobExecutor {
             settings_service,
             running_jobs,
             default_log_dir,
+            run_conditions: Vec::new(),
+            notifier: None,
+            rsync: RsyncProgram::new("rsync".to_string()),
+            file_system: Arc::new(RealFileSystem::new()),
+            ssh: Arc::new(ProcessSshClient::new()),
         }
     }
 
+    /// Run `binary` instead of the `rsync` found on the PATH.
+    pub fn with_rsync_binary(mut self, binary: impl Into<String>) -> Self {
+        self.rsync = RsyncProgram::new(binary.into());
+        self
+    }
+
+    /// Use `client` instead of running the rsync binary for the version probe
+    /// and snapshot verification.
+    pub fn with_rsync_client(mut self, client: Arc<dyn RsyncClient + Send + Sync>) -> Self {
+        self.rsync.client = client;
+        self.rsync.version = OnceLock::new();
+        self
+    }
+
+    /// Register a condition that can skip scheduled runs. Manual runs ignore it.
+    pub fn with_run_condition(mut self, condition: Arc<dyn RunCondition>) -> Self {
+        self.run_conditions.push(condition);
+        self
+    }
+
+    /// Use `file_system` instead of the real one for the checks before a run
+    /// and for removing pruned snapshots.
+    pub fn with_file_system(mut self, file_system: Arc<dyn FileSystem + Send + Sync>) -> Self {
+        self.file_system = file_system;
+        self
+    }
+
+    /// Use `ssh` instead of the `ssh` program to create missing remote
+    /// destinations.
+    pub fn with_ssh_client(mut self, ssh: Arc<dyn SshClient + Send + Sync>) -> Self {
+        self.ssh = ssh;
+        self
+    }
+
+    /// Report finished runs to `notifier`, as each job's `notify_on` allows.
+    pub fn with_notifier(mut self, notifier: Arc<dyn Notifier>) -> Self {
+        self.notifier = Some(notifier);
+        self
+    }
+
     pub fn job_service(&self) -> &Arc<JobService> {
         &self.job_service
     }
@@ -141,6 +548,37 @@ impl JobExecutor {
         &self.default_log_dir
     }
 
+    /// The first line of `rsync --version`, or `None` when it could not be
+    /// run. Probed once and reused by every later run.
+    pub fn rsync_version(&self) -> Option<&str> {
+        self.rsync
+            .version
+            .get_or_init(|| self.rsync.client.version().ok())
+            .as_deref()
+    }
+
+    /// The rsync command a manual run of `job` starts from, for review before
+    /// running it. Snapshot folders, the log file and the daemon password
+    /// file are only settled once the run starts, so they are left out.
+    pub fn preview_command(&self, job: &JobDefinition) -> String {
+        let test_mode = self.settings_service.get_test_mode().unwrap_or(false);
+        let job = job_for_run(job, test_mode);
+        let mut options = job.options.clone();
+        apply_bandwidth_schedule(&mut options, Local::now().time());
+        let args = build_rsync_args(
+            &job.transfer.source,
+            &job.transfer.destination,
+            &options,
+            job.ssh_config.as_ref(),
+            &[],
+            self.settings_service
+                .get_auto_trailing_slash()
+                .unwrap_or(true),
+            self.settings_service.get_relative_paths().unwrap_or(false),
+        );
+        format!("rsync {}", shell_words::join(&args))
+    }
+
     /// Execute a job with the given trigger, emitting events through the handler.
     ///
     /// Returns the invocation ID on success.
@@ -149,77 +587,302 @@ impl JobExecutor {
         job: &JobDefinition,
         trigger: InvocationTrigger,
         handler: Arc<dyn ExecutionEventHandler>,
+    ) -> Result<Uuid, String> {
+        self.start(job, trigger, handler, None)
+    }
+
+    /// Retry a failed or interrupted invocation with its job's current
+    /// settings, forcing `--partial --append-verify` so large files that were
+    /// cut off resume instead of starting over. The new invocation is a
+    /// manual run linked to the original through `rerun_of`.
+    pub fn retry(
+        &self,
+        invocation_id: &Uuid,
+        handler: Arc<dyn ExecutionEventHandler>,
+    ) -> Result<Uuid, String> {
+        let original = self
+            .job_service
+            .get_invocation(invocation_id)
+            .map_err(|e| e.to_string())?;
+        if matches!(
+            original.status,
+            InvocationStatus::Running | InvocationStatus::Succeeded
+        ) {
+            return Err(format!(
+                "Only failed or interrupted runs can be retried (this run is {:?})",
+                original.status
+            ));
+        }
+        let job = self
+            .job_service
+            .get_job(&original.job_id)
+            .map_err(|e| e.to_string())?;
+        self.start(
+            &job_for_retry(&job),
+            InvocationTrigger::Manual,
+            handler,
+            Some(original.id),
+        )
+    }
+
+    /// Run a past invocation's stored command again, ignoring the job's
+    /// current settings, so the rerun hits the same destination (including an
+    /// old snapshot directory) as the original. The original's daemon password
+    /// file and rsync log are replaced with the rerun's own, and the job's
+    /// environment applies. The new invocation is a manual run linked to the
+    /// original through `rerun_of`.
+    pub fn rerun(
+        &self,
+        invocation_id: &Uuid,
+        handler: Arc<dyn ExecutionEventHandler>,
+    ) -> Result<Uuid, String> {
+        // A stored command cannot be forced to a dry run
+        if self.settings_service.get_test_mode().unwrap_or(false) {
+            return Err("Re-running is disabled while test mode is on".to_string());
+        }
+        let original = self
+            .job_service
+            .get_invocation(invocation_id)
+            .map_err(|e| e.to_string())?;
+        let mut job = self
+            .job_service
+            .get_job(&original.job_id)
+            .map_err(|e| e.to_string())?;
+        if self.running_jobs.is_running(&job.id) {
+            return Err("Job is already running".to_string());
+        }
+        if let Some(reason) = missing_mount(&job, self.file_system.as_ref()) {
+            return Err(format!("Run refused: {}", reason));
+        }
+
+        let stored_command = &original.execution_output.command_executed;
+        let stored = stored_command_args(stored_command).map_err(|e| e.to_string())?;
+        let mut args = without_run_files(&stored);
+        // Whether the rerun counts towards statistics depends on what it runs
+        job.options.core_transfer.dry_run = command_was_dry_run(stored_command);
+
+        let rerun_id = Uuid::new_v4();
+        let password_file = match uses_password_file(&stored) {
+            true => self.daemon_password_file(&job, rerun_id)?,
+            false => None,
+        };
+        args.extend(password_file.as_ref().map(PasswordFile::arg));
+
+        let mut invocation = BackupInvocation {
+            id: rerun_id,
+            job_id: job.id,
+            started_at: Utc::now(),
+            finished_at: None,
+            status: InvocationStatus::Running,
+            trigger: InvocationTrigger::Manual,
+            rerun_of: Some(original.id),
+            resumed_from: None,
+            warning: None,
+            transfer_stats: TransferStats::default(),
+            execution_output: ExecutionOutput {
+                command_executed: String::new(),
+                exit_code: None,
+                snapshot_path: original.execution_output.snapshot_path.clone(),
+                log_file_path: None,
+            },
+        };
+        let log_settings = self
+            .settings_service
+            .get_log_file_settings()
+            .unwrap_or_default();
+        self.attach_log_file(&job, &mut invocation, &mut args, &log_settings);
+        invocation.execution_output.command_executed =
+            format!("rsync {}", shell_words::join(&args));
+        self.job_service
+            .record_invocation(&invocation)
+            .map_err(|e| e.to_string())?;
+
+        handler.on_status_change(JobStatusEvent {
+            job_id: job.id,
+            invocation_id: rerun_id,
+            status: JobStatus::Running,
+            exit_code: None,
+            error_message: None,
+        });
+        if !job.run_policy.env.is_empty() {
+            let line = format!(
+                "Environment: {}",
+                describe_env(&job.run_policy.env).join(" ")
+            );
+            log_line(handler.as_ref(), rerun_id, line, false);
+        }
+
+        // The stored command keeps any --stop-after; the watchdog covers the rest
+        let rsync_enforces_limit = args.iter().any(|arg| arg.starts_with("--stop-after"));
+        let runtime_limit = job.max_runtime().map(|limit| (limit, rsync_enforces_limit));
+        let plan = RunPlan {
+            job,
+            invocation,
+            part_args: vec![args],
+            test_mode: false,
+            runtime_limit,
+            snapshot: None,
+            password_file,
+            log_settings,
+        };
+        self.launch(plan, handler)
+    }
+
+    fn start(
+        &self,
+        job: &JobDefinition,
+        trigger: InvocationTrigger,
+        handler: Arc<dyn ExecutionEventHandler>,
+        rerun_of: Option<Uuid>,
     ) -> Result<Uuid, String> {
         let job_uuid = job.id;
 
+        // Test mode forces every run to be a dry run, whatever the job says
+        let test_mode = self.settings_service.get_test_mode().unwrap_or(false);
+        let job = job_for_run(job, test_mode);
+        let job = job.as_ref();
+
         // Reject if already running
         if self.running_jobs.is_running(&job_uuid) {
             return Err("Job is already running".to_string());
         }
 
+        // Scheduled runs respect environmental/window guards; manual runs bypass them
+        if let Some(reason) = check_run_conditions(job, &trigger, &self.run_conditions) {
+            return Err(format!("Scheduled run skipped: {}", reason));
+        }
+
+        // Never write into an empty mountpoint when the volume is absent
+        if let Some(reason) = missing_mount(job, self.file_system.as_ref()) {
+            return Err(format!("Run refused: {}", reason));
+        }
+
         // Prepare snapshot context if applicable
-        let snapshot_ctx = prepare_snapshot_context(job, &self.job_service)?;
+        let link_dest_count = self
+            .settings_service
+            .get_link_dest_count()
+            .unwrap_or(DEFAULT_LINK_DEST_COUNT);
+        let snapshot_ctx = prepare_snapshot_context(
+            job,
+            &self.job_service,
+            link_dest_count,
+            self.file_system.as_ref(),
+        )?;
+
+        // A resumed snapshot keeps and completes the files the interrupted run wrote
+        let resumed_from = snapshot_ctx.as_ref().and_then(|ctx| ctx.resumed_from);
+        let resumed_job = resumed_from.map(|_| job_for_retry(job));
+        let job = resumed_job.as_ref().unwrap_or(job);
 
         // Choose effective destination and link-dest
         let effective_dest = snapshot_ctx
             .as_ref()
             .map(|ctx| &ctx.effective_destination)
             .unwrap_or(&job.transfer.destination);
-        let link_dest = snapshot_ctx
+        let link_dests: Vec<&str> = snapshot_ctx
             .as_ref()
-            .and_then(|ctx| ctx.link_dest.as_deref());
+            .map(|ctx| ctx.link_dests.iter().map(String::as_str).collect())
+            .unwrap_or_default();
 
         // Read auto trailing slash setting
         let auto_trailing_slash = self
             .settings_service
             .get_auto_trailing_slash()
             .unwrap_or(true);
+        let relative_paths = self.settings_service.get_relative_paths().unwrap_or(false);
+
+        // Create a missing remote destination over SSH, or let rsync do it
+        let prep = destination_prep(job, effective_dest);
+        let wants_mkpath = matches!(prep, Some(DestinationPrep::Mkpath));
+        let mkpath = wants_mkpath && self.rsync_version().is_some_and(supports_mkpath);
+        if wants_mkpath && !mkpath {
+            log::warn!(
+                "Job {}: rsync predates 3.2.3 (no --mkpath), so a missing destination is not created",
+                job.id
+            );
+        }
+        let mut options = if mkpath {
+            options_with_mkpath(&job.options)
+        } else {
+            job.options.clone()
+        };
+        apply_bandwidth_schedule(&mut options, Local::now().time());
+        if let Some(DestinationPrep::RemoteMkdir(ssh_args)) = &prep {
+            create_remote_destination(ssh_args, self.ssh.as_ref())
+                .map_err(|e| format!("Run refused: could not create remote destination: {}", e))?;
+        }
 
         // Build rsync args
-        let args = build_rsync_args(
+        let mut args = build_rsync_args(
             &job.transfer.source,
             effective_dest,
-            &job.options,
+            &options,
             job.ssh_config.as_ref(),
-            link_dest,
+            &link_dests,
             auto_trailing_slash,
+            relative_paths,
         );
+        // Source and destination close the list until later flags are appended
+        let source_index = args.len() - 2;
+
+        // Prefer rsync's own run-time limit; the watchdog below covers older rsync
+        let max_runtime = job.max_runtime();
+        let rsync_enforces_limit = max_runtime.is_some()
+            && self.rsync_version().is_some_and(supports_stop_after);
+        if let (Some(limit), true) = (max_runtime, rsync_enforces_limit) {
+            args.push(stop_after_arg(limit));
+        }
 
         let invocation_id = Uuid::new_v4();
-        let command_str = format!("rsync {}", args.join(" "));
-        let snapshot_path_for_record = snapshot_ctx.as_ref().map(|ctx| ctx.snapshot_path.clone());
 
-        // Resolve log directory from settings, fallback to default
-        let log_dir = self
-            .settings_service
-            .get_log_directory()
-            .ok()
-            .flatten()
-            .unwrap_or_else(|| self.default_log_dir.clone());
+        let password_file = self.daemon_password_file(job, invocation_id)?;
+        args.extend(password_file.as_ref().map(PasswordFile::arg));
 
-        let dir = std::path::Path::new(&log_dir);
-        if let Err(e) = std::fs::create_dir_all(dir) {
-            log::error!("Failed to create log directory {}: {}", log_dir, e);
-        }
-        let log_file_path = format!("{}/{}.log", log_dir, invocation_id);
+        // Compares the new snapshot with the source once the run has succeeded
+        let verify_args = snapshot_ctx
+            .as_ref()
+            .filter(|_| job.safety.verify_after_snapshot)
+            .map(|ctx| {
+                let mut verify_args = verification_args(
+                    job,
+                    &ctx.effective_destination,
+                    auto_trailing_slash,
+                    relative_paths,
+                );
+                verify_args.extend(password_file.as_ref().map(PasswordFile::arg));
+                verify_args
+            });
+
+        let snapshot_path_for_record = snapshot_ctx.as_ref().map(|ctx| ctx.snapshot_path.clone());
 
         // Create invocation record
-        let invocation = BackupInvocation {
+        let mut invocation = BackupInvocation {
             id: invocation_id,
             job_id: job_uuid,
             started_at: Utc::now(),
             finished_at: None,
             status: InvocationStatus::Running,
             trigger: trigger.clone(),
+            rerun_of,
+            resumed_from,
+            warning: None,
             transfer_stats: TransferStats::default(),
             execution_output: ExecutionOutput {
-                command_executed: command_str,
+                command_executed: String::new(),
                 exit_code: None,
                 snapshot_path: snapshot_path_for_record.clone(),
-                log_file_path: Some(log_file_path.clone()),
+                log_file_path: None,
             },
         };
 
+        let log_settings = self
+            .settings_service
+            .get_log_file_settings()
+            .unwrap_or_default();
+        self.attach_log_file(job, &mut invocation, &mut args, &log_settings);
+        invocation.execution_output.command_executed =
+            format!("rsync {}", shell_words::join(&args));
+
         self.job_service
             .record_invocation(&invocation)
             .map_err(|e| e.to_string())?;
@@ -233,47 +896,248 @@ impl JobExecutor {
             error_message: None,
         });
 
-        // Spawn rsync process
-        let (child, rx) = run_job("rsync", &args, invocation_id).map_err(|e| e.to_string())?;
+        // Manual runs proceed regardless, but surface guards that asked to warn
+        if trigger == InvocationTrigger::Manual {
+            for warning in manual_run_warnings(job, &self.run_conditions) {
+                log::warn!("Job '{}' ({}): {}", job.name, job_uuid, warning);
+                handler.on_log_line(LogLine {
+                    invocation_id,
+                    timestamp: Utc::now(),
+                    line: format!("WARNING: {}", warning),
+                    is_stderr: true,
+                });
+            }
+        }
+
+        if test_mode {
+            handler.on_log_line(LogLine {
+                invocation_id,
+                timestamp: Utc::now(),
+                line: TEST_MODE_BANNER.to_string(),
+                is_stderr: false,
+            });
+        }
+
+        if let (Some(_), Some(path)) = (resumed_from, &snapshot_path_for_record) {
+            handler.on_log_line(LogLine {
+                invocation_id,
+                timestamp: Utc::now(),
+                line: format!("Resuming interrupted snapshot {}", path),
+                is_stderr: false,
+            });
+        }
+
+        if !job.run_policy.env.is_empty() {
+            handler.on_log_line(LogLine {
+                invocation_id,
+                timestamp: Utc::now(),
+                line: format!(
+                    "Environment: {}",
+                    describe_env(&job.run_policy.env).join(" ")
+                ),
+                is_stderr: false,
+            });
+        }
+
+        // A mirror into an unmounted, empty destination would copy everything again
+        if let Some(reason) = unexpectedly_empty_destination(job, self.file_system.as_ref()) {
+            self.fail_before_transfer(job, invocation, reason, handler.as_ref());
+            return Ok(invocation_id);
+        }
+
+        // Free space may have shrunk since preflight, so look again just before writing
+        let space_margin = self
+            .settings_service
+            .get_pre_run_space_margin()
+            .ok()
+            .flatten();
+        if let Some(reason) = space_margin
+            .and_then(|margin| insufficient_space(job, margin, self.file_system.as_ref()))
+        {
+            self.fail_before_transfer(job, invocation, reason, handler.as_ref());
+            return Ok(invocation_id);
+        }
+
+        // A split job runs one rsync per share of the source's top-level entries
+        let part_args = match self.part_args(job, args, source_index, relative_paths) {
+            Ok(part_args) => part_args,
+            Err(reason) => {
+                self.fail_before_transfer(job, invocation, reason, handler.as_ref());
+                return Ok(invocation_id);
+            }
+        };
+        if part_args.len() > 1 {
+            log_line(
+                handler.as_ref(),
+                invocation_id,
+                format!(
+                    "Splitting the source across {} rsync processes",
+                    part_args.len()
+                ),
+                false,
+            );
+            for (i, part) in part_args.iter().enumerate() {
+                let line = format!("Part {}: rsync {}", i + 1, shell_words::join(part));
+                log_line(handler.as_ref(), invocation_id, line, false);
+            }
+        }
+
+        let runtime_limit = max_runtime.map(|limit| (limit, rsync_enforces_limit));
+        let snapshot = snapshot_ctx.map(|ctx| SnapshotFollowUp {
+            link_dest: ctx.link_dests.first().cloned(),
+            verify_args,
+        });
+        let plan = RunPlan {
+            job: job.clone(),
+            invocation,
+            part_args,
+            test_mode,
+            runtime_limit,
+            snapshot,
+            password_file,
+            log_settings,
+        };
+        self.launch(plan, handler)
+    }
 
-        // Store in running jobs
-        let _child_arc = self.running_jobs.insert(job_uuid, child);
+    /// Spawn the rsync processes of a recorded invocation, register them as
+    /// the job's run and follow them to completion on a background thread.
+    fn launch(
+        &self,
+        plan: RunPlan,
+        handler: Arc<dyn ExecutionEventHandler>,
+    ) -> Result<Uuid, String> {
+        let RunPlan {
+            job,
+            invocation,
+            part_args,
+            test_mode,
+            runtime_limit,
+            snapshot,
+            password_file,
+            log_settings,
+        } = plan;
+        let job_uuid = job.id;
+        let invocation_id = invocation.id;
+        let max_runtime = runtime_limit.map(|(limit, _)| limit);
+        let rsync_enforces_limit = runtime_limit.is_some_and(|(_, enforced)| enforced);
+
+        // Spawn the rsync processes and store them in running jobs
+        let (child_arcs, rx) =
+            self.spawn_parts(&job, &part_args, log_settings.max_line_bytes, invocation_id)?;
+        let part_count = part_args.len();
+        let watchdogs: Vec<RuntimeWatchdog> = match max_runtime {
+            Some(limit) => {
+                let deadline = if rsync_enforces_limit {
+                    limit + STOP_AFTER_GRACE
+                } else {
+                    limit
+                };
+                child_arcs
+                    .into_iter()
+                    .map(|child_arc| RuntimeWatchdog::spawn(child_arc, deadline))
+                    .collect()
+            }
+            None => Vec::new(),
+        };
 
         // Capture snapshot info for the background thread
-        let is_snapshot_mode = snapshot_ctx.is_some();
+        let is_snapshot_mode = snapshot.is_some();
+        let (link_dest_for_record, verify_args) = snapshot
+            .map(|snapshot| (snapshot.link_dest, snapshot.verify_args))
+            .unwrap_or_default();
+        let snapshot_path_for_record = invocation.execution_output.snapshot_path.clone();
+        let trigger = invocation.trigger.clone();
+        let rerun_of = invocation.rerun_of;
+        let resumed_from = invocation.resumed_from;
         let is_dry_run = job.options.core_transfer.dry_run;
-        let link_dest_for_record = snapshot_ctx
-            .as_ref()
-            .and_then(|ctx| ctx.link_dest.clone());
+        let success_exit_codes = job.run_policy.success_exit_codes.clone();
+        let job_for_notify = job;
+        let notifier = self.notifier.clone();
         let invocation_started_at = invocation.started_at;
+        let anomaly_settings = self
+            .settings_service
+            .get_anomaly_settings()
+            .unwrap_or_default();
+        let itemize_store_limit = self
+            .settings_service
+            .get_itemize_store_limit()
+            .unwrap_or(DEFAULT_ITEMIZE_STORE_LIMIT);
+        let snapshot_trash_dir = self
+            .settings_service
+            .get_snapshot_trash_dir()
+            .ok()
+            .flatten();
 
         // Clone Arcs for the background thread
         let running_jobs = Arc::clone(&self.running_jobs);
+        let rsync_client = Arc::clone(&self.rsync.client);
         let job_service = Arc::clone(&self.job_service);
         let statistics_service = Arc::clone(&self.statistics_service);
+        let file_system = Arc::clone(&self.file_system);
 
-        let log_path_for_thread = log_file_path.clone();
+        let log_path_for_thread = invocation
+            .execution_output
+            .log_file_path
+            .clone()
+            .unwrap_or_default();
+        // The log file gets every line; the handler only as much as a UI can take
+        let handler: Arc<dyn ExecutionEventHandler> = Arc::new(ThrottledHandler::new(handler));
         std::thread::spawn(move || {
-            let mut last_bytes: u64 = 0;
-            let mut last_files: u64 = 0;
-            let mut last_total: u64 = 0;
+            // Counts are kept per rsync process and added up at the end
+            let mut tally = PartTally::new(part_count);
             let mut last_speedup: Option<f64> = None;
-            let mut summary_sent_bytes: Option<u64> = None;
+            let mut itemized = ItemizeRecorder::new(itemize_store_limit);
+            let mut protocol_mismatch: Option<String> = None;
+            let mut stderr_tail = StderrTail::default();
 
-            // Open log file for writing
-            let mut log_writer = std::fs::File::create(&log_path_for_thread)
-                .map(std::io::BufWriter::new)
-                .ok();
+            // Open log file for writing (appending, since templates may share a file)
+            let mut log_writer = RotatingLogWriter::open(
+                std::path::Path::new(&log_path_for_thread),
+                log_settings.max_size_bytes,
+            )
+            .ok();
+            let mut structured_writer = if log_settings.structured {
+                StructuredLogWriter::open(std::path::Path::new(&structured_log_path(
+                    &log_path_for_thread,
+                )))
+                .ok()
+            } else {
+                None
+            };
+            if test_mode {
+                if let Some(ref mut writer) = log_writer {
+                    let _ = writer.write_line(&format!(
+                        "[{}] {}",
+                        Utc::now().format("%Y-%m-%d %H:%M:%S"),
+                        TEST_MODE_BANNER
+                    ));
+                }
+                if let Some(ref mut writer) = structured_writer {
+                    let _ = writer.write_record(&StructuredLogRecord::Stdout {
+                        timestamp: Utc::now(),
+                        message: TEST_MODE_BANNER.to_string(),
+                    });
+                }
+            }
 
             let speedup_re = Regex::new(r"speedup is ([\d.]+)").ok();
 
-            while let Ok(event) = rx.recv() {
+            loop {
+                let (part, event) = match rx.recv_timeout(LOG_FLUSH_INTERVAL) {
+                    Ok(received) => received,
+                    // A quiet rsync still gets its last lines to the log file
+                    Err(RecvTimeoutError::Timeout) => {
+                        if let Some(ref mut writer) = log_writer {
+                            let _ = writer.flush_if_due();
+                        }
+                        continue;
+                    }
+                    Err(RecvTimeoutError::Disconnected) => break,
+                };
                 match event {
                     ExecutionEvent::StdoutLine(line) => {
-                        // Parse transfer summary ("sent X bytes  received Y bytes")
-                        if let Some(summary) = parse_summary_line(&line) {
-                            summary_sent_bytes = Some(summary.sent_bytes);
-                        }
+                        tally.stdout_line(part, &line);
 
                         // Parse speedup from rsync summary line
                         if let Some(ref re) = speedup_re {
@@ -288,12 +1152,17 @@ impl JobExecutor {
 
                         // Write to log file
                         if let Some(ref mut writer) = log_writer {
-                            let _ = writeln!(
-                                writer,
+                            let _ = writer.write_line(&format!(
                                 "[{}] {}",
                                 Utc::now().format("%Y-%m-%d %H:%M:%S"),
                                 line
-                            );
+                            ));
+                        }
+                        if let Some(ref mut writer) = structured_writer {
+                            let _ = writer.write_record(&StructuredLogRecord::Stdout {
+                                timestamp: Utc::now(),
+                                message: line.clone(),
+                            });
                         }
 
                         handler.on_log_line(LogLine {
@@ -304,14 +1173,23 @@ impl JobExecutor {
                         });
                     }
                     ExecutionEvent::StderrLine(line) => {
+                        if protocol_mismatch.is_none() && is_protocol_mismatch(&line) {
+                            protocol_mismatch = Some(line.trim().to_string());
+                        }
+                        stderr_tail.push(&line);
                         // Write to log file
                         if let Some(ref mut writer) = log_writer {
-                            let _ = writeln!(
-                                writer,
+                            let _ = writer.write_line(&format!(
                                 "[{}] STDERR: {}",
                                 Utc::now().format("%Y-%m-%d %H:%M:%S"),
                                 line
-                            );
+                            ));
+                        }
+                        if let Some(ref mut writer) = structured_writer {
+                            let _ = writer.write_record(&StructuredLogRecord::Stderr {
+                                timestamp: Utc::now(),
+                                message: line.clone(),
+                            });
                         }
 
                         handler.on_log_line(LogLine {
@@ -322,12 +1200,19 @@ impl JobExecutor {
                         });
                     }
                     ExecutionEvent::Progress(progress) => {
-                        last_bytes = progress.bytes_transferred;
-                        last_files = progress.files_transferred;
-                        last_total = progress.files_total;
+                        let progress = tally.progress(part, progress);
+                        if let Some(ref mut writer) = structured_writer {
+                            let _ = writer.write_record(&StructuredLogRecord::Progress {
+                                timestamp: Utc::now(),
+                                progress: progress.clone(),
+                            });
+                        }
+
+                        running_jobs.update_progress(job_uuid, progress.clone());
                         handler.on_progress(&progress);
                     }
                     ExecutionEvent::ItemizedChange(change) => {
+                        itemized.push(&change);
                         handler.on_itemized_change(invocation_id, &change);
                     }
                     ExecutionEvent::Finished { .. } => {
@@ -336,43 +1221,74 @@ impl JobExecutor {
                 }
             }
 
-            // Flush and drop the log writer before completion
+            // Flush and drop the log writers before completion
             if let Some(mut writer) = log_writer.take() {
                 let _ = writer.flush();
             }
+            if let Some(mut writer) = structured_writer.take() {
+                let _ = writer.flush();
+            }
 
             // Receiver disconnected — reader threads are done.
-            // Remove from running jobs and wait for exit code.
-            let exit_code: Option<i32> = {
-                if let Some(child_arc) = running_jobs.remove(&job_uuid) {
-                    if let Ok(mut child) = child_arc.lock() {
-                        child
-                            .wait()
-                            .ok()
-                            .and_then(|s: std::process::ExitStatus| s.code())
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                }
-            };
+            let exit_codes = wait_for_parts(&running_jobs, &job_uuid);
+            let exit_code = combined_exit_code(&exit_codes, &success_exit_codes);
+
+            // Verification runs rsync again, so a daemon password stays until then
+            let password_file = password_file.filter(|_| verify_args.is_some());
+            // Finish every watchdog, not just up to the first that fired
+            let fired: Vec<bool> = watchdogs.into_iter().map(RuntimeWatchdog::finish).collect();
+            let watchdog_fired = fired.contains(&true);
+            let hit_limit = max_runtime.is_some_and(|limit| {
+                let elapsed = (Utc::now() - invocation_started_at)
+                    .to_std()
+                    .unwrap_or_default();
+                stopped_by_limit(
+                    watchdog_fired,
+                    rsync_enforces_limit,
+                    exit_code,
+                    elapsed,
+                    limit,
+                )
+            });
 
             // On Unix, killed processes return None from .code()
             let was_cancelled = exit_code.is_none();
 
-            let (status, job_status) = if was_cancelled && exit_code != Some(0) {
+            let (status, job_status) = if hit_limit {
+                (InvocationStatus::StoppedByLimit, JobStatus::Failed)
+            } else if was_cancelled {
                 (InvocationStatus::Cancelled, JobStatus::Cancelled)
-            } else if exit_code == Some(0) {
+            } else if is_success_exit(exit_code, &success_exit_codes) {
                 (InvocationStatus::Succeeded, JobStatus::Completed)
             } else {
                 (InvocationStatus::Failed, JobStatus::Failed)
             };
 
             // Update invocation record
-            // Use total sent bytes from rsync summary when available (accurate total),
-            // falling back to the last per-file progress value.
-            let final_bytes = summary_sent_bytes.unwrap_or(last_bytes);
+            let final_bytes = tally.sent_bytes();
+            let last_bytes = tally.last_bytes();
+            let last_files = tally.files_transferred();
+            let last_total = tally.total_files();
+            let stats_block = tally.stats();
+            // Prefer the --stats count; itemized runs without --stats list each deletion
+            let files_deleted = stats_block.deleted_files.unwrap_or(itemized.deleted());
+            // A successful run that touched too much of the tree is flagged, not failed
+            let warning = job_for_notify
+                .safety
+                .change_alarm
+                .as_ref()
+                .filter(|_| status == InvocationStatus::Succeeded)
+                .and_then(|alarm| {
+                    let summary = if itemized.is_empty() {
+                        let transferred = stats_block.files_transferred.unwrap_or(last_files);
+                        summary_from_stats(transferred, files_deleted)
+                    } else {
+                        itemized.summary().clone()
+                    };
+                    let total_files = stats_block.total_files.unwrap_or(last_total);
+                    change_proportions(&summary, total_files)
+                        .and_then(|proportions| check_change_alarm(alarm, &proportions))
+                });
             let completed_invocation = BackupInvocation {
                 id: invocation_id,
                 job_id: job_uuid,
@@ -380,10 +1296,15 @@ impl JobExecutor {
                 finished_at: Some(Utc::now()),
                 status: status.clone(),
                 trigger,
+                rerun_of,
+                resumed_from,
+                warning: warning.clone(),
                 transfer_stats: TransferStats {
                     bytes_transferred: final_bytes,
                     files_transferred: last_files,
                     total_files: last_total,
+                    files_deleted,
+                    detailed: stats_block.detailed_stats(),
                 },
                 execution_output: ExecutionOutput {
                     command_executed: String::new(),
@@ -395,8 +1316,48 @@ impl JobExecutor {
 
             let _ = job_service.complete_invocation(&completed_invocation);
 
-            // Record run statistics for successful non-dry-run completions
-            if status == InvocationStatus::Succeeded && !is_dry_run {
+            if let Some(ref warning) = warning {
+                log::warn!("Job {}: {}", job_uuid, warning);
+                handler.on_log_line(LogLine {
+                    invocation_id,
+                    timestamp: Utc::now(),
+                    line: format!("WARNING: {}", warning),
+                    is_stderr: true,
+                });
+            }
+
+            if !itemized.is_empty() {
+                if let Err(e) =
+                    job_service.record_itemized_changes(&invocation_id, &itemized.finish())
+                {
+                    log::error!("Failed to record itemized changes: {}", e);
+                }
+            }
+
+            // Record run statistics for successful non-dry-run completions,
+            // warning first if the run moved far more than usual
+            if records_run_results(&status, is_dry_run) {
+                match statistics_service.run_anomaly(
+                    &job_uuid,
+                    &completed_invocation,
+                    &anomaly_settings,
+                ) {
+                    Ok(Some(anomaly)) if anomaly.is_anomaly => {
+                        let warning = format!(
+                            "Transferred {:.1}x the usual bytes and {:.1}x the usual files (average of last {} runs)",
+                            anomaly.bytes_ratio, anomaly.files_ratio, anomaly.baseline_runs
+                        );
+                        log::warn!("Job {}: {}", job_uuid, warning);
+                        handler.on_log_line(LogLine {
+                            invocation_id,
+                            timestamp: Utc::now(),
+                            line: format!("WARNING: {}", warning),
+                            is_stderr: true,
+                        });
+                    }
+                    Ok(_) => {}
+                    Err(e) => log::error!("Failed to compare run statistics: {}", e),
+                }
                 if let Err(e) =
                     statistics_service.record(job_uuid, &completed_invocation, last_speedup)
                 {
@@ -406,7 +1367,9 @@ impl JobExecutor {
 
             // On success for snapshot-mode jobs: record snapshot and apply retention
             // Skip snapshot recording for dry-run executions
-            if status == InvocationStatus::Succeeded && is_snapshot_mode && !is_dry_run {
+            let mut snapshot_recorded = false;
+            let mut prune_for_snapshot = None;
+            if is_snapshot_mode && records_run_results(&status, is_dry_run) {
                 if let Some(ref snap_path) = snapshot_path_for_record {
                     let snapshot = SnapshotRecord {
                         id: Uuid::new_v4(),
@@ -418,58 +1381,294 @@ impl JobExecutor {
                         size_bytes: last_bytes,
                         file_count: last_files,
                         is_latest: true,
+                        pinned: false,
                     };
 
-                    if let Err(e) = job_service.record_snapshot(&snapshot) {
-                        log::error!("Failed to record snapshot: {}", e);
-                    }
-
-                    // Apply retention policy — prune old snapshots from DB
-                    match job_service.apply_retention_policy(&job_uuid) {
-                        Ok(pruned_paths) => {
-                            for path in pruned_paths {
-                                log::info!("Retention: pruned snapshot {}", path);
-                                // Attempt to remove the directory on disk
-                                if let Err(e) = std::fs::remove_dir_all(&path) {
-                                    log::error!(
-                                        "Failed to remove pruned snapshot dir {}: {}",
-                                        path,
-                                        e
-                                    );
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            log::error!("Failed to apply retention policy: {}", e);
-                        }
+                    match job_service.record_snapshot(&snapshot) {
+                        Ok(()) => snapshot_recorded = true,
+                        Err(e) => log::error!("Failed to record snapshot: {}", e),
                     }
+                    prune_for_snapshot = Some(snap_path.clone());
                 }
             }
 
+            let error_message = match status {
+                InvocationStatus::Failed => Some(run_failure_message(
+                    exit_code,
+                    protocol_mismatch,
+                    &stderr_tail,
+                )),
+                InvocationStatus::StoppedByLimit => Some(format!(
+                    "Stopped after reaching the {}-minute run-time limit",
+                    max_runtime.map_or(0, |limit| limit.as_secs() / 60)
+                )),
+                _ => None,
+            };
+            let alarm_notified = warning.as_deref().is_some_and(|warning| {
+                notify_change_alarm(
+                    notifier.as_deref(),
+                    &job_for_notify,
+                    invocation_id,
+                    status.clone(),
+                    warning,
+                )
+            });
+            if !alarm_notified {
+                notify_finished(
+                    notifier.as_deref(),
+                    &job_for_notify,
+                    invocation_id,
+                    status,
+                    error_message.clone(),
+                );
+            }
+
             handler.on_status_change(JobStatusEvent {
                 job_id: job_uuid,
                 invocation_id,
                 status: job_status,
                 exit_code,
-                error_message: if status == InvocationStatus::Failed {
-                    Some(format!(
-                        "rsync exited with code {}",
-                        exit_code.unwrap_or(-1)
-                    ))
-                } else {
-                    None
-                },
+                error_message,
             });
+
+            // Prune after reporting the run: deleting old snapshot trees can take a
+            // while and the run is already over
+            if let Some(snap_path) = prune_for_snapshot {
+                prune_job_snapshots(
+                    &job_service,
+                    file_system.as_ref(),
+                    &job_uuid,
+                    Path::new(&snap_path),
+                    snapshot_trash_dir.as_deref().map(Path::new),
+                );
+            }
+
+            // Verify after reporting the run, so a long checksum pass doesn't hold it up
+            if let (true, Some(args)) = (snapshot_recorded, verify_args) {
+                verify_recorded_snapshot(
+                    rsync_client.as_ref(),
+                    &job_service,
+                    notifier.as_deref(),
+                    &job_for_notify,
+                    invocation_id,
+                    &args,
+                    handler.as_ref(),
+                );
+            }
+            drop(password_file);
         });
 
         Ok(invocation_id)
     }
 
+    /// The private file holding the rsync daemon password `job` needs, if it
+    /// needs one. The file lives only as long as the returned value.
+    fn daemon_password_file(
+        &self,
+        job: &JobDefinition,
+        invocation_id: Uuid,
+    ) -> Result<Option<PasswordFile>, String> {
+        let Some(name) = daemon_secret_name(job) else {
+            return Ok(None);
+        };
+        let password = self
+            .settings_service
+            .get_daemon_secret(name)
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("Run refused: rsync daemon password '{}' is not set", name))?;
+        PasswordFile::create(&std::env::temp_dir(), invocation_id, &password)
+            .map(Some)
+            .map_err(|e| format!("Failed to write rsync password file: {}", e))
+    }
+
+    /// Give `invocation` its log file, from the configured template under the
+    /// log directory, and add rsync's own log next to it to `args` when that
+    /// is enabled.
+    fn attach_log_file(
+        &self,
+        job: &JobDefinition,
+        invocation: &mut BackupInvocation,
+        args: &mut Vec<String>,
+        log_settings: &LogFileSettings,
+    ) {
+        // Resolve log directory from settings, fallback to default
+        let log_dir = self
+            .settings_service
+            .get_log_directory()
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| self.default_log_dir.clone());
+        let log_path = log_path_for(&log_dir, job, invocation, log_settings);
+        if let Err(e) = ensure_log_parent(&log_path) {
+            log::error!(
+                "Failed to create log directory for {}: {}",
+                log_path.display(),
+                e
+            );
+        }
+        let log_file_path = log_path.to_string_lossy().to_string();
+
+        // rsync's own log sits next to the captured one and shares its lifetime
+        args.extend(rsync_log_args(&log_file_path, log_settings));
+        invocation.execution_output.log_file_path = Some(log_file_path);
+    }
+
+    /// The argument list of each rsync process a run of `job` starts: one per
+    /// share of the source's top-level entries when the job is split,
+    /// otherwise `args` alone. `args` ends with the source at `source_index`
+    /// and the destination, followed by any flags appended later.
+    fn part_args(
+        &self,
+        job: &JobDefinition,
+        args: Vec<String>,
+        source_index: usize,
+        relative_paths: bool,
+    ) -> Result<Vec<Vec<String>>, String> {
+        let Some((parts, source_dir)) =
+            split_parallelism(job, relative_paths).zip(job.transfer.source.local_path())
+        else {
+            return Ok(vec![args]);
+        };
+        match split_source_entries(Path::new(source_dir), self.file_system.as_ref(), parts) {
+            Ok(Some(groups)) => Ok(part_arg_sets(&args, source_index, &groups)),
+            Ok(None) => Ok(vec![args]),
+            Err(e) => Err(format!("Could not list the source to split it: {}", e)),
+        }
+    }
+
+    /// Start one rsync process per argument list and register each under the
+    /// job, so cancelling the job stops them all. If one fails to start, the
+    /// ones already started are stopped. Their events arrive on one channel,
+    /// tagged with the index of the part that sent them.
+    fn spawn_parts(
+        &self,
+        job: &JobDefinition,
+        part_args: &[Vec<String>],
+        max_line_bytes: usize,
+        invocation_id: Uuid,
+    ) -> Result<StartedParts, String> {
+        let mut children = Vec::new();
+        let mut receivers = Vec::new();
+        for part in part_args {
+            match run_job(
+                &self.rsync.binary,
+                part,
+                &job.run_policy.env,
+                job.run_policy.process_priority,
+                max_line_bytes,
+                invocation_id,
+            ) {
+                Ok((child, rx)) => {
+                    children.push(child);
+                    receivers.push(rx);
+                }
+                Err(e) => {
+                    for mut child in children {
+                        let _ = child.kill();
+                        let _ = child.wait();
+                    }
+                    return Err(e.to_string());
+                }
+            }
+        }
+        let child_arcs = children
+            .into_iter()
+            .enumerate()
+            .map(|(i, child)| match i {
+                0 => self.running_jobs.insert(job.id, child),
+                _ => self.running_jobs.insert_part(job.id, child),
+            })
+            .collect();
+        Ok((child_arcs, merge_part_events(receivers)))
+    }
+
+    /// Finish a recorded invocation as failed without running rsync, reporting
+    /// `reason` like any other failure.
+    fn fail_before_transfer(
+        &self,
+        job: &JobDefinition,
+        mut invocation: BackupInvocation,
+        reason: String,
+        handler: &dyn ExecutionEventHandler,
+    ) {
+        log::warn!("Job '{}' ({}): {}", job.name, job.id, reason);
+        handler.on_log_line(LogLine {
+            invocation_id: invocation.id,
+            timestamp: Utc::now(),
+            line: format!("ERROR: {}", reason),
+            is_stderr: true,
+        });
+
+        invocation.finished_at = Some(Utc::now());
+        invocation.status = InvocationStatus::Failed;
+        if let Err(e) = self.job_service.complete_invocation(&invocation) {
+            log::error!("Failed to record invocation {}: {}", invocation.id, e);
+        }
+
+        notify_finished(
+            self.notifier.as_deref(),
+            job,
+            invocation.id,
+            InvocationStatus::Failed,
+            Some(reason.clone()),
+        );
+        handler.on_status_change(JobStatusEvent {
+            job_id: job.id,
+            invocation_id: invocation.id,
+            status: JobStatus::Failed,
+            exit_code: None,
+            error_message: Some(reason),
+        });
+    }
+
+    /// Execute a job and block until it reports a final status.
+    pub fn execute_and_wait(
+        &self,
+        job: &JobDefinition,
+        trigger: InvocationTrigger,
+        handler: Arc<dyn ExecutionEventHandler>,
+    ) -> Result<JobStatusEvent, String> {
+        let (tx, rx) = mpsc::channel();
+        let waiter = Arc::new(CompletionHandler {
+            inner: handler,
+            done: tx,
+        });
+        self.execute(job, trigger, waiter)?;
+        rx.recv()
+            .map_err(|_| "Job ended without reporting a status".to_string())
+    }
+
+    /// Run the jobs of a backup set one after another as manual runs.
+    /// Blocks until the set is done.
+    pub fn run_set(
+        &self,
+        set: &BackupSet,
+        stop_on_failure: bool,
+        handler: Arc<dyn ExecutionEventHandler>,
+    ) -> BackupSetRun {
+        let runner = SetExecutor {
+            executor: self,
+            handler,
+        };
+        run_backup_set(set, &self.job_service, &runner, stop_on_failure)
+    }
+
     /// Cancel a running job. Returns true if the job was found and killed.
     pub fn cancel(&self, job_id: &Uuid) -> bool {
         self.running_jobs.cancel(job_id)
     }
 
+    /// Cancel every running job, e.g. on shutdown. Each job's processes are
+    /// killed; its executor thread then records the run as cancelled and
+    /// removes it from the running set, so it cannot be started again first.
+    /// Returns how many jobs were cancelled; zero when nothing was running.
+    pub fn cancel_all(&self) -> usize {
+        self.running_job_ids()
+            .iter()
+            .filter(|job_id| self.running_jobs.cancel(job_id))
+            .count()
+    }
+
     /// Check if a job is currently running.
     pub fn is_running(&self, job_id: &Uuid) -> bool {
         self.running_jobs.is_running(job_id)
@@ -479,4 +1678,63 @@ impl JobExecutor {
     pub fn running_job_ids(&self) -> Vec<Uuid> {
         self.running_jobs.running_job_ids()
     }
+
+    /// Every running job with its name and latest progress.
+    pub fn running_jobs_info(&self) -> Vec<RunningJobInfo> {
+        self.running_job_ids()
+            .into_iter()
+            .map(|id| {
+                let name = self
+                    .job_service
+                    .get_job(&id)
+                    .map(|job| job.name)
+                    .unwrap_or_else(|_| id.to_string());
+                running_job_info(id, name, self.running_jobs.progress(&id).as_ref())
+            })
+            .collect()
+    }
+
+    /// Combined throughput and file progress across all running jobs.
+    pub fn aggregate_progress(&self) -> AggregateProgress {
+        self.running_jobs.aggregate_progress()
+    }
+}
+
+/// Forwards events to `inner` and reports the first final status on `done`.
+struct CompletionHandler {
+    inner: Arc<dyn ExecutionEventHandler>,
+    done: Sender<JobStatusEvent>,
+}
+
+impl ExecutionEventHandler for CompletionHandler {
+    fn on_log_line(&self, log_line: LogLine) {
+        self.inner.on_log_line(log_line);
+    }
+
+    fn on_progress(&self, progress: &ProgressUpdate) {
+        self.inner.on_progress(progress);
+    }
+
+    fn on_status_change(&self, status: JobStatusEvent) {
+        if status.status != JobStatus::Running {
+            let _ = self.done.send(status.clone());
+        }
+        self.inner.on_status_change(status);
+    }
+
+    fn on_itemized_change(&self, invocation_id: Uuid, change: &ItemizedChange) {
+        self.inner.on_itemized_change(invocation_id, change);
+    }
+}
+
+struct SetExecutor<'a> {
+    executor: &'a JobExecutor,
+    handler: Arc<dyn ExecutionEventHandler>,
+}
+
+impl SetJobRunner for SetExecutor<'_> {
+    fn run_to_completion(&self, job: &JobDefinition) -> Result<JobStatusEvent, String> {
+        self.executor
+            .execute_and_wait(job, InvocationTrigger::Manual, Arc::clone(&self.handler))
+    }
 }
@@ -1,4 +1,6 @@
-use std::io::BufRead;
+use std::borrow::Cow;
+use std::collections::{HashMap, VecDeque};
+use std::io::{BufRead, ErrorKind};
 use std::process::{Child, Command, Stdio};
 use std::sync::mpsc::{self, Receiver};
 
@@ -6,28 +8,311 @@ use uuid::Uuid;
 
 use crate::error::AppError;
 use crate::models::execution::event::ExecutionEvent;
+use crate::models::job::ProcessPriority;
+use crate::models::settings::DEFAULT_MAX_LOG_LINE_BYTES;
 use crate::services::itemize_parser::parse_itemize_line;
-use crate::services::progress_parser::parse_progress_line;
+use crate::services::progress_parser::ProgressTracker;
 use crate::rsync_client::RsyncError;
 
+/// Substrings that mark an environment variable as holding a secret.
+const SECRET_ENV_MARKERS: &[&str] = &["PASS", "SECRET", "TOKEN", "KEY", "CREDENTIAL", "PRIVATE"];
+
+/// Whether an environment variable's value should be hidden from logs.
+pub fn is_secret_env_var(name: &str) -> bool {
+    let upper = name.to_uppercase();
+    SECRET_ENV_MARKERS
+        .iter()
+        .any(|marker| upper.contains(marker))
+}
+
+/// Render environment variables as sorted `NAME=value` pairs for logging,
+/// masking the values of variables that look secret.
+pub fn describe_env(env: &HashMap<String, String>) -> Vec<String> {
+    let mut pairs: Vec<String> = env
+        .iter()
+        .map(|(name, value)| {
+            if is_secret_env_var(name) {
+                format!("{}=****", name)
+            } else {
+                format!("{}={}", name, value)
+            }
+        })
+        .collect();
+    pairs.sort();
+    pairs
+}
+
+/// rsync's exit code when `--max-delete` stopped it from deleting more files.
+pub const RSYNC_MAX_DELETE_EXIT_CODE: i32 = 25;
+
+/// The error message shown for a run that failed with `exit_code`.
+pub fn failure_message(exit_code: Option<i32>) -> String {
+    match exit_code {
+        Some(RSYNC_MAX_DELETE_EXIT_CODE) => {
+            "Delete limit reached: rsync stopped deleting files after hitting --max-delete"
+                .to_string()
+        }
+        code => format!("rsync exited with code {}", code.unwrap_or(-1)),
+    }
+}
+
+/// The failure message for a run whose stderr showed `protocol_mismatch`
+/// (the first line [`crate::rsync_client::is_protocol_mismatch`] matched),
+/// which explains it better than the exit code. Otherwise [`failure_message`],
+/// followed by the most telling line of `stderr` when rsync printed one.
+pub fn run_failure_message(
+    exit_code: Option<i32>,
+    protocol_mismatch: Option<String>,
+    stderr: &StderrTail,
+) -> String {
+    match (protocol_mismatch, stderr.salient_line()) {
+        (Some(detail), _) => RsyncError::ProtocolMismatch { detail, exit_code }.to_string(),
+        (None, Some(line)) => format!("{}: {}", failure_message(exit_code), line),
+        (None, None) => failure_message(exit_code),
+    }
+}
+
+/// How many of a run's last stderr lines [`StderrTail`] keeps.
+pub const STDERR_TAIL_LINES: usize = 5;
+
+/// The last few non-blank stderr lines of a run, kept so a failed run can
+/// say what rsync complained about and not just how it exited.
+#[derive(Debug, Default)]
+pub struct StderrTail {
+    lines: VecDeque<String>,
+}
+
+impl StderrTail {
+    pub fn push(&mut self, line: &str) {
+        let line = line.trim();
+        if line.is_empty() {
+            return;
+        }
+        if self.lines.len() == STDERR_TAIL_LINES {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line.to_string());
+    }
+
+    /// The line that best explains a failure: the last one that is not
+    /// rsync's closing "rsync error: ... (code N)" summary, which only
+    /// restates the exit code. The summary itself when nothing else was kept.
+    pub fn salient_line(&self) -> Option<&str> {
+        self.lines
+            .iter()
+            .rev()
+            .find(|line| !line.starts_with("rsync error:"))
+            .or_else(|| self.lines.back())
+            .map(String::as_str)
+    }
+}
+
+/// Whether a run that exited with `exit_code` counts as successful for a job
+/// accepting `success_codes`. A killed process (no code) never does.
+pub fn is_success_exit(exit_code: Option<i32>, success_codes: &[i32]) -> bool {
+    exit_code.is_some_and(|code| success_codes.contains(&code))
+}
+
+/// The program and arguments that run `binary` at `priority` on `os` (a
+/// `std::env::consts::OS` value).
+///
+/// Linux wraps the command in `nice` and `ionice`, other Unix systems in
+/// `nice` alone. Both wrappers exec the command, so the spawned child is still
+/// the one that gets cancelled. `Normal`, no priority, and platforms without
+/// these tools leave the command unchanged; Windows applies a priority class
+/// at spawn time instead.
+pub fn prioritized_command(
+    binary: &str,
+    args: &[String],
+    priority: Option<ProcessPriority>,
+    os: &str,
+) -> (String, Vec<String>) {
+    let (niceness, ionice_level) = match priority {
+        Some(ProcessPriority::Low) => ("10", "7"),
+        Some(ProcessPriority::High) => ("-5", "0"),
+        Some(ProcessPriority::Normal) | None => return (binary.to_string(), args.to_vec()),
+    };
+    let mut wrapped: Vec<String> = match os {
+        "linux" => ["-n", niceness, "ionice", "-c", "2", "-n", ionice_level]
+            .iter()
+            .map(|s| s.to_string())
+            .collect(),
+        "macos" | "freebsd" | "openbsd" | "netbsd" | "dragonfly" => {
+            vec!["-n".to_string(), niceness.to_string()]
+        }
+        _ => return (binary.to_string(), args.to_vec()),
+    };
+    wrapped.push(binary.to_string());
+    wrapped.extend_from_slice(args);
+    ("nice".to_string(), wrapped)
+}
+
+/// Windows process creation flag for `priority`, if it changes anything.
+#[cfg(windows)]
+fn priority_class(priority: Option<ProcessPriority>) -> Option<u32> {
+    const BELOW_NORMAL_PRIORITY_CLASS: u32 = 0x0000_4000;
+    const ABOVE_NORMAL_PRIORITY_CLASS: u32 = 0x0000_8000;
+    match priority {
+        Some(ProcessPriority::Low) => Some(BELOW_NORMAL_PRIORITY_CLASS),
+        Some(ProcessPriority::High) => Some(ABOVE_NORMAL_PRIORITY_CLASS),
+        Some(ProcessPriority::Normal) | None => None,
+    }
+}
+
+/// Marks the end of a line that was cut short by [`OutputLines`].
+pub const TRUNCATION_MARKER: &str = "…";
+
+/// Logged after a stream in which [`OutputLines`] replaced invalid UTF-8,
+/// so a `�` in a file name is not mistaken for the real name.
+pub fn invalid_utf8_notice(lossy_lines: usize) -> String {
+    format!(
+        "Note: {} line(s) of rsync output were not valid UTF-8; \
+         undecodable bytes are shown as \u{FFFD}",
+        lossy_lines
+    )
+}
+
+/// Splits rsync output into lines on both `\n` and `\r`.
+///
+/// rsync redraws `--progress` output in place with carriage returns, so a
+/// newline-only reader would see one long line per file and the percentage
+/// would appear frozen. Empty segments (such as the gap in `\r\n`) are
+/// skipped, and invalid UTF-8 (such as a Latin-1 file name) is replaced
+/// with U+FFFD rather than dropping the line or ending the stream; see
+/// [`OutputLines::lossy_lines`].
+///
+/// Lines longer than the cap (default [`DEFAULT_MAX_LOG_LINE_BYTES`]) keep
+/// their first bytes followed by [`TRUNCATION_MARKER`] and a count of what
+/// was dropped; the rest is discarded as it is read, never buffered.
+pub struct OutputLines<R> {
+    reader: R,
+    max_line_bytes: usize,
+    split_on_cr: bool,
+    lossy_lines: usize,
+}
+
+impl<R: BufRead> OutputLines<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            max_line_bytes: DEFAULT_MAX_LOG_LINE_BYTES,
+            split_on_cr: true,
+            lossy_lines: 0,
+        }
+    }
+
+    /// Cap each line at `max_line_bytes` bytes before the marker.
+    pub fn max_line_bytes(mut self, max_line_bytes: usize) -> Self {
+        self.max_line_bytes = max_line_bytes;
+        self
+    }
+
+    /// Split on `\n` only, for streams that never redraw in place.
+    pub fn newline_only(mut self) -> Self {
+        self.split_on_cr = false;
+        self
+    }
+
+    /// How many lines so far had invalid UTF-8 replaced.
+    pub fn lossy_lines(&self) -> usize {
+        self.lossy_lines
+    }
+
+    fn finish_line(&mut self, mut line: Vec<u8>, dropped: usize) -> String {
+        // Don't leave half of a multi-byte character before the marker
+        if dropped > 0 {
+            if let Err(e) = std::str::from_utf8(&line) {
+                if e.error_len().is_none() {
+                    line.truncate(e.valid_up_to());
+                }
+            }
+        }
+        let text = String::from_utf8_lossy(&line);
+        if matches!(text, Cow::Owned(_)) {
+            self.lossy_lines += 1;
+        }
+        if dropped == 0 {
+            return text.into_owned();
+        }
+        format!("{}{} [{} more bytes]", text, TRUNCATION_MARKER, dropped)
+    }
+}
+
+impl<R: BufRead> Iterator for OutputLines<R> {
+    type Item = std::io::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut line = Vec::new();
+        let mut dropped = 0;
+        loop {
+            let available = match self.reader.fill_buf() {
+                Ok(buf) => buf,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Some(Err(e)),
+            };
+            if available.is_empty() {
+                // End of stream: flush a final unterminated line
+                return (!line.is_empty()).then(|| Ok(self.finish_line(line, dropped)));
+            }
+            let split_on_cr = self.split_on_cr;
+            let end = available
+                .iter()
+                .position(|&b| b == b'\n' || (split_on_cr && b == b'\r'));
+            let chunk = &available[..end.unwrap_or(available.len())];
+            let room = self.max_line_bytes.saturating_sub(line.len());
+            let kept = chunk.len().min(room);
+            line.extend_from_slice(&chunk[..kept]);
+            dropped += chunk.len() - kept;
+            let consumed = chunk.len() + usize::from(end.is_some());
+            self.reader.consume(consumed);
+            if end.is_some() && !split_on_cr && line.last() == Some(&b'\r') {
+                line.pop();
+            }
+            if end.is_some() && !line.is_empty() {
+                return Some(Ok(self.finish_line(line, dropped)));
+            }
+        }
+    }
+}
+
 /// Spawns rsync as a child process with the given binary and args,
 /// returning the child handle and a receiver for execution events.
 ///
-/// Reader threads are spawned for stdout and stderr. Progress lines
-/// from stdout are parsed and emitted as Progress events in addition
+/// `env` is added on top of the inherited environment; an empty map leaves
+/// the child's environment unchanged. `priority` is applied as described in
+/// [`prioritized_command`].
+///
+/// Output lines longer than `max_line_bytes` are truncated before they become
+/// events, so one pathological line can't bloat the log or the UI.
+///
+/// Reader threads are spawned for stdout and stderr. Stdout is split on
+/// carriage returns as well as newlines (see [`OutputLines`]); progress lines
+/// from it are parsed and emitted as Progress events in addition
 /// to the StdoutLine event. When both readers finish, a Finished event
 /// is sent with the exit code.
 pub fn run_job(
     binary: &str,
     args: &[String],
+    env: &HashMap<String, String>,
+    priority: Option<ProcessPriority>,
+    max_line_bytes: usize,
     invocation_id: Uuid,
 ) -> Result<(Child, Receiver<ExecutionEvent>), AppError> {
-    let mut child = Command::new(binary)
-        .args(args)
+    let (program, program_args) = prioritized_command(binary, args, priority, std::env::consts::OS);
+    let mut command = Command::new(program);
+    command
+        .args(program_args)
+        .envs(env)
         .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .map_err(|e| AppError::RsyncError(RsyncError::IoError(format!("Failed to spawn rsync: {}", e))))?;
+        .stderr(Stdio::piped());
+    #[cfg(windows)]
+    if let Some(flags) = priority_class(priority) {
+        use std::os::windows::process::CommandExt;
+        command.creation_flags(flags);
+    }
+    let mut child = command.spawn().map_err(|e| {
+        AppError::RsyncError(RsyncError::IoError(format!("Failed to spawn rsync: {}", e)))
+    })?;
 
     let (tx, rx) = mpsc::channel();
 
@@ -45,10 +330,12 @@ pub fn run_job(
     let inv_id = invocation_id;
     std::thread::spawn(move || {
         let reader = std::io::BufReader::new(stdout);
-        for line_result in reader.lines() {
+        let mut tracker = ProgressTracker::new(inv_id);
+        let mut lines = OutputLines::new(reader).max_line_bytes(max_line_bytes);
+        for line_result in lines.by_ref() {
             match line_result {
                 Ok(text) => {
-                    if let Some(progress) = parse_progress_line(&text, inv_id) {
+                    if let Some(progress) = tracker.feed(&text) {
                         let _ = tx_out.send(ExecutionEvent::Progress(progress));
                     }
                     if let Some(change) = parse_itemize_line(&text) {
@@ -59,13 +346,20 @@ pub fn run_job(
                 Err(_) => break,
             }
         }
+        if lines.lossy_lines() > 0 {
+            let notice = invalid_utf8_notice(lines.lossy_lines());
+            let _ = tx_out.send(ExecutionEvent::StderrLine(notice));
+        }
     });
 
     // Stderr reader thread
     let tx_err = tx;
     std::thread::spawn(move || {
         let reader = std::io::BufReader::new(stderr);
-        for line_result in reader.lines() {
+        let mut lines = OutputLines::new(reader)
+            .max_line_bytes(max_line_bytes)
+            .newline_only();
+        for line_result in lines.by_ref() {
             match line_result {
                 Ok(text) => {
                     let _ = tx_err.send(ExecutionEvent::StderrLine(text));
@@ -73,6 +367,10 @@ pub fn run_job(
                 Err(_) => break,
             }
         }
+        if lines.lossy_lines() > 0 {
+            let notice = invalid_utf8_notice(lines.lossy_lines());
+            let _ = tx_err.send(ExecutionEvent::StderrLine(notice));
+        }
     });
 
     Ok((child, rx))
@@ -0,0 +1,96 @@
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use uuid::Uuid;
+
+use crate::error::JobServiceError;
+use crate::models::backup::{BackupInvocation, InvocationStatus};
+use crate::services::job_service::JobService;
+
+/// Reads lines appended to a log file since the previous read, like `tail -f`.
+///
+/// Works from any process: it only needs the log path, not the executor's
+/// in-memory events.
+pub struct LogFollower {
+    path: PathBuf,
+    offset: u64,
+    partial: Vec<u8>,
+    last_growth: Instant,
+}
+
+impl LogFollower {
+    /// Follow `path` from its beginning.
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            offset: 0,
+            partial: Vec::new(),
+            last_growth: Instant::now(),
+        }
+    }
+
+    /// Complete lines written since the last call.
+    ///
+    /// An unterminated last line is held back until its newline arrives. A
+    /// missing file yields nothing yet, and a file shorter than what was
+    /// already read (rotated by the writer) is read again from the start.
+    pub fn read_new_lines(&mut self) -> std::io::Result<Vec<String>> {
+        let mut file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        if file.metadata()?.len() < self.offset {
+            self.offset = 0;
+            self.partial.clear();
+        }
+
+        file.seek(SeekFrom::Start(self.offset))?;
+        let mut appended = Vec::new();
+        self.offset += file.read_to_end(&mut appended)? as u64;
+        if !appended.is_empty() {
+            self.last_growth = Instant::now();
+        }
+        self.partial.extend_from_slice(&appended);
+
+        let Some(last_newline) = self.partial.iter().rposition(|&b| b == b'\n') else {
+            return Ok(Vec::new());
+        };
+        let rest = self.partial.split_off(last_newline + 1);
+        let complete = std::mem::replace(&mut self.partial, rest);
+        Ok(String::from_utf8_lossy(&complete)
+            .lines()
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// How long the log has gone without growing, counted from when following
+    /// began. A run whose process died leaves its invocation `Running` and
+    /// its log still.
+    pub fn idle_for(&self) -> Duration {
+        self.last_growth.elapsed()
+    }
+
+    /// The held-back unterminated line, if any. Call once the writer is done.
+    pub fn finish(&mut self) -> Option<String> {
+        if self.partial.is_empty() {
+            return None;
+        }
+        let line = String::from_utf8_lossy(&self.partial).to_string();
+        self.partial.clear();
+        Some(line)
+    }
+}
+
+/// The job's most recent invocation, if it is still running.
+pub fn running_invocation(
+    job_service: &JobService,
+    job_id: &Uuid,
+) -> Result<Option<BackupInvocation>, JobServiceError> {
+    Ok(job_service
+        .get_job_history(job_id, 1)?
+        .into_iter()
+        .find(|inv| inv.status == InvocationStatus::Running))
+}
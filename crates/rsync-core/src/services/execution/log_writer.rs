@@ -0,0 +1,219 @@
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::models::backup::BackupInvocation;
+use crate::models::job::JobDefinition;
+use crate::models::log::StructuredLogRecord;
+use crate::models::settings::LogFileSettings;
+
+/// Number of rotated files (`<log>.1` … `<log>.N`) kept alongside a log.
+pub const MAX_ROTATED_LOG_FILES: usize = 5;
+
+/// Longest a written line waits in a text log's buffer: the poll interval of
+/// `rsync-commander tail`, so a follower still sees lines as they come.
+pub const LOG_FLUSH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Make a job name safe to use as a single path component.
+fn sanitize_component(name: &str) -> String {
+    let cleaned: String = name
+        .trim()
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    match cleaned.as_str() {
+        "" | "." | ".." => "job".to_string(),
+        _ => cleaned,
+    }
+}
+
+/// Expand `{job}`, `{date}` and `{id}` placeholders in a log path template.
+pub fn expand_log_template(
+    template: &str,
+    job: &JobDefinition,
+    invocation: &BackupInvocation,
+) -> String {
+    template
+        .replace("{job}", &sanitize_component(&job.name))
+        .replace(
+            "{date}",
+            &invocation.started_at.format("%Y-%m-%d").to_string(),
+        )
+        .replace("{id}", &invocation.id.to_string())
+}
+
+/// Resolve the log file path for an invocation under `log_dir`.
+///
+/// The expanded template is always treated as relative to `log_dir`; leading
+/// separators and `..` components are dropped so logs can't escape it.
+pub fn log_path_for(
+    log_dir: &str,
+    job: &JobDefinition,
+    invocation: &BackupInvocation,
+    settings: &LogFileSettings,
+) -> PathBuf {
+    let expanded = expand_log_template(&settings.path_template, job, invocation);
+    let mut path = PathBuf::from(log_dir);
+    for part in expanded.split(['/', '\\']) {
+        if part.is_empty() || part == "." || part == ".." {
+            continue;
+        }
+        path.push(part);
+    }
+    path
+}
+
+/// Create the directory that will hold a log file.
+pub fn ensure_log_parent(path: &Path) -> std::io::Result<()> {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => std::fs::create_dir_all(parent),
+        _ => Ok(()),
+    }
+}
+
+/// Paths of the rotated siblings of a log file (`<log>.1` … `<log>.N`).
+pub fn rotated_log_paths(path: &str) -> Vec<String> {
+    (1..=MAX_ROTATED_LOG_FILES)
+        .map(|i| format!("{}.{}", path, i))
+        .collect()
+}
+
+/// Path of the JSON Lines log kept next to a text log: a `.log` extension
+/// becomes `.jsonl`, any other name gets `.jsonl` appended.
+pub fn structured_log_path(path: &str) -> String {
+    match path.strip_suffix(".log") {
+        Some(stem) => format!("{}.jsonl", stem),
+        None => format!("{}.jsonl", path),
+    }
+}
+
+/// Path of the log rsync writes itself via `--log-file`: a `.log` extension
+/// becomes `.rsync.log`, any other name gets `.rsync.log` appended.
+pub fn rsync_log_path(path: &str) -> String {
+    match path.strip_suffix(".log") {
+        Some(stem) => format!("{}.rsync.log", stem),
+        None => format!("{}.rsync.log", path),
+    }
+}
+
+/// rsync arguments that make it write its own log next to the captured one,
+/// or nothing when the setting is off.
+pub fn rsync_log_args(path: &str, settings: &LogFileSettings) -> Vec<String> {
+    if !settings.rsync_log_file {
+        return Vec::new();
+    }
+    vec![
+        format!("--log-file={}", rsync_log_path(path)),
+        format!("--log-file-format={}", settings.rsync_log_format),
+    ]
+}
+
+/// Every file that belongs to a log: the log itself, its rotated siblings,
+/// its JSON Lines counterpart and rsync's own log.
+pub fn log_file_set(path: &str) -> Vec<String> {
+    let mut files = vec![path.to_string()];
+    files.extend(rotated_log_paths(path));
+    files.push(structured_log_path(path));
+    files.push(rsync_log_path(path));
+    files
+}
+
+/// Appends one JSON object per line. The file is not rotated.
+pub struct StructuredLogWriter {
+    writer: BufWriter<File>,
+}
+
+impl StructuredLogWriter {
+    pub fn open(path: &Path) -> std::io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            writer: BufWriter::new(file),
+        })
+    }
+
+    pub fn write_record(&mut self, record: &StructuredLogRecord) -> std::io::Result<()> {
+        serde_json::to_writer(&mut self.writer, record)?;
+        writeln!(self.writer)
+    }
+
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Appending log writer that rotates the file once it exceeds a size limit.
+pub struct RotatingLogWriter {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    written: u64,
+    max_size_bytes: Option<u64>,
+    last_flush: Instant,
+}
+
+impl RotatingLogWriter {
+    pub fn open(path: &Path, max_size_bytes: Option<u64>) -> std::io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let written = file.metadata().map(|m| m.len()).unwrap_or(0);
+        Ok(Self {
+            path: path.to_path_buf(),
+            writer: BufWriter::new(file),
+            written,
+            max_size_bytes,
+            last_flush: Instant::now(),
+        })
+    }
+
+    pub fn write_line(&mut self, line: &str) -> std::io::Result<()> {
+        let len = line.len() as u64 + 1;
+        if let Some(max) = self.max_size_bytes {
+            if self.written > 0 && self.written + len > max {
+                self.rotate()?;
+            }
+        }
+        writeln!(self.writer, "{}", line)?;
+        self.written += len;
+        self.flush_if_due()
+    }
+
+    /// Flush if the last flush was at least [`LOG_FLUSH_INTERVAL`] ago, so
+    /// another process can follow the log without a write per line. Call it
+    /// on a timer as well while lines may stop coming.
+    pub fn flush_if_due(&mut self) -> std::io::Result<()> {
+        if self.last_flush.elapsed() < LOG_FLUSH_INTERVAL {
+            return Ok(());
+        }
+        self.flush()
+    }
+
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.last_flush = Instant::now();
+        self.writer.flush()
+    }
+
+    /// Shift `<log>.N-1` → `<log>.N` … `<log>` → `<log>.1` and start a fresh file.
+    fn rotate(&mut self) -> std::io::Result<()> {
+        self.writer.flush()?;
+        let base = self.path.to_string_lossy().to_string();
+        let rotated = rotated_log_paths(&base);
+        for i in (1..rotated.len()).rev() {
+            let from = Path::new(&rotated[i - 1]);
+            if from.exists() {
+                std::fs::rename(from, &rotated[i])?;
+            }
+        }
+        std::fs::rename(&self.path, &rotated[0])?;
+
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        self.writer = BufWriter::new(file);
+        self.written = 0;
+        Ok(())
+    }
+}
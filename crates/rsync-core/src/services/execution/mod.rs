@@ -1,5 +1,21 @@
+pub mod backup_set_runner;
+pub mod change_alarm;
+pub mod daemon_auth;
+pub mod destination_prep;
 pub mod execution_handler;
+pub mod itemize_recorder;
 pub mod job_executor;
 pub mod job_runner;
+pub mod log_follow;
+pub mod log_writer;
+pub mod notifier;
+pub mod parallel_split;
 pub mod progress_parser;
+pub mod rerun;
+pub mod run_conditions;
 pub mod running_jobs;
+pub mod runtime_limit;
+pub mod snapshot_resume;
+pub mod snapshot_verify;
+pub mod test_mode;
+pub mod throttled_handler;
@@ -0,0 +1,104 @@
+use uuid::Uuid;
+
+use crate::models::backup::{InvocationStatus, SnapshotVerification};
+use crate::models::job::{JobDefinition, NotifyOn};
+use crate::models::progress::JobNotification;
+use crate::services::snapshot_verify::verification_summary;
+
+/// Delivers run notifications, e.g. as desktop notifications. Frontends
+/// register one with `JobExecutor::with_notifier`.
+pub trait Notifier: Send + Sync {
+    fn notify(&self, notification: &JobNotification);
+}
+
+/// Whether a run that finished with `status` should be reported under `notify_on`.
+pub fn should_notify(notify_on: NotifyOn, status: &InvocationStatus) -> bool {
+    match notify_on {
+        NotifyOn::Never => false,
+        NotifyOn::Always => *status != InvocationStatus::Running,
+        NotifyOn::Failure => matches!(
+            status,
+            InvocationStatus::Failed | InvocationStatus::StoppedByLimit
+        ),
+    }
+}
+
+/// Send a notification for a finished run if the job's `notify_on` asks for it.
+/// Returns whether one was sent.
+pub fn notify_finished(
+    notifier: Option<&dyn Notifier>,
+    job: &JobDefinition,
+    invocation_id: Uuid,
+    status: InvocationStatus,
+    error_message: Option<String>,
+) -> bool {
+    let Some(notifier) = notifier else {
+        return false;
+    };
+    if !should_notify(job.run_policy.notify_on, &status) {
+        return false;
+    }
+    notifier.notify(&JobNotification {
+        job_id: job.id,
+        job_name: job.name.clone(),
+        invocation_id,
+        status,
+        error_message,
+    });
+    true
+}
+
+/// Report a run that tripped the job's change alarm, if the alarm asks to
+/// notify. Sent whatever `notify_on` says, with the warning as its message.
+/// Returns whether one was sent.
+pub fn notify_change_alarm(
+    notifier: Option<&dyn Notifier>,
+    job: &JobDefinition,
+    invocation_id: Uuid,
+    status: InvocationStatus,
+    warning: &str,
+) -> bool {
+    let Some(notifier) = notifier else {
+        return false;
+    };
+    if !job
+        .safety
+        .change_alarm
+        .as_ref()
+        .is_some_and(|alarm| alarm.notify)
+    {
+        return false;
+    }
+    notifier.notify(&JobNotification {
+        job_id: job.id,
+        job_name: job.name.clone(),
+        invocation_id,
+        status,
+        error_message: Some(warning.to_string()),
+    });
+    true
+}
+
+/// Report a snapshot that failed its post-run verification, unless the job's
+/// `notify_on` is `Never`. A passing verification is never reported.
+/// Returns whether one was sent.
+pub fn notify_verification_failed(
+    notifier: Option<&dyn Notifier>,
+    job: &JobDefinition,
+    verification: &SnapshotVerification,
+) -> bool {
+    let Some(notifier) = notifier else {
+        return false;
+    };
+    if verification.passed || job.run_policy.notify_on == NotifyOn::Never {
+        return false;
+    }
+    notifier.notify(&JobNotification {
+        job_id: job.id,
+        job_name: job.name.clone(),
+        invocation_id: verification.invocation_id,
+        status: InvocationStatus::Succeeded,
+        error_message: Some(verification_summary(verification)),
+    });
+    true
+}
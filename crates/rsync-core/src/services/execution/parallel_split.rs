@@ -0,0 +1,208 @@
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver};
+
+use crate::file_system::{FileSystem, FsError};
+use crate::models::execution::event::ExecutionEvent;
+use crate::models::execution::progress::{ProgressUpdate, StatsBlock};
+use crate::models::job::{BackupMode, JobDefinition};
+use crate::services::job_runner::is_success_exit;
+use crate::services::progress_parser::parse_transfer_rate;
+
+/// The most rsync processes one job may split its source across.
+pub const MAX_PARALLEL_SPLIT: u32 = 16;
+
+/// How many rsync processes `job` should split its source across, or `None`
+/// for an ordinary single rsync run.
+///
+/// Only local and mounted sources can be listed up front, and `--relative`
+/// paths would change meaning once the source is split, so both fall back
+/// to a single process. So do snapshot jobs, whose parts would no longer
+/// line up with the `--link-dest` snapshot, and jobs using `--delete`,
+/// which could not remove top-level entries gone from the source.
+pub fn split_parallelism(job: &JobDefinition, relative_paths: bool) -> Option<usize> {
+    let parts = job.run_policy.parallel_split.filter(|n| *n >= 2)?;
+    if relative_paths
+        || job.transfer.source.local_path().is_none()
+        || matches!(job.transfer.backup_mode, BackupMode::Snapshot { .. })
+        || job.options.file_handling.delete
+    {
+        return None;
+    }
+    Some(parts.min(MAX_PARALLEL_SPLIT) as usize)
+}
+
+/// Deal the top-level entries of `source_dir` into up to `parts` groups of
+/// names, in sorted order and round-robin so neighbors land apart.
+///
+/// Returns `None` when there are fewer than two entries, as there is
+/// nothing to split.
+pub fn split_source_entries(
+    source_dir: &Path,
+    fs: &dyn FileSystem,
+    parts: usize,
+) -> Result<Option<Vec<Vec<String>>>, FsError> {
+    let mut names: Vec<String> = fs
+        .read_dir(source_dir)?
+        .iter()
+        .filter_map(|entry| entry.file_name())
+        .map(|name| name.to_string_lossy().to_string())
+        .collect();
+    if names.len() < 2 {
+        return Ok(None);
+    }
+    names.sort();
+
+    let mut groups = vec![Vec::new(); parts.min(names.len())];
+    let count = groups.len();
+    for (i, name) in names.into_iter().enumerate() {
+        groups[i % count].push(name);
+    }
+    Ok(Some(groups))
+}
+
+/// One rsync argument list per group, each copying only its group's entries.
+///
+/// `args` holds the source at `source_index` followed by the destination.
+/// A source with a trailing slash copies its contents into the destination,
+/// so each entry goes there as is; without one rsync would have created a
+/// folder named after the source, so each part targets that folder instead.
+pub fn part_arg_sets(
+    args: &[String],
+    source_index: usize,
+    groups: &[Vec<String>],
+) -> Vec<Vec<String>> {
+    let source = &args[source_index];
+    let destination = &args[source_index + 1];
+    let base = source.trim_end_matches('/');
+    let part_destination = if source.ends_with('/') {
+        destination.clone()
+    } else {
+        let name = Path::new(base)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        format!("{}/{}/", destination.trim_end_matches('/'), name)
+    };
+
+    groups
+        .iter()
+        .map(|group| {
+            let mut part = args[..source_index].to_vec();
+            part.extend(group.iter().map(|name| format!("{}/{}", base, name)));
+            part.push(part_destination.clone());
+            part.extend_from_slice(&args[source_index + 2..]);
+            part
+        })
+        .collect()
+}
+
+/// Forward the events of every part onto one channel, tagged with the
+/// part's index. The channel closes once every part's events have ended.
+pub fn merge_part_events(
+    receivers: Vec<Receiver<ExecutionEvent>>,
+) -> Receiver<(usize, ExecutionEvent)> {
+    let (tx, rx) = mpsc::channel();
+    for (part, receiver) in receivers.into_iter().enumerate() {
+        let tx = tx.clone();
+        std::thread::spawn(move || {
+            while let Ok(event) = receiver.recv() {
+                if tx.send((part, event)).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    rx
+}
+
+/// The exit code that stands for a run made of several rsync processes.
+///
+/// A killed part (no code) makes the whole run look killed. Otherwise the
+/// first code outside `success_codes` wins, so one failing part fails the
+/// run; with every part successful the first part's code is used.
+pub fn combined_exit_code(codes: &[Option<i32>], success_codes: &[i32]) -> Option<i32> {
+    let codes: Vec<i32> = codes.iter().copied().collect::<Option<_>>()?;
+    codes
+        .iter()
+        .find(|code| !is_success_exit(Some(**code), success_codes))
+        .or_else(|| codes.first())
+        .copied()
+}
+
+/// One progress update covering every part, from each part's latest update.
+///
+/// Byte and file counts and rates add up, the ETA is the slowest part's, and
+/// the current file and elapsed time come from `current`, the update that
+/// just arrived.
+pub fn combined_progress(latest: &[ProgressUpdate], current: &ProgressUpdate) -> ProgressUpdate {
+    let files_total: u64 = latest.iter().map(|p| p.files_total).sum();
+    let files_remaining: u64 = latest.iter().map(|p| p.files_remaining).sum();
+    let percentage = if files_total > 0 {
+        files_total.saturating_sub(files_remaining) as f64 * 100.0 / files_total as f64
+    } else {
+        latest.iter().map(|p| p.percentage).sum::<f64>() / latest.len().max(1) as f64
+    };
+    let rate: f64 = latest
+        .iter()
+        .filter_map(|p| parse_transfer_rate(&p.transfer_rate))
+        .sum();
+
+    ProgressUpdate {
+        invocation_id: current.invocation_id,
+        bytes_transferred: latest.iter().map(|p| p.bytes_transferred).sum(),
+        percentage,
+        transfer_rate: rsync_rate(rate),
+        elapsed: current.elapsed.clone(),
+        files_transferred: latest.iter().map(|p| p.files_transferred).sum(),
+        files_remaining,
+        files_total,
+        eta_secs: latest.iter().filter_map(|p| p.eta_secs).max(),
+        current_file: current.current_file.clone(),
+    }
+}
+
+/// The `--stats` figures of every part added together. Times are the
+/// longest part's, since the parts ran side by side.
+pub fn combined_stats(blocks: &[StatsBlock]) -> StatsBlock {
+    let sum = |field: fn(&StatsBlock) -> Option<u64>| {
+        blocks.iter().filter_map(field).reduce(|a, b| a + b)
+    };
+    let longest =
+        |field: fn(&StatsBlock) -> Option<f64>| blocks.iter().filter_map(field).reduce(f64::max);
+
+    let mut combined = StatsBlock {
+        total_files: sum(|b| b.total_files),
+        files_transferred: sum(|b| b.files_transferred),
+        total_file_size: sum(|b| b.total_file_size),
+        transferred_file_size: sum(|b| b.transferred_file_size),
+        deleted_files: sum(|b| b.deleted_files),
+        ..StatsBlock::default()
+    };
+    let detailed = &mut combined.detailed;
+    detailed.created_files = sum(|b| b.detailed.created_files);
+    detailed.literal_data = sum(|b| b.detailed.literal_data);
+    detailed.matched_data = sum(|b| b.detailed.matched_data);
+    detailed.file_list_size = sum(|b| b.detailed.file_list_size);
+    detailed.file_list_generation_secs = longest(|b| b.detailed.file_list_generation_secs);
+    detailed.file_list_transfer_secs = longest(|b| b.detailed.file_list_transfer_secs);
+    detailed.total_bytes_sent = sum(|b| b.detailed.total_bytes_sent);
+    detailed.total_bytes_received = sum(|b| b.detailed.total_bytes_received);
+    detailed.bytes_per_sec = blocks
+        .iter()
+        .filter_map(|b| b.detailed.bytes_per_sec)
+        .reduce(|a, b| a + b);
+    combined
+}
+
+/// A rate in the form rsync prints it, so `parse_transfer_rate` reads it back.
+fn rsync_rate(bytes_per_sec: f64) -> String {
+    if bytes_per_sec >= 1_000_000_000.0 {
+        format!("{:.2}GB/s", bytes_per_sec / 1_000_000_000.0)
+    } else if bytes_per_sec >= 1_000_000.0 {
+        format!("{:.2}MB/s", bytes_per_sec / 1_000_000.0)
+    } else if bytes_per_sec >= 1_000.0 {
+        format!("{:.2}kB/s", bytes_per_sec / 1_000.0)
+    } else {
+        format!("{:.2}B/s", bytes_per_sec)
+    }
+}
@@ -2,8 +2,10 @@ use regex::Regex;
 use std::sync::LazyLock;
 use uuid::Uuid;
 
-use crate::models::execution::progress::TransferSummary;
+use crate::models::execution::progress::{StatsBlock, TransferSummary};
+use crate::models::itemize::{FileType, TransferType};
 use crate::models::progress::ProgressUpdate;
+use crate::services::itemize_parser::parse_itemize_line;
 
 // rsync --progress output format:
 //      32,768 100%   31.25kB/s    0:00:00 (xfr#1, to-chk=2/4)
@@ -21,12 +23,14 @@ static PROGRESS_RE: LazyLock<Regex> = LazyLock::new(|| {
 // rsync summary line: "sent 123,456 bytes  received 789 bytes  41,415.00 bytes/sec"
 // With -h: "sent 120.56K bytes  received 789 bytes  40.45K bytes/sec"
 static SUMMARY_RE: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"^sent ([\d.,]+[KMGkmg]?) bytes\s+received ([\d.,]+[KMGkmg]?) bytes")
-        .expect("invalid summary regex")
+    Regex::new(
+        r"^sent ([\d.,]+[KMGkmg]?) bytes\s+received ([\d.,]+[KMGkmg]?) bytes(?:\s+([\d.,]+[KMGkmg]?) bytes/sec)?",
+    )
+    .expect("invalid summary regex")
 });
 
 /// Parse the rsync summary line: "sent 123,456 bytes  received 789 bytes  ..."
-/// Returns sent and received byte counts.
+/// Returns sent and received byte counts, and the average rate if present.
 pub fn parse_summary_line(line: &str) -> Option<TransferSummary> {
     let caps = SUMMARY_RE.captures(line)?;
     let sent = parse_human_bytes(&caps[1])?;
@@ -34,9 +38,71 @@ pub fn parse_summary_line(line: &str) -> Option<TransferSummary> {
     Some(TransferSummary {
         sent_bytes: sent,
         received_bytes: received,
+        bytes_per_sec: caps
+            .get(3)
+            .and_then(|rate| parse_transfer_rate(&format!("{}/s", rate.as_str()))),
     })
 }
 
+/// Parse the `--stats` block from rsync output, e.g.
+///
+/// ```text
+/// Number of files: 1,234 (reg: 1,000, dir: 234)
+/// Number of deleted files: 5 (reg: 4, dir: 1)
+/// Number of regular files transferred: 10
+/// Total file size: 12,345,678 bytes
+/// Total transferred file size: 1,234,567 bytes
+/// ```
+///
+/// The rest of an `--info=stats2` block (literal and matched data, file list
+/// size and times, total bytes sent and received) goes into
+/// `StatsBlock::detailed`, along with the rate from the summary line. Lines
+/// it doesn't know, such as `stats3` heap statistics, are ignored.
+///
+/// Returns `None` if the output has no "Total transferred file size" line.
+pub fn parse_stats_block(output: &str) -> Option<StatsBlock> {
+    let mut stats = StatsBlock::default();
+    for line in output.lines() {
+        feed_stats_line(&mut stats, line);
+        if let Some(summary) = parse_summary_line(line) {
+            stats.detailed.bytes_per_sec = summary.bytes_per_sec;
+        }
+    }
+    stats.transferred_file_size.map(|_| stats)
+}
+
+/// Fill in the `StatsBlock` field for one `--stats` line. Other lines are
+/// ignored, so streamed output can be fed line by line.
+pub fn feed_stats_line(stats: &mut StatsBlock, line: &str) {
+    let Some((label, value)) = line.trim().split_once(": ") else {
+        return;
+    };
+    // The count or size is the first token: "1,234 (reg: ...)", "12.35M bytes"
+    let first = value.split_whitespace().next();
+    let number = first.and_then(parse_human_bytes);
+    // Times are in seconds with three decimals: "0.001 seconds", "1,234.567 seconds"
+    let seconds = first.and_then(|s| s.replace(',', "").parse::<f64>().ok());
+    let detailed = &mut stats.detailed;
+    match label {
+        "Number of files" => stats.total_files = number,
+        "Number of deleted files" => stats.deleted_files = number,
+        "Number of regular files transferred" | "Number of files transferred" => {
+            stats.files_transferred = number
+        }
+        "Total file size" => stats.total_file_size = number,
+        "Total transferred file size" => stats.transferred_file_size = number,
+        "Number of created files" => detailed.created_files = number,
+        "Literal data" => detailed.literal_data = number,
+        "Matched data" => detailed.matched_data = number,
+        "File list size" => detailed.file_list_size = number,
+        "File list generation time" => detailed.file_list_generation_secs = seconds,
+        "File list transfer time" => detailed.file_list_transfer_secs = seconds,
+        "Total bytes sent" => detailed.total_bytes_sent = number,
+        "Total bytes received" => detailed.total_bytes_received = number,
+        _ => {}
+    }
+}
+
 /// Parse a byte value that may be human-readable (e.g. "205.18M") or a raw integer with commas.
 pub fn parse_human_bytes(s: &str) -> Option<u64> {
     let s = s.trim();
@@ -65,7 +131,63 @@ pub fn parse_human_bytes(s: &str) -> Option<u64> {
     }
 }
 
+/// Parse an rsync transfer rate (e.g. "31.25kB/s", "7.46M/s") into bytes per second.
+pub fn parse_transfer_rate(rate: &str) -> Option<f64> {
+    let value = rate.trim().strip_suffix("/s")?;
+    let value = value.strip_suffix('B').unwrap_or(value);
+    let (num, multiplier) = match value.as_bytes().last()? {
+        b'K' | b'k' => (&value[..value.len() - 1], 1_000.0),
+        b'M' | b'm' => (&value[..value.len() - 1], 1_000_000.0),
+        b'G' | b'g' => (&value[..value.len() - 1], 1_000_000_000.0),
+        _ => (value, 1.0),
+    };
+    let num: f64 = num.replace(',', "").parse().ok()?;
+    Some(num * multiplier)
+}
+
+/// Estimate the bytes still to transfer: the rest of the current file plus
+/// `files_remaining` further files of `avg_file_size` bytes each (falling
+/// back to the current file's size).
+///
+/// Returns None when the current file's size can't be inferred (0%).
+pub fn estimate_remaining_bytes(
+    update: &ProgressUpdate,
+    files_remaining: Option<u64>,
+    avg_file_size: Option<u64>,
+) -> Option<u64> {
+    if update.percentage <= 0.0 {
+        return None;
+    }
+    let file_size = (update.bytes_transferred as f64 * 100.0 / update.percentage) as u64;
+    let current_remaining = file_size.saturating_sub(update.bytes_transferred);
+
+    // While a file is mid-transfer it is still counted in the to-check total
+    let mut other_files = files_remaining.unwrap_or(0);
+    if update.percentage < 100.0 {
+        other_files = other_files.saturating_sub(1);
+    }
+    let other_bytes = other_files.saturating_mul(avg_file_size.unwrap_or(file_size));
+
+    Some(current_remaining.saturating_add(other_bytes))
+}
+
+/// Estimate seconds remaining from a rate in bytes/sec and the remaining bytes.
+///
+/// Returns None if the rate is zero or the remaining work is unknown.
+pub fn estimate_eta_secs(rate_bytes_per_sec: f64, remaining_bytes: Option<u64>) -> Option<u64> {
+    let remaining = remaining_bytes?;
+    if rate_bytes_per_sec <= 0.0 || !rate_bytes_per_sec.is_finite() {
+        return None;
+    }
+    Some((remaining as f64 / rate_bytes_per_sec).ceil() as u64)
+}
+
 pub fn parse_progress_line(line: &str, invocation_id: Uuid) -> Option<ProgressUpdate> {
+    // A chunk still holding several carriage-return updates: the last is current
+    let line = line
+        .rsplit('\r')
+        .find(|segment| !segment.trim().is_empty())
+        .unwrap_or(line);
     let caps = PROGRESS_RE.captures(line)?;
 
     let bytes_transferred: u64 = parse_human_bytes(&caps[1])?;
@@ -77,16 +199,14 @@ pub fn parse_progress_line(line: &str, invocation_id: Uuid) -> Option<ProgressUp
         .get(5)
         .and_then(|m| m.as_str().parse::<u64>().ok())
         .unwrap_or(0);
-    let files_remaining = caps
-        .get(6)
-        .and_then(|m| m.as_str().parse::<u64>().ok())
-        .unwrap_or(0);
+    let known_remaining = caps.get(6).and_then(|m| m.as_str().parse::<u64>().ok());
+    let files_remaining = known_remaining.unwrap_or(0);
     let files_total = caps
         .get(7)
         .and_then(|m| m.as_str().parse::<u64>().ok())
         .unwrap_or(0);
 
-    Some(ProgressUpdate {
+    let mut update = ProgressUpdate {
         invocation_id,
         bytes_transferred,
         percentage,
@@ -95,7 +215,90 @@ pub fn parse_progress_line(line: &str, invocation_id: Uuid) -> Option<ProgressUp
         files_transferred,
         files_remaining,
         files_total,
-    })
+        eta_secs: None,
+        current_file: None,
+    };
+    update.eta_secs = estimate_eta_secs(
+        parse_transfer_rate(&update.transfer_rate).unwrap_or(0.0),
+        estimate_remaining_bytes(&update, known_remaining, None),
+    );
+
+    Some(update)
+}
+
+// Non-file lines rsync prints to stdout around the per-file output
+const NON_FILE_PREFIXES: &[&str] = &[
+    "sending incremental file list",
+    "receiving incremental file list",
+    "sending file list",
+    "receiving file list",
+    "building file list",
+    "created directory ",
+    "deleting ",
+    "skipping ",
+    "sent ",
+    "total size is ",
+    "total: ",
+    "Number of ",
+    "Total ",
+    "Literal data: ",
+    "Matched data: ",
+    "File list ",
+    "rsync: ",
+    "rsync error: ",
+];
+
+/// The file name a stdout line announces, if it is one.
+///
+/// Plain `--progress` output puts each file's name on its own line before
+/// its progress lines; with `--itemize-changes` the name follows the change
+/// code. Directories, progress lines and the summary are not file names.
+pub fn parse_file_name_line(line: &str) -> Option<String> {
+    if line.trim().is_empty() || line.starts_with(char::is_whitespace) {
+        return None;
+    }
+    if let Some(change) = parse_itemize_line(line) {
+        return (change.file_type == FileType::File
+            && change.transfer_type != TransferType::Message)
+            .then_some(change.path);
+    }
+    if line == "done"
+        || line.ends_with('/')
+        || NON_FILE_PREFIXES.iter().any(|p| line.starts_with(p))
+        || PROGRESS_RE.is_match(line)
+        || SUMMARY_RE.is_match(line)
+    {
+        return None;
+    }
+    Some(line.to_string())
+}
+
+/// Stateful stdout parser that attaches the most recent file name to each
+/// progress update.
+pub struct ProgressTracker {
+    invocation_id: Uuid,
+    current_file: Option<String>,
+}
+
+impl ProgressTracker {
+    pub fn new(invocation_id: Uuid) -> Self {
+        Self {
+            invocation_id,
+            current_file: None,
+        }
+    }
+
+    /// Feed one stdout line. Returns an update if the line was a progress line.
+    pub fn feed(&mut self, line: &str) -> Option<ProgressUpdate> {
+        if let Some(mut update) = parse_progress_line(line, self.invocation_id) {
+            update.current_file = self.current_file.clone();
+            return Some(update);
+        }
+        if let Some(name) = parse_file_name_line(line) {
+            self.current_file = Some(name);
+        }
+        None
+    }
 }
 
 #[cfg(test)]
@@ -119,6 +322,15 @@ mod tests {
         assert_eq!(update.files_total, 4);
     }
 
+    #[test]
+    fn test_parse_uses_last_carriage_return_segment() {
+        let line = "     16,384  50%   15.00kB/s    0:00:01\r     32,768 100%   31.25kB/s    0:00:00 (xfr#1, to-chk=0/1)\r";
+        let update = parse_progress_line(line, test_id()).unwrap();
+        assert_eq!(update.bytes_transferred, 32768);
+        assert_eq!(update.percentage, 100.0);
+        assert_eq!(update.files_transferred, 1);
+    }
+
     #[test]
     fn test_parse_without_xfr_suffix() {
         let line = "  1,048,576  50%  500.00kB/s    0:00:01";
@@ -284,10 +496,282 @@ mod tests {
         assert_eq!(summary.received_bytes, 56);
     }
 
+    #[test]
+    fn test_parse_summary_line_rate() {
+        let plain =
+            parse_summary_line("sent 123,456 bytes  received 789 bytes  41,415.00 bytes/sec");
+        assert_eq!(plain.unwrap().bytes_per_sec, Some(41_415.0));
+
+        let human = parse_summary_line("sent 120.56K bytes  received 789 bytes  40.45K bytes/sec");
+        assert_eq!(human.unwrap().bytes_per_sec, Some(40_450.0));
+
+        let bare = parse_summary_line("sent 234 bytes  received 56 bytes");
+        assert_eq!(bare.unwrap().bytes_per_sec, None);
+    }
+
     #[test]
     fn test_parse_summary_line_not_summary() {
         assert!(parse_summary_line("sending incremental file list").is_none());
         assert!(parse_summary_line("total size is 987,654  speedup is 7.96").is_none());
         assert!(parse_summary_line("").is_none());
     }
+
+    // --- ETA tests ---
+
+    #[test]
+    fn test_parse_transfer_rate() {
+        assert_eq!(parse_transfer_rate("31.25kB/s"), Some(31_250.0));
+        assert_eq!(parse_transfer_rate("7.46M/s"), Some(7_460_000.0));
+        assert_eq!(parse_transfer_rate("1.20GB/s"), Some(1_200_000_000.0));
+        assert_eq!(parse_transfer_rate("512.00B/s"), Some(512.0));
+        assert_eq!(parse_transfer_rate("garbage"), None);
+    }
+
+    #[test]
+    fn test_eta_from_completed_file_with_remaining_count() {
+        // File of 1,000,000 bytes done; 2 more files of similar size at 100kB/s
+        let line = "  1,000,000 100%  100.00kB/s    0:00:10 (xfr#1, to-chk=2/3)";
+        let update = parse_progress_line(line, test_id()).unwrap();
+        assert_eq!(update.eta_secs, Some(20));
+    }
+
+    #[test]
+    fn test_eta_mid_file_without_totals_uses_current_file_only() {
+        // Halfway through a 1,024,000-byte file at 256kB/s
+        let line = "  512.00K  50%   256.00kB/s    0:00:02";
+        let update = parse_progress_line(line, test_id()).unwrap();
+        assert_eq!(update.eta_secs, Some(2));
+    }
+
+    #[test]
+    fn test_eta_none_when_rate_is_zero() {
+        let line = "     32,768  50%    0.00kB/s    0:00:00";
+        let update = parse_progress_line(line, test_id()).unwrap();
+        assert_eq!(update.eta_secs, None);
+    }
+
+    #[test]
+    fn test_eta_none_when_size_unknown() {
+        let line = "          0   0%    0.00kB/s    0:00:00 (xfr#0, to-chk=10/10)";
+        let update = parse_progress_line(line, test_id()).unwrap();
+        assert_eq!(update.eta_secs, None);
+    }
+
+    #[test]
+    fn test_estimate_remaining_bytes_uses_average_file_size() {
+        let line = "  1,000,000 100%  100.00kB/s    0:00:10 (xfr#1, to-chk=4/5)";
+        let update = parse_progress_line(line, test_id()).unwrap();
+        assert_eq!(
+            estimate_remaining_bytes(&update, Some(4), Some(250_000)),
+            Some(1_000_000)
+        );
+        assert_eq!(estimate_eta_secs(100_000.0, Some(1_000_000)), Some(10));
+    }
+
+    #[test]
+    fn test_estimate_eta_unknown_remaining() {
+        assert_eq!(estimate_eta_secs(100_000.0, None), None);
+        assert_eq!(estimate_eta_secs(0.0, Some(1_000)), None);
+    }
+
+    #[test]
+    fn test_parse_stats_block_modern_rsync() {
+        let output = "\
+Number of files: 1,234 (reg: 1,000, dir: 234)
+Number of created files: 10 (reg: 10)
+Number of deleted files: 0
+Number of regular files transferred: 12
+Total file size: 12,345,678 bytes
+Total transferred file size: 1,234,567 bytes
+Literal data: 0 bytes";
+        let stats = parse_stats_block(output).unwrap();
+        assert_eq!(stats.total_files, Some(1_234));
+        assert_eq!(stats.files_transferred, Some(12));
+        assert_eq!(stats.total_file_size, Some(12_345_678));
+        assert_eq!(stats.transferred_file_size, Some(1_234_567));
+        assert_eq!(stats.deleted_files, Some(0));
+    }
+
+    #[test]
+    fn test_parse_stats_block_with_deletions() {
+        let output = "\
+Number of files: 1,234 (reg: 1,000, dir: 234)
+Number of created files: 0
+Number of deleted files: 1,205 (reg: 1,200, dir: 5)
+Number of regular files transferred: 0
+Total file size: 12,345,678 bytes
+Total transferred file size: 0 bytes";
+        let stats = parse_stats_block(output).unwrap();
+        assert_eq!(stats.deleted_files, Some(1_205));
+        assert_eq!(stats.files_transferred, Some(0));
+    }
+
+    #[test]
+    fn test_feed_stats_line_ignores_other_output() {
+        let mut stats = StatsBlock::default();
+        for line in [
+            "*deleting   old/report.pdf",
+            "sent 1,024 bytes  received 35 bytes  2,118.00 bytes/sec",
+            "Number of deleted files: 3 (reg: 3)",
+        ] {
+            feed_stats_line(&mut stats, line);
+        }
+        assert_eq!(
+            stats,
+            StatsBlock {
+                deleted_files: Some(3),
+                ..StatsBlock::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_stats_block_old_labels_and_human_sizes() {
+        let output = "\
+Number of files: 50
+Number of files transferred: 3
+Total file size: 1.50M bytes
+Total transferred file size: 250.00K bytes";
+        let stats = parse_stats_block(output).unwrap();
+        assert_eq!(stats.files_transferred, Some(3));
+        // rsync before 3.1 has no deletion count
+        assert_eq!(stats.deleted_files, None);
+        assert_eq!(stats.total_file_size, Some(1_500_000));
+        assert_eq!(stats.transferred_file_size, Some(250_000));
+    }
+
+    #[test]
+    fn test_parse_stats_block_stats2_details() {
+        // --info=stats3 adds heap statistics, which are not parsed
+        let output = "\
+Number of files: 1,234 (reg: 1,000, dir: 234)
+Number of created files: 10 (reg: 10)
+Number of deleted files: 0
+Number of regular files transferred: 12
+Total file size: 12,345,678 bytes
+Total transferred file size: 1,234,567 bytes
+Literal data: 234,567 bytes
+Matched data: 1,000,000 bytes
+File list size: 45,678
+File list generation time: 1,002.125 seconds
+File list transfer time: 0.000 seconds
+Total bytes sent: 260,123
+Total bytes received: 4,567
+
+sent 260,123 bytes  received 4,567 bytes  52,938.00 bytes/sec
+total size is 12,345,678  speedup is 46.64
+rsync[4321] (sender) heap statistics:
+  arena:         540672   (bytes from sbrk)
+  ordblks:            2   (chunks not in use)";
+        let stats = parse_stats_block(output).unwrap();
+
+        assert_eq!(stats.files_transferred, Some(12));
+        let detailed = stats.detailed_stats().unwrap();
+        assert_eq!(detailed.created_files, Some(10));
+        assert_eq!(detailed.literal_data, Some(234_567));
+        assert_eq!(detailed.matched_data, Some(1_000_000));
+        assert_eq!(detailed.file_list_size, Some(45_678));
+        assert_eq!(detailed.file_list_generation_secs, Some(1_002.125));
+        assert_eq!(detailed.file_list_transfer_secs, Some(0.0));
+        assert_eq!(detailed.total_bytes_sent, Some(260_123));
+        assert_eq!(detailed.total_bytes_received, Some(4_567));
+        assert_eq!(detailed.bytes_per_sec, Some(52_938.0));
+    }
+
+    #[test]
+    fn test_parse_stats_block_without_details() {
+        let output = "\
+Number of files: 50
+Number of files transferred: 3
+Total file size: 1.50M bytes
+Total transferred file size: 250.00K bytes";
+
+        assert_eq!(parse_stats_block(output).unwrap().detailed_stats(), None);
+    }
+
+    #[test]
+    fn test_parse_stats_block_missing() {
+        assert_eq!(parse_stats_block("sent 100 bytes  received 20 bytes"), None);
+    }
+
+    fn feed_all(lines: &[&str]) -> Vec<ProgressUpdate> {
+        let mut tracker = ProgressTracker::new(test_id());
+        lines.iter().filter_map(|line| tracker.feed(line)).collect()
+    }
+
+    #[test]
+    fn test_tracker_attaches_preceding_file_name() {
+        let updates = feed_all(&[
+            "sending incremental file list",
+            "bigfile.iso",
+            "    104,857,600  10%   50.00MB/s    0:00:18",
+            "  1,048,576,000 100%   52.00MB/s    0:00:19 (xfr#1, to-chk=1/2)",
+        ]);
+        assert_eq!(updates.len(), 2);
+        assert!(updates
+            .iter()
+            .all(|u| u.current_file.as_deref() == Some("bigfile.iso")));
+    }
+
+    #[test]
+    fn test_tracker_follows_latest_file() {
+        let updates = feed_all(&[
+            "docs/",
+            "docs/a.txt",
+            "         32,768 100%   31.25kB/s    0:00:00 (xfr#1, to-chk=2/4)",
+            "docs/b with spaces.txt",
+            "         65,536 100%   62.50kB/s    0:00:00 (xfr#2, to-chk=1/4)",
+        ]);
+        let files: Vec<_> = updates.iter().map(|u| u.current_file.as_deref()).collect();
+        assert_eq!(
+            files,
+            vec![Some("docs/a.txt"), Some("docs/b with spaces.txt")]
+        );
+    }
+
+    #[test]
+    fn test_tracker_uses_itemized_path() {
+        let updates = feed_all(&[
+            ">f+++++++++ photos/img_001.jpg",
+            "      2,000,000 100%    1.90MB/s    0:00:01 (xfr#1, to-chk=0/1)",
+        ]);
+        assert_eq!(
+            updates[0].current_file.as_deref(),
+            Some("photos/img_001.jpg")
+        );
+    }
+
+    #[test]
+    fn test_tracker_without_file_name_leaves_none() {
+        let updates = feed_all(&["     32,768 100%   31.25kB/s    0:00:00 (xfr#1, to-chk=0/1)"]);
+        assert_eq!(updates[0].current_file, None);
+    }
+
+    #[test]
+    fn test_summary_lines_are_not_file_names() {
+        for line in [
+            "sending incremental file list",
+            "building file list ... done",
+            "done",
+            "created directory /backups/new",
+            "deleting old.txt",
+            "*deleting   old.txt",
+            "cd+++++++++ photos/",
+            "sent 1,234 bytes  received 56 bytes  2,580.00 bytes/sec",
+            "total size is 1,234  speedup is 1.00",
+            "Number of files: 4 (reg: 3, dir: 1)",
+            "Total transferred file size: 98,304 bytes",
+            "Literal data: 98,304 bytes",
+            "File list size: 0",
+            "rsync error: some files/attrs were not transferred (code 23)",
+            "     32,768 100%   31.25kB/s    0:00:00 (xfr#1, to-chk=0/1)",
+            "",
+        ] {
+            assert_eq!(parse_file_name_line(line), None, "{:?}", line);
+        }
+        assert_eq!(
+            parse_file_name_line("done.txt").as_deref(),
+            Some("done.txt")
+        );
+    }
 }
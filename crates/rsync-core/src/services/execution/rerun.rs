@@ -0,0 +1,76 @@
+use crate::error::AppError;
+use crate::models::job::JobDefinition;
+
+/// Makes rsync check the already-copied part of a file before appending the rest.
+const APPEND_VERIFY: &str = "--append-verify";
+
+/// Names the file rsync reads a daemon password from.
+const PASSWORD_FILE: &str = "--password-file";
+
+/// The job as it runs when retrying a failed invocation: `--partial` keeps
+/// what an interrupted transfer already wrote and `--append-verify` resumes
+/// those files from where they stopped.
+pub fn job_for_retry(job: &JobDefinition) -> JobDefinition {
+    let mut job = job.clone();
+    job.options.core_transfer.partial = true;
+    let custom_args = &mut job.options.advanced.custom_args;
+    if !custom_args.iter().any(|arg| arg == APPEND_VERIFY) {
+        custom_args.push(APPEND_VERIFY.to_string());
+    }
+    job
+}
+
+/// Split a stored `command_executed` string back into rsync's arguments,
+/// dropping the leading `rsync`.
+pub fn stored_command_args(command: &str) -> Result<Vec<String>, AppError> {
+    let mut parts = shell_words::split(command).map_err(|e| {
+        AppError::ValidationError(format!("Stored command could not be parsed: {}", e))
+    })?;
+    if parts.first().map(String::as_str) != Some("rsync") {
+        return Err(AppError::ValidationError(
+            "Stored command is not an rsync command".to_string(),
+        ));
+    }
+    parts.remove(0);
+    if parts.is_empty() {
+        return Err(AppError::ValidationError(
+            "Stored command has no arguments".to_string(),
+        ));
+    }
+    Ok(parts)
+}
+
+/// Whether stored rsync arguments read a daemon password from a file.
+pub fn uses_password_file(args: &[String]) -> bool {
+    args.iter()
+        .any(|arg| run_file_option(arg) == Some(PASSWORD_FILE))
+}
+
+/// Stored rsync arguments without the options naming files that belonged to
+/// the original run: its daemon password file, deleted when it finished, and
+/// rsync's own log next to the original run's log.
+pub fn without_run_files(args: &[String]) -> Vec<String> {
+    let mut kept = Vec::with_capacity(args.len());
+    let mut skip_value = false;
+    for arg in args {
+        if std::mem::take(&mut skip_value) {
+            continue;
+        }
+        match run_file_option(arg) {
+            // The value follows as its own argument unless given with `=`
+            Some(option) => skip_value = arg == option,
+            None => kept.push(arg.clone()),
+        }
+    }
+    kept
+}
+
+/// The run-file option `arg` sets, in either `--opt=value` or `--opt value` form.
+fn run_file_option(arg: &str) -> Option<&'static str> {
+    [PASSWORD_FILE, "--log-file", "--log-file-format"]
+        .into_iter()
+        .find(|option| {
+            arg.strip_prefix(option)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('='))
+        })
+}
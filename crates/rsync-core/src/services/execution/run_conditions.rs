@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use crate::models::backup::InvocationTrigger;
+use crate::models::job::JobDefinition;
+
+/// A guard that can hold back a scheduled run, such as an allowed time
+/// window, a metered network, or running on battery power.
+pub trait RunCondition: Send + Sync {
+    /// Returns the reason the job should be skipped, or `None` if it may run.
+    fn skip_reason(&self, job: &JobDefinition) -> Option<String>;
+
+    /// Whether a manual run should still surface this condition as a warning.
+    fn warn_on_manual(&self) -> bool {
+        false
+    }
+}
+
+/// Evaluate run conditions for a job and trigger.
+///
+/// Manual triggers always proceed — the guards only apply to scheduled runs.
+/// Returns the first skip reason reported by a condition, if any.
+pub fn check_run_conditions(
+    job: &JobDefinition,
+    trigger: &InvocationTrigger,
+    conditions: &[Arc<dyn RunCondition>],
+) -> Option<String> {
+    match trigger {
+        InvocationTrigger::Manual => None,
+        InvocationTrigger::Scheduled => conditions.iter().find_map(|c| c.skip_reason(job)),
+    }
+}
+
+/// Collect warnings for a manual run from conditions that ask to be surfaced
+/// even though they don't block it.
+pub fn manual_run_warnings(
+    job: &JobDefinition,
+    conditions: &[Arc<dyn RunCondition>],
+) -> Vec<String> {
+    conditions
+        .iter()
+        .filter(|c| c.warn_on_manual())
+        .filter_map(|c| c.skip_reason(job))
+        .collect()
+}
@@ -4,14 +4,20 @@ use std::sync::{Arc, Mutex};
 
 use uuid::Uuid;
 
+use crate::models::progress::{AggregateProgress, ProgressUpdate, RunningJobInfo};
+use crate::services::progress_parser::parse_transfer_rate;
+
 pub struct RunningJobs {
-    children: Mutex<HashMap<Uuid, Arc<Mutex<Child>>>>,
+    /// Each job's rsync processes; more than one when its source is split.
+    children: Mutex<HashMap<Uuid, Vec<Arc<Mutex<Child>>>>>,
+    progress: Mutex<HashMap<Uuid, ProgressUpdate>>,
 }
 
 impl RunningJobs {
     pub fn new() -> Self {
         Self {
             children: Mutex::new(HashMap::new()),
+            progress: Mutex::new(HashMap::new()),
         }
     }
 
@@ -20,7 +26,19 @@ impl RunningJobs {
         self.children
             .lock()
             .expect("lock poisoned")
-            .insert(job_id, arc.clone());
+            .insert(job_id, vec![arc.clone()]);
+        arc
+    }
+
+    /// Add another process to a job already started with [`insert`](Self::insert).
+    pub fn insert_part(&self, job_id: Uuid, child: Child) -> Arc<Mutex<Child>> {
+        let arc = Arc::new(Mutex::new(child));
+        self.children
+            .lock()
+            .expect("lock poisoned")
+            .entry(job_id)
+            .or_default()
+            .push(arc.clone());
         arc
     }
 
@@ -32,15 +50,17 @@ impl RunningJobs {
     }
 
     pub fn cancel(&self, job_id: &Uuid) -> bool {
-        if let Some(child_arc) = self
+        if let Some(child_arcs) = self
             .children
             .lock()
             .expect("lock poisoned")
             .get(job_id)
             .cloned()
         {
-            if let Ok(mut child) = child_arc.lock() {
-                let _ = child.kill();
+            for child_arc in child_arcs {
+                if let Ok(mut child) = child_arc.lock() {
+                    let _ = child.kill();
+                }
             }
             true
         } else {
@@ -48,11 +68,19 @@ impl RunningJobs {
         }
     }
 
+    /// Drop a job from the map, returning its first process.
     pub fn remove(&self, job_id: &Uuid) -> Option<Arc<Mutex<Child>>> {
+        self.remove_parts(job_id).into_iter().next()
+    }
+
+    /// Drop a job from the map, returning all of its processes.
+    pub fn remove_parts(&self, job_id: &Uuid) -> Vec<Arc<Mutex<Child>>> {
+        self.progress.lock().expect("lock poisoned").remove(job_id);
         self.children
             .lock()
             .expect("lock poisoned")
             .remove(job_id)
+            .unwrap_or_default()
     }
 
     pub fn running_job_ids(&self) -> Vec<Uuid> {
@@ -63,6 +91,63 @@ impl RunningJobs {
             .copied()
             .collect()
     }
+
+    /// Record the latest progress snapshot for a job, replacing the previous one.
+    pub fn update_progress(&self, job_id: Uuid, progress: ProgressUpdate) {
+        self.progress
+            .lock()
+            .expect("lock poisoned")
+            .insert(job_id, progress);
+    }
+
+    /// The latest progress snapshot for a job, if it has reported any.
+    pub fn progress(&self, job_id: &Uuid) -> Option<ProgressUpdate> {
+        self.progress
+            .lock()
+            .expect("lock poisoned")
+            .get(job_id)
+            .cloned()
+    }
+
+    /// Sum the latest progress snapshots of all jobs into one view.
+    pub fn aggregate_progress(&self) -> AggregateProgress {
+        let snapshots = self.progress.lock().expect("lock poisoned");
+        let mut agg = AggregateProgress {
+            active_jobs: snapshots.len(),
+            ..AggregateProgress::default()
+        };
+        let mut percentage_sum = 0.0;
+        for p in snapshots.values() {
+            agg.bytes_per_sec += parse_transfer_rate(&p.transfer_rate).unwrap_or(0.0);
+            agg.bytes_transferred += p.bytes_transferred;
+            agg.files_done += p.files_total.saturating_sub(p.files_remaining);
+            agg.files_total += p.files_total;
+            percentage_sum += p.percentage;
+        }
+        if agg.files_total > 0 {
+            agg.percentage = agg.files_done as f64 * 100.0 / agg.files_total as f64;
+        } else if agg.active_jobs > 0 {
+            agg.percentage = percentage_sum / agg.active_jobs as f64;
+        }
+        agg
+    }
+}
+
+/// Describe a running job from its latest progress snapshot, if any.
+pub fn running_job_info(
+    id: Uuid,
+    name: String,
+    progress: Option<&ProgressUpdate>,
+) -> RunningJobInfo {
+    RunningJobInfo {
+        id,
+        name,
+        percentage: progress.map_or(0.0, |p| p.percentage),
+        current_file: progress.and_then(|p| p.current_file.clone()),
+        bytes_per_sec: progress
+            .and_then(|p| parse_transfer_rate(&p.transfer_rate))
+            .unwrap_or(0.0),
+    }
 }
 
 impl Default for RunningJobs {
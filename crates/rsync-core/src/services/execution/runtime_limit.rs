@@ -0,0 +1,98 @@
+use std::process::Child;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// First rsync release with `--stop-after`.
+const STOP_AFTER_MIN_VERSION: (u32, u32, u32) = (3, 2, 3);
+
+/// Extra time given to rsync's own `--stop-after` before the watchdog steps in.
+pub const STOP_AFTER_GRACE: Duration = Duration::from_secs(60);
+
+/// rsync's exit code when it stops itself at the run-time limit.
+pub const RSYNC_TIMEOUT_EXIT_CODE: i32 = 30;
+
+/// Extract `(major, minor, patch)` from `rsync --version` output
+/// (e.g. "rsync  version 3.2.7  protocol version 31"). Only the number after
+/// "rsync version" counts, not the protocol version.
+pub fn parse_rsync_version(output: &str) -> Option<(u32, u32, u32)> {
+    let words: Vec<&str> = output.split_whitespace().collect();
+    let number = words
+        .windows(3)
+        .find(|w| w[0] == "rsync" && w[1] == "version")
+        .map(|w| w[2])?;
+    let mut parts = number.split('.').map(|p| {
+        p.chars()
+            .take_while(|c| c.is_ascii_digit())
+            .collect::<String>()
+            .parse::<u32>()
+            .ok()
+    });
+    let major = parts.next()??;
+    let minor = parts.next().flatten().unwrap_or(0);
+    let patch = parts.next().flatten().unwrap_or(0);
+    Some((major, minor, patch))
+}
+
+/// Whether the rsync that printed `version_output` understands `--stop-after`.
+pub fn supports_stop_after(version_output: &str) -> bool {
+    parse_rsync_version(version_output).is_some_and(|v| v >= STOP_AFTER_MIN_VERSION)
+}
+
+/// The `--stop-after=MINS` flag for `limit`, rounded up to whole minutes.
+pub fn stop_after_arg(limit: Duration) -> String {
+    let mins = limit.as_secs().div_ceil(60).max(1);
+    format!("--stop-after={}", mins)
+}
+
+/// Whether a finished run ended because of its run-time limit: either the
+/// watchdog killed it, or rsync exited with its timeout code once the limit
+/// had passed (telling it apart from an I/O `--timeout`).
+pub fn stopped_by_limit(
+    watchdog_fired: bool,
+    rsync_enforced: bool,
+    exit_code: Option<i32>,
+    elapsed: Duration,
+    limit: Duration,
+) -> bool {
+    watchdog_fired
+        || (rsync_enforced && exit_code == Some(RSYNC_TIMEOUT_EXIT_CODE) && elapsed >= limit)
+}
+
+/// Kills a child process that is still running once its deadline passes.
+///
+/// Used when rsync cannot enforce the run-time limit itself, and as a
+/// backstop when it can.
+pub struct RuntimeWatchdog {
+    done: Sender<()>,
+    fired: Arc<AtomicBool>,
+}
+
+impl RuntimeWatchdog {
+    pub fn spawn(child: Arc<Mutex<Child>>, deadline: Duration) -> Self {
+        let (done, rx) = mpsc::channel::<()>();
+        let fired = Arc::new(AtomicBool::new(false));
+        let fired_flag = Arc::clone(&fired);
+
+        std::thread::spawn(move || {
+            // Woken early (Disconnected) when the run finishes on its own
+            if let Err(RecvTimeoutError::Timeout) = rx.recv_timeout(deadline) {
+                if let Ok(mut child) = child.lock() {
+                    if matches!(child.try_wait(), Ok(None)) {
+                        fired_flag.store(true, Ordering::SeqCst);
+                        let _ = child.kill();
+                    }
+                }
+            }
+        });
+
+        Self { done, fired }
+    }
+
+    /// Stop watching. Returns true if the watchdog killed the process.
+    pub fn finish(self) -> bool {
+        drop(self.done);
+        self.fired.load(Ordering::SeqCst)
+    }
+}
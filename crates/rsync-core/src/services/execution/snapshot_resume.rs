@@ -0,0 +1,41 @@
+use std::path::Path;
+
+use crate::file_system::FileSystem;
+use crate::models::backup::{BackupInvocation, InvocationStatus, SnapshotRecord};
+use crate::models::job::StorageLocation;
+
+/// The run whose snapshot directory the next run should resume into, if the
+/// job's most recent snapshot run never completed.
+///
+/// `history` is newest first. The latest run that targeted a snapshot
+/// directory decides: a recorded snapshot for that directory, or a success
+/// without one (a dry run), means there is nothing to resume. Local and
+/// mounted destinations must still hold the directory; remote ones are
+/// trusted to.
+pub fn resumable_snapshot<'a>(
+    destination: &StorageLocation,
+    history: &'a [BackupInvocation],
+    snapshots: &[SnapshotRecord],
+    fs: &dyn FileSystem,
+) -> Option<&'a BackupInvocation> {
+    let latest = history
+        .iter()
+        .find(|inv| inv.execution_output.snapshot_path.is_some())?;
+    let path = latest.execution_output.snapshot_path.as_deref()?;
+
+    if latest.status == InvocationStatus::Succeeded
+        || snapshots.iter().any(|snap| snap.snapshot_path == path)
+    {
+        return None;
+    }
+
+    let checks_disk = matches!(
+        destination,
+        StorageLocation::Local { .. } | StorageLocation::Mount { .. }
+    );
+    if checks_disk && !fs.is_dir(Path::new(path)) {
+        return None;
+    }
+
+    Some(latest)
+}
@@ -0,0 +1,109 @@
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::models::backup::SnapshotVerification;
+use crate::models::job::{JobDefinition, StorageLocation};
+use crate::rsync_client::RsyncClient;
+use crate::services::command_builder::build_rsync_args;
+use crate::services::itemize_parser::parse_itemize_line;
+use crate::services::job_service::JobService;
+
+/// The rsync arguments that compare `job`'s source with the snapshot written
+/// to `snapshot`: a checksummed `--delete` dry run that itemizes every
+/// difference, so an identical snapshot itemizes nothing.
+///
+/// Link-dest targets, `--max-delete` and progress output are left out, as
+/// they would change or hide what the comparison reports.
+pub fn verification_args(
+    job: &JobDefinition,
+    snapshot: &StorageLocation,
+    auto_trailing_slash: bool,
+    relative_paths: bool,
+) -> Vec<String> {
+    let mut options = job.options.clone();
+    options.core_transfer.dry_run = true;
+    options.file_handling.checksum = true;
+    options.file_handling.delete = true;
+    options.file_handling.max_delete = None;
+    options.output.verbose = false;
+    options.output.progress = false;
+    options.output.stats = false;
+    options.output.itemize_changes = true;
+    options.advanced.bandwidth_schedule.clear();
+    build_rsync_args(
+        &job.transfer.source,
+        snapshot,
+        &options,
+        job.ssh_config.as_ref(),
+        &[],
+        auto_trailing_slash,
+        relative_paths,
+    )
+}
+
+/// How many entries a verification dry run itemized, not counting the
+/// transfer root itself.
+pub fn count_discrepancies(output: &str) -> u64 {
+    output
+        .lines()
+        .filter_map(parse_itemize_line)
+        .filter(|change| change.path != "./")
+        .count() as u64
+}
+
+/// Run the verification dry run `args` for the snapshot `invocation_id`
+/// wrote. The snapshot passes when rsync succeeds and itemizes nothing.
+pub fn verify_snapshot(
+    rsync: &dyn RsyncClient,
+    job_id: Uuid,
+    invocation_id: Uuid,
+    args: &[String],
+) -> SnapshotVerification {
+    let (discrepancies, error) = match rsync.dry_run(args) {
+        Ok(result) if result.exit_code == 0 => (count_discrepancies(&result.stdout), None),
+        Ok(result) => (
+            0,
+            Some(format!(
+                "Verification exited with code {}",
+                result.exit_code
+            )),
+        ),
+        Err(e) => (0, Some(format!("Verification could not run: {}", e))),
+    };
+    SnapshotVerification {
+        invocation_id,
+        job_id,
+        verified_at: Utc::now(),
+        passed: error.is_none() && discrepancies == 0,
+        discrepancies,
+        error,
+    }
+}
+
+/// [`verify_snapshot`], storing the result against the run's invocation.
+pub fn verify_and_record(
+    rsync: &dyn RsyncClient,
+    job_service: &JobService,
+    job_id: Uuid,
+    invocation_id: Uuid,
+    args: &[String],
+) -> SnapshotVerification {
+    let verification = verify_snapshot(rsync, job_id, invocation_id, args);
+    if let Err(e) = job_service.record_snapshot_verification(&verification) {
+        log::error!("Failed to record snapshot verification: {}", e);
+    }
+    verification
+}
+
+/// A one-line account of `verification`, for logs and notifications.
+pub fn verification_summary(verification: &SnapshotVerification) -> String {
+    match (&verification.error, verification.discrepancies) {
+        (Some(error), _) => format!("Snapshot verification failed: {}", error),
+        (None, 0) => "Snapshot verified: it matches the source".to_string(),
+        (None, 1) => "Snapshot verification found 1 difference from the source".to_string(),
+        (None, n) => format!(
+            "Snapshot verification found {} differences from the source",
+            n
+        ),
+    }
+}
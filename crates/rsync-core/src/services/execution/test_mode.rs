@@ -0,0 +1,39 @@
+use std::borrow::Cow;
+
+use crate::models::backup::InvocationStatus;
+use crate::models::job::JobDefinition;
+
+/// Written at the top of every run's log while test mode is on.
+pub const TEST_MODE_BANNER: &str =
+    "TEST MODE: this run is forced to --dry-run and nothing will be written";
+
+/// The job as it will actually run. With test mode on, the job is forced to
+/// a dry run whatever its own options say.
+pub fn job_for_run(job: &JobDefinition, test_mode: bool) -> Cow<'_, JobDefinition> {
+    if !test_mode || job.options.core_transfer.dry_run {
+        return Cow::Borrowed(job);
+    }
+    let mut job = job.clone();
+    job.options.core_transfer.dry_run = true;
+    Cow::Owned(job)
+}
+
+/// Whether a finished run's statistics and snapshot should be kept.
+/// Dry runs, including every run in test mode, record neither.
+pub fn records_run_results(status: &InvocationStatus, is_dry_run: bool) -> bool {
+    *status == InvocationStatus::Succeeded && !is_dry_run
+}
+
+/// Whether a recorded rsync command line ran as a dry run: `--dry-run`, or
+/// `-n` alone or bundled with other short flags (e.g. `-avn`).
+pub fn command_was_dry_run(command: &str) -> bool {
+    let args = shell_words::split(command)
+        .unwrap_or_else(|_| command.split_whitespace().map(str::to_string).collect());
+    args.iter().skip(1).any(|arg| {
+        arg == "--dry-run"
+            || (arg.starts_with('-')
+                && !arg.starts_with("--")
+                && arg[1..].chars().all(|c| c.is_ascii_alphabetic())
+                && arg.contains('n'))
+    })
+}
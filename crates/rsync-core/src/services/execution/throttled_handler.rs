@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use uuid::Uuid;
+
+use crate::models::itemize::ItemizedChange;
+use crate::models::job::JobStatus;
+use crate::models::progress::{JobStatusEvent, LogLine, ProgressUpdate};
+use crate::services::execution_handler::ExecutionEventHandler;
+
+/// Spacing between forwarded progress updates and log batches: about ten a
+/// second, plenty for a progress bar and a scrolling log.
+pub const DEFAULT_EMIT_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Decorates a handler so a run of many small files can't flood it.
+///
+/// Per invocation, at most one progress update per interval is forwarded;
+/// updates in between are coalesced into the latest one. Log lines are
+/// buffered and forwarded together through `on_log_lines` once per interval.
+/// Whatever is still held when no further event arrives goes out on a timer
+/// one interval later, and a status change first flushes whatever is
+/// pending, so nothing is lost at the end of a run. Itemized changes pass
+/// straight through.
+///
+/// Only what reaches the UI is throttled: the executor writes every line to
+/// the log file before calling its handler.
+pub struct ThrottledHandler {
+    shared: Arc<Shared>,
+}
+
+/// State shared with the timers that flush held events.
+struct Shared {
+    inner: Arc<dyn ExecutionEventHandler>,
+    interval: Duration,
+    streams: Mutex<HashMap<Uuid, Stream>>,
+}
+
+/// Throttling state of one invocation.
+#[derive(Default)]
+struct Stream {
+    last_progress: Option<Instant>,
+    pending_progress: Option<ProgressUpdate>,
+    last_lines: Option<Instant>,
+    pending_lines: Vec<LogLine>,
+    /// Whether a timer will flush what is pending.
+    flush_scheduled: bool,
+}
+
+/// Events released by a throttling decision, sent once the lock is dropped.
+#[derive(Default)]
+struct Release {
+    progress: Option<ProgressUpdate>,
+    lines: Vec<LogLine>,
+}
+
+fn due(last: Option<Instant>, now: Instant, interval: Duration) -> bool {
+    last.is_none_or(|last| now.duration_since(last) >= interval)
+}
+
+impl Stream {
+    /// Take pending events whose interval has passed (all of them if `force`).
+    fn release(&mut self, now: Instant, interval: Duration, force: bool) -> Release {
+        let mut release = Release::default();
+        if self.pending_progress.is_some() && (force || due(self.last_progress, now, interval)) {
+            release.progress = self.pending_progress.take();
+            self.last_progress = Some(now);
+        }
+        if !self.pending_lines.is_empty() && (force || due(self.last_lines, now, interval)) {
+            release.lines = std::mem::take(&mut self.pending_lines);
+            self.last_lines = Some(now);
+        }
+        release
+    }
+
+    fn has_pending(&self) -> bool {
+        self.pending_progress.is_some() || !self.pending_lines.is_empty()
+    }
+}
+
+impl Shared {
+    /// Apply `change` to the stream of `invocation_id` and release what is
+    /// due, starting a flush timer when something is left waiting.
+    fn update(
+        self: &Arc<Self>,
+        invocation_id: Uuid,
+        force: bool,
+        change: impl FnOnce(&mut Stream),
+    ) -> Release {
+        let mut streams = self.streams.lock().unwrap_or_else(|e| e.into_inner());
+        let stream = streams.entry(invocation_id).or_default();
+        change(stream);
+        let release = stream.release(Instant::now(), self.interval, force);
+        let schedule = stream.has_pending() && !stream.flush_scheduled;
+        if schedule {
+            stream.flush_scheduled = true;
+        }
+        drop(streams);
+        if schedule {
+            self.schedule_flush(invocation_id);
+        }
+        release
+    }
+
+    /// Send what `invocation_id` holds once the interval has passed.
+    fn schedule_flush(self: &Arc<Self>, invocation_id: Uuid) {
+        let shared = Arc::clone(self);
+        let spawned = thread::Builder::new()
+            .name("throttle-flush".to_string())
+            .spawn(move || {
+                thread::sleep(shared.interval);
+                shared.flush(invocation_id);
+            });
+        if let Err(e) = spawned {
+            log::warn!("Could not start throttle flush timer: {}", e);
+            let mut streams = self.streams.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(stream) = streams.get_mut(&invocation_id) {
+                stream.flush_scheduled = false;
+            }
+        }
+    }
+
+    /// Timer side of [`Shared::schedule_flush`]. A finished run's stream is
+    /// gone by now, its events already sent with the status change.
+    fn flush(self: &Arc<Self>, invocation_id: Uuid) {
+        let mut streams = self.streams.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(stream) = streams.get_mut(&invocation_id) else {
+            return;
+        };
+        stream.flush_scheduled = false;
+        let release = stream.release(Instant::now(), self.interval, true);
+        drop(streams);
+        self.send(release);
+    }
+
+    fn send(&self, release: Release) {
+        if !release.lines.is_empty() {
+            self.inner.on_log_lines(release.lines);
+        }
+        if let Some(progress) = release.progress {
+            self.inner.on_progress(&progress);
+        }
+    }
+}
+
+impl ThrottledHandler {
+    pub fn new(inner: Arc<dyn ExecutionEventHandler>) -> Self {
+        Self::with_interval(inner, DEFAULT_EMIT_INTERVAL)
+    }
+
+    pub fn with_interval(inner: Arc<dyn ExecutionEventHandler>, interval: Duration) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                inner,
+                interval,
+                streams: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+}
+
+impl ExecutionEventHandler for ThrottledHandler {
+    fn on_log_line(&self, log_line: LogLine) {
+        let release = self.shared.update(log_line.invocation_id, false, |stream| {
+            stream.pending_lines.push(log_line);
+        });
+        self.shared.send(release);
+    }
+
+    fn on_log_lines(&self, log_lines: Vec<LogLine>) {
+        for log_line in log_lines {
+            self.on_log_line(log_line);
+        }
+    }
+
+    fn on_progress(&self, progress: &ProgressUpdate) {
+        let release = self.shared.update(progress.invocation_id, false, |stream| {
+            stream.pending_progress = Some(progress.clone());
+        });
+        self.shared.send(release);
+    }
+
+    fn on_status_change(&self, status: JobStatusEvent) {
+        let release = self.shared.update(status.invocation_id, true, |_| {});
+        self.shared.send(release);
+        if status.status != JobStatus::Running {
+            let mut streams = self.shared.streams.lock().unwrap_or_else(|e| e.into_inner());
+            streams.remove(&status.invocation_id);
+        }
+        self.shared.inner.on_status_change(status);
+    }
+
+    fn on_itemized_change(&self, invocation_id: Uuid, change: &ItemizedChange) {
+        self.shared.inner.on_itemized_change(invocation_id, change);
+    }
+}
@@ -1,9 +1,17 @@
+use std::collections::HashSet;
+
 use chrono::Utc;
 use uuid::Uuid;
 
-use crate::models::job::{ExportData, JobDefinition};
+use crate::models::job::{
+    ExportData, FullExportData, ImportAction, ImportPreview, ImportPreviewJob, JobDefinition,
+};
+use crate::models::validation::validate_job;
+use crate::services::job_service::JobService;
+use crate::services::statistics_service::StatisticsService;
 
 const EXPORT_VERSION: u32 = 1;
+const FULL_EXPORT_VERSION: u32 = 1;
 
 /// Export a list of jobs to a JSON string.
 pub fn export_jobs(jobs: Vec<JobDefinition>) -> Result<String, String> {
@@ -15,9 +23,8 @@ pub fn export_jobs(jobs: Vec<JobDefinition>) -> Result<String, String> {
     serde_json::to_string_pretty(&data).map_err(|e| format!("Serialization error: {}", e))
 }
 
-/// Import jobs from a JSON string. Regenerates UUIDs and timestamps so imports
-/// never collide with existing jobs.
-pub fn import_jobs(json: &str) -> Result<Vec<JobDefinition>, String> {
+/// Parse and check a job export without changing anything.
+fn parse_export(json: &str) -> Result<ExportData, String> {
     let data: ExportData =
         serde_json::from_str(json).map_err(|e| format!("Invalid export file: {}", e))?;
 
@@ -31,6 +38,27 @@ pub fn import_jobs(json: &str) -> Result<Vec<JobDefinition>, String> {
     if data.jobs.is_empty() {
         return Err("Export file contains no jobs".to_string());
     }
+    Ok(data)
+}
+
+/// Parse and check a full backup bundle without changing anything.
+fn parse_full_export(json: &str) -> Result<FullExportData, String> {
+    let data: FullExportData =
+        serde_json::from_str(json).map_err(|e| format!("Invalid export file: {}", e))?;
+
+    if data.version > FULL_EXPORT_VERSION {
+        return Err(format!(
+            "Unsupported export version {} (max supported: {})",
+            data.version, FULL_EXPORT_VERSION
+        ));
+    }
+    Ok(data)
+}
+
+/// Import jobs from a JSON string. Regenerates UUIDs and timestamps so imports
+/// never collide with existing jobs.
+pub fn import_jobs(json: &str) -> Result<Vec<JobDefinition>, String> {
+    let data = parse_export(json)?;
 
     let now = Utc::now();
     let jobs = data
@@ -47,6 +75,96 @@ pub fn import_jobs(json: &str) -> Result<Vec<JobDefinition>, String> {
     Ok(jobs)
 }
 
+/// Export jobs together with their invocation history, snapshots and
+/// statistics as one versioned JSON bundle.
+pub fn export_full(
+    job_service: &JobService,
+    statistics_service: &StatisticsService,
+) -> Result<String, String> {
+    let data = FullExportData {
+        version: FULL_EXPORT_VERSION,
+        exported_at: Utc::now(),
+        jobs: job_service.list_jobs().map_err(|e| e.to_string())?,
+        invocations: job_service
+            .list_all_invocations()
+            .map_err(|e| e.to_string())?,
+        snapshots: job_service
+            .list_all_snapshots()
+            .map_err(|e| e.to_string())?,
+        statistics: statistics_service.list_all().map_err(|e| e.to_string())?,
+    };
+    serde_json::to_string_pretty(&data).map_err(|e| format!("Serialization error: {}", e))
+}
+
+/// Restore a bundle produced by [`export_full`], preserving all ids.
+///
+/// Every record is upserted, so importing the same bundle twice (or resuming
+/// after a failed import) never duplicates history. Returns the parsed bundle
+/// so callers can report what was restored.
+pub fn import_full(
+    json: &str,
+    job_service: &JobService,
+    statistics_service: &StatisticsService,
+) -> Result<FullExportData, String> {
+    let data = parse_full_export(json)?;
+
+    // Parents first so foreign keys resolve.
+    for job in &data.jobs {
+        job_service.upsert_job(job).map_err(|e| e.to_string())?;
+    }
+    for inv in &data.invocations {
+        job_service
+            .upsert_invocation(inv)
+            .map_err(|e| e.to_string())?;
+    }
+    for snapshot in &data.snapshots {
+        job_service
+            .upsert_snapshot(snapshot)
+            .map_err(|e| e.to_string())?;
+    }
+    for stat in &data.statistics {
+        statistics_service.upsert(stat).map_err(|e| e.to_string())?;
+    }
+
+    Ok(data)
+}
+
+/// Work out what importing `json` would do, given the jobs that already
+/// exist, without writing anything.
+///
+/// With `full_backup` unset this previews [`import_jobs`], which always
+/// creates new jobs; set, it previews [`import_full`], which updates jobs
+/// whose id already exists. Each job lists the validation checks it fails.
+pub fn validate_import(
+    json: &str,
+    existing: &[JobDefinition],
+    full_backup: bool,
+) -> Result<ImportPreview, String> {
+    let jobs = if full_backup {
+        parse_full_export(json)?.jobs
+    } else {
+        parse_export(json)?.jobs
+    };
+
+    let existing_ids: HashSet<Uuid> = existing.iter().map(|job| job.id).collect();
+    let jobs = jobs
+        .iter()
+        .map(|job| ImportPreviewJob {
+            name: job.name.clone(),
+            action: if full_backup && existing_ids.contains(&job.id) {
+                ImportAction::Update
+            } else {
+                ImportAction::Create
+            },
+            failed_checks: validate_job(job)
+                .into_iter()
+                .filter(|c| !c.passed)
+                .collect(),
+        })
+        .collect();
+    Ok(ImportPreview { full_backup, jobs })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -69,6 +187,9 @@ mod tests {
             },
             options: RsyncOptions::default(),
             ssh_config: None,
+            appearance: JobAppearance::default(),
+            run_policy: JobRunPolicy::default(),
+            safety: JobSafety::default(),
             schedule: None,
             enabled: true,
             created_at: Utc::now(),
@@ -176,6 +297,9 @@ mod tests {
             port: 2222,
             identity_file: Some("/home/user/.ssh/id_rsa".to_string()),
             strict_host_key_checking: false,
+            connect_timeout: None,
+            server_alive_interval: None,
+            jump_host: None,
             custom_ssh_command: None,
         });
         let json = export_jobs(vec![job.clone()]).unwrap();
@@ -218,4 +342,60 @@ mod tests {
         assert_ne!(ids[1], ids[2]);
         assert_ne!(ids[0], ids[2]);
     }
+
+    #[test]
+    fn validate_import_previews_clean_file_as_creates() {
+        let existing = sample_job("Already here");
+        let mut same_id = sample_job("Same id");
+        same_id.id = existing.id;
+        let json = export_jobs(vec![sample_job("New"), same_id]).unwrap();
+
+        let preview = validate_import(&json, &[existing], false).unwrap();
+
+        assert!(!preview.full_backup);
+        let names: Vec<&str> = preview.jobs.iter().map(|j| j.name.as_str()).collect();
+        assert_eq!(names, vec!["New", "Same id"]);
+        // Job imports regenerate ids, so nothing is ever overwritten
+        assert!(preview
+            .jobs
+            .iter()
+            .all(|j| j.action == ImportAction::Create && j.failed_checks.is_empty()));
+    }
+
+    #[test]
+    fn validate_import_flags_invalid_job() {
+        let mut bad = sample_job("Bad color");
+        bad.appearance.color = Some("blue".to_string());
+        let json = export_jobs(vec![sample_job("Good"), bad]).unwrap();
+
+        let preview = validate_import(&json, &[], false).unwrap();
+
+        assert!(preview.jobs[0].failed_checks.is_empty());
+        let failed = &preview.jobs[1].failed_checks;
+        assert_eq!(failed.len(), 1);
+        assert_eq!(
+            failed[0].check_type,
+            crate::models::validation::CheckType::JobColor
+        );
+    }
+
+    #[test]
+    fn validate_import_marks_existing_ids_as_updates_for_full_backups() {
+        let existing = sample_job("Already here");
+        let json = export_jobs(vec![existing.clone(), sample_job("New")]).unwrap();
+
+        let preview = validate_import(&json, &[existing], true).unwrap();
+
+        assert!(preview.full_backup);
+        let actions: Vec<ImportAction> = preview.jobs.iter().map(|j| j.action).collect();
+        assert_eq!(actions, vec![ImportAction::Update, ImportAction::Create]);
+    }
+
+    #[test]
+    fn validate_import_rejects_malformed_json() {
+        let err = validate_import("{\"version\": 1, \"jobs\": [", &[], false).unwrap_err();
+        assert!(err.contains("Invalid export file"));
+        let err = validate_import("not json", &[], true).unwrap_err();
+        assert!(err.contains("Invalid export file"));
+    }
 }
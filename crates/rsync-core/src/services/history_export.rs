@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::models::backup::BackupInvocation;
+use crate::models::execution::history_event::HistoryEvent;
+use crate::services::job_service::JobService;
+
+/// How many invocations are loaded from the database per query.
+pub const EXPORT_PAGE_SIZE: usize = 500;
+
+/// Write every invocation started at or after `since` (all of them when
+/// `None`) to `out` as newline-delimited JSON, oldest first, one
+/// [`HistoryEvent`] per line. Returns how many lines were written.
+///
+/// Invocations are read [`EXPORT_PAGE_SIZE`] at a time and each line is
+/// written as soon as it is serialized, so a large history is never held in
+/// memory at once; wrap `out` in a `BufWriter` for files.
+pub fn export_history_ndjson(
+    job_service: &JobService,
+    since: Option<DateTime<Utc>>,
+    out: &mut dyn Write,
+) -> io::Result<usize> {
+    let job_names: HashMap<Uuid, String> = job_service
+        .list_jobs()
+        .map_err(|e| io::Error::other(e.to_string()))?
+        .into_iter()
+        .map(|job| (job.id, job.name))
+        .collect();
+    let mut written = 0;
+    let mut page: Vec<BackupInvocation> = Vec::new();
+    loop {
+        page = job_service
+            .list_invocations_page(since.as_ref(), page.last(), EXPORT_PAGE_SIZE)
+            .map_err(|e| io::Error::other(e.to_string()))?;
+        for inv in &page {
+            let job_name = job_names.get(&inv.job_id).cloned().unwrap_or_default();
+            let event = HistoryEvent::new(inv, job_name);
+            serde_json::to_writer(&mut *out, &event)?;
+            out.write_all(b"\n")?;
+        }
+        written += page.len();
+        if page.len() < EXPORT_PAGE_SIZE {
+            break;
+        }
+    }
+    out.flush()?;
+    Ok(written)
+}
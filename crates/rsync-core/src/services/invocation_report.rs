@@ -0,0 +1,106 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use uuid::Uuid;
+
+use crate::error::JobServiceError;
+use crate::file_system::FileSystem;
+use crate::format::{format_bytes, format_duration};
+use crate::models::backup::{BackupInvocation, InvocationStatus, InvocationTrigger};
+use crate::services::job_service::JobService;
+use crate::services::log_scrubber::scrub_secrets;
+
+/// A Markdown report of one run for attaching to a bug report: when and how
+/// it ran, what it transferred, the command and the full log.
+///
+/// The command and the log go through [`scrub_secrets`]. A deleted job or a
+/// missing log file is noted in the report rather than failing it.
+pub fn invocation_report(
+    job_service: &JobService,
+    invocation_id: &Uuid,
+    fs: &dyn FileSystem,
+) -> Result<String, JobServiceError> {
+    let inv = job_service.get_invocation(invocation_id)?;
+    let job_name = job_service
+        .get_job(&inv.job_id)
+        .map(|job| job.name)
+        .unwrap_or_else(|_| "(deleted job)".to_string());
+
+    let mut report = format!("# Run report: {}\n\n", job_name);
+    write_header(&mut report, &inv);
+
+    let _ = write!(
+        report,
+        "\n## Command\n\n```\n{}\n```\n\n## Log\n\n",
+        scrub_secrets(&inv.execution_output.command_executed)
+    );
+    match read_log(&inv, fs) {
+        Some(log) => {
+            let _ = write!(report, "```\n{}\n```\n", scrub_secrets(log.trim_end()));
+        }
+        None => report.push_str("_Log file not found._\n"),
+    }
+    Ok(report)
+}
+
+fn write_header(report: &mut String, inv: &BackupInvocation) {
+    let stats = &inv.transfer_stats;
+    let mut rows = vec![
+        ("Job", inv.job_id.to_string()),
+        ("Invocation", inv.id.to_string()),
+        ("Trigger", trigger_label(&inv.trigger).to_string()),
+        ("Status", status_label(&inv.status).to_string()),
+        ("Started", inv.started_at.to_rfc3339()),
+    ];
+    if let Some(finished_at) = inv.finished_at {
+        rows.push(("Finished", finished_at.to_rfc3339()));
+        let secs = (finished_at - inv.started_at).num_milliseconds() as f64 / 1000.0;
+        rows.push(("Duration", format_duration(secs)));
+    }
+    if let Some(code) = inv.execution_output.exit_code {
+        rows.push(("Exit code", code.to_string()));
+    }
+    rows.push(("Transferred", format_bytes(stats.bytes_transferred)));
+    rows.push((
+        "Files",
+        format!(
+            "{} transferred, {} deleted, {} total",
+            stats.files_transferred, stats.files_deleted, stats.total_files
+        ),
+    ));
+    if let Some(id) = inv.rerun_of {
+        rows.push(("Rerun of", id.to_string()));
+    }
+    if let Some(id) = inv.resumed_from {
+        rows.push(("Resumed from", id.to_string()));
+    }
+    if let Some(warning) = &inv.warning {
+        rows.push(("Warning", warning.clone()));
+    }
+
+    for (label, value) in rows {
+        let _ = writeln!(report, "- **{}:** {}", label, value);
+    }
+}
+
+fn read_log(inv: &BackupInvocation, fs: &dyn FileSystem) -> Option<String> {
+    let path = inv.execution_output.log_file_path.as_deref()?;
+    fs.read_to_string(Path::new(path)).ok()
+}
+
+fn trigger_label(trigger: &InvocationTrigger) -> &'static str {
+    match trigger {
+        InvocationTrigger::Manual => "Manual",
+        InvocationTrigger::Scheduled => "Scheduled",
+    }
+}
+
+fn status_label(status: &InvocationStatus) -> &'static str {
+    match status {
+        InvocationStatus::Running => "Running",
+        InvocationStatus::Succeeded => "Succeeded",
+        InvocationStatus::Failed => "Failed",
+        InvocationStatus::Cancelled => "Cancelled",
+        InvocationStatus::StoppedByLimit => "Stopped by limit",
+    }
+}
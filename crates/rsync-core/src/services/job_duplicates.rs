@@ -0,0 +1,33 @@
+use uuid::Uuid;
+
+use crate::models::job::JobDefinition;
+
+/// Whether two jobs copy the same thing the same way: same source,
+/// destination and backup mode, rsync options and SSH settings. Name, id,
+/// timestamps, schedule and everything else are ignored.
+pub fn is_duplicate(a: &JobDefinition, b: &JobDefinition) -> bool {
+    a.transfer == b.transfer && a.options == b.options && a.ssh_config == b.ssh_config
+}
+
+/// Groups of two or more duplicate jobs (see [`is_duplicate`]), as job ids.
+///
+/// Each group lists its jobs oldest first, so the first one is the natural
+/// job to keep; groups are ordered by their oldest job.
+pub fn find_duplicate_jobs(jobs: &[JobDefinition]) -> Vec<Vec<Uuid>> {
+    let mut sorted: Vec<&JobDefinition> = jobs.iter().collect();
+    sorted.sort_by_key(|job| job.created_at);
+
+    let mut groups: Vec<Vec<&JobDefinition>> = Vec::new();
+    for job in sorted {
+        match groups.iter_mut().find(|group| is_duplicate(group[0], job)) {
+            Some(group) => group.push(job),
+            None => groups.push(vec![job]),
+        }
+    }
+
+    groups
+        .into_iter()
+        .filter(|group| group.len() > 1)
+        .map(|group| group.iter().map(|job| job.id).collect())
+        .collect()
+}
@@ -1,11 +1,21 @@
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::path::{Component, Path, PathBuf};
 use std::sync::Arc;
 
-use chrono::Utc;
+use chrono::{DateTime, Duration, Utc};
 use uuid::Uuid;
 
-use crate::error::AppError;
-use crate::models::backup::{BackupInvocation, SnapshotRecord};
-use crate::models::job::{BackupMode, JobDefinition};
+use crate::error::JobServiceError;
+use crate::models::audit::AuditAction;
+use crate::models::backup::{BackupInvocation, SnapshotRecord, SnapshotVerification};
+use crate::models::itemize::StoredItemizedChanges;
+use crate::models::job::{BackupMode, JobDefinition, RetargetResult, StorageLocation};
+use crate::models::settings::PruneRecord;
+use crate::models::validation::{validate_destination, validate_job, CheckSeverity};
+use crate::services::audit::AuditLog;
+use crate::services::preflight::destination_change_warnings;
+use crate::services::scheduler::next_run_time;
 use crate::services::snapshot_retention;
 use crate::repository::invocation::InvocationRepository;
 use crate::repository::job::JobRepository;
@@ -15,6 +25,7 @@ pub struct JobService {
     jobs: Arc<dyn JobRepository>,
     invocations: Arc<dyn InvocationRepository>,
     snapshots: Arc<dyn SnapshotRepository>,
+    audit: Option<Arc<AuditLog>>,
 }
 
 impl JobService {
@@ -27,15 +38,25 @@ impl JobService {
             jobs,
             invocations,
             snapshots,
+            audit: None,
         }
     }
 
-    pub fn create_job(&self, mut job: JobDefinition) -> Result<JobDefinition, AppError> {
-        if job.name.trim().is_empty() {
-            return Err(AppError::ValidationError(
-                "Job name must not be empty".to_string(),
-            ));
+    /// Record job deletions and merges, history purges and snapshot deletions
+    /// (including retention prunes) in `audit`.
+    pub fn with_audit_log(mut self, audit: Arc<AuditLog>) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    fn audit(&self, action: AuditAction, target_id: Uuid, detail: String) {
+        if let Some(audit) = &self.audit {
+            audit.record(action, Some(target_id), detail);
         }
+    }
+
+    pub fn create_job(&self, mut job: JobDefinition) -> Result<JobDefinition, JobServiceError> {
+        ensure_valid(&job)?;
         job.id = Uuid::new_v4();
         let now = Utc::now();
         job.created_at = now;
@@ -44,98 +65,415 @@ impl JobService {
         Ok(job)
     }
 
-    pub fn update_job(&self, mut job: JobDefinition) -> Result<JobDefinition, AppError> {
-        if job.name.trim().is_empty() {
-            return Err(AppError::ValidationError(
-                "Job name must not be empty".to_string(),
-            ));
-        }
-        // Verify job exists
-        self.jobs.get_job(&job.id)?;
+    pub fn update_job(&self, mut job: JobDefinition) -> Result<JobDefinition, JobServiceError> {
+        ensure_valid(&job)?;
+        // The incoming updated_at is the version the caller edited; reject the
+        // write if someone else saved the job since
+        let seen_updated_at = job.updated_at;
         job.updated_at = Utc::now();
-        self.jobs.update_job(&job)?;
+        self.jobs.update_job_if_unchanged(&job, &seen_updated_at)?;
         Ok(job)
     }
 
-    pub fn delete_job(&self, id: &Uuid) -> Result<(), AppError> {
+    /// Point a job at a new destination, e.g. a prototype local job at a
+    /// remote server.
+    ///
+    /// Fails if the destination is incomplete. The result carries warnings
+    /// for missing SSH settings and for changes in which preflight checks
+    /// apply, and lists the snapshots left behind at the old destination.
+    pub fn retarget(
+        &self,
+        job_id: &Uuid,
+        new_destination: StorageLocation,
+    ) -> Result<RetargetResult, JobServiceError> {
+        let mut job = self.jobs.get_job(job_id)?;
+
+        let mut checks = validate_destination(&new_destination, job.ssh_config.as_ref());
+        if checks
+            .iter()
+            .any(|c| !c.passed && c.severity == CheckSeverity::Error)
+        {
+            return Err(JobServiceError::Validation(checks));
+        }
+        checks.extend(destination_change_warnings(
+            &job.transfer.destination,
+            &new_destination,
+        ));
+
+        job.transfer.destination = new_destination;
+        let job = self.update_job(job)?;
+
+        // Old snapshots live under the previous destination, so the next run
+        // must not use them for --link-dest
+        let mut forgotten_snapshots = Vec::new();
+        for snapshot in self.snapshots.list_snapshots_for_job(job_id)? {
+            self.snapshots.delete_snapshot(&snapshot.id)?;
+            self.audit(
+                AuditAction::SnapshotDeleted,
+                snapshot.id,
+                format!(
+                    "Forgot snapshot {} when retargeting job '{}'",
+                    snapshot.snapshot_path, job.name
+                ),
+            );
+            forgotten_snapshots.push(snapshot.snapshot_path);
+        }
+
+        Ok(RetargetResult {
+            job,
+            checks,
+            forgotten_snapshots,
+        })
+    }
+
+    pub fn delete_job(&self, id: &Uuid) -> Result<(), JobServiceError> {
         // Verify job exists
-        self.jobs.get_job(id)?;
-        self.jobs.delete_job(id)
+        let job = self.jobs.get_job(id)?;
+        self.jobs.delete_job(id)?;
+        self.audit(
+            AuditAction::JobDeleted,
+            *id,
+            format!("Deleted job '{}'", job.name),
+        );
+        Ok(())
     }
 
-    pub fn get_job(&self, id: &Uuid) -> Result<JobDefinition, AppError> {
-        self.jobs.get_job(id)
+    /// Fold duplicate jobs into `keep_id`: their invocations and snapshot
+    /// records move to the kept job, then the jobs are deleted.
+    ///
+    /// The merge is a single transaction, so it either happens completely or
+    /// not at all. Run statistics belong to the removed jobs and are deleted
+    /// with them.
+    pub fn merge_jobs(&self, keep_id: &Uuid, remove_ids: &[Uuid]) -> Result<(), JobServiceError> {
+        if remove_ids.contains(keep_id) {
+            return Err(JobServiceError::Conflict(format!(
+                "Cannot merge job {} into itself",
+                keep_id
+            )));
+        }
+        let kept = self.jobs.get_job(keep_id)?;
+        let removed = remove_ids
+            .iter()
+            .map(|id| self.jobs.get_job(id))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.jobs.merge_jobs(keep_id, remove_ids)?;
+        for job in removed {
+            self.audit(
+                AuditAction::JobsMerged,
+                job.id,
+                format!("Merged job '{}' into '{}'", job.name, kept.name),
+            );
+        }
+        Ok(())
     }
 
-    pub fn list_jobs(&self) -> Result<Vec<JobDefinition>, AppError> {
-        self.jobs.list_jobs()
+    pub fn get_job(&self, id: &Uuid) -> Result<JobDefinition, JobServiceError> {
+        Ok(self.jobs.get_job(id)?)
     }
 
-    pub fn record_invocation(&self, inv: &BackupInvocation) -> Result<(), AppError> {
-        self.invocations.create_invocation(inv)
+    pub fn list_jobs(&self) -> Result<Vec<JobDefinition>, JobServiceError> {
+        Ok(self.jobs.list_jobs()?)
     }
 
-    pub fn complete_invocation(&self, inv: &BackupInvocation) -> Result<(), AppError> {
-        self.invocations.update_invocation(inv)
+    /// Jobs whose source or destination is `path` or lies inside it, i.e. the
+    /// jobs moving or deleting `path` would affect.
+    ///
+    /// Only local and mounted locations are compared. Paths that exist are
+    /// canonicalized, so symlinks and `..` resolve; others are normalized
+    /// lexically.
+    pub fn jobs_referencing_path(&self, path: &str) -> Result<Vec<JobDefinition>, JobServiceError> {
+        let target = comparable_path(path);
+        let references = |location: &StorageLocation| {
+            location
+                .local_path()
+                .is_some_and(|job_path| comparable_path(job_path).starts_with(&target))
+        };
+        Ok(self
+            .jobs
+            .list_jobs()?
+            .into_iter()
+            .filter(|job| references(&job.transfer.source) || references(&job.transfer.destination))
+            .collect())
     }
 
-    pub fn get_invocation(&self, id: &Uuid) -> Result<BackupInvocation, AppError> {
-        self.invocations.get_invocation(id)
+    pub fn record_invocation(&self, inv: &BackupInvocation) -> Result<(), JobServiceError> {
+        Ok(self.invocations.create_invocation(inv)?)
     }
 
-    pub fn delete_invocation(&self, id: &Uuid) -> Result<(), AppError> {
-        self.invocations.delete_invocation(id)
+    pub fn complete_invocation(&self, inv: &BackupInvocation) -> Result<(), JobServiceError> {
+        Ok(self.invocations.update_invocation(inv)?)
     }
 
-    pub fn delete_invocations_for_job(&self, job_id: &Uuid) -> Result<(), AppError> {
-        self.invocations.delete_invocations_for_job(job_id)
+    pub fn get_invocation(&self, id: &Uuid) -> Result<BackupInvocation, JobServiceError> {
+        Ok(self.invocations.get_invocation(id)?)
     }
 
-    pub fn list_all_invocations(&self) -> Result<Vec<BackupInvocation>, AppError> {
-        self.invocations.list_all_invocations()
+    pub fn delete_invocation(&self, id: &Uuid) -> Result<(), JobServiceError> {
+        Ok(self.invocations.delete_invocation(id)?)
+    }
+
+    pub fn delete_invocations_for_job(&self, job_id: &Uuid) -> Result<(), JobServiceError> {
+        self.invocations.delete_invocations_for_job(job_id)?;
+        let name = self
+            .jobs
+            .get_job(job_id)
+            .map_or_else(|_| job_id.to_string(), |job| format!("'{}'", job.name));
+        self.audit(
+            AuditAction::HistoryPurged,
+            *job_id,
+            format!("Deleted all history of job {}", name),
+        );
+        Ok(())
+    }
+
+    /// Forget an invocation's log file while keeping its record.
+    pub fn clear_log_file_path(&self, id: &Uuid) -> Result<(), JobServiceError> {
+        Ok(self.invocations.clear_log_file_path(id)?)
+    }
+
+    /// Point several invocations at new log files, all-or-nothing.
+    pub fn update_log_file_paths(
+        &self,
+        updates: &[(Uuid, Option<String>)],
+    ) -> Result<(), JobServiceError> {
+        Ok(self.invocations.update_log_file_paths(updates)?)
+    }
+
+    /// Store a run's (possibly truncated) itemized changes and their summary.
+    pub fn record_itemized_changes(
+        &self,
+        invocation_id: &Uuid,
+        itemized: &StoredItemizedChanges,
+    ) -> Result<(), JobServiceError> {
+        Ok(self
+            .invocations
+            .save_itemized_changes(invocation_id, itemized)?)
+    }
+
+    pub fn get_itemized_changes(
+        &self,
+        invocation_id: &Uuid,
+    ) -> Result<Option<StoredItemizedChanges>, JobServiceError> {
+        Ok(self.invocations.get_itemized_changes(invocation_id)?)
+    }
+
+    pub fn list_all_invocations(&self) -> Result<Vec<BackupInvocation>, JobServiceError> {
+        Ok(self.invocations.list_all_invocations()?)
+    }
+
+    /// Invocations started at or after `since`, oldest first.
+    pub fn list_invocations_since(
+        &self,
+        since: &DateTime<Utc>,
+    ) -> Result<Vec<BackupInvocation>, JobServiceError> {
+        Ok(self.invocations.list_invocations_since(since)?)
+    }
+
+    /// One page of invocations started at or after `since`, oldest first;
+    /// pass the last one of the previous page as `after`.
+    pub fn list_invocations_page(
+        &self,
+        since: Option<&DateTime<Utc>>,
+        after: Option<&BackupInvocation>,
+        limit: usize,
+    ) -> Result<Vec<BackupInvocation>, JobServiceError> {
+        let after = after.map(|inv| (&inv.started_at, &inv.id));
+        Ok(self
+            .invocations
+            .list_invocations_page(since, after, limit)?)
     }
 
     pub fn get_job_history(
         &self,
         job_id: &Uuid,
         limit: usize,
-    ) -> Result<Vec<BackupInvocation>, AppError> {
+    ) -> Result<Vec<BackupInvocation>, JobServiceError> {
         let mut invocations = self.invocations.list_invocations_for_job(job_id)?;
         invocations.sort_by(|a, b| b.started_at.cmp(&a.started_at));
         invocations.truncate(limit);
         Ok(invocations)
     }
 
-    pub fn record_snapshot(&self, snapshot: &SnapshotRecord) -> Result<(), AppError> {
-        self.snapshots.create_snapshot(snapshot)
+    /// Enabled, scheduled jobs whose next run was due more than `grace` ago,
+    /// with how overdue each one is, most overdue first.
+    ///
+    /// The due time is the schedule's next occurrence after the job's last
+    /// invocation, or after its creation if it has never run.
+    pub fn overdue_jobs(
+        &self,
+        now: DateTime<Utc>,
+        grace: Duration,
+    ) -> Result<Vec<(JobDefinition, Duration)>, JobServiceError> {
+        let mut overdue = Vec::new();
+        for job in self.jobs.list_jobs()? {
+            let schedule = match (&job.schedule, job.enabled) {
+                (Some(schedule), true) => schedule,
+                _ => continue,
+            };
+            let last_run = self
+                .invocations
+                .list_invocations_for_job(&job.id)?
+                .iter()
+                .map(|inv| inv.started_at)
+                .max()
+                .unwrap_or(job.created_at);
+            let due = match next_run_time(schedule, last_run) {
+                Some(due) => due,
+                None => continue,
+            };
+            let late_by = now - due;
+            if late_by > grace {
+                overdue.push((job, late_by));
+            }
+        }
+        overdue.sort_by_key(|(_, late_by)| Reverse(*late_by));
+        Ok(overdue)
+    }
+
+    pub fn record_snapshot(&self, snapshot: &SnapshotRecord) -> Result<(), JobServiceError> {
+        Ok(self.snapshots.create_snapshot(snapshot)?)
     }
 
     pub fn get_latest_snapshot(
         &self,
         job_id: &Uuid,
-    ) -> Result<Option<SnapshotRecord>, AppError> {
-        self.snapshots.get_latest_snapshot_for_job(job_id)
+    ) -> Result<Option<SnapshotRecord>, JobServiceError> {
+        Ok(self.snapshots.get_latest_snapshot_for_job(job_id)?)
+    }
+
+    pub fn list_snapshots(&self, job_id: &Uuid) -> Result<Vec<SnapshotRecord>, JobServiceError> {
+        Ok(self.snapshots.list_snapshots_for_job(job_id)?)
     }
 
-    pub fn list_snapshots(
+    /// Paths of the job's `count` most recent snapshots, newest first.
+    pub fn recent_snapshot_paths(
         &self,
         job_id: &Uuid,
-    ) -> Result<Vec<SnapshotRecord>, AppError> {
-        self.snapshots.list_snapshots_for_job(job_id)
+        count: usize,
+    ) -> Result<Vec<String>, JobServiceError> {
+        Ok(self
+            .snapshots
+            .list_snapshots_for_job(job_id)?
+            .into_iter()
+            .take(count)
+            .map(|snap| snap.snapshot_path)
+            .collect())
+    }
+
+    pub fn delete_snapshot(&self, id: &Uuid) -> Result<(), JobServiceError> {
+        self.snapshots.delete_snapshot(id)?;
+        self.audit(
+            AuditAction::SnapshotDeleted,
+            *id,
+            format!("Deleted snapshot record {}", id),
+        );
+        Ok(())
+    }
+
+    /// Pin or unpin a snapshot. Pinned snapshots survive retention.
+    pub fn set_snapshot_pinned(&self, id: &Uuid, pinned: bool) -> Result<(), JobServiceError> {
+        Ok(self.snapshots.set_snapshot_pinned(id, pinned)?)
+    }
+
+    pub fn list_all_snapshots(&self) -> Result<Vec<SnapshotRecord>, JobServiceError> {
+        Ok(self.snapshots.list_all_snapshots()?)
     }
 
-    pub fn delete_snapshot(&self, id: &Uuid) -> Result<(), AppError> {
-        self.snapshots.delete_snapshot(id)
+    pub fn record_snapshot_verification(
+        &self,
+        verification: &SnapshotVerification,
+    ) -> Result<(), JobServiceError> {
+        Ok(self.snapshots.record_verification(verification)?)
+    }
+
+    /// The verification of the snapshot `invocation_id` wrote, if one ran.
+    pub fn get_snapshot_verification(
+        &self,
+        invocation_id: &Uuid,
+    ) -> Result<Option<SnapshotVerification>, JobServiceError> {
+        Ok(self.snapshots.get_verification(invocation_id)?)
+    }
+
+    /// Insert or overwrite a job, keeping its id and timestamps as given.
+    /// Used when restoring a full export.
+    pub fn upsert_job(&self, job: &JobDefinition) -> Result<(), JobServiceError> {
+        Ok(self.jobs.upsert_job(job)?)
+    }
+
+    /// Swap the whole job set for `jobs` atomically, keeping ids and
+    /// timestamps as given. Jobs whose id is kept keep their history and
+    /// snapshots; jobs missing from `jobs` are deleted with theirs. On any
+    /// error the original set is left intact.
+    pub fn replace_all(&self, jobs: Vec<JobDefinition>) -> Result<(), JobServiceError> {
+        let mut ids = HashSet::new();
+        for job in &jobs {
+            ensure_valid(job)?;
+            if !ids.insert(job.id) {
+                return Err(JobServiceError::Conflict(format!(
+                    "Job id {} appears more than once",
+                    job.id
+                )));
+            }
+        }
+        let existing = self.jobs.list_jobs()?;
+        self.jobs.replace_all_jobs(&jobs)?;
+        for job in existing.iter().filter(|job| !ids.contains(&job.id)) {
+            self.audit(
+                AuditAction::JobDeleted,
+                job.id,
+                format!("Deleted job '{}' when replacing all jobs", job.name),
+            );
+        }
+        Ok(())
+    }
+
+    pub fn upsert_invocation(&self, inv: &BackupInvocation) -> Result<(), JobServiceError> {
+        Ok(self.invocations.upsert_invocation(inv)?)
+    }
+
+    pub fn upsert_snapshot(&self, snapshot: &SnapshotRecord) -> Result<(), JobServiceError> {
+        Ok(self.snapshots.upsert_snapshot(snapshot)?)
+    }
+
+    pub fn insert_prune_record(&self, record: &PruneRecord) -> Result<(), JobServiceError> {
+        Ok(self.invocations.insert_prune_record(record)?)
+    }
+
+    /// Remove and return the most recent prune record newer than `cutoff`.
+    pub fn take_latest_prune_record(
+        &self,
+        cutoff: &DateTime<Utc>,
+    ) -> Result<Option<PruneRecord>, JobServiceError> {
+        Ok(self.invocations.take_latest_prune_record(cutoff)?)
+    }
+
+    pub fn delete_prune_records_before(
+        &self,
+        cutoff: &DateTime<Utc>,
+    ) -> Result<usize, JobServiceError> {
+        Ok(self.invocations.delete_prune_records_before(cutoff)?)
     }
 
     /// Apply the retention policy for a snapshot-mode job.
     ///
     /// Returns the list of snapshot paths that were pruned from the database.
-    /// The caller is responsible for deleting the actual directories on disk.
-    pub fn apply_retention_policy(
+    /// The caller is responsible for the actual directories on disk: deleting
+    /// them, or moving them to the trash, with `dispose_pruned_snapshot()`.
+    pub fn apply_retention_policy(&self, job_id: &Uuid) -> Result<Vec<String>, JobServiceError> {
+        Ok(self
+            .prune_snapshots_by_policy(job_id)?
+            .into_iter()
+            .map(|snap| snap.snapshot_path)
+            .collect())
+    }
+
+    /// Like [`apply_retention_policy`](Self::apply_retention_policy), but
+    /// returns the pruned records themselves so the prune can be undone.
+    pub fn prune_snapshots_by_policy(
         &self,
         job_id: &Uuid,
-    ) -> Result<Vec<String>, AppError> {
+    ) -> Result<Vec<SnapshotRecord>, JobServiceError> {
         let job = self.jobs.get_job(job_id)?;
         let policy = match &job.transfer.backup_mode {
             BackupMode::Snapshot { retention_policy } => retention_policy,
@@ -145,14 +483,52 @@ impl JobService {
         let snapshots = self.snapshots.list_snapshots_for_job(job_id)?;
         let to_delete = snapshot_retention::compute_snapshots_to_delete(&snapshots, policy);
 
-        let mut pruned_paths = Vec::new();
+        let mut pruned = Vec::new();
         for snap_id in &to_delete {
-            if let Some(snap) = snapshots.iter().find(|s| s.id == *snap_id) {
-                pruned_paths.push(snap.snapshot_path.clone());
-            }
+            let Some(snap) = snapshots.iter().find(|s| s.id == *snap_id) else {
+                continue;
+            };
             self.snapshots.delete_snapshot(snap_id)?;
+            self.audit(
+                AuditAction::SnapshotDeleted,
+                *snap_id,
+                format!(
+                    "Retention pruned snapshot {} of job '{}'",
+                    snap.snapshot_path, job.name
+                ),
+            );
+            pruned.push(snap.clone());
         }
 
-        Ok(pruned_paths)
+        Ok(pruned)
+    }
+}
+
+/// Reject a job definition that fails any of its structured checks.
+fn ensure_valid(job: &JobDefinition) -> Result<(), JobServiceError> {
+    let checks = validate_job(job);
+    if checks.iter().all(|c| c.passed) {
+        Ok(())
+    } else {
+        Err(JobServiceError::Validation(checks))
+    }
+}
+
+/// `path` in a form whose prefixes can be compared: canonical if it exists,
+/// otherwise with `.`, `..` and trailing separators removed.
+fn comparable_path(path: &str) -> PathBuf {
+    if let Ok(canonical) = std::fs::canonicalize(path) {
+        return canonical;
+    }
+    let mut normalized = PathBuf::new();
+    for component in Path::new(path).components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
     }
+    normalized
 }
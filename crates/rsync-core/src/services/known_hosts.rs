@@ -0,0 +1,166 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use base64::engine::general_purpose::{STANDARD, STANDARD_NO_PAD};
+use base64::Engine;
+use sha2::{Digest, Sha256};
+
+use crate::error::AppError;
+use crate::models::job::{HostKey, JobDefinition, StorageLocation};
+use crate::ssh_client::SshClient;
+
+/// `~/.ssh/known_hosts`, the file OpenSSH checks by default.
+pub fn default_known_hosts_path() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".ssh").join("known_hosts"))
+}
+
+/// The remote SSH host and port a job connects to, if any.
+pub fn ssh_target(job: &JobDefinition) -> Option<(String, u16)> {
+    [&job.transfer.source, &job.transfer.destination]
+        .into_iter()
+        .find_map(|loc| match loc {
+            StorageLocation::RemoteSsh { host, port, .. } => Some((host.clone(), *port)),
+            _ => None,
+        })
+}
+
+/// The host field of a `known_hosts` line: `host` on port 22, `[host]:port` otherwise.
+pub fn known_hosts_host(host: &str, port: u16) -> String {
+    if port == 22 {
+        host.to_string()
+    } else {
+        format!("[{}]:{}", host, port)
+    }
+}
+
+/// OpenSSH `SHA256:` fingerprint of a base64-encoded public key blob.
+pub fn fingerprint(key_base64: &str) -> Option<String> {
+    let blob = STANDARD.decode(key_base64).ok()?;
+    Some(format!(
+        "SHA256:{}",
+        STANDARD_NO_PAD.encode(Sha256::digest(&blob))
+    ))
+}
+
+/// Parse `ssh-keyscan` output (`host keytype base64key` per line, `#` comments)
+/// into host keys. Lines whose key does not decode are skipped.
+pub fn parse_keyscan_output(output: &str, host: &str, port: u16) -> Vec<HostKey> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let _host_field = fields.next()?;
+            let key_type = fields.next()?;
+            let key = fields.next()?;
+            Some(HostKey {
+                host: host.to_string(),
+                port,
+                key_type: key_type.to_string(),
+                fingerprint: fingerprint(key)?,
+                known_hosts_line: format!("{} {} {}", known_hosts_host(host, port), key_type, key),
+            })
+        })
+        .collect()
+}
+
+/// Fetch the host keys offered by the job's SSH server so the user can check
+/// the fingerprints. Nothing is trusted here; see [`trust_host_key`].
+pub fn scan_host_keys(job: &JobDefinition, ssh: &dyn SshClient) -> Result<Vec<HostKey>, AppError> {
+    let (host, port) = ssh_target(job).ok_or_else(|| {
+        AppError::ValidationError(format!("Job '{}' has no SSH location", job.name))
+    })?;
+    scan_host(&host, port, ssh)
+}
+
+/// Scan `host` again and pick the key with the fingerprint the user
+/// confirmed, so only a line derived here from the server's answer is ever
+/// written to `known_hosts`.
+pub fn confirmed_host_key(
+    host: &str,
+    port: u16,
+    fingerprint: &str,
+    ssh: &dyn SshClient,
+) -> Result<HostKey, AppError> {
+    scan_host(host, port, ssh)?
+        .into_iter()
+        .find(|key| key.fingerprint == fingerprint)
+        .ok_or_else(|| {
+            AppError::ValidationError(format!(
+                "{} no longer offers a key with fingerprint {}",
+                known_hosts_host(host, port),
+                fingerprint
+            ))
+        })
+}
+
+fn scan_host(host: &str, port: u16, ssh: &dyn SshClient) -> Result<Vec<HostKey>, AppError> {
+    check_field("host", host)?;
+    let output = ssh.keyscan(host, port)?;
+    let keys = parse_keyscan_output(&output, host, port);
+    if keys.is_empty() {
+        return Err(AppError::ValidationError(format!(
+            "ssh-keyscan returned no usable keys for {}",
+            known_hosts_host(host, port)
+        )));
+    }
+    Ok(keys)
+}
+
+/// A `known_hosts` field must be one non-empty word, or it would split the
+/// line or start another one. A leading `-` would make the host an
+/// `ssh-keyscan` option.
+fn check_field(name: &str, value: &str) -> Result<(), AppError> {
+    if value.is_empty()
+        || value.starts_with('-')
+        || value.chars().any(|c| c.is_whitespace() || c.is_control())
+    {
+        return Err(AppError::ValidationError(format!(
+            "Invalid {} for known_hosts: {:?}",
+            name, value
+        )));
+    }
+    Ok(())
+}
+
+/// Append a host key the user has confirmed to `known_hosts_path`, creating
+/// the file if needed. Returns false if the exact line is already present.
+/// `key` should come from [`confirmed_host_key`], not from the caller.
+pub fn trust_host_key(known_hosts_path: &Path, key: &HostKey) -> Result<bool, AppError> {
+    check_field("host", &key.host)?;
+    check_field("key type", &key.key_type)?;
+    if key.known_hosts_line.contains(['\n', '\r']) {
+        return Err(AppError::ValidationError(
+            "A known_hosts entry must be a single line".to_string(),
+        ));
+    }
+    let existing = match std::fs::read_to_string(known_hosts_path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+    if existing
+        .lines()
+        .any(|line| line.trim() == key.known_hosts_line)
+    {
+        return Ok(false);
+    }
+
+    if let Some(parent) = known_hosts_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(known_hosts_path)?;
+    // Don't glue the new entry onto a last line without a newline
+    let separator = if existing.is_empty() || existing.ends_with('\n') {
+        ""
+    } else {
+        "\n"
+    };
+    writeln!(file, "{}{}", separator, key.known_hosts_line)?;
+    Ok(true)
+}
@@ -0,0 +1,121 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::file_system::FileSystem;
+use crate::models::settings::LogRelocationResult;
+use crate::services::job_service::JobService;
+use crate::services::log_writer::log_file_set;
+use crate::services::settings_service::SettingsService;
+
+/// Move the log directory to `new_dir`, taking existing logs with it.
+///
+/// Every invocation log under the current directory (including rotated
+/// `.N` siblings) is moved to the same relative path under `new_dir`, and
+/// the invocations' `log_file_path` values are updated in one transaction.
+/// Logs that no longer exist still have their records updated. If any move
+/// or the database update fails, moved files are put back and the previous
+/// log directory setting is restored.
+pub fn relocate_logs(
+    fs: &dyn FileSystem,
+    job_service: &JobService,
+    settings_service: &SettingsService,
+    default_log_dir: &str,
+    new_dir: &str,
+) -> Result<LogRelocationResult, AppError> {
+    let new_dir = new_dir.trim();
+    if new_dir.is_empty() {
+        return Err(AppError::ValidationError(
+            "Log directory must not be empty".to_string(),
+        ));
+    }
+
+    let previous = settings_service.get_log_directory()?;
+    let old_dir = previous
+        .clone()
+        .filter(|d| !d.trim().is_empty())
+        .unwrap_or_else(|| default_log_dir.to_string());
+
+    settings_service.set_log_directory(new_dir)?;
+    if Path::new(&old_dir) == Path::new(new_dir) {
+        return Ok(LogRelocationResult::default());
+    }
+
+    let mut moved = Vec::new();
+    match move_logs(
+        fs,
+        job_service,
+        Path::new(&old_dir),
+        Path::new(new_dir),
+        &mut moved,
+    ) {
+        Ok(result) => Ok(result),
+        Err(e) => {
+            // Best effort: put files back in reverse order, then restore the setting.
+            for (from, to) in moved.iter().rev() {
+                if let Err(undo) = fs.rename(to, from) {
+                    log::error!("Failed to restore log {}: {}", from.display(), undo);
+                }
+            }
+            match previous {
+                Some(dir) => settings_service.set_log_directory(&dir)?,
+                None => settings_service.clear_log_directory()?,
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Move each invocation's log files and update the records. Successful moves
+/// are pushed onto `moved` so the caller can undo them on failure.
+fn move_logs(
+    fs: &dyn FileSystem,
+    job_service: &JobService,
+    old_dir: &Path,
+    new_dir: &Path,
+    moved: &mut Vec<(PathBuf, PathBuf)>,
+) -> Result<LogRelocationResult, AppError> {
+    let mut result = LogRelocationResult::default();
+    let mut handled: HashSet<String> = HashSet::new();
+    let mut updates: Vec<(Uuid, Option<String>)> = Vec::new();
+
+    for inv in job_service.list_all_invocations()? {
+        let old_path = match inv.execution_output.log_file_path {
+            Some(p) => p,
+            None => continue,
+        };
+        let rel = match Path::new(&old_path).strip_prefix(old_dir) {
+            Ok(rel) => rel.to_path_buf(),
+            Err(_) => continue,
+        };
+        let new_path = new_dir.join(rel).to_string_lossy().to_string();
+
+        // Templates without `{id}` share one log between invocations; move it once.
+        if handled.insert(old_path.clone()) {
+            if fs.exists(Path::new(&old_path)) {
+                result.moved += 1;
+            } else {
+                result.missing += 1;
+            }
+            let files = log_file_set(&old_path)
+                .into_iter()
+                .zip(log_file_set(&new_path));
+            for (from, to) in files {
+                let (from, to) = (PathBuf::from(from), PathBuf::from(to));
+                if !fs.exists(&from) {
+                    continue;
+                }
+                fs.rename(&from, &to)?;
+                moved.push((from, to));
+            }
+        }
+
+        updates.push((inv.id, Some(new_path)));
+    }
+
+    job_service.update_log_file_paths(&updates)?;
+    result.updated = updates.len();
+    Ok(result)
+}
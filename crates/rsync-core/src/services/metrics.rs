@@ -0,0 +1,191 @@
+use std::fmt::Write;
+
+use crate::error::AppError;
+use crate::models::backup::InvocationStatus;
+use crate::services::job_service::JobService;
+use crate::services::statistics_service::StatisticsService;
+
+/// Render backup health in the Prometheus text exposition format.
+///
+/// Per-job series carry `job_id` and `job` (name) labels. Jobs that have
+/// never finished a run have no `last_run` series.
+pub fn render_metrics(
+    job_service: &JobService,
+    statistics_service: &StatisticsService,
+) -> Result<String, AppError> {
+    let jobs = job_service.list_jobs()?;
+
+    let mut last_timestamp = Vec::new();
+    let mut last_status = Vec::new();
+    let mut bytes_total = Vec::new();
+    let mut recorded_failures = Vec::new();
+
+    for job in &jobs {
+        let labels = format!("job_id=\"{}\",job=\"{}\"", job.id, escape_label(&job.name));
+        let invocations = job_service.get_job_history(&job.id, usize::MAX)?;
+
+        // History is newest first; skip a run still in progress
+        if let Some(last) = invocations
+            .iter()
+            .find(|inv| inv.status != InvocationStatus::Running)
+        {
+            let finished = last.finished_at.unwrap_or(last.started_at);
+            last_timestamp.push(format!("{{{}}} {}", labels, finished.timestamp()));
+            let ok = u8::from(last.status == InvocationStatus::Succeeded);
+            last_status.push(format!("{{{}}} {}", labels, ok));
+        }
+
+        let failures = invocations
+            .iter()
+            .filter(|inv| {
+                matches!(
+                    inv.status,
+                    InvocationStatus::Failed | InvocationStatus::StoppedByLimit
+                )
+            })
+            .count();
+        recorded_failures.push(format!("{{{}}} {}", labels, failures));
+
+        let stats = statistics_service.get_aggregated_for_job(&job.id)?;
+        bytes_total.push(format!("{{{}}} {}", labels, stats.total_bytes_transferred));
+    }
+
+    let mut out = String::new();
+    write_family(
+        &mut out,
+        "rsync_studio_jobs",
+        "gauge",
+        "Number of configured jobs.",
+        &[format!(" {}", jobs.len())],
+    );
+    write_family(
+        &mut out,
+        "rsync_studio_job_last_run_timestamp_seconds",
+        "gauge",
+        "Unix time the job's most recent run finished.",
+        &last_timestamp,
+    );
+    write_family(
+        &mut out,
+        "rsync_studio_job_last_run_success",
+        "gauge",
+        "1 if the job's most recent run succeeded, 0 otherwise.",
+        &last_status,
+    );
+    write_family(
+        &mut out,
+        "rsync_studio_job_bytes_transferred",
+        "gauge",
+        "Bytes transferred by the job's recorded successful runs. Drops when statistics are reset.",
+        &bytes_total,
+    );
+    write_family(
+        &mut out,
+        "rsync_studio_job_recorded_failures",
+        "gauge",
+        "Runs in the job's retained history that failed or hit their run-time limit.",
+        &recorded_failures,
+    );
+    Ok(out)
+}
+
+/// Write one metric family; each sample is its label set (or empty) and value.
+fn write_family(out: &mut String, name: &str, kind: &str, help: &str, samples: &[String]) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+    for sample in samples {
+        let _ = writeln!(out, "{}{}", name, sample);
+    }
+}
+
+/// Escape a label value per the text format: backslash, quote and newline.
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// How long a metrics connection may take to send each part of its request.
+#[cfg(feature = "metrics-server")]
+const REQUEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Most of a request read before answering; the rest is ignored.
+#[cfg(feature = "metrics-server")]
+const MAX_REQUEST_BYTES: u64 = 8 * 1024;
+
+/// Serve `GET /metrics` on `addr` until the listener fails. Blocks the caller;
+/// each connection is answered on its own thread, so a slow client holds up
+/// no one else.
+#[cfg(feature = "metrics-server")]
+pub fn serve_metrics(
+    addr: &str,
+    job_service: std::sync::Arc<JobService>,
+    statistics_service: std::sync::Arc<StatisticsService>,
+) -> std::io::Result<()> {
+    use std::net::TcpListener;
+    use std::sync::Arc;
+
+    let listener = TcpListener::bind(addr)?;
+    log::info!("Serving metrics on http://{}/metrics", addr);
+
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(s) => s,
+            Err(e) => {
+                log::warn!("Metrics connection failed: {}", e);
+                continue;
+            }
+        };
+        let job_service = Arc::clone(&job_service);
+        let statistics_service = Arc::clone(&statistics_service);
+        std::thread::spawn(move || {
+            if let Err(e) = answer_metrics_request(stream, &job_service, &statistics_service) {
+                log::warn!("Metrics request failed: {}", e);
+            }
+        });
+    }
+    Ok(())
+}
+
+/// Read one request from `stream`, up to the blank line ending its headers,
+/// and write the response.
+#[cfg(feature = "metrics-server")]
+fn answer_metrics_request(
+    mut stream: std::net::TcpStream,
+    job_service: &JobService,
+    statistics_service: &StatisticsService,
+) -> std::io::Result<()> {
+    use std::io::{BufRead, BufReader, Read, Write as _};
+
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    stream.set_write_timeout(Some(REQUEST_TIMEOUT))?;
+    let mut reader = BufReader::new((&stream).take(MAX_REQUEST_BYTES));
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Closing with unread headers in the socket makes the kernel reset the
+    // connection, and the client may never see the response
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 0 && !header.trim_end().is_empty() {
+        header.clear();
+    }
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = (parts.next(), parts.next());
+
+    let (status, content_type, body) = match (method, path) {
+        (Some("GET"), Some("/metrics")) => match render_metrics(job_service, statistics_service) {
+            Ok(body) => ("200 OK", "text/plain; version=0.0.4", body),
+            Err(e) => ("500 Internal Server Error", "text/plain", e.to_string()),
+        },
+        _ => ("404 Not Found", "text/plain", "Not found\n".to_string()),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )
+}
@@ -5,25 +5,65 @@ pub mod retention;
 pub mod scheduling;
 
 // Root modules
+pub mod audit;
+pub mod backup_set_service;
+pub mod dashboard;
+pub mod destination_usage;
 pub mod export_import;
+pub mod history_export;
+pub mod invocation_report;
+pub mod job_duplicates;
 pub mod job_service;
+pub mod log_relocation;
 pub mod log_scrubber;
+pub mod metrics;
+pub mod known_hosts;
 pub mod preflight;
+pub mod restore;
 pub mod settings_service;
+pub mod snapshot_diff;
 pub mod statistics_service;
+pub mod transfer_estimate;
 
 // Re-exports for API stability
 pub use command::command_builder;
 pub use command::command_explainer;
 pub use command::command_parser;
 pub use command::itemize_parser;
+pub use command::pattern_tester;
+pub use command::rsync_glob;
+pub use execution::backup_set_runner;
+pub use execution::change_alarm;
+pub use execution::daemon_auth;
+pub use execution::destination_prep;
 pub use execution::execution_handler;
+pub use execution::itemize_recorder;
 pub use execution::job_executor;
 pub use execution::job_runner;
+pub use execution::log_follow;
+pub use execution::log_writer;
+pub use execution::notifier;
+pub use execution::parallel_split;
 pub use execution::progress_parser;
+pub use execution::rerun;
+pub use execution::run_conditions;
 pub use execution::running_jobs;
+pub use execution::runtime_limit;
+pub use execution::snapshot_resume;
+pub use execution::snapshot_verify;
+pub use execution::test_mode;
+pub use execution::throttled_handler;
 pub use retention::history_retention;
+pub use retention::orphaned_logs;
+pub use retention::prune_recovery;
 pub use retention::retention_runner;
 pub use retention::snapshot_retention;
+pub use retention::snapshot_trash;
+pub use scheduling::bandwidth_schedule;
+pub use scheduling::data_budget;
+pub use scheduling::quiet_hours;
 pub use scheduling::scheduler;
 pub use scheduling::scheduler_backend;
+pub use scheduling::scheduler_events;
+pub use scheduling::source_change;
+pub use scheduling::startup_runner;
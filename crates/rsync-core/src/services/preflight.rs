@@ -1,28 +1,88 @@
 use std::path::Path;
 
-use crate::models::job::{JobDefinition, StorageLocation};
+use crate::models::itemize::{DifferenceKind, TransferType};
+use crate::models::job::{BackupMode, JobDefinition, StorageLocation};
+use crate::models::settings::MirrorDeletionCheck;
 use crate::models::validation::{CheckSeverity, CheckType, PreflightResult, ValidationCheck};
 use crate::file_system::FileSystem;
-use crate::rsync_client::RsyncClient;
+use crate::format::format_bytes;
+use crate::rsync_client::{RsyncClient, RsyncError, RsyncResult};
+use crate::services::command_builder::build_rsync_args;
+use crate::services::destination_prep::{options_with_mkpath, supports_mkpath};
+use crate::services::itemize_parser::parse_itemize_line;
+use crate::services::runtime_limit::parse_rsync_version;
+
+/// Itemizes unchanged files too, so the output lists every destination entry.
+const ITEMIZE_ALL_ARG: &str = "-ii";
+
+/// Connect timeout for the connectivity test when the job's SSH config sets
+/// none, so an unreachable host fails fast instead of hanging.
+pub const DEFAULT_CONNECT_TEST_TIMEOUT_SECS: u32 = 10;
+
+/// First rsync release with `--checksum-choice`, which then knew only these.
+const CHECKSUM_CHOICE_MIN_VERSION: (u32, u32, u32) = (3, 1, 3);
+const LEGACY_CHECKSUMS: [&str; 3] = ["md4", "md5", "none"];
+
+/// First rsync release with xxhash, whose `--version` may list what it has.
+const XXHASH_MIN_VERSION: (u32, u32, u32) = (3, 2, 0);
+
+/// rsync's exit code for "timeout waiting for daemon connection".
+const DAEMON_CONNECT_TIMEOUT_EXIT: i32 = 35;
 
 /// Run preflight validation checks for a job.
 ///
-/// Checks: rsync installed, source exists (local only), destination writable
-/// (local only), disk space (local destination), SSH connectivity (dry-run test).
+/// Checks: rsync installed, volume mounted (mount locations only), source
+/// exists (local only), destination writable (local only), disk space (local
+/// destination), snapshot link-dest on the destination's filesystem (snapshot
+/// mode with a previous snapshot), SSH connectivity (dry-run test), remote
+/// destination missing (when the dry run says so and the job does not
+/// create it), checksum algorithm (when the job picks one), deletion estimate (mirror jobs with `--delete`, when
+/// `mirror_check` is set and the destination is reachable).
+///
+/// `link_dest` is the latest snapshot for snapshot-mode jobs.
 pub fn run_preflight(
     job: &JobDefinition,
+    link_dest: Option<&str>,
+    mirror_check: Option<&MirrorDeletionCheck>,
     fs: &dyn FileSystem,
     rsync: &dyn RsyncClient,
 ) -> PreflightResult {
     let mut checks = Vec::new();
 
-    checks.push(check_rsync_installed(rsync));
+    let version = rsync.version();
+    checks.push(check_rsync_installed(&version));
+    if let (Some(choice), Ok(version)) = (&job.options.file_handling.checksum_choice, &version) {
+        if let Some(check) = check_checksum_choice(choice, version) {
+            checks.push(check);
+        }
+    }
+    for loc in [&job.transfer.source, &job.transfer.destination] {
+        if let StorageLocation::Mount { .. } = loc {
+            checks.push(check_mount_present(loc, fs));
+        }
+    }
     checks.push(check_source_exists(&job.transfer.source, fs));
     checks.push(check_destination_writable(&job.transfer.destination, fs));
     checks.push(check_disk_space(&job.transfer.source, &job.transfer.destination, fs));
+    if let Some(check) = check_snapshot_filesystem(job, link_dest, fs) {
+        checks.push(check);
+    }
 
+    let mut reachable = true;
     if is_remote(&job.transfer.source) || is_remote(&job.transfer.destination) {
-        checks.push(check_ssh_connectivity(job, rsync));
+        let timeout_secs = connect_test_timeout(job);
+        let dry_run = remote_dry_run(job, timeout_secs, rsync);
+        let connectivity = check_ssh_connectivity(&dry_run, timeout_secs);
+        reachable = connectivity.passed;
+        checks.push(connectivity);
+        if let Some(check) = check_remote_destination_missing(job, &dry_run) {
+            checks.push(check);
+        }
+    }
+    if let (Some(mirror_check), true) = (mirror_check, reachable) {
+        if let Some(check) = check_mirror_deletions(job, mirror_check, rsync) {
+            checks.push(check);
+        }
     }
 
     let overall_pass = checks
@@ -36,12 +96,233 @@ pub fn run_preflight(
     }
 }
 
+/// Warn when a new destination changes which preflight checks apply.
+///
+/// Writability and disk space are only checked for local destinations, and
+/// the SSH connectivity test only runs for remote ones.
+pub fn destination_change_warnings(
+    old: &StorageLocation,
+    new: &StorageLocation,
+) -> Vec<ValidationCheck> {
+    let message = match (is_remote(old), is_remote(new)) {
+        (false, true) => {
+            "Destination is now remote: preflight no longer checks that it is writable or has enough disk space, and tests SSH connectivity instead"
+        }
+        (true, false) => {
+            "Destination is now local: preflight checks that it is writable and has enough disk space"
+        }
+        _ => return Vec::new(),
+    };
+    vec![ValidationCheck {
+        check_type: CheckType::PreflightScope,
+        passed: false,
+        message: message.to_string(),
+        severity: CheckSeverity::Warning,
+    }]
+}
+
+/// Run preflight for each job and return the results worst-first.
+///
+/// Results are ordered by `severity_rank`, so jobs with failed errors come
+/// before failed warnings and clean passes. Ties keep the order of `jobs`.
+pub fn run_preflight_all(
+    jobs: &[JobDefinition],
+    link_dest_for: impl Fn(&JobDefinition) -> Option<String>,
+    mirror_check: Option<&MirrorDeletionCheck>,
+    fs: &dyn FileSystem,
+    rsync: &dyn RsyncClient,
+) -> Vec<PreflightResult> {
+    let mut results: Vec<PreflightResult> = jobs
+        .iter()
+        .map(|job| run_preflight(job, link_dest_for(job).as_deref(), mirror_check, fs, rsync))
+        .collect();
+    results.sort_by_key(|r| std::cmp::Reverse(severity_rank(r)));
+    results
+}
+
+/// Rank a result by its worst failed check: 2 for a failed error, 1 for a
+/// failed warning, 0 when every check passed.
+pub fn severity_rank(result: &PreflightResult) -> u8 {
+    result
+        .checks
+        .iter()
+        .filter(|c| !c.passed)
+        .map(|c| match c.severity {
+            CheckSeverity::Error => 2,
+            CheckSeverity::Warning => 1,
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Describe the first mount location of `job` that is not mounted, if any.
+///
+/// Used to refuse a run before rsync writes into an empty mountpoint.
+pub fn missing_mount(job: &JobDefinition, fs: &dyn FileSystem) -> Option<String> {
+    [&job.transfer.source, &job.transfer.destination]
+        .into_iter()
+        .find_map(|loc| verify_mount(loc, fs).err())
+}
+
+/// Describe why the local destination of `job` cannot take the source plus
+/// `margin_percent` of its size right now, if it cannot.
+///
+/// The executor calls this just before spawning rsync, since free space can
+/// shrink between preflight and a scheduled run. Remote locations, dry runs
+/// and sizes that cannot be read are let through.
+pub fn insufficient_space(
+    job: &JobDefinition,
+    margin_percent: u32,
+    fs: &dyn FileSystem,
+) -> Option<String> {
+    if job.options.core_transfer.dry_run {
+        return None;
+    }
+    let src = job.transfer.source.local_path()?;
+    let dst = job.transfer.destination.local_path()?;
+    let src_size = fs.dir_size(Path::new(src)).ok()?;
+    let available = fs.available_space(Path::new(dst)).ok()?;
+
+    let margin = src_size.saturating_mul(u64::from(margin_percent)) / 100;
+    let needed = src_size.saturating_add(margin);
+    if available >= needed {
+        return None;
+    }
+    Some(format!(
+        "Insufficient space on destination: {} available, {} needed (source {} plus {}%)",
+        format_bytes(available),
+        format_bytes(needed),
+        format_bytes(src_size),
+        margin_percent
+    ))
+}
+
+/// Describe why the local destination of `job` looks wrongly empty, if the
+/// job sets `expect_nonempty_destination` and the destination directory is
+/// missing or holds nothing.
+///
+/// Like [`insufficient_space`], the executor calls this just before spawning
+/// rsync. Remote locations and dry runs are let through.
+pub fn unexpectedly_empty_destination(job: &JobDefinition, fs: &dyn FileSystem) -> Option<String> {
+    if !job.safety.expect_nonempty_destination || job.options.core_transfer.dry_run {
+        return None;
+    }
+    let dst = job.transfer.destination.local_path()?;
+    let has_entries = fs
+        .read_dir(Path::new(dst))
+        .is_ok_and(|entries| !entries.is_empty());
+    if has_entries {
+        return None;
+    }
+    Some(format!(
+        "Destination {} is empty, but the job expects it to hold data. \
+         Is the drive or share mounted?",
+        dst
+    ))
+}
+
+/// Verify that a `Mount` location has a volume mounted at its mountpoint,
+/// with the expected label if one is set. Other locations always pass.
+pub fn verify_mount(loc: &StorageLocation, fs: &dyn FileSystem) -> Result<String, String> {
+    let (mountpoint, path, expected_fs_label) = match loc {
+        StorageLocation::Mount {
+            mountpoint,
+            path,
+            expected_fs_label,
+        } => (mountpoint, path, expected_fs_label),
+        _ => return Ok("Not a mount location".to_string()),
+    };
+
+    let mp = Path::new(mountpoint);
+    if !Path::new(path).starts_with(mp) {
+        return Err(format!(
+            "Path {} is not inside mountpoint {}",
+            path, mountpoint
+        ));
+    }
+    if !fs.is_dir(mp) {
+        return Err(format!("Mountpoint does not exist: {}", mountpoint));
+    }
+    if fs.mount_point(mp).as_deref() != Some(mp) {
+        return Err(format!("Nothing is mounted at {}", mountpoint));
+    }
+    match (expected_fs_label, fs.volume_label(mp)) {
+        (None, _) => Ok(format!("Volume is mounted at {}", mountpoint)),
+        (Some(expected), Some(actual)) if *expected == actual => Ok(format!(
+            "Volume \"{}\" is mounted at {}",
+            actual, mountpoint
+        )),
+        (Some(expected), Some(actual)) => Err(format!(
+            "Volume at {} is labeled \"{}\", expected \"{}\"",
+            mountpoint, actual, expected
+        )),
+        (Some(expected), None) => Err(format!(
+            "Could not read the volume label at {} (expected \"{}\")",
+            mountpoint, expected
+        )),
+    }
+}
+
+fn check_mount_present(loc: &StorageLocation, fs: &dyn FileSystem) -> ValidationCheck {
+    let (passed, message) = match verify_mount(loc, fs) {
+        Ok(message) => (true, message),
+        Err(message) => (false, message),
+    };
+    ValidationCheck {
+        check_type: CheckType::MountPresent,
+        passed,
+        message,
+        severity: CheckSeverity::Error,
+    }
+}
+
+/// Warn when a snapshot job's previous snapshot is on a different filesystem
+/// from its destination. Hard links can't cross filesystems, so rsync would
+/// silently make a full copy instead of linking unchanged files.
+///
+/// Returns `None` for other modes, remote destinations and first runs.
+pub fn check_snapshot_filesystem(
+    job: &JobDefinition,
+    link_dest: Option<&str>,
+    fs: &dyn FileSystem,
+) -> Option<ValidationCheck> {
+    if !matches!(job.transfer.backup_mode, BackupMode::Snapshot { .. }) {
+        return None;
+    }
+    let dest = Path::new(job.transfer.destination.local_path()?);
+    let link_dest = Path::new(link_dest?);
+
+    // Only a known difference warns; an unreadable side is given the benefit of the doubt
+    let type_differs = known_and_different(fs.filesystem_type(dest), fs.filesystem_type(link_dest));
+    let mount_differs = known_and_different(fs.mount_point(dest), fs.mount_point(link_dest));
+
+    let passed = !(type_differs || mount_differs);
+    Some(ValidationCheck {
+        check_type: CheckType::SnapshotFilesystem,
+        passed,
+        message: if passed {
+            "Previous snapshot is on the destination's filesystem".to_string()
+        } else {
+            format!(
+                "Previous snapshot {} is on a different filesystem from {}: unchanged files will be copied in full instead of hard-linked",
+                link_dest.display(),
+                dest.display()
+            )
+        },
+        severity: CheckSeverity::Warning,
+    })
+}
+
+fn known_and_different<T: PartialEq>(a: Option<T>, b: Option<T>) -> bool {
+    matches!((a, b), (Some(a), Some(b)) if a != b)
+}
+
 fn is_remote(loc: &StorageLocation) -> bool {
-    !matches!(loc, StorageLocation::Local { .. })
+    loc.local_path().is_none()
 }
 
-fn check_rsync_installed(rsync: &dyn RsyncClient) -> ValidationCheck {
-    match rsync.version() {
+fn check_rsync_installed(version: &Result<String, RsyncError>) -> ValidationCheck {
+    match version {
         Ok(version) => ValidationCheck {
             check_type: CheckType::RsyncInstalled,
             passed: true,
@@ -57,9 +338,74 @@ fn check_rsync_installed(rsync: &dyn RsyncClient) -> ValidationCheck {
     }
 }
 
+/// The checksum algorithms the rsync that printed `version_output` accepts
+/// for `--checksum-choice`, or `None` when that can't be told.
+///
+/// rsync 3.2 lists them under "Checksum list:"; 3.1.3 up to 3.2 only knows
+/// md4 and md5, and older versions have no `--checksum-choice` at all.
+pub fn supported_checksums(version_output: &str) -> Option<Vec<String>> {
+    let mut lines = version_output.lines();
+    if lines.any(|line| line.trim() == "Checksum list:") {
+        let names = lines
+            .take_while(|line| line.starts_with(char::is_whitespace) && !line.trim().is_empty())
+            .flat_map(str::split_whitespace)
+            .filter(|name| !name.starts_with('('))
+            .map(str::to_string)
+            .collect();
+        return Some(names);
+    }
+
+    let version = parse_rsync_version(version_output)?;
+    if version >= XXHASH_MIN_VERSION {
+        None
+    } else if version >= CHECKSUM_CHOICE_MIN_VERSION {
+        Some(
+            LEGACY_CHECKSUMS
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+        )
+    } else {
+        Some(Vec::new())
+    }
+}
+
+/// Warn when the local rsync doesn't know the job's `--checksum-choice`
+/// algorithm. `choice` may name one algorithm or a `transfer,checksum` pair;
+/// `auto` is always accepted. Returns `None` when the version can't tell.
+pub fn check_checksum_choice(choice: &str, version_output: &str) -> Option<ValidationCheck> {
+    let supported = supported_checksums(version_output)?;
+    let unsupported: Vec<&str> = choice
+        .split(',')
+        .map(str::trim)
+        .filter(|name| *name != "auto" && !supported.iter().any(|s| s == name))
+        .collect();
+
+    let message = if unsupported.is_empty() {
+        format!("rsync supports --checksum-choice={}", choice)
+    } else if supported.is_empty() {
+        format!(
+            "This rsync ({}) does not support --checksum-choice; it needs rsync 3.1.3 or newer",
+            version_output.lines().next().unwrap_or_default().trim()
+        )
+    } else {
+        format!(
+            "This rsync does not support the {} checksum (it supports: {})",
+            unsupported.join(", "),
+            supported.join(", ")
+        )
+    };
+    Some(ValidationCheck {
+        check_type: CheckType::ChecksumChoice,
+        passed: unsupported.is_empty(),
+        message,
+        severity: CheckSeverity::Warning,
+    })
+}
+
 fn check_source_exists(source: &StorageLocation, fs: &dyn FileSystem) -> ValidationCheck {
-    match source {
-        StorageLocation::Local { path } => {
+    match source.local_path() {
+        Some(path) => {
             let exists = fs.exists(Path::new(path));
             ValidationCheck {
                 check_type: CheckType::SourceExists,
@@ -82,8 +428,8 @@ fn check_source_exists(source: &StorageLocation, fs: &dyn FileSystem) -> Validat
 }
 
 fn check_destination_writable(dest: &StorageLocation, fs: &dyn FileSystem) -> ValidationCheck {
-    match dest {
-        StorageLocation::Local { path } => {
+    match dest.local_path() {
+        Some(path) => {
             let p = Path::new(path);
             if fs.exists(p) && fs.is_dir(p) {
                 ValidationCheck {
@@ -135,8 +481,8 @@ fn check_disk_space(
     dest: &StorageLocation,
     fs: &dyn FileSystem,
 ) -> ValidationCheck {
-    let (src_local, dst_local) = match (source, dest) {
-        (StorageLocation::Local { path: src }, StorageLocation::Local { path: dst }) => (src, dst),
+    let (src_local, dst_local) = match (source.local_path(), dest.local_path()) {
+        (Some(src), Some(dst)) => (src, dst),
         _ => {
             return ValidationCheck {
                 check_type: CheckType::DiskSpace,
@@ -184,69 +530,264 @@ fn check_disk_space(
     }
 }
 
-fn check_ssh_connectivity(job: &JobDefinition, rsync: &dyn RsyncClient) -> ValidationCheck {
+/// The connectivity test's connect timeout: the job's SSH `connect_timeout`,
+/// or [`DEFAULT_CONNECT_TEST_TIMEOUT_SECS`].
+pub fn connect_test_timeout(job: &JobDefinition) -> u32 {
+    job.ssh_config
+        .as_ref()
+        .and_then(|ssh| ssh.connect_timeout)
+        .unwrap_or(DEFAULT_CONNECT_TEST_TIMEOUT_SECS)
+}
+
+fn remote_dry_run(
+    job: &JobDefinition,
+    timeout_secs: u32,
+    rsync: &dyn RsyncClient,
+) -> Result<RsyncResult, RsyncError> {
     // Build a minimal dry-run command to test connectivity
     use crate::services::command_builder;
 
     let mut test_job = job.clone();
     test_job.options.core_transfer.dry_run = true;
+    // The run creates the destination first, so the dry run must not trip over it
+    if job.run_policy.create_destination && rsync.version().is_ok_and(|v| supports_mkpath(&v)) {
+        test_job.options = options_with_mkpath(&test_job.options);
+    }
+
+    // Bound the connect so an unreachable host fails fast: ssh takes
+    // ConnectTimeout (a custom ssh command is left as written), a daemon
+    // takes --contimeout
+    let locations = [&job.transfer.source, &job.transfer.destination];
+    if locations
+        .iter()
+        .any(|loc| matches!(loc, StorageLocation::RemoteSsh { .. }))
+    {
+        let mut ssh = test_job.ssh_config.take().unwrap_or_default();
+        ssh.connect_timeout = Some(timeout_secs);
+        test_job.ssh_config = Some(ssh);
+    }
+    let custom_args = &mut test_job.options.advanced.custom_args;
+    if locations
+        .iter()
+        .any(|loc| matches!(loc, StorageLocation::RemoteRsync { .. }))
+        && !custom_args
+            .iter()
+            .any(|arg| arg.starts_with("--contimeout"))
+    {
+        custom_args.push(format!("--contimeout={}", timeout_secs));
+    }
 
     let args = command_builder::build_rsync_args(
         &test_job.transfer.source,
         &test_job.transfer.destination,
         &test_job.options,
         test_job.ssh_config.as_ref(),
-        None,
+        &[],
+        false,
         false,
     );
-    match rsync.dry_run(&args) {
-        Ok(result) if result.exit_code == 0 => ValidationCheck {
-            check_type: CheckType::SshConnectivity,
-            passed: true,
-            message: "SSH connectivity test passed (dry-run succeeded)".to_string(),
-            severity: CheckSeverity::Error,
-        },
-        Ok(result) => ValidationCheck {
-            check_type: CheckType::SshConnectivity,
-            passed: false,
-            message: format!(
+    rsync.dry_run(&args)
+}
+
+fn check_ssh_connectivity(
+    dry_run: &Result<RsyncResult, RsyncError>,
+    timeout_secs: u32,
+) -> ValidationCheck {
+    let (exit_code, detail) = match dry_run {
+        Ok(result) if result.exit_code == 0 => {
+            return ValidationCheck {
+                check_type: CheckType::SshConnectivity,
+                passed: true,
+                message: "SSH connectivity test passed (dry-run succeeded)".to_string(),
+                severity: CheckSeverity::Error,
+            }
+        }
+        Ok(result) => (
+            Some(result.exit_code),
+            result
+                .stderr
+                .lines()
+                .next()
+                .unwrap_or("unknown error")
+                .to_string(),
+        ),
+        Err(RsyncError::ProcessError { message, exit_code }) => (
+            *exit_code,
+            message
+                .lines()
+                .next()
+                .unwrap_or("unknown error")
+                .to_string(),
+        ),
+        Err(e) => (None, e.to_string()),
+    };
+
+    let lower = detail.to_lowercase();
+    let message = if exit_code == Some(DAEMON_CONNECT_TIMEOUT_EXIT)
+        || lower.contains("timed out")
+        || lower.contains("timeout waiting")
+    {
+        format!(
+            "SSH connectivity test timed out: no connection within {}s: {}",
+            timeout_secs, detail
+        )
+    } else if lower.contains("connection refused") {
+        format!(
+            "SSH connectivity test failed: connection refused: {}",
+            detail
+        )
+    } else {
+        match exit_code {
+            Some(code) => format!(
                 "SSH connectivity test failed (exit code {}): {}",
-                result.exit_code,
-                result.stderr.lines().next().unwrap_or("unknown error")
+                code, detail
             ),
-            severity: CheckSeverity::Error,
-        },
-        Err(e) => ValidationCheck {
-            check_type: CheckType::SshConnectivity,
-            passed: false,
-            message: format!("SSH connectivity test failed: {}", e),
-            severity: CheckSeverity::Error,
-        },
+            None => format!("SSH connectivity test failed: {}", detail),
+        }
+    };
+    ValidationCheck {
+        check_type: CheckType::SshConnectivity,
+        passed: false,
+        message,
+        severity: CheckSeverity::Error,
     }
 }
 
-fn format_bytes(bytes: u64) -> String {
-    const KB: u64 = 1024;
-    const MB: u64 = KB * 1024;
-    const GB: u64 = MB * 1024;
-
-    if bytes >= GB {
-        format!("{:.1} GB", bytes as f64 / GB as f64)
-    } else if bytes >= MB {
-        format!("{:.1} MB", bytes as f64 / MB as f64)
-    } else if bytes >= KB {
-        format!("{:.1} KB", bytes as f64 / KB as f64)
+/// Dry-run a mirror job with `--delete` and compare the deletions rsync
+/// would make with the number of entries already at the destination.
+///
+/// `-ii` makes rsync itemize unchanged entries as well, so everything that
+/// is not newly created (deletions included) is on the destination. Returns
+/// `None` for jobs that delete nothing.
+pub fn check_mirror_deletions(
+    job: &JobDefinition,
+    check: &MirrorDeletionCheck,
+    rsync: &dyn RsyncClient,
+) -> Option<ValidationCheck> {
+    if !matches!(job.transfer.backup_mode, BackupMode::Mirror) || !job.options.file_handling.delete
+    {
+        return None;
+    }
+
+    let mut options = job.options.clone();
+    options.core_transfer.dry_run = true;
+    options.output.progress = false;
+    options.output.stats = false;
+    options.output.itemize_changes = false;
+    options
+        .advanced
+        .custom_args
+        .push(ITEMIZE_ALL_ARG.to_string());
+    let args = build_rsync_args(
+        &job.transfer.source,
+        &job.transfer.destination,
+        &options,
+        job.ssh_config.as_ref(),
+        &[],
+        check.auto_trailing_slash,
+        check.relative_paths,
+    );
+
+    let output = match rsync.dry_run(&args) {
+        Ok(result) if result.exit_code == 0 => result.stdout,
+        Ok(result) => {
+            return Some(deletion_check(
+                false,
+                format!(
+                    "Could not estimate deletions (exit code {})",
+                    result.exit_code
+                ),
+            ))
+        }
+        Err(e) => {
+            return Some(deletion_check(
+                false,
+                format!("Could not estimate deletions: {}", e),
+            ))
+        }
+    };
+
+    let (deleted, existing) = count_deletions(&output);
+    let percent = if existing == 0 {
+        0.0
+    } else {
+        deleted as f64 * 100.0 / existing as f64
+    };
+    let message = format!(
+        "Mirror would delete {} of {} destination entries ({:.1}%)",
+        deleted, existing, percent
+    );
+    Some(if percent > check.max_delete_percent {
+        deletion_check(
+            false,
+            format!("{}, above the {}% limit", message, check.max_delete_percent),
+        )
     } else {
-        format!("{} B", bytes)
+        deletion_check(true, message)
+    })
+}
+
+/// Count `*deleting` lines and entries already on the destination in
+/// `-ii` dry-run output.
+fn count_deletions(output: &str) -> (u64, u64) {
+    let mut deleted = 0;
+    let mut existing = 0;
+    for change in output.lines().filter_map(parse_itemize_line) {
+        // The transfer root itself
+        if change.path == "./" {
+            continue;
+        }
+        if change.transfer_type == TransferType::Message {
+            deleted += 1;
+            existing += 1;
+        } else if !change.differences.contains(&DifferenceKind::NewlyCreated) {
+            existing += 1;
+        }
+    }
+    (deleted, existing)
+}
+
+fn deletion_check(passed: bool, message: String) -> ValidationCheck {
+    ValidationCheck {
+        check_type: CheckType::DeletionEstimate,
+        passed,
+        message,
+        severity: CheckSeverity::Warning,
     }
 }
 
+/// Warn when the dry run failed because the remote destination directory
+/// is missing and the job is not set to create it.
+fn check_remote_destination_missing(
+    job: &JobDefinition,
+    dry_run: &Result<RsyncResult, RsyncError>,
+) -> Option<ValidationCheck> {
+    if job.run_policy.create_destination || !is_remote(&job.transfer.destination) {
+        return None;
+    }
+    let stderr = match dry_run {
+        Ok(result) if result.exit_code != 0 => result.stderr.clone(),
+        Ok(_) => return None,
+        Err(e) => e.to_string(),
+    };
+    let missing = stderr.contains("No such file or directory")
+        && (stderr.contains("mkdir") || stderr.contains("change_dir"));
+    missing.then(|| ValidationCheck {
+        check_type: CheckType::DestinationWritable,
+        passed: false,
+        message: "Remote destination directory likely does not exist: enable \"Create destination\" to create it before transferring".to_string(),
+        severity: CheckSeverity::Warning,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::models::job::*;
     use crate::file_system::FsError;
     use crate::rsync_client::{RsyncError, RsyncResult};
+    use crate::tests::test_file_system::TestFileSystem;
+    use std::cell::RefCell;
     use std::path::{Path, PathBuf};
     use uuid::Uuid;
 
@@ -273,6 +814,11 @@ mod tests {
             self
         }
 
+        fn with_file(mut self, path: &str) -> Self {
+            self.existing_files.push(path.to_string());
+            self
+        }
+
         fn with_space(mut self, available: u64, source_size: u64) -> Self {
             self.available_space_bytes = available;
             self.dir_size_bytes = source_size;
@@ -294,14 +840,29 @@ mod tests {
         fn is_symlink(&self, _: &Path) -> bool {
             false
         }
+        fn modified(&self, _: &Path) -> Result<chrono::DateTime<chrono::Utc>, FsError> {
+            Ok(chrono::DateTime::UNIX_EPOCH)
+        }
         fn create_dir_all(&self, _: &Path) -> Result<(), FsError> {
             Ok(())
         }
         fn remove_dir_all(&self, _: &Path) -> Result<(), FsError> {
             Ok(())
         }
-        fn read_dir(&self, _: &Path) -> Result<Vec<PathBuf>, FsError> {
-            Ok(vec![])
+        fn remove_file(&self, _: &Path) -> Result<(), FsError> {
+            Ok(())
+        }
+        fn read_dir(&self, path: &Path) -> Result<Vec<PathBuf>, FsError> {
+            if !self.is_dir(path) {
+                return Err(FsError::NotFound(path.display().to_string()));
+            }
+            Ok(self
+                .existing_dirs
+                .iter()
+                .chain(&self.existing_files)
+                .map(PathBuf::from)
+                .filter(|entry| entry.parent() == Some(path))
+                .collect())
         }
         fn read_to_string(&self, _: &Path) -> Result<String, FsError> {
             Ok(String::new())
@@ -324,9 +885,15 @@ mod tests {
         fn dir_size(&self, _: &Path) -> Result<u64, FsError> {
             Ok(self.dir_size_bytes)
         }
+        fn unique_dir_size(&self, _: &Path) -> Result<u64, FsError> {
+            Ok(self.dir_size_bytes)
+        }
         fn copy_file(&self, _: &Path, _: &Path) -> Result<(), FsError> {
             Ok(())
         }
+        fn rename(&self, _: &Path, _: &Path) -> Result<(), FsError> {
+            Ok(())
+        }
         fn hard_link(&self, _: &Path, _: &Path) -> Result<(), FsError> {
             Ok(())
         }
@@ -336,11 +903,18 @@ mod tests {
         fn filesystem_type(&self, _: &Path) -> Option<String> {
             None
         }
+        fn mount_point(&self, _: &Path) -> Option<PathBuf> {
+            Some(PathBuf::from("/"))
+        }
+        fn volume_label(&self, _: &Path) -> Option<String> {
+            None
+        }
     }
 
     struct MockRsync {
         installed: bool,
         dry_run_exit: i32,
+        version_output: &'static str,
     }
 
     impl MockRsync {
@@ -348,18 +922,24 @@ mod tests {
             Self {
                 installed: true,
                 dry_run_exit: 0,
+                version_output: "rsync version 3.2.7",
             }
         }
         fn not_installed() -> Self {
             Self {
                 installed: false,
                 dry_run_exit: 1,
+                version_output: "",
             }
         }
         fn with_dry_run_exit(mut self, code: i32) -> Self {
             self.dry_run_exit = code;
             self
         }
+        fn with_version(mut self, output: &'static str) -> Self {
+            self.version_output = output;
+            self
+        }
     }
 
     impl RsyncClient for MockRsync {
@@ -388,7 +968,7 @@ mod tests {
         }
         fn version(&self) -> Result<String, RsyncError> {
             if self.installed {
-                Ok("rsync version 3.2.7".to_string())
+                Ok(self.version_output.to_string())
             } else {
                 Err(RsyncError::RsyncNotFound)
             }
@@ -411,6 +991,9 @@ mod tests {
             },
             options: RsyncOptions::default(),
             ssh_config: None,
+            appearance: JobAppearance::default(),
+            run_policy: JobRunPolicy::default(),
+            safety: JobSafety::default(),
             schedule: None,
             enabled: true,
             created_at: chrono::Utc::now(),
@@ -430,11 +1013,195 @@ mod tests {
         job
     }
 
+    struct MissingDirRsync;
+
+    impl RsyncClient for MissingDirRsync {
+        fn execute(&self, _args: &[String]) -> Result<RsyncResult, RsyncError> {
+            unreachable!()
+        }
+        fn dry_run(&self, args: &[String]) -> Result<RsyncResult, RsyncError> {
+            // rsync creates the path itself when --mkpath is passed
+            let mkpath = args.iter().any(|a| a == "--mkpath");
+            Ok(RsyncResult {
+                exit_code: if mkpath { 0 } else { 11 },
+                stdout: String::new(),
+                stderr: if mkpath {
+                    String::new()
+                } else {
+                    "rsync: [Receiver] mkdir \"/backup\" failed: No such file or directory (2)"
+                        .to_string()
+                },
+                command: "rsync --dry-run".to_string(),
+            })
+        }
+        fn version(&self) -> Result<String, RsyncError> {
+            Ok("rsync version 3.2.7".to_string())
+        }
+    }
+
+    /// Answers dry runs with canned `-ii` output and records the arguments.
+    struct ItemizeRsync {
+        stdout: String,
+        args: RefCell<Vec<String>>,
+    }
+
+    impl ItemizeRsync {
+        /// `existing` unchanged files, `created` new files, `deleted` deletions.
+        fn new(existing: usize, created: usize, deleted: usize) -> Self {
+            let mut lines = vec![".d          ./".to_string()];
+            lines.extend((0..existing).map(|i| format!(".f          keep/{}.txt", i)));
+            lines.extend((0..created).map(|i| format!(">f+++++++++ new/{}.txt", i)));
+            lines.extend((0..deleted).map(|i| format!("*deleting   old/{}.txt", i)));
+            Self {
+                stdout: lines.join("\n"),
+                args: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl RsyncClient for ItemizeRsync {
+        fn execute(&self, _args: &[String]) -> Result<RsyncResult, RsyncError> {
+            unreachable!()
+        }
+        fn dry_run(&self, args: &[String]) -> Result<RsyncResult, RsyncError> {
+            *self.args.borrow_mut() = args.to_vec();
+            Ok(RsyncResult {
+                exit_code: 0,
+                stdout: self.stdout.clone(),
+                stderr: String::new(),
+                command: "rsync --dry-run".to_string(),
+            })
+        }
+        fn version(&self) -> Result<String, RsyncError> {
+            Ok("rsync version 3.2.7".to_string())
+        }
+    }
+
+    fn deleting_mirror_job() -> JobDefinition {
+        let mut job = local_job();
+        job.options.file_handling.delete = true;
+        job
+    }
+
+    fn mirror_check(max_delete_percent: f64) -> MirrorDeletionCheck {
+        MirrorDeletionCheck {
+            max_delete_percent,
+            auto_trailing_slash: true,
+            relative_paths: false,
+        }
+    }
+
+    #[test]
+    fn deletion_estimate_below_threshold_passes() {
+        // 5 deletions out of 95 kept + 5 deleted entries = 5%
+        let rsync = ItemizeRsync::new(95, 40, 5);
+        let check =
+            check_mirror_deletions(&deleting_mirror_job(), &mirror_check(10.0), &rsync).unwrap();
+
+        assert_eq!(check.check_type, CheckType::DeletionEstimate);
+        assert!(check.passed);
+        assert_eq!(check.severity, CheckSeverity::Warning);
+        assert!(check
+            .message
+            .contains("delete 5 of 100 destination entries (5.0%)"));
+
+        let args = rsync.args.borrow();
+        assert!(args.iter().any(|a| a == "-ii"));
+        assert!(args.iter().any(|a| a == "--dry-run"));
+        assert!(args.iter().any(|a| a == "--delete"));
+    }
+
+    #[test]
+    fn deletion_estimate_above_threshold_warns() {
+        // 30 deletions out of 70 kept + 30 deleted entries = 30%
+        let rsync = ItemizeRsync::new(70, 0, 30);
+        let check =
+            check_mirror_deletions(&deleting_mirror_job(), &mirror_check(10.0), &rsync).unwrap();
+
+        assert!(!check.passed);
+        assert_eq!(check.severity, CheckSeverity::Warning);
+        assert!(check.message.contains("delete 30 of 100"));
+        assert!(check.message.contains("above the 10% limit"));
+    }
+
+    #[test]
+    fn deletion_estimate_skips_jobs_that_do_not_delete() {
+        let rsync = ItemizeRsync::new(1, 0, 1);
+        assert_eq!(
+            check_mirror_deletions(&local_job(), &mirror_check(10.0), &rsync),
+            None
+        );
+
+        let mut versioned = deleting_mirror_job();
+        versioned.transfer.backup_mode = BackupMode::Versioned {
+            backup_dir: "/dest/.versions".to_string(),
+        };
+        assert_eq!(
+            check_mirror_deletions(&versioned, &mirror_check(10.0), &rsync),
+            None
+        );
+        assert!(rsync.args.borrow().is_empty());
+    }
+
+    #[test]
+    fn deletion_estimate_runs_only_when_enabled() {
+        let fs = MockFs::new().with_dir("/source").with_dir("/dest");
+        let rsync = ItemizeRsync::new(1, 0, 9);
+        let has_estimate = |result: &PreflightResult| {
+            result
+                .checks
+                .iter()
+                .any(|c| c.check_type == CheckType::DeletionEstimate)
+        };
+
+        let off = run_preflight(&deleting_mirror_job(), None, None, &fs, &rsync);
+        assert!(!has_estimate(&off));
+
+        let check = mirror_check(50.0);
+        let on = run_preflight(&deleting_mirror_job(), None, Some(&check), &fs, &rsync);
+        assert!(has_estimate(&on));
+        // A deletion warning does not fail preflight
+        assert!(on.overall_pass);
+    }
+
+    #[test]
+    fn deletion_estimate_skips_unreachable_remote() {
+        let fs = MockFs::new().with_dir("/source");
+        let rsync = MockRsync::installed().with_dry_run_exit(255);
+        let mut job = remote_job();
+        job.options.file_handling.delete = true;
+
+        let result = run_preflight(&job, None, Some(&mirror_check(10.0)), &fs, &rsync);
+
+        assert!(!result
+            .checks
+            .iter()
+            .any(|c| c.check_type == CheckType::DeletionEstimate));
+    }
+
+    #[test]
+    fn missing_remote_destination_suggests_create_destination() {
+        let fs = MockFs::new().with_dir("/source");
+        let result = run_preflight(&remote_job(), None, None, &fs, &MissingDirRsync);
+        assert!(result.checks.iter().any(|c| {
+            c.check_type == CheckType::DestinationWritable
+                && !c.passed
+                && c.severity == CheckSeverity::Warning
+                && c.message.contains("Create destination")
+        }));
+
+        let mut job = remote_job();
+        job.run_policy.create_destination = true;
+        let result = run_preflight(&job, None, None, &fs, &MissingDirRsync);
+        assert!(result.overall_pass);
+        assert!(result.checks.iter().all(|c| c.passed));
+    }
+
     #[test]
     fn all_pass_for_local_job() {
         let fs = MockFs::new().with_dir("/source").with_dir("/dest");
         let rsync = MockRsync::installed();
-        let result = run_preflight(&local_job(), &fs, &rsync);
+        let result = run_preflight(&local_job(), None, None, &fs, &rsync);
         assert!(result.overall_pass);
         // Should have 4 checks (no SSH for local)
         assert_eq!(result.checks.len(), 4);
@@ -444,7 +1211,7 @@ mod tests {
     fn rsync_not_installed_fails() {
         let fs = MockFs::new().with_dir("/source").with_dir("/dest");
         let rsync = MockRsync::not_installed();
-        let result = run_preflight(&local_job(), &fs, &rsync);
+        let result = run_preflight(&local_job(), None, None, &fs, &rsync);
         assert!(!result.overall_pass);
 
         let rsync_check = result
@@ -459,7 +1226,7 @@ mod tests {
     fn source_not_found_fails() {
         let fs = MockFs::new().with_dir("/dest"); // no /source
         let rsync = MockRsync::installed();
-        let result = run_preflight(&local_job(), &fs, &rsync);
+        let result = run_preflight(&local_job(), None, None, &fs, &rsync);
         assert!(!result.overall_pass);
 
         let src_check = result
@@ -474,7 +1241,7 @@ mod tests {
     fn destination_missing_but_parent_exists() {
         let fs = MockFs::new().with_dir("/source").with_dir("/"); // / exists but not /dest
         let rsync = MockRsync::installed();
-        let result = run_preflight(&local_job(), &fs, &rsync);
+        let result = run_preflight(&local_job(), None, None, &fs, &rsync);
         assert!(result.overall_pass);
 
         let dst_check = result
@@ -490,7 +1257,7 @@ mod tests {
     fn destination_and_parent_missing_fails() {
         let fs = MockFs::new().with_dir("/source"); // neither /dest nor / marked as existing dir
         let rsync = MockRsync::installed();
-        let result = run_preflight(&local_job(), &fs, &rsync);
+        let result = run_preflight(&local_job(), None, None, &fs, &rsync);
         assert!(!result.overall_pass);
 
         let dst_check = result
@@ -508,7 +1275,7 @@ mod tests {
             .with_dir("/dest")
             .with_space(500, 1000); // 500 available, 1000 needed
         let rsync = MockRsync::installed();
-        let result = run_preflight(&local_job(), &fs, &rsync);
+        let result = run_preflight(&local_job(), None, None, &fs, &rsync);
         assert!(!result.overall_pass);
 
         let space_check = result
@@ -520,11 +1287,89 @@ mod tests {
         assert!(space_check.message.contains("Insufficient"));
     }
 
+    #[test]
+    fn pre_run_space_check_aborts_when_margin_does_not_fit() {
+        // 1000 bytes of source plus 10% needs 1100
+        let fs = MockFs::new().with_space(1050, 1000);
+
+        let reason = insufficient_space(&local_job(), 10, &fs).unwrap();
+
+        assert!(reason.starts_with("Insufficient space on destination"));
+        assert!(reason.contains("10%"));
+    }
+
+    #[test]
+    fn pre_run_space_check_proceeds_when_space_suffices() {
+        let fs = MockFs::new().with_space(1100, 1000);
+
+        assert_eq!(insufficient_space(&local_job(), 10, &fs), None);
+        assert_eq!(
+            insufficient_space(&local_job(), 0, &MockFs::new().with_space(1000, 1000)),
+            None
+        );
+    }
+
+    #[test]
+    fn pre_run_space_check_skips_remote_and_dry_runs() {
+        let fs = MockFs::new().with_space(0, 1000);
+        assert_eq!(insufficient_space(&remote_job(), 10, &fs), None);
+
+        let mut dry_run = local_job();
+        dry_run.options.core_transfer.dry_run = true;
+        assert_eq!(insufficient_space(&dry_run, 10, &fs), None);
+    }
+
+    fn expecting_data(mut job: JobDefinition) -> JobDefinition {
+        job.safety.expect_nonempty_destination = true;
+        job
+    }
+
+    #[test]
+    fn empty_destination_aborts_when_data_expected() {
+        let fs = MockFs::new().with_dir("/dest");
+
+        let reason = unexpectedly_empty_destination(&expecting_data(local_job()), &fs).unwrap();
+
+        assert!(reason.starts_with("Destination /dest is empty"));
+        assert!(reason.contains("mounted"));
+    }
+
+    #[test]
+    fn missing_destination_aborts_when_data_expected() {
+        let job = expecting_data(local_job());
+
+        assert!(unexpectedly_empty_destination(&job, &MockFs::new()).is_some());
+    }
+
+    #[test]
+    fn nonempty_destination_proceeds() {
+        let fs = MockFs::new().with_dir("/dest").with_file("/dest/photo.jpg");
+
+        assert_eq!(
+            unexpectedly_empty_destination(&expecting_data(local_job()), &fs),
+            None
+        );
+    }
+
+    #[test]
+    fn empty_destination_check_is_opt_in_and_local_only() {
+        let fs = MockFs::new().with_dir("/dest");
+        assert_eq!(unexpectedly_empty_destination(&local_job(), &fs), None);
+        assert_eq!(
+            unexpectedly_empty_destination(&expecting_data(remote_job()), &fs),
+            None
+        );
+
+        let mut dry_run = expecting_data(local_job());
+        dry_run.options.core_transfer.dry_run = true;
+        assert_eq!(unexpectedly_empty_destination(&dry_run, &fs), None);
+    }
+
     #[test]
     fn remote_job_includes_ssh_check() {
         let fs = MockFs::new().with_dir("/source");
         let rsync = MockRsync::installed();
-        let result = run_preflight(&remote_job(), &fs, &rsync);
+        let result = run_preflight(&remote_job(), None, None, &fs, &rsync);
 
         assert_eq!(result.checks.len(), 5); // includes SSH
         let ssh_check = result
@@ -539,7 +1384,7 @@ mod tests {
     fn ssh_connection_failure() {
         let fs = MockFs::new().with_dir("/source");
         let rsync = MockRsync::installed().with_dry_run_exit(255);
-        let result = run_preflight(&remote_job(), &fs, &rsync);
+        let result = run_preflight(&remote_job(), None, None, &fs, &rsync);
         assert!(!result.overall_pass);
 
         let ssh_check = result
@@ -549,6 +1394,116 @@ mod tests {
             .unwrap();
         assert!(!ssh_check.passed);
         assert!(ssh_check.message.contains("Connection refused"));
+        assert!(ssh_check.message.contains("connection refused:"));
+        assert!(!ssh_check.message.contains("timed out"));
+    }
+
+    /// Fails every dry run the way `ProcessRsyncClient` reports an
+    /// unreachable host, recording the arguments it was given.
+    struct TimeoutRsync {
+        args: RefCell<Vec<String>>,
+    }
+
+    impl TimeoutRsync {
+        fn new() -> Self {
+            Self {
+                args: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl RsyncClient for TimeoutRsync {
+        fn execute(&self, _args: &[String]) -> Result<RsyncResult, RsyncError> {
+            unreachable!()
+        }
+        fn dry_run(&self, args: &[String]) -> Result<RsyncResult, RsyncError> {
+            *self.args.borrow_mut() = args.to_vec();
+            Err(RsyncError::ProcessError {
+                message: "ssh: connect to host server port 22: Connection timed out\nrsync: connection unexpectedly closed".to_string(),
+                exit_code: Some(255),
+            })
+        }
+        fn version(&self) -> Result<String, RsyncError> {
+            Ok("rsync version 3.2.7".to_string())
+        }
+    }
+
+    #[test]
+    fn ssh_connection_timeout_is_reported_distinctly() {
+        let fs = MockFs::new().with_dir("/source");
+        let rsync = TimeoutRsync::new();
+        let result = run_preflight(&remote_job(), None, None, &fs, &rsync);
+        assert!(!result.overall_pass);
+
+        let ssh_check = result
+            .checks
+            .iter()
+            .find(|c| c.check_type == CheckType::SshConnectivity)
+            .unwrap();
+        assert!(!ssh_check.passed);
+        assert!(ssh_check
+            .message
+            .contains("timed out: no connection within 10s"));
+        assert!(!ssh_check.message.contains("refused"));
+    }
+
+    #[test]
+    fn ssh_connectivity_test_sets_connect_timeout() {
+        let fs = MockFs::new().with_dir("/source");
+        let rsync = TimeoutRsync::new();
+        run_preflight(&remote_job(), None, None, &fs, &rsync);
+        assert!(rsync
+            .args
+            .borrow()
+            .iter()
+            .any(|a| a == "ssh -o ConnectTimeout=10"));
+
+        let mut job = remote_job();
+        job.ssh_config = Some(SshConfig {
+            port: 2222,
+            connect_timeout: Some(3),
+            ..SshConfig::default()
+        });
+        let result = run_preflight(&job, None, None, &fs, &rsync);
+        assert!(rsync
+            .args
+            .borrow()
+            .iter()
+            .any(|a| a == "ssh -p 2222 -o ConnectTimeout=3"));
+        assert!(result
+            .checks
+            .iter()
+            .any(|c| c.message.contains("no connection within 3s")));
+    }
+
+    #[test]
+    fn daemon_connectivity_test_sets_contimeout() {
+        let fs = MockFs::new().with_dir("/source");
+        let rsync = TimeoutRsync::new();
+        let mut job = local_job();
+        job.transfer.destination = StorageLocation::RemoteRsync {
+            host: "nas".to_string(),
+            module: "backup".to_string(),
+            path: "/".to_string(),
+            username: None,
+            password_secret: None,
+        };
+        run_preflight(&job, None, None, &fs, &rsync);
+        assert!(rsync.args.borrow().iter().any(|a| a == "--contimeout=10"));
+        assert!(!rsync.args.borrow().iter().any(|a| a == "-e"));
+
+        job.options
+            .advanced
+            .custom_args
+            .push("--contimeout=30".to_string());
+        run_preflight(&job, None, None, &fs, &rsync);
+        let args = rsync.args.borrow();
+        assert_eq!(
+            args.iter()
+                .filter(|a| a.starts_with("--contimeout"))
+                .count(),
+            1
+        );
     }
 
     #[test]
@@ -563,7 +1518,7 @@ mod tests {
         };
         let fs = MockFs::new().with_dir("/dest");
         let rsync = MockRsync::installed();
-        let result = run_preflight(&job, &fs, &rsync);
+        let result = run_preflight(&job, None, None, &fs, &rsync);
 
         let src_check = result
             .checks
@@ -575,11 +1530,414 @@ mod tests {
     }
 
     #[test]
-    fn format_bytes_display() {
-        assert_eq!(format_bytes(500), "500 B");
-        assert_eq!(format_bytes(1024), "1.0 KB");
-        assert_eq!(format_bytes(1024 * 1024), "1.0 MB");
-        assert_eq!(format_bytes(1024 * 1024 * 1024), "1.0 GB");
-        assert_eq!(format_bytes(2 * 1024 * 1024 * 1024), "2.0 GB");
+    fn preflight_all_reports_each_job() {
+        let passing = local_job();
+        let mut missing_source = local_job();
+        missing_source.transfer.source = StorageLocation::Local {
+            path: "/missing".to_string(),
+        };
+        let fs = MockFs::new().with_dir("/source").with_dir("/dest");
+        let rsync = MockRsync::installed();
+
+        let results = run_preflight_all(
+            &[passing.clone(), missing_source.clone()],
+            |_| None,
+            None,
+            &fs,
+            &rsync,
+        );
+
+        assert_eq!(results.len(), 2);
+        let pass_of = |id| {
+            results
+                .iter()
+                .find(|r| r.job_id == id)
+                .unwrap()
+                .overall_pass
+        };
+        assert!(pass_of(passing.id));
+        assert!(!pass_of(missing_source.id));
+        for result in &results {
+            let rsync_check = result
+                .checks
+                .iter()
+                .find(|c| c.check_type == CheckType::RsyncInstalled)
+                .unwrap();
+            assert!(rsync_check.passed);
+        }
+    }
+
+    #[test]
+    fn preflight_all_orders_worst_first() {
+        let passing = local_job();
+        let mut missing_source = local_job();
+        missing_source.transfer.source = StorageLocation::Local {
+            path: "/missing".to_string(),
+        };
+        let fs = MockFs::new().with_dir("/source").with_dir("/dest");
+        let rsync = MockRsync::installed();
+
+        let results = run_preflight_all(
+            &[passing.clone(), missing_source.clone()],
+            |_| None,
+            None,
+            &fs,
+            &rsync,
+        );
+
+        assert_eq!(results[0].job_id, missing_source.id);
+        assert_eq!(severity_rank(&results[0]), 2);
+        assert_eq!(results[1].job_id, passing.id);
+        assert_eq!(severity_rank(&results[1]), 0);
+    }
+
+    #[test]
+    fn preflight_all_empty() {
+        let results =
+            run_preflight_all(&[], |_| None, None, &MockFs::new(), &MockRsync::installed());
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn severity_rank_counts_failed_warnings() {
+        let result = PreflightResult {
+            job_id: Uuid::new_v4(),
+            checks: vec![ValidationCheck {
+                check_type: CheckType::DiskSpace,
+                passed: false,
+                message: "unknown".to_string(),
+                severity: CheckSeverity::Warning,
+            }],
+            overall_pass: true,
+        };
+        assert_eq!(severity_rank(&result), 1);
+    }
+
+    fn mount_location(label: Option<&str>) -> StorageLocation {
+        StorageLocation::Mount {
+            mountpoint: "/mnt/backup".to_string(),
+            path: "/mnt/backup/laptop".to_string(),
+            expected_fs_label: label.map(String::from),
+        }
+    }
+
+    #[test]
+    fn verify_mount_passes_when_mounted() {
+        let fs = TestFileSystem::new().with_mount("/mnt/backup", Some("BACKUP"));
+        assert!(verify_mount(&mount_location(None), &fs).is_ok());
+        assert!(verify_mount(&mount_location(Some("BACKUP")), &fs).is_ok());
+    }
+
+    #[test]
+    fn verify_mount_fails_on_empty_mountpoint() {
+        let fs = TestFileSystem::new().with_dir("/mnt/backup");
+        let err = verify_mount(&mount_location(None), &fs).unwrap_err();
+        assert!(err.contains("Nothing is mounted"));
+    }
+
+    #[test]
+    fn verify_mount_fails_when_mountpoint_missing() {
+        let fs = TestFileSystem::new();
+        let err = verify_mount(&mount_location(None), &fs).unwrap_err();
+        assert!(err.contains("does not exist"));
+    }
+
+    #[test]
+    fn verify_mount_fails_on_label_mismatch() {
+        let fs = TestFileSystem::new().with_mount("/mnt/backup", Some("OTHER"));
+        let err = verify_mount(&mount_location(Some("BACKUP")), &fs).unwrap_err();
+        assert!(err.contains("expected \"BACKUP\""));
+
+        let unlabeled = TestFileSystem::new().with_mount("/mnt/backup", None);
+        assert!(verify_mount(&mount_location(Some("BACKUP")), &unlabeled).is_err());
+    }
+
+    #[test]
+    fn verify_mount_rejects_path_outside_mountpoint() {
+        let fs = TestFileSystem::new().with_mount("/mnt/backup", None);
+        let loc = StorageLocation::Mount {
+            mountpoint: "/mnt/backup".to_string(),
+            path: "/mnt/backup-old/laptop".to_string(),
+            expected_fs_label: None,
+        };
+        let err = verify_mount(&loc, &fs).unwrap_err();
+        assert!(err.contains("not inside mountpoint"));
+    }
+
+    #[test]
+    fn verify_mount_ignores_other_locations() {
+        let fs = TestFileSystem::new();
+        let loc = StorageLocation::Local {
+            path: "/data".to_string(),
+        };
+        assert!(verify_mount(&loc, &fs).is_ok());
+    }
+
+    #[test]
+    fn preflight_checks_mount_destination() {
+        let mut job = local_job();
+        job.transfer.destination = mount_location(None);
+        let rsync = MockRsync::installed();
+
+        let mounted = TestFileSystem::new()
+            .with_dir("/source")
+            .with_mount("/mnt/backup", None)
+            .with_dir("/mnt/backup/laptop");
+        let result = run_preflight(&job, None, None, &mounted, &rsync);
+        assert!(result.overall_pass);
+        assert!(!result
+            .checks
+            .iter()
+            .any(|c| c.check_type == CheckType::SshConnectivity));
+        assert_eq!(missing_mount(&job, &mounted), None);
+
+        let empty = TestFileSystem::new()
+            .with_dir("/source")
+            .with_dir("/mnt/backup");
+        let result = run_preflight(&job, None, None, &empty, &rsync);
+        assert!(!result.overall_pass);
+        let mount_check = result
+            .checks
+            .iter()
+            .find(|c| c.check_type == CheckType::MountPresent)
+            .unwrap();
+        assert!(!mount_check.passed);
+        assert!(missing_mount(&job, &empty).is_some());
+    }
+
+    #[test]
+    fn destination_change_warns_only_when_locality_changes() {
+        let local = StorageLocation::Local {
+            path: "/dst/".to_string(),
+        };
+        let other_local = StorageLocation::Local {
+            path: "/mnt/dst/".to_string(),
+        };
+        let remote = StorageLocation::RemoteSsh {
+            user: "backup".to_string(),
+            host: "server".to_string(),
+            port: 22,
+            path: "/dst/".to_string(),
+            identity_file: None,
+        };
+
+        assert!(destination_change_warnings(&local, &other_local).is_empty());
+        let to_remote = destination_change_warnings(&local, &remote);
+        assert_eq!(to_remote.len(), 1);
+        assert!(to_remote[0].message.contains("now remote"));
+        let to_local = destination_change_warnings(&remote, &local);
+        assert!(to_local[0].message.contains("now local"));
+    }
+
+    fn snapshot_job(dest: &str) -> JobDefinition {
+        let mut job = local_job();
+        job.transfer.destination = StorageLocation::Local {
+            path: dest.to_string(),
+        };
+        job.transfer.backup_mode = BackupMode::Snapshot {
+            retention_policy: RetentionPolicy::default(),
+        };
+        job
+    }
+
+    #[test]
+    fn snapshot_on_same_filesystem_passes() {
+        let fs = TestFileSystem::new()
+            .with_dir("/backups/snaps/2026-10-01_020000")
+            .with_filesystem_type("/backups", "ext4");
+
+        let check = check_snapshot_filesystem(
+            &snapshot_job("/backups/snaps"),
+            Some("/backups/snaps/2026-10-01_020000"),
+            &fs,
+        )
+        .unwrap();
+        assert!(check.passed);
+        assert_eq!(check.check_type, CheckType::SnapshotFilesystem);
+    }
+
+    #[test]
+    fn snapshot_on_different_filesystem_type_warns() {
+        let fs = TestFileSystem::new()
+            .with_dir("/backups/snaps")
+            .with_dir("/old/snaps/2026-10-01_020000")
+            .with_filesystem_type("/backups", "ext4")
+            .with_filesystem_type("/old", "exfat");
+
+        let check = check_snapshot_filesystem(
+            &snapshot_job("/backups/snaps"),
+            Some("/old/snaps/2026-10-01_020000"),
+            &fs,
+        )
+        .unwrap();
+        assert!(!check.passed);
+        assert_eq!(check.severity, CheckSeverity::Warning);
+        assert!(check.message.contains("different filesystem"));
+    }
+
+    #[test]
+    fn snapshot_on_different_mount_of_same_type_warns() {
+        let fs = TestFileSystem::new()
+            .with_mount("/mnt/new", None)
+            .with_filesystem_type("/", "ext4");
+
+        let check = check_snapshot_filesystem(
+            &snapshot_job("/mnt/new/snaps"),
+            Some("/backups/2026-10-01_020000"),
+            &fs,
+        )
+        .unwrap();
+        assert!(!check.passed);
+    }
+
+    #[test]
+    fn snapshot_filesystem_check_skips_other_cases() {
+        let fs = TestFileSystem::new()
+            .with_filesystem_type("/a", "ext4")
+            .with_filesystem_type("/b", "exfat");
+
+        // First run: no previous snapshot to link against
+        assert!(check_snapshot_filesystem(&snapshot_job("/a"), None, &fs).is_none());
+        // Mirror jobs never use --link-dest
+        let mut mirror = snapshot_job("/a");
+        mirror.transfer.backup_mode = BackupMode::Mirror;
+        assert!(check_snapshot_filesystem(&mirror, Some("/b/snap"), &fs).is_none());
+    }
+
+    #[test]
+    fn preflight_includes_snapshot_filesystem_warning() {
+        let fs = TestFileSystem::new()
+            .with_dir("/source")
+            .with_dir("/backups/snaps")
+            .with_dir("/old/snap")
+            .with_filesystem_type("/backups", "ext4")
+            .with_filesystem_type("/old", "exfat");
+        let rsync = MockRsync::installed();
+
+        let result = run_preflight(
+            &snapshot_job("/backups/snaps"),
+            Some("/old/snap"),
+            None,
+            &fs,
+            &rsync,
+        );
+        let check = result
+            .checks
+            .iter()
+            .find(|c| c.check_type == CheckType::SnapshotFilesystem)
+            .unwrap();
+        assert!(!check.passed);
+        // A warning does not fail preflight
+        assert!(result.overall_pass);
+    }
+
+    const RSYNC_3_2_VERSION: &str = "rsync  version 3.2.7  protocol version 31
+Capabilities:
+    64-bit files, 64-bit inums, 64-bit timestamps, 64-bit long ints,
+Checksum list:
+    xxh128 xxh3 xxh64 (xxhash) md5 md4 none
+Compress list:
+    zstd lz4 zlibx zlib none
+";
+
+    fn checksum_check(result: &PreflightResult) -> Option<&ValidationCheck> {
+        result
+            .checks
+            .iter()
+            .find(|c| c.check_type == CheckType::ChecksumChoice)
+    }
+
+    #[test]
+    fn supported_checksums_from_version_output() {
+        assert_eq!(
+            supported_checksums(RSYNC_3_2_VERSION).unwrap(),
+            vec!["xxh128", "xxh3", "xxh64", "md5", "md4", "none"]
+        );
+        assert_eq!(
+            supported_checksums("rsync  version 3.1.3  protocol version 31").unwrap(),
+            vec!["md4", "md5", "none"]
+        );
+        assert!(
+            supported_checksums("rsync  version 3.0.9  protocol version 30")
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            supported_checksums("rsync  version 3.2.1  protocol version 31"),
+            None
+        );
+    }
+
+    #[test]
+    fn preflight_passes_supported_checksum_choice() {
+        let mut job = local_job();
+        job.options.file_handling.checksum_choice = Some("xxh64".to_string());
+        let fs = MockFs::new().with_dir("/source").with_dir("/dest");
+        let rsync = MockRsync::installed().with_version(RSYNC_3_2_VERSION);
+
+        let result = run_preflight(&job, None, None, &fs, &rsync);
+
+        assert!(checksum_check(&result).unwrap().passed);
+    }
+
+    #[test]
+    fn preflight_warns_about_unsupported_checksum_choice() {
+        let mut job = local_job();
+        job.options.file_handling.checksum_choice = Some("xxh64".to_string());
+        let fs = MockFs::new().with_dir("/source").with_dir("/dest");
+        let rsync =
+            MockRsync::installed().with_version("rsync  version 3.1.3  protocol version 31");
+
+        let result = run_preflight(&job, None, None, &fs, &rsync);
+
+        let check = checksum_check(&result).unwrap();
+        assert!(!check.passed);
+        assert_eq!(check.severity, CheckSeverity::Warning);
+        assert!(check.message.contains("xxh64"));
+        assert!(check.message.contains("md4, md5, none"));
+        // A warning does not fail the preflight
+        assert!(result.overall_pass);
+    }
+
+    #[test]
+    fn preflight_warns_when_rsync_lacks_checksum_choice() {
+        let mut job = local_job();
+        job.options.file_handling.checksum_choice = Some("md5".to_string());
+        let fs = MockFs::new().with_dir("/source").with_dir("/dest");
+        let rsync =
+            MockRsync::installed().with_version("rsync  version 3.0.9  protocol version 30");
+
+        let check = checksum_check(&run_preflight(&job, None, None, &fs, &rsync))
+            .cloned()
+            .unwrap();
+
+        assert!(!check.passed);
+        assert!(check.message.contains("needs rsync 3.1.3"));
+    }
+
+    #[test]
+    fn preflight_skips_checksum_check_without_choice() {
+        let job = local_job();
+        let fs = MockFs::new().with_dir("/source").with_dir("/dest");
+        let rsync = MockRsync::installed().with_version(RSYNC_3_2_VERSION);
+
+        assert!(checksum_check(&run_preflight(&job, None, None, &fs, &rsync)).is_none());
+    }
+
+    #[test]
+    fn checksum_pair_and_auto_are_checked_per_name() {
+        assert!(
+            check_checksum_choice("auto", RSYNC_3_2_VERSION)
+                .unwrap()
+                .passed
+        );
+        assert!(
+            check_checksum_choice("xxh3,md5", RSYNC_3_2_VERSION)
+                .unwrap()
+                .passed
+        );
+        let check = check_checksum_choice("xxh3,sha1", RSYNC_3_2_VERSION).unwrap();
+        assert!(!check.passed);
+        assert!(check.message.contains("sha1 checksum"));
     }
 }
@@ -0,0 +1,123 @@
+use crate::error::AppError;
+use crate::models::execution::itemize::{RestorePreview, TransferType};
+use crate::models::job::{JobDefinition, StorageLocation};
+use crate::rsync_client::RsyncClient;
+use crate::services::command_builder::{build_rsync_args, ensure_trailing_slash};
+use crate::services::itemize_parser::parse_itemize_line;
+use crate::services::progress_parser::parse_stats_block;
+
+/// Most changes a restore preview lists; the rest are only counted.
+pub const MAX_PREVIEW_CHANGES: usize = 1000;
+
+/// rsync args that copy a job's backup back onto its source, with source
+/// and destination swapped.
+///
+/// Deleting options are always dropped, including `--delete*` and
+/// `--remove-source-files` in custom args, so a restore never removes
+/// anything. For snapshot jobs, `snapshot_path` is the snapshot to restore
+/// from. Without a trailing slash (and `auto_trailing_slash` off) rsync
+/// copies the source into a subdirectory of the destination, so the restore
+/// reads from that subdirectory.
+pub fn build_restore_command(
+    job: &JobDefinition,
+    snapshot_path: Option<&str>,
+    auto_trailing_slash: bool,
+) -> Vec<String> {
+    let source_path = location_path(&job.transfer.source);
+    let backup_root = snapshot_path.unwrap_or(location_path(&job.transfer.destination));
+
+    let copy_path = if auto_trailing_slash || source_path.ends_with('/') {
+        ensure_trailing_slash(backup_root)
+    } else {
+        let name = source_path.rsplit('/').next().unwrap_or(source_path);
+        format!("{}/{}/", backup_root.trim_end_matches('/'), name)
+    };
+    let from = with_path(&job.transfer.destination, copy_path);
+    let to = with_path(&job.transfer.source, ensure_trailing_slash(source_path));
+
+    let mut options = job.options.clone();
+    options.file_handling.delete = false;
+    options.file_handling.max_delete = None;
+    options
+        .advanced
+        .custom_args
+        .retain(|arg| !is_deleting_arg(arg));
+
+    build_rsync_args(
+        &from,
+        &to,
+        &options,
+        job.ssh_config.as_ref(),
+        &[],
+        false,
+        false,
+    )
+}
+
+/// Dry-run the restore from [`build_restore_command`] and report what it
+/// would write back onto the source. Nothing is written.
+pub fn preview_restore(
+    job: &JobDefinition,
+    rsync: &dyn RsyncClient,
+    snapshot_path: Option<&str>,
+    auto_trailing_slash: bool,
+) -> Result<RestorePreview, AppError> {
+    let mut restore_job = job.clone();
+    restore_job.options.core_transfer.dry_run = true;
+    restore_job.options.output.stats = true;
+    restore_job.options.output.itemize_changes = true;
+    restore_job.options.output.progress = false;
+    let args = build_restore_command(&restore_job, snapshot_path, auto_trailing_slash);
+    let result = rsync.dry_run(&args)?;
+
+    let stats = parse_stats_block(&result.stdout).ok_or_else(|| {
+        AppError::ValidationError("rsync output had no --stats summary".to_string())
+    })?;
+    let mut changes = Vec::new();
+    let mut truncated = 0;
+    for change in result.stdout.lines().filter_map(parse_itemize_line) {
+        // The transfer root itself, and messages
+        if change.path == "./" || change.transfer_type == TransferType::Message {
+            continue;
+        }
+        if changes.len() < MAX_PREVIEW_CHANGES {
+            changes.push(change);
+        } else {
+            truncated += 1;
+        }
+    }
+
+    let mut command_args = build_restore_command(job, snapshot_path, auto_trailing_slash);
+    command_args.retain(|arg| arg != "--dry-run");
+    Ok(RestorePreview {
+        command: format!("rsync {}", shell_words::join(&command_args)),
+        changes,
+        truncated,
+        files_to_restore: stats.files_transferred.unwrap_or(0),
+        bytes_to_restore: stats.transferred_file_size.unwrap_or(0),
+    })
+}
+
+fn is_deleting_arg(arg: &str) -> bool {
+    arg == "--del" || arg.starts_with("--delete") || arg.starts_with("--remove-source-files")
+}
+
+fn location_path(location: &StorageLocation) -> &str {
+    match location {
+        StorageLocation::Local { path }
+        | StorageLocation::RemoteSsh { path, .. }
+        | StorageLocation::RemoteRsync { path, .. }
+        | StorageLocation::Mount { path, .. } => path,
+    }
+}
+
+fn with_path(location: &StorageLocation, new_path: String) -> StorageLocation {
+    let mut location = location.clone();
+    match &mut location {
+        StorageLocation::Local { path }
+        | StorageLocation::RemoteSsh { path, .. }
+        | StorageLocation::RemoteRsync { path, .. }
+        | StorageLocation::Mount { path, .. } => *path = new_path,
+    }
+    location
+}
@@ -4,23 +4,48 @@ use chrono::{Duration, Utc};
 use uuid::Uuid;
 
 use crate::models::backup::{BackupInvocation, InvocationStatus};
-use crate::models::settings::HistoryRetentionConfig;
+use crate::models::settings::{HistoryRetentionConfig, RetentionPlan};
 
 /// Compute which invocations should be pruned based on the retention config.
 ///
 /// Returns a list of `(invocation_id, Option<log_file_path>)` tuples to delete.
-/// Skips invocations with status `Running`.
+/// See `compute_retention_plan` for the rules.
 pub fn compute_invocations_to_prune(
     invocations: &[BackupInvocation],
     config: &HistoryRetentionConfig,
 ) -> Vec<(Uuid, Option<String>)> {
-    let cutoff = Utc::now() - Duration::days(config.max_age_days as i64);
+    compute_retention_plan(invocations, config).prune
+}
+
+/// Compute which invocations to delete and which logs to drop.
+///
+/// Skips invocations with status `Running`. Records older than
+/// `max_record_age_days` (or `max_age_days` when unset) are deleted. When
+/// `max_record_age_days` is set, invocations between the two ages keep their
+/// record but have their log cleared. If a split limit is configured,
+/// each job's successes and failures (failed or cancelled runs) are capped
+/// separately, so a burst of successful runs never pushes failures out. The
+/// most recent successful invocation of each job is never pruned, regardless
+/// of age or count limits, so there is always a record of the last good run.
+/// When a log path template groups several invocations into one file (e.g.
+/// `{job}/{date}.log`), the path is only returned once no invocation that
+/// keeps its log still refers to it.
+pub fn compute_retention_plan(
+    invocations: &[BackupInvocation],
+    config: &HistoryRetentionConfig,
+) -> RetentionPlan {
+    let log_cutoff = Utc::now() - Duration::days(config.max_age_days as i64);
+    let record_age_days = config
+        .max_record_age_days
+        .map_or(config.max_age_days, |days| days.max(config.max_age_days));
+    let cutoff = Utc::now() - Duration::days(record_age_days as i64);
     let mut to_prune = Vec::new();
     let mut marked: std::collections::HashSet<Uuid> = std::collections::HashSet::new();
+    let protected = latest_success_per_job(invocations);
 
     // First pass: mark invocations older than cutoff
     for inv in invocations {
-        if inv.status == InvocationStatus::Running {
+        if inv.status == InvocationStatus::Running || protected.contains(&inv.id) {
             continue;
         }
         if inv.started_at < cutoff {
@@ -38,21 +63,92 @@ pub fn compute_invocations_to_prune(
         by_job.entry(inv.job_id).or_default().push(inv);
     }
 
+    let split = config.max_success_per_job.is_some() || config.max_failure_per_job.is_some();
     for (_job_id, mut job_invocations) in by_job {
         // Sort newest first
         job_invocations.sort_by(|a, b| b.started_at.cmp(&a.started_at));
 
-        if job_invocations.len() > config.max_per_job {
-            for inv in &job_invocations[config.max_per_job..] {
-                if !marked.contains(&inv.id) {
-                    marked.insert(inv.id);
-                    to_prune.push((inv.id, inv.execution_output.log_file_path.clone()));
+        let buckets = if split {
+            let (successes, failures): (Vec<_>, Vec<_>) = job_invocations
+                .into_iter()
+                .partition(|inv| inv.status == InvocationStatus::Succeeded);
+            vec![
+                (
+                    successes,
+                    config.max_success_per_job.unwrap_or(config.max_per_job),
+                ),
+                (
+                    failures,
+                    config.max_failure_per_job.unwrap_or(config.max_per_job),
+                ),
+            ]
+        } else {
+            vec![(job_invocations, config.max_per_job)]
+        };
+
+        for (bucket, max) in buckets {
+            if bucket.len() > max {
+                for inv in &bucket[max..] {
+                    if !marked.contains(&inv.id) && !protected.contains(&inv.id) {
+                        marked.insert(inv.id);
+                        to_prune.push((inv.id, inv.execution_output.log_file_path.clone()));
+                    }
                 }
             }
         }
     }
 
-    to_prune
+    // Third pass: logs-only tier, for records that outlive their logs
+    let mut clear_logs = Vec::new();
+    let mut cleared: std::collections::HashSet<Uuid> = std::collections::HashSet::new();
+    if config.max_record_age_days.is_some() {
+        for inv in invocations {
+            if inv.status == InvocationStatus::Running || marked.contains(&inv.id) {
+                continue;
+            }
+            if let Some(path) = &inv.execution_output.log_file_path {
+                if inv.started_at < log_cutoff {
+                    cleared.insert(inv.id);
+                    clear_logs.push((inv.id, Some(path.clone())));
+                }
+            }
+        }
+    }
+
+    // Keep shared log files that an invocation keeping its log still points at
+    let retained_logs: std::collections::HashSet<&str> = invocations
+        .iter()
+        .filter(|inv| !marked.contains(&inv.id) && !cleared.contains(&inv.id))
+        .filter_map(|inv| inv.execution_output.log_file_path.as_deref())
+        .collect();
+    let mut released: std::collections::HashSet<String> = std::collections::HashSet::new();
+    for (_, log_path) in to_prune.iter_mut().chain(clear_logs.iter_mut()) {
+        if let Some(path) = log_path {
+            if retained_logs.contains(path.as_str()) || !released.insert(path.clone()) {
+                *log_path = None;
+            }
+        }
+    }
+
+    RetentionPlan {
+        prune: to_prune,
+        clear_logs,
+    }
+}
+
+/// IDs of the newest `Succeeded` invocation of each job.
+fn latest_success_per_job(invocations: &[BackupInvocation]) -> std::collections::HashSet<Uuid> {
+    let mut latest: HashMap<Uuid, &BackupInvocation> = HashMap::new();
+    for inv in invocations {
+        if inv.status != InvocationStatus::Succeeded {
+            continue;
+        }
+        let entry = latest.entry(inv.job_id).or_insert(inv);
+        if inv.started_at > entry.started_at {
+            *entry = inv;
+        }
+    }
+    latest.values().map(|inv| inv.id).collect()
 }
 
 #[cfg(test)]
@@ -77,6 +173,9 @@ mod tests {
             finished_at: Some(Utc::now() - Duration::days(age_days)),
             status,
             trigger: InvocationTrigger::Manual,
+            rerun_of: None,
+            resumed_from: None,
+            warning: None,
             transfer_stats: TransferStats::default(),
             execution_output: ExecutionOutput {
                 command_executed: String::new(),
@@ -97,6 +196,9 @@ mod tests {
         let config = HistoryRetentionConfig {
             max_age_days: 90,
             max_per_job: 100,
+            max_success_per_job: None,
+            max_failure_per_job: None,
+            max_record_age_days: None,
         };
         let pruned = compute_invocations_to_prune(&invocations, &config);
         assert_eq!(pruned.len(), 1);
@@ -112,6 +214,9 @@ mod tests {
         let config = HistoryRetentionConfig {
             max_age_days: 365,
             max_per_job: 3,
+            max_success_per_job: None,
+            max_failure_per_job: None,
+            max_record_age_days: None,
         };
         let pruned = compute_invocations_to_prune(&invocations, &config);
         assert_eq!(pruned.len(), 2);
@@ -126,6 +231,9 @@ mod tests {
         let config = HistoryRetentionConfig {
             max_age_days: 1,
             max_per_job: 0,
+            max_success_per_job: None,
+            max_failure_per_job: None,
+            max_record_age_days: None,
         };
         let pruned = compute_invocations_to_prune(&invocations, &config);
         assert!(pruned.is_empty());
@@ -140,6 +248,9 @@ mod tests {
         let config = HistoryRetentionConfig {
             max_age_days: 90,
             max_per_job: 15,
+            max_success_per_job: None,
+            max_failure_per_job: None,
+            max_record_age_days: None,
         };
         let pruned = compute_invocations_to_prune(&invocations, &config);
         assert!(pruned.is_empty());
@@ -157,6 +268,9 @@ mod tests {
         let config = HistoryRetentionConfig {
             max_age_days: 90,
             max_per_job: 4,
+            max_success_per_job: None,
+            max_failure_per_job: None,
+            max_record_age_days: None,
         };
         let pruned = compute_invocations_to_prune(&invocations, &config);
         // The old one is pruned by age, plus 1 excess by count = 2 pruned
@@ -164,4 +278,355 @@ mod tests {
         //  5 > 4 so 1 more)
         assert_eq!(pruned.len(), 2);
     }
+
+    fn split_config(success: Option<usize>, failure: Option<usize>) -> HistoryRetentionConfig {
+        HistoryRetentionConfig {
+            max_age_days: 365,
+            max_per_job: 3,
+            max_success_per_job: success,
+            max_failure_per_job: failure,
+            max_record_age_days: None,
+        }
+    }
+
+    /// Alternating success/failure runs, newest first: S0 F1 S2 F3 S4 F5 S6 F7.
+    fn mixed_history(job_id: Uuid) -> Vec<BackupInvocation> {
+        (0..8)
+            .map(|i| {
+                let status = if i % 2 == 0 {
+                    InvocationStatus::Succeeded
+                } else {
+                    InvocationStatus::Failed
+                };
+                make_invocation(job_id, i, status)
+            })
+            .collect()
+    }
+
+    fn pruned_ids(pruned: &[(Uuid, Option<String>)]) -> std::collections::HashSet<Uuid> {
+        pruned.iter().map(|(id, _)| *id).collect()
+    }
+
+    #[test]
+    fn test_split_limits_prune_each_bucket() {
+        let job_id = Uuid::new_v4();
+        let invocations = mixed_history(job_id);
+
+        let pruned = compute_invocations_to_prune(&invocations, &split_config(Some(1), Some(3)));
+
+        // Keep newest success (S0) and three newest failures (F1, F3, F5)
+        let expected: std::collections::HashSet<Uuid> =
+            [2, 4, 6, 7].iter().map(|&i| invocations[i].id).collect();
+        assert_eq!(pruned_ids(&pruned), expected);
+    }
+
+    #[test]
+    fn test_split_limits_keep_failures_beyond_shared_cap() {
+        let job_id = Uuid::new_v4();
+        let invocations = mixed_history(job_id);
+
+        // max_per_job is 3, but all four failures are kept
+        let pruned = compute_invocations_to_prune(&invocations, &split_config(Some(1), Some(10)));
+
+        let ids = pruned_ids(&pruned);
+        assert_eq!(ids.len(), 3);
+        for inv in &invocations[1..] {
+            let is_failure = inv.status == InvocationStatus::Failed;
+            assert_eq!(ids.contains(&inv.id), !is_failure);
+        }
+    }
+
+    #[test]
+    fn test_unset_split_limit_falls_back_to_max_per_job() {
+        let job_id = Uuid::new_v4();
+        let invocations = mixed_history(job_id);
+
+        // Successes capped at 1, failures at max_per_job (3)
+        let pruned = compute_invocations_to_prune(&invocations, &split_config(Some(1), None));
+
+        let expected: std::collections::HashSet<Uuid> =
+            [2, 4, 6, 7].iter().map(|&i| invocations[i].id).collect();
+        assert_eq!(pruned_ids(&pruned), expected);
+    }
+
+    #[test]
+    fn test_cancelled_counts_as_failure() {
+        let job_id = Uuid::new_v4();
+        let invocations = vec![
+            make_invocation(job_id, 0, InvocationStatus::Succeeded),
+            make_invocation(job_id, 1, InvocationStatus::Cancelled),
+            make_invocation(job_id, 2, InvocationStatus::Failed),
+        ];
+
+        let pruned = compute_invocations_to_prune(&invocations, &split_config(Some(5), Some(1)));
+
+        assert_eq!(pruned.len(), 1);
+        assert_eq!(pruned[0].0, invocations[2].id);
+    }
+
+    #[test]
+    fn test_without_split_limits_buckets_are_shared() {
+        let job_id = Uuid::new_v4();
+        let invocations = mixed_history(job_id);
+
+        let pruned = compute_invocations_to_prune(&invocations, &split_config(None, None));
+
+        // Uniform cap of 3: S0 F1 S2 kept regardless of status
+        let expected: std::collections::HashSet<Uuid> = (3..8).map(|i| invocations[i].id).collect();
+        assert_eq!(pruned_ids(&pruned), expected);
+    }
+
+    #[test]
+    fn test_shared_log_file_kept_while_referenced() {
+        let job_id = Uuid::new_v4();
+        let mut old = make_invocation(job_id, 100, InvocationStatus::Succeeded);
+        let mut recent = make_invocation(job_id, 1, InvocationStatus::Succeeded);
+        let shared = "/logs/Nightly/2025-06-01.log".to_string();
+        old.execution_output.log_file_path = Some(shared.clone());
+        recent.execution_output.log_file_path = Some(shared);
+        let config = HistoryRetentionConfig {
+            max_age_days: 90,
+            max_per_job: 15,
+            max_success_per_job: None,
+            max_failure_per_job: None,
+            max_record_age_days: None,
+        };
+
+        let pruned = compute_invocations_to_prune(&[old.clone(), recent], &config);
+
+        assert_eq!(pruned.len(), 1);
+        assert_eq!(pruned[0].0, old.id);
+        assert_eq!(pruned[0].1, None);
+    }
+
+    #[test]
+    fn test_shared_log_file_released_once_when_all_pruned() {
+        let job_id = Uuid::new_v4();
+        let mut a = make_invocation(job_id, 100, InvocationStatus::Failed);
+        let mut b = make_invocation(job_id, 101, InvocationStatus::Failed);
+        let shared = "/logs/Nightly/2025-03-01.log".to_string();
+        a.execution_output.log_file_path = Some(shared.clone());
+        b.execution_output.log_file_path = Some(shared.clone());
+        let config = HistoryRetentionConfig {
+            max_age_days: 90,
+            max_per_job: 15,
+            max_success_per_job: None,
+            max_failure_per_job: None,
+            max_record_age_days: None,
+        };
+
+        let pruned = compute_invocations_to_prune(&[a, b], &config);
+
+        assert_eq!(pruned.len(), 2);
+        let paths: Vec<_> = pruned.iter().filter_map(|(_, p)| p.clone()).collect();
+        assert_eq!(paths, vec![shared]);
+    }
+
+    #[test]
+    fn test_latest_success_survives_zero_count_and_age() {
+        let job_id = Uuid::new_v4();
+        let invocations = vec![
+            make_invocation(job_id, 5, InvocationStatus::Failed),
+            make_invocation(job_id, 10, InvocationStatus::Succeeded),
+            make_invocation(job_id, 20, InvocationStatus::Succeeded),
+            make_invocation(job_id, 30, InvocationStatus::Succeeded),
+        ];
+        let config = HistoryRetentionConfig {
+            max_age_days: 1,
+            max_per_job: 0,
+            max_success_per_job: None,
+            max_failure_per_job: None,
+            max_record_age_days: None,
+        };
+
+        let pruned = compute_invocations_to_prune(&invocations, &config);
+
+        let ids = pruned_ids(&pruned);
+        assert_eq!(ids.len(), 3);
+        assert!(!ids.contains(&invocations[1].id));
+        assert!(ids.contains(&invocations[0].id));
+        assert!(ids.contains(&invocations[2].id));
+        assert!(ids.contains(&invocations[3].id));
+    }
+
+    #[test]
+    fn test_latest_success_protected_per_job() {
+        let job_a = Uuid::new_v4();
+        let job_b = Uuid::new_v4();
+        let invocations = vec![
+            make_invocation(job_a, 50, InvocationStatus::Succeeded),
+            make_invocation(job_a, 60, InvocationStatus::Succeeded),
+            make_invocation(job_b, 70, InvocationStatus::Succeeded),
+        ];
+        let config = HistoryRetentionConfig {
+            max_age_days: 1,
+            max_per_job: 0,
+            max_success_per_job: None,
+            max_failure_per_job: None,
+            max_record_age_days: None,
+        };
+
+        let pruned = compute_invocations_to_prune(&invocations, &config);
+
+        assert_eq!(pruned.len(), 1);
+        assert_eq!(pruned[0].0, invocations[1].id);
+    }
+
+    #[test]
+    fn test_latest_success_survives_split_limit_of_zero() {
+        let job_id = Uuid::new_v4();
+        let invocations = mixed_history(job_id);
+
+        let pruned = compute_invocations_to_prune(&invocations, &split_config(Some(0), Some(0)));
+
+        let ids = pruned_ids(&pruned);
+        assert_eq!(ids.len(), 7);
+        assert!(!ids.contains(&invocations[0].id));
+    }
+
+    #[test]
+    fn test_job_without_success_has_nothing_protected() {
+        let job_id = Uuid::new_v4();
+        let invocations = vec![
+            make_invocation(job_id, 100, InvocationStatus::Failed),
+            make_invocation(job_id, 100, InvocationStatus::Cancelled),
+        ];
+        let config = HistoryRetentionConfig {
+            max_age_days: 1,
+            max_per_job: 0,
+            max_success_per_job: None,
+            max_failure_per_job: None,
+            max_record_age_days: None,
+        };
+
+        let pruned = compute_invocations_to_prune(&invocations, &config);
+
+        assert_eq!(pruned.len(), 2);
+    }
+
+    fn tiered_config(log_days: u32, record_days: u32) -> HistoryRetentionConfig {
+        HistoryRetentionConfig {
+            max_age_days: log_days,
+            max_per_job: 100,
+            max_success_per_job: None,
+            max_failure_per_job: None,
+            max_record_age_days: Some(record_days),
+        }
+    }
+
+    #[test]
+    fn test_tiered_retention_stages() {
+        let job_id = Uuid::new_v4();
+        let invocations = vec![
+            make_invocation(job_id, 1, InvocationStatus::Succeeded),
+            make_invocation(job_id, 60, InvocationStatus::Failed),
+            make_invocation(job_id, 400, InvocationStatus::Failed),
+        ];
+
+        let plan = compute_retention_plan(&invocations, &tiered_config(30, 365));
+
+        // Younger than the log age: untouched
+        // Between log and record age: log cleared, record kept
+        // Older than the record age: record deleted
+        assert_eq!(
+            plan.clear_logs,
+            vec![(
+                invocations[1].id,
+                invocations[1].execution_output.log_file_path.clone()
+            )]
+        );
+        assert_eq!(
+            plan.prune,
+            vec![(
+                invocations[2].id,
+                invocations[2].execution_output.log_file_path.clone()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_tiered_retention_at_each_threshold() {
+        let job_id = Uuid::new_v4();
+        let recent = make_invocation(job_id, 0, InvocationStatus::Succeeded);
+        let past_log_age = make_invocation(job_id, 31, InvocationStatus::Failed);
+        let before_record_age = make_invocation(job_id, 364, InvocationStatus::Failed);
+        let past_record_age = make_invocation(job_id, 366, InvocationStatus::Failed);
+        let invocations = vec![
+            recent.clone(),
+            past_log_age.clone(),
+            before_record_age.clone(),
+            past_record_age.clone(),
+        ];
+
+        let plan = compute_retention_plan(&invocations, &tiered_config(30, 365));
+
+        let cleared: Vec<Uuid> = plan.clear_logs.iter().map(|(id, _)| *id).collect();
+        assert_eq!(cleared, vec![past_log_age.id, before_record_age.id]);
+        assert_eq!(
+            pruned_ids(&plan.prune),
+            [past_record_age.id].into_iter().collect()
+        );
+    }
+
+    #[test]
+    fn test_tiered_retention_skips_records_without_logs() {
+        let job_id = Uuid::new_v4();
+        let mut no_log = make_invocation(job_id, 60, InvocationStatus::Failed);
+        no_log.execution_output.log_file_path = None;
+
+        let plan = compute_retention_plan(&[no_log], &tiered_config(30, 365));
+
+        assert!(plan.clear_logs.is_empty());
+        assert!(plan.prune.is_empty());
+    }
+
+    #[test]
+    fn test_without_record_age_logs_are_not_cleared_separately() {
+        let job_id = Uuid::new_v4();
+        let invocations = vec![
+            make_invocation(job_id, 1, InvocationStatus::Succeeded),
+            make_invocation(job_id, 60, InvocationStatus::Failed),
+        ];
+        let config = HistoryRetentionConfig {
+            max_age_days: 30,
+            max_per_job: 100,
+            max_success_per_job: None,
+            max_failure_per_job: None,
+            max_record_age_days: None,
+        };
+
+        let plan = compute_retention_plan(&invocations, &config);
+
+        assert!(plan.clear_logs.is_empty());
+        assert_eq!(plan.prune.len(), 1);
+        assert_eq!(plan.prune[0].0, invocations[1].id);
+    }
+
+    #[test]
+    fn test_record_age_below_log_age_uses_log_age() {
+        let job_id = Uuid::new_v4();
+        let invocations = vec![
+            make_invocation(job_id, 1, InvocationStatus::Succeeded),
+            make_invocation(job_id, 20, InvocationStatus::Failed),
+        ];
+
+        let plan = compute_retention_plan(&invocations, &tiered_config(30, 10));
+
+        assert!(plan.prune.is_empty());
+        assert!(plan.clear_logs.is_empty());
+    }
+
+    #[test]
+    fn test_tiered_shared_log_kept_while_referenced() {
+        let job_id = Uuid::new_v4();
+        let mut old = make_invocation(job_id, 60, InvocationStatus::Failed);
+        let mut recent = make_invocation(job_id, 1, InvocationStatus::Succeeded);
+        let shared = "/logs/Nightly/2025-06.log".to_string();
+        old.execution_output.log_file_path = Some(shared.clone());
+        recent.execution_output.log_file_path = Some(shared);
+
+        let plan = compute_retention_plan(&[old.clone(), recent], &tiered_config(30, 365));
+
+        assert_eq!(plan.clear_logs, vec![(old.id, None)]);
+    }
 }
@@ -1,3 +1,6 @@
 pub mod history_retention;
+pub mod orphaned_logs;
+pub mod prune_recovery;
 pub mod retention_runner;
 pub mod snapshot_retention;
+pub mod snapshot_trash;
@@ -0,0 +1,73 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::file_system::FileSystem;
+use crate::services::job_service::JobService;
+use crate::services::log_writer::log_file_set;
+
+/// The invocation ID a log file is named after: the first 36 characters of
+/// its file name, followed by the end of the name or a `.` (so `<id>.log`,
+/// `<id>.log.1`, `<id>.jsonl` and `<id>.rsync.log` all match).
+pub fn log_invocation_id(path: &Path) -> Option<Uuid> {
+    let name = path.file_name()?.to_str()?;
+    let id = name.get(..36)?;
+    match name[36..].chars().next() {
+        None | Some('.') => Uuid::parse_str(id).ok(),
+        _ => None,
+    }
+}
+
+/// List files under `log_dir` named after an invocation that no longer has a
+/// record, e.g. logs left behind by a crashed run.
+///
+/// Files that aren't named after a UUID are never reported, and neither is
+/// any file a recorded invocation still points at (including its rotated,
+/// JSON Lines and rsync siblings). A missing log directory has no orphans.
+pub fn find_orphaned_logs(
+    fs: &dyn FileSystem,
+    log_dir: &Path,
+    job_service: &JobService,
+) -> Result<Vec<PathBuf>, AppError> {
+    if !fs.is_dir(log_dir) {
+        return Ok(Vec::new());
+    }
+
+    let invocations = job_service.list_all_invocations()?;
+    let known_ids: HashSet<Uuid> = invocations.iter().map(|inv| inv.id).collect();
+    let referenced: HashSet<PathBuf> = invocations
+        .iter()
+        .filter_map(|inv| inv.execution_output.log_file_path.as_deref())
+        .flat_map(log_file_set)
+        .map(PathBuf::from)
+        .collect();
+
+    let mut orphans: Vec<PathBuf> = fs
+        .walk_dir(log_dir)?
+        .into_iter()
+        .filter(|path| fs.is_file(path))
+        .filter(|path| !referenced.contains(path))
+        .filter(|path| log_invocation_id(path).is_some_and(|id| !known_ids.contains(&id)))
+        .collect();
+    orphans.sort();
+    Ok(orphans)
+}
+
+/// Delete the files [`find_orphaned_logs`] reports. Returns the paths that
+/// were removed; files that can't be deleted are logged and skipped.
+pub fn clean_orphaned_logs(
+    fs: &dyn FileSystem,
+    log_dir: &Path,
+    job_service: &JobService,
+) -> Result<Vec<PathBuf>, AppError> {
+    let mut removed = Vec::new();
+    for path in find_orphaned_logs(fs, log_dir, job_service)? {
+        match fs.remove_file(&path) {
+            Ok(()) => removed.push(path),
+            Err(e) => log::warn!("Failed to remove orphaned log {}: {}", path.display(), e),
+        }
+    }
+    Ok(removed)
+}
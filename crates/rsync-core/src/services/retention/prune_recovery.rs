@@ -0,0 +1,94 @@
+use std::path::Path;
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::error::JobServiceError;
+use crate::file_system::FileSystem;
+use crate::models::settings::{PruneRecord, UndoPruneResult};
+use crate::services::job_service::JobService;
+
+/// How long after a retention prune it can still be undone.
+pub const PRUNE_UNDO_WINDOW_HOURS: i64 = 24;
+
+/// Add `record` to the recovery log, dropping entries past the undo window.
+///
+/// A prune that removed nothing is not recorded. Failures are logged rather
+/// than returned, so the recovery log never holds up retention itself.
+pub fn record_prune(job_service: &JobService, record: PruneRecord) {
+    if record.invocations.is_empty() && record.snapshots.is_empty() {
+        return;
+    }
+    let cutoff = record.pruned_at - Duration::hours(PRUNE_UNDO_WINDOW_HOURS);
+    if let Err(e) = job_service.delete_prune_records_before(&cutoff) {
+        log::error!("Retention: failed to drop expired prune records: {}", e);
+    }
+    if let Err(e) = job_service.insert_prune_record(&record) {
+        log::error!("Retention: failed to record prune for undo: {}", e);
+    }
+}
+
+/// Undo the most recent prune still inside the undo window.
+///
+/// Invocation and snapshot records are always put back. Snapshot directories
+/// that were moved to the trash are moved back to where they were; ones that
+/// were deleted outright, or whose trash has since been emptied, are listed
+/// in `missing_directories`. Log files deleted with pruned invocations are
+/// not recovered. Returns `None` when there is nothing to undo.
+pub fn undo_last_prune(
+    job_service: &JobService,
+    fs: &dyn FileSystem,
+    now: DateTime<Utc>,
+) -> Result<Option<UndoPruneResult>, JobServiceError> {
+    let cutoff = now - Duration::hours(PRUNE_UNDO_WINDOW_HOURS);
+    job_service.delete_prune_records_before(&cutoff)?;
+    let Some(record) = job_service.take_latest_prune_record(&cutoff)? else {
+        return Ok(None);
+    };
+
+    let mut result = UndoPruneResult::default();
+    // Invocations first: snapshot records refer to them
+    for inv in &record.invocations {
+        match job_service.upsert_invocation(inv) {
+            Ok(()) => result.invocations_restored += 1,
+            Err(e) => log::error!("Undo prune: failed to restore invocation {}: {}", inv.id, e),
+        }
+    }
+
+    for pruned in &record.snapshots {
+        if let Err(e) = job_service.upsert_snapshot(&pruned.record) {
+            log::error!(
+                "Undo prune: failed to restore snapshot {}: {}",
+                pruned.record.id,
+                e
+            );
+            continue;
+        }
+        result.snapshots_restored += 1;
+
+        let path = Path::new(&pruned.record.snapshot_path);
+        if fs.exists(path) {
+            continue;
+        }
+        let moved_back = match pruned.trashed_to.as_deref() {
+            Some(trashed) if fs.exists(Path::new(trashed)) => {
+                match fs.rename(Path::new(trashed), path) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        log::error!("Undo prune: failed to move {} back: {}", trashed, e);
+                        false
+                    }
+                }
+            }
+            _ => false,
+        };
+        if moved_back {
+            result.directories_restored += 1;
+        } else {
+            result
+                .missing_directories
+                .push(pruned.record.snapshot_path.clone());
+        }
+    }
+
+    Ok(Some(result))
+}
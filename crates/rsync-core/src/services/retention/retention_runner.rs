@@ -1,11 +1,21 @@
-use crate::models::settings::HistoryRetentionConfig;
+use std::collections::HashSet;
+
+use chrono::Utc;
+
+use crate::models::settings::{HistoryRetentionConfig, PruneRecord, PrunedSnapshot};
 use crate::services::history_retention;
 use crate::services::job_service::JobService;
+use crate::services::log_writer::log_file_set;
+use crate::services::prune_recovery;
 use crate::services::settings_service::SettingsService;
 
 /// Run history retention, pruning old invocations and their log files.
 ///
-/// Returns the number of invocations pruned.
+/// With a separate record age configured, invocations past the log age only
+/// lose their log file. The pruned records, and the snapshot records deleted
+/// along with them, go to the prune recovery log so they can be restored
+/// with `prune_recovery::undo_last_prune()`. Returns the number of
+/// invocations pruned.
 pub fn run_history_retention(
     job_service: &JobService,
     settings_service: &SettingsService,
@@ -21,6 +31,9 @@ pub fn run_history_retention(
     let config = HistoryRetentionConfig {
         max_age_days: retention.max_log_age_days,
         max_per_job: retention.max_history_per_job,
+        max_success_per_job: retention.max_success_per_job,
+        max_failure_per_job: retention.max_failure_per_job,
+        max_record_age_days: retention.max_record_age_days,
     };
 
     let all_invocations = match job_service.list_all_invocations() {
@@ -31,17 +44,39 @@ pub fn run_history_retention(
         }
     };
 
-    let to_prune = history_retention::compute_invocations_to_prune(&all_invocations, &config);
-    let count = to_prune.len();
+    let plan = history_retention::compute_retention_plan(&all_invocations, &config);
+    let count = plan.prune.len();
+
+    if count > 0 {
+        let pruned_ids: HashSet<_> = plan.prune.iter().map(|(id, _)| *id).collect();
+        // Snapshot records go with their invocation (ON DELETE CASCADE)
+        let snapshots = job_service
+            .list_all_snapshots()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|snap| pruned_ids.contains(&snap.invocation_id))
+            .map(|record| PrunedSnapshot {
+                record,
+                trashed_to: None,
+            })
+            .collect();
+        prune_recovery::record_prune(
+            job_service,
+            PruneRecord {
+                pruned_at: Utc::now(),
+                invocations: all_invocations
+                    .iter()
+                    .filter(|inv| pruned_ids.contains(&inv.id))
+                    .cloned()
+                    .collect(),
+                snapshots,
+            },
+        );
+    }
 
-    for (inv_id, log_path) in &to_prune {
-        // Delete log file if it exists
+    for (inv_id, log_path) in &plan.prune {
         if let Some(path) = log_path {
-            if std::path::Path::new(path).exists() {
-                if let Err(e) = std::fs::remove_file(path) {
-                    log::error!("Retention: failed to delete log file {}: {}", path, e);
-                }
-            }
+            delete_log_files(path);
         }
         // Delete invocation from DB
         if let Err(e) = job_service.delete_invocation(inv_id) {
@@ -49,9 +84,40 @@ pub fn run_history_retention(
         }
     }
 
+    for (inv_id, log_path) in &plan.clear_logs {
+        if let Some(path) = log_path {
+            delete_log_files(path);
+        }
+        // Keep the record, but forget its log
+        if let Err(e) = job_service.clear_log_file_path(inv_id) {
+            log::error!(
+                "Retention: failed to clear log for invocation {}: {}",
+                inv_id,
+                e
+            );
+        }
+    }
+
     if count > 0 {
         log::info!("Retention: pruned {} invocations", count);
     }
+    if !plan.clear_logs.is_empty() {
+        log::info!(
+            "Retention: cleared logs of {} invocations",
+            plan.clear_logs.len()
+        );
+    }
 
     count
 }
+
+/// Delete a log file and any rotated or structured siblings that exist.
+fn delete_log_files(path: &str) {
+    for file in log_file_set(path) {
+        if std::path::Path::new(&file).exists() {
+            if let Err(e) = std::fs::remove_file(&file) {
+                log::error!("Retention: failed to delete log file {}: {}", file, e);
+            }
+        }
+    }
+}
@@ -13,7 +13,7 @@ use crate::models::job::RetentionPolicy;
 /// 1. Group snapshots by calendar day, ISO week, and month
 /// 2. For each period type, keep the latest snapshot per period up to the limit
 /// 3. Any snapshot kept by at least one rule survives; the rest are pruned
-/// 4. The most recent snapshot is always kept regardless of policy
+/// 4. The most recent snapshot and pinned snapshots are always kept regardless of policy
 pub fn compute_snapshots_to_delete(
     snapshots: &[SnapshotRecord],
     policy: &RetentionPolicy,
@@ -29,6 +29,9 @@ pub fn compute_snapshots_to_delete(
         keep.insert(latest.id);
     }
 
+    // Always keep pinned snapshots
+    keep.extend(snapshots.iter().filter(|s| s.pinned).map(|s| s.id));
+
     // Keep daily: latest snapshot per calendar day, up to keep_daily days
     keep_by_period(snapshots, policy.keep_daily as usize, &mut keep, |dt| {
         dt.date_naive()
@@ -98,6 +101,7 @@ mod tests {
             size_bytes: 1024,
             file_count: 10,
             is_latest: false,
+            pinned: false,
         }
     }
 
@@ -264,6 +268,26 @@ mod tests {
         assert!(to_delete.is_empty());
     }
 
+    #[test]
+    fn pinned_snapshots_are_never_deleted() {
+        let policy = RetentionPolicy {
+            keep_daily: 0,
+            keep_weekly: 0,
+            keep_monthly: 0,
+        };
+
+        let mut pinned = make_snapshot_at(1, dt(2025, 6, 4, 9));
+        pinned.pinned = true;
+        let snaps = vec![
+            make_snapshot_at(3, dt(2025, 6, 18, 9)),
+            make_snapshot_at(2, dt(2025, 6, 11, 9)),
+            pinned,
+        ];
+
+        let to_delete = compute_snapshots_to_delete(&snaps, &policy);
+        assert_eq!(to_delete, vec![Uuid::from_bytes([2; 16])]);
+    }
+
     #[test]
     fn snapshot_dir_name_format() {
         let ts = dt(2025, 6, 15, 14);
@@ -0,0 +1,83 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+
+use crate::file_system::{FileSystem, FsError};
+
+/// Remove a snapshot directory pruned by retention, or, when a trash
+/// directory is configured, move it there instead so it can be recovered.
+/// Returns where the snapshot was moved to, or `None` if it was deleted.
+pub fn dispose_pruned_snapshot(
+    fs: &dyn FileSystem,
+    path: &Path,
+    trash_dir: Option<&Path>,
+) -> Result<Option<PathBuf>, FsError> {
+    match trash_dir {
+        Some(trash_dir) => move_to_trash(fs, path, trash_dir, Utc::now()).map(Some),
+        None => fs.remove_dir_all(path).map(|_| None),
+    }
+}
+
+/// Create `trash_dir` and make sure the snapshot at `path` can be renamed
+/// into it: both must be on the same filesystem, or moving a directory fails.
+pub fn prepare_trash(fs: &dyn FileSystem, path: &Path, trash_dir: &Path) -> Result<(), FsError> {
+    fs.create_dir_all(trash_dir)?;
+    match (fs.mount_point(path), fs.mount_point(trash_dir)) {
+        (Some(snapshot_mount), Some(trash_mount)) if snapshot_mount != trash_mount => {
+            Err(FsError::CrossDevice(format!(
+                "snapshot {} is on {} but the trash directory {} is on {}; choose a trash \
+                 directory on the backup volume",
+                path.display(),
+                snapshot_mount.display(),
+                trash_dir.display(),
+                trash_mount.display()
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Move `path` into `trash_dir` as `<YYYYmmdd-HHMMSS>-<name>`, adding a
+/// `-N` suffix if that name is already taken. This is a rename, so the trash
+/// directory must be on the same filesystem as the snapshot (see
+/// [`prepare_trash`]).
+pub fn move_to_trash(
+    fs: &dyn FileSystem,
+    path: &Path,
+    trash_dir: &Path,
+    now: DateTime<Utc>,
+) -> Result<PathBuf, FsError> {
+    if !fs.exists(path) {
+        return Err(FsError::NotFound(path.display().to_string()));
+    }
+    prepare_trash(fs, path, trash_dir)?;
+
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "snapshot".to_string());
+    let base = format!("{}-{}", now.format("%Y%m%d-%H%M%S"), name);
+    let mut target = trash_dir.join(&base);
+    let mut n = 1;
+    while fs.exists(&target) {
+        target = trash_dir.join(format!("{}-{}", base, n));
+        n += 1;
+    }
+
+    fs.rename(path, &target)?;
+    Ok(target)
+}
+
+/// Permanently delete everything in the trash directory. Returns the number
+/// of trashed snapshots removed; a missing trash directory is already empty.
+pub fn empty_trash(fs: &dyn FileSystem, trash_dir: &Path) -> Result<usize, FsError> {
+    if !fs.is_dir(trash_dir) {
+        return Ok(0);
+    }
+    let mut removed = 0;
+    for entry in fs.read_dir(trash_dir)? {
+        fs.remove_dir_all(&entry)?;
+        removed += 1;
+    }
+    Ok(removed)
+}
@@ -0,0 +1,78 @@
+use chrono::{NaiveTime, Timelike};
+
+use crate::models::rsync_options::{AdvancedOptions, BandwidthWindow, RsyncOptions};
+use crate::models::settings::MINUTES_PER_DAY;
+
+/// Whether `minute` (after midnight) falls inside `window`. The start is
+/// inclusive and the end exclusive, as with quiet hours.
+fn covers(window: &BandwidthWindow, minute: u16) -> bool {
+    let (start, end) = (window.start_minute, window.end_minute);
+    if start <= end {
+        (start..end).contains(&minute)
+    } else {
+        minute >= start || minute < end
+    }
+}
+
+fn limit_at_minute(advanced: &AdvancedOptions, minute: u16) -> Option<u64> {
+    match advanced
+        .bandwidth_schedule
+        .iter()
+        .find(|w| covers(w, minute))
+    {
+        Some(window) => window.limit,
+        None => advanced.bandwidth_limit,
+    }
+}
+
+fn minute_of(time: NaiveTime) -> u16 {
+    (time.hour() * 60 + time.minute()) as u16
+}
+
+/// The `--bwlimit` value (KB/s) in force at `now`: the first schedule window
+/// covering it, or the job's plain `bandwidth_limit` outside every window.
+pub fn bandwidth_limit_at(advanced: &AdvancedOptions, now: NaiveTime) -> Option<u64> {
+    limit_at_minute(advanced, minute_of(now))
+}
+
+/// When the limit in force at `now` next changes, and the limit from then
+/// on. `None` when the schedule never changes it, including when there is
+/// no schedule at all.
+pub fn next_bandwidth_change(
+    advanced: &AdvancedOptions,
+    now: NaiveTime,
+) -> Option<(NaiveTime, Option<u64>)> {
+    let minute = minute_of(now);
+    let current = limit_at_minute(advanced, minute);
+
+    // The limit can only change where a window starts or ends; a boundary
+    // at the current minute has already passed, so it comes round tomorrow
+    let mut boundaries: Vec<u16> = advanced
+        .bandwidth_schedule
+        .iter()
+        .flat_map(|w| [w.start_minute, w.end_minute])
+        .filter(|m| *m < MINUTES_PER_DAY)
+        .collect();
+    let until = |m: u16| match (m + MINUTES_PER_DAY - minute) % MINUTES_PER_DAY {
+        0 => MINUTES_PER_DAY,
+        ahead => ahead,
+    };
+    boundaries.sort_by_key(|m| until(*m));
+
+    boundaries
+        .into_iter()
+        .map(|m| (m, limit_at_minute(advanced, m)))
+        .find(|(_, limit)| *limit != current)
+        .and_then(|(m, limit)| {
+            NaiveTime::from_hms_opt((m / 60) as u32, (m % 60) as u32, 0).map(|t| (t, limit))
+        })
+}
+
+/// Set `bandwidth_limit` to the schedule's limit for a run starting at
+/// `now`. rsync cannot change `--bwlimit` mid-transfer, so that limit holds
+/// for the whole run. Options without a schedule are left alone.
+pub fn apply_bandwidth_schedule(options: &mut RsyncOptions, now: NaiveTime) {
+    if !options.advanced.bandwidth_schedule.is_empty() {
+        options.advanced.bandwidth_limit = bandwidth_limit_at(&options.advanced, now);
+    }
+}
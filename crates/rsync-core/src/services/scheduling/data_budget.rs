@@ -0,0 +1,109 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+
+use crate::error::AppError;
+use crate::models::backup::{BackupInvocation, InvocationStatus};
+use crate::models::job::JobDefinition;
+use crate::services::job_service::JobService;
+use crate::services::run_conditions::RunCondition;
+use crate::services::settings_service::SettingsService;
+
+/// Sum the bytes transferred by invocations started in the same calendar
+/// month (UTC) as `now`.
+pub fn bytes_transferred_in_month(invocations: &[BackupInvocation], now: DateTime<Utc>) -> u64 {
+    invocations
+        .iter()
+        .filter(|inv| inv.started_at.year() == now.year() && inv.started_at.month() == now.month())
+        .map(|inv| inv.transfer_stats.bytes_transferred)
+        .sum()
+}
+
+/// Midnight UTC on the first day of `now`'s month.
+pub fn month_start(now: DateTime<Utc>) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(now.year(), now.month(), 1, 0, 0, 0)
+        .single()
+        .unwrap_or(now)
+}
+
+/// Remaining bytes under a monthly cap. Returns 0 once the cap is exhausted.
+pub fn remaining_bytes(budget: u64, used: u64) -> u64 {
+    budget.saturating_sub(used)
+}
+
+/// Tracks cumulative bytes transferred this month against the configured
+/// monthly data budget.
+///
+/// Registered as a [`RunCondition`] so scheduled runs are refused when the
+/// budget would be exceeded; manual runs only receive a warning.
+pub struct DataBudget {
+    job_service: Arc<JobService>,
+    settings_service: Arc<SettingsService>,
+}
+
+impl DataBudget {
+    pub fn new(job_service: Arc<JobService>, settings_service: Arc<SettingsService>) -> Self {
+        Self {
+            job_service,
+            settings_service,
+        }
+    }
+
+    /// Bytes transferred by all jobs in the current month.
+    pub fn used_this_month(&self) -> Result<u64, AppError> {
+        let now = Utc::now();
+        let invocations = self.job_service.list_invocations_since(&month_start(now))?;
+        Ok(bytes_transferred_in_month(&invocations, now))
+    }
+
+    /// Bytes left in this month's budget, or `None` when no budget is configured.
+    pub fn remaining_budget(&self) -> Result<Option<u64>, AppError> {
+        match self.settings_service.get_monthly_data_budget()? {
+            Some(budget) => Ok(Some(remaining_bytes(budget, self.used_this_month()?))),
+            None => Ok(None),
+        }
+    }
+
+    /// Estimate the next transfer size from the job's last successful run.
+    fn estimated_transfer(&self, job: &JobDefinition) -> u64 {
+        self.job_service
+            .get_job_history(&job.id, 50)
+            .ok()
+            .and_then(|history| {
+                history
+                    .into_iter()
+                    .find(|inv| inv.status == InvocationStatus::Succeeded)
+            })
+            .map(|inv| inv.transfer_stats.bytes_transferred)
+            .unwrap_or(0)
+    }
+}
+
+impl RunCondition for DataBudget {
+    fn skip_reason(&self, job: &JobDefinition) -> Option<String> {
+        let remaining = match self.remaining_budget() {
+            Ok(Some(remaining)) => remaining,
+            Ok(None) => return None,
+            Err(e) => {
+                log::error!("Failed to compute monthly data budget: {}", e);
+                return None;
+            }
+        };
+
+        let estimate = self.estimated_transfer(job);
+        if remaining == 0 {
+            Some("monthly data budget is exhausted".to_string())
+        } else if estimate > remaining {
+            Some(format!(
+                "monthly data budget would be exceeded ({} bytes remaining, last run transferred {} bytes)",
+                remaining, estimate
+            ))
+        } else {
+            None
+        }
+    }
+
+    fn warn_on_manual(&self) -> bool {
+        true
+    }
+}
@@ -1,2 +1,8 @@
+pub mod bandwidth_schedule;
+pub mod data_budget;
+pub mod quiet_hours;
 pub mod scheduler;
 pub mod scheduler_backend;
+pub mod scheduler_events;
+pub mod source_change;
+pub mod startup_runner;
@@ -0,0 +1,47 @@
+use chrono::{Duration, NaiveTime, Timelike};
+
+use crate::models::backup::InvocationTrigger;
+use crate::models::settings::{QuietHours, MINUTES_PER_DAY};
+
+/// Whether `now` falls inside the quiet-hours window.
+///
+/// The start is inclusive and the end exclusive. A window whose end is
+/// before its start wraps past midnight.
+pub fn is_quiet_time(quiet_hours: &QuietHours, now: NaiveTime) -> bool {
+    let minute = (now.hour() * 60 + now.minute()) as u16;
+    let (start, end) = (quiet_hours.start_minute, quiet_hours.end_minute);
+    if start <= end {
+        (start..end).contains(&minute)
+    } else {
+        minute >= start || minute < end
+    }
+}
+
+/// How long from `now` until the quiet-hours window ends, or `None` when
+/// `now` is outside it.
+pub fn time_until_quiet_end(quiet_hours: &QuietHours, now: NaiveTime) -> Option<Duration> {
+    if !is_quiet_time(quiet_hours, now) {
+        return None;
+    }
+    let minute = (now.hour() * 60 + now.minute()) as i64;
+    let minutes = (quiet_hours.end_minute as i64 - minute).rem_euclid(MINUTES_PER_DAY as i64);
+    Some(
+        Duration::minutes(minutes)
+            - Duration::seconds(now.second() as i64)
+            - Duration::nanoseconds(now.nanosecond() as i64),
+    )
+}
+
+/// Whether a run with `trigger` should be suppressed by quiet hours at `now`.
+///
+/// Manual runs are never suppressed.
+pub fn suppressed_by_quiet_hours(
+    quiet_hours: Option<&QuietHours>,
+    trigger: &InvocationTrigger,
+    now: NaiveTime,
+) -> bool {
+    match trigger {
+        InvocationTrigger::Manual => false,
+        InvocationTrigger::Scheduled => quiet_hours.is_some_and(|q| is_quiet_time(q, now)),
+    }
+}
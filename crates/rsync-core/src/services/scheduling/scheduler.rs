@@ -1,9 +1,11 @@
 use std::str::FromStr;
 
-use chrono::{DateTime, Duration, Utc};
+use chrono::{DateTime, Duration, TimeZone, Utc};
 use croner::Cron;
 
 use crate::models::schedule::{ScheduleConfig, ScheduleType};
+use crate::models::settings::QuietHours;
+use crate::services::quiet_hours::time_until_quiet_end;
 
 /// Determines whether a scheduled job is due for execution.
 ///
@@ -70,6 +72,45 @@ pub fn next_run_time(
     }
 }
 
+/// Lists the next `count` run times of a schedule after `from`, stepping
+/// `next_run_time` forward from each result.
+///
+/// Returns an empty list for a disabled schedule or an invalid cron expression.
+pub fn upcoming_runs(
+    schedule: &ScheduleConfig,
+    from: DateTime<Utc>,
+    count: usize,
+) -> Vec<DateTime<Utc>> {
+    upcoming_runs_around_quiet_hours(schedule, from, count, None, &Utc)
+}
+
+/// Like [`upcoming_runs`], but a run falling inside `quiet_hours` (read in
+/// `tz`) moves to the end of the window, where the scheduler catches up.
+/// Later runs follow on from the moved one.
+pub fn upcoming_runs_around_quiet_hours<Tz: TimeZone>(
+    schedule: &ScheduleConfig,
+    from: DateTime<Utc>,
+    count: usize,
+    quiet_hours: Option<&QuietHours>,
+    tz: &Tz,
+) -> Vec<DateTime<Utc>> {
+    let mut runs = Vec::with_capacity(count);
+    let mut last = from;
+    while runs.len() < count {
+        let Some(mut next) = next_run_time(schedule, last) else {
+            break;
+        };
+        if let Some(wait) =
+            quiet_hours.and_then(|q| time_until_quiet_end(q, next.with_timezone(tz).time()))
+        {
+            next += wait;
+        }
+        runs.push(next);
+        last = next;
+    }
+    runs
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -264,4 +305,104 @@ mod tests {
         let last_run = now - Duration::seconds(61);
         assert!(is_job_due(&schedule, Some(last_run), now));
     }
+
+    // --- upcoming_runs ---
+
+    #[test]
+    fn upcoming_runs_interval_sequence() {
+        let schedule = make_schedule(ScheduleType::Interval { minutes: 90 }, true);
+        let from = Utc.with_ymd_and_hms(2025, 6, 16, 10, 0, 0).unwrap();
+        assert_eq!(
+            upcoming_runs(&schedule, from, 3),
+            vec![
+                Utc.with_ymd_and_hms(2025, 6, 16, 11, 30, 0).unwrap(),
+                Utc.with_ymd_and_hms(2025, 6, 16, 13, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2025, 6, 16, 14, 30, 0).unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn upcoming_runs_cron_sequence() {
+        // Weekdays at 02:15; 2025-06-20 is a Friday
+        let schedule = make_schedule(
+            ScheduleType::Cron {
+                expression: "15 2 * * 1-5".to_string(),
+            },
+            true,
+        );
+        let from = Utc.with_ymd_and_hms(2025, 6, 19, 12, 0, 0).unwrap();
+        assert_eq!(
+            upcoming_runs(&schedule, from, 3),
+            vec![
+                Utc.with_ymd_and_hms(2025, 6, 20, 2, 15, 0).unwrap(),
+                Utc.with_ymd_and_hms(2025, 6, 23, 2, 15, 0).unwrap(),
+                Utc.with_ymd_and_hms(2025, 6, 24, 2, 15, 0).unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn upcoming_runs_disabled_or_invalid_is_empty() {
+        let disabled = make_schedule(ScheduleType::Interval { minutes: 10 }, false);
+        assert!(upcoming_runs(&disabled, Utc::now(), 10).is_empty());
+
+        let invalid = make_schedule(
+            ScheduleType::Cron {
+                expression: "not valid".to_string(),
+            },
+            true,
+        );
+        assert!(upcoming_runs(&invalid, Utc::now(), 10).is_empty());
+    }
+
+    #[test]
+    fn upcoming_runs_zero_count_is_empty() {
+        let schedule = make_schedule(ScheduleType::Interval { minutes: 10 }, true);
+        assert!(upcoming_runs(&schedule, Utc::now(), 0).is_empty());
+    }
+
+    #[test]
+    fn upcoming_runs_defer_past_quiet_hours() {
+        // Every 4 hours with quiet hours 22:00-06:00 UTC
+        let schedule = make_schedule(ScheduleType::Interval { minutes: 240 }, true);
+        let quiet = QuietHours {
+            start_minute: 22 * 60,
+            end_minute: 6 * 60,
+        };
+        let from = Utc.with_ymd_and_hms(2025, 6, 16, 14, 0, 0).unwrap();
+        assert_eq!(
+            upcoming_runs_around_quiet_hours(&schedule, from, 3, Some(&quiet), &Utc),
+            vec![
+                Utc.with_ymd_and_hms(2025, 6, 16, 18, 0, 0).unwrap(),
+                // 22:00 falls in quiet hours and catches up at 06:00
+                Utc.with_ymd_and_hms(2025, 6, 17, 6, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2025, 6, 17, 10, 0, 0).unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn upcoming_runs_cron_collapses_runs_inside_quiet_hours() {
+        // Hourly, with quiet hours 01:00-03:00 UTC
+        let schedule = make_schedule(
+            ScheduleType::Cron {
+                expression: "0 * * * *".to_string(),
+            },
+            true,
+        );
+        let quiet = QuietHours {
+            start_minute: 60,
+            end_minute: 180,
+        };
+        let from = Utc.with_ymd_and_hms(2025, 6, 16, 0, 30, 0).unwrap();
+        assert_eq!(
+            upcoming_runs_around_quiet_hours(&schedule, from, 3, Some(&quiet), &Utc),
+            vec![
+                Utc.with_ymd_and_hms(2025, 6, 16, 3, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2025, 6, 16, 4, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2025, 6, 16, 5, 0, 0).unwrap(),
+            ]
+        );
+    }
 }
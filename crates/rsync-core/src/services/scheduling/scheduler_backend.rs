@@ -1,17 +1,24 @@
 use std::sync::Arc;
 use std::time::Duration;
 
-use chrono::Utc;
+use chrono::{DateTime, Local, Utc};
+use uuid::Uuid;
 
 use crate::models::backup::InvocationTrigger;
-use crate::models::schedule::SchedulerConfig;
+use crate::models::job::JobDefinition;
+use crate::models::schedule::{SchedulerConfig, SchedulerDecision};
 use crate::services::execution_handler::ExecutionEventHandler;
 use crate::services::job_executor::JobExecutor;
 use crate::services::job_service::JobService;
+use crate::services::quiet_hours::suppressed_by_quiet_hours;
 use crate::services::retention_runner;
 use crate::services::scheduler;
+use crate::services::scheduler_events::SchedulerEventLog;
 use crate::services::settings_service::SettingsService;
 
+/// Called with a job's id just before the scheduler starts it.
+pub type JobScheduledCallback = Arc<dyn Fn(&Uuid) + Send + Sync>;
+
 /// Handle returned from starting a scheduler, allowing it to be stopped.
 pub struct SchedulerHandle {
     stop_sender: std::sync::mpsc::Sender<()>,
@@ -40,7 +47,8 @@ pub struct InProcessScheduler {
     settings_service: Arc<SettingsService>,
     handler_factory: Arc<dyn Fn() -> Arc<dyn ExecutionEventHandler> + Send + Sync>,
     /// Optional callback emitted when a job is scheduled (e.g., Tauri event).
-    on_job_scheduled: Option<Arc<dyn Fn(&uuid::Uuid) + Send + Sync>>,
+    on_job_scheduled: Option<JobScheduledCallback>,
+    events: Arc<SchedulerEventLog>,
 }
 
 impl InProcessScheduler {
@@ -58,13 +66,21 @@ impl InProcessScheduler {
             settings_service,
             handler_factory,
             on_job_scheduled: None,
+            events: Arc::new(SchedulerEventLog::default()),
         }
     }
 
-    pub fn with_on_job_scheduled(
-        mut self,
-        callback: Arc<dyn Fn(&uuid::Uuid) + Send + Sync>,
-    ) -> Self {
+    /// Record decisions into a shared log, so callers can query it.
+    pub fn with_event_log(mut self, events: Arc<SchedulerEventLog>) -> Self {
+        self.events = events;
+        self
+    }
+
+    pub fn event_log(&self) -> &Arc<SchedulerEventLog> {
+        &self.events
+    }
+
+    pub fn with_on_job_scheduled(mut self, callback: JobScheduledCallback) -> Self {
         self.on_job_scheduled = Some(callback);
         self
     }
@@ -76,11 +92,14 @@ impl SchedulerBackend for InProcessScheduler {
 
         let config_interval = self.config.check_interval_secs;
         let config_retention_n = self.config.retention_check_every_n_cycles;
-        let job_executor = Arc::clone(&self.job_executor);
         let job_service = Arc::clone(&self.job_service);
         let settings_service = Arc::clone(&self.settings_service);
-        let handler_factory = Arc::clone(&self.handler_factory);
-        let on_job_scheduled = self.on_job_scheduled.clone();
+        let events = Arc::clone(&self.events);
+        let runner = ExecutorRunner::new(
+            Arc::clone(&self.job_executor),
+            Arc::clone(&self.handler_factory),
+            self.on_job_scheduled.clone(),
+        );
 
         std::thread::spawn(move || {
             let mut cycle_count: u64 = 0;
@@ -99,6 +118,17 @@ impl SchedulerBackend for InProcessScheduler {
                     retention_runner::run_history_retention(&job_service, &settings_service);
                 }
 
+                // Quiet hours hold back every scheduled run; due jobs catch up afterwards
+                let quiet_hours = settings_service.get_quiet_hours().unwrap_or_else(|e| {
+                    log::error!("Scheduler: failed to read quiet hours: {}", e);
+                    None
+                });
+                let quiet = suppressed_by_quiet_hours(
+                    quiet_hours.as_ref(),
+                    &InvocationTrigger::Scheduled,
+                    Local::now().time(),
+                );
+
                 let jobs = match job_service.list_jobs() {
                     Ok(j) => j,
                     Err(e) => {
@@ -107,53 +137,7 @@ impl SchedulerBackend for InProcessScheduler {
                     }
                 };
 
-                let now = Utc::now();
-
-                for job in &jobs {
-                    // Skip disabled jobs or jobs without a schedule
-                    if !job.enabled {
-                        continue;
-                    }
-                    let schedule = match &job.schedule {
-                        Some(s) if s.enabled => s,
-                        _ => continue,
-                    };
-
-                    // Skip jobs that are currently running
-                    if job_executor.is_running(&job.id) {
-                        continue;
-                    }
-
-                    // Determine the last run time from history
-                    let last_run = job_service
-                        .get_job_history(&job.id, 1)
-                        .ok()
-                        .and_then(|h| h.first().map(|inv| inv.started_at));
-
-                    if scheduler::is_job_due(schedule, last_run, now) {
-                        log::info!(
-                            "Scheduler: job '{}' ({}) is due, executing",
-                            job.name,
-                            job.id
-                        );
-
-                        if let Some(ref callback) = on_job_scheduled {
-                            callback(&job.id);
-                        }
-
-                        let handler = handler_factory();
-                        if let Err(e) =
-                            job_executor.execute(job, InvocationTrigger::Scheduled, handler)
-                        {
-                            log::error!(
-                                "Scheduler: failed to execute job '{}' ({}): {}",
-                                job.name,
-                                job.id,
-                                e
-                            );
-                        }
-                    }
-                }
+                run_scheduler_cycle(&jobs, &job_service, &runner, quiet, Utc::now(), &events);
             }
         });
 
@@ -162,3 +146,111 @@ impl SchedulerBackend for InProcessScheduler {
         }
     }
 }
+
+/// What a scheduler cycle needs from the executor, so cycles can be
+/// exercised without spawning rsync.
+pub trait ScheduledRunner {
+    fn is_running(&self, job_id: &Uuid) -> bool;
+    fn run_scheduled(&self, job: &JobDefinition) -> Result<Uuid, String>;
+}
+
+/// Starts scheduled runs on the real executor.
+pub(crate) struct ExecutorRunner {
+    job_executor: Arc<JobExecutor>,
+    handler_factory: Arc<dyn Fn() -> Arc<dyn ExecutionEventHandler> + Send + Sync>,
+    on_job_scheduled: Option<JobScheduledCallback>,
+}
+
+impl ExecutorRunner {
+    pub(crate) fn new(
+        job_executor: Arc<JobExecutor>,
+        handler_factory: Arc<dyn Fn() -> Arc<dyn ExecutionEventHandler> + Send + Sync>,
+        on_job_scheduled: Option<JobScheduledCallback>,
+    ) -> Self {
+        Self {
+            job_executor,
+            handler_factory,
+            on_job_scheduled,
+        }
+    }
+}
+
+impl ScheduledRunner for ExecutorRunner {
+    fn is_running(&self, job_id: &Uuid) -> bool {
+        self.job_executor.is_running(job_id)
+    }
+
+    fn run_scheduled(&self, job: &JobDefinition) -> Result<Uuid, String> {
+        log::info!(
+            "Scheduler: job '{}' ({}) is due, executing",
+            job.name,
+            job.id
+        );
+
+        if let Some(ref callback) = self.on_job_scheduled {
+            callback(&job.id);
+        }
+
+        let handler = (self.handler_factory)();
+        self.job_executor
+            .execute(job, InvocationTrigger::Scheduled, handler)
+    }
+}
+
+/// Evaluate each scheduled job once, start the due ones, and record every
+/// decision in `events`. Jobs without a schedule are ignored. When `quiet`
+/// is set, due jobs are held back and recorded as `QuietHours`.
+pub fn run_scheduler_cycle(
+    jobs: &[JobDefinition],
+    job_service: &JobService,
+    runner: &dyn ScheduledRunner,
+    quiet: bool,
+    now: DateTime<Utc>,
+    events: &SchedulerEventLog,
+) {
+    for job in jobs {
+        let schedule = match &job.schedule {
+            Some(s) => s,
+            None => continue,
+        };
+        if !job.enabled || !schedule.enabled {
+            events.record(job.id, SchedulerDecision::Disabled);
+            continue;
+        }
+
+        if runner.is_running(&job.id) {
+            events.record(job.id, SchedulerDecision::AlreadyRunning);
+            continue;
+        }
+
+        // Determine the last run time from history
+        let last_run = job_service
+            .get_job_history(&job.id, 1)
+            .ok()
+            .and_then(|h| h.first().map(|inv| inv.started_at));
+
+        if !scheduler::is_job_due(schedule, last_run, now) {
+            events.record(job.id, SchedulerDecision::NotDue);
+            continue;
+        }
+
+        if quiet {
+            events.record(job.id, SchedulerDecision::QuietHours);
+            continue;
+        }
+
+        let decision = match runner.run_scheduled(job) {
+            Ok(_) => SchedulerDecision::Executed,
+            Err(e) => {
+                log::error!(
+                    "Scheduler: failed to execute job '{}' ({}): {}",
+                    job.name,
+                    job.id,
+                    e
+                );
+                SchedulerDecision::Refused { message: e }
+            }
+        };
+        events.record(job.id, decision);
+    }
+}
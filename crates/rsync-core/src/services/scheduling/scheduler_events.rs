@@ -0,0 +1,68 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::models::schedule::{SchedulerDecision, SchedulerEvent};
+
+/// How many decisions the scheduler keeps before dropping the oldest.
+pub const DEFAULT_SCHEDULER_EVENT_CAPACITY: usize = 1000;
+
+/// Bounded in-memory record of scheduler decisions, oldest dropped first.
+///
+/// Kept only for the life of the process: it answers "why didn't my job
+/// run?" for recent cycles, while run history covers everything that ran.
+pub struct SchedulerEventLog {
+    capacity: usize,
+    events: Mutex<VecDeque<SchedulerEvent>>,
+}
+
+impl SchedulerEventLog {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            events: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    pub fn record(&self, job_id: Uuid, decision: SchedulerDecision) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut events = self.events.lock().expect("lock poisoned");
+        if events.len() == self.capacity {
+            events.pop_front();
+        }
+        events.push_back(SchedulerEvent {
+            timestamp: Utc::now(),
+            job_id,
+            decision,
+        });
+    }
+
+    /// Decisions about `job_id`, newest first.
+    pub fn scheduler_events(&self, job_id: &Uuid) -> Vec<SchedulerEvent> {
+        let events = self.events.lock().expect("lock poisoned");
+        events
+            .iter()
+            .rev()
+            .filter(|e| &e.job_id == job_id)
+            .cloned()
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.lock().expect("lock poisoned").len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for SchedulerEventLog {
+    fn default() -> Self {
+        Self::new(DEFAULT_SCHEDULER_EVENT_CAPACITY)
+    }
+}
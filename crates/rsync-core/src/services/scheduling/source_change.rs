@@ -0,0 +1,86 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+
+use crate::file_system::real_file_system::RealFileSystem;
+use crate::file_system::{FileSystem, FsError};
+use crate::models::backup::InvocationStatus;
+use crate::models::job::JobDefinition;
+use crate::services::job_service::JobService;
+use crate::services::run_conditions::RunCondition;
+use crate::services::test_mode::command_was_dry_run;
+
+/// Newest modification time of `path` and, for a directory, everything under it.
+pub fn source_max_mtime(fs: &dyn FileSystem, path: &Path) -> Result<DateTime<Utc>, FsError> {
+    let mut newest = fs.modified(path)?;
+    if fs.is_dir(path) {
+        for entry in fs.walk_dir(path)? {
+            newest = newest.max(fs.modified(&entry)?);
+        }
+    }
+    Ok(newest)
+}
+
+/// Skip reason for a job whose source has not changed since `last_run`.
+///
+/// Only jobs with `skip_if_unchanged` and a local source are checked. A job
+/// that has never succeeded, or whose source cannot be scanned, always runs.
+pub fn unchanged_skip_reason(
+    fs: &dyn FileSystem,
+    job: &JobDefinition,
+    last_run: Option<DateTime<Utc>>,
+) -> Option<String> {
+    if !job.safety.skip_if_unchanged {
+        return None;
+    }
+    let source = job.transfer.source.local_path()?;
+    let last_run = last_run?;
+
+    match source_max_mtime(fs, Path::new(source)) {
+        Ok(newest) if newest <= last_run => Some(format!(
+            "source has not changed since the last successful run at {}",
+            last_run.to_rfc3339()
+        )),
+        Ok(_) => None,
+        Err(e) => {
+            log::warn!("Could not scan source {} for changes: {}", source, e);
+            None
+        }
+    }
+}
+
+/// Holds back scheduled runs of jobs marked `skip_if_unchanged` while
+/// nothing in their local source is newer than the last successful run.
+pub struct SourceUnchanged {
+    job_service: Arc<JobService>,
+}
+
+impl SourceUnchanged {
+    pub fn new(job_service: Arc<JobService>) -> Self {
+        Self { job_service }
+    }
+
+    /// Start time of the job's most recent successful run. Dry runs are
+    /// passed over: they left the destination as it was.
+    fn last_success_started(&self, job: &JobDefinition) -> Option<DateTime<Utc>> {
+        self.job_service
+            .get_job_history(&job.id, 50)
+            .ok()?
+            .into_iter()
+            .find(|inv| {
+                inv.status == InvocationStatus::Succeeded
+                    && !command_was_dry_run(&inv.execution_output.command_executed)
+            })
+            .map(|inv| inv.started_at)
+    }
+}
+
+impl RunCondition for SourceUnchanged {
+    fn skip_reason(&self, job: &JobDefinition) -> Option<String> {
+        if !job.safety.skip_if_unchanged {
+            return None;
+        }
+        unchanged_skip_reason(&RealFileSystem::new(), job, self.last_success_started(job))
+    }
+}
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+use uuid::Uuid;
+
+use crate::models::job::JobDefinition;
+use crate::services::execution_handler::ExecutionEventHandler;
+use crate::services::job_executor::JobExecutor;
+use crate::services::job_service::JobService;
+use crate::services::scheduler_backend::{ExecutorRunner, JobScheduledCallback, ScheduledRunner};
+
+/// Enabled jobs flagged `run_on_startup`, in list order.
+pub fn startup_jobs(jobs: &[JobDefinition]) -> Vec<&JobDefinition> {
+    jobs.iter()
+        .filter(|j| j.enabled && j.run_policy.run_on_startup)
+        .collect()
+}
+
+/// Start each startup job once. Jobs that are already running are skipped,
+/// and a refused run is logged without stopping the rest. Returns the
+/// invocation ids of the runs that started.
+pub fn run_startup_jobs(jobs: &[JobDefinition], runner: &dyn ScheduledRunner) -> Vec<Uuid> {
+    let mut started = Vec::new();
+    for job in startup_jobs(jobs) {
+        if runner.is_running(&job.id) {
+            log::info!(
+                "Startup: job '{}' ({}) is already running, skipping",
+                job.name,
+                job.id
+            );
+            continue;
+        }
+        match runner.run_scheduled(job) {
+            Ok(invocation_id) => started.push(invocation_id),
+            Err(e) => {
+                log::error!(
+                    "Startup: failed to run job '{}' ({}): {}",
+                    job.name,
+                    job.id,
+                    e
+                )
+            }
+        }
+    }
+    started
+}
+
+/// Start the startup jobs on a background thread so the caller's startup
+/// path isn't held up by listing jobs or spawning rsync.
+pub fn spawn_startup_runs(
+    job_executor: Arc<JobExecutor>,
+    job_service: Arc<JobService>,
+    handler_factory: Arc<dyn Fn() -> Arc<dyn ExecutionEventHandler> + Send + Sync>,
+    on_job_started: Option<JobScheduledCallback>,
+) -> JoinHandle<()> {
+    std::thread::spawn(move || {
+        let jobs = match job_service.list_jobs() {
+            Ok(j) => j,
+            Err(e) => {
+                log::error!("Startup: failed to list jobs: {}", e);
+                return;
+            }
+        };
+        let runner = ExecutorRunner::new(job_executor, handler_factory, on_job_started);
+        run_startup_jobs(&jobs, &runner);
+    })
+}
@@ -2,19 +2,49 @@ use std::sync::Arc;
 
 use crate::error::AppError;
 use crate::models::job::JobDefinition;
-use crate::models::settings::{DryModeSettings, RetentionSettings};
+use crate::models::settings::{
+    AnomalySettings, DryModeSettings, LogFileSettings, QuietHours, RetentionSettings,
+    SafeMirrorSettings, DEFAULT_ITEMIZE_STORE_LIMIT, DEFAULT_LINK_DEST_COUNT,
+    DEFAULT_LOG_PATH_TEMPLATE, DEFAULT_MAX_LOG_LINE_BYTES, DEFAULT_RSYNC_LOG_FORMAT,
+    MINUTES_PER_DAY,
+};
 use crate::repository::settings::SettingsRepository;
+use crate::services::command_builder::MAX_LINK_DEST_DIRS;
 
 const KEY_LOG_DIRECTORY: &str = "log_directory";
 const KEY_MAX_LOG_AGE_DAYS: &str = "max_log_age_days";
 const KEY_MAX_HISTORY_PER_JOB: &str = "max_history_per_job";
+const KEY_MAX_SUCCESS_PER_JOB: &str = "max_success_per_job";
+const KEY_MAX_FAILURE_PER_JOB: &str = "max_failure_per_job";
+const KEY_MAX_RECORD_AGE_DAYS: &str = "max_record_age_days";
 const KEY_AUTO_TRAILING_SLASH: &str = "auto_trailing_slash";
+const KEY_RELATIVE_PATHS: &str = "relative_paths";
 const KEY_DRY_MODE_ITEMIZE_CHANGES: &str = "dry_mode_itemize_changes";
 const KEY_DRY_MODE_CHECKSUM: &str = "dry_mode_checksum";
 const KEY_NAS_AUTO_DETECT: &str = "nas_auto_detect";
 const KEY_SHOW_FILE_HANDLING_OPTIONS: &str = "show_file_handling_options";
 const KEY_SHOW_METADATA_OPTIONS: &str = "show_metadata_options";
 const KEY_SHOW_OUTPUT_OPTIONS: &str = "show_output_options";
+const KEY_MONTHLY_DATA_BUDGET_BYTES: &str = "monthly_data_budget_bytes";
+const KEY_PRE_RUN_SPACE_MARGIN_PERCENT: &str = "pre_run_space_margin_percent";
+const KEY_LOG_PATH_TEMPLATE: &str = "log_path_template";
+const KEY_LOG_MAX_SIZE_BYTES: &str = "log_max_size_bytes";
+const KEY_LOG_STRUCTURED: &str = "log_structured";
+const KEY_LOG_MAX_LINE_BYTES: &str = "log_max_line_bytes";
+const KEY_LOG_RSYNC_LOG_FILE: &str = "log_rsync_log_file";
+const KEY_LOG_RSYNC_LOG_FORMAT: &str = "log_rsync_log_format";
+const KEY_ANOMALY_BASELINE_RUNS: &str = "anomaly_baseline_runs";
+const KEY_ANOMALY_FACTOR: &str = "anomaly_factor";
+const KEY_SAFE_MIRROR_ENABLED: &str = "safe_mirror_enabled";
+const KEY_SAFE_MIRROR_MAX_DELETE_PERCENT: &str = "safe_mirror_max_delete_percent";
+const KEY_ITEMIZE_STORE_LIMIT: &str = "itemize_store_limit";
+const KEY_LINK_DEST_COUNT: &str = "link_dest_count";
+const KEY_KNOWN_HOSTS_PATH: &str = "known_hosts_path";
+const KEY_SNAPSHOT_TRASH_DIR: &str = "snapshot_trash_dir";
+const KEY_QUIET_HOURS: &str = "quiet_hours";
+const KEY_TEST_MODE: &str = "test_mode";
+/// Prefix for stored rsync daemon passwords, keyed by secret name.
+const KEY_PREFIX_DAEMON_SECRET: &str = "daemon_secret.";
 
 const DEFAULT_NAS_AUTO_DETECT: bool = true;
 
@@ -52,6 +82,304 @@ impl SettingsService {
         self.settings.set_setting(KEY_LOG_DIRECTORY, path)
     }
 
+    /// Remove the configured log directory so the app default applies again.
+    pub fn clear_log_directory(&self) -> Result<(), AppError> {
+        self.settings.delete_setting(KEY_LOG_DIRECTORY)
+    }
+
+    /// Directory pruned snapshots are moved to instead of being deleted.
+    /// `None` means pruned snapshots are deleted outright.
+    pub fn get_snapshot_trash_dir(&self) -> Result<Option<String>, AppError> {
+        Ok(self
+            .settings
+            .get_setting(KEY_SNAPSHOT_TRASH_DIR)?
+            .filter(|v| !v.trim().is_empty()))
+    }
+
+    pub fn set_snapshot_trash_dir(&self, path: &str) -> Result<(), AppError> {
+        self.settings.set_setting(KEY_SNAPSHOT_TRASH_DIR, path)
+    }
+
+    /// Go back to deleting pruned snapshots outright.
+    pub fn clear_snapshot_trash_dir(&self) -> Result<(), AppError> {
+        self.settings.delete_setting(KEY_SNAPSHOT_TRASH_DIR)
+    }
+
+    /// The `known_hosts` file confirmed host keys are added to. `None` means
+    /// the caller's default (normally `~/.ssh/known_hosts`).
+    pub fn get_known_hosts_path(&self) -> Result<Option<String>, AppError> {
+        self.settings.get_setting(KEY_KNOWN_HOSTS_PATH)
+    }
+
+    pub fn set_known_hosts_path(&self, path: &str) -> Result<(), AppError> {
+        self.settings.set_setting(KEY_KNOWN_HOSTS_PATH, path)
+    }
+
+    pub fn get_log_file_settings(&self) -> Result<LogFileSettings, AppError> {
+        let path_template = self
+            .settings
+            .get_setting(KEY_LOG_PATH_TEMPLATE)?
+            .filter(|v| !v.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_LOG_PATH_TEMPLATE.to_string());
+
+        let max_size_bytes = self
+            .settings
+            .get_setting(KEY_LOG_MAX_SIZE_BYTES)?
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|&v| v > 0);
+
+        let structured = self
+            .settings
+            .get_setting(KEY_LOG_STRUCTURED)?
+            .is_some_and(|v| v == "true");
+
+        let max_line_bytes = self
+            .settings
+            .get_setting(KEY_LOG_MAX_LINE_BYTES)?
+            .and_then(|v| v.parse::<usize>().ok())
+            .filter(|&v| v > 0)
+            .unwrap_or(DEFAULT_MAX_LOG_LINE_BYTES);
+
+        let rsync_log_file = self
+            .settings
+            .get_setting(KEY_LOG_RSYNC_LOG_FILE)?
+            .is_some_and(|v| v == "true");
+
+        let rsync_log_format = self
+            .settings
+            .get_setting(KEY_LOG_RSYNC_LOG_FORMAT)?
+            .filter(|v| !v.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_RSYNC_LOG_FORMAT.to_string());
+
+        Ok(LogFileSettings {
+            path_template,
+            max_size_bytes,
+            structured,
+            max_line_bytes,
+            rsync_log_file,
+            rsync_log_format,
+        })
+    }
+
+    pub fn set_log_file_settings(&self, settings: &LogFileSettings) -> Result<(), AppError> {
+        self.settings
+            .set_setting(KEY_LOG_PATH_TEMPLATE, &settings.path_template)?;
+        match settings.max_size_bytes {
+            Some(max) => self
+                .settings
+                .set_setting(KEY_LOG_MAX_SIZE_BYTES, &max.to_string())?,
+            None => self.settings.delete_setting(KEY_LOG_MAX_SIZE_BYTES)?,
+        }
+        self.settings.set_setting(
+            KEY_LOG_STRUCTURED,
+            if settings.structured { "true" } else { "false" },
+        )?;
+        self.settings
+            .set_setting(KEY_LOG_MAX_LINE_BYTES, &settings.max_line_bytes.to_string())?;
+        self.settings.set_setting(
+            KEY_LOG_RSYNC_LOG_FILE,
+            if settings.rsync_log_file {
+                "true"
+            } else {
+                "false"
+            },
+        )?;
+        self.settings
+            .set_setting(KEY_LOG_RSYNC_LOG_FORMAT, &settings.rsync_log_format)?;
+        Ok(())
+    }
+
+    /// Monthly transfer cap in bytes, or `None` when unlimited.
+    pub fn get_monthly_data_budget(&self) -> Result<Option<u64>, AppError> {
+        Ok(self
+            .settings
+            .get_setting(KEY_MONTHLY_DATA_BUDGET_BYTES)?
+            .and_then(|v| v.parse::<u64>().ok()))
+    }
+
+    pub fn set_monthly_data_budget(&self, bytes: Option<u64>) -> Result<(), AppError> {
+        match bytes {
+            Some(b) => self
+                .settings
+                .set_setting(KEY_MONTHLY_DATA_BUDGET_BYTES, &b.to_string()),
+            None => self.settings.delete_setting(KEY_MONTHLY_DATA_BUDGET_BYTES),
+        }
+    }
+
+    /// Headroom in percent of the source size that a local destination must
+    /// have free right before each run, or `None` to skip that re-check.
+    pub fn get_pre_run_space_margin(&self) -> Result<Option<u32>, AppError> {
+        Ok(self
+            .settings
+            .get_setting(KEY_PRE_RUN_SPACE_MARGIN_PERCENT)?
+            .and_then(|v| v.parse::<u32>().ok()))
+    }
+
+    pub fn set_pre_run_space_margin(&self, percent: Option<u32>) -> Result<(), AppError> {
+        match percent {
+            Some(p) => self
+                .settings
+                .set_setting(KEY_PRE_RUN_SPACE_MARGIN_PERCENT, &p.to_string()),
+            None => self
+                .settings
+                .delete_setting(KEY_PRE_RUN_SPACE_MARGIN_PERCENT),
+        }
+    }
+
+    /// The rsync daemon password stored under `name`.
+    pub fn get_daemon_secret(&self, name: &str) -> Result<Option<String>, AppError> {
+        self.settings
+            .get_setting(&format!("{}{}", KEY_PREFIX_DAEMON_SECRET, name))
+    }
+
+    pub fn set_daemon_secret(&self, name: &str, password: &str) -> Result<(), AppError> {
+        if name.trim().is_empty() {
+            return Err(AppError::ValidationError(
+                "Secret name must not be empty".to_string(),
+            ));
+        }
+        self.settings
+            .set_setting(&format!("{}{}", KEY_PREFIX_DAEMON_SECRET, name), password)
+    }
+
+    pub fn delete_daemon_secret(&self, name: &str) -> Result<(), AppError> {
+        self.settings
+            .delete_setting(&format!("{}{}", KEY_PREFIX_DAEMON_SECRET, name))
+    }
+
+    /// Daily window during which scheduled runs are suppressed, or `None`.
+    pub fn get_quiet_hours(&self) -> Result<Option<QuietHours>, AppError> {
+        Ok(self
+            .settings
+            .get_setting(KEY_QUIET_HOURS)?
+            .and_then(|v| QuietHours::parse(&v)))
+    }
+
+    pub fn set_quiet_hours(&self, quiet_hours: Option<&QuietHours>) -> Result<(), AppError> {
+        match quiet_hours {
+            Some(q) => {
+                if q.start_minute >= MINUTES_PER_DAY || q.end_minute >= MINUTES_PER_DAY {
+                    return Err(AppError::ValidationError(
+                        "Quiet hours must be times within a day".to_string(),
+                    ));
+                }
+                self.settings
+                    .set_setting(KEY_QUIET_HOURS, &q.to_setting_value())
+            }
+            None => self.settings.delete_setting(KEY_QUIET_HOURS),
+        }
+    }
+
+    /// Maximum itemized changes stored per invocation.
+    pub fn get_itemize_store_limit(&self) -> Result<usize, AppError> {
+        Ok(self
+            .settings
+            .get_setting(KEY_ITEMIZE_STORE_LIMIT)?
+            .and_then(|v| v.parse::<usize>().ok())
+            .unwrap_or(DEFAULT_ITEMIZE_STORE_LIMIT))
+    }
+
+    pub fn set_itemize_store_limit(&self, limit: usize) -> Result<(), AppError> {
+        self.settings
+            .set_setting(KEY_ITEMIZE_STORE_LIMIT, &limit.to_string())
+    }
+
+    /// How many recent snapshots a snapshot run passes as `--link-dest`.
+    pub fn get_link_dest_count(&self) -> Result<usize, AppError> {
+        Ok(self
+            .settings
+            .get_setting(KEY_LINK_DEST_COUNT)?
+            .and_then(|v| v.parse::<usize>().ok())
+            .unwrap_or(DEFAULT_LINK_DEST_COUNT))
+    }
+
+    pub fn set_link_dest_count(&self, count: usize) -> Result<(), AppError> {
+        if !(1..=MAX_LINK_DEST_DIRS).contains(&count) {
+            return Err(AppError::ValidationError(format!(
+                "Link-dest snapshots must be between 1 and {}",
+                MAX_LINK_DEST_DIRS
+            )));
+        }
+        self.settings
+            .set_setting(KEY_LINK_DEST_COUNT, &count.to_string())
+    }
+
+    pub fn get_anomaly_settings(&self) -> Result<AnomalySettings, AppError> {
+        let defaults = AnomalySettings::default();
+        let baseline_runs = self
+            .settings
+            .get_setting(KEY_ANOMALY_BASELINE_RUNS)?
+            .and_then(|v| v.parse::<usize>().ok())
+            .filter(|&v| v > 0)
+            .unwrap_or(defaults.baseline_runs);
+        let factor = self
+            .settings
+            .get_setting(KEY_ANOMALY_FACTOR)?
+            .and_then(|v| v.parse::<f64>().ok())
+            .filter(|&v| v > 1.0)
+            .unwrap_or(defaults.factor);
+
+        Ok(AnomalySettings {
+            baseline_runs,
+            factor,
+        })
+    }
+
+    pub fn set_anomaly_settings(&self, settings: &AnomalySettings) -> Result<(), AppError> {
+        if settings.baseline_runs == 0 {
+            return Err(AppError::ValidationError(
+                "Baseline must include at least one run".to_string(),
+            ));
+        }
+        if settings.factor.is_nan() || settings.factor <= 1.0 {
+            return Err(AppError::ValidationError(
+                "Anomaly factor must be greater than 1".to_string(),
+            ));
+        }
+        self.settings.set_setting(
+            KEY_ANOMALY_BASELINE_RUNS,
+            &settings.baseline_runs.to_string(),
+        )?;
+        self.settings
+            .set_setting(KEY_ANOMALY_FACTOR, &settings.factor.to_string())
+    }
+
+    pub fn get_safe_mirror_settings(&self) -> Result<SafeMirrorSettings, AppError> {
+        let defaults = SafeMirrorSettings::default();
+        let enabled = self
+            .settings
+            .get_setting(KEY_SAFE_MIRROR_ENABLED)?
+            .map(|v| v == "true")
+            .unwrap_or(defaults.enabled);
+        let max_delete_percent = self
+            .settings
+            .get_setting(KEY_SAFE_MIRROR_MAX_DELETE_PERCENT)?
+            .and_then(|v| v.parse::<f64>().ok())
+            .filter(|&v| (0.0..=100.0).contains(&v))
+            .unwrap_or(defaults.max_delete_percent);
+
+        Ok(SafeMirrorSettings {
+            enabled,
+            max_delete_percent,
+        })
+    }
+
+    pub fn set_safe_mirror_settings(&self, settings: &SafeMirrorSettings) -> Result<(), AppError> {
+        if !(0.0..=100.0).contains(&settings.max_delete_percent) {
+            return Err(AppError::ValidationError(
+                "Deletion limit must be between 0 and 100 percent".to_string(),
+            ));
+        }
+        self.settings.set_setting(
+            KEY_SAFE_MIRROR_ENABLED,
+            if settings.enabled { "true" } else { "false" },
+        )?;
+        self.settings.set_setting(
+            KEY_SAFE_MIRROR_MAX_DELETE_PERCENT,
+            &settings.max_delete_percent.to_string(),
+        )
+    }
+
     pub fn get_retention_settings(&self) -> Result<RetentionSettings, AppError> {
         let max_age = self
             .settings
@@ -65,9 +393,27 @@ impl SettingsService {
             .and_then(|v| v.parse::<usize>().ok())
             .unwrap_or(DEFAULT_MAX_HISTORY_PER_JOB);
 
+        let max_success_per_job = self
+            .settings
+            .get_setting(KEY_MAX_SUCCESS_PER_JOB)?
+            .and_then(|v| v.parse::<usize>().ok());
+
+        let max_failure_per_job = self
+            .settings
+            .get_setting(KEY_MAX_FAILURE_PER_JOB)?
+            .and_then(|v| v.parse::<usize>().ok());
+
+        let max_record_age_days = self
+            .settings
+            .get_setting(KEY_MAX_RECORD_AGE_DAYS)?
+            .and_then(|v| v.parse::<u32>().ok());
+
         Ok(RetentionSettings {
             max_log_age_days: max_age,
             max_history_per_job: max_per_job,
+            max_success_per_job,
+            max_failure_per_job,
+            max_record_age_days,
         })
     }
 
@@ -86,11 +432,54 @@ impl SettingsService {
         )
     }
 
+    /// Whether generated commands use `--relative` with the source anchored at
+    /// its last component instead of the plain source path.
+    pub fn get_relative_paths(&self) -> Result<bool, AppError> {
+        Ok(self
+            .settings
+            .get_setting(KEY_RELATIVE_PATHS)?
+            .map(|v| v == "true")
+            .unwrap_or(false))
+    }
+
+    pub fn set_relative_paths(&self, enabled: bool) -> Result<(), AppError> {
+        self.settings
+            .set_setting(KEY_RELATIVE_PATHS, if enabled { "true" } else { "false" })
+    }
+
+    /// Whether test mode is on. While it is, every run is forced to be a dry run.
+    pub fn get_test_mode(&self) -> Result<bool, AppError> {
+        Ok(self
+            .settings
+            .get_setting(KEY_TEST_MODE)?
+            .is_some_and(|v| v == "true"))
+    }
+
+    pub fn set_test_mode(&self, enabled: bool) -> Result<(), AppError> {
+        self.settings
+            .set_setting(KEY_TEST_MODE, if enabled { "true" } else { "false" })
+    }
+
     pub fn set_retention_settings(&self, settings: &RetentionSettings) -> Result<(), AppError> {
         self.settings
             .set_setting(KEY_MAX_LOG_AGE_DAYS, &settings.max_log_age_days.to_string())?;
         self.settings
             .set_setting(KEY_MAX_HISTORY_PER_JOB, &settings.max_history_per_job.to_string())?;
+        for (key, value) in [
+            (KEY_MAX_SUCCESS_PER_JOB, settings.max_success_per_job),
+            (KEY_MAX_FAILURE_PER_JOB, settings.max_failure_per_job),
+        ] {
+            match value {
+                Some(max) => self.settings.set_setting(key, &max.to_string())?,
+                None => self.settings.delete_setting(key)?,
+            }
+        }
+        match settings.max_record_age_days {
+            Some(days) => self
+                .settings
+                .set_setting(KEY_MAX_RECORD_AGE_DAYS, &days.to_string())?,
+            None => self.settings.delete_setting(KEY_MAX_RECORD_AGE_DAYS)?,
+        }
         Ok(())
     }
 
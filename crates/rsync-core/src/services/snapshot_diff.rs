@@ -0,0 +1,71 @@
+use crate::error::AppError;
+use crate::models::backup::SnapshotRecord;
+use crate::models::itemize::{DifferenceKind, SnapshotDiff, TransferType};
+use crate::rsync_client::RsyncClient;
+use crate::services::itemize_parser::parse_itemize_line;
+
+/// A snapshot path as an rsync directory argument, so the contents are
+/// compared rather than the directory itself.
+fn dir_arg(path: &str) -> String {
+    format!("{}/", path.trim_end_matches('/'))
+}
+
+/// Compare snapshot `a` against the later snapshot `b` with an itemized
+/// `--dry-run`, so nothing on either side is touched.
+///
+/// rsync is pointed from `b` to `a` with `--delete`: files it would copy are
+/// created or updated in `b`, and files it would delete only exist in `a`.
+pub fn diff_snapshots(
+    a: &SnapshotRecord,
+    b: &SnapshotRecord,
+    rsync: &dyn RsyncClient,
+) -> Result<SnapshotDiff, AppError> {
+    if a.job_id != b.job_id {
+        return Err(AppError::ValidationError(
+            "Only snapshots of the same job can be compared".to_string(),
+        ));
+    }
+    let args = vec![
+        "-ni".to_string(),
+        "--dry-run".to_string(),
+        "-a".to_string(),
+        "--delete".to_string(),
+        dir_arg(&b.snapshot_path),
+        dir_arg(&a.snapshot_path),
+    ];
+    let result = rsync.dry_run(&args)?;
+
+    let mut diff = SnapshotDiff {
+        from_path: a.snapshot_path.clone(),
+        to_path: b.snapshot_path.clone(),
+        created: Vec::new(),
+        updated: Vec::new(),
+        deleted: Vec::new(),
+    };
+    for change in result.stdout.lines().filter_map(parse_itemize_line) {
+        // The snapshot roots themselves always differ in timestamp
+        if change.path == "./" {
+            continue;
+        }
+        if change.transfer_type == TransferType::Message {
+            diff.deleted.push(change);
+        } else if change.differences.contains(&DifferenceKind::NewlyCreated) {
+            diff.created.push(change);
+        } else {
+            diff.updated.push(change);
+        }
+    }
+    Ok(diff)
+}
+
+/// The two most recent snapshots in `snapshots`, oldest first.
+pub fn latest_snapshot_pair(
+    snapshots: &[SnapshotRecord],
+) -> Option<(&SnapshotRecord, &SnapshotRecord)> {
+    let mut sorted: Vec<&SnapshotRecord> = snapshots.iter().collect();
+    sorted.sort_by_key(|s| s.created_at);
+    match sorted.as_slice() {
+        [.., older, newer] => Some((older, newer)),
+        _ => None,
+    }
+}
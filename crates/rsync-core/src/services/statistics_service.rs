@@ -1,20 +1,36 @@
 use std::sync::Arc;
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use regex::Regex;
 use uuid::Uuid;
 
 use crate::error::AppError;
-use crate::models::backup::BackupInvocation;
-use crate::models::statistics::{AggregatedStats, RunStatistic};
+use crate::models::audit::AuditAction;
+use crate::models::backup::{BackupInvocation, InvocationStatus};
+use crate::models::settings::AnomalySettings;
+use crate::models::statistics::{AggregatedStats, RunAnomaly, RunStatistic};
 use crate::repository::statistics::StatisticsRepository;
+use crate::services::audit::AuditLog;
+use crate::services::job_service::JobService;
+use crate::services::test_mode::command_was_dry_run;
+
+/// Fewer previous runs than this are too little to judge a run against.
+const MIN_ANOMALY_BASELINE_RUNS: usize = 3;
 
 pub struct StatisticsService {
     stats: Arc<dyn StatisticsRepository>,
+    audit: Option<Arc<AuditLog>>,
 }
 
 impl StatisticsService {
     pub fn new(stats: Arc<dyn StatisticsRepository>) -> Self {
-        Self { stats }
+        Self { stats, audit: None }
+    }
+
+    /// Record statistics resets in `audit`.
+    pub fn with_audit_log(mut self, audit: Arc<AuditLog>) -> Self {
+        self.audit = Some(audit);
+        self
     }
 
     /// Record a run statistic after a successful job completion.
@@ -24,23 +40,89 @@ impl StatisticsService {
         inv: &BackupInvocation,
         speedup: Option<f64>,
     ) -> Result<(), AppError> {
-        let duration_secs = match inv.finished_at {
-            Some(finished) => (finished - inv.started_at).num_milliseconds() as f64 / 1000.0,
-            None => 0.0,
-        };
-
-        let stat = RunStatistic {
-            id: Uuid::new_v4(),
-            job_id,
+        self.stats
+            .record_statistic(&statistic_for(job_id, inv, speedup, Utc::now()))
+    }
+
+    /// Clear every statistic and rebuild them from the stored history: one per
+    /// successful, non-dry-run invocation, with the speedup read back from the
+    /// invocation's log when it still exists. Returns the number recorded.
+    pub fn recompute_from_invocations(&self, job_service: &JobService) -> Result<usize, AppError> {
+        let invocations = job_service.list_all_invocations()?;
+        self.stats.delete_all_statistics()?;
+
+        let mut recorded = 0;
+        for inv in invocations.iter().filter(|inv| {
+            inv.status == InvocationStatus::Succeeded
+                && !command_was_dry_run(&inv.execution_output.command_executed)
+        }) {
+            let recorded_at = inv.finished_at.unwrap_or(inv.started_at);
+            let speedup = inv
+                .execution_output
+                .log_file_path
+                .as_deref()
+                .and_then(speedup_from_log);
+            self.stats
+                .record_statistic(&statistic_for(inv.job_id, inv, speedup, recorded_at))?;
+            recorded += 1;
+        }
+
+        if let Some(audit) = &self.audit {
+            audit.record(
+                AuditAction::StatisticsReset,
+                None,
+                format!("Recomputed statistics from history ({} runs)", recorded),
+            );
+        }
+        Ok(recorded)
+    }
+
+    /// Compare an invocation's transfer with the average of the job's last
+    /// `baseline_runs` recorded runs (excluding the invocation itself).
+    ///
+    /// Returns `None` until the job has enough history to form a baseline.
+    pub fn run_anomaly(
+        &self,
+        job_id: &Uuid,
+        inv: &BackupInvocation,
+        settings: &AnomalySettings,
+    ) -> Result<Option<RunAnomaly>, AppError> {
+        // Statistics come back newest first
+        let baseline: Vec<RunStatistic> = self
+            .stats
+            .get_statistics_for_job(job_id)?
+            .into_iter()
+            .filter(|s| s.invocation_id != inv.id)
+            .take(settings.baseline_runs)
+            .collect();
+        if baseline.len() < MIN_ANOMALY_BASELINE_RUNS.min(settings.baseline_runs) {
+            return Ok(None);
+        }
+
+        let n = baseline.len() as f64;
+        let avg_bytes = baseline
+            .iter()
+            .map(|s| s.bytes_transferred as f64)
+            .sum::<f64>()
+            / n;
+        let avg_files = baseline
+            .iter()
+            .map(|s| s.files_transferred as f64)
+            .sum::<f64>()
+            / n;
+        // Floor the averages so a history of no-op runs does not divide by zero
+        let bytes_ratio = inv.transfer_stats.bytes_transferred as f64 / avg_bytes.max(1.0);
+        let files_ratio = inv.transfer_stats.files_transferred as f64 / avg_files.max(1.0);
+
+        Ok(Some(RunAnomaly {
             invocation_id: inv.id,
-            recorded_at: Utc::now(),
-            files_transferred: inv.transfer_stats.files_transferred,
-            bytes_transferred: inv.transfer_stats.bytes_transferred,
-            duration_secs,
-            speedup,
-        };
-
-        self.stats.record_statistic(&stat)
+            baseline_runs: baseline.len(),
+            avg_bytes_transferred: avg_bytes,
+            avg_files_transferred: avg_files,
+            bytes_ratio,
+            files_ratio,
+            is_anomaly: bytes_ratio > settings.factor || files_ratio > settings.factor,
+        }))
     }
 
     pub fn get_aggregated(&self) -> Result<AggregatedStats, AppError> {
@@ -59,19 +141,78 @@ impl StatisticsService {
             .map_err(|e| AppError::SerializationError(e.to_string()))
     }
 
+    pub fn list_all(&self) -> Result<Vec<RunStatistic>, AppError> {
+        self.stats.get_all_statistics()
+    }
+
+    /// Insert or overwrite a statistic as-is. Used when restoring a full export.
+    pub fn upsert(&self, stat: &RunStatistic) -> Result<(), AppError> {
+        self.stats.upsert_statistic(stat)
+    }
+
     pub fn reset(&self) -> Result<(), AppError> {
-        self.stats.delete_all_statistics()
+        self.stats.delete_all_statistics()?;
+        if let Some(audit) = &self.audit {
+            audit.record(
+                AuditAction::StatisticsReset,
+                None,
+                "Reset statistics for all jobs".to_string(),
+            );
+        }
+        Ok(())
     }
 
     pub fn reset_for_job(&self, job_id: &Uuid) -> Result<(), AppError> {
-        self.stats.delete_statistics_for_job(job_id)
+        self.stats.delete_statistics_for_job(job_id)?;
+        if let Some(audit) = &self.audit {
+            audit.record(
+                AuditAction::StatisticsReset,
+                Some(*job_id),
+                format!("Reset statistics for job {}", job_id),
+            );
+        }
+        Ok(())
     }
 }
 
+fn statistic_for(
+    job_id: Uuid,
+    inv: &BackupInvocation,
+    speedup: Option<f64>,
+    recorded_at: DateTime<Utc>,
+) -> RunStatistic {
+    let duration_secs = match inv.finished_at {
+        Some(finished) => (finished - inv.started_at).num_milliseconds() as f64 / 1000.0,
+        None => 0.0,
+    };
+
+    RunStatistic {
+        id: Uuid::new_v4(),
+        job_id,
+        invocation_id: inv.id,
+        recorded_at,
+        files_transferred: inv.transfer_stats.files_transferred,
+        bytes_transferred: inv.transfer_stats.bytes_transferred,
+        files_deleted: inv.transfer_stats.files_deleted,
+        duration_secs,
+        speedup,
+    }
+}
+
+/// The last `speedup is X` of rsync's summary in a run's log, if it is still on disk.
+fn speedup_from_log(path: &str) -> Option<f64> {
+    let log = std::fs::read_to_string(path).ok()?;
+    let re = Regex::new(r"speedup is ([\d.]+)").ok()?;
+    log.lines()
+        .rev()
+        .find_map(|line| re.captures(line)?.get(1)?.as_str().parse().ok())
+}
+
 fn aggregate(stats: &[RunStatistic]) -> AggregatedStats {
     let total_jobs_run = stats.len() as u64;
     let total_files_transferred: u64 = stats.iter().map(|s| s.files_transferred).sum();
     let total_bytes_transferred: u64 = stats.iter().map(|s| s.bytes_transferred).sum();
+    let total_files_deleted: u64 = stats.iter().map(|s| s.files_deleted).sum();
     let total_duration_secs: f64 = stats.iter().map(|s| s.duration_secs).sum();
 
     // Time saved: for each run with a speedup > 1, the time it would have taken
@@ -93,6 +234,7 @@ fn aggregate(stats: &[RunStatistic]) -> AggregatedStats {
         total_jobs_run,
         total_files_transferred,
         total_bytes_transferred,
+        total_files_deleted,
         total_duration_secs,
         total_time_saved_secs,
     }
@@ -0,0 +1,47 @@
+use crate::error::AppError;
+use crate::models::job::JobDefinition;
+use crate::models::statistics::TransferEstimate;
+use crate::rsync_client::RsyncClient;
+use crate::services::command_builder::build_rsync_args;
+use crate::services::progress_parser::parse_stats_block;
+
+/// Estimate what running `job` would transfer by doing a `--dry-run --stats`
+/// pass, without copying anything.
+///
+/// `link_dest` should be the latest snapshot for snapshot-mode jobs, so
+/// files that would be hard-linked are not counted.
+pub fn estimate_transfer(
+    job: &JobDefinition,
+    rsync: &dyn RsyncClient,
+    link_dest: Option<&str>,
+    auto_trailing_slash: bool,
+    relative_paths: bool,
+) -> Result<TransferEstimate, AppError> {
+    let mut options = job.options.clone();
+    options.core_transfer.dry_run = true;
+    options.output.stats = true;
+    // Per-file output only slows the pass down
+    options.output.progress = false;
+    options.output.itemize_changes = false;
+
+    let args = build_rsync_args(
+        &job.transfer.source,
+        &job.transfer.destination,
+        &options,
+        job.ssh_config.as_ref(),
+        link_dest.as_slice(),
+        auto_trailing_slash,
+        relative_paths,
+    );
+    let result = rsync.dry_run(&args)?;
+
+    let stats = parse_stats_block(&result.stdout).ok_or_else(|| {
+        AppError::ValidationError("rsync output had no --stats summary".to_string())
+    })?;
+    Ok(TransferEstimate {
+        bytes_to_transfer: stats.transferred_file_size.unwrap_or(0),
+        files_to_transfer: stats.files_transferred.unwrap_or(0),
+        total_size_bytes: stats.total_file_size.unwrap_or(0),
+        total_files: stats.total_files.unwrap_or(0),
+    })
+}
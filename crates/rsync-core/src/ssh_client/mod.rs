@@ -0,0 +1,11 @@
+pub mod process_ssh_client;
+
+use crate::rsync_client::RsyncError;
+
+pub trait SshClient {
+    /// Run `ssh-keyscan` against `host` and return its stdout.
+    fn keyscan(&self, host: &str, port: u16) -> Result<String, RsyncError>;
+
+    /// Run `ssh` with `args` (options, target and remote command).
+    fn run(&self, args: &[String]) -> Result<(), RsyncError>;
+}
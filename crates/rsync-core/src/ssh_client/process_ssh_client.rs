@@ -0,0 +1,68 @@
+use std::process::Command;
+
+use super::SshClient;
+use crate::rsync_client::RsyncError;
+
+/// Seconds `ssh-keyscan` waits for a host before giving up.
+const KEYSCAN_TIMEOUT_SECS: u32 = 10;
+
+pub struct ProcessSshClient {
+    keyscan_binary: String,
+    ssh_binary: String,
+}
+
+impl ProcessSshClient {
+    pub fn new() -> Self {
+        Self {
+            keyscan_binary: "ssh-keyscan".to_string(),
+            ssh_binary: "ssh".to_string(),
+        }
+    }
+}
+
+impl Default for ProcessSshClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SshClient for ProcessSshClient {
+    fn keyscan(&self, host: &str, port: u16) -> Result<String, RsyncError> {
+        let output = Command::new(&self.keyscan_binary)
+            .args([
+                "-p".to_string(),
+                port.to_string(),
+                "-T".to_string(),
+                KEYSCAN_TIMEOUT_SECS.to_string(),
+                "--".to_string(),
+                host.to_string(),
+            ])
+            .output()
+            .map_err(|e| RsyncError::IoError(format!("{}: {}", self.keyscan_binary, e)))?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        // ssh-keyscan exits 0 even when the host is unreachable; no keys is the failure
+        if !output.status.success() || stdout.trim().is_empty() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(RsyncError::SshError(format!(
+                "No host keys returned for {}:{}: {}",
+                host,
+                port,
+                stderr.trim()
+            )));
+        }
+        Ok(stdout)
+    }
+
+    fn run(&self, args: &[String]) -> Result<(), RsyncError> {
+        let output = Command::new(&self.ssh_binary)
+            .args(args)
+            .output()
+            .map_err(|e| RsyncError::IoError(format!("{}: {}", self.ssh_binary, e)))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(RsyncError::SshError(stderr.trim().to_string()));
+        }
+        Ok(())
+    }
+}
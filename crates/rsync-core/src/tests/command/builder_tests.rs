@@ -2,7 +2,9 @@ use crate::models::job::{
     AdvancedOptions, CoreTransferOptions, FileHandlingOptions, MetadataOptions, OutputOptions,
     RsyncOptions, SshConfig, StorageLocation,
 };
-use crate::services::command_builder::build_rsync_args;
+use crate::services::command_builder::{
+    build_rsync_args, relative_source_path, MAX_LINK_DEST_DIRS, SYSTEM_FILE_EXCLUDES,
+};
 
 fn local(path: &str) -> StorageLocation {
     StorageLocation::Local {
@@ -27,7 +29,8 @@ fn test_basic_archive_args() {
             ..default_opts()
         },
         None,
-        None,
+        &[],
+        false,
         false,
     );
     assert!(args.contains(&"-a".to_string()));
@@ -40,6 +43,7 @@ fn test_all_flags_enabled() {
             archive: true,
             compress: true,
             partial: true,
+            partial_dir: None,
             dry_run: true,
         },
         output: OutputOptions {
@@ -54,7 +58,15 @@ fn test_all_flags_enabled() {
         },
         ..default_opts()
     };
-    let args = build_rsync_args(&local("/src/"), &local("/dst/"), &options, None, None, false);
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/dst/"),
+        &options,
+        None,
+        &[],
+        false,
+        false,
+    );
 
     assert!(args.contains(&"-a".to_string()));
     assert!(args.contains(&"-z".to_string()));
@@ -72,6 +84,9 @@ fn test_ssh_config_produces_e_flag() {
         port: 2222,
         identity_file: Some("/home/user/.ssh/key".to_string()),
         strict_host_key_checking: true,
+        connect_timeout: None,
+        server_alive_interval: None,
+        jump_host: None,
         custom_ssh_command: None,
     };
     let args = build_rsync_args(
@@ -79,7 +94,8 @@ fn test_ssh_config_produces_e_flag() {
         &local("/dst/"),
         &default_opts(),
         Some(&ssh),
-        None,
+        &[],
+        false,
         false,
     );
 
@@ -92,6 +108,81 @@ fn test_ssh_config_produces_e_flag() {
     assert!(ssh_cmd.contains("-i /home/user/.ssh/key"));
 }
 
+fn ssh_command_for(ssh: &SshConfig) -> String {
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/dst/"),
+        &default_opts(),
+        Some(ssh),
+        &[],
+        false,
+        false,
+    );
+    let pos = args
+        .iter()
+        .position(|a| a == "-e")
+        .expect("should have -e flag");
+    args[pos + 1].clone()
+}
+
+#[test]
+fn test_ssh_connect_timeout() {
+    let ssh = SshConfig {
+        connect_timeout: Some(10),
+        ..SshConfig::default()
+    };
+    assert_eq!(ssh_command_for(&ssh), "ssh -o ConnectTimeout=10");
+}
+
+#[test]
+fn test_ssh_server_alive_interval() {
+    let ssh = SshConfig {
+        server_alive_interval: Some(30),
+        ..SshConfig::default()
+    };
+    assert_eq!(ssh_command_for(&ssh), "ssh -o ServerAliveInterval=30");
+}
+
+#[test]
+fn test_ssh_jump_host() {
+    let ssh = SshConfig {
+        jump_host: Some("admin@bastion:2200".to_string()),
+        ..SshConfig::default()
+    };
+    assert_eq!(ssh_command_for(&ssh), "ssh -J admin@bastion:2200");
+}
+
+#[test]
+fn test_ssh_all_structured_options() {
+    let ssh = SshConfig {
+        port: 2222,
+        identity_file: Some("/key".to_string()),
+        strict_host_key_checking: false,
+        connect_timeout: Some(10),
+        server_alive_interval: Some(30),
+        jump_host: Some("bastion".to_string()),
+        custom_ssh_command: None,
+    };
+    assert_eq!(
+        ssh_command_for(&ssh),
+        "ssh -p 2222 -i /key -J bastion -o StrictHostKeyChecking=no -o ConnectTimeout=10 -o ServerAliveInterval=30"
+    );
+}
+
+#[test]
+fn test_default_ssh_config_adds_no_e_flag() {
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/dst/"),
+        &default_opts(),
+        Some(&SshConfig::default()),
+        &[],
+        false,
+        false,
+    );
+    assert!(!args.contains(&"-e".to_string()));
+}
+
 #[test]
 fn test_local_paths() {
     let args = build_rsync_args(
@@ -99,7 +190,8 @@ fn test_local_paths() {
         &local("/backup/docs/"),
         &default_opts(),
         None,
-        None,
+        &[],
+        false,
         false,
     );
     assert!(args.contains(&"/home/user/docs/".to_string()));
@@ -115,7 +207,15 @@ fn test_remote_ssh_paths() {
         path: "/data/backup/".to_string(),
         identity_file: None,
     };
-    let args = build_rsync_args(&source, &local("/local/"), &default_opts(), None, None, false);
+    let args = build_rsync_args(
+        &source,
+        &local("/local/"),
+        &default_opts(),
+        None,
+        &[],
+        false,
+        false,
+    );
     assert!(args.contains(&"admin@server.example.com:/data/backup/".to_string()));
 }
 
@@ -125,11 +225,80 @@ fn test_remote_rsync_paths() {
         host: "rsync.example.com".to_string(),
         module: "backups".to_string(),
         path: "daily/".to_string(),
+        username: None,
+        password_secret: None,
     };
-    let args = build_rsync_args(&local("/src/"), &dest, &default_opts(), None, None, false);
+    let args = build_rsync_args(
+        &local("/src/"),
+        &dest,
+        &default_opts(),
+        None,
+        &[],
+        false,
+        false,
+    );
     assert!(args.contains(&"rsync://rsync.example.com/backups/daily/".to_string()));
 }
 
+#[test]
+fn test_remote_rsync_path_with_username() {
+    let dest = StorageLocation::RemoteRsync {
+        host: "nas.local".to_string(),
+        module: "backups".to_string(),
+        path: "laptop/".to_string(),
+        username: Some("backup".to_string()),
+        password_secret: Some("nas".to_string()),
+    };
+    let args = build_rsync_args(
+        &local("/src/"),
+        &dest,
+        &default_opts(),
+        None,
+        &[],
+        false,
+        false,
+    );
+    assert!(args.contains(&"rsync://backup@nas.local/backups/laptop/".to_string()));
+    assert!(!args.iter().any(|a| a.starts_with("--password-file")));
+}
+
+#[test]
+fn test_compress_choice_and_level() {
+    let options = RsyncOptions {
+        advanced: AdvancedOptions {
+            compress_choice: Some("zstd".to_string()),
+            compress_level: Some(3),
+            ..Default::default()
+        },
+        ..default_opts()
+    };
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/dst/"),
+        &options,
+        None,
+        &[],
+        false,
+        false,
+    );
+    assert!(args.contains(&"--compress-choice=zstd".to_string()));
+    assert!(args.contains(&"--compress-level=3".to_string()));
+}
+
+#[test]
+fn test_no_compression_flags_by_default() {
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/dst/"),
+        &default_opts(),
+        None,
+        &[],
+        false,
+        false,
+    );
+    assert!(!args.iter().any(|a| a.starts_with("--compress-")));
+}
+
 #[test]
 fn test_exclude_patterns() {
     let options = RsyncOptions {
@@ -139,13 +308,69 @@ fn test_exclude_patterns() {
         },
         ..default_opts()
     };
-    let args = build_rsync_args(&local("/src/"), &local("/dst/"), &options, None, None, false);
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/dst/"),
+        &options,
+        None,
+        &[],
+        false,
+        false,
+    );
 
     assert!(args.contains(&"--exclude=*.log".to_string()));
     assert!(args.contains(&"--exclude=tmp/".to_string()));
     assert!(args.contains(&"--exclude=.git".to_string()));
 }
 
+#[test]
+fn test_system_file_excludes_only_when_enabled() {
+    let mut options = RsyncOptions {
+        advanced: AdvancedOptions {
+            exclude_patterns: vec!["*.log".to_string()],
+            ..Default::default()
+        },
+        ..default_opts()
+    };
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/dst/"),
+        &options,
+        None,
+        &[],
+        false,
+        false,
+    );
+    assert!(!args.contains(&"--exclude=.DS_Store".to_string()));
+    assert!(!args.contains(&"--exclude=@eaDir".to_string()));
+
+    options.advanced.exclude_system_files = true;
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/dst/"),
+        &options,
+        None,
+        &[],
+        false,
+        false,
+    );
+    for junk in SYSTEM_FILE_EXCLUDES {
+        assert!(
+            args.contains(&format!("--exclude={}", junk)),
+            "missing {}",
+            junk
+        );
+    }
+    // Appended after the user's own excludes, which stay untouched
+    let user_pos = args.iter().position(|a| a == "--exclude=*.log").unwrap();
+    let junk_pos = args
+        .iter()
+        .position(|a| a == "--exclude=.DS_Store")
+        .unwrap();
+    assert!(user_pos < junk_pos);
+    assert_eq!(options.advanced.exclude_patterns, vec!["*.log"]);
+}
+
 #[test]
 fn test_link_dest() {
     let args = build_rsync_args(
@@ -153,12 +378,61 @@ fn test_link_dest() {
         &local("/dst/"),
         &default_opts(),
         None,
-        Some("/prev/snapshot"),
+        &["/prev/snapshot"],
+        false,
         false,
     );
     assert!(args.contains(&"--link-dest=/prev/snapshot".to_string()));
 }
 
+#[test]
+fn test_multiple_link_dests_keep_order() {
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/dst/"),
+        &default_opts(),
+        None,
+        &["/snap/3", "/snap/2", "/snap/1"],
+        false,
+        false,
+    );
+    let links: Vec<&String> = args
+        .iter()
+        .filter(|a| a.starts_with("--link-dest="))
+        .collect();
+    assert_eq!(
+        links,
+        vec![
+            "--link-dest=/snap/3",
+            "--link-dest=/snap/2",
+            "--link-dest=/snap/1"
+        ]
+    );
+}
+
+#[test]
+fn test_link_dests_capped_at_rsync_limit() {
+    let paths: Vec<String> = (0..25).map(|i| format!("/snap/{i}")).collect();
+    let refs: Vec<&str> = paths.iter().map(String::as_str).collect();
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/dst/"),
+        &default_opts(),
+        None,
+        &refs,
+        false,
+        false,
+    );
+
+    let links: Vec<&String> = args
+        .iter()
+        .filter(|a| a.starts_with("--link-dest="))
+        .collect();
+    assert_eq!(links.len(), MAX_LINK_DEST_DIRS);
+    assert_eq!(links[0], "--link-dest=/snap/0");
+    assert_eq!(links[MAX_LINK_DEST_DIRS - 1], "--link-dest=/snap/19");
+}
+
 #[test]
 fn test_bandwidth_limit() {
     let options = RsyncOptions {
@@ -168,7 +442,15 @@ fn test_bandwidth_limit() {
         },
         ..default_opts()
     };
-    let args = build_rsync_args(&local("/src/"), &local("/dst/"), &options, None, None, false);
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/dst/"),
+        &options,
+        None,
+        &[],
+        false,
+        false,
+    );
     assert!(args.contains(&"--bwlimit=1000".to_string()));
 }
 
@@ -181,7 +463,15 @@ fn test_custom_args_appended() {
         },
         ..default_opts()
     };
-    let args = build_rsync_args(&local("/src/"), &local("/dst/"), &options, None, None, false);
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/dst/"),
+        &options,
+        None,
+        &[],
+        false,
+        false,
+    );
 
     // Custom args should be before source/dest (which are last two)
     let checksum_pos = args.iter().position(|a| a == "--checksum").unwrap();
@@ -197,8 +487,9 @@ fn test_auto_trailing_slash_appends() {
         &local("/backup/docs"),
         &default_opts(),
         None,
-        None,
+        &[],
         true,
+        false,
     );
     assert!(args.contains(&"/home/user/docs/".to_string()));
     assert!(args.contains(&"/backup/docs/".to_string()));
@@ -213,7 +504,15 @@ fn test_size_only_flag() {
         },
         ..default_opts()
     };
-    let args = build_rsync_args(&local("/src/"), &local("/dst/"), &options, None, None, false);
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/dst/"),
+        &options,
+        None,
+        &[],
+        false,
+        false,
+    );
     assert!(args.contains(&"--size-only".to_string()));
 }
 
@@ -224,7 +523,8 @@ fn test_size_only_disabled_by_default() {
         &local("/dst/"),
         &default_opts(),
         None,
-        None,
+        &[],
+        false,
         false,
     );
     assert!(!args.contains(&"--size-only".to_string()));
@@ -236,7 +536,15 @@ fn test_checksum_flag() {
         file_handling: FileHandlingOptions { checksum: true, ..Default::default() },
         ..default_opts()
     };
-    let args = build_rsync_args(&local("/src/"), &local("/dst/"), &options, None, None, false);
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/dst/"),
+        &options,
+        None,
+        &[],
+        false,
+        false,
+    );
     assert!(args.contains(&"--checksum".to_string()));
 }
 
@@ -246,7 +554,15 @@ fn test_update_flag() {
         file_handling: FileHandlingOptions { update: true, ..Default::default() },
         ..default_opts()
     };
-    let args = build_rsync_args(&local("/src/"), &local("/dst/"), &options, None, None, false);
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/dst/"),
+        &options,
+        None,
+        &[],
+        false,
+        false,
+    );
     assert!(args.contains(&"--update".to_string()));
 }
 
@@ -256,7 +572,15 @@ fn test_whole_file_flag() {
         file_handling: FileHandlingOptions { whole_file: true, ..Default::default() },
         ..default_opts()
     };
-    let args = build_rsync_args(&local("/src/"), &local("/dst/"), &options, None, None, false);
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/dst/"),
+        &options,
+        None,
+        &[],
+        false,
+        false,
+    );
     assert!(args.contains(&"--whole-file".to_string()));
 }
 
@@ -266,7 +590,15 @@ fn test_ignore_existing_flag() {
         file_handling: FileHandlingOptions { ignore_existing: true, ..Default::default() },
         ..default_opts()
     };
-    let args = build_rsync_args(&local("/src/"), &local("/dst/"), &options, None, None, false);
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/dst/"),
+        &options,
+        None,
+        &[],
+        false,
+        false,
+    );
     assert!(args.contains(&"--ignore-existing".to_string()));
 }
 
@@ -276,17 +608,69 @@ fn test_one_file_system_flag() {
         file_handling: FileHandlingOptions { one_file_system: true, ..Default::default() },
         ..default_opts()
     };
-    let args = build_rsync_args(&local("/src/"), &local("/dst/"), &options, None, None, false);
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/dst/"),
+        &options,
+        None,
+        &[],
+        false,
+        false,
+    );
     assert!(args.contains(&"--one-file-system".to_string()));
 }
 
+#[test]
+fn test_max_delete_flag() {
+    let options = RsyncOptions {
+        file_handling: FileHandlingOptions {
+            delete: true,
+            max_delete: Some(25),
+            ..Default::default()
+        },
+        ..default_opts()
+    };
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/dst/"),
+        &options,
+        None,
+        &[],
+        false,
+        false,
+    );
+    assert!(args.contains(&"--max-delete=25".to_string()));
+}
+
+#[test]
+fn test_no_max_delete_by_default() {
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/dst/"),
+        &default_opts(),
+        None,
+        &[],
+        false,
+        false,
+    );
+    assert!(!args.iter().any(|a| a.starts_with("--max-delete")));
+}
+
 #[test]
 fn test_hard_links_flag() {
     let options = RsyncOptions {
         metadata: MetadataOptions { hard_links: true, ..Default::default() },
         ..default_opts()
     };
-    let args = build_rsync_args(&local("/src/"), &local("/dst/"), &options, None, None, false);
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/dst/"),
+        &options,
+        None,
+        &[],
+        false,
+        false,
+    );
     assert!(args.contains(&"--hard-links".to_string()));
 }
 
@@ -296,7 +680,15 @@ fn test_acls_flag() {
         metadata: MetadataOptions { acls: true, ..Default::default() },
         ..default_opts()
     };
-    let args = build_rsync_args(&local("/src/"), &local("/dst/"), &options, None, None, false);
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/dst/"),
+        &options,
+        None,
+        &[],
+        false,
+        false,
+    );
     assert!(args.contains(&"--acls".to_string()));
 }
 
@@ -306,7 +698,15 @@ fn test_xattrs_flag() {
         metadata: MetadataOptions { xattrs: true, ..Default::default() },
         ..default_opts()
     };
-    let args = build_rsync_args(&local("/src/"), &local("/dst/"), &options, None, None, false);
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/dst/"),
+        &options,
+        None,
+        &[],
+        false,
+        false,
+    );
     assert!(args.contains(&"--xattrs".to_string()));
 }
 
@@ -316,7 +716,15 @@ fn test_numeric_ids_flag() {
         metadata: MetadataOptions { numeric_ids: true, ..Default::default() },
         ..default_opts()
     };
-    let args = build_rsync_args(&local("/src/"), &local("/dst/"), &options, None, None, false);
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/dst/"),
+        &options,
+        None,
+        &[],
+        false,
+        false,
+    );
     assert!(args.contains(&"--numeric-ids".to_string()));
 }
 
@@ -326,7 +734,15 @@ fn test_stats_flag() {
         output: OutputOptions { stats: true, ..Default::default() },
         ..default_opts()
     };
-    let args = build_rsync_args(&local("/src/"), &local("/dst/"), &options, None, None, false);
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/dst/"),
+        &options,
+        None,
+        &[],
+        false,
+        false,
+    );
     assert!(args.contains(&"--stats".to_string()));
 }
 
@@ -336,7 +752,15 @@ fn test_itemize_changes_flag() {
         output: OutputOptions { itemize_changes: true, ..Default::default() },
         ..default_opts()
     };
-    let args = build_rsync_args(&local("/src/"), &local("/dst/"), &options, None, None, false);
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/dst/"),
+        &options,
+        None,
+        &[],
+        false,
+        false,
+    );
     assert!(args.contains(&"--itemize-changes".to_string()));
 }
 
@@ -364,7 +788,15 @@ fn test_all_11_promoted_flags_together() {
         },
         ..default_opts()
     };
-    let args = build_rsync_args(&local("/src/"), &local("/dst/"), &options, None, None, false);
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/dst/"),
+        &options,
+        None,
+        &[],
+        false,
+        false,
+    );
     assert!(args.contains(&"--checksum".to_string()));
     assert!(args.contains(&"--update".to_string()));
     assert!(args.contains(&"--whole-file".to_string()));
@@ -385,11 +817,231 @@ fn test_auto_trailing_slash_no_double() {
         &local("/backup/docs/"),
         &default_opts(),
         None,
-        None,
+        &[],
         true,
+        false,
     );
     assert!(args.contains(&"/home/user/docs/".to_string()));
     assert!(args.contains(&"/backup/docs/".to_string()));
     // Ensure no double slashes
     assert!(!args.iter().any(|a| a.ends_with("//")));
 }
+
+#[test]
+fn test_relative_paths_off_omits_relative_flag() {
+    let args = build_rsync_args(
+        &local("/home/user/docs"),
+        &local("/backup"),
+        &default_opts(),
+        None,
+        &[],
+        true,
+        false,
+    );
+    assert!(!args.contains(&"--relative".to_string()));
+    assert_eq!(args[args.len() - 2], "/home/user/docs/");
+    assert_eq!(args[args.len() - 1], "/backup/");
+}
+
+#[test]
+fn test_relative_paths_anchors_source_at_last_component() {
+    let args = build_rsync_args(
+        &local("/home/user/docs/"),
+        &local("/backup"),
+        &default_opts(),
+        None,
+        &[],
+        true,
+        true,
+    );
+    assert!(args.contains(&"--relative".to_string()));
+    // The source's trailing slash is dropped; the destination keeps auto slash
+    assert_eq!(args[args.len() - 2], "/home/user/./docs");
+    assert_eq!(args[args.len() - 1], "/backup/");
+}
+
+#[test]
+fn test_relative_paths_without_auto_trailing_slash() {
+    let args = build_rsync_args(
+        &local("/home/user/docs"),
+        &local("/backup"),
+        &default_opts(),
+        None,
+        &[],
+        false,
+        true,
+    );
+    assert_eq!(args[args.len() - 2], "/home/user/./docs");
+    assert_eq!(args[args.len() - 1], "/backup");
+}
+
+#[test]
+fn test_relative_paths_precede_custom_args() {
+    let options = RsyncOptions {
+        advanced: AdvancedOptions {
+            custom_args: vec!["--no-implied-dirs".to_string()],
+            ..Default::default()
+        },
+        ..default_opts()
+    };
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/dst/"),
+        &options,
+        None,
+        &[],
+        false,
+        true,
+    );
+    let relative = args.iter().position(|a| a == "--relative").unwrap();
+    let custom = args.iter().position(|a| a == "--no-implied-dirs").unwrap();
+    assert!(relative < custom);
+}
+
+#[test]
+fn test_relative_paths_remote_source() {
+    let source = StorageLocation::RemoteSsh {
+        user: "me".to_string(),
+        host: "nas".to_string(),
+        port: 22,
+        path: "/volume1/photos/".to_string(),
+        identity_file: None,
+    };
+    let args = build_rsync_args(
+        &source,
+        &local("/dst/"),
+        &default_opts(),
+        None,
+        &[],
+        false,
+        true,
+    );
+    assert_eq!(args[args.len() - 2], "me@nas:/volume1/./photos");
+}
+
+#[test]
+fn test_relative_source_path_forms() {
+    assert_eq!(relative_source_path("/home/user/docs"), "/home/user/./docs");
+    assert_eq!(
+        relative_source_path("/home/user/docs/"),
+        "/home/user/./docs"
+    );
+    assert_eq!(relative_source_path("/docs"), "/./docs");
+    // An explicit anchor is kept as written
+    assert_eq!(
+        relative_source_path("/home/./user/docs/"),
+        "/home/./user/docs/"
+    );
+    // Nothing to anchor
+    assert_eq!(relative_source_path("/"), "/");
+    assert_eq!(relative_source_path("docs"), "docs");
+}
+
+#[test]
+fn test_partial_dir_replaces_partial() {
+    let options = RsyncOptions {
+        core_transfer: CoreTransferOptions {
+            partial: true,
+            partial_dir: Some(".rsync-partial".to_string()),
+            ..Default::default()
+        },
+        ..default_opts()
+    };
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/dst/"),
+        &options,
+        None,
+        &[],
+        false,
+        false,
+    );
+    assert!(args.contains(&"--partial-dir=.rsync-partial".to_string()));
+    // --partial-dir implies --partial, so it isn't repeated
+    assert!(!args.contains(&"--partial".to_string()));
+}
+
+#[test]
+fn test_partial_dir_without_partial_flag() {
+    let options = RsyncOptions {
+        core_transfer: CoreTransferOptions {
+            partial_dir: Some("/var/tmp/rsync-partial".to_string()),
+            ..Default::default()
+        },
+        ..default_opts()
+    };
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/dst/"),
+        &options,
+        None,
+        &[],
+        false,
+        false,
+    );
+    assert!(args.contains(&"--partial-dir=/var/tmp/rsync-partial".to_string()));
+}
+
+#[test]
+fn test_modify_window() {
+    let options = RsyncOptions {
+        file_handling: FileHandlingOptions {
+            modify_window: Some(2),
+            ..Default::default()
+        },
+        ..default_opts()
+    };
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/mnt/usb/"),
+        &options,
+        None,
+        &[],
+        false,
+        false,
+    );
+    assert!(args.contains(&"--modify-window=2".to_string()));
+
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/dst/"),
+        &default_opts(),
+        None,
+        &[],
+        false,
+        false,
+    );
+    assert!(!args.iter().any(|a| a.starts_with("--modify-window")));
+}
+
+#[test]
+fn test_checksum_choice() {
+    let options = RsyncOptions {
+        file_handling: FileHandlingOptions {
+            checksum_choice: Some("xxh64".to_string()),
+            ..Default::default()
+        },
+        ..default_opts()
+    };
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/dst/"),
+        &options,
+        None,
+        &[],
+        false,
+        false,
+    );
+    assert!(args.contains(&"--checksum-choice=xxh64".to_string()));
+
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/dst/"),
+        &default_opts(),
+        None,
+        &[],
+        false,
+        false,
+    );
+    assert!(!args.iter().any(|a| a.starts_with("--checksum-choice")));
+}
@@ -1,5 +1,12 @@
-use crate::models::command::ArgCategory;
-use crate::services::command_explainer::{explain_command, explain_flag};
+use chrono::NaiveTime;
+
+use crate::models::command::{ArgCategory, ArgumentExplanation, CommandExplanation};
+use crate::models::job::{BackupMode, RetentionPolicy, StorageLocation};
+use crate::models::rsync_options::{AdvancedOptions, BandwidthWindow};
+use crate::services::command_builder::SYSTEM_FILE_EXCLUDES;
+use crate::services::command_explainer::{
+    explain_bandwidth_schedule, explain_command, explain_flag, explain_trailing_slash,
+};
 use crate::services::command_parser::parse_rsync_command;
 
 #[test]
@@ -35,6 +42,26 @@ fn explain_excludes() {
         .any(|a| a.argument == "--exclude=*.log" && a.category == ArgCategory::Pattern));
 }
 
+#[test]
+fn explain_system_file_excludes() {
+    let mut args: Vec<String> = SYSTEM_FILE_EXCLUDES
+        .iter()
+        .map(|p| format!("--exclude={}", p))
+        .collect();
+    args.insert(0, "--exclude=*.log".to_string());
+    let cmd = format!("rsync -a {} /src/ /dst/", shell_words::join(&args));
+    let explanation = explain_command(&parse_rsync_command(&cmd).unwrap());
+
+    assert!(explanation.summary.contains("System and NAS junk files"));
+    assert!(explanation
+        .summary
+        .contains("1 other pattern(s) are excluded"));
+    assert!(explanation
+        .arguments
+        .iter()
+        .any(|a| a.argument == "--exclude=@eaDir" && a.description.contains("built-in")));
+}
+
 #[test]
 fn explain_unknown_args() {
     let parsed =
@@ -72,6 +99,25 @@ fn explain_bandwidth_limit() {
         .any(|a| a.argument == "--bwlimit=500" && a.category == ArgCategory::Performance));
 }
 
+#[test]
+fn explain_compress_choice_and_level() {
+    let parsed =
+        parse_rsync_command("rsync -az --compress-choice=zstd --compress-level=3 /src/ /dst/")
+            .unwrap();
+    let explanation = explain_command(&parsed);
+    assert!(explanation.summary.contains("compressed with zstd"));
+    for arg in ["--compress-choice=zstd", "--compress-level=3"] {
+        assert!(explanation
+            .arguments
+            .iter()
+            .any(|a| a.argument == arg && a.category == ArgCategory::Performance));
+    }
+    assert!(!explanation
+        .arguments
+        .iter()
+        .any(|a| a.category == ArgCategory::Unknown));
+}
+
 #[test]
 fn explain_link_dest() {
     let parsed = parse_rsync_command(
@@ -152,3 +198,258 @@ fn flag_categories_are_assigned() {
     let hl_arg = explanation.arguments.iter().find(|a| a.argument == "hard_links").unwrap();
     assert_eq!(hl_arg.category, ArgCategory::Metadata);
 }
+
+#[test]
+fn max_delete_is_explained_as_deletion() {
+    let parsed = parse_rsync_command("rsync -a --delete --max-delete=100 /src/ /dst/").unwrap();
+    let explanation = explain_command(&parsed);
+
+    let arg = explanation
+        .arguments
+        .iter()
+        .find(|a| a.argument == "--max-delete=100")
+        .unwrap();
+    assert_eq!(arg.category, ArgCategory::Deletion);
+    assert!(arg.description.contains("at most 100"));
+    assert!(explanation
+        .summary
+        .contains("No more than 100 file(s) will be deleted."));
+}
+
+#[test]
+fn explain_relative_source_with_anchor() {
+    let parsed = parse_rsync_command("rsync -a --relative /home/me/./docs /dst/").unwrap();
+    let explanation = explain_command(&parsed);
+    let source = explanation
+        .arguments
+        .iter()
+        .find(|a| a.argument == "/home/me/./docs")
+        .unwrap();
+    assert!(source
+        .description
+        .contains("'docs' is recreated under the destination"));
+}
+
+#[test]
+fn explain_relative_source_without_anchor() {
+    let parsed = parse_rsync_command("rsync -aR /home/me/docs/ /dst/").unwrap();
+    let explanation = explain_command(&parsed);
+    let source = explanation
+        .arguments
+        .iter()
+        .find(|a| a.argument == "/home/me/docs/")
+        .unwrap();
+    assert!(source.description.contains("full path '/home/me/docs'"));
+}
+
+#[test]
+fn explain_source_without_relative() {
+    let parsed = parse_rsync_command("rsync -a /home/me/docs/ /dst/").unwrap();
+    let explanation = explain_command(&parsed);
+    let source = explanation
+        .arguments
+        .iter()
+        .find(|a| a.argument == "/home/me/docs/")
+        .unwrap();
+    assert!(!source.description.contains("--relative"));
+}
+
+#[test]
+fn explain_partial_dir() {
+    let parsed = parse_rsync_command("rsync -a --partial-dir=.rsync-partial /src/ /dst/").unwrap();
+    let explanation = explain_command(&parsed);
+    let arg = explanation
+        .arguments
+        .iter()
+        .find(|a| a.argument == "--partial-dir=.rsync-partial")
+        .unwrap();
+    assert_eq!(arg.category, ArgCategory::FileHandling);
+    assert!(arg.description.contains("Implies --partial"));
+}
+
+fn custom_arg<'a>(explanation: &'a CommandExplanation, argument: &str) -> &'a ArgumentExplanation {
+    explanation
+        .arguments
+        .iter()
+        .find(|a| a.argument == argument)
+        .unwrap()
+}
+
+#[test]
+fn explain_custom_block_size() {
+    let parsed = parse_rsync_command("rsync -a --block-size=4096 /src/ /dst/").unwrap();
+    assert_eq!(parsed.custom_args, vec!["--block-size=4096"]);
+    let explanation = explain_command(&parsed);
+    let arg = custom_arg(&explanation, "--block-size=4096");
+    assert_eq!(arg.category, ArgCategory::FileHandling);
+    assert!(arg.description.starts_with("Block size"));
+    assert!(arg.description.ends_with("Set to '4096'."));
+}
+
+#[test]
+fn explain_modify_window() {
+    let parsed = parse_rsync_command("rsync -a --modify-window=2 /src/ /mnt/usb/").unwrap();
+    let explanation = explain_command(&parsed);
+    let arg = custom_arg(&explanation, "--modify-window=2");
+    assert_eq!(arg.category, ArgCategory::FileHandling);
+    assert!(arg.description.contains("up to 2 second(s)"));
+    assert!(arg.description.contains("FAT/exFAT and SMB"));
+}
+
+#[test]
+fn explain_checksum_choice() {
+    let parsed = parse_rsync_command("rsync -a --checksum-choice=xxh64 /src/ /dst/").unwrap();
+    let explanation = explain_command(&parsed);
+    let arg = custom_arg(&explanation, "--checksum-choice=xxh64");
+    assert_eq!(arg.category, ArgCategory::FileHandling);
+    assert!(arg.description.contains("'xxh64' checksum algorithm"));
+}
+
+#[test]
+fn explain_negative_modify_window_as_custom_arg() {
+    let parsed = parse_rsync_command("rsync -a --modify-window=-1 /src/ /dst/").unwrap();
+    let explanation = explain_command(&parsed);
+    let arg = custom_arg(&explanation, "--modify-window=-1");
+    assert!(arg.description.starts_with("Modify window"));
+    assert!(arg.description.ends_with("Set to '-1'."));
+}
+
+#[test]
+fn explain_custom_bwlimit_with_units() {
+    // A rate with units isn't promoted to bandwidth_limit, so it stays a custom arg
+    let parsed = parse_rsync_command("rsync -a --bwlimit=1.5M /src/ /dst/").unwrap();
+    assert_eq!(parsed.bandwidth_limit, None);
+    let explanation = explain_command(&parsed);
+    let arg = custom_arg(&explanation, "--bwlimit=1.5M");
+    assert_eq!(arg.category, ArgCategory::Performance);
+    assert!(arg.description.contains("Bandwidth limit"));
+    assert!(arg.description.contains("'1.5M'"));
+}
+
+#[test]
+fn explain_custom_arg_falls_back_to_flag_descriptions() {
+    let parsed = parse_rsync_command("rsync -a --timeout=30 /src/ /dst/").unwrap();
+    let explanation = explain_command(&parsed);
+    let arg = custom_arg(&explanation, "--timeout=30");
+    assert_eq!(arg.category, ArgCategory::Performance);
+    assert!(arg.description.starts_with("Timeout (--timeout=SECONDS)"));
+}
+
+#[test]
+fn explain_made_up_value_flag_stays_unknown() {
+    let parsed = parse_rsync_command("rsync -a --frobnicate=7 /src/ /dst/").unwrap();
+    let explanation = explain_command(&parsed);
+    let arg = custom_arg(&explanation, "--frobnicate=7");
+    assert_eq!(arg.category, ArgCategory::Unknown);
+    assert_eq!(
+        arg.description,
+        "Argument not recognized. Please check the rsync manual for more details."
+    );
+}
+
+fn local(path: &str) -> StorageLocation {
+    StorageLocation::Local {
+        path: path.to_string(),
+    }
+}
+
+#[test]
+fn trailing_slash_with_slash_copies_contents() {
+    let text = explain_trailing_slash(&local("/src/"), &local("/dst"), &BackupMode::Mirror, false);
+    assert_eq!(
+        text,
+        "The contents of /src will be placed directly into /dst."
+    );
+}
+
+#[test]
+fn trailing_slash_without_slash_creates_folder() {
+    let text = explain_trailing_slash(
+        &local("/data/src"),
+        &local("/dst/"),
+        &BackupMode::Mirror,
+        false,
+    );
+    assert!(text.starts_with("A /dst/src folder will be created, holding /data/src."));
+}
+
+#[test]
+fn trailing_slash_auto_applied_copies_contents() {
+    let text = explain_trailing_slash(&local("/src"), &local("/dst"), &BackupMode::Mirror, true);
+    assert!(text.starts_with("The contents of /src will be placed directly into /dst."));
+    assert!(text.contains("added automatically"));
+    assert!(text.contains("copy the src folder itself"));
+}
+
+#[test]
+fn trailing_slash_snapshot_targets_snapshot_folder() {
+    let mode = BackupMode::Snapshot {
+        retention_policy: RetentionPolicy::default(),
+    };
+    let text = explain_trailing_slash(&local("/src"), &local("/backups"), &mode, false);
+    assert!(text.starts_with("A /backups/<snapshot>/src folder will be created"));
+}
+
+/// 500 KB/s during office hours, unlimited the rest of the day.
+fn office_hours_bandwidth() -> AdvancedOptions {
+    AdvancedOptions {
+        bandwidth_schedule: vec![
+            BandwidthWindow {
+                start_minute: 8 * 60,
+                end_minute: 18 * 60,
+                limit: Some(500),
+            },
+            BandwidthWindow {
+                start_minute: 18 * 60,
+                end_minute: 8 * 60,
+                limit: None,
+            },
+        ],
+        ..Default::default()
+    }
+}
+
+fn at(h: u32, m: u32) -> NaiveTime {
+    NaiveTime::from_hms_opt(h, m, 0).unwrap()
+}
+
+#[test]
+fn explain_bandwidth_schedule_inside_limited_band() {
+    let note = explain_bandwidth_schedule(&office_hours_bandwidth(), at(9, 30)).unwrap();
+
+    assert!(
+        note.starts_with("Bandwidth right now: 500.0 KB/s."),
+        "{}",
+        note
+    );
+    assert!(
+        note.contains("From 18:00 it changes to unlimited."),
+        "{}",
+        note
+    );
+}
+
+#[test]
+fn explain_bandwidth_schedule_in_overnight_band() {
+    let note = explain_bandwidth_schedule(&office_hours_bandwidth(), at(23, 0)).unwrap();
+
+    assert!(
+        note.starts_with("Bandwidth right now: unlimited."),
+        "{}",
+        note
+    );
+    assert!(
+        note.contains("From 08:00 it changes to 500.0 KB/s."),
+        "{}",
+        note
+    );
+}
+
+#[test]
+fn explain_bandwidth_schedule_absent_without_windows() {
+    let advanced = AdvancedOptions {
+        bandwidth_limit: Some(500),
+        ..Default::default()
+    };
+    assert_eq!(explain_bandwidth_schedule(&advanced, at(9, 30)), None);
+}
@@ -1,3 +1,5 @@
 mod builder_tests;
 mod parser_tests;
 mod explainer_tests;
+mod pattern_tester_tests;
+mod rsync_glob_tests;
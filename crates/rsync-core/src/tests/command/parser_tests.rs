@@ -1,6 +1,6 @@
 use crate::models::job::{
     AdvancedOptions, CoreTransferOptions, FileHandlingOptions, MetadataOptions, OutputOptions,
-    RsyncOptions, StorageLocation,
+    RsyncOptions, SshConfig, StorageLocation,
 };
 use crate::services::command_builder::build_rsync_args;
 use crate::services::command_parser::{
@@ -56,6 +56,89 @@ fn parse_bandwidth_limit() {
     assert_eq!(parsed.bandwidth_limit, Some(1000));
 }
 
+#[test]
+fn parse_compress_choice_and_level() {
+    let parsed =
+        parse_rsync_command("rsync -az --compress-choice=zstd --compress-level=3 /src/ /dst/")
+            .unwrap();
+    assert_eq!(parsed.compress_choice.as_deref(), Some("zstd"));
+    assert_eq!(parsed.compress_level, Some(3));
+    assert!(parsed.custom_args.is_empty());
+}
+
+#[test]
+fn parse_compress_short_aliases() {
+    let parsed = parse_rsync_command("rsync -az --zc=lz4 --zl=1 /src/ /dst/").unwrap();
+    assert_eq!(parsed.compress_choice.as_deref(), Some("lz4"));
+    assert_eq!(parsed.compress_level, Some(1));
+}
+
+#[test]
+fn parse_invalid_compress_level_stays_custom() {
+    let parsed = parse_rsync_command("rsync -az --compress-level=max /src/ /dst/").unwrap();
+    assert_eq!(parsed.compress_level, None);
+    assert_eq!(parsed.custom_args, vec!["--compress-level=max"]);
+}
+
+#[test]
+fn parse_max_delete_promoted_from_custom_args() {
+    let parsed = parse_rsync_command("rsync -a --delete --max-delete=50 /src/ /dst/").unwrap();
+    assert_eq!(parsed.max_delete, Some(50));
+    assert!(parsed.custom_args.is_empty());
+    let job = to_job_definition(&parsed).unwrap();
+    assert_eq!(job.options.file_handling.max_delete, Some(50));
+}
+
+#[test]
+fn parse_modify_window_promoted_from_custom_args() {
+    let parsed = parse_rsync_command("rsync -a --modify-window=2 /src/ /mnt/usb/").unwrap();
+    assert_eq!(parsed.modify_window, Some(2));
+    assert!(parsed.custom_args.is_empty());
+    let job = to_job_definition(&parsed).unwrap();
+    assert_eq!(job.options.file_handling.modify_window, Some(2));
+}
+
+#[test]
+fn parse_checksum_choice_promoted_from_custom_args() {
+    let parsed = parse_rsync_command("rsync -a --checksum-choice=xxh64 /src/ /dst/").unwrap();
+    assert_eq!(parsed.checksum_choice.as_deref(), Some("xxh64"));
+    assert!(parsed.custom_args.is_empty());
+    let job = to_job_definition(&parsed).unwrap();
+    assert_eq!(
+        job.options.file_handling.checksum_choice.as_deref(),
+        Some("xxh64")
+    );
+
+    let parsed = parse_rsync_command("rsync -a --cc=md5 /src/ /dst/").unwrap();
+    assert_eq!(parsed.checksum_choice.as_deref(), Some("md5"));
+}
+
+#[test]
+fn parse_negative_modify_window_stays_custom() {
+    let parsed = parse_rsync_command("rsync -a --modify-window=-1 /src/ /dst/").unwrap();
+    assert_eq!(parsed.modify_window, None);
+    assert_eq!(parsed.custom_args, vec!["--modify-window=-1"]);
+}
+
+#[test]
+fn parse_partial_dir_promoted_from_custom_args() {
+    let parsed = parse_rsync_command("rsync -a --partial-dir=.rsync-partial /src/ /dst/").unwrap();
+    assert_eq!(parsed.partial_dir.as_deref(), Some(".rsync-partial"));
+    assert!(parsed.custom_args.is_empty());
+    let job = to_job_definition(&parsed).unwrap();
+    assert_eq!(
+        job.options.core_transfer.partial_dir.as_deref(),
+        Some(".rsync-partial")
+    );
+}
+
+#[test]
+fn parse_invalid_max_delete_stays_custom() {
+    let parsed = parse_rsync_command("rsync -a --max-delete=lots /src/ /dst/").unwrap();
+    assert_eq!(parsed.max_delete, None);
+    assert_eq!(parsed.custom_args, vec!["--max-delete=lots"]);
+}
+
 #[test]
 fn parse_link_dest() {
     let parsed =
@@ -100,10 +183,58 @@ fn parse_remote_rsync_paths() {
             host: "rsync.example.com".to_string(),
             module: "backups".to_string(),
             path: "daily/".to_string(),
+            username: None,
+            password_secret: None,
+        }
+    );
+}
+
+#[test]
+fn parse_authenticated_rsync_url() {
+    let loc = parse_storage_location("rsync://backup@nas.local/backups/laptop/");
+    assert_eq!(
+        loc,
+        StorageLocation::RemoteRsync {
+            host: "nas.local".to_string(),
+            module: "backups".to_string(),
+            path: "laptop/".to_string(),
+            username: Some("backup".to_string()),
+            password_secret: None,
+        }
+    );
+}
+
+#[test]
+fn parse_double_colon_daemon_path() {
+    let loc = parse_storage_location("backup@nas.local::backups/laptop/");
+    assert_eq!(
+        loc,
+        StorageLocation::RemoteRsync {
+            host: "nas.local".to_string(),
+            module: "backups".to_string(),
+            path: "laptop/".to_string(),
+            username: Some("backup".to_string()),
+            password_secret: None,
         }
     );
 }
 
+#[test]
+fn parse_password_file_flag() {
+    let parsed = parse_rsync_command(
+        "rsync -a --password-file=/etc/rsync.pass /src/ rsync://backup@nas.local/backups/",
+    )
+    .unwrap();
+    assert!(parsed.flags.contains(&"password_file".to_string()));
+    assert!(parsed
+        .custom_args
+        .contains(&"--password-file=/etc/rsync.pass".to_string()));
+    assert_eq!(
+        parsed.destination.as_deref(),
+        Some("rsync://backup@nas.local/backups/")
+    );
+}
+
 #[test]
 fn parse_local_path() {
     let loc = parse_storage_location("/home/user/docs/");
@@ -179,7 +310,7 @@ fn parse_value_flags_recognized() {
         "rsync -a --max-size=100M --compress-level=9 --chmod=ugo=rwX /src/ /dst/"
     ).unwrap();
     assert!(parsed.flags.contains(&"max_size".to_string()));
-    assert!(parsed.flags.contains(&"compress_level".to_string()));
+    assert_eq!(parsed.compress_level, Some(9));
     assert!(parsed.flags.contains(&"chmod".to_string()));
 }
 
@@ -281,7 +412,7 @@ fn parse_more_value_carrying_flags() {
     assert!(parsed.flags.contains(&"info".to_string()));
     assert!(parsed.flags.contains(&"debug".to_string()));
     assert!(parsed.flags.contains(&"out_format".to_string()));
-    assert!(parsed.flags.contains(&"max_delete".to_string()));
+    assert_eq!(parsed.max_delete, Some(100));
     assert!(parsed.flags.contains(&"min_size".to_string()));
 }
 
@@ -348,7 +479,7 @@ fn roundtrip_all_11_promoted_flags() {
         ..RsyncOptions::default()
     };
 
-    let args = build_rsync_args(&source, &dest, &opts, None, None, false);
+    let args = build_rsync_args(&source, &dest, &opts, None, &[], false, false);
     let cmd = format!("rsync {}", args.join(" "));
 
     let parsed = parse_rsync_command(&cmd).unwrap();
@@ -383,11 +514,14 @@ fn roundtrip_file_handling_group() {
             whole_file: true,
             ignore_existing: true,
             one_file_system: true,
+            max_delete: Some(100),
+            modify_window: Some(2),
+            checksum_choice: Some("md5".to_string()),
         },
         ..RsyncOptions::default()
     };
 
-    let args = build_rsync_args(&source, &dest, &opts, None, None, false);
+    let args = build_rsync_args(&source, &dest, &opts, None, &[], false, false);
     let cmd = format!("rsync {}", args.join(" "));
 
     let parsed = parse_rsync_command(&cmd).unwrap();
@@ -399,6 +533,22 @@ fn roundtrip_file_handling_group() {
     assert!(job.options.file_handling.whole_file, "whole_file");
     assert!(job.options.file_handling.ignore_existing, "ignore_existing");
     assert!(job.options.file_handling.one_file_system, "one_file_system");
+    assert_eq!(
+        job.options.file_handling.max_delete,
+        Some(100),
+        "max_delete"
+    );
+    assert_eq!(
+        job.options.file_handling.modify_window,
+        Some(2),
+        "modify_window"
+    );
+    assert_eq!(
+        job.options.file_handling.checksum_choice.as_deref(),
+        Some("md5"),
+        "checksum_choice"
+    );
+    assert!(job.options.advanced.custom_args.is_empty());
 }
 
 #[test]
@@ -419,7 +569,7 @@ fn roundtrip_metadata_group() {
         ..RsyncOptions::default()
     };
 
-    let args = build_rsync_args(&source, &dest, &opts, None, None, false);
+    let args = build_rsync_args(&source, &dest, &opts, None, &[], false, false);
     let cmd = format!("rsync {}", args.join(" "));
 
     let parsed = parse_rsync_command(&cmd).unwrap();
@@ -450,7 +600,7 @@ fn roundtrip_output_group() {
         ..RsyncOptions::default()
     };
 
-    let args = build_rsync_args(&source, &dest, &opts, None, None, false);
+    let args = build_rsync_args(&source, &dest, &opts, None, &[], false, false);
     let cmd = format!("rsync {}", args.join(" "));
 
     let parsed = parse_rsync_command(&cmd).unwrap();
@@ -508,6 +658,132 @@ fn parse_ssh_command_parts() {
     assert!(!config.strict_host_key_checking);
 }
 
+#[test]
+fn parse_ssh_command_connect_timeout() {
+    let config = parse_ssh_command("ssh -o ConnectTimeout=10").unwrap();
+    assert_eq!(config.connect_timeout, Some(10));
+    assert!(config.custom_ssh_command.is_none());
+}
+
+#[test]
+fn parse_ssh_command_server_alive_interval() {
+    let config = parse_ssh_command("ssh -o ServerAliveInterval=30").unwrap();
+    assert_eq!(config.server_alive_interval, Some(30));
+    assert!(config.custom_ssh_command.is_none());
+}
+
+#[test]
+fn parse_ssh_command_jump_host() {
+    let config = parse_ssh_command("ssh -J admin@bastion:2200").unwrap();
+    assert_eq!(config.jump_host.as_deref(), Some("admin@bastion:2200"));
+
+    let config = parse_ssh_command("ssh -o ProxyJump=bastion").unwrap();
+    assert_eq!(config.jump_host.as_deref(), Some("bastion"));
+}
+
+#[test]
+fn parse_ssh_command_invalid_timeout_is_custom() {
+    let config = parse_ssh_command("ssh -o ConnectTimeout=soon").unwrap();
+    assert_eq!(config.connect_timeout, None);
+    assert_eq!(
+        config.custom_ssh_command.as_deref(),
+        Some("ssh -o ConnectTimeout=soon")
+    );
+}
+
+#[test]
+fn roundtrip_ssh_structured_options() {
+    let ssh = SshConfig {
+        port: 2222,
+        identity_file: Some("/key".to_string()),
+        strict_host_key_checking: false,
+        connect_timeout: Some(10),
+        server_alive_interval: Some(30),
+        jump_host: Some("admin@bastion".to_string()),
+        custom_ssh_command: None,
+    };
+    let local = |p: &str| StorageLocation::Local {
+        path: p.to_string(),
+    };
+    let args = build_rsync_args(
+        &local("/src/"),
+        &local("/dst/"),
+        &RsyncOptions::default(),
+        Some(&ssh),
+        &[],
+        false,
+        false,
+    );
+    let pos = args.iter().position(|a| a == "-e").unwrap();
+
+    assert_eq!(parse_ssh_command(&args[pos + 1]), Some(ssh));
+}
+
+#[test]
+fn roundtrip_compression_options() {
+    let source = StorageLocation::Local {
+        path: "/src/".to_string(),
+    };
+    let dest = StorageLocation::Local {
+        path: "/dst/".to_string(),
+    };
+    let opts = RsyncOptions {
+        core_transfer: CoreTransferOptions {
+            archive: true,
+            compress: true,
+            ..Default::default()
+        },
+        advanced: AdvancedOptions {
+            compress_choice: Some("zstd".to_string()),
+            compress_level: Some(-1),
+            ..Default::default()
+        },
+        ..RsyncOptions::default()
+    };
+
+    let args = build_rsync_args(&source, &dest, &opts, None, &[], false, false);
+    let cmd = format!("rsync {}", args.join(" "));
+    let job = to_job_definition(&parse_rsync_command(&cmd).unwrap()).unwrap();
+
+    assert_eq!(
+        job.options.advanced.compress_choice.as_deref(),
+        Some("zstd")
+    );
+    assert_eq!(job.options.advanced.compress_level, Some(-1));
+    assert!(job.options.advanced.custom_args.is_empty());
+}
+
+#[test]
+fn roundtrip_partial_dir() {
+    let source = StorageLocation::Local {
+        path: "/src/".to_string(),
+    };
+    let dest = StorageLocation::Local {
+        path: "/dst/".to_string(),
+    };
+    let opts = RsyncOptions {
+        core_transfer: CoreTransferOptions {
+            partial: true,
+            partial_dir: Some(".rsync-partial".to_string()),
+            ..Default::default()
+        },
+        ..RsyncOptions::default()
+    };
+
+    let args = build_rsync_args(&source, &dest, &opts, None, &[], false, false);
+    let cmd = format!("rsync {}", args.join(" "));
+    let job = to_job_definition(&parse_rsync_command(&cmd).unwrap()).unwrap();
+
+    assert_eq!(
+        job.options.core_transfer.partial_dir.as_deref(),
+        Some(".rsync-partial")
+    );
+    assert!(job.options.advanced.custom_args.is_empty());
+    // Rebuilding gives the same command
+    let rebuilt = build_rsync_args(&source, &dest, &job.options, None, &[], false, false);
+    assert_eq!(rebuilt, args);
+}
+
 #[test]
 fn roundtrip_simple_command() {
     let source = StorageLocation::Local {
@@ -538,7 +814,7 @@ fn roundtrip_simple_command() {
         ..RsyncOptions::default()
     };
 
-    let args = build_rsync_args(&source, &dest, &opts, None, None, false);
+    let args = build_rsync_args(&source, &dest, &opts, None, &[], false, false);
     let cmd = format!("rsync {}", args.join(" "));
 
     let parsed = parse_rsync_command(&cmd).unwrap();
@@ -551,3 +827,36 @@ fn roundtrip_simple_command() {
     assert_eq!(parsed.source.as_deref(), Some("/src/"));
     assert_eq!(parsed.destination.as_deref(), Some("/dst/"));
 }
+
+#[test]
+fn system_file_excludes_round_trip_to_the_toggle() {
+    let opts = RsyncOptions {
+        advanced: AdvancedOptions {
+            exclude_patterns: vec!["*.log".to_string()],
+            exclude_system_files: true,
+            ..Default::default()
+        },
+        ..RsyncOptions::default()
+    };
+    let source = StorageLocation::Local {
+        path: "/src/".to_string(),
+    };
+    let dest = StorageLocation::Local {
+        path: "/dst/".to_string(),
+    };
+    let args = build_rsync_args(&source, &dest, &opts, None, &[], false, false);
+    let cmd = format!("rsync {}", shell_words::join(&args));
+
+    let job = to_job_definition(&parse_rsync_command(&cmd).unwrap()).unwrap();
+
+    assert!(job.options.advanced.exclude_system_files);
+    assert_eq!(job.options.advanced.exclude_patterns, vec!["*.log"]);
+}
+
+#[test]
+fn partial_system_file_list_stays_as_patterns() {
+    let parsed = parse_rsync_command("rsync -a --exclude=.DS_Store /src/ /dst/").unwrap();
+    let job = to_job_definition(&parsed).unwrap();
+    assert!(!job.options.advanced.exclude_system_files);
+    assert_eq!(job.options.advanced.exclude_patterns, vec![".DS_Store"]);
+}
@@ -0,0 +1,147 @@
+use std::path::{Path, PathBuf};
+
+use crate::models::pattern::Matched;
+use crate::services::pattern_tester::{test_pattern_entries, test_patterns};
+use crate::tests::test_file_system::TestFileSystem;
+
+fn sample_fs() -> TestFileSystem {
+    TestFileSystem::new()
+        .with_file("/src/app.log", "log")
+        .with_file("/src/readme.txt", "readme")
+        .with_file("/src/top", "root-level top")
+        .with_file("/src/nested/top", "nested top")
+        .with_file("/src/nested/debug.log", "log")
+        .with_file("/src/cache/data.bin", "data")
+        .with_file("/src/notes/cache", "a file named cache")
+}
+
+fn strings(v: &[&str]) -> Vec<String> {
+    v.iter().map(|s| s.to_string()).collect()
+}
+
+fn result_for(results: &[(PathBuf, Matched)], path: &str) -> Matched {
+    results
+        .iter()
+        .find(|(p, _)| p == Path::new(path))
+        .map(|(_, m)| m.clone())
+        .unwrap_or_else(|| panic!("no result for {}", path))
+}
+
+fn excluded(pattern: &str) -> Matched {
+    Matched::Excluded {
+        pattern: pattern.to_string(),
+    }
+}
+
+#[test]
+fn test_floating_wildcard_matches_at_any_depth() {
+    let fs = sample_fs();
+    let results = test_patterns(&fs, Path::new("/src"), &[], &strings(&["*.log"])).unwrap();
+
+    assert_eq!(result_for(&results, "app.log"), excluded("*.log"));
+    assert_eq!(result_for(&results, "nested/debug.log"), excluded("*.log"));
+    assert_eq!(result_for(&results, "readme.txt"), Matched::Unmatched);
+}
+
+#[test]
+fn test_trailing_slash_matches_directories_only() {
+    let fs = sample_fs();
+    let results = test_patterns(&fs, Path::new("/src"), &[], &strings(&["cache/"])).unwrap();
+
+    assert_eq!(result_for(&results, "cache"), excluded("cache/"));
+    // Contents of an excluded directory are excluded with it
+    assert_eq!(result_for(&results, "cache/data.bin"), excluded("cache/"));
+    // A regular file named "cache" is not a directory
+    assert_eq!(result_for(&results, "notes/cache"), Matched::Unmatched);
+}
+
+#[test]
+fn test_anchored_pattern_matches_only_at_root() {
+    let fs = sample_fs();
+    let results = test_patterns(&fs, Path::new("/src"), &[], &strings(&["/top"])).unwrap();
+
+    assert_eq!(result_for(&results, "top"), excluded("/top"));
+    assert_eq!(result_for(&results, "nested/top"), Matched::Unmatched);
+}
+
+#[test]
+fn test_unanchored_pattern_matches_any_top() {
+    let fs = sample_fs();
+    let results = test_patterns(&fs, Path::new("/src"), &[], &strings(&["top"])).unwrap();
+
+    assert_eq!(result_for(&results, "top"), excluded("top"));
+    assert_eq!(result_for(&results, "nested/top"), excluded("top"));
+}
+
+#[test]
+fn test_slash_pattern_matches_path_suffix() {
+    let fs = sample_fs();
+    let results = test_patterns(&fs, Path::new("/src"), &[], &strings(&["nested/*.log"])).unwrap();
+
+    assert_eq!(
+        result_for(&results, "nested/debug.log"),
+        excluded("nested/*.log")
+    );
+    assert_eq!(result_for(&results, "app.log"), Matched::Unmatched);
+}
+
+#[test]
+fn test_include_patterns_are_reported() {
+    let fs = sample_fs();
+    let results = test_patterns(
+        &fs,
+        Path::new("/src"),
+        &strings(&["*.txt"]),
+        &strings(&["*.log"]),
+    )
+    .unwrap();
+
+    assert_eq!(
+        result_for(&results, "readme.txt"),
+        Matched::Included {
+            pattern: "*.txt".to_string()
+        }
+    );
+    assert_eq!(result_for(&results, "app.log"), excluded("*.log"));
+}
+
+#[test]
+fn test_exclude_wins_over_include_in_builder_order() {
+    let fs = sample_fs();
+    let results = test_patterns(
+        &fs,
+        Path::new("/src"),
+        &strings(&["app.log"]),
+        &strings(&["*.log"]),
+    )
+    .unwrap();
+
+    assert_eq!(result_for(&results, "app.log"), excluded("*.log"));
+}
+
+#[test]
+fn test_question_mark_matches_single_char() {
+    let fs = sample_fs();
+    let results = test_patterns(&fs, Path::new("/src"), &[], &strings(&["to?"])).unwrap();
+
+    assert_eq!(result_for(&results, "top"), excluded("to?"));
+    assert_eq!(result_for(&results, "readme.txt"), Matched::Unmatched);
+}
+
+#[test]
+fn test_entries_report_directories() {
+    let fs = sample_fs();
+    let entries = test_pattern_entries(&fs, Path::new("/src"), &[], &[]).unwrap();
+
+    let cache = entries.iter().find(|e| e.path == "cache").unwrap();
+    assert!(cache.is_dir);
+    let readme = entries.iter().find(|e| e.path == "readme.txt").unwrap();
+    assert!(!readme.is_dir);
+    assert!(entries.iter().all(|e| e.matched == Matched::Unmatched));
+}
+
+#[test]
+fn test_missing_source_is_error() {
+    let fs = sample_fs();
+    assert!(test_patterns(&fs, Path::new("/missing"), &[], &[]).is_err());
+}
@@ -0,0 +1,264 @@
+use crate::services::rsync_glob::matches;
+
+fn file(pattern: &str, path: &str) -> bool {
+    matches(pattern, path, false)
+}
+
+fn dir(pattern: &str, path: &str) -> bool {
+    matches(pattern, path, true)
+}
+
+// --- Floating name patterns ---
+
+#[test]
+fn test_plain_name_matches_final_component_at_any_depth() {
+    assert!(file("file-name", "file-name"));
+    assert!(file("file-name", "a/b/file-name"));
+    assert!(!file("file-name", "file-name.txt"));
+    assert!(!file("file-name", "file-name/child"));
+}
+
+#[test]
+fn test_star_suffix_excludes_object_files() {
+    // "- *.o" would exclude all names ending in .o
+    assert!(file("*.o", "main.o"));
+    assert!(file("*.o", "src/lib/util.o"));
+    assert!(!file("*.o", "main.c"));
+    assert!(!file("*.o", "main.o.bak"));
+}
+
+#[test]
+fn test_star_matches_empty_and_whole_name() {
+    assert!(file("*", "anything"));
+    assert!(file("a*", "a"));
+    assert!(file("*.log", ".log"));
+}
+
+#[test]
+fn test_name_pattern_matches_files_and_directories() {
+    assert!(file("build", "build"));
+    assert!(dir("build", "build"));
+    assert!(dir("build", "project/build"));
+}
+
+// --- Anchoring ---
+
+#[test]
+fn test_leading_slash_anchors_to_transfer_root() {
+    // "- /foo" would exclude a file (or directory) named foo in the transfer root
+    assert!(file("/foo", "foo"));
+    assert!(dir("/foo", "foo"));
+    assert!(!file("/foo", "sub/foo"));
+}
+
+#[test]
+fn test_anchored_path_pattern() {
+    assert!(file("/some/path/file", "some/path/file"));
+    assert!(!file("/some/path/file", "x/some/path/file"));
+}
+
+#[test]
+fn test_anchored_wildcard_pattern() {
+    assert!(file("/*.txt", "notes.txt"));
+    assert!(!file("/*.txt", "docs/notes.txt"));
+}
+
+#[test]
+fn test_path_with_leading_slash_is_treated_as_relative() {
+    assert!(file("/foo", "/foo"));
+    assert!(file("*.o", "/src/main.o"));
+}
+
+// --- Directory-only rules ---
+
+#[test]
+fn test_trailing_slash_matches_directories_only() {
+    // "- foo/" would exclude any directory named foo
+    assert!(dir("foo/", "foo"));
+    assert!(dir("foo/", "a/b/foo"));
+    assert!(!file("foo/", "foo"));
+    assert!(!file("foo/", "a/foo"));
+}
+
+#[test]
+fn test_anchored_directory_only() {
+    assert!(dir("/cache/", "cache"));
+    assert!(!dir("/cache/", "app/cache"));
+    assert!(!file("/cache/", "cache"));
+}
+
+#[test]
+fn test_wildcard_directory_only() {
+    // "+ */" matches every directory
+    assert!(dir("*/", "src"));
+    assert!(dir("*/", "src/nested"));
+    assert!(!file("*/", "main.c"));
+}
+
+#[test]
+fn test_trailing_slash_on_path_is_ignored() {
+    assert!(dir("foo/", "foo/"));
+}
+
+// --- Floating path patterns ---
+
+#[test]
+fn test_pattern_with_slash_matches_full_path_suffix() {
+    assert!(file("sub/foo", "sub/foo"));
+    assert!(file("sub/foo", "a/sub/foo"));
+    assert!(!file("sub/foo", "xsub/foo"));
+    assert!(!file("sub/foo", "sub/foo/bar"));
+}
+
+#[test]
+fn test_star_does_not_cross_slashes() {
+    // "- foo/*/bar" excludes bar exactly two levels below a directory named foo
+    assert!(file("foo/*/bar", "foo/x/bar"));
+    assert!(file("foo/*/bar", "top/foo/x/bar"));
+    assert!(!file("foo/*/bar", "foo/bar"));
+    assert!(!file("foo/*/bar", "foo/x/y/bar"));
+}
+
+#[test]
+fn test_star_in_name_only_pattern_stays_in_component() {
+    assert!(!file("a*b", "a/b"));
+    assert!(file("a*b", "dir/a-to-b"));
+}
+
+// --- Double star ---
+
+#[test]
+fn test_double_star_crosses_slashes() {
+    // "- /foo/**/bar" excludes bar two or more levels below top-level foo
+    assert!(file("/foo/**/bar", "foo/x/bar"));
+    assert!(file("/foo/**/bar", "foo/x/y/z/bar"));
+    assert!(!file("/foo/**/bar", "foo/bar"));
+    assert!(!file("/foo/**/bar", "top/foo/x/bar"));
+}
+
+#[test]
+fn test_floating_double_star() {
+    assert!(file("foo/**/bar", "a/foo/x/y/bar"));
+    assert!(!file("foo/**/bar", "a/foo/bar"));
+}
+
+#[test]
+fn test_double_star_without_slash_matches_full_path() {
+    // A "**" makes the pattern match against the full path
+    assert!(file("foo**", "foo/bar/baz"));
+    assert!(file("foo**", "foo"));
+    assert!(file("**.bak", "a/b/c.bak"));
+}
+
+#[test]
+fn test_trailing_double_star_matches_contents_not_dir() {
+    assert!(file("/data/**", "data/a"));
+    assert!(file("/data/**", "data/a/b"));
+    assert!(!dir("/data/**", "data"));
+}
+
+#[test]
+fn test_triple_star_matches_dir_and_contents() {
+    // "dir_name/***" matches both the directory and everything in it
+    assert!(dir("/dir_name/***", "dir_name"));
+    assert!(file("/dir_name/***", "dir_name/file"));
+    assert!(file("/dir_name/***", "dir_name/a/b/c"));
+    assert!(!dir("/dir_name/***", "dir_name2"));
+    assert!(file("dir_name/***", "top/dir_name/file"));
+}
+
+// --- Question mark ---
+
+#[test]
+fn test_question_mark_matches_single_non_slash_char() {
+    assert!(file("file?.txt", "file1.txt"));
+    assert!(!file("file?.txt", "file.txt"));
+    assert!(!file("file?.txt", "file12.txt"));
+    assert!(!file("a?b", "a/b"));
+}
+
+// --- Character classes ---
+
+#[test]
+fn test_character_class_set_and_range() {
+    assert!(file("log[0-9].txt", "log5.txt"));
+    assert!(!file("log[0-9].txt", "logx.txt"));
+    assert!(file("[abc].c", "b.c"));
+    assert!(!file("[abc].c", "d.c"));
+}
+
+#[test]
+fn test_character_class_negation() {
+    assert!(file("[!a]*", "bcd"));
+    assert!(!file("[!a]*", "abc"));
+    assert!(file("[^a]*", "bcd"));
+    assert!(!file("[^a]*", "abc"));
+}
+
+#[test]
+fn test_character_class_named() {
+    assert!(file("[[:digit:]]*", "2024-report"));
+    assert!(!file("[[:digit:]]*", "report"));
+    assert!(file("[[:upper:]]*", "README"));
+    assert!(file("x[[:alpha:][:digit:]]", "x7"));
+}
+
+#[test]
+fn test_character_class_literal_close_bracket() {
+    assert!(file("[]]", "]"));
+    assert!(file("[!]]", "a"));
+    assert!(!file("[!]]", "]"));
+}
+
+#[test]
+fn test_character_class_never_matches_slash() {
+    assert!(!file("a[!x]b", "a/b"));
+}
+
+#[test]
+fn test_unterminated_class_is_literal() {
+    assert!(file("[abc", "[abc"));
+    assert!(!file("[abc", "a"));
+}
+
+// --- Escaping ---
+
+#[test]
+fn test_backslash_escapes_wildcards() {
+    assert!(file("\\*.txt", "*.txt"));
+    assert!(!file("\\*.txt", "a.txt"));
+    assert!(file("what\\?*", "what?-now"));
+}
+
+#[test]
+fn test_backslash_is_literal_without_wildcards() {
+    assert!(file("back\\slash", "back\\slash"));
+}
+
+// --- Degenerate patterns ---
+
+#[test]
+fn test_empty_patterns_match_nothing() {
+    assert!(!file("", "a"));
+    assert!(!dir("/", "a"));
+    assert!(!dir("//", "a"));
+}
+
+// --- Filter list example from the rsync man page ---
+
+#[test]
+fn test_c_files_only_filter_list() {
+    // "+ */", "+ *.c", "- *": keep directories and .c files only
+    let rules = [("+", "*/"), ("+", "*.c"), ("-", "*")];
+    let first_match = |path: &str, is_dir: bool| {
+        rules
+            .iter()
+            .find(|(_, p)| matches(p, path, is_dir))
+            .map(|(action, _)| *action)
+    };
+
+    assert_eq!(first_match("src", true), Some("+"));
+    assert_eq!(first_match("src/main.c", false), Some("+"));
+    assert_eq!(first_match("src/main.h", false), Some("-"));
+    assert_eq!(first_match("Makefile", false), Some("-"));
+}
@@ -163,3 +163,28 @@ fn test_auto_create_parents() {
     assert!(fs.is_dir(Path::new("/a/b")));
     assert!(fs.is_file(Path::new("/a/b/c.txt")));
 }
+
+#[test]
+fn test_real_available_space_reads_the_filesystem() {
+    use crate::file_system::real_file_system::RealFileSystem;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let fs = RealFileSystem::new();
+
+    let available = fs.available_space(tmp.path()).unwrap();
+
+    assert!(available > 0);
+    assert!(available < u64::MAX);
+}
+
+#[test]
+fn test_real_available_space_of_missing_dir_uses_its_parent() {
+    use crate::file_system::real_file_system::RealFileSystem;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let fs = RealFileSystem::new();
+
+    let missing = fs.available_space(&tmp.path().join("not/yet/created"));
+
+    assert!(missing.is_ok_and(|bytes| bytes > 0 && bytes < u64::MAX));
+}
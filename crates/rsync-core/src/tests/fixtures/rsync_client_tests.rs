@@ -271,7 +271,7 @@ fn test_build_command_args_mirror_mode() {
         },
         ..RsyncOptions::default()
     };
-    let args = command_builder::build_rsync_args(&source, &dest, &options, None, None, false);
+    let args = command_builder::build_rsync_args(&source, &dest, &options, None, &[], false, false);
 
     assert!(args.contains(&"-a".to_string()));
     assert!(args.contains(&"--delete".to_string()));
@@ -294,7 +294,7 @@ fn test_build_command_args_versioned_mode() {
         },
         ..RsyncOptions::default()
     };
-    let args = command_builder::build_rsync_args(&source, &dest, &options, None, None, false);
+    let args = command_builder::build_rsync_args(&source, &dest, &options, None, &[], false, false);
 
     assert!(args.contains(&"-a".to_string()));
     assert!(args.contains(&"/src/".to_string()));
@@ -310,8 +310,15 @@ fn test_build_command_args_snapshot_mode() {
         path: "/dst/current/".to_string(),
     };
     let options = RsyncOptions::default();
-    let args =
-        command_builder::build_rsync_args(&source, &dest, &options, None, Some("/dst/prev/"), false);
+    let args = command_builder::build_rsync_args(
+        &source,
+        &dest,
+        &options,
+        None,
+        &["/dst/prev/"],
+        false,
+        false,
+    );
 
     assert!(args.contains(&"--link-dest=/dst/prev/".to_string()));
 }
@@ -332,10 +339,21 @@ fn test_build_command_args_ssh_config() {
         port: 2222,
         identity_file: Some("/home/user/.ssh/id_rsa".to_string()),
         strict_host_key_checking: true,
+        connect_timeout: None,
+        server_alive_interval: None,
+        jump_host: None,
         custom_ssh_command: None,
     };
     let options = RsyncOptions::default();
-    let args = command_builder::build_rsync_args(&source, &dest, &options, Some(&ssh_config), None, false);
+    let args = command_builder::build_rsync_args(
+        &source,
+        &dest,
+        &options,
+        Some(&ssh_config),
+        &[],
+        false,
+        false,
+    );
 
     assert!(args.contains(&"-e".to_string()));
     let ssh_arg = args
@@ -362,7 +380,7 @@ fn test_build_command_args_excludes() {
         },
         ..RsyncOptions::default()
     };
-    let args = command_builder::build_rsync_args(&source, &dest, &options, None, None, false);
+    let args = command_builder::build_rsync_args(&source, &dest, &options, None, &[], false, false);
 
     assert!(args.contains(&"--exclude=*.log".to_string()));
     assert!(args.contains(&"--exclude=tmp/".to_string()));
@@ -0,0 +1,67 @@
+use std::path::Path;
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+
+use uuid::Uuid;
+
+use crate::database::sqlite::Database;
+use crate::models::itemize::ItemizedChange;
+use crate::models::job::JobStatus;
+use crate::models::progress::{JobStatusEvent, LogLine, ProgressUpdate};
+use crate::repository::sqlite::invocation::SqliteInvocationRepository;
+use crate::repository::sqlite::job::SqliteJobRepository;
+use crate::repository::sqlite::settings::SqliteSettingsRepository;
+use crate::repository::sqlite::snapshot::SqliteSnapshotRepository;
+use crate::repository::sqlite::statistics::SqliteStatisticsRepository;
+use crate::services::execution_handler::ExecutionEventHandler;
+use crate::services::job_executor::JobExecutor;
+use crate::services::job_service::JobService;
+use crate::services::running_jobs::RunningJobs;
+use crate::services::settings_service::SettingsService;
+use crate::services::statistics_service::StatisticsService;
+
+/// Passes the final status of each run to the test.
+pub struct StatusHandler(pub Mutex<Sender<JobStatusEvent>>);
+
+impl ExecutionEventHandler for StatusHandler {
+    fn on_log_line(&self, _line: LogLine) {}
+    fn on_progress(&self, _progress: &ProgressUpdate) {}
+    fn on_status_change(&self, status: JobStatusEvent) {
+        if status.status != JobStatus::Running {
+            let _ = self.0.lock().unwrap().send(status);
+        }
+    }
+    fn on_itemized_change(&self, _invocation_id: Uuid, _change: &ItemizedChange) {}
+}
+
+/// A test-mode executor running `script` in place of rsync.
+pub fn mock_rsync_executor(dir: &Path, script: &str) -> (JobExecutor, Arc<JobService>, Database) {
+    use std::os::unix::fs::PermissionsExt;
+
+    let binary = dir.join("rsync-mock");
+    std::fs::write(&binary, format!("#!/bin/sh\n{}\n", script)).unwrap();
+    std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let db = Database::in_memory().unwrap();
+    let conn = db.conn();
+    let job_service = Arc::new(JobService::new(
+        Arc::new(SqliteJobRepository::new(conn.clone())),
+        Arc::new(SqliteInvocationRepository::new(conn.clone())),
+        Arc::new(SqliteSnapshotRepository::new(conn.clone())),
+    ));
+    let settings_service = Arc::new(SettingsService::new(Arc::new(
+        SqliteSettingsRepository::new(conn.clone()),
+    )));
+    settings_service.set_test_mode(true).unwrap();
+    let executor = JobExecutor::new(
+        Arc::clone(&job_service),
+        Arc::new(StatisticsService::new(Arc::new(
+            SqliteStatisticsRepository::new(conn),
+        ))),
+        settings_service,
+        Arc::new(RunningJobs::new()),
+        dir.join("logs").to_string_lossy().to_string(),
+    )
+    .with_rsync_binary(binary.to_string_lossy().to_string());
+    (executor, job_service, db)
+}
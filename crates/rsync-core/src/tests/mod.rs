@@ -8,5 +8,7 @@ mod command;
 mod repository;
 #[cfg(test)]
 mod fixtures;
+#[cfg(all(test, unix))]
+mod mock_executor;
 #[cfg(test)]
 mod service;
@@ -0,0 +1,180 @@
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use rusqlite::Connection;
+
+use crate::database::sqlite::{db_error, with_busy_retry, Database, BUSY_RETRIES};
+use crate::error::AppError;
+use crate::repository::settings::SettingsRepository;
+use crate::repository::sqlite::settings::SqliteSettingsRepository;
+
+fn db_path(tmp: &tempfile::TempDir) -> String {
+    let path = tmp.path().join("app.db").to_str().unwrap().to_string();
+    Database::open(&path).unwrap();
+    path
+}
+
+/// Take the write lock from a second connection and release it after `hold`.
+fn hold_write_lock(path: &str, hold: Duration) -> thread::JoinHandle<()> {
+    let holder = Connection::open(path).unwrap();
+    holder.execute_batch("BEGIN IMMEDIATE").unwrap();
+    thread::spawn(move || {
+        thread::sleep(hold);
+        holder.execute_batch("COMMIT").unwrap();
+    })
+}
+
+/// A connection that reports a held lock at once instead of waiting on it.
+fn impatient_connection(path: &str) -> Mutex<Connection> {
+    let conn = Connection::open(path).unwrap();
+    conn.busy_timeout(Duration::ZERO).unwrap();
+    Mutex::new(conn)
+}
+
+fn insert_setting(conn: &mut Connection, attempts: &mut u32) -> Result<(), AppError> {
+    *attempts += 1;
+    conn.execute(
+        "INSERT OR REPLACE INTO settings (key, value) VALUES ('k', 'v')",
+        [],
+    )
+    .map_err(db_error)?;
+    Ok(())
+}
+
+#[test]
+fn test_open_sets_busy_timeout() {
+    let tmp = tempfile::tempdir().unwrap();
+    let db = Database::open(&db_path(&tmp)).unwrap();
+    let conn = db.conn();
+    let timeout: i64 = conn
+        .lock()
+        .unwrap()
+        .query_row("PRAGMA busy_timeout", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(timeout, 5000);
+}
+
+#[test]
+fn test_busy_retry_succeeds_once_lock_is_released() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = db_path(&tmp);
+    let writer = impatient_connection(&path);
+
+    let holder = hold_write_lock(&path, Duration::from_millis(120));
+    let mut attempts = 0;
+    let result = with_busy_retry(&writer, |conn| insert_setting(conn, &mut attempts));
+    holder.join().unwrap();
+
+    assert!(result.is_ok(), "{:?}", result);
+    assert!(attempts > 1, "write should have been retried");
+    let value: String = writer
+        .lock()
+        .unwrap()
+        .query_row("SELECT value FROM settings WHERE key = 'k'", [], |row| {
+            row.get(0)
+        })
+        .unwrap();
+    assert_eq!(value, "v");
+}
+
+#[test]
+fn test_busy_retry_gives_up_with_busy_error() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = db_path(&tmp);
+    let writer = impatient_connection(&path);
+
+    let holder = hold_write_lock(&path, Duration::from_secs(2));
+    let mut attempts = 0;
+    let result = with_busy_retry(&writer, |conn| insert_setting(conn, &mut attempts));
+    holder.join().unwrap();
+
+    assert!(
+        matches!(result, Err(AppError::DatabaseBusy(_))),
+        "{:?}",
+        result
+    );
+    assert_eq!(attempts, BUSY_RETRIES + 1);
+}
+
+#[test]
+fn test_busy_retry_does_not_retry_other_errors() {
+    let writer = Mutex::new(Connection::open_in_memory().unwrap());
+    let mut attempts = 0;
+    let result: Result<(), AppError> = with_busy_retry(&writer, |_| {
+        attempts += 1;
+        Err(AppError::NotFound("missing".to_string()))
+    });
+    assert!(matches!(result, Err(AppError::NotFound(_))));
+    assert_eq!(attempts, 1);
+}
+
+#[test]
+fn test_busy_retry_ignores_lock_wording_in_other_errors() {
+    let writer = Mutex::new(Connection::open_in_memory().unwrap());
+    let mut attempts = 0;
+    let result: Result<(), AppError> = with_busy_retry(&writer, |_| {
+        attempts += 1;
+        Err(AppError::DatabaseError("database is locked".to_string()))
+    });
+    assert!(matches!(result, Err(AppError::DatabaseError(_))));
+    assert_eq!(attempts, 1);
+}
+
+#[test]
+fn test_db_error_classifies_lock_by_error_code() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = db_path(&tmp);
+    let writer = Connection::open(&path).unwrap();
+    writer.busy_timeout(Duration::ZERO).unwrap();
+
+    let holder = hold_write_lock(&path, Duration::from_millis(100));
+    let busy = writer.execute_batch("BEGIN IMMEDIATE").unwrap_err();
+    holder.join().unwrap();
+    let other = writer
+        .execute_batch("SELECT * FROM no_such_table")
+        .unwrap_err();
+
+    assert!(matches!(db_error(busy), AppError::DatabaseBusy(_)));
+    assert!(matches!(db_error(other), AppError::DatabaseError(_)));
+}
+
+#[test]
+fn test_db_error_classifies_duplicate_key_by_error_code() {
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch(
+        "CREATE TABLE t (id TEXT PRIMARY KEY, name TEXT UNIQUE, n INTEGER NOT NULL);
+         INSERT INTO t VALUES ('a', 'x', 1);",
+    )
+    .unwrap();
+
+    let duplicate_id = conn
+        .execute_batch("INSERT INTO t VALUES ('a', 'y', 1)")
+        .unwrap_err();
+    let duplicate_name = conn
+        .execute_batch("INSERT INTO t VALUES ('b', 'x', 1)")
+        .unwrap_err();
+    let missing_value = conn
+        .execute_batch("INSERT INTO t VALUES ('c', 'z', NULL)")
+        .unwrap_err();
+
+    assert!(matches!(db_error(duplicate_id), AppError::Conflict(_)));
+    assert!(matches!(db_error(duplicate_name), AppError::Conflict(_)));
+    assert!(matches!(
+        db_error(missing_value),
+        AppError::DatabaseError(_)
+    ));
+}
+
+#[test]
+fn test_repository_write_waits_for_other_connection() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = db_path(&tmp);
+    let repo = SqliteSettingsRepository::new(Database::open(&path).unwrap().conn());
+
+    let holder = hold_write_lock(&path, Duration::from_millis(200));
+    repo.set_setting("key", "value").unwrap();
+    holder.join().unwrap();
+
+    assert_eq!(repo.get_setting("key").unwrap(), Some("value".to_string()));
+}
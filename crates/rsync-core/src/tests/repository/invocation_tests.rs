@@ -1,9 +1,10 @@
-use chrono::Utc;
+use chrono::{Duration, Utc};
 use uuid::Uuid;
 
 use crate::database::sqlite::Database;
 use crate::models::backup::{
-    BackupInvocation, ExecutionOutput, InvocationStatus, InvocationTrigger, TransferStats,
+    BackupInvocation, DetailedStats, ExecutionOutput, InvocationStatus, InvocationTrigger,
+    TransferStats,
 };
 use crate::repository::invocation::InvocationRepository;
 use crate::repository::job::JobRepository;
@@ -28,10 +29,15 @@ fn make_invocation(job_id: Uuid) -> BackupInvocation {
         finished_at: None,
         status: InvocationStatus::Running,
         trigger: InvocationTrigger::Manual,
+        rerun_of: None,
+        resumed_from: None,
+        warning: None,
         transfer_stats: TransferStats {
             bytes_transferred: 0,
             files_transferred: 0,
             total_files: 100,
+            files_deleted: 0,
+            detailed: None,
         },
         execution_output: ExecutionOutput {
             command_executed: "rsync -a /src/ /dst/".to_string(),
@@ -59,6 +65,46 @@ fn test_create_and_get_invocation() {
     assert_eq!(retrieved.execution_output.log_file_path, inv.execution_output.log_file_path);
 }
 
+#[test]
+fn test_rerun_of_round_trip() {
+    let (job_repo, inv_repo) = setup();
+    let job = create_test_job();
+    job_repo.create_job(&job).unwrap();
+
+    let original = make_invocation(job.id);
+    inv_repo.create_invocation(&original).unwrap();
+    let mut rerun = make_invocation(job.id);
+    rerun.rerun_of = Some(original.id);
+    inv_repo.create_invocation(&rerun).unwrap();
+
+    assert_eq!(
+        inv_repo.get_invocation(&original.id).unwrap().rerun_of,
+        None
+    );
+    assert_eq!(
+        inv_repo.get_invocation(&rerun.id).unwrap().rerun_of,
+        Some(original.id)
+    );
+}
+
+#[test]
+fn test_resumed_from_round_trip() {
+    let (job_repo, inv_repo) = setup();
+    let job = create_test_job();
+    job_repo.create_job(&job).unwrap();
+
+    let interrupted = make_invocation(job.id);
+    inv_repo.create_invocation(&interrupted).unwrap();
+    let mut resumed = make_invocation(job.id);
+    resumed.resumed_from = Some(interrupted.id);
+    inv_repo.create_invocation(&resumed).unwrap();
+
+    assert_eq!(
+        inv_repo.get_invocation(&resumed.id).unwrap().resumed_from,
+        Some(interrupted.id)
+    );
+}
+
 #[test]
 fn test_update_invocation() {
     let (job_repo, inv_repo) = setup();
@@ -72,6 +118,7 @@ fn test_update_invocation() {
     inv.finished_at = Some(Utc::now());
     inv.transfer_stats.bytes_transferred = 1024;
     inv.transfer_stats.files_transferred = 10;
+    inv.transfer_stats.files_deleted = 4;
     inv.execution_output.exit_code = Some(0);
     inv_repo.update_invocation(&inv).unwrap();
 
@@ -79,7 +126,57 @@ fn test_update_invocation() {
     assert_eq!(retrieved.status, InvocationStatus::Succeeded);
     assert!(retrieved.finished_at.is_some());
     assert_eq!(retrieved.transfer_stats.bytes_transferred, 1024);
+    assert_eq!(retrieved.transfer_stats.files_deleted, 4);
     assert_eq!(retrieved.execution_output.exit_code, Some(0));
+    assert_eq!(retrieved.warning, None);
+}
+
+#[test]
+fn test_update_invocation_warning() {
+    let (job_repo, inv_repo) = setup();
+    let job = create_test_job();
+    job_repo.create_job(&job).unwrap();
+
+    let mut inv = make_invocation(job.id);
+    inv_repo.create_invocation(&inv).unwrap();
+
+    inv.status = InvocationStatus::Succeeded;
+    inv.warning = Some("Change alarm: 95.0% of files changed (limit 90%)".to_string());
+    inv_repo.update_invocation(&inv).unwrap();
+
+    assert_eq!(
+        inv_repo.get_invocation(&inv.id).unwrap().warning,
+        inv.warning
+    );
+}
+
+#[test]
+fn test_update_invocation_detailed_stats() {
+    let (job_repo, inv_repo) = setup();
+    let job = create_test_job();
+    job_repo.create_job(&job).unwrap();
+
+    let mut inv = make_invocation(job.id);
+    inv_repo.create_invocation(&inv).unwrap();
+    assert_eq!(
+        inv_repo
+            .get_invocation(&inv.id)
+            .unwrap()
+            .transfer_stats
+            .detailed,
+        None
+    );
+
+    inv.transfer_stats.detailed = Some(DetailedStats {
+        matched_data: Some(1_000_000),
+        file_list_generation_secs: Some(0.25),
+        bytes_per_sec: Some(52_938.0),
+        ..DetailedStats::default()
+    });
+    inv_repo.update_invocation(&inv).unwrap();
+
+    let stored = inv_repo.get_invocation(&inv.id).unwrap();
+    assert_eq!(stored.transfer_stats.detailed, inv.transfer_stats.detailed);
 }
 
 #[test]
@@ -97,6 +194,66 @@ fn test_list_invocations_for_job() {
     assert_eq!(list.len(), 2);
 }
 
+#[test]
+fn test_list_invocations_since_is_oldest_first_from_cutoff() {
+    let (job_repo, inv_repo) = setup();
+    let job = create_test_job();
+    job_repo.create_job(&job).unwrap();
+    let cutoff = Utc::now() - Duration::hours(1);
+    let mut invocations = Vec::new();
+    for offset in [Duration::hours(2), Duration::zero(), -Duration::minutes(30)] {
+        let mut inv = make_invocation(job.id);
+        inv.started_at = cutoff - offset;
+        inv_repo.create_invocation(&inv).unwrap();
+        invocations.push(inv);
+    }
+
+    let ids: Vec<Uuid> = inv_repo
+        .list_invocations_since(&cutoff)
+        .unwrap()
+        .iter()
+        .map(|inv| inv.id)
+        .collect();
+
+    assert_eq!(ids, vec![invocations[1].id, invocations[2].id]);
+}
+
+#[test]
+fn test_list_invocations_page_walks_every_run_once() {
+    let (job_repo, inv_repo) = setup();
+    let job = create_test_job();
+    job_repo.create_job(&job).unwrap();
+    let start = Utc::now() - Duration::hours(1);
+    // Three runs share a start time, so a page boundary falls among them
+    let mut expected = Vec::new();
+    for offset in [0, 0, 0, 10, 20] {
+        let mut inv = make_invocation(job.id);
+        inv.started_at = start + Duration::minutes(offset);
+        inv_repo.create_invocation(&inv).unwrap();
+        expected.push(inv);
+    }
+    expected.sort_by_key(|inv| (inv.started_at, inv.id.to_string()));
+
+    let mut seen = Vec::new();
+    let mut page: Vec<BackupInvocation> = Vec::new();
+    loop {
+        let after = page.last().map(|inv| (&inv.started_at, &inv.id));
+        page = inv_repo.list_invocations_page(None, after, 2).unwrap();
+        if page.is_empty() {
+            break;
+        }
+        seen.extend(page.iter().map(|inv| inv.id));
+    }
+    let expected_ids: Vec<Uuid> = expected.iter().map(|inv| inv.id).collect();
+    assert_eq!(seen, expected_ids);
+
+    let since = start + Duration::minutes(5);
+    let later = inv_repo
+        .list_invocations_page(Some(&since), None, 10)
+        .unwrap();
+    assert_eq!(later.len(), 2);
+}
+
 #[test]
 fn test_cascade_delete_invocations() {
     let (job_repo, inv_repo) = setup();
@@ -174,3 +331,25 @@ fn test_list_all_invocations() {
     let all = inv_repo.list_all_invocations().unwrap();
     assert_eq!(all.len(), 2);
 }
+
+#[test]
+fn test_clear_log_file_path_keeps_record() {
+    let (job_repo, inv_repo) = setup();
+    let job = create_test_job();
+    job_repo.create_job(&job).unwrap();
+
+    let inv = make_invocation(job.id);
+    inv_repo.create_invocation(&inv).unwrap();
+
+    inv_repo.clear_log_file_path(&inv.id).unwrap();
+    let retrieved = inv_repo.get_invocation(&inv.id).unwrap();
+    assert_eq!(retrieved.execution_output.log_file_path, None);
+    assert_eq!(retrieved.transfer_stats, inv.transfer_stats);
+    assert_eq!(retrieved.started_at.timestamp(), inv.started_at.timestamp());
+}
+
+#[test]
+fn test_clear_log_file_path_nonexistent_invocation() {
+    let (_job_repo, inv_repo) = setup();
+    assert!(inv_repo.clear_log_file_path(&Uuid::new_v4()).is_err());
+}
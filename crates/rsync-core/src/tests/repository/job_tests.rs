@@ -1,4 +1,8 @@
+use chrono::Duration;
+
 use crate::database::sqlite::Database;
+use crate::error::AppError;
+use crate::models::job::{ChangeAlarm, ProcessPriority};
 use crate::repository::job::JobRepository;
 use crate::repository::sqlite::job::SqliteJobRepository;
 use crate::tests::test_helpers::create_test_job;
@@ -54,6 +58,51 @@ fn test_update_job() {
     assert!(!retrieved.enabled);
 }
 
+#[test]
+fn test_update_job_if_unchanged_succeeds_for_current_version() {
+    let repo = setup();
+    let mut job = create_test_job();
+    repo.create_job(&job).unwrap();
+
+    let seen = job.updated_at;
+    job.name = "Edited".to_string();
+    job.updated_at = seen + Duration::seconds(1);
+    repo.update_job_if_unchanged(&job, &seen).unwrap();
+
+    let retrieved = repo.get_job(&job.id).unwrap();
+    assert_eq!(retrieved.name, "Edited");
+    assert_eq!(retrieved.updated_at, job.updated_at);
+}
+
+#[test]
+fn test_update_job_if_unchanged_rejects_stale_version() {
+    let repo = setup();
+    let mut job = create_test_job();
+    repo.create_job(&job).unwrap();
+    let stale = job.updated_at;
+
+    // Another writer saves first
+    let mut other = job.clone();
+    other.name = "Saved elsewhere".to_string();
+    other.updated_at = stale + Duration::seconds(5);
+    repo.update_job(&other).unwrap();
+
+    job.name = "Stale edit".to_string();
+    job.updated_at = stale + Duration::seconds(10);
+    let result = repo.update_job_if_unchanged(&job, &stale);
+
+    assert!(matches!(result, Err(AppError::Conflict(_))));
+    assert_eq!(repo.get_job(&job.id).unwrap().name, "Saved elsewhere");
+}
+
+#[test]
+fn test_update_job_if_unchanged_missing_job() {
+    let repo = setup();
+    let job = create_test_job();
+    let result = repo.update_job_if_unchanged(&job, &job.updated_at);
+    assert!(matches!(result, Err(AppError::NotFound(_))));
+}
+
 #[test]
 fn test_delete_job() {
     let repo = setup();
@@ -71,3 +120,224 @@ fn test_get_nonexistent_job() {
     let result = repo.get_job(&Uuid::new_v4());
     assert!(result.is_err());
 }
+
+#[test]
+fn test_job_env_roundtrip() {
+    let repo = setup();
+    let mut job = create_test_job();
+    job.run_policy
+        .env
+        .insert("SSH_AUTH_SOCK".to_string(), "/tmp/agent.sock".to_string());
+    repo.create_job(&job).unwrap();
+    assert_eq!(
+        repo.get_job(&job.id).unwrap().run_policy.env,
+        job.run_policy.env
+    );
+
+    job.run_policy
+        .env
+        .insert("RSYNC_RSH".to_string(), "ssh -p 2222".to_string());
+    repo.update_job(&job).unwrap();
+    assert_eq!(
+        repo.get_job(&job.id).unwrap().run_policy.env,
+        job.run_policy.env
+    );
+}
+
+#[test]
+fn test_job_max_runtime_roundtrip() {
+    let repo = setup();
+    let mut job = create_test_job();
+    job.run_policy.max_runtime_mins = Some(90);
+    repo.create_job(&job).unwrap();
+    assert_eq!(
+        repo.get_job(&job.id).unwrap().run_policy.max_runtime_mins,
+        Some(90)
+    );
+
+    job.run_policy.max_runtime_mins = None;
+    repo.update_job(&job).unwrap();
+    assert_eq!(
+        repo.get_job(&job.id).unwrap().run_policy.max_runtime_mins,
+        None
+    );
+}
+
+#[test]
+fn test_job_success_exit_codes_roundtrip() {
+    let repo = setup();
+    let mut job = create_test_job();
+    job.run_policy.success_exit_codes = vec![0, 24];
+    repo.create_job(&job).unwrap();
+    assert_eq!(
+        repo.get_job(&job.id).unwrap().run_policy.success_exit_codes,
+        vec![0, 24]
+    );
+
+    job.run_policy.success_exit_codes = vec![0];
+    repo.update_job(&job).unwrap();
+    assert_eq!(
+        repo.get_job(&job.id).unwrap().run_policy.success_exit_codes,
+        vec![0]
+    );
+}
+
+#[test]
+fn test_job_process_priority_roundtrip() {
+    let repo = setup();
+    let mut job = create_test_job();
+    job.run_policy.process_priority = Some(ProcessPriority::Low);
+    repo.create_job(&job).unwrap();
+    assert_eq!(
+        repo.get_job(&job.id).unwrap().run_policy.process_priority,
+        Some(ProcessPriority::Low)
+    );
+
+    job.run_policy.process_priority = None;
+    repo.update_job(&job).unwrap();
+    assert_eq!(
+        repo.get_job(&job.id).unwrap().run_policy.process_priority,
+        None
+    );
+}
+
+#[test]
+fn test_job_create_destination_roundtrip() {
+    let repo = setup();
+    let mut job = create_test_job();
+    job.run_policy.create_destination = true;
+    repo.create_job(&job).unwrap();
+    assert!(repo.get_job(&job.id).unwrap().run_policy.create_destination);
+
+    job.run_policy.create_destination = false;
+    repo.update_job(&job).unwrap();
+    assert!(!repo.get_job(&job.id).unwrap().run_policy.create_destination);
+}
+
+#[test]
+fn test_job_run_on_startup_roundtrip() {
+    let repo = setup();
+    let mut job = create_test_job();
+    job.run_policy.run_on_startup = true;
+    repo.create_job(&job).unwrap();
+    assert!(repo.get_job(&job.id).unwrap().run_policy.run_on_startup);
+
+    job.run_policy.run_on_startup = false;
+    repo.update_job(&job).unwrap();
+    assert!(!repo.get_job(&job.id).unwrap().run_policy.run_on_startup);
+}
+
+#[test]
+fn test_job_change_alarm_roundtrip() {
+    let repo = setup();
+    let mut job = create_test_job();
+    let alarm = ChangeAlarm {
+        max_changed_percent: Some(90.0),
+        max_deleted_percent: Some(25.5),
+        notify: true,
+    };
+    job.safety.change_alarm = Some(alarm.clone());
+    repo.create_job(&job).unwrap();
+    assert_eq!(
+        repo.get_job(&job.id).unwrap().safety.change_alarm,
+        Some(alarm)
+    );
+
+    job.safety.change_alarm = None;
+    repo.update_job(&job).unwrap();
+    assert_eq!(repo.get_job(&job.id).unwrap().safety.change_alarm, None);
+}
+
+#[test]
+fn test_job_skip_if_unchanged_roundtrip() {
+    let repo = setup();
+    let mut job = create_test_job();
+    job.safety.skip_if_unchanged = true;
+    repo.create_job(&job).unwrap();
+    assert!(repo.get_job(&job.id).unwrap().safety.skip_if_unchanged);
+
+    job.safety.skip_if_unchanged = false;
+    repo.update_job(&job).unwrap();
+    assert!(!repo.get_job(&job.id).unwrap().safety.skip_if_unchanged);
+}
+
+#[test]
+fn test_job_expect_nonempty_destination_roundtrip() {
+    let repo = setup();
+    let mut job = create_test_job();
+    job.safety.expect_nonempty_destination = true;
+    repo.create_job(&job).unwrap();
+    assert!(
+        repo.get_job(&job.id)
+            .unwrap()
+            .safety
+            .expect_nonempty_destination
+    );
+
+    job.safety.expect_nonempty_destination = false;
+    repo.update_job(&job).unwrap();
+    assert!(
+        !repo
+            .get_job(&job.id)
+            .unwrap()
+            .safety
+            .expect_nonempty_destination
+    );
+}
+
+#[test]
+fn test_job_parallel_split_roundtrip() {
+    let repo = setup();
+    let mut job = create_test_job();
+    job.run_policy.parallel_split = Some(4);
+    repo.create_job(&job).unwrap();
+    assert_eq!(
+        repo.get_job(&job.id).unwrap().run_policy.parallel_split,
+        Some(4)
+    );
+
+    job.run_policy.parallel_split = None;
+    repo.update_job(&job).unwrap();
+    assert_eq!(
+        repo.get_job(&job.id).unwrap().run_policy.parallel_split,
+        None
+    );
+}
+
+#[test]
+fn test_job_verify_after_snapshot_roundtrip() {
+    let repo = setup();
+    let mut job = create_test_job();
+    job.safety.verify_after_snapshot = true;
+    repo.create_job(&job).unwrap();
+    assert!(repo.get_job(&job.id).unwrap().safety.verify_after_snapshot);
+
+    job.safety.verify_after_snapshot = false;
+    repo.update_job(&job).unwrap();
+    assert!(!repo.get_job(&job.id).unwrap().safety.verify_after_snapshot);
+}
+
+#[test]
+fn test_job_without_env_defaults_to_empty() {
+    let repo = setup();
+    let job = create_test_job();
+    repo.create_job(&job).unwrap();
+    assert!(repo.get_job(&job.id).unwrap().run_policy.env.is_empty());
+}
+
+#[test]
+fn test_merge_jobs_with_missing_job_changes_nothing() {
+    let repo = setup();
+    let keep = create_test_job();
+    let dup = create_test_job();
+    repo.create_job(&keep).unwrap();
+    repo.create_job(&dup).unwrap();
+
+    let result = repo.merge_jobs(&keep.id, &[dup.id, Uuid::new_v4()]);
+    assert!(matches!(result, Err(AppError::NotFound(_))));
+    assert_eq!(repo.list_jobs().unwrap().len(), 2);
+
+    repo.merge_jobs(&keep.id, &[dup.id]).unwrap();
+    let remaining: Vec<Uuid> = repo.list_jobs().unwrap().iter().map(|j| j.id).collect();
+    assert_eq!(remaining, vec![keep.id]);
+}
@@ -3,3 +3,4 @@ mod invocation_tests;
 mod snapshot_tests;
 mod statistics_tests;
 mod settings_tests;
+mod database_tests;
@@ -36,10 +36,15 @@ fn make_invocation(job_id: Uuid) -> BackupInvocation {
         finished_at: Some(Utc::now()),
         status: InvocationStatus::Succeeded,
         trigger: InvocationTrigger::Manual,
+        rerun_of: None,
+        resumed_from: None,
+        warning: None,
         transfer_stats: TransferStats {
             bytes_transferred: 1024,
             files_transferred: 5,
             total_files: 5,
+            files_deleted: 0,
+            detailed: None,
         },
         execution_output: ExecutionOutput {
             command_executed: "rsync -a /src/ /dst/".to_string(),
@@ -61,6 +66,7 @@ fn make_snapshot(job_id: Uuid, invocation_id: Uuid) -> SnapshotRecord {
         size_bytes: 2048,
         file_count: 5,
         is_latest: true,
+        pinned: false,
     }
 }
 
@@ -148,3 +154,24 @@ fn test_no_latest_snapshot() {
         .unwrap();
     assert!(result.is_none());
 }
+
+#[test]
+fn test_set_snapshot_pinned() {
+    let (job_repo, inv_repo, snap_repo) = setup();
+    let job = create_test_job();
+    job_repo.create_job(&job).unwrap();
+    let inv = make_invocation(job.id);
+    inv_repo.create_invocation(&inv).unwrap();
+    let snap = make_snapshot(job.id, inv.id);
+    snap_repo.create_snapshot(&snap).unwrap();
+
+    snap_repo.set_snapshot_pinned(&snap.id, true).unwrap();
+    assert!(snap_repo.list_snapshots_for_job(&job.id).unwrap()[0].pinned);
+
+    snap_repo.set_snapshot_pinned(&snap.id, false).unwrap();
+    assert!(!snap_repo.list_snapshots_for_job(&job.id).unwrap()[0].pinned);
+
+    assert!(snap_repo
+        .set_snapshot_pinned(&Uuid::new_v4(), true)
+        .is_err());
+}
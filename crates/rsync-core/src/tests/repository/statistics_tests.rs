@@ -36,10 +36,15 @@ fn make_invocation(job_id: Uuid) -> BackupInvocation {
         finished_at: Some(Utc::now()),
         status: InvocationStatus::Succeeded,
         trigger: InvocationTrigger::Manual,
+        rerun_of: None,
+        resumed_from: None,
+        warning: None,
         transfer_stats: TransferStats {
             bytes_transferred: 1024,
             files_transferred: 10,
             total_files: 100,
+            files_deleted: 0,
+            detailed: None,
         },
         execution_output: ExecutionOutput {
             command_executed: "rsync -a /src/ /dst/".to_string(),
@@ -58,6 +63,7 @@ fn make_statistic(job_id: Uuid, invocation_id: Uuid) -> RunStatistic {
         recorded_at: Utc::now(),
         files_transferred: 10,
         bytes_transferred: 1024,
+        files_deleted: 0,
         duration_secs: 5.5,
         speedup: Some(2.5),
     }
@@ -0,0 +1,245 @@
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::database::sqlite::Database;
+use crate::models::audit::{AuditAction, AuditEntry};
+use crate::models::backup::{
+    BackupInvocation, ExecutionOutput, InvocationStatus, InvocationTrigger, SnapshotRecord,
+    TransferStats,
+};
+use crate::models::job::{RetentionPolicy, StorageLocation};
+use crate::repository::sqlite::audit::SqliteAuditLogRepository;
+use crate::repository::sqlite::invocation::SqliteInvocationRepository;
+use crate::repository::sqlite::job::SqliteJobRepository;
+use crate::repository::sqlite::snapshot::SqliteSnapshotRepository;
+use crate::repository::sqlite::statistics::SqliteStatisticsRepository;
+use crate::services::audit::AuditLog;
+use crate::services::job_service::JobService;
+use crate::services::statistics_service::StatisticsService;
+use crate::tests::test_helpers::{create_snapshot_job, create_test_job};
+
+fn setup() -> (JobService, StatisticsService, Arc<AuditLog>) {
+    let db = Database::in_memory().unwrap();
+    let conn = db.conn();
+    let audit = Arc::new(
+        AuditLog::new(Arc::new(SqliteAuditLogRepository::new(conn.clone()))).with_source("tui"),
+    );
+    let job_service = JobService::new(
+        Arc::new(SqliteJobRepository::new(conn.clone())),
+        Arc::new(SqliteInvocationRepository::new(conn.clone())),
+        Arc::new(SqliteSnapshotRepository::new(conn.clone())),
+    )
+    .with_audit_log(Arc::clone(&audit));
+    let statistics_service =
+        StatisticsService::new(Arc::new(SqliteStatisticsRepository::new(conn)))
+            .with_audit_log(Arc::clone(&audit));
+    (job_service, statistics_service, audit)
+}
+
+fn only_entry(audit: &AuditLog) -> AuditEntry {
+    let entries = audit.list(10).unwrap();
+    assert_eq!(entries.len(), 1, "expected one entry, got {:?}", entries);
+    entries.into_iter().next().unwrap()
+}
+
+#[test]
+fn test_deleting_a_job_is_audited() {
+    let (job_service, _, audit) = setup();
+    let mut job = create_test_job();
+    job.name = "Photos".to_string();
+    let job = job_service.create_job(job).unwrap();
+
+    job_service.delete_job(&job.id).unwrap();
+
+    let entry = only_entry(&audit);
+    assert_eq!(entry.action, AuditAction::JobDeleted);
+    assert_eq!(entry.target_id, Some(job.id));
+    assert!(entry.detail.contains("Photos"));
+    assert_eq!(entry.source.as_deref(), Some("tui"));
+}
+
+#[test]
+fn test_failed_job_deletion_is_not_audited() {
+    let (job_service, _, audit) = setup();
+
+    assert!(job_service.delete_job(&Uuid::new_v4()).is_err());
+
+    assert!(audit.list(10).unwrap().is_empty());
+}
+
+#[test]
+fn test_resetting_statistics_is_audited() {
+    let (_, statistics_service, audit) = setup();
+
+    statistics_service.reset().unwrap();
+
+    let entry = only_entry(&audit);
+    assert_eq!(entry.action, AuditAction::StatisticsReset);
+    assert_eq!(entry.target_id, None);
+}
+
+#[test]
+fn test_resetting_one_jobs_statistics_is_audited() {
+    let (_, statistics_service, audit) = setup();
+    let job_id = Uuid::new_v4();
+
+    statistics_service.reset_for_job(&job_id).unwrap();
+
+    let entry = only_entry(&audit);
+    assert_eq!(entry.action, AuditAction::StatisticsReset);
+    assert_eq!(entry.target_id, Some(job_id));
+}
+
+fn invocation(job_id: Uuid) -> BackupInvocation {
+    BackupInvocation {
+        id: Uuid::new_v4(),
+        job_id,
+        started_at: Utc::now(),
+        finished_at: Some(Utc::now()),
+        status: InvocationStatus::Succeeded,
+        trigger: InvocationTrigger::Manual,
+        rerun_of: None,
+        resumed_from: None,
+        warning: None,
+        transfer_stats: TransferStats::default(),
+        execution_output: ExecutionOutput {
+            command_executed: "rsync -a /src/ /dst/".to_string(),
+            exit_code: Some(0),
+            snapshot_path: Some("/backups/snap1".to_string()),
+            log_file_path: None,
+        },
+    }
+}
+
+fn snapshot(job_id: Uuid, invocation_id: Uuid, path: &str, hours_ago: i64) -> SnapshotRecord {
+    SnapshotRecord {
+        id: Uuid::new_v4(),
+        job_id,
+        invocation_id,
+        snapshot_path: path.to_string(),
+        link_dest_path: None,
+        created_at: Utc::now() - Duration::hours(hours_ago),
+        size_bytes: 0,
+        file_count: 0,
+        is_latest: hours_ago == 0,
+        pinned: false,
+    }
+}
+
+#[test]
+fn test_purging_history_and_deleting_snapshots_are_audited() {
+    let (job_service, _, audit) = setup();
+    let job = job_service.create_job(create_test_job()).unwrap();
+    let invocation = invocation(job.id);
+    job_service.record_invocation(&invocation).unwrap();
+    let snapshot = snapshot(job.id, invocation.id, "/backups/snap1", 0);
+    job_service.record_snapshot(&snapshot).unwrap();
+
+    job_service.delete_snapshot(&snapshot.id).unwrap();
+    job_service.delete_invocations_for_job(&job.id).unwrap();
+
+    let entries = audit.list(10).unwrap();
+    let actions: Vec<_> = entries.iter().map(|e| (e.action, e.target_id)).collect();
+    // Most recent first
+    assert_eq!(
+        actions,
+        vec![
+            (AuditAction::HistoryPurged, Some(job.id)),
+            (AuditAction::SnapshotDeleted, Some(snapshot.id)),
+        ]
+    );
+}
+
+#[test]
+fn test_retention_prune_is_audited() {
+    let (job_service, _, audit) = setup();
+    let policy = RetentionPolicy {
+        keep_daily: 0,
+        keep_weekly: 0,
+        keep_monthly: 0,
+    };
+    let job = job_service
+        .create_job(create_snapshot_job("/src/", "/backups/", policy))
+        .unwrap();
+    let invocation = invocation(job.id);
+    job_service.record_invocation(&invocation).unwrap();
+    let older = snapshot(job.id, invocation.id, "/backups/old", 2);
+    job_service.record_snapshot(&older).unwrap();
+    job_service
+        .record_snapshot(&snapshot(job.id, invocation.id, "/backups/new", 0))
+        .unwrap();
+
+    assert_eq!(
+        job_service.prune_snapshots_by_policy(&job.id).unwrap(),
+        vec![older.clone()]
+    );
+
+    let entry = only_entry(&audit);
+    assert_eq!(entry.action, AuditAction::SnapshotDeleted);
+    assert_eq!(entry.target_id, Some(older.id));
+    assert!(entry.detail.contains("/backups/old"));
+}
+
+#[test]
+fn test_retarget_audits_forgotten_snapshots() {
+    let (job_service, _, audit) = setup();
+    let job = job_service.create_job(create_test_job()).unwrap();
+    let invocation = invocation(job.id);
+    job_service.record_invocation(&invocation).unwrap();
+    let snapshot = snapshot(job.id, invocation.id, "/dst/snap1", 0);
+    job_service.record_snapshot(&snapshot).unwrap();
+
+    job_service
+        .retarget(
+            &job.id,
+            StorageLocation::Local {
+                path: "/elsewhere/".to_string(),
+            },
+        )
+        .unwrap();
+
+    let entry = only_entry(&audit);
+    assert_eq!(entry.action, AuditAction::SnapshotDeleted);
+    assert_eq!(entry.target_id, Some(snapshot.id));
+}
+
+#[test]
+fn test_replace_all_audits_dropped_jobs() {
+    let (job_service, _, audit) = setup();
+    let kept = job_service.create_job(create_test_job()).unwrap();
+    let mut dropped = create_test_job();
+    dropped.name = "Old laptop".to_string();
+    let dropped = job_service.create_job(dropped).unwrap();
+
+    job_service.replace_all(vec![kept]).unwrap();
+
+    let entry = only_entry(&audit);
+    assert_eq!(entry.action, AuditAction::JobDeleted);
+    assert_eq!(entry.target_id, Some(dropped.id));
+    assert!(entry.detail.contains("Old laptop"));
+}
+
+#[test]
+fn test_merge_has_its_own_audit_action() {
+    let (job_service, _, audit) = setup();
+    let keep = job_service.create_job(create_test_job()).unwrap();
+    let dup = job_service.create_job(create_test_job()).unwrap();
+
+    job_service.merge_jobs(&keep.id, &[dup.id]).unwrap();
+
+    let entry = only_entry(&audit);
+    assert_eq!(entry.action, AuditAction::JobsMerged);
+    assert_eq!(entry.target_id, Some(dup.id));
+}
+
+#[test]
+fn test_list_respects_limit() {
+    let (_, statistics_service, audit) = setup();
+    for _ in 0..3 {
+        statistics_service.reset().unwrap();
+    }
+
+    assert_eq!(audit.list(2).unwrap().len(), 2);
+}
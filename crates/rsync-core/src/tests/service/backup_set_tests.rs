@@ -0,0 +1,229 @@
+use std::collections::HashMap;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::database::sqlite::Database;
+use crate::error::AppError;
+use crate::models::backup_set::{BackupSet, SetJobOutcome};
+use crate::models::job::{JobDefinition, JobStatus};
+use crate::models::progress::JobStatusEvent;
+use crate::repository::sqlite::backup_set::SqliteBackupSetRepository;
+use crate::repository::sqlite::invocation::SqliteInvocationRepository;
+use crate::repository::sqlite::job::SqliteJobRepository;
+use crate::repository::sqlite::snapshot::SqliteSnapshotRepository;
+use crate::services::backup_set_runner::{run_backup_set, SetJobRunner};
+use crate::services::backup_set_service::BackupSetService;
+use crate::services::job_service::JobService;
+use crate::tests::mock_executor::{mock_rsync_executor, StatusHandler};
+use crate::tests::test_helpers::create_test_job;
+
+/// Finishes each job with a preset status instead of running rsync.
+#[derive(Default)]
+struct FakeRunner {
+    statuses: HashMap<Uuid, JobStatus>,
+    ran: Mutex<Vec<Uuid>>,
+}
+
+impl SetJobRunner for FakeRunner {
+    fn run_to_completion(&self, job: &JobDefinition) -> Result<JobStatusEvent, String> {
+        self.ran.lock().unwrap().push(job.id);
+        let status = self
+            .statuses
+            .get(&job.id)
+            .cloned()
+            .unwrap_or(JobStatus::Completed);
+        Ok(JobStatusEvent {
+            job_id: job.id,
+            invocation_id: Uuid::new_v4(),
+            error_message: (status == JobStatus::Failed).then(|| "Exit code 23".to_string()),
+            exit_code: Some(if status == JobStatus::Failed { 23 } else { 0 }),
+            status,
+        })
+    }
+}
+
+fn setup() -> (JobService, BackupSetService) {
+    let db = Database::in_memory().unwrap();
+    let conn = db.conn();
+    (
+        JobService::new(
+            Arc::new(SqliteJobRepository::new(conn.clone())),
+            Arc::new(SqliteInvocationRepository::new(conn.clone())),
+            Arc::new(SqliteSnapshotRepository::new(conn.clone())),
+        ),
+        BackupSetService::new(Arc::new(SqliteBackupSetRepository::new(conn))),
+    )
+}
+
+fn two_job_set(
+    jobs: &JobService,
+    sets: &BackupSetService,
+) -> (JobDefinition, JobDefinition, BackupSet) {
+    let first = jobs.create_job(create_test_job()).unwrap();
+    let second = jobs.create_job(create_test_job()).unwrap();
+    let set = sets
+        .create_set("Offsite", vec![first.id, second.id])
+        .unwrap();
+    (first, second, set)
+}
+
+#[test]
+fn test_stop_on_failure_skips_jobs_after_a_failure() {
+    let (jobs, sets) = setup();
+    let (first, second, set) = two_job_set(&jobs, &sets);
+    let runner = FakeRunner {
+        statuses: HashMap::from([(first.id, JobStatus::Failed)]),
+        ..Default::default()
+    };
+
+    let run = run_backup_set(&set, &jobs, &runner, true);
+
+    assert_eq!(*runner.ran.lock().unwrap(), vec![first.id]);
+    assert_eq!(run.set_id, set.id);
+    assert!(matches!(
+        &run.results[0].outcome,
+        SetJobOutcome::Finished {
+            status: JobStatus::Failed,
+            ..
+        }
+    ));
+    assert_eq!(run.results[1].job_id, second.id);
+    assert_eq!(run.results[1].outcome, SetJobOutcome::Skipped);
+    assert!(!run.succeeded());
+}
+
+#[test]
+fn test_all_jobs_succeed_in_order() {
+    let (jobs, sets) = setup();
+    let (first, second, set) = two_job_set(&jobs, &sets);
+    let runner = FakeRunner::default();
+
+    let run = run_backup_set(&set, &jobs, &runner, true);
+
+    assert_eq!(*runner.ran.lock().unwrap(), vec![first.id, second.id]);
+    assert_eq!(run.results.len(), 2);
+    assert!(run.results.iter().all(|r| r.outcome.is_success()));
+    assert_eq!(run.results[0].job_name, first.name);
+    assert!(run.succeeded());
+}
+
+#[test]
+fn test_without_stop_on_failure_every_job_runs() {
+    let (jobs, sets) = setup();
+    let (first, second, set) = two_job_set(&jobs, &sets);
+    let runner = FakeRunner {
+        statuses: HashMap::from([(first.id, JobStatus::Failed)]),
+        ..Default::default()
+    };
+
+    let run = run_backup_set(&set, &jobs, &runner, false);
+
+    assert_eq!(*runner.ran.lock().unwrap(), vec![first.id, second.id]);
+    assert!(run.results[1].outcome.is_success());
+}
+
+#[test]
+fn test_cancelled_job_stops_the_set_without_stop_on_failure() {
+    let (jobs, sets) = setup();
+    let (first, second, set) = two_job_set(&jobs, &sets);
+    let runner = FakeRunner {
+        statuses: HashMap::from([(first.id, JobStatus::Cancelled)]),
+        ..Default::default()
+    };
+
+    let run = run_backup_set(&set, &jobs, &runner, false);
+
+    assert_eq!(*runner.ran.lock().unwrap(), vec![first.id]);
+    assert!(run.results[0].outcome.is_cancelled());
+    assert_eq!(run.results[1].job_id, second.id);
+    assert_eq!(run.results[1].outcome, SetJobOutcome::Skipped);
+    assert!(!run.succeeded());
+}
+
+#[test]
+fn test_cancel_all_stops_a_running_set() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (executor, svc, _db) = mock_rsync_executor(tmp.path(), "exec sleep 60");
+    let first = svc.create_job(create_test_job()).unwrap();
+    let second = svc.create_job(create_test_job()).unwrap();
+    let set = BackupSet {
+        id: Uuid::new_v4(),
+        name: "Offsite".to_string(),
+        job_ids: vec![first.id, second.id],
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    };
+    let (tx, _rx) = mpsc::channel();
+    let handler = Arc::new(StatusHandler(Mutex::new(tx)));
+
+    let run = thread::scope(|scope| {
+        let running = scope.spawn(|| executor.run_set(&set, false, handler));
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while !executor.is_running(&first.id) && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(executor.cancel_all(), 1);
+        running.join().unwrap()
+    });
+
+    assert!(run.results[0].outcome.is_cancelled(), "{:?}", run.results[0]);
+    assert_eq!(run.results[1].outcome, SetJobOutcome::Skipped);
+    assert!(!executor.is_running(&second.id));
+}
+
+#[test]
+fn test_missing_job_is_not_started_and_stops_the_set() {
+    let (jobs, sets) = setup();
+    let (first, second, set) = two_job_set(&jobs, &sets);
+    jobs.delete_job(&first.id).unwrap();
+    let runner = FakeRunner::default();
+
+    let run = run_backup_set(&set, &jobs, &runner, true);
+
+    assert!(runner.ran.lock().unwrap().is_empty());
+    assert!(matches!(
+        run.results[0].outcome,
+        SetJobOutcome::NotStarted { .. }
+    ));
+    assert_eq!(run.results[1].job_id, second.id);
+    assert_eq!(run.results[1].outcome, SetJobOutcome::Skipped);
+}
+
+#[test]
+fn test_create_set_validates_name_and_duplicates() {
+    let (_jobs, sets) = setup();
+    let id = Uuid::new_v4();
+
+    assert!(matches!(
+        sets.create_set("  ", vec![id]),
+        Err(AppError::ValidationError(_))
+    ));
+    assert!(matches!(
+        sets.create_set("Twice", vec![id, id]),
+        Err(AppError::ValidationError(_))
+    ));
+}
+
+#[test]
+fn test_set_crud_round_trip() {
+    let (jobs, sets) = setup();
+    let (first, second, set) = two_job_set(&jobs, &sets);
+
+    assert_eq!(sets.get_set(&set.id).unwrap(), set);
+
+    let mut reordered = set.clone();
+    reordered.job_ids = vec![second.id, first.id];
+    sets.update_set(reordered).unwrap();
+    assert_eq!(
+        sets.get_set(&set.id).unwrap().job_ids,
+        vec![second.id, first.id]
+    );
+    assert_eq!(sets.list_sets().unwrap().len(), 1);
+
+    sets.delete_set(&set.id).unwrap();
+    assert!(matches!(sets.get_set(&set.id), Err(AppError::NotFound(_))));
+}
@@ -0,0 +1,137 @@
+use chrono::NaiveTime;
+
+use crate::models::rsync_options::{AdvancedOptions, BandwidthWindow, RsyncOptions};
+use crate::services::bandwidth_schedule::{
+    apply_bandwidth_schedule, bandwidth_limit_at, next_bandwidth_change,
+};
+
+fn at(h: u32, m: u32) -> NaiveTime {
+    NaiveTime::from_hms_opt(h, m, 0).unwrap()
+}
+
+fn window(start: (u16, u16), end: (u16, u16), limit: Option<u64>) -> BandwidthWindow {
+    BandwidthWindow {
+        start_minute: start.0 * 60 + start.1,
+        end_minute: end.0 * 60 + end.1,
+        limit,
+    }
+}
+
+/// 500 KB/s during office hours; 5000 KB/s (the plain limit) otherwise.
+fn office_hours() -> AdvancedOptions {
+    AdvancedOptions {
+        bandwidth_limit: Some(5000),
+        bandwidth_schedule: vec![window((8, 0), (18, 0), Some(500))],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_limit_follows_window_and_falls_back_outside() {
+    let advanced = office_hours();
+
+    assert_eq!(bandwidth_limit_at(&advanced, at(8, 0)), Some(500));
+    assert_eq!(bandwidth_limit_at(&advanced, at(17, 59)), Some(500));
+    // The end is exclusive
+    assert_eq!(bandwidth_limit_at(&advanced, at(18, 0)), Some(5000));
+    assert_eq!(bandwidth_limit_at(&advanced, at(3, 0)), Some(5000));
+}
+
+#[test]
+fn test_window_wrapping_midnight() {
+    let advanced = AdvancedOptions {
+        bandwidth_schedule: vec![
+            window((22, 0), (6, 0), None),
+            window((6, 0), (22, 0), Some(100)),
+        ],
+        ..Default::default()
+    };
+
+    assert_eq!(bandwidth_limit_at(&advanced, at(23, 30)), None);
+    assert_eq!(bandwidth_limit_at(&advanced, at(2, 0)), None);
+    assert_eq!(bandwidth_limit_at(&advanced, at(12, 0)), Some(100));
+}
+
+#[test]
+fn test_first_matching_window_wins() {
+    let advanced = AdvancedOptions {
+        bandwidth_schedule: vec![
+            window((9, 0), (10, 0), Some(50)),
+            window((8, 0), (18, 0), Some(500)),
+        ],
+        ..Default::default()
+    };
+
+    assert_eq!(bandwidth_limit_at(&advanced, at(9, 30)), Some(50));
+    assert_eq!(bandwidth_limit_at(&advanced, at(10, 30)), Some(500));
+}
+
+#[test]
+fn test_next_change_for_two_bands() {
+    let advanced = office_hours();
+
+    assert_eq!(
+        next_bandwidth_change(&advanced, at(9, 30)),
+        Some((at(18, 0), Some(5000)))
+    );
+    assert_eq!(
+        next_bandwidth_change(&advanced, at(20, 0)),
+        Some((at(8, 0), Some(500)))
+    );
+    // A boundary at the current minute has already passed
+    assert_eq!(
+        next_bandwidth_change(&advanced, at(8, 0)),
+        Some((at(18, 0), Some(5000)))
+    );
+}
+
+#[test]
+fn test_next_change_skips_boundaries_that_keep_the_limit() {
+    let advanced = AdvancedOptions {
+        bandwidth_limit: Some(500),
+        bandwidth_schedule: vec![
+            window((8, 0), (12, 0), Some(500)),
+            window((12, 0), (13, 0), None),
+        ],
+        ..Default::default()
+    };
+
+    assert_eq!(
+        next_bandwidth_change(&advanced, at(9, 0)),
+        Some((at(12, 0), None))
+    );
+    assert_eq!(
+        next_bandwidth_change(&advanced, at(12, 30)),
+        Some((at(13, 0), Some(500)))
+    );
+}
+
+#[test]
+fn test_no_change_without_effective_schedule() {
+    assert_eq!(
+        next_bandwidth_change(&AdvancedOptions::default(), at(9, 0)),
+        None
+    );
+
+    let same = AdvancedOptions {
+        bandwidth_limit: Some(500),
+        bandwidth_schedule: vec![window((8, 0), (18, 0), Some(500))],
+        ..Default::default()
+    };
+    assert_eq!(next_bandwidth_change(&same, at(9, 0)), None);
+}
+
+#[test]
+fn test_apply_sets_limit_only_with_a_schedule() {
+    let mut options = RsyncOptions {
+        advanced: office_hours(),
+        ..Default::default()
+    };
+    apply_bandwidth_schedule(&mut options, at(9, 0));
+    assert_eq!(options.advanced.bandwidth_limit, Some(500));
+
+    let mut plain = RsyncOptions::default();
+    plain.advanced.bandwidth_limit = Some(700);
+    apply_bandwidth_schedule(&mut plain, at(9, 0));
+    assert_eq!(plain.advanced.bandwidth_limit, Some(700));
+}
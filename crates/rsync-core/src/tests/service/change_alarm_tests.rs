@@ -0,0 +1,129 @@
+use crate::models::itemize::ItemizeSummary;
+use crate::models::job::{ChangeAlarm, ChangeProportions};
+use crate::services::change_alarm::{change_proportions, check_change_alarm, summary_from_stats};
+
+fn summary(created: u64, updated: u64, deleted: u64) -> ItemizeSummary {
+    ItemizeSummary {
+        total: created + updated + deleted,
+        created,
+        updated,
+        deleted,
+        stored: created + updated + deleted,
+        truncated: 0,
+    }
+}
+
+fn alarm(max_changed: Option<f64>, max_deleted: Option<f64>) -> ChangeAlarm {
+    ChangeAlarm {
+        max_changed_percent: max_changed,
+        max_deleted_percent: max_deleted,
+        notify: false,
+    }
+}
+
+#[test]
+fn test_proportions_from_change_summary() {
+    // 200 files considered: 20 new, 30 updated; 50 deleted from the 230
+    // entries the destination held (180 kept + 50 deleted)
+    let proportions = change_proportions(&summary(20, 30, 50), 200).unwrap();
+
+    assert_eq!(proportions.changed_percent, 25.0);
+    assert!((proportions.deleted_percent - 50.0 * 100.0 / 230.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_proportions_without_files_is_none() {
+    assert_eq!(change_proportions(&summary(0, 0, 0), 0), None);
+}
+
+#[test]
+fn test_proportions_when_everything_was_deleted() {
+    // Empty source mirrored onto a full destination
+    let proportions = change_proportions(&summary(0, 0, 40), 0).unwrap();
+
+    assert_eq!(proportions.changed_percent, 0.0);
+    assert_eq!(proportions.deleted_percent, 100.0);
+}
+
+#[test]
+fn test_summary_from_stats_counts_transfers_as_updates() {
+    let summary = summary_from_stats(95, 3);
+
+    assert_eq!(summary.updated, 95);
+    assert_eq!(summary.created, 0);
+    assert_eq!(summary.deleted, 3);
+    assert_eq!(
+        change_proportions(&summary, 100).unwrap().changed_percent,
+        95.0
+    );
+}
+
+#[test]
+fn test_alarm_triggers_when_changes_exceed_threshold() {
+    let proportions = change_proportions(&summary(0, 95, 0), 100).unwrap();
+
+    let warning = check_change_alarm(&alarm(Some(90.0), None), &proportions).unwrap();
+
+    assert!(warning.contains("95.0% of files changed"), "{warning}");
+}
+
+#[test]
+fn test_alarm_triggers_when_deletions_exceed_threshold() {
+    let proportions = ChangeProportions {
+        changed_percent: 1.0,
+        deleted_percent: 30.0,
+    };
+
+    let warning = check_change_alarm(&alarm(Some(90.0), Some(20.0)), &proportions).unwrap();
+
+    assert!(
+        warning.contains("30.0% of destination files deleted"),
+        "{warning}"
+    );
+    assert!(!warning.contains("changed"), "{warning}");
+}
+
+#[test]
+fn test_alarm_reports_both_thresholds() {
+    let proportions = ChangeProportions {
+        changed_percent: 99.0,
+        deleted_percent: 60.0,
+    };
+
+    let warning = check_change_alarm(&alarm(Some(90.0), Some(20.0)), &proportions).unwrap();
+
+    assert!(
+        warning.contains("changed") && warning.contains("deleted"),
+        "{warning}"
+    );
+}
+
+#[test]
+fn test_alarm_does_not_trigger_within_thresholds() {
+    let proportions = change_proportions(&summary(5, 10, 2), 100).unwrap();
+
+    assert_eq!(
+        check_change_alarm(&alarm(Some(90.0), Some(20.0)), &proportions),
+        None
+    );
+}
+
+#[test]
+fn test_alarm_at_threshold_does_not_trigger() {
+    let proportions = change_proportions(&summary(0, 90, 0), 100).unwrap();
+
+    assert_eq!(
+        check_change_alarm(&alarm(Some(90.0), None), &proportions),
+        None
+    );
+}
+
+#[test]
+fn test_unset_thresholds_never_trigger() {
+    let proportions = ChangeProportions {
+        changed_percent: 100.0,
+        deleted_percent: 100.0,
+    };
+
+    assert_eq!(check_change_alarm(&alarm(None, None), &proportions), None);
+}
@@ -0,0 +1,129 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::database::sqlite::Database;
+use crate::models::backup::InvocationTrigger;
+use crate::models::itemize::ItemizedChange;
+use crate::models::job::StorageLocation;
+use crate::models::progress::{JobStatusEvent, LogLine, ProgressUpdate};
+use crate::repository::sqlite::invocation::SqliteInvocationRepository;
+use crate::repository::sqlite::job::SqliteJobRepository;
+use crate::repository::sqlite::settings::SqliteSettingsRepository;
+use crate::repository::sqlite::snapshot::SqliteSnapshotRepository;
+use crate::repository::sqlite::statistics::SqliteStatisticsRepository;
+use crate::services::daemon_auth::{daemon_secret_name, PasswordFile};
+use crate::services::execution_handler::ExecutionEventHandler;
+use crate::services::job_executor::JobExecutor;
+use crate::services::job_service::JobService;
+use crate::services::running_jobs::RunningJobs;
+use crate::services::settings_service::SettingsService;
+use crate::services::statistics_service::StatisticsService;
+use crate::tests::test_helpers::create_mirror_job;
+
+fn daemon_location(password_secret: Option<&str>) -> StorageLocation {
+    StorageLocation::RemoteRsync {
+        host: "nas.local".to_string(),
+        module: "backups".to_string(),
+        path: "laptop/".to_string(),
+        username: Some("backup".to_string()),
+        password_secret: password_secret.map(String::from),
+    }
+}
+
+struct NoopHandler;
+
+impl ExecutionEventHandler for NoopHandler {
+    fn on_log_line(&self, _line: LogLine) {}
+    fn on_progress(&self, _progress: &ProgressUpdate) {}
+    fn on_status_change(&self, _status: JobStatusEvent) {}
+    fn on_itemized_change(&self, _invocation_id: Uuid, _change: &ItemizedChange) {}
+}
+
+#[test]
+fn test_daemon_secret_name_from_destination() {
+    let mut job = create_mirror_job("/src/", "/dst/");
+    assert_eq!(daemon_secret_name(&job), None);
+
+    job.transfer.destination = daemon_location(Some("nas"));
+    assert_eq!(daemon_secret_name(&job), Some("nas"));
+}
+
+#[test]
+fn test_daemon_secret_name_from_source() {
+    let mut job = create_mirror_job("/src/", "/dst/");
+    job.transfer.source = daemon_location(Some("mirror"));
+    assert_eq!(daemon_secret_name(&job), Some("mirror"));
+}
+
+#[test]
+fn test_daemon_secret_name_none_without_secret() {
+    let mut job = create_mirror_job("/src/", "/dst/");
+    job.transfer.destination = daemon_location(None);
+    assert_eq!(daemon_secret_name(&job), None);
+}
+
+#[test]
+fn test_password_file_written_and_removed_on_drop() {
+    let tmp = tempfile::tempdir().unwrap();
+    let file = PasswordFile::create(tmp.path(), Uuid::new_v4(), "hunter2").unwrap();
+    let path = file.path().to_path_buf();
+
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "hunter2\n");
+    assert_eq!(file.arg(), format!("--password-file={}", path.display()));
+
+    drop(file);
+    assert!(!path.exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_password_file_is_private() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let file = PasswordFile::create(tmp.path(), Uuid::new_v4(), "hunter2").unwrap();
+    let mode = std::fs::metadata(file.path()).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+}
+
+#[test]
+fn test_password_file_does_not_overwrite_existing() {
+    let tmp = tempfile::tempdir().unwrap();
+    let id = Uuid::new_v4();
+    let _first = PasswordFile::create(tmp.path(), id, "one").unwrap();
+    assert!(PasswordFile::create(tmp.path(), id, "two").is_err());
+}
+
+#[test]
+fn test_executor_refuses_run_when_daemon_secret_missing() {
+    let tmp = tempfile::tempdir().unwrap();
+    let db = Database::in_memory().unwrap();
+    let conn = db.conn();
+    let job_service = Arc::new(JobService::new(
+        Arc::new(SqliteJobRepository::new(conn.clone())),
+        Arc::new(SqliteInvocationRepository::new(conn.clone())),
+        Arc::new(SqliteSnapshotRepository::new(conn.clone())),
+    ));
+    let executor = JobExecutor::new(
+        Arc::clone(&job_service),
+        Arc::new(StatisticsService::new(Arc::new(
+            SqliteStatisticsRepository::new(conn.clone()),
+        ))),
+        Arc::new(SettingsService::new(Arc::new(
+            SqliteSettingsRepository::new(conn),
+        ))),
+        Arc::new(RunningJobs::new()),
+        tmp.path().to_string_lossy().to_string(),
+    );
+    let mut job = create_mirror_job("/src/", "/dst/");
+    job.transfer.destination = daemon_location(Some("nas"));
+    let job = job_service.create_job(job).unwrap();
+
+    let err = executor
+        .execute(&job, InvocationTrigger::Manual, Arc::new(NoopHandler))
+        .unwrap_err();
+
+    assert!(err.contains("rsync daemon password 'nas' is not set"));
+    assert!(job_service.get_job_history(&job.id, 10).unwrap().is_empty());
+}
@@ -0,0 +1,202 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+use crate::database::sqlite::Database;
+use crate::models::backup::{
+    BackupInvocation, ExecutionOutput, InvocationStatus, InvocationTrigger, TransferStats,
+};
+use crate::models::job::JobDefinition;
+use crate::models::progress::RunningJobInfo;
+use crate::models::schedule::{ScheduleConfig, ScheduleType};
+use crate::models::statistics::RunStatistic;
+use crate::repository::sqlite::invocation::SqliteInvocationRepository;
+use crate::repository::sqlite::job::SqliteJobRepository;
+use crate::repository::sqlite::snapshot::SqliteSnapshotRepository;
+use crate::repository::sqlite::statistics::SqliteStatisticsRepository;
+use crate::services::dashboard::dashboard_summary;
+use crate::services::job_service::JobService;
+use crate::services::statistics_service::StatisticsService;
+use crate::tests::test_helpers::create_test_job;
+
+fn setup() -> (JobService, StatisticsService) {
+    let db = Database::in_memory().unwrap();
+    let conn = db.conn();
+    let job_service = JobService::new(
+        Arc::new(SqliteJobRepository::new(conn.clone())),
+        Arc::new(SqliteInvocationRepository::new(conn.clone())),
+        Arc::new(SqliteSnapshotRepository::new(conn.clone())),
+    );
+    let statistics = StatisticsService::new(Arc::new(SqliteStatisticsRepository::new(conn)));
+    (job_service, statistics)
+}
+
+fn daily_job(
+    svc: &JobService,
+    name: &str,
+    enabled: bool,
+    created_at: DateTime<Utc>,
+) -> JobDefinition {
+    let mut job = create_test_job();
+    job.name = name.to_string();
+    job.enabled = enabled;
+    job.schedule = Some(ScheduleConfig {
+        schedule_type: ScheduleType::Interval { minutes: 24 * 60 },
+        enabled: true,
+    });
+    job.created_at = created_at;
+    job.updated_at = created_at;
+    svc.upsert_job(&job).unwrap();
+    job
+}
+
+fn run(
+    svc: &JobService,
+    job_id: Uuid,
+    status: InvocationStatus,
+    started_at: DateTime<Utc>,
+) -> Uuid {
+    let id = Uuid::new_v4();
+    svc.record_invocation(&BackupInvocation {
+        id,
+        job_id,
+        started_at,
+        finished_at: Some(started_at + Duration::minutes(5)),
+        status,
+        trigger: InvocationTrigger::Scheduled,
+        rerun_of: None,
+        resumed_from: None,
+        warning: None,
+        transfer_stats: TransferStats::default(),
+        execution_output: ExecutionOutput {
+            command_executed: "rsync -a /src/ /dst/".to_string(),
+            exit_code: Some(0),
+            snapshot_path: None,
+            log_file_path: None,
+        },
+    })
+    .unwrap();
+    id
+}
+
+fn statistic(
+    job_id: Uuid,
+    invocation_id: Uuid,
+    bytes: u64,
+    recorded_at: DateTime<Utc>,
+) -> RunStatistic {
+    RunStatistic {
+        id: Uuid::new_v4(),
+        job_id,
+        invocation_id,
+        recorded_at,
+        files_transferred: 1,
+        bytes_transferred: bytes,
+        files_deleted: 0,
+        duration_secs: 1.0,
+        speedup: None,
+    }
+}
+
+#[test]
+fn test_dashboard_summary_aggregates_system_state() {
+    let (svc, statistics) = setup();
+    let now = Utc::now();
+    let fresh = daily_job(&svc, "Fresh", true, now - Duration::days(30));
+    let late = daily_job(&svc, "Late", true, now - Duration::days(30));
+    let paused = daily_job(&svc, "Paused", false, now - Duration::days(30));
+
+    let recent = run(
+        &svc,
+        fresh.id,
+        InvocationStatus::Succeeded,
+        now - Duration::hours(2),
+    );
+    run(
+        &svc,
+        fresh.id,
+        InvocationStatus::Succeeded,
+        now - Duration::hours(20),
+    );
+    let last_week = run(
+        &svc,
+        late.id,
+        InvocationStatus::Failed,
+        now - Duration::days(3),
+    );
+    let long_ago = run(
+        &svc,
+        late.id,
+        InvocationStatus::Succeeded,
+        now - Duration::days(9),
+    );
+    run(
+        &svc,
+        paused.id,
+        InvocationStatus::StoppedByLimit,
+        now - Duration::hours(6),
+    );
+    run(
+        &svc,
+        paused.id,
+        InvocationStatus::Cancelled,
+        now - Duration::hours(1),
+    );
+    // Outside the 24-hour window
+    run(
+        &svc,
+        fresh.id,
+        InvocationStatus::Failed,
+        now - Duration::hours(30),
+    );
+
+    let stats = [
+        statistic(fresh.id, recent, 1_000, now - Duration::days(1)),
+        statistic(late.id, last_week, 500, now - Duration::days(6)),
+        // Outside the 7-day window
+        statistic(late.id, long_ago, 9_999, now - Duration::days(8)),
+    ];
+    for stat in &stats {
+        statistics.upsert(stat).unwrap();
+    }
+
+    let running = vec![RunningJobInfo {
+        id: fresh.id,
+        name: fresh.name.clone(),
+        percentage: 42.0,
+        current_file: Some("docs/a.txt".to_string()),
+        bytes_per_sec: 1024.0,
+    }];
+
+    let summary = dashboard_summary(&svc, &statistics, running.clone(), now).unwrap();
+
+    assert_eq!(summary.total_jobs, 3);
+    assert_eq!(summary.enabled_jobs, 2);
+    assert_eq!(summary.running, running);
+    assert_eq!(summary.overdue.len(), 1);
+    assert_eq!(summary.overdue[0].job_id, late.id);
+    assert_eq!(summary.overdue[0].name, "Late");
+    assert_eq!(
+        summary.overdue[0].overdue_secs,
+        Duration::days(2).num_seconds()
+    );
+    assert_eq!(summary.succeeded_last_24h, 2);
+    assert_eq!(summary.failed_last_24h, 1);
+    assert_eq!(summary.bytes_last_7d, 1_500);
+}
+
+#[test]
+fn test_dashboard_summary_of_empty_system() {
+    let (svc, statistics) = setup();
+
+    let summary = dashboard_summary(&svc, &statistics, Vec::new(), Utc::now()).unwrap();
+
+    assert_eq!(summary.total_jobs, 0);
+    assert_eq!(summary.enabled_jobs, 0);
+    assert!(summary.running.is_empty());
+    assert!(summary.overdue.is_empty());
+    assert_eq!(summary.succeeded_last_24h, 0);
+    assert_eq!(summary.failed_last_24h, 0);
+    assert_eq!(summary.bytes_last_7d, 0);
+}
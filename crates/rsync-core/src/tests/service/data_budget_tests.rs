@@ -0,0 +1,261 @@
+use std::sync::Arc;
+
+use chrono::{TimeZone, Utc};
+use uuid::Uuid;
+
+use crate::database::sqlite::Database;
+use crate::models::backup::{
+    BackupInvocation, ExecutionOutput, InvocationStatus, InvocationTrigger, TransferStats,
+};
+use crate::repository::sqlite::invocation::SqliteInvocationRepository;
+use crate::repository::sqlite::job::SqliteJobRepository;
+use crate::repository::sqlite::settings::SqliteSettingsRepository;
+use crate::repository::sqlite::snapshot::SqliteSnapshotRepository;
+use crate::services::data_budget::{
+    bytes_transferred_in_month, month_start, remaining_bytes, DataBudget,
+};
+use crate::services::job_service::JobService;
+use crate::services::run_conditions::{check_run_conditions, manual_run_warnings, RunCondition};
+use crate::services::settings_service::SettingsService;
+use crate::tests::test_helpers::create_mirror_job;
+
+fn make_invocation(
+    job_id: Uuid,
+    started_at: chrono::DateTime<Utc>,
+    bytes: u64,
+) -> BackupInvocation {
+    BackupInvocation {
+        id: Uuid::new_v4(),
+        job_id,
+        started_at,
+        finished_at: Some(started_at),
+        status: InvocationStatus::Succeeded,
+        trigger: InvocationTrigger::Scheduled,
+        rerun_of: None,
+        resumed_from: None,
+        warning: None,
+        transfer_stats: TransferStats {
+            bytes_transferred: bytes,
+            files_transferred: 1,
+            total_files: 1,
+            files_deleted: 0,
+            detailed: None,
+        },
+        execution_output: ExecutionOutput {
+            command_executed: "rsync -a /src /dst".to_string(),
+            exit_code: Some(0),
+            snapshot_path: None,
+            log_file_path: None,
+        },
+    }
+}
+
+fn setup() -> (Arc<JobService>, Arc<SettingsService>, DataBudget, Database) {
+    let db = Database::in_memory().unwrap();
+    let conn = db.conn();
+    let job_service = Arc::new(JobService::new(
+        Arc::new(SqliteJobRepository::new(conn.clone())),
+        Arc::new(SqliteInvocationRepository::new(conn.clone())),
+        Arc::new(SqliteSnapshotRepository::new(conn.clone())),
+    ));
+    let settings_service = Arc::new(SettingsService::new(Arc::new(
+        SqliteSettingsRepository::new(conn),
+    )));
+    let budget = DataBudget::new(Arc::clone(&job_service), Arc::clone(&settings_service));
+    (job_service, settings_service, budget, db)
+}
+
+#[test]
+fn test_bytes_in_month_excludes_other_months() {
+    let job_id = Uuid::new_v4();
+    let invocations = vec![
+        // Last second of the previous month
+        make_invocation(
+            job_id,
+            Utc.with_ymd_and_hms(2025, 5, 31, 23, 59, 59).unwrap(),
+            500,
+        ),
+        // First second of the current month
+        make_invocation(
+            job_id,
+            Utc.with_ymd_and_hms(2025, 6, 1, 0, 0, 0).unwrap(),
+            100,
+        ),
+        make_invocation(
+            job_id,
+            Utc.with_ymd_and_hms(2025, 6, 30, 23, 59, 59).unwrap(),
+            200,
+        ),
+        // First second of the next month
+        make_invocation(
+            job_id,
+            Utc.with_ymd_and_hms(2025, 7, 1, 0, 0, 0).unwrap(),
+            800,
+        ),
+    ];
+    let now = Utc.with_ymd_and_hms(2025, 6, 15, 12, 0, 0).unwrap();
+
+    assert_eq!(bytes_transferred_in_month(&invocations, now), 300);
+}
+
+#[test]
+fn test_bytes_in_month_ignores_same_month_of_other_year() {
+    let job_id = Uuid::new_v4();
+    let invocations = vec![
+        make_invocation(
+            job_id,
+            Utc.with_ymd_and_hms(2024, 6, 10, 0, 0, 0).unwrap(),
+            1_000,
+        ),
+        make_invocation(
+            job_id,
+            Utc.with_ymd_and_hms(2025, 6, 10, 0, 0, 0).unwrap(),
+            42,
+        ),
+    ];
+    let now = Utc.with_ymd_and_hms(2025, 6, 20, 0, 0, 0).unwrap();
+
+    assert_eq!(bytes_transferred_in_month(&invocations, now), 42);
+}
+
+#[test]
+fn test_bytes_in_month_across_year_boundary() {
+    let job_id = Uuid::new_v4();
+    let invocations = vec![
+        make_invocation(
+            job_id,
+            Utc.with_ymd_and_hms(2024, 12, 31, 23, 0, 0).unwrap(),
+            700,
+        ),
+        make_invocation(
+            job_id,
+            Utc.with_ymd_and_hms(2025, 1, 1, 1, 0, 0).unwrap(),
+            30,
+        ),
+    ];
+    let now = Utc.with_ymd_and_hms(2025, 1, 2, 0, 0, 0).unwrap();
+
+    assert_eq!(bytes_transferred_in_month(&invocations, now), 30);
+}
+
+#[test]
+fn test_month_start_is_first_midnight_of_the_month() {
+    let now = Utc.with_ymd_and_hms(2025, 3, 17, 13, 45, 0).unwrap();
+    assert_eq!(
+        month_start(now),
+        Utc.with_ymd_and_hms(2025, 3, 1, 0, 0, 0).unwrap()
+    );
+}
+
+#[test]
+fn test_remaining_bytes_saturates_at_zero() {
+    assert_eq!(remaining_bytes(1_000, 400), 600);
+    assert_eq!(remaining_bytes(1_000, 1_000), 0);
+    assert_eq!(remaining_bytes(1_000, 5_000), 0);
+}
+
+#[test]
+fn test_remaining_budget_none_without_cap() {
+    let (_jobs, _settings, budget, _db) = setup();
+    assert_eq!(budget.remaining_budget().unwrap(), None);
+}
+
+#[test]
+fn test_remaining_budget_subtracts_current_month_usage() {
+    let (job_service, settings_service, budget, _db) = setup();
+    let job = job_service
+        .create_job(create_mirror_job("/src/", "/dst/"))
+        .unwrap();
+    job_service
+        .record_invocation(&make_invocation(job.id, Utc::now(), 300))
+        .unwrap();
+    settings_service
+        .set_monthly_data_budget(Some(1_000))
+        .unwrap();
+
+    // Last month's runs are not counted
+    job_service
+        .record_invocation(&make_invocation(
+            job.id,
+            month_start(Utc::now()) - chrono::Duration::seconds(1),
+            5_000,
+        ))
+        .unwrap();
+
+    assert_eq!(budget.used_this_month().unwrap(), 300);
+    assert_eq!(budget.remaining_budget().unwrap(), Some(700));
+}
+
+#[test]
+fn test_scheduled_run_refused_when_budget_exhausted() {
+    let (job_service, settings_service, budget, _db) = setup();
+    let job = job_service
+        .create_job(create_mirror_job("/src/", "/dst/"))
+        .unwrap();
+    job_service
+        .record_invocation(&make_invocation(job.id, Utc::now(), 1_500))
+        .unwrap();
+    settings_service
+        .set_monthly_data_budget(Some(1_000))
+        .unwrap();
+
+    let conditions: Vec<Arc<dyn RunCondition>> = vec![Arc::new(budget)];
+    let reason = check_run_conditions(&job, &InvocationTrigger::Scheduled, &conditions);
+
+    assert!(reason.unwrap().contains("exhausted"));
+}
+
+#[test]
+fn test_scheduled_run_refused_when_last_transfer_exceeds_remaining() {
+    let (job_service, settings_service, budget, _db) = setup();
+    let job = job_service
+        .create_job(create_mirror_job("/src/", "/dst/"))
+        .unwrap();
+    job_service
+        .record_invocation(&make_invocation(job.id, Utc::now(), 600))
+        .unwrap();
+    settings_service
+        .set_monthly_data_budget(Some(1_000))
+        .unwrap();
+
+    let reason = budget.skip_reason(&job);
+
+    assert!(reason.unwrap().contains("would be exceeded"));
+}
+
+#[test]
+fn test_scheduled_run_allowed_within_budget() {
+    let (job_service, settings_service, budget, _db) = setup();
+    let job = job_service
+        .create_job(create_mirror_job("/src/", "/dst/"))
+        .unwrap();
+    job_service
+        .record_invocation(&make_invocation(job.id, Utc::now(), 100))
+        .unwrap();
+    settings_service
+        .set_monthly_data_budget(Some(1_000))
+        .unwrap();
+
+    assert!(budget.skip_reason(&job).is_none());
+}
+
+#[test]
+fn test_manual_run_warns_but_is_not_refused() {
+    let (job_service, settings_service, budget, _db) = setup();
+    let job = job_service
+        .create_job(create_mirror_job("/src/", "/dst/"))
+        .unwrap();
+    job_service
+        .record_invocation(&make_invocation(job.id, Utc::now(), 1_500))
+        .unwrap();
+    settings_service
+        .set_monthly_data_budget(Some(1_000))
+        .unwrap();
+
+    let conditions: Vec<Arc<dyn RunCondition>> = vec![Arc::new(budget)];
+
+    assert!(check_run_conditions(&job, &InvocationTrigger::Manual, &conditions).is_none());
+    let warnings = manual_run_warnings(&job, &conditions);
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].contains("exhausted"));
+}
@@ -0,0 +1,286 @@
+use std::cell::RefCell;
+
+use crate::error::AppError;
+use crate::models::job::{DestinationPrep, JobDefinition, SshConfig, StorageLocation};
+use crate::rsync_client::RsyncError;
+use crate::services::destination_prep::{
+    create_remote_destination, destination_prep, options_with_mkpath, remote_mkdir_args,
+    supports_mkpath, MKPATH_ARG,
+};
+use crate::ssh_client::SshClient;
+use crate::tests::test_helpers::create_test_job;
+
+/// Records every `run` call and fails with `error` when one is set.
+struct RecordingSsh {
+    error: Option<String>,
+    ran: RefCell<Vec<Vec<String>>>,
+}
+
+impl RecordingSsh {
+    fn new(error: Option<&str>) -> Self {
+        Self {
+            error: error.map(str::to_string),
+            ran: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+impl SshClient for RecordingSsh {
+    fn keyscan(&self, _host: &str, _port: u16) -> Result<String, RsyncError> {
+        panic!("destination prep never scans host keys");
+    }
+
+    fn run(&self, args: &[String]) -> Result<(), RsyncError> {
+        self.ran.borrow_mut().push(args.to_vec());
+        match &self.error {
+            Some(e) => Err(RsyncError::SshError(e.clone())),
+            None => Ok(()),
+        }
+    }
+}
+
+fn ssh_destination(path: &str) -> StorageLocation {
+    StorageLocation::RemoteSsh {
+        user: "backup".to_string(),
+        host: "nas.local".to_string(),
+        port: 22,
+        path: path.to_string(),
+        identity_file: None,
+    }
+}
+
+fn creating_job() -> JobDefinition {
+    let mut job = create_test_job();
+    job.run_policy.create_destination = true;
+    job.transfer.destination = ssh_destination("/srv/backups/laptop");
+    job
+}
+
+fn strings(args: &[&str]) -> Vec<String> {
+    args.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_remote_mkdir_args_without_ssh_config() {
+    assert_eq!(
+        remote_mkdir_args("backup", "nas.local", "/srv/backups", None),
+        strings(&[
+            "-o",
+            "BatchMode=yes",
+            "--",
+            "backup@nas.local",
+            "mkdir -p -- /srv/backups",
+        ])
+    );
+    assert_eq!(
+        remote_mkdir_args("", "nas.local", "/srv/backups", None),
+        strings(&[
+            "-o",
+            "BatchMode=yes",
+            "--",
+            "nas.local",
+            "mkdir -p -- /srv/backups",
+        ])
+    );
+}
+
+#[test]
+fn test_remote_mkdir_args_use_ssh_config() {
+    let ssh = SshConfig {
+        port: 2222,
+        identity_file: Some("/home/me/.ssh/id_ed25519".to_string()),
+        strict_host_key_checking: false,
+        connect_timeout: Some(10),
+        server_alive_interval: Some(30),
+        jump_host: Some("bastion".to_string()),
+        custom_ssh_command: None,
+    };
+
+    assert_eq!(
+        remote_mkdir_args("backup", "nas.local", "/srv/backups", Some(&ssh)),
+        strings(&[
+            "-o",
+            "BatchMode=yes",
+            "-p",
+            "2222",
+            "-i",
+            "/home/me/.ssh/id_ed25519",
+            "-J",
+            "bastion",
+            "-o",
+            "StrictHostKeyChecking=no",
+            "-o",
+            "ConnectTimeout=10",
+            "-o",
+            "ServerAliveInterval=30",
+            "--",
+            "backup@nas.local",
+            "mkdir -p -- /srv/backups",
+        ])
+    );
+}
+
+#[test]
+fn test_remote_mkdir_quotes_path_for_remote_shell() {
+    let args = remote_mkdir_args(
+        "backup",
+        "nas.local",
+        "/srv/My Backups/it's; rm -rf ~",
+        None,
+    );
+    assert_eq!(
+        args.last().unwrap(),
+        "mkdir -p -- '/srv/My Backups/it'\\''s; rm -rf ~'"
+    );
+}
+
+#[test]
+fn test_remote_mkdir_leaves_home_prefix_for_remote_shell() {
+    let args = remote_mkdir_args("backup", "nas.local", "~/My Backups", None);
+    assert_eq!(args.last().unwrap(), "mkdir -p -- ~/'My Backups'");
+
+    let args = remote_mkdir_args("backup", "nas.local", "/srv/~backups", None);
+    assert_eq!(args.last().unwrap(), "mkdir -p -- '/srv/~backups'");
+}
+
+#[test]
+fn test_host_cannot_pass_as_ssh_option() {
+    let args = remote_mkdir_args("", "-oProxyCommand=touch /tmp/x", "/srv", None);
+    let separator = args.iter().position(|a| a == "--").unwrap();
+    assert_eq!(args[separator + 1], "-oProxyCommand=touch /tmp/x");
+}
+
+#[test]
+fn test_ssh_destination_is_created_over_ssh() {
+    let job = creating_job();
+    assert_eq!(
+        destination_prep(&job, &job.transfer.destination),
+        Some(DestinationPrep::RemoteMkdir(strings(&[
+            "-o",
+            "BatchMode=yes",
+            "--",
+            "backup@nas.local",
+            "mkdir -p -- /srv/backups/laptop",
+        ])))
+    );
+}
+
+#[test]
+fn test_snapshot_destination_is_the_effective_one() {
+    let job = creating_job();
+    let snapshot_dir = ssh_destination("/srv/backups/laptop/2026-10-15_020000");
+    assert_eq!(
+        destination_prep(&job, &snapshot_dir),
+        Some(DestinationPrep::RemoteMkdir(strings(&[
+            "-o",
+            "BatchMode=yes",
+            "--",
+            "backup@nas.local",
+            "mkdir -p -- /srv/backups/laptop/2026-10-15_020000",
+        ])))
+    );
+}
+
+#[test]
+fn test_mkpath_fallback_for_daemon_destination() {
+    let mut job = creating_job();
+    job.transfer.destination = StorageLocation::RemoteRsync {
+        host: "nas.local".to_string(),
+        module: "backups".to_string(),
+        path: "laptop".to_string(),
+        username: None,
+        password_secret: None,
+    };
+    assert_eq!(
+        destination_prep(&job, &job.transfer.destination),
+        Some(DestinationPrep::Mkpath)
+    );
+}
+
+#[test]
+fn test_mkpath_fallback_for_custom_ssh_command() {
+    let mut job = creating_job();
+    job.ssh_config = Some(SshConfig {
+        custom_ssh_command: Some("ssh -F ~/.ssh/backup_config".to_string()),
+        ..SshConfig::default()
+    });
+    assert_eq!(
+        destination_prep(&job, &job.transfer.destination),
+        Some(DestinationPrep::Mkpath)
+    );
+}
+
+#[test]
+fn test_dry_run_never_creates_directories() {
+    let mut job = creating_job();
+    job.options.core_transfer.dry_run = true;
+    assert_eq!(
+        destination_prep(&job, &job.transfer.destination),
+        Some(DestinationPrep::Mkpath)
+    );
+}
+
+#[test]
+fn test_no_prep_when_disabled_or_local() {
+    let mut job = creating_job();
+    job.run_policy.create_destination = false;
+    assert_eq!(destination_prep(&job, &job.transfer.destination), None);
+
+    let job = creating_job();
+    let local = StorageLocation::Local {
+        path: "/backups".to_string(),
+    };
+    assert_eq!(destination_prep(&job, &local), None);
+}
+
+#[test]
+fn test_options_with_mkpath_adds_flag_once() {
+    let job = create_test_job();
+    let options = options_with_mkpath(&options_with_mkpath(&job.options));
+    let count = options
+        .advanced
+        .custom_args
+        .iter()
+        .filter(|a| *a == MKPATH_ARG)
+        .count();
+    assert_eq!(count, 1);
+    assert!(!job
+        .options
+        .advanced
+        .custom_args
+        .iter()
+        .any(|a| a == MKPATH_ARG));
+}
+
+#[test]
+fn test_supports_mkpath_from_3_2_3() {
+    assert!(supports_mkpath("rsync  version 3.2.3  protocol version 31"));
+    assert!(supports_mkpath("rsync  version 3.3.0  protocol version 32"));
+    assert!(!supports_mkpath(
+        "rsync  version 3.2.2  protocol version 31"
+    ));
+    assert!(!supports_mkpath("openrsync: protocol version 29"));
+}
+
+#[test]
+fn test_create_remote_destination_runs_ssh() {
+    let ssh = RecordingSsh::new(None);
+    let args = strings(&["backup@nas.local", "mkdir -p -- /srv/backups"]);
+
+    create_remote_destination(&args, &ssh).unwrap();
+
+    assert_eq!(*ssh.ran.borrow(), vec![args]);
+}
+
+#[test]
+fn test_create_remote_destination_surfaces_ssh_failure() {
+    let ssh = RecordingSsh::new(Some("Permission denied (publickey)"));
+    let args = strings(&["backup@nas.local", "mkdir -p -- /srv/backups"]);
+
+    let result = create_remote_destination(&args, &ssh);
+
+    assert!(matches!(
+        result,
+        Err(AppError::RsyncError(RsyncError::SshError(ref e))) if e.contains("Permission denied")
+    ));
+}
@@ -0,0 +1,96 @@
+use std::path::Path;
+
+use crate::file_system::FileSystem;
+use crate::models::job::{RetentionPolicy, StorageLocation};
+use crate::services::destination_usage::destination_usage;
+use crate::tests::test_file_system::TestFileSystem;
+use crate::tests::test_helpers::{create_mirror_job, create_snapshot_job};
+
+#[test]
+fn test_plain_directory_usage() {
+    let fs = TestFileSystem::new()
+        .with_file("/dst/a.txt", "hello")
+        .with_file("/dst/sub/b.txt", "world!");
+    let job = create_mirror_job("/src/", "/dst/");
+
+    let usage = destination_usage(&job, &fs).unwrap().unwrap();
+
+    assert_eq!(usage.path, "/dst/");
+    assert_eq!(usage.logical_bytes, 11);
+    assert_eq!(usage.physical_bytes, 11);
+}
+
+#[test]
+fn test_hard_linked_snapshots_count_shared_files_once() {
+    let fs = TestFileSystem::new()
+        .with_file("/backups/2025-06-14_020000/big.bin", "0123456789")
+        .with_file("/backups/2025-06-14_020000/old.txt", "abc");
+    // The second snapshot hard-links the unchanged file and adds a new one
+    fs.hard_link(
+        Path::new("/backups/2025-06-14_020000/big.bin"),
+        Path::new("/backups/2025-06-15_020000/big.bin"),
+    )
+    .unwrap();
+    fs.write(Path::new("/backups/2025-06-15_020000/new.txt"), "defg")
+        .unwrap();
+    let job = create_snapshot_job("/src/", "/backups/", RetentionPolicy::default());
+
+    let usage = destination_usage(&job, &fs).unwrap().unwrap();
+
+    assert_eq!(usage.logical_bytes, 10 + 3 + 10 + 4);
+    assert_eq!(usage.physical_bytes, 10 + 3 + 4);
+}
+
+#[test]
+fn test_missing_destination_is_unknown() {
+    let fs = TestFileSystem::new();
+    let job = create_mirror_job("/src/", "/dst/");
+
+    assert_eq!(destination_usage(&job, &fs).unwrap(), None);
+}
+
+#[test]
+fn test_remote_destination_is_unknown() {
+    let fs = TestFileSystem::new().with_file("/backups/a.txt", "hello");
+    let mut job = create_mirror_job("/src/", "/backups/");
+    job.transfer.destination = StorageLocation::RemoteSsh {
+        user: "backup".to_string(),
+        host: "nas.local".to_string(),
+        port: 22,
+        path: "/backups/".to_string(),
+        identity_file: None,
+    };
+
+    assert_eq!(destination_usage(&job, &fs).unwrap(), None);
+}
+
+#[test]
+fn test_unmounted_volume_is_unknown() {
+    // The mountpoint exists but nothing is mounted on it
+    let fs = TestFileSystem::new().with_file("/mnt/backup/data/a.txt", "hello");
+    let mut job = create_mirror_job("/src/", "/dst/");
+    job.transfer.destination = StorageLocation::Mount {
+        mountpoint: "/mnt/backup".to_string(),
+        path: "/mnt/backup/data/".to_string(),
+        expected_fs_label: None,
+    };
+
+    assert_eq!(destination_usage(&job, &fs).unwrap(), None);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_real_file_system_counts_hard_links_once() {
+    use crate::file_system::real_file_system::RealFileSystem;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let first = tmp.path().join("snap1");
+    std::fs::create_dir(&first).unwrap();
+    std::fs::write(first.join("big.bin"), vec![0u8; 4096]).unwrap();
+    std::fs::create_dir(tmp.path().join("snap2")).unwrap();
+    std::fs::hard_link(first.join("big.bin"), tmp.path().join("snap2/big.bin")).unwrap();
+
+    let fs = RealFileSystem::new();
+    assert_eq!(fs.dir_size(tmp.path()).unwrap(), 8192);
+    assert_eq!(fs.unique_dir_size(tmp.path()).unwrap(), 4096);
+}
@@ -0,0 +1,232 @@
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::database::sqlite::Database;
+use crate::models::backup::{
+    BackupInvocation, ExecutionOutput, InvocationStatus, InvocationTrigger, SnapshotRecord,
+    TransferStats,
+};
+use crate::models::job::{ExportData, FullExportData};
+use crate::repository::sqlite::invocation::SqliteInvocationRepository;
+use crate::repository::sqlite::job::SqliteJobRepository;
+use crate::repository::sqlite::snapshot::SqliteSnapshotRepository;
+use crate::repository::sqlite::statistics::SqliteStatisticsRepository;
+use crate::services::export_import::{export_full, import_full};
+use crate::services::job_service::JobService;
+use crate::services::statistics_service::StatisticsService;
+use crate::tests::test_helpers::create_test_job;
+
+fn setup() -> (JobService, StatisticsService) {
+    let db = Database::in_memory().unwrap();
+    let conn = db.conn();
+    (
+        JobService::new(
+            Arc::new(SqliteJobRepository::new(conn.clone())),
+            Arc::new(SqliteInvocationRepository::new(conn.clone())),
+            Arc::new(SqliteSnapshotRepository::new(conn.clone())),
+        ),
+        StatisticsService::new(Arc::new(SqliteStatisticsRepository::new(conn))),
+    )
+}
+
+fn make_invocation(job_id: Uuid, bytes: u64) -> BackupInvocation {
+    let started = Utc::now() - Duration::seconds(30);
+    BackupInvocation {
+        id: Uuid::new_v4(),
+        job_id,
+        started_at: started,
+        finished_at: Some(started + Duration::seconds(10)),
+        status: InvocationStatus::Succeeded,
+        trigger: InvocationTrigger::Scheduled,
+        rerun_of: None,
+        resumed_from: None,
+        warning: None,
+        transfer_stats: TransferStats {
+            bytes_transferred: bytes,
+            files_transferred: 3,
+            total_files: 10,
+            files_deleted: 0,
+            detailed: None,
+        },
+        execution_output: ExecutionOutput {
+            command_executed: "rsync -a /src/ /dst/".to_string(),
+            exit_code: Some(0),
+            snapshot_path: None,
+            log_file_path: Some(format!("/logs/{}.log", job_id)),
+        },
+    }
+}
+
+fn make_snapshot(inv: &BackupInvocation) -> SnapshotRecord {
+    SnapshotRecord {
+        id: Uuid::new_v4(),
+        job_id: inv.job_id,
+        invocation_id: inv.id,
+        snapshot_path: "/dst/2024-01-01".to_string(),
+        link_dest_path: None,
+        created_at: Utc::now(),
+        size_bytes: 4096,
+        file_count: 10,
+        is_latest: true,
+        pinned: false,
+    }
+}
+
+/// Two jobs, three invocations, one snapshot and three statistics.
+fn populate(job_service: &JobService, stats: &StatisticsService) {
+    let job_a = create_test_job();
+    let mut job_b = create_test_job();
+    job_b.id = Uuid::new_v4();
+    job_b.name = "Second Job".to_string();
+    job_service.upsert_job(&job_a).unwrap();
+    job_service.upsert_job(&job_b).unwrap();
+
+    let invocations = [
+        make_invocation(job_a.id, 100),
+        make_invocation(job_a.id, 200),
+        make_invocation(job_b.id, 300),
+    ];
+    for inv in &invocations {
+        job_service.record_invocation(inv).unwrap();
+        stats.record(inv.job_id, inv, Some(2.0)).unwrap();
+    }
+    job_service
+        .record_snapshot(&make_snapshot(&invocations[0]))
+        .unwrap();
+}
+
+fn counts(job_service: &JobService, stats: &StatisticsService) -> (usize, usize, usize, usize) {
+    (
+        job_service.list_jobs().unwrap().len(),
+        job_service.list_all_invocations().unwrap().len(),
+        job_service.list_all_snapshots().unwrap().len(),
+        stats.list_all().unwrap().len(),
+    )
+}
+
+#[test]
+fn test_full_roundtrip_into_fresh_database() {
+    let (src_jobs, src_stats) = setup();
+    populate(&src_jobs, &src_stats);
+    let json = export_full(&src_jobs, &src_stats).unwrap();
+
+    let (dst_jobs, dst_stats) = setup();
+    let restored = import_full(&json, &dst_jobs, &dst_stats).unwrap();
+
+    assert_eq!(counts(&dst_jobs, &dst_stats), (2, 3, 1, 3));
+    assert_eq!(counts(&dst_jobs, &dst_stats), counts(&src_jobs, &src_stats));
+    assert_eq!(restored.jobs.len(), 2);
+    assert_eq!(restored.invocations.len(), 3);
+}
+
+#[test]
+fn test_full_roundtrip_preserves_ids_and_data() {
+    let (src_jobs, src_stats) = setup();
+    populate(&src_jobs, &src_stats);
+    let json = export_full(&src_jobs, &src_stats).unwrap();
+
+    let (dst_jobs, dst_stats) = setup();
+    import_full(&json, &dst_jobs, &dst_stats).unwrap();
+
+    for job in src_jobs.list_jobs().unwrap() {
+        assert_eq!(dst_jobs.get_job(&job.id).unwrap(), job);
+    }
+    for inv in src_jobs.list_all_invocations().unwrap() {
+        assert_eq!(dst_jobs.get_invocation(&inv.id).unwrap(), inv);
+    }
+    assert_eq!(
+        dst_jobs.list_all_snapshots().unwrap(),
+        src_jobs.list_all_snapshots().unwrap()
+    );
+    let mut src_stat_ids: Vec<Uuid> = src_stats.list_all().unwrap().iter().map(|s| s.id).collect();
+    let mut dst_stat_ids: Vec<Uuid> = dst_stats.list_all().unwrap().iter().map(|s| s.id).collect();
+    src_stat_ids.sort();
+    dst_stat_ids.sort();
+    assert_eq!(src_stat_ids, dst_stat_ids);
+}
+
+#[test]
+fn test_reimport_does_not_duplicate_history() {
+    let (src_jobs, src_stats) = setup();
+    populate(&src_jobs, &src_stats);
+    let json = export_full(&src_jobs, &src_stats).unwrap();
+
+    let (dst_jobs, dst_stats) = setup();
+    import_full(&json, &dst_jobs, &dst_stats).unwrap();
+    import_full(&json, &dst_jobs, &dst_stats).unwrap();
+
+    assert_eq!(counts(&dst_jobs, &dst_stats), (2, 3, 1, 3));
+}
+
+#[test]
+fn test_import_into_source_database_keeps_history() {
+    // Upserting a job that already has history must not cascade-delete it.
+    let (jobs, stats) = setup();
+    populate(&jobs, &stats);
+    let json = export_full(&jobs, &stats).unwrap();
+
+    import_full(&json, &jobs, &stats).unwrap();
+
+    assert_eq!(counts(&jobs, &stats), (2, 3, 1, 3));
+}
+
+#[test]
+fn test_import_overwrites_changed_records() {
+    let (src_jobs, src_stats) = setup();
+    populate(&src_jobs, &src_stats);
+    let json = export_full(&src_jobs, &src_stats).unwrap();
+
+    let (dst_jobs, dst_stats) = setup();
+    import_full(&json, &dst_jobs, &dst_stats).unwrap();
+    let mut job = dst_jobs.list_jobs().unwrap()[0].clone();
+    job.name = "Renamed locally".to_string();
+    dst_jobs.upsert_job(&job).unwrap();
+
+    import_full(&json, &dst_jobs, &dst_stats).unwrap();
+
+    let original = src_jobs.get_job(&job.id).unwrap();
+    assert_eq!(dst_jobs.get_job(&job.id).unwrap().name, original.name);
+}
+
+#[test]
+fn test_import_full_accepts_jobs_only_export() {
+    let job = create_test_job();
+    let data = ExportData {
+        version: 1,
+        exported_at: Utc::now(),
+        jobs: vec![job.clone()],
+    };
+    let json = serde_json::to_string(&data).unwrap();
+
+    let (jobs, stats) = setup();
+    let restored = import_full(&json, &jobs, &stats).unwrap();
+
+    assert!(restored.invocations.is_empty());
+    assert_eq!(jobs.get_job(&job.id).unwrap().name, job.name);
+}
+
+#[test]
+fn test_import_full_rejects_future_version() {
+    let data = FullExportData {
+        version: 999,
+        exported_at: Utc::now(),
+        jobs: vec![],
+        invocations: vec![],
+        snapshots: vec![],
+        statistics: vec![],
+    };
+    let json = serde_json::to_string(&data).unwrap();
+
+    let (jobs, stats) = setup();
+    let err = import_full(&json, &jobs, &stats).unwrap_err();
+    assert!(err.contains("Unsupported export version"));
+}
+
+#[test]
+fn test_import_full_rejects_invalid_json() {
+    let (jobs, stats) = setup();
+    let err = import_full("not json", &jobs, &stats).unwrap_err();
+    assert!(err.contains("Invalid export file"));
+}
@@ -0,0 +1,132 @@
+use std::sync::Arc;
+
+use chrono::{Duration, TimeZone, Utc};
+use uuid::Uuid;
+
+use crate::database::sqlite::Database;
+use crate::models::backup::{
+    BackupInvocation, ExecutionOutput, InvocationStatus, InvocationTrigger, TransferStats,
+};
+use crate::repository::sqlite::invocation::SqliteInvocationRepository;
+use crate::repository::sqlite::job::SqliteJobRepository;
+use crate::repository::sqlite::snapshot::SqliteSnapshotRepository;
+use crate::models::execution::history_event::HistoryEvent;
+use crate::services::history_export::export_history_ndjson;
+use crate::services::job_service::JobService;
+use crate::tests::test_helpers::create_test_job;
+
+fn setup() -> JobService {
+    let db = Database::in_memory().unwrap();
+    let conn = db.conn();
+    JobService::new(
+        Arc::new(SqliteJobRepository::new(conn.clone())),
+        Arc::new(SqliteInvocationRepository::new(conn.clone())),
+        Arc::new(SqliteSnapshotRepository::new(conn)),
+    )
+}
+
+fn record(svc: &JobService, job_id: Uuid, day: u32, status: InvocationStatus) -> BackupInvocation {
+    let started_at = Utc.with_ymd_and_hms(2025, 6, day, 14, 0, 0).unwrap();
+    let inv = BackupInvocation {
+        id: Uuid::new_v4(),
+        job_id,
+        started_at,
+        finished_at: Some(started_at + Duration::seconds(90)),
+        status,
+        trigger: InvocationTrigger::Scheduled,
+        rerun_of: None,
+        resumed_from: None,
+        warning: None,
+        transfer_stats: TransferStats {
+            bytes_transferred: 4096,
+            files_transferred: 4,
+            total_files: 40,
+            files_deleted: 2,
+            detailed: None,
+        },
+        execution_output: ExecutionOutput {
+            command_executed: "rsync -a /src/ /dst/".to_string(),
+            exit_code: Some(0),
+            snapshot_path: None,
+            log_file_path: None,
+        },
+    };
+    svc.record_invocation(&inv).unwrap();
+    inv
+}
+
+fn export(svc: &JobService, since: Option<chrono::DateTime<Utc>>) -> (usize, Vec<HistoryEvent>) {
+    let mut out = Vec::new();
+    let count = export_history_ndjson(svc, since, &mut out).unwrap();
+    let text = String::from_utf8(out).unwrap();
+    let events = text
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    (count, events)
+}
+
+#[test]
+fn test_each_invocation_is_one_json_line_oldest_first() {
+    let svc = setup();
+    let mut job = create_test_job();
+    job.name = "Photos".to_string();
+    svc.upsert_job(&job).unwrap();
+    let later = record(&svc, job.id, 16, InvocationStatus::Failed);
+    let earlier = record(&svc, job.id, 15, InvocationStatus::Succeeded);
+
+    let (count, events) = export(&svc, None);
+
+    assert_eq!(count, 2);
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].invocation_id, earlier.id);
+    assert_eq!(events[1].invocation_id, later.id);
+    let first = &events[0];
+    assert_eq!(first.job_id, job.id);
+    assert_eq!(first.job_name, "Photos");
+    assert_eq!(first.status, InvocationStatus::Succeeded);
+    assert_eq!(first.trigger, InvocationTrigger::Scheduled);
+    assert_eq!(first.started_at, earlier.started_at);
+    assert_eq!(first.duration_secs, Some(90.0));
+    assert_eq!(first.exit_code, Some(0));
+    assert_eq!(first.stats.bytes_transferred, 4096);
+    assert_eq!(first.stats.files_transferred, 4);
+    assert_eq!(first.stats.total_files, 40);
+    assert_eq!(first.stats.files_deleted, 2);
+}
+
+#[test]
+fn test_lines_stay_flat() {
+    let svc = setup();
+    let job = create_test_job();
+    svc.upsert_job(&job).unwrap();
+    record(&svc, job.id, 15, InvocationStatus::Succeeded);
+
+    let mut out = Vec::new();
+    export_history_ndjson(&svc, None, &mut out).unwrap();
+
+    let line: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(line["bytes_transferred"], 4096);
+    assert_eq!(line["files_deleted"], 2);
+    assert!(line.get("stats").is_none());
+}
+
+#[test]
+fn test_since_skips_older_runs() {
+    let svc = setup();
+    let job = create_test_job();
+    svc.upsert_job(&job).unwrap();
+    record(&svc, job.id, 14, InvocationStatus::Succeeded);
+    let on_cutoff = record(&svc, job.id, 15, InvocationStatus::Succeeded);
+    let after = record(&svc, job.id, 20, InvocationStatus::Failed);
+
+    let (count, events) = export(&svc, Some(on_cutoff.started_at));
+
+    assert_eq!(count, 2);
+    let ids: Vec<Uuid> = events.iter().map(|e| e.invocation_id).collect();
+    assert_eq!(ids, vec![on_cutoff.id, after.id]);
+
+    let (count, events) = export(&svc, Some(after.started_at + Duration::seconds(1)));
+    assert_eq!(count, 0);
+    assert!(events.is_empty());
+}
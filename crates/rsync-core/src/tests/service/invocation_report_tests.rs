@@ -0,0 +1,107 @@
+use std::sync::Arc;
+
+use chrono::{Duration, TimeZone, Utc};
+use uuid::Uuid;
+
+use crate::database::sqlite::Database;
+use crate::models::backup::{
+    BackupInvocation, ExecutionOutput, InvocationStatus, InvocationTrigger, TransferStats,
+};
+use crate::repository::sqlite::invocation::SqliteInvocationRepository;
+use crate::repository::sqlite::job::SqliteJobRepository;
+use crate::repository::sqlite::snapshot::SqliteSnapshotRepository;
+use crate::services::invocation_report::invocation_report;
+use crate::services::job_service::JobService;
+use crate::tests::test_file_system::TestFileSystem;
+use crate::tests::test_helpers::create_test_job;
+
+const LOG_PATH: &str = "/logs/run.log";
+
+fn setup() -> JobService {
+    let db = Database::in_memory().unwrap();
+    let conn = db.conn();
+    JobService::new(
+        Arc::new(SqliteJobRepository::new(conn.clone())),
+        Arc::new(SqliteInvocationRepository::new(conn.clone())),
+        Arc::new(SqliteSnapshotRepository::new(conn)),
+    )
+}
+
+fn stored_invocation(svc: &JobService, log_file_path: Option<&str>) -> BackupInvocation {
+    let mut job = create_test_job();
+    job.name = "Photos".to_string();
+    svc.upsert_job(&job).unwrap();
+
+    let started_at = Utc.with_ymd_and_hms(2025, 6, 15, 14, 0, 0).unwrap();
+    let inv = BackupInvocation {
+        id: Uuid::new_v4(),
+        job_id: job.id,
+        started_at,
+        finished_at: Some(started_at + Duration::seconds(65)),
+        status: InvocationStatus::Failed,
+        trigger: InvocationTrigger::Manual,
+        rerun_of: None,
+        resumed_from: None,
+        warning: None,
+        transfer_stats: TransferStats {
+            bytes_transferred: 2048,
+            files_transferred: 3,
+            total_files: 10,
+            files_deleted: 1,
+            detailed: None,
+        },
+        execution_output: ExecutionOutput {
+            command_executed: "RSYNC_PASSWORD=hunter2 rsync -a /src/ rsync://nas/share/"
+                .to_string(),
+            exit_code: Some(23),
+            snapshot_path: None,
+            log_file_path: log_file_path.map(str::to_string),
+        },
+    };
+    svc.record_invocation(&inv).unwrap();
+    inv
+}
+
+#[test]
+fn test_report_has_header_fields() {
+    let svc = setup();
+    let inv = stored_invocation(&svc, None);
+
+    let report = invocation_report(&svc, &inv.id, &TestFileSystem::new()).unwrap();
+
+    assert!(report.starts_with("# Run report: Photos\n"));
+    assert!(report.contains(&format!("- **Invocation:** {}", inv.id)));
+    assert!(report.contains("- **Status:** Failed"));
+    assert!(report.contains("- **Trigger:** Manual"));
+    assert!(report.contains("- **Started:** 2025-06-15T14:00:00+00:00"));
+    assert!(report.contains("- **Finished:** 2025-06-15T14:01:05+00:00"));
+    assert!(report.contains("- **Duration:** 1m 5s"));
+    assert!(report.contains("- **Exit code:** 23"));
+    assert!(report.contains("- **Transferred:** 2.0 KB"));
+    assert!(report.contains("- **Files:** 3 transferred, 1 deleted, 10 total"));
+    assert!(report.contains("_Log file not found._"));
+}
+
+#[test]
+fn test_report_redacts_secrets_in_command_and_log() {
+    let svc = setup();
+    let inv = stored_invocation(&svc, Some(LOG_PATH));
+    let fs = TestFileSystem::new().with_file(
+        LOG_PATH,
+        "[STDOUT] sending incremental file list\n[STDERR] auth failed: password=hunter2\n",
+    );
+
+    let report = invocation_report(&svc, &inv.id, &fs).unwrap();
+
+    assert!(!report.contains("hunter2"));
+    assert!(report.contains("RSYNC_PASSWORD=**** rsync -a /src/ rsync://nas/share/"));
+    assert!(report.contains("[STDOUT] sending incremental file list"));
+    assert!(report.contains("auth failed: password=****"));
+}
+
+#[test]
+fn test_report_for_unknown_invocation_fails() {
+    let svc = setup();
+
+    assert!(invocation_report(&svc, &Uuid::new_v4(), &TestFileSystem::new()).is_err());
+}
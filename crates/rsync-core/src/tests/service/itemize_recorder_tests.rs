@@ -0,0 +1,205 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::database::sqlite::Database;
+use crate::models::backup::{
+    BackupInvocation, ExecutionOutput, InvocationStatus, InvocationTrigger, TransferStats,
+};
+use crate::models::itemize::{DifferenceKind, FileType, ItemizedChange, TransferType};
+use crate::repository::sqlite::invocation::SqliteInvocationRepository;
+use crate::repository::sqlite::job::SqliteJobRepository;
+use crate::repository::sqlite::snapshot::SqliteSnapshotRepository;
+use crate::services::itemize_parser::parse_itemize_line;
+use crate::services::itemize_recorder::ItemizeRecorder;
+use crate::services::job_service::JobService;
+use crate::tests::test_helpers::create_test_job;
+
+fn setup() -> JobService {
+    let db = Database::in_memory().unwrap();
+    let conn = db.conn();
+    JobService::new(
+        Arc::new(SqliteJobRepository::new(conn.clone())),
+        Arc::new(SqliteInvocationRepository::new(conn.clone())),
+        Arc::new(SqliteSnapshotRepository::new(conn)),
+    )
+}
+
+fn record_invocation(job_service: &JobService) -> Uuid {
+    let job = job_service.create_job(create_test_job()).unwrap();
+    let inv = BackupInvocation {
+        id: Uuid::new_v4(),
+        job_id: job.id,
+        started_at: Utc::now(),
+        finished_at: Some(Utc::now()),
+        status: InvocationStatus::Succeeded,
+        trigger: InvocationTrigger::Manual,
+        rerun_of: None,
+        resumed_from: None,
+        warning: None,
+        transfer_stats: TransferStats::default(),
+        execution_output: ExecutionOutput {
+            command_executed: "rsync -ai /src /dst".to_string(),
+            exit_code: Some(0),
+            snapshot_path: None,
+            log_file_path: None,
+        },
+    };
+    job_service.record_invocation(&inv).unwrap();
+    inv.id
+}
+
+fn change(
+    transfer_type: TransferType,
+    differences: Vec<DifferenceKind>,
+    path: &str,
+) -> ItemizedChange {
+    ItemizedChange {
+        transfer_type,
+        file_type: FileType::File,
+        differences,
+        path: path.to_string(),
+    }
+}
+
+fn created(i: usize) -> ItemizedChange {
+    change(
+        TransferType::Sent,
+        vec![DifferenceKind::NewlyCreated],
+        &format!("new/{}.txt", i),
+    )
+}
+
+#[test]
+fn test_recorder_stores_everything_under_cap() {
+    let mut recorder = ItemizeRecorder::new(10);
+    for i in 0..3 {
+        recorder.push(&created(i));
+    }
+
+    let stored = recorder.finish();
+    assert_eq!(stored.changes.len(), 3);
+    assert_eq!(stored.summary.total, 3);
+    assert_eq!(stored.summary.stored, 3);
+    assert_eq!(stored.summary.truncated, 0);
+}
+
+#[test]
+fn test_recorder_bounds_stored_list_past_cap() {
+    let mut recorder = ItemizeRecorder::new(100);
+    for i in 0..1_000 {
+        recorder.push(&created(i));
+    }
+
+    let stored = recorder.finish();
+    assert_eq!(stored.changes.len(), 100);
+    assert_eq!(stored.changes[0].path, "new/0.txt");
+    assert_eq!(stored.changes[99].path, "new/99.txt");
+    assert_eq!(stored.summary.total, 1_000);
+    assert_eq!(stored.summary.created, 1_000);
+    assert_eq!(stored.summary.stored, 100);
+    assert_eq!(stored.summary.truncated, 900);
+}
+
+#[test]
+fn test_recorder_counts_truncated_changes_by_kind() {
+    let mut recorder = ItemizeRecorder::new(1);
+    recorder.push(&created(0));
+    recorder.push(&change(
+        TransferType::Sent,
+        vec![DifferenceKind::Size],
+        "changed.txt",
+    ));
+    recorder.push(&change(TransferType::Message, vec![], "gone.txt"));
+    recorder.push(&change(TransferType::Message, vec![], "gone2.txt"));
+
+    let stored = recorder.finish();
+    assert_eq!(stored.changes.len(), 1);
+    assert_eq!(stored.summary.created, 1);
+    assert_eq!(stored.summary.updated, 1);
+    assert_eq!(stored.summary.deleted, 2);
+    assert_eq!(stored.summary.total, 4);
+    assert_eq!(stored.summary.truncated, 3);
+}
+
+#[test]
+fn test_recorder_counts_deleting_lines_in_itemize_stream() {
+    let output = "\
+*deleting   old/report.pdf
+*deleting   old/
+>f+++++++++ new/notes.txt
+.d..t...... docs/
+*deleting   tmp/cache.bin";
+    // Only one change is stored; deletions are still counted past the cap
+    let mut recorder = ItemizeRecorder::new(1);
+    for change in output.lines().filter_map(parse_itemize_line) {
+        recorder.push(&change);
+    }
+
+    assert_eq!(recorder.deleted(), 3);
+    assert_eq!(recorder.finish().summary.total, 5);
+}
+
+#[test]
+fn test_recorder_zero_limit_keeps_summary_only() {
+    let mut recorder = ItemizeRecorder::new(0);
+    recorder.push(&created(0));
+
+    let stored = recorder.finish();
+    assert!(stored.changes.is_empty());
+    assert_eq!(stored.summary.total, 1);
+    assert_eq!(stored.summary.truncated, 1);
+}
+
+#[test]
+fn test_truncated_changes_persist_with_complete_summary() {
+    let job_service = setup();
+    let invocation_id = record_invocation(&job_service);
+    let mut recorder = ItemizeRecorder::new(50);
+    for i in 0..500 {
+        recorder.push(&created(i));
+    }
+    let expected = recorder.finish();
+
+    job_service
+        .record_itemized_changes(&invocation_id, &expected)
+        .unwrap();
+
+    let loaded = job_service
+        .get_itemized_changes(&invocation_id)
+        .unwrap()
+        .unwrap();
+    assert_eq!(loaded.changes.len(), 50);
+    assert_eq!(loaded.summary.total, 500);
+    assert_eq!(loaded.summary.truncated, 450);
+    assert_eq!(loaded, expected);
+}
+
+#[test]
+fn test_get_itemized_changes_none_when_not_recorded() {
+    let job_service = setup();
+    let invocation_id = record_invocation(&job_service);
+    assert!(job_service
+        .get_itemized_changes(&invocation_id)
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn test_itemized_changes_deleted_with_invocation() {
+    let job_service = setup();
+    let invocation_id = record_invocation(&job_service);
+    let mut recorder = ItemizeRecorder::new(10);
+    recorder.push(&created(0));
+    job_service
+        .record_itemized_changes(&invocation_id, &recorder.finish())
+        .unwrap();
+
+    job_service.delete_invocation(&invocation_id).unwrap();
+
+    assert!(job_service
+        .get_itemized_changes(&invocation_id)
+        .unwrap()
+        .is_none());
+}
@@ -0,0 +1,169 @@
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::database::sqlite::Database;
+use crate::error::JobServiceError;
+use crate::models::backup::{
+    BackupInvocation, ExecutionOutput, InvocationStatus, InvocationTrigger, SnapshotRecord,
+    TransferStats,
+};
+use crate::models::job::JobDefinition;
+use crate::repository::sqlite::invocation::SqliteInvocationRepository;
+use crate::repository::sqlite::job::SqliteJobRepository;
+use crate::repository::sqlite::snapshot::SqliteSnapshotRepository;
+use crate::services::job_duplicates::find_duplicate_jobs;
+use crate::services::job_service::JobService;
+use crate::tests::test_helpers::create_mirror_job;
+
+fn job_service() -> JobService {
+    let db = Database::in_memory().unwrap();
+    let conn = db.conn();
+    JobService::new(
+        Arc::new(SqliteJobRepository::new(conn.clone())),
+        Arc::new(SqliteInvocationRepository::new(conn.clone())),
+        Arc::new(SqliteSnapshotRepository::new(conn)),
+    )
+}
+
+fn job_created(name: &str, source: &str, days_ago: i64) -> JobDefinition {
+    let mut job = create_mirror_job(source, "/dst/");
+    job.name = name.to_string();
+    job.created_at = Utc::now() - Duration::days(days_ago);
+    job
+}
+
+fn invocation(job_id: Uuid) -> BackupInvocation {
+    BackupInvocation {
+        id: Uuid::new_v4(),
+        job_id,
+        started_at: Utc::now(),
+        finished_at: Some(Utc::now()),
+        status: InvocationStatus::Succeeded,
+        trigger: InvocationTrigger::Manual,
+        rerun_of: None,
+        resumed_from: None,
+        warning: None,
+        transfer_stats: TransferStats::default(),
+        execution_output: ExecutionOutput {
+            command_executed: "rsync -a /src/ /dst/".to_string(),
+            exit_code: Some(0),
+            snapshot_path: None,
+            log_file_path: None,
+        },
+    }
+}
+
+fn snapshot(job_id: Uuid, invocation_id: Uuid) -> SnapshotRecord {
+    SnapshotRecord {
+        id: Uuid::new_v4(),
+        job_id,
+        invocation_id,
+        snapshot_path: format!("/dst/{}", invocation_id),
+        link_dest_path: None,
+        created_at: Utc::now(),
+        size_bytes: 0,
+        file_count: 0,
+        is_latest: true,
+        pinned: false,
+    }
+}
+
+#[test]
+fn test_find_duplicates_groups_identical_jobs_oldest_first() {
+    let original = job_created("Docs", "/docs/", 3);
+    let mut imported = job_created("Docs (imported)", "/docs/", 1);
+    imported.schedule = None;
+    imported.enabled = false;
+    let photos = job_created("Photos", "/photos/", 2);
+    let photos_copy = job_created("Photos 2", "/photos/", 5);
+    let unique = job_created("Music", "/music/", 4);
+
+    let groups = find_duplicate_jobs(&[
+        imported.clone(),
+        photos.clone(),
+        unique,
+        original.clone(),
+        photos_copy.clone(),
+    ]);
+
+    assert_eq!(
+        groups,
+        vec![
+            vec![photos_copy.id, photos.id],
+            vec![original.id, imported.id],
+        ]
+    );
+}
+
+#[test]
+fn test_jobs_with_different_options_are_not_duplicates() {
+    let job = job_created("Docs", "/docs/", 2);
+    let mut compressed = job_created("Docs", "/docs/", 1);
+    compressed.options.core_transfer.compress = true;
+
+    assert!(find_duplicate_jobs(&[job, compressed]).is_empty());
+}
+
+#[test]
+fn test_merge_reattaches_history_and_snapshots() {
+    let service = job_service();
+    let keep = service
+        .create_job(job_created("Docs", "/docs/", 2))
+        .unwrap();
+    let dup = service
+        .create_job(job_created("Docs copy", "/docs/", 1))
+        .unwrap();
+    let kept_run = invocation(keep.id);
+    let dup_run = invocation(dup.id);
+    service.record_invocation(&kept_run).unwrap();
+    service.record_invocation(&dup_run).unwrap();
+    service
+        .record_snapshot(&snapshot(dup.id, dup_run.id))
+        .unwrap();
+
+    service.merge_jobs(&keep.id, &[dup.id]).unwrap();
+
+    let history = service.get_job_history(&keep.id, 10).unwrap();
+    let mut ids: Vec<Uuid> = history.iter().map(|inv| inv.id).collect();
+    ids.sort();
+    let mut expected = vec![kept_run.id, dup_run.id];
+    expected.sort();
+    assert_eq!(ids, expected);
+
+    let snapshots = service.list_snapshots(&keep.id).unwrap();
+    assert_eq!(snapshots.len(), 1);
+    assert_eq!(snapshots[0].invocation_id, dup_run.id);
+
+    assert!(matches!(
+        service.get_job(&dup.id),
+        Err(JobServiceError::NotFound(_))
+    ));
+    assert_eq!(service.list_jobs().unwrap().len(), 1);
+}
+
+#[test]
+fn test_merge_checks_every_job_before_changing_anything() {
+    let service = job_service();
+    let keep = service
+        .create_job(job_created("Docs", "/docs/", 2))
+        .unwrap();
+    let dup = service
+        .create_job(job_created("Docs copy", "/docs/", 1))
+        .unwrap();
+    service.record_invocation(&invocation(dup.id)).unwrap();
+
+    let missing = Uuid::new_v4();
+    assert!(matches!(
+        service.merge_jobs(&keep.id, &[dup.id, missing]),
+        Err(JobServiceError::NotFound(_))
+    ));
+    assert!(matches!(
+        service.merge_jobs(&keep.id, &[keep.id]),
+        Err(JobServiceError::Conflict(_))
+    ));
+
+    assert_eq!(service.list_jobs().unwrap().len(), 2);
+    assert_eq!(service.get_job_history(&dup.id, 10).unwrap().len(), 1);
+}
@@ -0,0 +1,326 @@
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::database::sqlite::Database;
+use crate::models::backup::{
+    BackupInvocation, ExecutionOutput, InvocationStatus, InvocationTrigger, SnapshotRecord,
+    TransferStats,
+};
+use crate::models::itemize::ItemizedChange;
+use crate::models::job::{JobDefinition, JobStatus, RetentionPolicy, StorageLocation};
+use crate::models::progress::{JobStatusEvent, LogLine, ProgressUpdate};
+use crate::rsync_client::{RsyncClient, RsyncError, RsyncResult};
+use crate::ssh_client::SshClient;
+use crate::services::execution_handler::ExecutionEventHandler;
+use crate::services::job_executor::JobExecutor;
+use crate::services::job_service::JobService;
+use crate::tests::mock_executor::{mock_rsync_executor, StatusHandler};
+use crate::tests::test_file_system::TestFileSystem;
+use crate::tests::test_helpers::{create_mirror_job, create_snapshot_job};
+
+/// A mock executor seeing `fs` in place of the real filesystem. The mock
+/// rsync leaves a `called` file behind, so tests can tell if it ran.
+fn executor_with_fs(dir: &Path, fs: TestFileSystem) -> (JobExecutor, Arc<JobService>, Database) {
+    let script = format!("touch '{}'", dir.join("called").display());
+    let (executor, svc, db) = mock_rsync_executor(dir, &script);
+    (executor.with_file_system(Arc::new(fs)), svc, db)
+}
+
+fn run(
+    executor: &JobExecutor,
+    job: &JobDefinition,
+) -> (Result<Uuid, String>, Receiver<JobStatusEvent>) {
+    let (tx, rx) = mpsc::channel();
+    let handler = Arc::new(StatusHandler(Mutex::new(tx)));
+    (
+        executor.execute(job, InvocationTrigger::Manual, handler),
+        rx,
+    )
+}
+
+fn mount_job() -> JobDefinition {
+    let mut job = create_mirror_job("/src/", "/mnt/backup/data/");
+    job.transfer.destination = StorageLocation::Mount {
+        mountpoint: "/mnt/backup".to_string(),
+        path: "/mnt/backup/data/".to_string(),
+        expected_fs_label: None,
+    };
+    job
+}
+
+#[test]
+fn test_run_refused_when_volume_is_not_mounted() {
+    let tmp = tempfile::tempdir().unwrap();
+    let fs = TestFileSystem::new()
+        .with_dir("/src")
+        .with_dir("/mnt/backup");
+    let (executor, svc, _db) = executor_with_fs(tmp.path(), fs);
+    let job = svc.create_job(mount_job()).unwrap();
+
+    let (result, _rx) = run(&executor, &job);
+
+    let err = result.unwrap_err();
+    assert!(err.contains("Nothing is mounted at /mnt/backup"), "{}", err);
+    assert!(!tmp.path().join("called").exists());
+}
+
+#[test]
+fn test_run_starts_when_volume_is_mounted() {
+    let tmp = tempfile::tempdir().unwrap();
+    let fs = TestFileSystem::new()
+        .with_dir("/src")
+        .with_mount("/mnt/backup", None);
+    let (executor, svc, _db) = executor_with_fs(tmp.path(), fs);
+    let job = svc.create_job(mount_job()).unwrap();
+
+    let (result, rx) = run(&executor, &job);
+
+    assert!(result.is_ok(), "{:?}", result);
+    let event = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+    assert_eq!(event.status, JobStatus::Completed);
+    assert!(tmp.path().join("called").exists());
+}
+
+#[test]
+fn test_run_fails_before_transfer_without_free_space() {
+    let tmp = tempfile::tempdir().unwrap();
+    let fs = TestFileSystem::new()
+        .with_file("/src/big.bin", &"x".repeat(1_000))
+        .with_dir("/dst")
+        .with_available_space(500);
+    let (executor, svc, _db) = executor_with_fs(tmp.path(), fs);
+    let settings = executor.settings_service();
+    settings.set_test_mode(false).unwrap();
+    settings.set_pre_run_space_margin(Some(10)).unwrap();
+    let job = svc.create_job(create_mirror_job("/src/", "/dst/")).unwrap();
+
+    let (result, rx) = run(&executor, &job);
+
+    let invocation_id = result.unwrap();
+    let event = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+    assert_eq!(event.status, JobStatus::Failed);
+    let message = event.error_message.unwrap();
+    assert!(
+        message.starts_with("Insufficient space on destination"),
+        "{}",
+        message
+    );
+    let invocation = svc.get_invocation(&invocation_id).unwrap();
+    assert_eq!(invocation.status, InvocationStatus::Failed);
+    assert!(!tmp.path().join("called").exists());
+}
+
+/// Answers the version probe and counts how often it is asked.
+struct CountingVersion {
+    version: &'static str,
+    probes: AtomicUsize,
+}
+
+impl RsyncClient for CountingVersion {
+    fn execute(&self, _args: &[String]) -> Result<RsyncResult, RsyncError> {
+        Err(RsyncError::RsyncNotFound)
+    }
+
+    fn dry_run(&self, _args: &[String]) -> Result<RsyncResult, RsyncError> {
+        Err(RsyncError::RsyncNotFound)
+    }
+
+    fn version(&self) -> Result<String, RsyncError> {
+        self.probes.fetch_add(1, Ordering::SeqCst);
+        Ok(self.version.to_string())
+    }
+}
+
+#[test]
+fn test_rsync_version_is_probed_once_across_runs() {
+    let tmp = tempfile::tempdir().unwrap();
+    let args_file = tmp.path().join("args");
+    let script = format!("printf '%s\\n' \"$@\" > '{}'", args_file.display());
+    let (executor, svc, _db) = mock_rsync_executor(tmp.path(), &script);
+    let client = Arc::new(CountingVersion {
+        version: "rsync  version 3.2.7  protocol version 31",
+        probes: AtomicUsize::new(0),
+    });
+    let fs = TestFileSystem::new().with_dir("/src").with_dir("/dst");
+    let executor = executor
+        .with_file_system(Arc::new(fs))
+        .with_rsync_client(client.clone());
+    let mut job = create_mirror_job("/src/", "/dst/");
+    job.run_policy.max_runtime_mins = Some(5);
+    let job = svc.create_job(job).unwrap();
+
+    for _ in 0..2 {
+        let (result, rx) = run(&executor, &job);
+        assert!(result.is_ok(), "{:?}", result);
+        rx.recv_timeout(Duration::from_secs(10)).unwrap();
+    }
+
+    assert_eq!(client.probes.load(Ordering::SeqCst), 1);
+    let args = std::fs::read_to_string(&args_file).unwrap();
+    assert!(args.lines().any(|arg| arg == "--stop-after=5"), "{}", args);
+}
+
+/// Records the `ssh` commands a run asks for.
+#[derive(Default)]
+struct RecordingSsh {
+    ran: Mutex<Vec<Vec<String>>>,
+}
+
+impl SshClient for RecordingSsh {
+    fn keyscan(&self, _host: &str, _port: u16) -> Result<String, RsyncError> {
+        Err(RsyncError::SshError("not scanned".to_string()))
+    }
+
+    fn run(&self, args: &[String]) -> Result<(), RsyncError> {
+        self.ran.lock().unwrap().push(args.to_vec());
+        Ok(())
+    }
+}
+
+fn creating_ssh_job() -> JobDefinition {
+    let mut job = create_mirror_job("/src/", "/unused/");
+    job.run_policy.create_destination = true;
+    job.transfer.destination = StorageLocation::RemoteSsh {
+        user: "backup".to_string(),
+        host: "nas.local".to_string(),
+        port: 22,
+        path: "/srv/backups".to_string(),
+        identity_file: None,
+    };
+    job
+}
+
+#[test]
+fn test_remote_destination_is_created_through_injected_ssh() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (executor, svc, _db) = mock_rsync_executor(tmp.path(), "exit 0");
+    executor.settings_service().set_test_mode(false).unwrap();
+    let ssh = Arc::new(RecordingSsh::default());
+    let executor = executor.with_ssh_client(ssh.clone());
+    let job = svc.create_job(creating_ssh_job()).unwrap();
+
+    let (result, rx) = run(&executor, &job);
+
+    assert!(result.is_ok(), "{:?}", result);
+    rx.recv_timeout(Duration::from_secs(10)).unwrap();
+    let ran = ssh.ran.lock().unwrap();
+    assert_eq!(ran.len(), 1);
+    assert_eq!(ran[0].last().unwrap(), "mkdir -p -- /srv/backups");
+}
+
+#[test]
+fn test_mkpath_is_left_out_for_old_rsync() {
+    let tmp = tempfile::tempdir().unwrap();
+    let args_file = tmp.path().join("args");
+    let script = format!("printf '%s\\n' \"$@\" > '{}'", args_file.display());
+    let (executor, svc, _db) = mock_rsync_executor(tmp.path(), &script);
+    let client = Arc::new(CountingVersion {
+        version: "rsync  version 3.1.3  protocol version 31",
+        probes: AtomicUsize::new(0),
+    });
+    let executor = executor.with_rsync_client(client);
+    // Dry runs never create directories themselves, so they rely on --mkpath
+    let mut job = creating_ssh_job();
+    job.options.core_transfer.dry_run = true;
+    let job = svc.create_job(job).unwrap();
+
+    let (result, rx) = run(&executor, &job);
+
+    assert!(result.is_ok(), "{:?}", result);
+    rx.recv_timeout(Duration::from_secs(10)).unwrap();
+    let args = std::fs::read_to_string(&args_file).unwrap();
+    assert!(!args.lines().any(|arg| arg == "--mkpath"), "{}", args);
+}
+
+/// Counts the job's snapshot records at the moment the run is reported.
+struct SnapshotCountHandler {
+    svc: Arc<JobService>,
+    job_id: Uuid,
+    counts: Mutex<mpsc::Sender<usize>>,
+}
+
+impl ExecutionEventHandler for SnapshotCountHandler {
+    fn on_log_line(&self, _line: LogLine) {}
+    fn on_progress(&self, _progress: &ProgressUpdate) {}
+    fn on_status_change(&self, status: JobStatusEvent) {
+        if status.status != JobStatus::Running {
+            let count = self.svc.list_snapshots(&self.job_id).unwrap().len();
+            let _ = self.counts.lock().unwrap().send(count);
+        }
+    }
+    fn on_itemized_change(&self, _invocation_id: Uuid, _change: &ItemizedChange) {}
+}
+
+#[test]
+fn test_snapshots_are_pruned_after_the_run_is_reported() {
+    let tmp = tempfile::tempdir().unwrap();
+    let fs = TestFileSystem::new()
+        .with_dir("/src")
+        .with_dir("/backups/old");
+    let (executor, svc, _db) = executor_with_fs(tmp.path(), fs);
+    executor.settings_service().set_test_mode(false).unwrap();
+    let policy = RetentionPolicy {
+        keep_daily: 0,
+        keep_weekly: 0,
+        keep_monthly: 0,
+    };
+    let job = svc
+        .create_job(create_snapshot_job("/src/", "/backups/", policy))
+        .unwrap();
+    let earlier = BackupInvocation {
+        id: Uuid::new_v4(),
+        job_id: job.id,
+        started_at: Utc::now() - chrono::Duration::days(1),
+        finished_at: Some(Utc::now() - chrono::Duration::days(1)),
+        status: InvocationStatus::Succeeded,
+        trigger: InvocationTrigger::Manual,
+        rerun_of: None,
+        resumed_from: None,
+        warning: None,
+        transfer_stats: TransferStats::default(),
+        execution_output: ExecutionOutput {
+            command_executed: "rsync -a /src/ /backups/old/".to_string(),
+            exit_code: Some(0),
+            snapshot_path: Some("/backups/old".to_string()),
+            log_file_path: None,
+        },
+    };
+    svc.record_invocation(&earlier).unwrap();
+    svc.record_snapshot(&SnapshotRecord {
+        id: Uuid::new_v4(),
+        job_id: job.id,
+        invocation_id: earlier.id,
+        snapshot_path: "/backups/old".to_string(),
+        link_dest_path: None,
+        created_at: Utc::now() - chrono::Duration::days(1),
+        size_bytes: 0,
+        file_count: 0,
+        is_latest: true,
+        pinned: false,
+    })
+    .unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let handler = Arc::new(SnapshotCountHandler {
+        svc: Arc::clone(&svc),
+        job_id: job.id,
+        counts: Mutex::new(tx),
+    });
+    let result = executor.execute(&job, InvocationTrigger::Manual, handler);
+
+    assert!(result.is_ok(), "{:?}", result);
+    // Both snapshots are still there when the run is reported...
+    assert_eq!(rx.recv_timeout(Duration::from_secs(10)).unwrap(), 2);
+    // ...and the old one is pruned afterwards
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while svc.list_snapshots(&job.id).unwrap().len() > 1 {
+        assert!(Instant::now() < deadline, "old snapshot was never pruned");
+        std::thread::sleep(Duration::from_millis(20));
+    }
+}
@@ -0,0 +1,541 @@
+use std::collections::HashMap;
+use std::io::Cursor;
+#[cfg(unix)]
+use std::sync::{mpsc, Arc, Mutex};
+#[cfg(unix)]
+use std::time::Duration;
+
+use uuid::Uuid;
+
+#[cfg(unix)]
+use crate::models::backup::InvocationTrigger;
+use crate::models::execution::event::ExecutionEvent;
+#[cfg(unix)]
+use crate::models::job::JobStatus;
+use crate::models::job::{JobDefinition, ProcessPriority};
+use crate::models::settings::DEFAULT_MAX_LOG_LINE_BYTES;
+use crate::rsync_client::process_rsync_client::ProcessRsyncClient;
+use crate::rsync_client::{classify_failure, RsyncClient, RsyncError};
+use crate::services::itemize_parser::parse_itemize_line;
+use crate::services::job_runner::{
+    describe_env, failure_message, invalid_utf8_notice, is_secret_env_var, is_success_exit,
+    prioritized_command, run_failure_message, run_job, OutputLines, StderrTail,
+    RSYNC_MAX_DELETE_EXIT_CODE, STDERR_TAIL_LINES, TRUNCATION_MARKER,
+};
+use crate::services::progress_parser::parse_progress_line;
+#[cfg(unix)]
+use crate::tests::mock_executor::{mock_rsync_executor, StatusHandler};
+use crate::tests::test_helpers::create_test_job;
+
+/// A file transfer as rsync draws it: one in-place update per `\r`.
+const CR_PROGRESS: &str = "photo.jpg\n\
+     16,384  25%   15.00kB/s    0:00:03\r\
+     32,768  50%   15.00kB/s    0:00:02\r\
+     65,536 100%   31.25kB/s    0:00:02 (xfr#1, to-chk=0/1)\n";
+
+/// Run `sh -c script` through `run_job` and collect its stdout lines.
+fn run_shell(script: &str, env: &HashMap<String, String>) -> Vec<String> {
+    let args = vec!["-c".to_string(), script.to_string()];
+    let (mut child, rx) = run_job(
+        "sh",
+        &args,
+        env,
+        None,
+        DEFAULT_MAX_LOG_LINE_BYTES,
+        Uuid::new_v4(),
+    )
+    .unwrap();
+    let lines = rx
+        .iter()
+        .filter_map(|event| match event {
+            ExecutionEvent::StdoutLine(line) => Some(line),
+            _ => None,
+        })
+        .collect();
+    child.wait().unwrap();
+    lines
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_job_applies_env_to_child() {
+    let mut env = HashMap::new();
+    env.insert("SSH_AUTH_SOCK".to_string(), "/tmp/agent.sock".to_string());
+    env.insert("RSYNC_RSH".to_string(), "ssh -p 2222".to_string());
+
+    let lines = run_shell("echo \"$SSH_AUTH_SOCK|$RSYNC_RSH\"", &env);
+
+    assert_eq!(lines, vec!["/tmp/agent.sock|ssh -p 2222".to_string()]);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_job_empty_env_inherits_parent() {
+    let lines = run_shell("echo \"$PATH\"", &HashMap::new());
+
+    assert_eq!(lines.len(), 1);
+    assert_eq!(lines[0], std::env::var("PATH").unwrap_or_default());
+}
+
+#[test]
+fn test_secret_env_vars_detected() {
+    assert!(is_secret_env_var("RSYNC_PASSWORD"));
+    assert!(is_secret_env_var("aws_secret_access_key"));
+    assert!(is_secret_env_var("GITHUB_TOKEN"));
+    assert!(!is_secret_env_var("SSH_AUTH_SOCK"));
+    assert!(!is_secret_env_var("RSYNC_RSH"));
+}
+
+#[test]
+fn test_describe_env_masks_secrets_and_sorts() {
+    let mut env = HashMap::new();
+    env.insert("RSYNC_PASSWORD".to_string(), "hunter2".to_string());
+    env.insert("RSYNC_RSH".to_string(), "ssh -p 2222".to_string());
+
+    let described = describe_env(&env);
+
+    assert_eq!(
+        described,
+        vec![
+            "RSYNC_PASSWORD=****".to_string(),
+            "RSYNC_RSH=ssh -p 2222".to_string(),
+        ]
+    );
+    assert!(!described.join(" ").contains("hunter2"));
+}
+
+#[test]
+fn test_failure_message_for_delete_limit() {
+    let message = failure_message(Some(RSYNC_MAX_DELETE_EXIT_CODE));
+    assert!(message.starts_with("Delete limit reached"));
+}
+
+#[test]
+fn test_failure_message_for_other_codes() {
+    assert_eq!(failure_message(Some(23)), "rsync exited with code 23");
+    assert_eq!(failure_message(None), "rsync exited with code -1");
+}
+
+/// What a 3.x client prints when the daemon only speaks protocol 2x.
+const PROTOCOL_MISMATCH_STDERR: &str = "protocol version mismatch -- is your shell clean?
+(see the rsync manpage for an explanation)
+rsync error: protocol incompatibility (code 2) at compat.c(622) [sender=3.2.7]";
+
+#[test]
+fn test_protocol_mismatch_stderr_is_classified() {
+    let error = classify_failure(PROTOCOL_MISMATCH_STDERR, Some(2));
+
+    assert_eq!(
+        error,
+        RsyncError::ProtocolMismatch {
+            detail: "protocol version mismatch -- is your shell clean?".to_string(),
+            exit_code: Some(2),
+        }
+    );
+    assert!(error.to_string().contains("--protocol="));
+    assert!(error.to_string().contains("upgrade rsync on the remote"));
+}
+
+#[test]
+fn test_other_stderr_stays_a_process_error() {
+    let error = classify_failure("rsync: connection unexpectedly closed", Some(12));
+
+    assert!(matches!(
+        error,
+        RsyncError::ProcessError {
+            exit_code: Some(12),
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_run_failure_message_prefers_protocol_mismatch() {
+    let detail = "rsync error: protocol incompatibility (code 2)".to_string();
+    let mut stderr = StderrTail::default();
+    stderr.push(&detail);
+    let message = run_failure_message(Some(2), Some(detail), &stderr);
+
+    assert!(message.starts_with("rsync protocol version mismatch: rsync error: protocol"));
+    let silent = StderrTail::default();
+    assert_eq!(
+        run_failure_message(Some(2), None, &silent),
+        "rsync exited with code 2"
+    );
+}
+
+const CHANGE_DIR_FAILED: &str = "rsync: change_dir \"/nope\" failed: No such file or directory (2)";
+const PARTIAL_TRANSFER: &str = "rsync error: some files/attrs were not transferred \
+    (see previous errors) (code 23) at main.c(1338) [sender=3.2.7]";
+
+#[test]
+fn test_run_failure_message_names_salient_stderr_line() {
+    let mut stderr = StderrTail::default();
+    stderr.push("sending incremental file list");
+    stderr.push(&format!("{}\n", CHANGE_DIR_FAILED));
+    stderr.push("");
+    stderr.push(PARTIAL_TRANSFER);
+
+    assert_eq!(
+        run_failure_message(Some(23), None, &stderr),
+        format!("rsync exited with code 23: {}", CHANGE_DIR_FAILED)
+    );
+}
+
+#[test]
+fn test_stderr_tail_falls_back_to_rsync_error_summary() {
+    let mut stderr = StderrTail::default();
+    stderr.push(PARTIAL_TRANSFER);
+
+    assert_eq!(stderr.salient_line(), Some(PARTIAL_TRANSFER));
+}
+
+#[test]
+fn test_stderr_tail_keeps_only_last_lines() {
+    let mut stderr = StderrTail::default();
+    stderr.push("rsync: early complaint");
+    for i in 0..STDERR_TAIL_LINES {
+        stderr.push(&format!("rsync error: summary {} (code 23)", i));
+    }
+
+    assert_eq!(
+        stderr.salient_line(),
+        Some(format!("rsync error: summary {} (code 23)", STDERR_TAIL_LINES - 1).as_str())
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_process_client_reports_protocol_mismatch() {
+    let client = ProcessRsyncClient::with_binary("sh".to_string());
+    let script = format!("printf '%s\\n' '{}' >&2; exit 2", PROTOCOL_MISMATCH_STDERR);
+
+    let error = client.execute(&["-c".to_string(), script]).unwrap_err();
+
+    assert!(matches!(
+        error,
+        RsyncError::ProtocolMismatch {
+            exit_code: Some(2),
+            ..
+        }
+    ));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_failed_run_reports_rsync_stderr() {
+    let tmp = tempfile::tempdir().unwrap();
+    let script = format!(
+        "printf '%s\\n' '{}' '{}' >&2; exit 23",
+        CHANGE_DIR_FAILED, PARTIAL_TRANSFER
+    );
+    let (executor, svc, _db) = mock_rsync_executor(tmp.path(), &script);
+    let job = svc.create_job(create_test_job()).unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let handler = Arc::new(StatusHandler(Mutex::new(tx)));
+    executor
+        .execute(&job, InvocationTrigger::Manual, handler)
+        .unwrap();
+    let event = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+
+    assert_eq!(event.status, JobStatus::Failed);
+    assert_eq!(
+        event.error_message,
+        Some(format!("rsync exited with code 23: {}", CHANGE_DIR_FAILED))
+    );
+}
+
+#[test]
+fn test_vanished_files_succeed_when_job_accepts_24() {
+    let mut job = create_test_job();
+    job.run_policy.success_exit_codes = vec![0, 24];
+    assert!(is_success_exit(
+        Some(24),
+        &job.run_policy.success_exit_codes
+    ));
+    assert!(is_success_exit(Some(0), &job.run_policy.success_exit_codes));
+    assert!(!is_success_exit(
+        Some(23),
+        &job.run_policy.success_exit_codes
+    ));
+}
+
+#[test]
+fn test_vanished_files_fail_by_default() {
+    let job = create_test_job();
+    assert_eq!(job.run_policy.success_exit_codes, vec![0]);
+    assert!(!is_success_exit(
+        Some(24),
+        &job.run_policy.success_exit_codes
+    ));
+    assert!(is_success_exit(Some(0), &job.run_policy.success_exit_codes));
+}
+
+#[test]
+fn test_killed_run_is_never_success() {
+    assert!(!is_success_exit(None, &[0, 24]));
+}
+
+#[test]
+fn test_job_without_success_exit_codes_deserializes_to_zero() {
+    let mut value = serde_json::to_value(create_test_job()).unwrap();
+    value.as_object_mut().unwrap().remove("success_exit_codes");
+    let job: JobDefinition = serde_json::from_value(value).unwrap();
+    assert_eq!(job.run_policy.success_exit_codes, vec![0]);
+}
+
+fn output_lines(input: &str) -> Vec<String> {
+    OutputLines::new(Cursor::new(input.as_bytes()))
+        .map(|line| line.unwrap())
+        .collect()
+}
+
+#[test]
+fn test_output_lines_truncate_oversized_lines() {
+    let huge = "x".repeat(10_000);
+    let input = format!("before\n{}\nafter\n", huge);
+
+    let lines: Vec<String> = OutputLines::new(Cursor::new(input.as_bytes()))
+        .max_line_bytes(100)
+        .map(|line| line.unwrap())
+        .collect();
+
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0], "before");
+    assert_eq!(
+        lines[1],
+        format!("{}{} [9900 more bytes]", "x".repeat(100), TRUNCATION_MARKER)
+    );
+    assert_eq!(lines[2], "after");
+}
+
+#[test]
+fn test_output_lines_truncate_on_a_character_boundary() {
+    // "é" is two bytes, so a 5-byte cap falls inside the third one
+    let lines: Vec<String> = OutputLines::new(Cursor::new("ééééé\n".as_bytes()))
+        .max_line_bytes(5)
+        .map(|line| line.unwrap())
+        .collect();
+
+    assert_eq!(
+        lines,
+        vec![format!("éé{} [5 more bytes]", TRUNCATION_MARKER)]
+    );
+}
+
+#[test]
+fn test_output_lines_keep_lines_with_invalid_utf8() {
+    // A Latin-1 "café.txt": 0xE9 is not valid UTF-8 on its own
+    let input: &[u8] = b"sending incremental file list\n>f+++++++++ caf\xe9.txt\ndone\n";
+    let mut reader = OutputLines::new(Cursor::new(input));
+    let lines: Vec<String> = reader.by_ref().map(|line| line.unwrap()).collect();
+
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[1], ">f+++++++++ caf\u{FFFD}.txt");
+    assert_eq!(lines[2], "done");
+    assert_eq!(reader.lossy_lines(), 1);
+
+    let change = parse_itemize_line(&lines[1]).unwrap();
+    assert_eq!(change.path, "caf\u{FFFD}.txt");
+}
+
+#[test]
+fn test_output_lines_count_no_lossy_lines_for_valid_utf8() {
+    let mut reader = OutputLines::new(Cursor::new("café.txt\nnaïve.txt\n".as_bytes()));
+    assert_eq!(reader.by_ref().count(), 2);
+    assert_eq!(reader.lossy_lines(), 0);
+}
+
+#[test]
+fn test_output_lines_newline_only_keeps_carriage_returns_inside_lines() {
+    let lines: Vec<String> = OutputLines::new(Cursor::new("a\rb\r\nc\n".as_bytes()))
+        .newline_only()
+        .map(|line| line.unwrap())
+        .collect();
+
+    assert_eq!(lines, vec!["a\rb", "c"]);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_job_truncates_oversized_stderr_lines() {
+    let args = vec![
+        "-c".to_string(),
+        "head -c 5000 /dev/zero | tr '\\0' 'y' >&2; echo >&2; echo ok >&2".to_string(),
+    ];
+    let (mut child, rx) = run_job("sh", &args, &HashMap::new(), None, 64, Uuid::new_v4()).unwrap();
+    let stderr: Vec<String> = rx
+        .iter()
+        .filter_map(|event| match event {
+            ExecutionEvent::StderrLine(line) => Some(line),
+            _ => None,
+        })
+        .collect();
+    child.wait().unwrap();
+
+    assert_eq!(
+        stderr,
+        vec![
+            format!("{}{} [4936 more bytes]", "y".repeat(64), TRUNCATION_MARKER),
+            "ok".to_string()
+        ]
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_job_notes_invalid_utf8_output() {
+    let args = vec![
+        "-c".to_string(),
+        "printf 'caf\\351.txt\\nok\\n'".to_string(),
+    ];
+    let (mut child, rx) = run_job("sh", &args, &HashMap::new(), None, 64, Uuid::new_v4()).unwrap();
+    let events: Vec<ExecutionEvent> = rx.iter().collect();
+    child.wait().unwrap();
+
+    let stdout: Vec<&str> = events
+        .iter()
+        .filter_map(|event| match event {
+            ExecutionEvent::StdoutLine(line) => Some(line.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(stdout, vec!["caf\u{FFFD}.txt", "ok"]);
+    let notice = invalid_utf8_notice(1);
+    assert!(events
+        .iter()
+        .any(|event| matches!(event, ExecutionEvent::StderrLine(line) if *line == notice)));
+}
+
+#[test]
+fn test_output_lines_split_on_carriage_returns() {
+    let lines = output_lines(CR_PROGRESS);
+
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[0], "photo.jpg");
+    assert!(lines[1].contains("25%"));
+    assert!(lines[3].contains("100%"));
+}
+
+#[test]
+fn test_output_lines_skip_empty_segments() {
+    assert_eq!(output_lines("a\r\nb\n\n\rc"), vec!["a", "b", "c"]);
+}
+
+#[test]
+fn test_output_lines_flush_unterminated_line() {
+    assert_eq!(
+        output_lines("done\r     1,024 100%"),
+        vec!["done", "     1,024 100%"]
+    );
+}
+
+#[test]
+fn test_cr_delimited_stream_yields_each_progress_update() {
+    let id = Uuid::new_v4();
+    let updates: Vec<_> = output_lines(CR_PROGRESS)
+        .iter()
+        .filter_map(|line| parse_progress_line(line, id))
+        .collect();
+
+    let percentages: Vec<f64> = updates.iter().map(|u| u.percentage).collect();
+    assert_eq!(percentages, vec![25.0, 50.0, 100.0]);
+    assert_eq!(updates[2].files_transferred, 1);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_job_emits_progress_for_carriage_return_updates() {
+    let args = vec![
+        "-c".to_string(),
+        // %b expands the escapes without treating the `%` signs as directives
+        format!(
+            "printf '%b' '{}'",
+            CR_PROGRESS.replace('\r', "\\r").replace('\n', "\\n")
+        ),
+    ];
+    let (mut child, rx) = run_job(
+        "sh",
+        &args,
+        &HashMap::new(),
+        None,
+        DEFAULT_MAX_LOG_LINE_BYTES,
+        Uuid::new_v4(),
+    )
+    .unwrap();
+    let progress: Vec<f64> = rx
+        .iter()
+        .filter_map(|event| match event {
+            ExecutionEvent::Progress(update) => Some(update.percentage),
+            _ => None,
+        })
+        .collect();
+    child.wait().unwrap();
+
+    assert_eq!(progress, vec![25.0, 50.0, 100.0]);
+}
+
+fn strings(parts: &[&str]) -> Vec<String> {
+    parts.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_prioritized_command_wraps_low_priority_in_nice_and_ionice_on_linux() {
+    let args = strings(&["-a", "/src/"]);
+    let (program, args) = prioritized_command("rsync", &args, Some(ProcessPriority::Low), "linux");
+
+    assert_eq!(program, "nice");
+    assert_eq!(
+        args,
+        strings(&["-n", "10", "ionice", "-c", "2", "-n", "7", "rsync", "-a", "/src/"])
+    );
+}
+
+#[test]
+fn test_prioritized_command_raises_high_priority_on_linux() {
+    let (program, args) = prioritized_command(
+        "rsync",
+        &strings(&["-a"]),
+        Some(ProcessPriority::High),
+        "linux",
+    );
+
+    assert_eq!(program, "nice");
+    assert_eq!(
+        args,
+        strings(&["-n", "-5", "ionice", "-c", "2", "-n", "0", "rsync", "-a"])
+    );
+}
+
+#[test]
+fn test_prioritized_command_uses_nice_only_on_macos() {
+    let (program, args) = prioritized_command(
+        "rsync",
+        &strings(&["-a"]),
+        Some(ProcessPriority::Low),
+        "macos",
+    );
+
+    assert_eq!(program, "nice");
+    assert_eq!(args, strings(&["-n", "10", "rsync", "-a"]));
+}
+
+#[test]
+fn test_prioritized_command_leaves_normal_and_unsupported_unchanged() {
+    let args = strings(&["-a", "/src/"]);
+    for (priority, os) in [
+        (None, "linux"),
+        (Some(ProcessPriority::Normal), "linux"),
+        (Some(ProcessPriority::Low), "windows"),
+        (Some(ProcessPriority::High), "haiku"),
+    ] {
+        assert_eq!(
+            prioritized_command("rsync", &args, priority, os),
+            ("rsync".to_string(), args.clone()),
+            "{:?} on {}",
+            priority,
+            os
+        );
+    }
+}
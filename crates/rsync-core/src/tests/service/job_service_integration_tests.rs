@@ -1,9 +1,10 @@
 use std::sync::Arc;
 
-use chrono::Utc;
+use chrono::{TimeZone, Utc};
 use uuid::Uuid;
 
 use crate::database::sqlite::Database;
+use crate::error::{AppError, JobServiceError};
 use crate::repository::sqlite::invocation::SqliteInvocationRepository;
 use crate::repository::sqlite::job::SqliteJobRepository;
 use crate::repository::sqlite::snapshot::SqliteSnapshotRepository;
@@ -11,7 +12,17 @@ use crate::models::backup::{
     BackupInvocation, ExecutionOutput, InvocationStatus, InvocationTrigger, SnapshotRecord,
     TransferStats,
 };
-use crate::models::job::{BackupMode, JobDefinition, RsyncOptions, StorageLocation, TransferConfig};
+use crate::models::schedule::{ScheduleConfig, ScheduleType};
+use crate::models::job::{
+    BackupMode, JobAppearance, JobDefinition, JobRunPolicy, JobSafety, RsyncOptions, SshConfig,
+    StorageLocation, TransferConfig,
+};
+use crate::models::settings::QuietHours;
+use crate::models::validation::{
+    is_valid_hex_color, parse_hex_color, schedule_check, validate_job, CheckSeverity, CheckType,
+    ValidationCheck,
+};
+use crate::services::command_builder::build_rsync_args;
 use crate::services::job_service::JobService;
 
 fn setup() -> JobService {
@@ -41,6 +52,9 @@ fn make_job_definition(name: &str) -> JobDefinition {
         },
         options: RsyncOptions::default(),
         ssh_config: None,
+        appearance: JobAppearance::default(),
+        run_policy: JobRunPolicy::default(),
+        safety: JobSafety::default(),
         schedule: None,
         enabled: true,
         created_at: now,
@@ -56,10 +70,15 @@ fn make_invocation(job_id: Uuid) -> BackupInvocation {
         finished_at: None,
         status: InvocationStatus::Running,
         trigger: InvocationTrigger::Manual,
+        rerun_of: None,
+        resumed_from: None,
+        warning: None,
         transfer_stats: TransferStats {
             bytes_transferred: 0,
             files_transferred: 0,
             total_files: 50,
+            files_deleted: 0,
+            detailed: None,
         },
         execution_output: ExecutionOutput {
             command_executed: "rsync -a /src/ /dst/".to_string(),
@@ -97,6 +116,262 @@ fn test_create_job_whitespace_name_fails() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_get_job_missing_returns_not_found() {
+    let svc = setup();
+    let result = svc.get_job(&Uuid::new_v4());
+    assert!(matches!(result, Err(JobServiceError::NotFound(_))));
+}
+
+#[test]
+fn test_update_missing_job_returns_not_found() {
+    let svc = setup();
+    let result = svc.update_job(make_job_definition("Ghost"));
+    assert!(matches!(result, Err(JobServiceError::NotFound(_))));
+}
+
+#[test]
+fn test_create_job_empty_name_returns_structured_checks() {
+    let svc = setup();
+    let err = svc.create_job(make_job_definition("")).unwrap_err();
+
+    let checks = match err {
+        JobServiceError::Validation(checks) => checks,
+        other => panic!("expected validation error, got {:?}", other),
+    };
+    let failed: Vec<_> = checks.iter().filter(|c| !c.passed).collect();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].check_type, CheckType::JobName);
+    assert_eq!(failed[0].message, "Job name must not be empty");
+}
+
+#[test]
+fn test_validation_error_message_lists_failed_checks() {
+    let svc = setup();
+    let err = svc.create_job(make_job_definition(" ")).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Validation failed: Job name must not be empty"
+    );
+}
+
+#[test]
+fn test_hex_color_validation() {
+    assert_eq!(parse_hex_color("#3b82f6"), Some((0x3b, 0x82, 0xf6)));
+    assert_eq!(parse_hex_color("#F00"), Some((0xff, 0x00, 0x00)));
+    assert!(is_valid_hex_color("#ABCDEF"));
+
+    for bad in [
+        "3b82f6",
+        "#3b82f",
+        "#3b82f6aa",
+        "#ggg",
+        "#",
+        "",
+        "red",
+        "#12 456",
+        "#ü12",
+    ] {
+        assert!(!is_valid_hex_color(bad), "{:?} should be rejected", bad);
+    }
+}
+
+#[test]
+fn test_create_job_rejects_invalid_color() {
+    let svc = setup();
+    let mut job = make_job_definition("Colorful");
+    job.appearance.color = Some("blue".to_string());
+
+    let checks = match svc.create_job(job).unwrap_err() {
+        JobServiceError::Validation(checks) => checks,
+        other => panic!("expected validation error, got {:?}", other),
+    };
+    let failed: Vec<_> = checks.iter().filter(|c| !c.passed).collect();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].check_type, CheckType::JobColor);
+}
+
+#[test]
+fn test_partial_dir_validation() {
+    let check = |dir: &str| {
+        let mut job = make_job_definition("Partial");
+        job.transfer.destination = StorageLocation::Local {
+            path: "/backups/docs/".to_string(),
+        };
+        job.options.core_transfer.partial_dir = Some(dir.to_string());
+        validate_job(&job)
+            .into_iter()
+            .find(|c| c.check_type == CheckType::PartialDir)
+            .unwrap()
+    };
+
+    assert!(check(".rsync-partial").passed);
+    assert!(check("/var/tmp/rsync-partial").passed);
+    // Inside the destination, the destination itself, or one of its parents
+    assert!(!check("/backups/docs/.partial").passed);
+    assert!(!check("/backups/docs").passed);
+    assert!(!check("/backups").passed);
+    assert!(!check("  ").passed);
+}
+
+#[test]
+fn test_create_job_rejects_partial_dir_inside_destination() {
+    let svc = setup();
+    let mut job = make_job_definition("Partial");
+    job.transfer.destination = StorageLocation::Local {
+        path: "/backups/docs".to_string(),
+    };
+    job.options.core_transfer.partial_dir = Some("/backups/docs/.partial".to_string());
+
+    let checks = match svc.create_job(job).unwrap_err() {
+        JobServiceError::Validation(checks) => checks,
+        other => panic!("expected validation error, got {:?}", other),
+    };
+    let failed: Vec<_> = checks.iter().filter(|c| !c.passed).collect();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].check_type, CheckType::PartialDir);
+}
+
+#[test]
+fn test_create_job_rejects_ssh_host_starting_with_dash() {
+    let svc = setup();
+    let mut job = make_job_definition("Injected");
+    job.transfer.destination = StorageLocation::RemoteSsh {
+        user: String::new(),
+        host: "-oProxyCommand=touch /tmp/pwned".to_string(),
+        port: 22,
+        path: "/srv/backups".to_string(),
+        identity_file: None,
+    };
+
+    let checks = match svc.create_job(job).unwrap_err() {
+        JobServiceError::Validation(checks) => checks,
+        other => panic!("expected validation error, got {:?}", other),
+    };
+    let failed: Vec<_> = checks.iter().filter(|c| !c.passed).collect();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].check_type, CheckType::RemoteHost);
+    assert!(failed[0].message.starts_with("Destination host"));
+}
+
+#[test]
+fn test_app_errors_keep_their_kind_as_job_service_errors() {
+    let validation = JobServiceError::from(AppError::ValidationError("bad range".to_string()));
+    match validation {
+        JobServiceError::Validation(checks) => {
+            assert_eq!(checks.len(), 1);
+            assert!(!checks[0].passed);
+            assert_eq!(checks[0].message, "bad range");
+        }
+        other => panic!("expected validation error, got {:?}", other),
+    }
+
+    let io = JobServiceError::from(AppError::IoError(std::io::Error::other("disk gone")));
+    assert!(matches!(io, JobServiceError::Other(_)));
+    assert_eq!(io.to_string(), "IO error: disk gone");
+
+    let conflict = JobServiceError::from(AppError::Conflict("duplicate id".to_string()));
+    assert!(matches!(conflict, JobServiceError::Conflict(_)));
+}
+
+fn cron(expression: &str) -> ScheduleType {
+    ScheduleType::Cron {
+        expression: expression.to_string(),
+    }
+}
+
+#[test]
+fn test_schedule_check_passes_valid_schedules() {
+    let from = Utc.with_ymd_and_hms(2025, 6, 1, 12, 0, 0).unwrap();
+    let quiet = QuietHours::parse("22:00-06:00").unwrap();
+    for schedule in [ScheduleType::Interval { minutes: 60 }, cron("0 9 * * *")] {
+        let check = schedule_check(&schedule, Some(&quiet), from, &Utc);
+        assert_eq!(check.check_type, CheckType::ScheduleHealth);
+        assert!(check.passed, "{:?}: {}", schedule, check.message);
+    }
+}
+
+#[test]
+fn test_schedule_check_rejects_zero_interval() {
+    let check = schedule_check(
+        &ScheduleType::Interval { minutes: 0 },
+        None,
+        Utc::now(),
+        &Utc,
+    );
+    assert!(!check.passed);
+    assert_eq!(check.severity, CheckSeverity::Error);
+}
+
+#[test]
+fn test_schedule_check_rejects_never_firing_cron() {
+    for expression in ["0 0 30 2 *", "not a cron"] {
+        let check = schedule_check(&cron(expression), None, Utc::now(), &Utc);
+        assert!(!check.passed, "{}", expression);
+        assert_eq!(check.severity, CheckSeverity::Error);
+    }
+}
+
+#[test]
+fn test_schedule_check_warns_when_quiet_hours_cover_every_run() {
+    let from = Utc.with_ymd_and_hms(2025, 6, 1, 12, 0, 0).unwrap();
+    let quiet = QuietHours::parse("01:00-05:00").unwrap();
+
+    // The only daily run at 03:00 is always inside quiet hours
+    let check = schedule_check(&cron("0 3 * * *"), Some(&quiet), from, &Utc);
+    assert!(!check.passed);
+    assert_eq!(check.severity, CheckSeverity::Warning);
+    assert!(check.message.contains("01:00-05:00"));
+
+    // One of the two daily runs is outside them
+    assert!(schedule_check(&cron("0 3,9 * * *"), Some(&quiet), from, &Utc).passed);
+}
+
+#[test]
+fn test_create_job_rejects_never_firing_schedule() {
+    let svc = setup();
+    let mut job = make_job_definition("Never");
+    job.schedule = Some(ScheduleConfig {
+        schedule_type: ScheduleType::Interval { minutes: 0 },
+        enabled: true,
+    });
+
+    match svc.create_job(job.clone()).unwrap_err() {
+        JobServiceError::Validation(checks) => assert!(checks
+            .iter()
+            .any(|c| c.check_type == CheckType::ScheduleHealth && !c.passed)),
+        other => panic!("expected validation error, got {:?}", other),
+    }
+
+    // A disabled schedule is allowed to never run
+    job.schedule.as_mut().unwrap().enabled = false;
+    assert!(svc.create_job(job).is_ok());
+}
+
+#[test]
+fn test_color_and_icon_round_trip() {
+    let svc = setup();
+    let mut job = make_job_definition("Photos");
+    job.appearance.color = Some("#22c55e".to_string());
+    job.appearance.icon = Some("camera".to_string());
+
+    let created = svc.create_job(job).unwrap();
+    let fetched = svc.get_job(&created.id).unwrap();
+    assert_eq!(fetched.appearance.color.as_deref(), Some("#22c55e"));
+    assert_eq!(fetched.appearance.icon.as_deref(), Some("camera"));
+}
+
+#[test]
+fn test_duplicate_invocation_returns_conflict() {
+    let svc = setup();
+    let job = svc.create_job(make_job_definition("Dupes")).unwrap();
+    let inv = make_invocation(job.id);
+    svc.record_invocation(&inv).unwrap();
+
+    let result = svc.record_invocation(&inv);
+    assert!(matches!(result, Err(JobServiceError::Conflict(_))));
+}
+
 #[test]
 fn test_update_job_changes_updated_at() {
     let svc = setup();
@@ -115,6 +390,42 @@ fn test_update_job_changes_updated_at() {
     assert!(updated.updated_at > original_updated_at);
 }
 
+#[test]
+fn test_update_job_with_current_version_succeeds_twice() {
+    let svc = setup();
+    let created = svc.create_job(make_job_definition("Versioned")).unwrap();
+
+    let mut first = created;
+    first.name = "First edit".to_string();
+    let first = svc.update_job(first).unwrap();
+
+    // Editing the returned (current) version is not a conflict
+    let mut second = first;
+    second.name = "Second edit".to_string();
+    svc.update_job(second).unwrap();
+
+    assert_eq!(svc.list_jobs().unwrap()[0].name, "Second edit");
+}
+
+#[test]
+fn test_stale_update_returns_conflict() {
+    let svc = setup();
+    let created = svc.create_job(make_job_definition("Shared")).unwrap();
+
+    // Two editors load the same version; the GUI saves first
+    let mut gui_copy = created.clone();
+    let mut tui_copy = created;
+    std::thread::sleep(std::time::Duration::from_millis(10));
+    gui_copy.name = "From GUI".to_string();
+    svc.update_job(gui_copy).unwrap();
+
+    tui_copy.name = "From TUI".to_string();
+    let result = svc.update_job(tui_copy);
+
+    assert!(matches!(result, Err(JobServiceError::Conflict(_))));
+    assert_eq!(svc.list_jobs().unwrap()[0].name, "From GUI");
+}
+
 #[test]
 fn test_delete_job_cascades() {
     let svc = setup();
@@ -135,6 +446,7 @@ fn test_delete_job_cascades() {
         size_bytes: 1024,
         file_count: 3,
         is_latest: true,
+        pinned: false,
     };
     svc.record_snapshot(&snap).unwrap();
 
@@ -150,6 +462,66 @@ fn test_delete_job_cascades() {
     assert!(latest.is_none());
 }
 
+#[test]
+fn test_recent_snapshot_paths_newest_first_as_link_dests() {
+    let svc = setup();
+    let job = svc
+        .create_job(make_job_definition("Snapshot Chain"))
+        .unwrap();
+    let inv = make_invocation(job.id);
+    svc.record_invocation(&inv).unwrap();
+
+    let now = Utc::now();
+    let snapshots = [
+        ("/backups/b", 2),
+        ("/backups/d", 0),
+        ("/backups/a", 3),
+        ("/backups/c", 1),
+    ];
+    for (path, hours_ago) in snapshots {
+        svc.record_snapshot(&SnapshotRecord {
+            id: Uuid::new_v4(),
+            job_id: job.id,
+            invocation_id: inv.id,
+            snapshot_path: path.to_string(),
+            link_dest_path: None,
+            created_at: now - chrono::Duration::hours(hours_ago),
+            size_bytes: 0,
+            file_count: 0,
+            is_latest: hours_ago == 0,
+            pinned: false,
+        })
+        .unwrap();
+    }
+
+    let paths = svc.recent_snapshot_paths(&job.id, 3).unwrap();
+    assert_eq!(paths, vec!["/backups/d", "/backups/c", "/backups/b"]);
+
+    let refs: Vec<&str> = paths.iter().map(String::as_str).collect();
+    let args = build_rsync_args(
+        &job.transfer.source,
+        &job.transfer.destination,
+        &job.options,
+        None,
+        &refs,
+        true,
+        false,
+    );
+    let links: Vec<&String> = args
+        .iter()
+        .filter(|a| a.starts_with("--link-dest="))
+        .collect();
+    assert_eq!(
+        links,
+        vec![
+            "--link-dest=/backups/d",
+            "--link-dest=/backups/c",
+            "--link-dest=/backups/b"
+        ]
+    );
+    assert_eq!(svc.recent_snapshot_paths(&job.id, 10).unwrap().len(), 4);
+}
+
 #[test]
 fn test_full_lifecycle() {
     let svc = setup();
@@ -180,6 +552,7 @@ fn test_full_lifecycle() {
         size_bytes: 2048,
         file_count: 10,
         is_latest: true,
+        pinned: false,
     };
     svc.record_snapshot(&snap).unwrap();
 
@@ -217,3 +590,428 @@ fn test_list_multiple_jobs() {
     let jobs = svc.list_jobs().unwrap();
     assert_eq!(jobs.len(), 3);
 }
+
+fn scheduled_job(svc: &JobService, name: &str, created_at: chrono::DateTime<Utc>) -> JobDefinition {
+    let mut job = make_job_definition(name);
+    job.schedule = Some(ScheduleConfig {
+        schedule_type: ScheduleType::Interval { minutes: 24 * 60 },
+        enabled: true,
+    });
+    job.created_at = created_at;
+    job.updated_at = created_at;
+    svc.upsert_job(&job).unwrap();
+    job
+}
+
+fn record_run_at(svc: &JobService, job_id: Uuid, started_at: chrono::DateTime<Utc>) {
+    let mut inv = make_invocation(job_id);
+    inv.started_at = started_at;
+    svc.record_invocation(&inv).unwrap();
+}
+
+#[test]
+fn test_overdue_jobs_skips_recently_run_job() {
+    let svc = setup();
+    let now = Utc::now();
+    let job = scheduled_job(&svc, "Daily", now - chrono::Duration::days(30));
+    record_run_at(&svc, job.id, now - chrono::Duration::hours(23));
+
+    let overdue = svc.overdue_jobs(now, chrono::Duration::hours(1)).unwrap();
+    assert!(overdue.is_empty());
+}
+
+#[test]
+fn test_overdue_jobs_reports_late_job() {
+    let svc = setup();
+    let now = Utc::now();
+    let job = scheduled_job(&svc, "Daily", now - chrono::Duration::days(30));
+    record_run_at(&svc, job.id, now - chrono::Duration::days(5));
+    // Older runs don't matter, only the latest
+    record_run_at(&svc, job.id, now - chrono::Duration::days(10));
+
+    let overdue = svc.overdue_jobs(now, chrono::Duration::hours(1)).unwrap();
+    assert_eq!(overdue.len(), 1);
+    assert_eq!(overdue[0].0.id, job.id);
+    assert_eq!(overdue[0].1, chrono::Duration::days(4));
+}
+
+#[test]
+fn test_overdue_jobs_respects_grace_period() {
+    let svc = setup();
+    let now = Utc::now();
+    let job = scheduled_job(&svc, "Daily", now - chrono::Duration::days(30));
+    // Due one hour ago
+    record_run_at(&svc, job.id, now - chrono::Duration::hours(25));
+
+    assert!(svc
+        .overdue_jobs(now, chrono::Duration::hours(2))
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        svc.overdue_jobs(now, chrono::Duration::minutes(30))
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn test_overdue_jobs_never_run_counts_from_creation() {
+    let svc = setup();
+    let now = Utc::now();
+    let fresh = scheduled_job(&svc, "Fresh", now - chrono::Duration::hours(2));
+    let stale = scheduled_job(&svc, "Stale", now - chrono::Duration::days(3));
+
+    let overdue = svc.overdue_jobs(now, chrono::Duration::hours(1)).unwrap();
+    assert_eq!(overdue.len(), 1);
+    assert_eq!(overdue[0].0.id, stale.id);
+    assert_eq!(overdue[0].1, chrono::Duration::days(2));
+    assert!(overdue.iter().all(|(job, _)| job.id != fresh.id));
+}
+
+#[test]
+fn test_overdue_jobs_ignores_disabled_and_unscheduled() {
+    let svc = setup();
+    let now = Utc::now();
+    let long_ago = now - chrono::Duration::days(30);
+    let mut disabled = scheduled_job(&svc, "Disabled", long_ago);
+    disabled.enabled = false;
+    svc.upsert_job(&disabled).unwrap();
+    let mut paused = scheduled_job(&svc, "Paused schedule", long_ago);
+    paused.schedule.as_mut().unwrap().enabled = false;
+    svc.upsert_job(&paused).unwrap();
+    let mut manual = make_job_definition("Manual only");
+    manual.created_at = long_ago;
+    svc.upsert_job(&manual).unwrap();
+
+    assert!(svc
+        .overdue_jobs(now, chrono::Duration::hours(1))
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_overdue_jobs_sorted_most_overdue_first() {
+    let svc = setup();
+    let now = Utc::now();
+    let a = scheduled_job(&svc, "A", now - chrono::Duration::days(3));
+    let b = scheduled_job(&svc, "B", now - chrono::Duration::days(10));
+
+    let overdue = svc.overdue_jobs(now, chrono::Duration::hours(1)).unwrap();
+    let ids: Vec<Uuid> = overdue.iter().map(|(job, _)| job.id).collect();
+    assert_eq!(ids, vec![b.id, a.id]);
+}
+
+fn ssh_destination(host: &str) -> StorageLocation {
+    StorageLocation::RemoteSsh {
+        user: "backup".to_string(),
+        host: host.to_string(),
+        port: 22,
+        path: "/srv/backups/laptop/".to_string(),
+        identity_file: None,
+    }
+}
+
+fn failed_warnings(checks: &[ValidationCheck], check_type: CheckType) -> usize {
+    checks
+        .iter()
+        .filter(|c| !c.passed && c.severity == CheckSeverity::Warning && c.check_type == check_type)
+        .count()
+}
+
+#[test]
+fn test_retarget_local_job_to_ssh() {
+    let svc = setup();
+    let job = svc.create_job(make_job_definition("Prototype")).unwrap();
+
+    let result = svc
+        .retarget(&job.id, ssh_destination("backup.example.com"))
+        .unwrap();
+
+    assert_eq!(
+        result.job.transfer.destination,
+        ssh_destination("backup.example.com")
+    );
+    assert_eq!(
+        svc.get_job(&job.id).unwrap().transfer.destination,
+        ssh_destination("backup.example.com")
+    );
+    assert_eq!(
+        failed_warnings(&result.checks, CheckType::PreflightScope),
+        1
+    );
+}
+
+#[test]
+fn test_retarget_to_ssh_flags_missing_ssh_config() {
+    let svc = setup();
+    let job = svc.create_job(make_job_definition("Prototype")).unwrap();
+
+    let result = svc
+        .retarget(&job.id, ssh_destination("backup.example.com"))
+        .unwrap();
+
+    assert_eq!(
+        failed_warnings(&result.checks, CheckType::DestinationConfig),
+        1
+    );
+    assert!(result
+        .checks
+        .iter()
+        .any(|c| !c.passed && c.message.contains("no SSH settings")));
+}
+
+#[test]
+fn test_retarget_to_ssh_with_ssh_config_passes() {
+    let svc = setup();
+    let mut def = make_job_definition("Configured");
+    def.ssh_config = Some(SshConfig::default());
+    let job = svc.create_job(def).unwrap();
+
+    let result = svc
+        .retarget(&job.id, ssh_destination("backup.example.com"))
+        .unwrap();
+
+    assert_eq!(
+        failed_warnings(&result.checks, CheckType::DestinationConfig),
+        0
+    );
+}
+
+#[test]
+fn test_retarget_rejects_incomplete_destination() {
+    let svc = setup();
+    let job = svc.create_job(make_job_definition("Prototype")).unwrap();
+
+    match svc.retarget(&job.id, ssh_destination("  ")) {
+        Err(JobServiceError::Validation(checks)) => {
+            assert!(checks
+                .iter()
+                .any(|c| !c.passed && c.message == "Destination host must not be empty"));
+        }
+        other => panic!("expected validation error, got {:?}", other),
+    }
+    assert_eq!(
+        svc.get_job(&job.id).unwrap().transfer.destination,
+        job.transfer.destination
+    );
+}
+
+#[test]
+fn test_retarget_between_local_paths_has_no_scope_warning() {
+    let svc = setup();
+    let job = svc.create_job(make_job_definition("Local")).unwrap();
+
+    let result = svc
+        .retarget(
+            &job.id,
+            StorageLocation::Local {
+                path: "/mnt/other/".to_string(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        failed_warnings(&result.checks, CheckType::PreflightScope),
+        0
+    );
+}
+
+#[test]
+fn test_retarget_forgets_old_snapshots() {
+    let svc = setup();
+    let job = svc.create_job(make_job_definition("Snapshots")).unwrap();
+    let inv = make_invocation(job.id);
+    svc.record_invocation(&inv).unwrap();
+    svc.record_snapshot(&SnapshotRecord {
+        id: Uuid::new_v4(),
+        job_id: job.id,
+        invocation_id: inv.id,
+        snapshot_path: "/dst/2024-01-01_000000".to_string(),
+        link_dest_path: None,
+        created_at: Utc::now(),
+        size_bytes: 1024,
+        file_count: 3,
+        is_latest: true,
+        pinned: false,
+    })
+    .unwrap();
+
+    let result = svc
+        .retarget(&job.id, ssh_destination("backup.example.com"))
+        .unwrap();
+
+    assert_eq!(
+        result.forgotten_snapshots,
+        vec!["/dst/2024-01-01_000000".to_string()]
+    );
+    assert!(svc.get_latest_snapshot(&job.id).unwrap().is_none());
+}
+
+#[test]
+fn test_retarget_missing_job_returns_not_found() {
+    let svc = setup();
+    let result = svc.retarget(&Uuid::new_v4(), ssh_destination("backup.example.com"));
+    assert!(matches!(result, Err(JobServiceError::NotFound(_))));
+}
+
+fn setup_with_db() -> (JobService, Database) {
+    let db = Database::in_memory().unwrap();
+    let conn = db.conn();
+    let svc = JobService::new(
+        Arc::new(SqliteJobRepository::new(conn.clone())),
+        Arc::new(SqliteInvocationRepository::new(conn.clone())),
+        Arc::new(SqliteSnapshotRepository::new(conn)),
+    );
+    (svc, db)
+}
+
+fn job_names(svc: &JobService) -> Vec<String> {
+    svc.list_jobs()
+        .unwrap()
+        .into_iter()
+        .map(|j| j.name)
+        .collect()
+}
+
+#[test]
+fn test_replace_all_swaps_job_set_and_keeps_matching_history() {
+    let svc = setup();
+    let kept = svc.create_job(make_job_definition("Kept")).unwrap();
+    let dropped = svc.create_job(make_job_definition("Dropped")).unwrap();
+    let kept_run = make_invocation(kept.id);
+    svc.record_invocation(&kept_run).unwrap();
+    let dropped_run = make_invocation(dropped.id);
+    svc.record_invocation(&dropped_run).unwrap();
+
+    let mut renamed = kept.clone();
+    renamed.name = "Kept (renamed)".to_string();
+    svc.replace_all(vec![renamed, make_job_definition("New")])
+        .unwrap();
+
+    assert_eq!(job_names(&svc), vec!["Kept (renamed)", "New"]);
+    assert!(svc.get_invocation(&kept_run.id).is_ok());
+    assert!(matches!(
+        svc.get_invocation(&dropped_run.id),
+        Err(JobServiceError::NotFound(_))
+    ));
+}
+
+#[test]
+fn test_replace_all_failure_leaves_original_set() {
+    let (svc, db) = setup_with_db();
+    let a = svc.create_job(make_job_definition("A")).unwrap();
+    svc.create_job(make_job_definition("B")).unwrap();
+    svc.record_invocation(&make_invocation(a.id)).unwrap();
+    // Fail the write of the last job, after the deletes and earlier inserts ran
+    db.conn()
+        .lock()
+        .unwrap()
+        .execute_batch(
+            "CREATE TRIGGER fail_boom BEFORE INSERT ON jobs WHEN NEW.name = 'Boom'
+             BEGIN SELECT RAISE(ABORT, 'boom'); END;",
+        )
+        .unwrap();
+
+    let result = svc.replace_all(vec![make_job_definition("C"), make_job_definition("Boom")]);
+
+    assert!(matches!(result, Err(JobServiceError::Database(_))));
+    assert_eq!(job_names(&svc), vec!["A", "B"]);
+    assert_eq!(svc.get_job_history(&a.id, 10).unwrap().len(), 1);
+}
+
+#[test]
+fn test_replace_all_rejects_invalid_or_duplicate_jobs() {
+    let svc = setup();
+    svc.create_job(make_job_definition("Original")).unwrap();
+
+    let result = svc.replace_all(vec![make_job_definition("  ")]);
+    assert!(matches!(result, Err(JobServiceError::Validation(_))));
+
+    let job = make_job_definition("Twice");
+    let result = svc.replace_all(vec![job.clone(), job]);
+    assert!(matches!(result, Err(JobServiceError::Conflict(_))));
+
+    assert_eq!(job_names(&svc), vec!["Original"]);
+}
+
+fn job_between(svc: &JobService, name: &str, source: &str, destination: StorageLocation) -> Uuid {
+    let mut job = make_job_definition(name);
+    job.transfer.source = StorageLocation::Local {
+        path: source.to_string(),
+    };
+    job.transfer.destination = destination;
+    svc.upsert_job(&job).unwrap();
+    job.id
+}
+
+fn local(path: &str) -> StorageLocation {
+    StorageLocation::Local {
+        path: path.to_string(),
+    }
+}
+
+fn referencing_ids(svc: &JobService, path: &str) -> Vec<Uuid> {
+    svc.jobs_referencing_path(path)
+        .unwrap()
+        .into_iter()
+        .map(|job| job.id)
+        .collect()
+}
+
+#[test]
+fn test_jobs_referencing_path_matches_source() {
+    let svc = setup();
+    let photos = job_between(&svc, "Photos", "/data/photos/", local("/backup/photos/"));
+    job_between(&svc, "Mail", "/data/mail/", local("/backup/mail/"));
+
+    assert_eq!(referencing_ids(&svc, "/data/photos"), vec![photos]);
+}
+
+#[test]
+fn test_jobs_referencing_path_matches_destination() {
+    let svc = setup();
+    let photos = job_between(&svc, "Photos", "/data/photos/", local("/backup/photos/"));
+    job_between(&svc, "Mail", "/data/mail/", local("/archive/mail/"));
+
+    assert_eq!(referencing_ids(&svc, "/backup/photos/"), vec![photos]);
+}
+
+#[test]
+fn test_jobs_referencing_path_matches_children_of_parent() {
+    let svc = setup();
+    let photos = job_between(&svc, "Photos", "/data/photos/", local("/backup/photos/"));
+    let mail = job_between(&svc, "Mail", "/data/./mail/", local("/backup/mail/"));
+
+    let mut ids = referencing_ids(&svc, "/data");
+    ids.sort();
+    let mut expected = vec![photos, mail];
+    expected.sort();
+    assert_eq!(ids, expected);
+    assert_eq!(referencing_ids(&svc, "/data/photos/../mail"), vec![mail]);
+}
+
+#[test]
+fn test_jobs_referencing_path_ignores_unrelated_paths() {
+    let svc = setup();
+    job_between(&svc, "Photos", "/data/photos/", local("/backup/photos/"));
+
+    assert!(referencing_ids(&svc, "/data/photos/2024").is_empty());
+    assert!(referencing_ids(&svc, "/data/pho").is_empty());
+    assert!(referencing_ids(&svc, "/srv").is_empty());
+}
+
+#[test]
+fn test_jobs_referencing_path_skips_remote_locations() {
+    let svc = setup();
+    let remote = StorageLocation::RemoteSsh {
+        user: "backup".to_string(),
+        host: "nas".to_string(),
+        port: 22,
+        path: "/backup/photos/".to_string(),
+        identity_file: None,
+    };
+    job_between(&svc, "Photos", "/data/photos/", remote);
+
+    assert!(referencing_ids(&svc, "/backup").is_empty());
+}
@@ -0,0 +1,232 @@
+use std::cell::RefCell;
+
+use crate::models::job::StorageLocation;
+use crate::rsync_client::RsyncError;
+use crate::error::AppError;
+use crate::services::known_hosts::{
+    confirmed_host_key, fingerprint, known_hosts_host, parse_keyscan_output, scan_host_keys,
+    trust_host_key,
+};
+use crate::ssh_client::SshClient;
+use crate::tests::test_helpers::create_test_job;
+
+const ED25519_KEY: &str = "AAAAC3NzaC1lZDI1NTE5AAAAIPysyzOQ+AMGeNn3vjB9vHlGh9+dtz6nBTqLJPKRSTV1";
+const ED25519_FINGERPRINT: &str = "SHA256:KnOue/HpuGhJfMixNaa4odiG6SZtUkLxj7u08+G2v+k";
+const ECDSA_KEY: &str = "AAAAE2VjZHNhLXNoYTItbmlzdHAyNTYAAAAIbmlzdHAyNTYAAABBBMmNKp7fldt1bhUTmmfwvgR3OecedaWSvbfezoB1L53slstJr+ftMhp0AxreB12tFsBLnloogbOBOSpRao4HCoQ=";
+const ECDSA_FINGERPRINT: &str = "SHA256:9Ub3Yga4BbssSMGrwFhRPIk5YrBfZa53n652Q7hRMmw";
+
+/// Returns canned `ssh-keyscan` output and records the host/port it was asked for.
+struct CannedSsh {
+    output: Result<String, RsyncError>,
+    scanned: RefCell<Option<(String, u16)>>,
+}
+
+impl CannedSsh {
+    fn new(output: &str) -> Self {
+        Self {
+            output: Ok(output.to_string()),
+            scanned: RefCell::new(None),
+        }
+    }
+}
+
+impl SshClient for CannedSsh {
+    fn keyscan(&self, host: &str, port: u16) -> Result<String, RsyncError> {
+        *self.scanned.borrow_mut() = Some((host.to_string(), port));
+        self.output.clone()
+    }
+
+    fn run(&self, _args: &[String]) -> Result<(), RsyncError> {
+        panic!("host key scans never run remote commands");
+    }
+}
+
+fn keyscan_output(host: &str) -> String {
+    format!(
+        "# {host}:22 SSH-2.0-OpenSSH_9.6\n{host} ssh-ed25519 {ED25519_KEY}\n# {host}:22 SSH-2.0-OpenSSH_9.6\n{host} ecdsa-sha2-nistp256 {ECDSA_KEY}\n"
+    )
+}
+
+fn remote_job(port: u16) -> crate::models::job::JobDefinition {
+    let mut job = create_test_job();
+    job.transfer.destination = StorageLocation::RemoteSsh {
+        user: "backup".to_string(),
+        host: "nas.local".to_string(),
+        port,
+        path: "/volume1/backups".to_string(),
+        identity_file: None,
+    };
+    job
+}
+
+#[test]
+fn test_fingerprint_matches_ssh_keygen() {
+    assert_eq!(
+        fingerprint(ED25519_KEY).as_deref(),
+        Some(ED25519_FINGERPRINT)
+    );
+    assert_eq!(fingerprint(ECDSA_KEY).as_deref(), Some(ECDSA_FINGERPRINT));
+}
+
+#[test]
+fn test_fingerprint_rejects_invalid_base64() {
+    assert_eq!(fingerprint("not base64!"), None);
+}
+
+#[test]
+fn test_parse_keyscan_output_skips_comments() {
+    let keys = parse_keyscan_output(&keyscan_output("nas.local"), "nas.local", 22);
+
+    assert_eq!(keys.len(), 2);
+    assert_eq!(keys[0].key_type, "ssh-ed25519");
+    assert_eq!(keys[0].fingerprint, ED25519_FINGERPRINT);
+    assert_eq!(
+        keys[0].known_hosts_line,
+        format!("nas.local ssh-ed25519 {}", ED25519_KEY)
+    );
+    assert_eq!(keys[1].key_type, "ecdsa-sha2-nistp256");
+    assert_eq!(keys[1].fingerprint, ECDSA_FINGERPRINT);
+}
+
+#[test]
+fn test_parse_keyscan_output_skips_malformed_lines() {
+    let output = format!(
+        "nas.local ssh-rsa\nnas.local ssh-ed25519 %%%\nnas.local ssh-ed25519 {ED25519_KEY}\n"
+    );
+    let keys = parse_keyscan_output(&output, "nas.local", 22);
+    assert_eq!(keys.len(), 1);
+    assert_eq!(keys[0].fingerprint, ED25519_FINGERPRINT);
+}
+
+#[test]
+fn test_known_hosts_host_brackets_non_default_port() {
+    assert_eq!(known_hosts_host("nas.local", 22), "nas.local");
+    assert_eq!(known_hosts_host("nas.local", 2222), "[nas.local]:2222");
+}
+
+#[test]
+fn test_scan_host_keys_uses_job_host_and_port() {
+    let ssh = CannedSsh::new(&keyscan_output("[nas.local]:2222"));
+    let keys = scan_host_keys(&remote_job(2222), &ssh).unwrap();
+
+    assert_eq!(*ssh.scanned.borrow(), Some(("nas.local".to_string(), 2222)));
+    assert_eq!(keys.len(), 2);
+    assert!(keys[0]
+        .known_hosts_line
+        .starts_with("[nas.local]:2222 ssh-ed25519 "));
+}
+
+#[test]
+fn test_scan_host_keys_requires_ssh_location() {
+    let ssh = CannedSsh::new(&keyscan_output("nas.local"));
+    assert!(scan_host_keys(&create_test_job(), &ssh).is_err());
+    assert!(ssh.scanned.borrow().is_none());
+}
+
+#[test]
+fn test_scan_host_keys_fails_without_keys() {
+    let ssh = CannedSsh::new("# nas.local:22 SSH-2.0-OpenSSH_9.6\n");
+    assert!(scan_host_keys(&remote_job(22), &ssh).is_err());
+}
+
+#[test]
+fn test_trust_host_key_appends_once() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(".ssh").join("known_hosts");
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(&path, "other.host ssh-ed25519 AAAA").unwrap();
+    let key = parse_keyscan_output(&keyscan_output("nas.local"), "nas.local", 22).remove(0);
+
+    assert!(trust_host_key(&path, &key).unwrap());
+    assert!(!trust_host_key(&path, &key).unwrap());
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(
+        content,
+        format!("other.host ssh-ed25519 AAAA\n{}\n", key.known_hosts_line)
+    );
+}
+
+#[test]
+fn test_trust_host_key_creates_missing_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("ssh").join("known_hosts");
+    let key = parse_keyscan_output(&keyscan_output("nas.local"), "nas.local", 22).remove(0);
+
+    assert!(trust_host_key(&path, &key).unwrap());
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        format!("{}\n", key.known_hosts_line)
+    );
+}
+
+#[test]
+fn test_confirmed_host_key_rescans_for_the_fingerprint() {
+    let ssh = CannedSsh::new(&keyscan_output("[nas.local]:2222"));
+
+    let key = confirmed_host_key("nas.local", 2222, ECDSA_FINGERPRINT, &ssh).unwrap();
+
+    assert_eq!(*ssh.scanned.borrow(), Some(("nas.local".to_string(), 2222)));
+    assert_eq!(
+        key.known_hosts_line,
+        format!("[nas.local]:2222 ecdsa-sha2-nistp256 {}", ECDSA_KEY)
+    );
+}
+
+#[test]
+fn test_confirmed_host_key_fails_for_unknown_fingerprint() {
+    let ssh = CannedSsh::new(&keyscan_output("nas.local"));
+
+    let result = confirmed_host_key("nas.local", 22, "SHA256:changed", &ssh);
+
+    assert!(matches!(result, Err(AppError::ValidationError(_))));
+}
+
+#[test]
+fn test_confirmed_host_key_rejects_hosts_with_whitespace() {
+    let ssh = CannedSsh::new(&keyscan_output("nas.local"));
+
+    for host in ["nas.local\nevil.host", "nas local", ""] {
+        let result = confirmed_host_key(host, 22, ED25519_FINGERPRINT, &ssh);
+        assert!(
+            matches!(result, Err(AppError::ValidationError(_))),
+            "{:?}",
+            host
+        );
+    }
+    assert!(ssh.scanned.borrow().is_none());
+}
+
+#[test]
+fn test_host_starting_with_dash_is_never_scanned() {
+    let ssh = CannedSsh::new(&keyscan_output("nas.local"));
+    let mut job = remote_job(22);
+    if let StorageLocation::RemoteSsh { host, .. } = &mut job.transfer.destination {
+        *host = "-oProxyCommand=touch /tmp/pwned".to_string();
+    }
+
+    assert!(matches!(
+        scan_host_keys(&job, &ssh),
+        Err(AppError::ValidationError(_))
+    ));
+    assert!(matches!(
+        confirmed_host_key("-v", 22, ED25519_FINGERPRINT, &ssh),
+        Err(AppError::ValidationError(_))
+    ));
+    assert!(ssh.scanned.borrow().is_none());
+}
+
+#[test]
+fn test_trust_host_key_rejects_multi_line_entries() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("known_hosts");
+    let mut key = parse_keyscan_output(&keyscan_output("nas.local"), "nas.local", 22).remove(0);
+    key.known_hosts_line
+        .push_str("\nevil.host ssh-ed25519 AAAA");
+
+    assert!(matches!(
+        trust_host_key(&path, &key),
+        Err(AppError::ValidationError(_))
+    ));
+    assert!(!path.exists());
+}
@@ -0,0 +1,147 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::database::sqlite::Database;
+use crate::models::backup::{
+    BackupInvocation, ExecutionOutput, InvocationStatus, InvocationTrigger, TransferStats,
+};
+use crate::repository::sqlite::invocation::SqliteInvocationRepository;
+use crate::repository::sqlite::job::SqliteJobRepository;
+use crate::repository::sqlite::snapshot::SqliteSnapshotRepository;
+use crate::services::job_service::JobService;
+use crate::services::log_follow::{running_invocation, LogFollower};
+
+fn append(path: &Path, text: &str) {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .unwrap();
+    file.write_all(text.as_bytes()).unwrap();
+}
+
+#[test]
+fn test_reads_only_newly_appended_lines() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("run.log");
+    append(&path, "first\nsecond\n");
+
+    let mut follower = LogFollower::new(&path);
+    assert_eq!(follower.read_new_lines().unwrap(), vec!["first", "second"]);
+    assert!(follower.read_new_lines().unwrap().is_empty());
+
+    append(&path, "third\n");
+    assert_eq!(follower.read_new_lines().unwrap(), vec!["third"]);
+}
+
+#[test]
+fn test_idle_time_restarts_when_the_log_grows() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("run.log");
+    let mut follower = LogFollower::new(&path);
+    let pause = std::time::Duration::from_millis(50);
+
+    std::thread::sleep(pause);
+    follower.read_new_lines().unwrap();
+    assert!(follower.idle_for() >= pause);
+
+    append(&path, "partial");
+    follower.read_new_lines().unwrap();
+    assert!(follower.idle_for() < pause);
+}
+
+#[test]
+fn test_holds_back_unterminated_line() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("run.log");
+    append(&path, "done\nhalf");
+
+    let mut follower = LogFollower::new(&path);
+    assert_eq!(follower.read_new_lines().unwrap(), vec!["done"]);
+
+    append(&path, " a line\nnext");
+    assert_eq!(follower.read_new_lines().unwrap(), vec!["half a line"]);
+    assert_eq!(follower.finish().as_deref(), Some("next"));
+    assert_eq!(follower.finish(), None);
+}
+
+#[test]
+fn test_missing_file_yields_nothing_until_created() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("run.log");
+
+    let mut follower = LogFollower::new(&path);
+    assert!(follower.read_new_lines().unwrap().is_empty());
+
+    append(&path, "started\n");
+    assert_eq!(follower.read_new_lines().unwrap(), vec!["started"]);
+}
+
+#[test]
+fn test_rotated_file_is_read_from_start() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("run.log");
+    append(&path, "a long line before rotation\n");
+
+    let mut follower = LogFollower::new(&path);
+    follower.read_new_lines().unwrap();
+
+    std::fs::rename(&path, tmp.path().join("run.log.1")).unwrap();
+    append(&path, "fresh\n");
+    assert_eq!(follower.read_new_lines().unwrap(), vec!["fresh"]);
+}
+
+fn make_invocation(job_id: Uuid, minutes_ago: i64, status: InvocationStatus) -> BackupInvocation {
+    BackupInvocation {
+        id: Uuid::new_v4(),
+        job_id,
+        started_at: Utc::now() - Duration::minutes(minutes_ago),
+        finished_at: None,
+        status,
+        trigger: InvocationTrigger::Scheduled,
+        rerun_of: None,
+        resumed_from: None,
+        warning: None,
+        transfer_stats: TransferStats::default(),
+        execution_output: ExecutionOutput {
+            command_executed: "rsync -a /src /dst".to_string(),
+            exit_code: None,
+            snapshot_path: None,
+            log_file_path: Some("/logs/run.log".to_string()),
+        },
+    }
+}
+
+#[test]
+fn test_running_invocation_is_latest_running_run() {
+    let db = Database::in_memory().unwrap();
+    let conn = db.conn();
+    let job_service = JobService::new(
+        Arc::new(SqliteJobRepository::new(conn.clone())),
+        Arc::new(SqliteInvocationRepository::new(conn.clone())),
+        Arc::new(SqliteSnapshotRepository::new(conn)),
+    );
+    let job_id = job_service
+        .create_job(crate::tests::test_helpers::create_test_job())
+        .unwrap()
+        .id;
+    assert_eq!(running_invocation(&job_service, &job_id).unwrap(), None);
+
+    let finished = make_invocation(job_id, 30, InvocationStatus::Succeeded);
+    job_service.record_invocation(&finished).unwrap();
+    assert_eq!(running_invocation(&job_service, &job_id).unwrap(), None);
+
+    let running = make_invocation(job_id, 1, InvocationStatus::Running);
+    job_service.record_invocation(&running).unwrap();
+    assert_eq!(
+        running_invocation(&job_service, &job_id)
+            .unwrap()
+            .map(|inv| inv.id),
+        Some(running.id)
+    );
+}
@@ -0,0 +1,267 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::database::sqlite::Database;
+use crate::file_system::FileSystem;
+use crate::models::backup::{
+    BackupInvocation, ExecutionOutput, InvocationStatus, InvocationTrigger, TransferStats,
+};
+use crate::repository::sqlite::invocation::SqliteInvocationRepository;
+use crate::repository::sqlite::job::SqliteJobRepository;
+use crate::repository::sqlite::settings::SqliteSettingsRepository;
+use crate::repository::sqlite::snapshot::SqliteSnapshotRepository;
+use crate::services::job_service::JobService;
+use crate::services::log_relocation::relocate_logs;
+use crate::services::settings_service::SettingsService;
+use crate::tests::test_file_system::TestFileSystem;
+
+const DEFAULT_DIR: &str = "/data/logs";
+
+fn setup_services() -> (JobService, SettingsService) {
+    let db = Database::in_memory().unwrap();
+    let conn = db.conn();
+    (
+        JobService::new(
+            Arc::new(SqliteJobRepository::new(conn.clone())),
+            Arc::new(SqliteInvocationRepository::new(conn.clone())),
+            Arc::new(SqliteSnapshotRepository::new(conn.clone())),
+        ),
+        SettingsService::new(Arc::new(SqliteSettingsRepository::new(conn))),
+    )
+}
+
+fn record_invocation(job_service: &JobService, job_id: Uuid, log_file_path: Option<&str>) -> Uuid {
+    let inv = BackupInvocation {
+        id: Uuid::new_v4(),
+        job_id,
+        started_at: Utc::now(),
+        finished_at: Some(Utc::now()),
+        status: InvocationStatus::Succeeded,
+        trigger: InvocationTrigger::Manual,
+        rerun_of: None,
+        resumed_from: None,
+        warning: None,
+        transfer_stats: TransferStats::default(),
+        execution_output: ExecutionOutput {
+            command_executed: "rsync -a /src /dst".to_string(),
+            exit_code: Some(0),
+            snapshot_path: None,
+            log_file_path: log_file_path.map(String::from),
+        },
+    };
+    job_service.record_invocation(&inv).unwrap();
+    inv.id
+}
+
+fn log_path_of(job_service: &JobService, id: &Uuid) -> Option<String> {
+    job_service
+        .get_invocation(id)
+        .unwrap()
+        .execution_output
+        .log_file_path
+}
+
+fn create_job(job_service: &JobService) -> Uuid {
+    job_service
+        .create_job(crate::tests::test_helpers::create_test_job())
+        .unwrap()
+        .id
+}
+
+#[test]
+fn test_relocate_moves_files_and_updates_records() {
+    let (jobs, settings) = setup_services();
+    let job_id = create_job(&jobs);
+    let a = record_invocation(&jobs, job_id, Some("/data/logs/a.log"));
+    let b = record_invocation(&jobs, job_id, Some("/data/logs/nightly/b.log"));
+    let fs = TestFileSystem::new()
+        .with_file("/data/logs/a.log", "log a")
+        .with_file("/data/logs/nightly/b.log", "log b");
+
+    let result = relocate_logs(&fs, &jobs, &settings, DEFAULT_DIR, "/archive/logs").unwrap();
+
+    assert_eq!(result.moved, 2);
+    assert_eq!(result.missing, 0);
+    assert_eq!(result.updated, 2);
+    assert!(!fs.exists(Path::new("/data/logs/a.log")));
+    assert_eq!(
+        fs.file_content("/archive/logs/a.log").as_deref(),
+        Some("log a")
+    );
+    assert_eq!(
+        fs.file_content("/archive/logs/nightly/b.log").as_deref(),
+        Some("log b")
+    );
+    assert_eq!(
+        log_path_of(&jobs, &a).as_deref(),
+        Some("/archive/logs/a.log")
+    );
+    assert_eq!(
+        log_path_of(&jobs, &b).as_deref(),
+        Some("/archive/logs/nightly/b.log")
+    );
+    assert_eq!(
+        settings.get_log_directory().unwrap().as_deref(),
+        Some("/archive/logs")
+    );
+}
+
+#[test]
+fn test_relocate_uses_configured_directory_over_default() {
+    let (jobs, settings) = setup_services();
+    settings.set_log_directory("/custom").unwrap();
+    let job_id = create_job(&jobs);
+    let id = record_invocation(&jobs, job_id, Some("/custom/a.log"));
+    let fs = TestFileSystem::new().with_file("/custom/a.log", "log");
+
+    relocate_logs(&fs, &jobs, &settings, DEFAULT_DIR, "/moved").unwrap();
+
+    assert!(fs.exists(Path::new("/moved/a.log")));
+    assert_eq!(log_path_of(&jobs, &id).as_deref(), Some("/moved/a.log"));
+}
+
+#[test]
+fn test_relocate_moves_rotated_siblings() {
+    let (jobs, settings) = setup_services();
+    let job_id = create_job(&jobs);
+    record_invocation(&jobs, job_id, Some("/data/logs/a.log"));
+    let fs = TestFileSystem::new()
+        .with_file("/data/logs/a.log", "current")
+        .with_file("/data/logs/a.log.1", "older")
+        .with_file("/data/logs/a.log.2", "oldest");
+
+    relocate_logs(&fs, &jobs, &settings, DEFAULT_DIR, "/new").unwrap();
+
+    assert_eq!(
+        fs.files_under("/new"),
+        vec![
+            Path::new("/new/a.log").to_path_buf(),
+            Path::new("/new/a.log.1").to_path_buf(),
+            Path::new("/new/a.log.2").to_path_buf(),
+        ]
+    );
+    assert!(fs.files_under("/data/logs").is_empty());
+}
+
+#[test]
+fn test_relocate_updates_record_when_log_missing() {
+    let (jobs, settings) = setup_services();
+    let job_id = create_job(&jobs);
+    let id = record_invocation(&jobs, job_id, Some("/data/logs/gone.log"));
+    let fs = TestFileSystem::new().with_dir("/data/logs");
+
+    let result = relocate_logs(&fs, &jobs, &settings, DEFAULT_DIR, "/new").unwrap();
+
+    assert_eq!(result.moved, 0);
+    assert_eq!(result.missing, 1);
+    assert_eq!(result.updated, 1);
+    assert_eq!(log_path_of(&jobs, &id).as_deref(), Some("/new/gone.log"));
+}
+
+#[test]
+fn test_relocate_skips_logs_outside_old_directory() {
+    let (jobs, settings) = setup_services();
+    let job_id = create_job(&jobs);
+    let outside = record_invocation(&jobs, job_id, Some("/elsewhere/a.log"));
+    let no_log = record_invocation(&jobs, job_id, None);
+    let fs = TestFileSystem::new().with_file("/elsewhere/a.log", "log");
+
+    let result = relocate_logs(&fs, &jobs, &settings, DEFAULT_DIR, "/new").unwrap();
+
+    assert_eq!(result.updated, 0);
+    assert!(fs.exists(Path::new("/elsewhere/a.log")));
+    assert_eq!(
+        log_path_of(&jobs, &outside).as_deref(),
+        Some("/elsewhere/a.log")
+    );
+    assert_eq!(log_path_of(&jobs, &no_log), None);
+}
+
+#[test]
+fn test_relocate_moves_shared_log_once() {
+    let (jobs, settings) = setup_services();
+    let job_id = create_job(&jobs);
+    let a = record_invocation(&jobs, job_id, Some("/data/logs/job.log"));
+    let b = record_invocation(&jobs, job_id, Some("/data/logs/job.log"));
+    let fs = TestFileSystem::new().with_file("/data/logs/job.log", "shared");
+
+    let result = relocate_logs(&fs, &jobs, &settings, DEFAULT_DIR, "/new").unwrap();
+
+    assert_eq!(result.moved, 1);
+    assert_eq!(result.updated, 2);
+    assert_eq!(log_path_of(&jobs, &a).as_deref(), Some("/new/job.log"));
+    assert_eq!(log_path_of(&jobs, &b).as_deref(), Some("/new/job.log"));
+}
+
+#[test]
+fn test_relocate_move_failure_rolls_back() {
+    let (jobs, settings) = setup_services();
+    settings.set_log_directory("/data/logs").unwrap();
+    let job_id = create_job(&jobs);
+    let a = record_invocation(&jobs, job_id, Some("/data/logs/a.log"));
+    // Recorded later, so listed (and moved) before `a`
+    let b = record_invocation(&jobs, job_id, Some("/data/logs/b.log"));
+    let fs = TestFileSystem::new()
+        .with_file("/data/logs/a.log", "log a")
+        .with_file("/data/logs/b.log", "log b")
+        .with_read_only_dir("/new/a.log");
+
+    let result = relocate_logs(&fs, &jobs, &settings, DEFAULT_DIR, "/new");
+
+    assert!(result.is_err());
+    assert_eq!(
+        settings.get_log_directory().unwrap().as_deref(),
+        Some("/data/logs")
+    );
+    assert_eq!(
+        fs.file_content("/data/logs/a.log").as_deref(),
+        Some("log a")
+    );
+    assert_eq!(
+        fs.file_content("/data/logs/b.log").as_deref(),
+        Some("log b")
+    );
+    assert!(fs.files_under("/new").is_empty());
+    assert_eq!(log_path_of(&jobs, &a).as_deref(), Some("/data/logs/a.log"));
+    assert_eq!(log_path_of(&jobs, &b).as_deref(), Some("/data/logs/b.log"));
+}
+
+#[test]
+fn test_relocate_failure_restores_unset_directory() {
+    let (jobs, settings) = setup_services();
+    let job_id = create_job(&jobs);
+    record_invocation(&jobs, job_id, Some("/data/logs/a.log"));
+    let fs = TestFileSystem::new()
+        .with_file("/data/logs/a.log", "log a")
+        .with_read_only_dir("/readonly");
+
+    assert!(relocate_logs(&fs, &jobs, &settings, DEFAULT_DIR, "/readonly").is_err());
+    assert_eq!(settings.get_log_directory().unwrap(), None);
+}
+
+#[test]
+fn test_relocate_to_same_directory_is_noop() {
+    let (jobs, settings) = setup_services();
+    let job_id = create_job(&jobs);
+    let id = record_invocation(&jobs, job_id, Some("/data/logs/a.log"));
+    let fs = TestFileSystem::new().with_file("/data/logs/a.log", "log");
+
+    let result = relocate_logs(&fs, &jobs, &settings, DEFAULT_DIR, "/data/logs").unwrap();
+
+    assert_eq!(result.updated, 0);
+    assert_eq!(log_path_of(&jobs, &id).as_deref(), Some("/data/logs/a.log"));
+    assert_eq!(
+        settings.get_log_directory().unwrap().as_deref(),
+        Some("/data/logs")
+    );
+}
+
+#[test]
+fn test_relocate_rejects_empty_directory() {
+    let (jobs, settings) = setup_services();
+    let fs = TestFileSystem::new();
+    assert!(relocate_logs(&fs, &jobs, &settings, DEFAULT_DIR, "  ").is_err());
+}
@@ -0,0 +1,295 @@
+use chrono::{TimeZone, Utc};
+use uuid::Uuid;
+
+use crate::models::backup::{
+    BackupInvocation, ExecutionOutput, InvocationStatus, InvocationTrigger, TransferStats,
+};
+use crate::models::log::StructuredLogRecord;
+use crate::models::progress::ProgressUpdate;
+use crate::models::settings::LogFileSettings;
+use crate::services::log_writer::{
+    ensure_log_parent, expand_log_template, log_file_set, log_path_for, rotated_log_paths,
+    rsync_log_args, rsync_log_path, structured_log_path, RotatingLogWriter, StructuredLogWriter,
+    LOG_FLUSH_INTERVAL,
+};
+use crate::tests::test_helpers::create_mirror_job;
+
+fn make_invocation() -> BackupInvocation {
+    BackupInvocation {
+        id: Uuid::parse_str("00000000-0000-0000-0000-000000000042").unwrap(),
+        job_id: Uuid::new_v4(),
+        started_at: Utc.with_ymd_and_hms(2025, 6, 15, 14, 0, 0).unwrap(),
+        finished_at: None,
+        status: InvocationStatus::Running,
+        trigger: InvocationTrigger::Manual,
+        rerun_of: None,
+        resumed_from: None,
+        warning: None,
+        transfer_stats: TransferStats::default(),
+        execution_output: ExecutionOutput {
+            command_executed: String::new(),
+            exit_code: None,
+            snapshot_path: None,
+            log_file_path: None,
+        },
+    }
+}
+
+fn template(t: &str) -> LogFileSettings {
+    LogFileSettings {
+        path_template: t.to_string(),
+        max_size_bytes: None,
+        structured: false,
+        ..LogFileSettings::default()
+    }
+}
+
+#[test]
+fn test_expand_all_placeholders() {
+    let mut job = create_mirror_job("/src/", "/dst/");
+    job.name = "Nightly".to_string();
+    let inv = make_invocation();
+
+    let expanded = expand_log_template("{job}/{date}-{id}.log", &job, &inv);
+
+    assert_eq!(
+        expanded,
+        "Nightly/2025-06-15-00000000-0000-0000-0000-000000000042.log"
+    );
+}
+
+#[test]
+fn test_default_template_matches_legacy_layout() {
+    let job = create_mirror_job("/src/", "/dst/");
+    let inv = make_invocation();
+
+    let path = log_path_for("/var/logs", &job, &inv, &LogFileSettings::default());
+
+    assert_eq!(
+        path.to_str().unwrap(),
+        "/var/logs/00000000-0000-0000-0000-000000000042.log"
+    );
+}
+
+#[test]
+fn test_job_name_is_sanitized() {
+    let mut job = create_mirror_job("/src/", "/dst/");
+    job.name = "Photos/2025: NAS".to_string();
+    let inv = make_invocation();
+
+    let path = log_path_for("/logs", &job, &inv, &template("{job}/{date}.log"));
+
+    assert_eq!(
+        path.to_str().unwrap(),
+        "/logs/Photos_2025_ NAS/2025-06-15.log"
+    );
+}
+
+#[test]
+fn test_template_cannot_escape_log_dir() {
+    let job = create_mirror_job("/src/", "/dst/");
+    let inv = make_invocation();
+
+    let path = log_path_for("/logs", &job, &inv, &template("/../../etc/{date}.log"));
+
+    assert_eq!(path.to_str().unwrap(), "/logs/etc/2025-06-15.log");
+}
+
+#[test]
+fn test_ensure_log_parent_creates_job_directory() {
+    let tmp = tempfile::tempdir().unwrap();
+    let mut job = create_mirror_job("/src/", "/dst/");
+    job.name = "Nightly".to_string();
+    let inv = make_invocation();
+
+    let path = log_path_for(
+        tmp.path().to_str().unwrap(),
+        &job,
+        &inv,
+        &template("{job}/{date}.log"),
+    );
+    ensure_log_parent(&path).unwrap();
+
+    assert!(tmp.path().join("Nightly").is_dir());
+}
+
+#[test]
+fn test_writer_appends_to_existing_file() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("shared.log");
+    std::fs::write(&path, "first\n").unwrap();
+
+    let mut writer = RotatingLogWriter::open(&path, None).unwrap();
+    writer.write_line("second").unwrap();
+    writer.flush().unwrap();
+
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "first\nsecond\n");
+}
+
+#[test]
+fn test_writer_buffers_lines_until_flush_is_due() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("job.log");
+
+    let mut writer = RotatingLogWriter::open(&path, None).unwrap();
+    writer.write_line("first").unwrap();
+    writer.flush_if_due().unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "");
+
+    std::thread::sleep(LOG_FLUSH_INTERVAL);
+    writer.flush_if_due().unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "first\n");
+}
+
+#[test]
+fn test_writer_flushes_with_the_line_once_due() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("job.log");
+
+    let mut writer = RotatingLogWriter::open(&path, None).unwrap();
+    std::thread::sleep(LOG_FLUSH_INTERVAL);
+    writer.write_line("late").unwrap();
+
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "late\n");
+}
+
+#[test]
+fn test_writer_rotates_when_size_exceeded() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("job.log");
+
+    let mut writer = RotatingLogWriter::open(&path, Some(12)).unwrap();
+    writer.write_line("aaaaa").unwrap();
+    writer.write_line("bbbbb").unwrap();
+    writer.write_line("ccccc").unwrap();
+    writer.flush().unwrap();
+
+    let rotated = rotated_log_paths(path.to_str().unwrap());
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "ccccc\n");
+    assert_eq!(
+        std::fs::read_to_string(&rotated[0]).unwrap(),
+        "aaaaa\nbbbbb\n"
+    );
+}
+
+#[test]
+fn test_writer_shifts_older_rotations() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("job.log");
+
+    let mut writer = RotatingLogWriter::open(&path, Some(6)).unwrap();
+    for line in ["one..", "two..", "three"] {
+        writer.write_line(line).unwrap();
+    }
+    writer.flush().unwrap();
+
+    let rotated = rotated_log_paths(path.to_str().unwrap());
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "three\n");
+    assert_eq!(std::fs::read_to_string(&rotated[0]).unwrap(), "two..\n");
+    assert_eq!(std::fs::read_to_string(&rotated[1]).unwrap(), "one..\n");
+}
+
+#[test]
+fn test_structured_log_path_replaces_log_extension() {
+    assert_eq!(structured_log_path("/logs/abc.log"), "/logs/abc.jsonl");
+    assert_eq!(
+        structured_log_path("/logs/Nightly/2025-01-01"),
+        "/logs/Nightly/2025-01-01.jsonl"
+    );
+}
+
+#[test]
+fn test_rsync_log_path_sits_next_to_log() {
+    assert_eq!(rsync_log_path("/logs/abc.log"), "/logs/abc.rsync.log");
+    assert_eq!(
+        rsync_log_path("/logs/Nightly/2025-01-01"),
+        "/logs/Nightly/2025-01-01.rsync.log"
+    );
+}
+
+#[test]
+fn test_rsync_log_args_off_by_default() {
+    assert!(rsync_log_args("/logs/abc.log", &LogFileSettings::default()).is_empty());
+}
+
+#[test]
+fn test_rsync_log_args_use_log_path_and_configured_format() {
+    let settings = LogFileSettings {
+        rsync_log_file: true,
+        rsync_log_format: "%t %o %f %l".to_string(),
+        ..LogFileSettings::default()
+    };
+    assert_eq!(
+        rsync_log_args("/logs/Nightly/abc.log", &settings),
+        vec![
+            "--log-file=/logs/Nightly/abc.rsync.log".to_string(),
+            "--log-file-format=%t %o %f %l".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_log_file_set_includes_rsync_log() {
+    let files = log_file_set("/logs/abc.log");
+    assert!(files.contains(&"/logs/abc.jsonl".to_string()));
+    assert!(files.contains(&"/logs/abc.rsync.log".to_string()));
+}
+
+#[test]
+fn test_structured_writer_records_read_back() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("job.jsonl");
+    let timestamp = Utc.with_ymd_and_hms(2025, 6, 15, 14, 0, 0).unwrap();
+    let progress = ProgressUpdate {
+        invocation_id: Uuid::new_v4(),
+        bytes_transferred: 32_768,
+        percentage: 50.0,
+        transfer_rate: "1.00MB/s".to_string(),
+        elapsed: "0:00:01".to_string(),
+        files_transferred: 1,
+        files_remaining: 3,
+        files_total: 4,
+        eta_secs: Some(2),
+        current_file: Some("docs/report.pdf".to_string()),
+    };
+    let records = vec![
+        StructuredLogRecord::Stdout {
+            timestamp,
+            message: "docs/report.pdf".to_string(),
+        },
+        StructuredLogRecord::Progress {
+            timestamp,
+            progress,
+        },
+        StructuredLogRecord::Stdout {
+            timestamp,
+            message: "sent 32,768 bytes  received 35 bytes".to_string(),
+        },
+        StructuredLogRecord::Stderr {
+            timestamp,
+            message: "rsync: link_stat \"/src/missing\" failed".to_string(),
+        },
+    ];
+
+    let mut writer = StructuredLogWriter::open(&path).unwrap();
+    for record in &records {
+        writer.write_record(record).unwrap();
+    }
+    writer.flush().unwrap();
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    let lines: Vec<&str> = content.lines().collect();
+    assert_eq!(lines.len(), 4);
+    let first: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+    assert_eq!(first["stream"], "stdout");
+    assert_eq!(first["message"], "docs/report.pdf");
+    let progress: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+    assert_eq!(progress["stream"], "progress");
+    assert_eq!(progress["progress"]["percentage"], 50.0);
+
+    let read: Vec<StructuredLogRecord> = lines
+        .iter()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(read, records);
+}
@@ -0,0 +1,197 @@
+use std::sync::Arc;
+
+use chrono::{Duration, TimeZone, Utc};
+use uuid::Uuid;
+
+use crate::database::sqlite::Database;
+use crate::models::backup::{
+    BackupInvocation, ExecutionOutput, InvocationStatus, InvocationTrigger, TransferStats,
+};
+use crate::models::job::JobDefinition;
+use crate::repository::sqlite::invocation::SqliteInvocationRepository;
+use crate::repository::sqlite::job::SqliteJobRepository;
+use crate::repository::sqlite::snapshot::SqliteSnapshotRepository;
+use crate::repository::sqlite::statistics::SqliteStatisticsRepository;
+use crate::services::job_service::JobService;
+use crate::services::metrics::render_metrics;
+use crate::services::statistics_service::StatisticsService;
+use crate::tests::test_helpers::create_test_job;
+
+fn setup() -> (JobService, StatisticsService) {
+    let db = Database::in_memory().unwrap();
+    let conn = db.conn();
+    (
+        JobService::new(
+            Arc::new(SqliteJobRepository::new(conn.clone())),
+            Arc::new(SqliteInvocationRepository::new(conn.clone())),
+            Arc::new(SqliteSnapshotRepository::new(conn.clone())),
+        ),
+        StatisticsService::new(Arc::new(SqliteStatisticsRepository::new(conn))),
+    )
+}
+
+fn add_job(jobs: &JobService, name: &str) -> JobDefinition {
+    let mut job = create_test_job();
+    job.name = name.to_string();
+    jobs.create_job(job).unwrap()
+}
+
+fn record_run(
+    jobs: &JobService,
+    stats: &StatisticsService,
+    job_id: Uuid,
+    status: InvocationStatus,
+    finished_secs: i64,
+    bytes: u64,
+) {
+    let finished = Utc.timestamp_opt(finished_secs, 0).unwrap();
+    let inv = BackupInvocation {
+        id: Uuid::new_v4(),
+        job_id,
+        started_at: finished - Duration::seconds(60),
+        finished_at: Some(finished),
+        status: status.clone(),
+        trigger: InvocationTrigger::Scheduled,
+        rerun_of: None,
+        resumed_from: None,
+        warning: None,
+        transfer_stats: TransferStats {
+            bytes_transferred: bytes,
+            files_transferred: 1,
+            total_files: 1,
+            files_deleted: 0,
+            detailed: None,
+        },
+        execution_output: ExecutionOutput {
+            command_executed: "rsync -a /src/ /dst/".to_string(),
+            exit_code: Some(if status == InvocationStatus::Succeeded {
+                0
+            } else {
+                23
+            }),
+            snapshot_path: None,
+            log_file_path: None,
+        },
+    };
+    jobs.record_invocation(&inv).unwrap();
+    if status == InvocationStatus::Succeeded {
+        stats.record(job_id, &inv, None).unwrap();
+    }
+}
+
+fn labels(job: &JobDefinition) -> String {
+    format!("job_id=\"{}\",job=\"{}\"", job.id, job.name)
+}
+
+#[test]
+fn test_render_includes_all_metric_families() {
+    let (jobs, stats) = setup();
+    add_job(&jobs, "Photos");
+
+    let text = render_metrics(&jobs, &stats).unwrap();
+
+    for name in [
+        "rsync_studio_jobs",
+        "rsync_studio_job_last_run_timestamp_seconds",
+        "rsync_studio_job_last_run_success",
+        "rsync_studio_job_bytes_transferred",
+        "rsync_studio_job_recorded_failures",
+    ] {
+        assert!(
+            text.contains(&format!("# TYPE {} ", name)),
+            "missing {}",
+            name
+        );
+    }
+    assert!(text.contains("\nrsync_studio_jobs 1\n"));
+    // Counted from retained history, so it can drop when old runs are pruned
+    assert!(text.contains("# TYPE rsync_studio_job_recorded_failures gauge\n"));
+    // Statistics can be reset, so this is not a counter either
+    assert!(text.contains("# TYPE rsync_studio_job_bytes_transferred gauge\n"));
+}
+
+#[test]
+fn test_render_labels_each_job() {
+    let (jobs, stats) = setup();
+    let photos = add_job(&jobs, "Photos");
+    let docs = add_job(&jobs, "Documents");
+    record_run(
+        &jobs,
+        &stats,
+        photos.id,
+        InvocationStatus::Succeeded,
+        1_700_000_000,
+        1000,
+    );
+    record_run(
+        &jobs,
+        &stats,
+        photos.id,
+        InvocationStatus::Succeeded,
+        1_700_086_400,
+        500,
+    );
+    record_run(
+        &jobs,
+        &stats,
+        docs.id,
+        InvocationStatus::Succeeded,
+        1_700_000_000,
+        10,
+    );
+    record_run(
+        &jobs,
+        &stats,
+        docs.id,
+        InvocationStatus::Failed,
+        1_700_090_000,
+        0,
+    );
+
+    let text = render_metrics(&jobs, &stats).unwrap();
+
+    let p = labels(&photos);
+    let d = labels(&docs);
+    assert!(text.contains("\nrsync_studio_jobs 2\n"));
+    assert!(text.contains(&format!(
+        "rsync_studio_job_last_run_timestamp_seconds{{{}}} 1700086400",
+        p
+    )));
+    assert!(text.contains(&format!("rsync_studio_job_last_run_success{{{}}} 1", p)));
+    assert!(text.contains(&format!("rsync_studio_job_last_run_success{{{}}} 0", d)));
+    assert!(text.contains(&format!(
+        "rsync_studio_job_bytes_transferred{{{}}} 1500",
+        p
+    )));
+    assert!(text.contains(&format!("rsync_studio_job_recorded_failures{{{}}} 0", p)));
+    assert!(text.contains(&format!("rsync_studio_job_recorded_failures{{{}}} 1", d)));
+}
+
+#[test]
+fn test_render_omits_last_run_for_never_run_job() {
+    let (jobs, stats) = setup();
+    let job = add_job(&jobs, "Fresh");
+
+    let text = render_metrics(&jobs, &stats).unwrap();
+
+    let l = labels(&job);
+    assert!(!text.contains(&format!("rsync_studio_job_last_run_success{{{}}}", l)));
+    assert!(text.contains(&format!("rsync_studio_job_recorded_failures{{{}}} 0", l)));
+    assert!(text.contains(&format!(
+        "rsync_studio_job_bytes_transferred{{{}}} 0",
+        l
+    )));
+}
+
+#[test]
+fn test_render_escapes_label_values() {
+    let (jobs, stats) = setup();
+    let job = add_job(&jobs, "Say \"hi\" \\ bye");
+
+    let text = render_metrics(&jobs, &stats).unwrap();
+
+    assert!(text.contains(&format!(
+        "rsync_studio_job_recorded_failures{{job_id=\"{}\",job=\"Say \\\"hi\\\" \\\\ bye\"}} 0",
+        job.id
+    )));
+}
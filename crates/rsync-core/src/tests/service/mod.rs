@@ -1,8 +1,52 @@
+mod audit_log_tests;
+mod backup_set_tests;
+mod bandwidth_schedule_tests;
+mod change_alarm_tests;
+mod daemon_auth_tests;
+mod destination_prep_tests;
+mod destination_usage_tests;
+mod dashboard_tests;
+mod data_budget_tests;
+mod export_import_full_tests;
+mod history_export_tests;
+mod invocation_report_tests;
 mod itemize_parser_tests;
+mod itemize_recorder_tests;
+mod job_duplicates_tests;
+#[cfg(unix)]
+mod job_executor_tests;
+mod job_runner_tests;
 mod job_service_integration_tests;
+mod known_hosts_tests;
+mod log_follow_tests;
+mod log_relocation_tests;
 mod log_scrubber_tests;
+mod log_writer_tests;
+mod metrics_tests;
+mod notifier_tests;
+mod orphaned_logs_tests;
+mod parallel_split_tests;
 mod progress_statistics_tests;
+mod prune_recovery_tests;
+mod quiet_hours_tests;
+#[cfg(unix)]
+mod rerun_tests;
+mod restore_tests;
+mod retry_tests;
 mod retention_runner_tests;
+mod run_conditions_tests;
 mod running_jobs_tests;
+mod runtime_limit_tests;
+mod scheduler_events_tests;
 mod settings_service_tests;
+mod snapshot_diff_tests;
+mod snapshot_resume_tests;
+mod snapshot_trash_tests;
+mod snapshot_verify_tests;
+mod source_change_tests;
+mod startup_runner_tests;
 mod statistics_service_tests;
+mod status_tests;
+mod test_mode_tests;
+mod throttled_handler_tests;
+mod transfer_estimate_tests;
@@ -0,0 +1,213 @@
+use std::sync::Mutex;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::database::sqlite::Database;
+use crate::models::backup::{InvocationStatus, SnapshotVerification};
+use crate::models::job::{ChangeAlarm, NotifyOn};
+use crate::models::progress::JobNotification;
+use crate::repository::job::JobRepository;
+use crate::repository::sqlite::job::SqliteJobRepository;
+use crate::services::notifier::{
+    notify_change_alarm, notify_finished, notify_verification_failed, should_notify, Notifier,
+};
+use crate::tests::test_helpers::create_test_job;
+
+/// Collects every notification it is asked to send.
+#[derive(Default)]
+struct MockNotifier {
+    sent: Mutex<Vec<JobNotification>>,
+}
+
+impl Notifier for MockNotifier {
+    fn notify(&self, notification: &JobNotification) {
+        self.sent.lock().unwrap().push(notification.clone());
+    }
+}
+
+/// Finish a run of a job with the given setting and return how many
+/// notifications went out.
+fn finish(notify_on: NotifyOn, status: InvocationStatus) -> usize {
+    let notifier = MockNotifier::default();
+    let mut job = create_test_job();
+    job.run_policy.notify_on = notify_on;
+
+    notify_finished(Some(&notifier), &job, Uuid::new_v4(), status, None);
+
+    let sent = notifier.sent.lock().unwrap();
+    sent.len()
+}
+
+#[test]
+fn test_never_sends_nothing() {
+    assert_eq!(finish(NotifyOn::Never, InvocationStatus::Succeeded), 0);
+    assert_eq!(finish(NotifyOn::Never, InvocationStatus::Failed), 0);
+}
+
+#[test]
+fn test_failure_sends_only_on_failure() {
+    assert_eq!(finish(NotifyOn::Failure, InvocationStatus::Succeeded), 0);
+    assert_eq!(finish(NotifyOn::Failure, InvocationStatus::Failed), 1);
+    assert_eq!(
+        finish(NotifyOn::Failure, InvocationStatus::StoppedByLimit),
+        1
+    );
+    assert_eq!(finish(NotifyOn::Failure, InvocationStatus::Cancelled), 0);
+}
+
+#[test]
+fn test_always_sends_on_success_and_failure() {
+    assert_eq!(finish(NotifyOn::Always, InvocationStatus::Succeeded), 1);
+    assert_eq!(finish(NotifyOn::Always, InvocationStatus::Failed), 1);
+    assert_eq!(finish(NotifyOn::Always, InvocationStatus::Cancelled), 1);
+}
+
+#[test]
+fn test_notification_describes_the_run() {
+    let notifier = MockNotifier::default();
+    let job = create_test_job();
+    let invocation_id = Uuid::new_v4();
+
+    let sent = notify_finished(
+        Some(&notifier),
+        &job,
+        invocation_id,
+        InvocationStatus::Failed,
+        Some("rsync exited with code 23".to_string()),
+    );
+
+    assert!(sent);
+    assert_eq!(
+        *notifier.sent.lock().unwrap(),
+        vec![JobNotification {
+            job_id: job.id,
+            job_name: job.name.clone(),
+            invocation_id,
+            status: InvocationStatus::Failed,
+            error_message: Some("rsync exited with code 23".to_string()),
+        }]
+    );
+}
+
+#[test]
+fn test_without_notifier_nothing_is_sent() {
+    let mut job = create_test_job();
+    job.run_policy.notify_on = NotifyOn::Always;
+
+    assert!(!notify_finished(
+        None,
+        &job,
+        Uuid::new_v4(),
+        InvocationStatus::Failed,
+        None,
+    ));
+}
+
+#[test]
+fn test_change_alarm_notifies_regardless_of_notify_on() {
+    let notifier = MockNotifier::default();
+    let mut job = create_test_job();
+    job.run_policy.notify_on = NotifyOn::Never;
+    job.safety.change_alarm = Some(ChangeAlarm {
+        max_changed_percent: Some(90.0),
+        max_deleted_percent: None,
+        notify: true,
+    });
+
+    let sent = notify_change_alarm(
+        Some(&notifier),
+        &job,
+        Uuid::new_v4(),
+        InvocationStatus::Succeeded,
+        "Change alarm: 95.0% of files changed (limit 90%)",
+    );
+
+    assert!(sent);
+    let sent = notifier.sent.lock().unwrap();
+    assert_eq!(
+        sent[0].error_message.as_deref(),
+        Some("Change alarm: 95.0% of files changed (limit 90%)")
+    );
+}
+
+#[test]
+fn test_change_alarm_without_notify_sends_nothing() {
+    let notifier = MockNotifier::default();
+    let mut job = create_test_job();
+    job.safety.change_alarm = Some(ChangeAlarm {
+        max_changed_percent: Some(90.0),
+        max_deleted_percent: None,
+        notify: false,
+    });
+
+    assert!(!notify_change_alarm(
+        Some(&notifier),
+        &job,
+        Uuid::new_v4(),
+        InvocationStatus::Succeeded,
+        "Change alarm",
+    ));
+    assert!(notifier.sent.lock().unwrap().is_empty());
+}
+
+#[test]
+fn test_only_failed_verification_notifies() {
+    let notifier = MockNotifier::default();
+    let mut job = create_test_job();
+    let mut verification = SnapshotVerification {
+        invocation_id: Uuid::new_v4(),
+        job_id: job.id,
+        verified_at: Utc::now(),
+        passed: true,
+        discrepancies: 0,
+        error: None,
+    };
+    assert!(!notify_verification_failed(
+        Some(&notifier),
+        &job,
+        &verification
+    ));
+
+    verification.passed = false;
+    verification.discrepancies = 1;
+    assert!(notify_verification_failed(
+        Some(&notifier),
+        &job,
+        &verification
+    ));
+    let sent = notifier.sent.lock().unwrap().clone();
+    assert_eq!(sent[0].invocation_id, verification.invocation_id);
+    assert_eq!(
+        sent[0].error_message.as_deref(),
+        Some("Snapshot verification found 1 difference from the source")
+    );
+
+    job.run_policy.notify_on = NotifyOn::Never;
+    assert!(!notify_verification_failed(
+        Some(&notifier),
+        &job,
+        &verification
+    ));
+}
+
+#[test]
+fn test_running_status_is_never_notified() {
+    assert!(!should_notify(NotifyOn::Always, &InvocationStatus::Running));
+}
+
+#[test]
+fn test_notify_on_round_trips_through_the_database() {
+    let db = Database::in_memory().unwrap();
+    let repo = SqliteJobRepository::new(db.conn());
+    let mut job = create_test_job();
+    assert_eq!(job.run_policy.notify_on, NotifyOn::Failure);
+    job.run_policy.notify_on = NotifyOn::Always;
+
+    repo.create_job(&job).unwrap();
+
+    assert_eq!(
+        repo.get_job(&job.id).unwrap().run_policy.notify_on,
+        NotifyOn::Always
+    );
+}
@@ -0,0 +1,169 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::database::sqlite::Database;
+use crate::file_system::FileSystem;
+use crate::models::backup::{
+    BackupInvocation, ExecutionOutput, InvocationStatus, InvocationTrigger, TransferStats,
+};
+use crate::repository::sqlite::invocation::SqliteInvocationRepository;
+use crate::repository::sqlite::job::SqliteJobRepository;
+use crate::repository::sqlite::snapshot::SqliteSnapshotRepository;
+use crate::services::job_service::JobService;
+use crate::services::orphaned_logs::{clean_orphaned_logs, find_orphaned_logs, log_invocation_id};
+use crate::tests::test_file_system::TestFileSystem;
+
+const LOG_DIR: &str = "/data/logs";
+
+fn setup() -> (JobService, Uuid) {
+    let db = Database::in_memory().unwrap();
+    let conn = db.conn();
+    let job_service = JobService::new(
+        Arc::new(SqliteJobRepository::new(conn.clone())),
+        Arc::new(SqliteInvocationRepository::new(conn.clone())),
+        Arc::new(SqliteSnapshotRepository::new(conn)),
+    );
+    let job_id = job_service
+        .create_job(crate::tests::test_helpers::create_test_job())
+        .unwrap()
+        .id;
+    (job_service, job_id)
+}
+
+fn record_invocation(job_service: &JobService, job_id: Uuid, log_file_path: Option<&str>) -> Uuid {
+    let inv = BackupInvocation {
+        id: Uuid::new_v4(),
+        job_id,
+        started_at: Utc::now(),
+        finished_at: Some(Utc::now()),
+        status: InvocationStatus::Succeeded,
+        trigger: InvocationTrigger::Manual,
+        rerun_of: None,
+        resumed_from: None,
+        warning: None,
+        transfer_stats: TransferStats::default(),
+        execution_output: ExecutionOutput {
+            command_executed: "rsync -a /src /dst".to_string(),
+            exit_code: Some(0),
+            snapshot_path: None,
+            log_file_path: log_file_path.map(String::from),
+        },
+    };
+    job_service.record_invocation(&inv).unwrap();
+    inv.id
+}
+
+fn log_path(id: Uuid, suffix: &str) -> String {
+    format!("{}/{}{}", LOG_DIR, id, suffix)
+}
+
+#[test]
+fn test_log_invocation_id_parses_file_names() {
+    let id = Uuid::new_v4();
+    for suffix in [".log", ".log.1", ".jsonl", ".rsync.log", ""] {
+        let path = PathBuf::from(format!("/logs/{}{}", id, suffix));
+        assert_eq!(log_invocation_id(&path), Some(id), "suffix {:?}", suffix);
+    }
+    assert_eq!(log_invocation_id(Path::new("/logs/notes.log")), None);
+    assert_eq!(
+        log_invocation_id(Path::new(&format!("/logs/{}x.log", id))),
+        None
+    );
+}
+
+#[test]
+fn test_finds_logs_without_invocation() {
+    let (jobs, job_id) = setup();
+    let kept = record_invocation(&jobs, job_id, None);
+    let orphan = Uuid::new_v4();
+    let fs = TestFileSystem::new()
+        .with_file(&log_path(kept, ".log"), "kept")
+        .with_file(&log_path(orphan, ".log"), "orphan")
+        .with_file(&log_path(orphan, ".log.1"), "orphan rotated")
+        .with_file(&log_path(orphan, ".jsonl"), "{}");
+
+    let orphans = find_orphaned_logs(&fs, Path::new(LOG_DIR), &jobs).unwrap();
+
+    assert_eq!(
+        orphans,
+        vec![
+            PathBuf::from(log_path(orphan, ".jsonl")),
+            PathBuf::from(log_path(orphan, ".log")),
+            PathBuf::from(log_path(orphan, ".log.1")),
+        ]
+    );
+}
+
+#[test]
+fn test_referenced_logs_are_not_orphans() {
+    let (jobs, job_id) = setup();
+    // A log named after an invocation whose record still exists
+    let recorded = record_invocation(&jobs, job_id, None);
+    // A log whose name isn't its invocation's ID, kept because a record points at it
+    let shared = format!("{}/{}.log", LOG_DIR, job_id);
+    record_invocation(&jobs, job_id, Some(&shared));
+    let fs = TestFileSystem::new()
+        .with_file(&log_path(recorded, ".log"), "recorded")
+        .with_file(&shared, "shared")
+        .with_file(&format!("{}/{}.rsync.log", LOG_DIR, job_id), "rsync");
+
+    assert!(find_orphaned_logs(&fs, Path::new(LOG_DIR), &jobs)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_files_not_named_after_a_uuid_are_ignored() {
+    let (jobs, _) = setup();
+    let fs = TestFileSystem::new()
+        .with_file(&format!("{}/README.txt", LOG_DIR), "notes")
+        .with_file(&format!("{}/nightly/2025-06-01.log", LOG_DIR), "templated");
+
+    assert!(find_orphaned_logs(&fs, Path::new(LOG_DIR), &jobs)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_finds_orphans_in_subdirectories() {
+    let (jobs, _) = setup();
+    let orphan = Uuid::new_v4();
+    let nested = format!("{}/nightly/{}.log", LOG_DIR, orphan);
+    let fs = TestFileSystem::new().with_file(&nested, "orphan");
+
+    let orphans = find_orphaned_logs(&fs, Path::new(LOG_DIR), &jobs).unwrap();
+    assert_eq!(orphans, vec![PathBuf::from(nested)]);
+}
+
+#[test]
+fn test_missing_log_dir_has_no_orphans() {
+    let (jobs, _) = setup();
+    let fs = TestFileSystem::new();
+    assert!(find_orphaned_logs(&fs, Path::new(LOG_DIR), &jobs)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_clean_removes_only_orphans() {
+    let (jobs, job_id) = setup();
+    let kept = record_invocation(&jobs, job_id, None);
+    let orphan = Uuid::new_v4();
+    let fs = TestFileSystem::new()
+        .with_file(&log_path(kept, ".log"), "kept")
+        .with_file(&log_path(orphan, ".log"), "orphan")
+        .with_file(&format!("{}/README.txt", LOG_DIR), "notes");
+
+    let removed = clean_orphaned_logs(&fs, Path::new(LOG_DIR), &jobs).unwrap();
+
+    assert_eq!(removed, vec![PathBuf::from(log_path(orphan, ".log"))]);
+    assert!(!fs.exists(Path::new(&log_path(orphan, ".log"))));
+    assert!(fs.exists(Path::new(&log_path(kept, ".log"))));
+    assert!(fs.exists(Path::new(&format!("{}/README.txt", LOG_DIR))));
+    assert!(find_orphaned_logs(&fs, Path::new(LOG_DIR), &jobs)
+        .unwrap()
+        .is_empty());
+}
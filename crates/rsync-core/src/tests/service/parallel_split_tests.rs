@@ -0,0 +1,299 @@
+use std::path::Path;
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use crate::models::backup::{InvocationStatus, InvocationTrigger};
+use crate::models::execution::progress::StatsBlock;
+use crate::models::job::{JobDefinition, JobStatus, RetentionPolicy, StorageLocation};
+use crate::models::progress::{JobStatusEvent, ProgressUpdate};
+use crate::services::parallel_split::{
+    combined_exit_code, combined_progress, combined_stats, part_arg_sets, split_parallelism,
+    split_source_entries,
+};
+#[cfg(unix)]
+use crate::tests::mock_executor::{mock_rsync_executor, StatusHandler};
+use crate::tests::test_file_system::TestFileSystem;
+use crate::tests::test_helpers::{create_mirror_job, create_snapshot_job};
+
+fn args(source: &str) -> Vec<String> {
+    [
+        "-a",
+        "--delete",
+        source,
+        "/dst/",
+        "--log-file=/logs/run.rsync.log",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect()
+}
+
+fn progress(bytes: u64, rate: &str, done: u64, total: u64, eta: Option<u64>) -> ProgressUpdate {
+    ProgressUpdate {
+        invocation_id: Uuid::new_v4(),
+        bytes_transferred: bytes,
+        percentage: 50.0,
+        transfer_rate: rate.to_string(),
+        elapsed: "0:00:05".to_string(),
+        files_transferred: done,
+        files_remaining: total - done,
+        files_total: total,
+        eta_secs: eta,
+        current_file: None,
+    }
+}
+
+/// A job that may be split: a mirror without `--delete`.
+fn split_job(source: &str, dest: &str) -> JobDefinition {
+    let mut job = create_mirror_job(source, dest);
+    job.options.file_handling.delete = false;
+    job
+}
+
+#[test]
+fn test_split_needs_two_or_more_parts() {
+    let mut job = split_job("/src/", "/dst/");
+    assert_eq!(split_parallelism(&job, false), None);
+
+    job.run_policy.parallel_split = Some(1);
+    assert_eq!(split_parallelism(&job, false), None);
+
+    job.run_policy.parallel_split = Some(4);
+    assert_eq!(split_parallelism(&job, false), Some(4));
+
+    job.run_policy.parallel_split = Some(500);
+    assert_eq!(split_parallelism(&job, false), Some(16));
+}
+
+#[test]
+fn test_split_skips_relative_paths_and_remote_sources() {
+    let mut job = split_job("/src/", "/dst/");
+    job.run_policy.parallel_split = Some(4);
+    assert_eq!(split_parallelism(&job, true), None);
+
+    job.transfer.source = StorageLocation::RemoteSsh {
+        user: "backup".to_string(),
+        host: "nas".to_string(),
+        port: 22,
+        path: "/data".to_string(),
+        identity_file: None,
+    };
+    assert_eq!(split_parallelism(&job, false), None);
+}
+
+#[test]
+fn test_split_skips_snapshot_jobs() {
+    let mut job = create_snapshot_job("/src", "/backups", RetentionPolicy::default());
+    job.options.file_handling.delete = false;
+    job.run_policy.parallel_split = Some(4);
+
+    assert_eq!(split_parallelism(&job, false), None);
+}
+
+#[test]
+fn test_split_skips_jobs_that_delete() {
+    let mut job = split_job("/src/", "/dst/");
+    job.run_policy.parallel_split = Some(4);
+    assert_eq!(split_parallelism(&job, false), Some(4));
+
+    job.options.file_handling.delete = true;
+    assert_eq!(split_parallelism(&job, false), None);
+}
+
+#[test]
+fn test_entries_dealt_round_robin_in_sorted_order() {
+    let fs = TestFileSystem::new()
+        .with_dir("/src/e")
+        .with_dir("/src/a")
+        .with_file("/src/d.txt", "d")
+        .with_dir("/src/b")
+        .with_file("/src/c.txt", "c");
+
+    let groups = split_source_entries(Path::new("/src"), &fs, 2)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(groups, vec![vec!["a", "c.txt", "e"], vec!["b", "d.txt"]]);
+}
+
+#[test]
+fn test_fewer_entries_than_parts_gives_one_group_each() {
+    let fs = TestFileSystem::new().with_dir("/src/a").with_dir("/src/b");
+
+    let groups = split_source_entries(Path::new("/src"), &fs, 8)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(groups, vec![vec!["a"], vec!["b"]]);
+}
+
+#[test]
+fn test_single_entry_is_not_split() {
+    let fs = TestFileSystem::new().with_dir("/src/only");
+
+    assert_eq!(
+        split_source_entries(Path::new("/src"), &fs, 4).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn test_unreadable_source_is_an_error() {
+    let fs = TestFileSystem::new();
+
+    assert!(split_source_entries(Path::new("/missing"), &fs, 4).is_err());
+}
+
+#[test]
+fn test_part_args_copy_entries_into_destination() {
+    let groups = vec![
+        vec!["a".to_string(), "c".to_string()],
+        vec!["b".to_string()],
+    ];
+
+    let parts = part_arg_sets(&args("/src/"), 2, &groups);
+
+    assert_eq!(
+        parts,
+        vec![
+            vec![
+                "-a",
+                "--delete",
+                "/src/a",
+                "/src/c",
+                "/dst/",
+                "--log-file=/logs/run.rsync.log"
+            ],
+            vec![
+                "-a",
+                "--delete",
+                "/src/b",
+                "/dst/",
+                "--log-file=/logs/run.rsync.log"
+            ],
+        ]
+    );
+}
+
+#[test]
+fn test_part_args_without_trailing_slash_target_source_folder() {
+    let groups = vec![vec!["a".to_string()], vec!["b".to_string()]];
+
+    let parts = part_arg_sets(&args("/data/src"), 2, &groups);
+
+    assert_eq!(parts[0][2..4], ["/data/src/a", "/dst/src/"]);
+    assert_eq!(parts[1][2..4], ["/data/src/b", "/dst/src/"]);
+}
+
+#[test]
+fn test_combined_exit_code() {
+    let success = [0, 24];
+    assert_eq!(combined_exit_code(&[Some(0), Some(0)], &success), Some(0));
+    assert_eq!(combined_exit_code(&[Some(24), Some(0)], &success), Some(24));
+    assert_eq!(
+        combined_exit_code(&[Some(0), Some(23), Some(12)], &success),
+        Some(23)
+    );
+    assert_eq!(combined_exit_code(&[Some(23), None], &success), None);
+    assert_eq!(combined_exit_code(&[], &success), None);
+}
+
+#[test]
+fn test_combined_progress_adds_parts() {
+    let a = progress(1_000, "1.00MB/s", 3, 10, Some(20));
+    let b = progress(500, "500.00kB/s", 7, 10, Some(60));
+
+    let combined = combined_progress(&[a.clone(), b.clone()], &b);
+
+    assert_eq!(combined.bytes_transferred, 1_500);
+    assert_eq!(combined.files_transferred, 10);
+    assert_eq!(combined.files_total, 20);
+    assert_eq!(combined.percentage, 50.0);
+    assert_eq!(combined.transfer_rate, "1.50MB/s");
+    assert_eq!(combined.eta_secs, Some(60));
+    assert_eq!(combined.invocation_id, b.invocation_id);
+}
+
+#[test]
+fn test_combined_stats_sums_counts_and_keeps_longest_time() {
+    let mut a = StatsBlock {
+        total_files: Some(10),
+        deleted_files: Some(1),
+        ..StatsBlock::default()
+    };
+    a.detailed.file_list_generation_secs = Some(0.5);
+    let mut b = StatsBlock {
+        total_files: Some(5),
+        ..StatsBlock::default()
+    };
+    b.detailed.file_list_generation_secs = Some(1.5);
+
+    let combined = combined_stats(&[a, b]);
+
+    assert_eq!(combined.total_files, Some(15));
+    assert_eq!(combined.deleted_files, Some(1));
+    assert_eq!(combined.files_transferred, None);
+    assert_eq!(combined.detailed.file_list_generation_secs, Some(1.5));
+}
+
+/// Run a job splitting a three-entry source three ways and wait for it.
+#[cfg(unix)]
+fn run_split_job(dir: &Path, script: &str) -> (JobStatusEvent, InvocationStatus) {
+    let src = dir.join("src");
+    for entry in ["a", "b", "c"] {
+        std::fs::create_dir_all(src.join(entry)).unwrap();
+    }
+    let (executor, svc, _db) = mock_rsync_executor(dir, script);
+    let mut job = split_job(&src.to_string_lossy(), &dir.join("dst").to_string_lossy());
+    job.run_policy.parallel_split = Some(3);
+    let job = svc.create_job(job).unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let handler = Arc::new(StatusHandler(Mutex::new(tx)));
+    let invocation_id = executor
+        .execute(&job, InvocationTrigger::Manual, handler)
+        .unwrap();
+    let event = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+    let invocation = svc.get_invocation(&invocation_id).unwrap();
+    (event, invocation.status)
+}
+
+#[cfg(unix)]
+#[test]
+fn test_split_job_launches_one_process_per_part() {
+    let tmp = tempfile::tempdir().unwrap();
+    let calls = tmp.path().join("calls");
+    let script = format!("echo \"$@\" >> '{}'", calls.display());
+
+    let (event, status) = run_split_job(tmp.path(), &script);
+
+    assert_eq!(event.status, JobStatus::Completed);
+    assert_eq!(status, InvocationStatus::Succeeded);
+    let calls = std::fs::read_to_string(&calls).unwrap();
+    assert_eq!(calls.lines().count(), 3);
+    let mut sources: Vec<&str> = calls
+        .split_whitespace()
+        .filter(|arg| arg.contains("/src/"))
+        .collect();
+    sources.sort();
+    assert_eq!(sources.len(), 3);
+    assert!(sources[0].ends_with("/src/a"));
+    assert!(sources[1].ends_with("/src/b"));
+    assert!(sources[2].ends_with("/src/c"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_split_job_fails_when_any_part_fails() {
+    let tmp = tempfile::tempdir().unwrap();
+    let script = "case \"$*\" in *src/b*) exit 23;; esac";
+
+    let (event, status) = run_split_job(tmp.path(), script);
+
+    assert_eq!(event.status, JobStatus::Failed);
+    assert_eq!(event.exit_code, Some(23));
+    assert_eq!(status, InvocationStatus::Failed);
+}
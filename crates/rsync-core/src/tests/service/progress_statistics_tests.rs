@@ -239,10 +239,15 @@ fn test_full_pipeline_summary_bytes_recorded_in_statistics() {
         finished_at: Some(Utc::now()),
         status: InvocationStatus::Succeeded,
         trigger: InvocationTrigger::Manual,
+        rerun_of: None,
+        resumed_from: None,
+        warning: None,
         transfer_stats: TransferStats {
             bytes_transferred: bytes,
             files_transferred: files,
             total_files: total,
+            files_deleted: 0,
+            detailed: None,
         },
         execution_output: ExecutionOutput {
             command_executed: "rsync -av /src/ /dst/".to_string(),
@@ -285,10 +290,15 @@ fn test_full_pipeline_multiple_runs_accumulate() {
         finished_at: Some(Utc::now()),
         status: InvocationStatus::Succeeded,
         trigger: InvocationTrigger::Manual,
+        rerun_of: None,
+        resumed_from: None,
+        warning: None,
         transfer_stats: TransferStats {
             bytes_transferred: bytes1,
             files_transferred: files1,
             total_files: total1,
+            files_deleted: 0,
+            detailed: None,
         },
         execution_output: ExecutionOutput {
             command_executed: "rsync -av /src/ /dst/".to_string(),
@@ -315,10 +325,15 @@ fn test_full_pipeline_multiple_runs_accumulate() {
         finished_at: Some(Utc::now()),
         status: InvocationStatus::Succeeded,
         trigger: InvocationTrigger::Manual,
+        rerun_of: None,
+        resumed_from: None,
+        warning: None,
         transfer_stats: TransferStats {
             bytes_transferred: bytes2,
             files_transferred: files2,
             total_files: total2,
+            files_deleted: 0,
+            detailed: None,
         },
         execution_output: ExecutionOutput {
             command_executed: "rsync -av /src/ /dst/".to_string(),
@@ -359,10 +374,15 @@ fn test_ir_chk_files_counted_in_statistics() {
         finished_at: Some(Utc::now()),
         status: InvocationStatus::Succeeded,
         trigger: InvocationTrigger::Manual,
+        rerun_of: None,
+        resumed_from: None,
+        warning: None,
         transfer_stats: TransferStats {
             bytes_transferred: bytes,
             files_transferred: files,
             total_files: total,
+            files_deleted: 0,
+            detailed: None,
         },
         execution_output: ExecutionOutput {
             command_executed: "rsync -av /src/ /dst/".to_string(),
@@ -0,0 +1,288 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::database::sqlite::Database;
+use crate::file_system::FileSystem;
+use crate::models::backup::{
+    BackupInvocation, ExecutionOutput, InvocationStatus, InvocationTrigger, SnapshotRecord,
+    TransferStats,
+};
+use crate::models::job::RetentionPolicy;
+use crate::models::settings::{PruneRecord, PrunedSnapshot};
+use crate::repository::sqlite::invocation::SqliteInvocationRepository;
+use crate::repository::sqlite::job::SqliteJobRepository;
+use crate::repository::sqlite::settings::SqliteSettingsRepository;
+use crate::repository::sqlite::snapshot::SqliteSnapshotRepository;
+use crate::services::job_service::JobService;
+use crate::services::prune_recovery::{record_prune, undo_last_prune, PRUNE_UNDO_WINDOW_HOURS};
+use crate::services::retention_runner::run_history_retention;
+use crate::services::settings_service::SettingsService;
+use crate::services::snapshot_trash::dispose_pruned_snapshot;
+use crate::tests::test_file_system::TestFileSystem;
+use crate::tests::test_helpers::{create_snapshot_job, create_test_job};
+
+fn setup_services() -> (JobService, SettingsService, Database) {
+    let db = Database::open(":memory:").expect("open in-memory db");
+    let conn = db.conn();
+    let job_service = JobService::new(
+        Arc::new(SqliteJobRepository::new(conn.clone())),
+        Arc::new(SqliteInvocationRepository::new(conn.clone())),
+        Arc::new(SqliteSnapshotRepository::new(conn.clone())),
+    );
+    let settings_service = SettingsService::new(Arc::new(SqliteSettingsRepository::new(conn)));
+    (job_service, settings_service, db)
+}
+
+fn make_invocation(job_id: Uuid, age_days: i64) -> BackupInvocation {
+    BackupInvocation {
+        id: Uuid::new_v4(),
+        job_id,
+        started_at: Utc::now() - Duration::days(age_days),
+        finished_at: Some(Utc::now() - Duration::days(age_days)),
+        status: InvocationStatus::Succeeded,
+        trigger: InvocationTrigger::Manual,
+        rerun_of: None,
+        resumed_from: None,
+        warning: None,
+        transfer_stats: TransferStats::default(),
+        execution_output: ExecutionOutput {
+            command_executed: "rsync -a /src/ /backups/".to_string(),
+            exit_code: Some(0),
+            snapshot_path: None,
+            log_file_path: None,
+        },
+    }
+}
+
+fn make_snapshot(job_id: Uuid, invocation_id: Uuid, path: &str, hours_ago: i64) -> SnapshotRecord {
+    SnapshotRecord {
+        id: Uuid::new_v4(),
+        job_id,
+        invocation_id,
+        snapshot_path: path.to_string(),
+        link_dest_path: None,
+        created_at: Utc::now() - Duration::hours(hours_ago),
+        size_bytes: 5,
+        file_count: 1,
+        is_latest: hours_ago == 1,
+        pinned: false,
+    }
+}
+
+#[test]
+fn test_undo_restores_history_prune() {
+    let (job_service, settings_service, _db) = setup_services();
+    let fs = TestFileSystem::new().with_dir("/backups/old");
+    let job = job_service.create_job(create_test_job()).unwrap();
+    let old = make_invocation(job.id, 100);
+    let recent = make_invocation(job.id, 1);
+    job_service.record_invocation(&old).unwrap();
+    job_service.record_invocation(&recent).unwrap();
+    let snapshot = make_snapshot(job.id, old.id, "/backups/old", 2400);
+    job_service.record_snapshot(&snapshot).unwrap();
+
+    assert_eq!(run_history_retention(&job_service, &settings_service), 1);
+    // The snapshot record went with its invocation
+    assert!(job_service.list_all_snapshots().unwrap().is_empty());
+
+    let result = undo_last_prune(&job_service, &fs, Utc::now())
+        .unwrap()
+        .expect("a prune to undo");
+
+    assert_eq!(result.invocations_restored, 1);
+    assert_eq!(result.snapshots_restored, 1);
+    assert!(result.missing_directories.is_empty());
+    let history = job_service.get_job_history(&job.id, 10).unwrap();
+    assert_eq!(history.len(), 2);
+    assert!(history.iter().any(|inv| inv == &old));
+    assert_eq!(job_service.list_all_snapshots().unwrap(), vec![snapshot]);
+
+    // Undone prunes leave the recovery log
+    assert!(undo_last_prune(&job_service, &fs, Utc::now())
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn test_undo_moves_trashed_snapshot_back() {
+    let (job_service, _, _db) = setup_services();
+    let fs = TestFileSystem::new()
+        .with_file("/backups/2025-01-01/docs/a.txt", "alpha")
+        .with_file("/backups/2025-01-02/docs/a.txt", "beta");
+    let policy = RetentionPolicy {
+        keep_daily: 0,
+        keep_weekly: 0,
+        keep_monthly: 0,
+    };
+    let job = job_service
+        .create_job(create_snapshot_job("/src/", "/backups/", policy))
+        .unwrap();
+    let inv = make_invocation(job.id, 0);
+    job_service.record_invocation(&inv).unwrap();
+    let older = make_snapshot(job.id, inv.id, "/backups/2025-01-01", 2);
+    job_service.record_snapshot(&older).unwrap();
+    job_service
+        .record_snapshot(&make_snapshot(job.id, inv.id, "/backups/2025-01-02", 1))
+        .unwrap();
+
+    let pruned = job_service.prune_snapshots_by_policy(&job.id).unwrap();
+    assert_eq!(pruned, vec![older.clone()]);
+    let trashed_to = dispose_pruned_snapshot(
+        &fs,
+        Path::new(&older.snapshot_path),
+        Some(Path::new("/trash")),
+    )
+    .unwrap()
+    .map(|p| p.to_string_lossy().to_string());
+    record_prune(
+        &job_service,
+        PruneRecord {
+            pruned_at: Utc::now(),
+            invocations: Vec::new(),
+            snapshots: vec![PrunedSnapshot {
+                record: older.clone(),
+                trashed_to,
+            }],
+        },
+    );
+    assert!(!fs.exists(Path::new("/backups/2025-01-01")));
+
+    let result = undo_last_prune(&job_service, &fs, Utc::now())
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(result.snapshots_restored, 1);
+    assert_eq!(result.directories_restored, 1);
+    assert_eq!(
+        fs.file_content("/backups/2025-01-01/docs/a.txt").as_deref(),
+        Some("alpha")
+    );
+    assert!(fs.read_dir(Path::new("/trash")).unwrap().is_empty());
+    assert!(job_service
+        .list_snapshots(&job.id)
+        .unwrap()
+        .contains(&older));
+}
+
+#[test]
+fn test_deleted_snapshot_directory_is_reported_missing() {
+    let (job_service, _, _db) = setup_services();
+    let fs = TestFileSystem::new();
+    let job = job_service.create_job(create_test_job()).unwrap();
+    let inv = make_invocation(job.id, 0);
+    job_service.record_invocation(&inv).unwrap();
+    let snapshot = make_snapshot(job.id, inv.id, "/backups/gone", 3);
+    record_prune(
+        &job_service,
+        PruneRecord {
+            pruned_at: Utc::now(),
+            invocations: Vec::new(),
+            snapshots: vec![PrunedSnapshot {
+                record: snapshot.clone(),
+                trashed_to: None,
+            }],
+        },
+    );
+
+    let result = undo_last_prune(&job_service, &fs, Utc::now())
+        .unwrap()
+        .unwrap();
+
+    // The record is back even though the directory is not
+    assert_eq!(result.snapshots_restored, 1);
+    assert_eq!(
+        result.missing_directories,
+        vec!["/backups/gone".to_string()]
+    );
+    assert_eq!(job_service.list_all_snapshots().unwrap(), vec![snapshot]);
+}
+
+#[test]
+fn test_undo_takes_latest_prune_first() {
+    let (job_service, _, _db) = setup_services();
+    let fs = TestFileSystem::new();
+    let job = job_service.create_job(create_test_job()).unwrap();
+    let first = make_invocation(job.id, 10);
+    let second = make_invocation(job.id, 5);
+    for inv in [&first, &second] {
+        record_prune(
+            &job_service,
+            PruneRecord {
+                pruned_at: Utc::now(),
+                invocations: vec![inv.clone()],
+                snapshots: Vec::new(),
+            },
+        );
+    }
+
+    undo_last_prune(&job_service, &fs, Utc::now()).unwrap();
+    let history = job_service.get_job_history(&job.id, 10).unwrap();
+    assert_eq!(history, vec![second.clone()]);
+
+    undo_last_prune(&job_service, &fs, Utc::now()).unwrap();
+    assert_eq!(job_service.get_job_history(&job.id, 10).unwrap().len(), 2);
+}
+
+#[test]
+fn test_prune_outside_window_cannot_be_undone() {
+    let (job_service, _, _db) = setup_services();
+    let fs = TestFileSystem::new();
+    let job = job_service.create_job(create_test_job()).unwrap();
+    let inv = make_invocation(job.id, 10);
+    record_prune(
+        &job_service,
+        PruneRecord {
+            pruned_at: Utc::now(),
+            invocations: vec![inv],
+            snapshots: Vec::new(),
+        },
+    );
+
+    let later = Utc::now() + Duration::hours(PRUNE_UNDO_WINDOW_HOURS + 1);
+    assert!(undo_last_prune(&job_service, &fs, later).unwrap().is_none());
+    assert!(job_service.get_job_history(&job.id, 10).unwrap().is_empty());
+    // The expired record is gone, not just skipped
+    let long_ago = Utc::now() - Duration::days(365);
+    assert!(job_service
+        .take_latest_prune_record(&long_ago)
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn test_recording_a_prune_drops_expired_records() {
+    let (job_service, _, _db) = setup_services();
+    let job = job_service.create_job(create_test_job()).unwrap();
+    let stale = make_invocation(job.id, 10);
+    let fresh = make_invocation(job.id, 5);
+    record_prune(
+        &job_service,
+        PruneRecord {
+            pruned_at: Utc::now() - Duration::hours(PRUNE_UNDO_WINDOW_HOURS + 1),
+            invocations: vec![stale],
+            snapshots: Vec::new(),
+        },
+    );
+    record_prune(
+        &job_service,
+        PruneRecord {
+            pruned_at: Utc::now(),
+            invocations: vec![fresh.clone()],
+            snapshots: Vec::new(),
+        },
+    );
+
+    let long_ago = Utc::now() - Duration::days(365);
+    let latest = job_service
+        .take_latest_prune_record(&long_ago)
+        .unwrap()
+        .unwrap();
+    assert_eq!(latest.invocations, vec![fresh]);
+    assert!(job_service
+        .take_latest_prune_record(&long_ago)
+        .unwrap()
+        .is_none());
+}
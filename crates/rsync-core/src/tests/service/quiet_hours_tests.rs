@@ -0,0 +1,112 @@
+use chrono::{Duration, NaiveTime};
+
+use crate::models::backup::InvocationTrigger;
+use crate::models::settings::QuietHours;
+use crate::services::quiet_hours::{is_quiet_time, suppressed_by_quiet_hours, time_until_quiet_end};
+
+fn at(hour: u32, minute: u32) -> NaiveTime {
+    NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+}
+
+fn quiet(start: (u16, u16), end: (u16, u16)) -> QuietHours {
+    QuietHours {
+        start_minute: start.0 * 60 + start.1,
+        end_minute: end.0 * 60 + end.1,
+    }
+}
+
+#[test]
+fn test_daytime_window() {
+    let q = quiet((9, 0), (10, 30));
+    assert!(!is_quiet_time(&q, at(8, 59)));
+    assert!(is_quiet_time(&q, at(9, 0)));
+    assert!(is_quiet_time(&q, at(10, 29)));
+    assert!(!is_quiet_time(&q, at(10, 30)));
+    assert!(!is_quiet_time(&q, at(23, 0)));
+}
+
+#[test]
+fn test_window_wrapping_midnight() {
+    let q = quiet((22, 0), (6, 0));
+    assert!(is_quiet_time(&q, at(22, 0)));
+    assert!(is_quiet_time(&q, at(23, 59)));
+    assert!(is_quiet_time(&q, at(0, 0)));
+    assert!(is_quiet_time(&q, at(5, 59)));
+    assert!(!is_quiet_time(&q, at(6, 0)));
+    assert!(!is_quiet_time(&q, at(12, 0)));
+    assert!(!is_quiet_time(&q, at(21, 59)));
+}
+
+#[test]
+fn test_equal_start_and_end_is_empty() {
+    let q = quiet((9, 0), (9, 0));
+    assert!(!is_quiet_time(&q, at(9, 0)));
+    assert!(!is_quiet_time(&q, at(15, 0)));
+}
+
+#[test]
+fn test_scheduled_run_suppressed_during_quiet_hours() {
+    let q = quiet((9, 0), (10, 0));
+    assert!(suppressed_by_quiet_hours(
+        Some(&q),
+        &InvocationTrigger::Scheduled,
+        at(9, 30)
+    ));
+    assert!(!suppressed_by_quiet_hours(
+        Some(&q),
+        &InvocationTrigger::Scheduled,
+        at(10, 30)
+    ));
+}
+
+#[test]
+fn test_manual_run_bypasses_quiet_hours() {
+    let q = quiet((9, 0), (10, 0));
+    assert!(!suppressed_by_quiet_hours(
+        Some(&q),
+        &InvocationTrigger::Manual,
+        at(9, 30)
+    ));
+}
+
+#[test]
+fn test_no_quiet_hours_never_suppresses() {
+    assert!(!suppressed_by_quiet_hours(
+        None,
+        &InvocationTrigger::Scheduled,
+        at(9, 30)
+    ));
+}
+
+#[test]
+fn test_quiet_hours_setting_value_roundtrip() {
+    let q = quiet((22, 5), (6, 0));
+    assert_eq!(q.to_setting_value(), "22:05-06:00");
+    assert_eq!(QuietHours::parse("22:05-06:00"), Some(q));
+}
+
+#[test]
+fn test_quiet_hours_parse_rejects_invalid() {
+    assert_eq!(QuietHours::parse("24:00-06:00"), None);
+    assert_eq!(QuietHours::parse("09:60-10:00"), None);
+    assert_eq!(QuietHours::parse("0900-1000"), None);
+    assert_eq!(QuietHours::parse(""), None);
+}
+
+#[test]
+fn test_time_until_quiet_end() {
+    let q = quiet((22, 0), (6, 0));
+    assert_eq!(time_until_quiet_end(&q, at(12, 0)), None);
+    assert_eq!(
+        time_until_quiet_end(&q, at(22, 0)),
+        Some(Duration::hours(8))
+    );
+    assert_eq!(
+        time_until_quiet_end(&q, at(5, 30)),
+        Some(Duration::minutes(30))
+    );
+    assert_eq!(
+        time_until_quiet_end(&q, NaiveTime::from_hms_opt(5, 59, 30).unwrap()),
+        Some(Duration::seconds(30))
+    );
+}
@@ -0,0 +1,280 @@
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::database::sqlite::Database;
+use crate::error::AppError;
+use crate::models::backup::{
+    BackupInvocation, ExecutionOutput, InvocationStatus, InvocationTrigger, TransferStats,
+};
+use crate::models::job::{JobDefinition, JobStatus};
+use crate::models::progress::JobStatusEvent;
+use crate::services::job_executor::JobExecutor;
+use crate::services::job_service::JobService;
+use crate::services::rerun::{stored_command_args, uses_password_file, without_run_files};
+use crate::tests::mock_executor::{mock_rsync_executor, StatusHandler};
+use crate::tests::test_helpers::create_test_job;
+
+/// A real-mode executor whose mock rsync runs `script`.
+fn executor(dir: &Path, script: &str) -> (JobExecutor, Arc<JobService>, Database) {
+    let (executor, svc, db) = mock_rsync_executor(dir, script);
+    executor.settings_service().set_test_mode(false).unwrap();
+    (executor, svc, db)
+}
+
+/// A mock rsync that writes its arguments, one per line, to `args`.
+fn recording_script(dir: &Path, exit_code: i32) -> String {
+    format!(
+        "printf '%s\\n' \"$@\" > '{}'\nexit {}",
+        dir.join("args").display(),
+        exit_code
+    )
+}
+
+fn recorded_args(dir: &Path) -> Vec<String> {
+    std::fs::read_to_string(dir.join("args"))
+        .unwrap()
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+fn rerun(
+    executor: &JobExecutor,
+    invocation_id: &Uuid,
+) -> (Result<Uuid, String>, Receiver<JobStatusEvent>) {
+    let (tx, rx) = mpsc::channel();
+    let handler = Arc::new(StatusHandler(Mutex::new(tx)));
+    (executor.rerun(invocation_id, handler), rx)
+}
+
+fn rerun_to_end(executor: &JobExecutor, invocation_id: &Uuid) -> (Uuid, JobStatusEvent) {
+    let (result, rx) = rerun(executor, invocation_id);
+    let id = result.unwrap();
+    (id, rx.recv_timeout(Duration::from_secs(10)).unwrap())
+}
+
+fn record_past_run(svc: &JobService, job_id: Uuid, command: &str) -> BackupInvocation {
+    let inv = BackupInvocation {
+        id: Uuid::new_v4(),
+        job_id,
+        started_at: Utc::now(),
+        finished_at: Some(Utc::now()),
+        status: InvocationStatus::Failed,
+        trigger: InvocationTrigger::Scheduled,
+        rerun_of: None,
+        resumed_from: None,
+        warning: None,
+        transfer_stats: TransferStats::default(),
+        execution_output: ExecutionOutput {
+            command_executed: command.to_string(),
+            exit_code: Some(23),
+            snapshot_path: Some("/backups/2026-10-01_020000".to_string()),
+            log_file_path: None,
+        },
+    };
+    svc.record_invocation(&inv).unwrap();
+    inv
+}
+
+fn create_job(svc: &JobService, customize: impl FnOnce(&mut JobDefinition)) -> JobDefinition {
+    let mut job = create_test_job();
+    customize(&mut job);
+    svc.create_job(job).unwrap()
+}
+
+const STORED: &str = "rsync -a --delete '/home/me/My Documents/' /backups/2026-10-01_020000/";
+
+#[test]
+fn test_rerun_executes_the_stored_command() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (executor, svc, _db) = executor(tmp.path(), &recording_script(tmp.path(), 0));
+    let job = create_job(&svc, |_| {});
+    let original = record_past_run(&svc, job.id, STORED);
+
+    let (_, event) = rerun_to_end(&executor, &original.id);
+
+    assert_eq!(event.status, JobStatus::Completed);
+    assert_eq!(
+        recorded_args(tmp.path()),
+        vec![
+            "-a",
+            "--delete",
+            "/home/me/My Documents/",
+            "/backups/2026-10-01_020000/"
+        ]
+    );
+}
+
+#[test]
+fn test_rerun_records_linked_invocation() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (executor, svc, _db) = executor(tmp.path(), &recording_script(tmp.path(), 0));
+    let job = create_job(&svc, |_| {});
+    let original = record_past_run(&svc, job.id, STORED);
+
+    let (rerun_id, _) = rerun_to_end(&executor, &original.id);
+
+    let stored = svc.get_invocation(&rerun_id).unwrap();
+    assert_ne!(stored.id, original.id);
+    assert_eq!(stored.rerun_of, Some(original.id));
+    assert_eq!(stored.job_id, job.id);
+    assert_eq!(stored.trigger, InvocationTrigger::Manual);
+    assert_eq!(stored.status, InvocationStatus::Succeeded);
+    assert_eq!(
+        stored.execution_output.snapshot_path,
+        original.execution_output.snapshot_path
+    );
+    // The original run is left untouched
+    assert_eq!(svc.get_invocation(&original.id).unwrap(), original);
+}
+
+#[test]
+fn test_rerun_uses_job_success_exit_codes() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (executor, svc, _db) = executor(tmp.path(), &recording_script(tmp.path(), 24));
+    let job = create_job(&svc, |job| job.run_policy.success_exit_codes = vec![0, 24]);
+    let original = record_past_run(&svc, job.id, STORED);
+
+    let (rerun_id, event) = rerun_to_end(&executor, &original.id);
+
+    assert_eq!(event.status, JobStatus::Completed);
+    let stored = svc.get_invocation(&rerun_id).unwrap();
+    assert_eq!(stored.status, InvocationStatus::Succeeded);
+    assert_eq!(stored.execution_output.exit_code, Some(24));
+}
+
+#[test]
+fn test_rerun_of_missing_invocation_fails() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (executor, _svc, _db) = executor(tmp.path(), &recording_script(tmp.path(), 0));
+
+    let (result, _rx) = rerun(&executor, &Uuid::new_v4());
+
+    assert!(result.is_err());
+    assert!(!tmp.path().join("args").exists());
+}
+
+#[test]
+fn test_rerun_refused_in_test_mode() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (executor, svc, _db) = mock_rsync_executor(tmp.path(), &recording_script(tmp.path(), 0));
+    let job = create_job(&svc, |_| {});
+    let original = record_past_run(&svc, job.id, STORED);
+
+    let (result, _rx) = rerun(&executor, &original.id);
+
+    assert!(result.unwrap_err().contains("test mode"));
+    assert!(!tmp.path().join("args").exists());
+}
+
+#[test]
+fn test_rerun_replaces_the_original_run_files() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (executor, svc, _db) = executor(tmp.path(), &recording_script(tmp.path(), 0));
+    let job = create_job(&svc, |_| {});
+    let stored = format!(
+        "{} --password-file=/tmp/gone.pw --log-file /old/run.rsync.log --log-file-format=%i",
+        STORED
+    );
+    let original = record_past_run(&svc, job.id, &stored);
+
+    let (rerun_id, _) = rerun_to_end(&executor, &original.id);
+
+    let args = recorded_args(tmp.path());
+    assert!(!args
+        .iter()
+        .any(|arg| arg.contains("gone.pw") || arg.contains("/old/")));
+    let rerun = svc.get_invocation(&rerun_id).unwrap();
+    let log_file = rerun.execution_output.log_file_path.unwrap();
+    assert!(log_file.starts_with(&tmp.path().join("logs").to_string_lossy().to_string()));
+    assert!(Path::new(&log_file).exists());
+}
+
+#[test]
+fn test_rerun_applies_the_job_environment() {
+    let tmp = tempfile::tempdir().unwrap();
+    let script = format!(
+        "printf '%s' \"$RERUN_MARKER\" > '{}'",
+        tmp.path().join("env").display()
+    );
+    let (executor, svc, _db) = executor(tmp.path(), &script);
+    let job = create_job(&svc, |job| {
+        job.run_policy
+            .env
+            .insert("RERUN_MARKER".to_string(), "set".to_string());
+    });
+    let original = record_past_run(&svc, job.id, STORED);
+
+    rerun_to_end(&executor, &original.id);
+
+    assert_eq!(
+        std::fs::read_to_string(tmp.path().join("env")).unwrap(),
+        "set"
+    );
+}
+
+#[test]
+fn test_rerun_is_registered_as_running() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (executor, svc, _db) = executor(tmp.path(), "exec sleep 60");
+    let job = create_job(&svc, |_| {});
+    let original = record_past_run(&svc, job.id, STORED);
+
+    let (result, rx) = rerun(&executor, &original.id);
+    result.unwrap();
+
+    assert!(executor.is_running(&job.id));
+    assert_eq!(
+        rerun(&executor, &original.id).0.unwrap_err(),
+        "Job is already running"
+    );
+    assert!(executor.cancel(&job.id));
+    let event = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+    assert_eq!(event.status, JobStatus::Cancelled);
+}
+
+#[test]
+fn test_without_run_files_strips_both_option_forms() {
+    let args: Vec<String> = [
+        "-a",
+        "--password-file",
+        "/tmp/a.pw",
+        "--log-file=/logs/x.rsync.log",
+        "--log-file-format",
+        "%i %n",
+        "--logs-are-not-this",
+        "src/",
+        "dst/",
+    ]
+    .iter()
+    .map(|arg| arg.to_string())
+    .collect();
+
+    assert_eq!(
+        without_run_files(&args),
+        vec!["-a", "--logs-are-not-this", "src/", "dst/"]
+    );
+    assert!(uses_password_file(&args));
+    assert!(!uses_password_file(&without_run_files(&args)));
+}
+
+#[test]
+fn test_stored_command_args_rejects_non_rsync_commands() {
+    assert!(matches!(
+        stored_command_args("rm -rf /"),
+        Err(AppError::ValidationError(_))
+    ));
+    assert!(matches!(
+        stored_command_args("rsync"),
+        Err(AppError::ValidationError(_))
+    ));
+    assert!(matches!(
+        stored_command_args("rsync -a 'unterminated"),
+        Err(AppError::ValidationError(_))
+    ));
+}
@@ -0,0 +1,173 @@
+use std::cell::RefCell;
+
+use crate::models::job::StorageLocation;
+use crate::rsync_client::{RsyncClient, RsyncError, RsyncResult};
+use crate::services::restore::{build_restore_command, preview_restore};
+use crate::tests::test_helpers::{create_mirror_job, create_test_job};
+
+const RESTORE_DRY_RUN: &str = "\
+receiving incremental file list
+.d..t...... ./
+>f+++++++++ docs/report.pdf
+>f.st...... notes.txt
+
+Number of files: 12 (reg: 10, dir: 2)
+Number of created files: 1 (reg: 1)
+Number of deleted files: 0
+Number of regular files transferred: 2
+Total file size: 9,000 bytes
+Total transferred file size: 4,096 bytes
+Literal data: 0 bytes
+Matched data: 0 bytes
+File list size: 0
+Total bytes sent: 120
+Total bytes received: 340
+
+sent 120 bytes  received 340 bytes  920.00 bytes/sec
+total size is 9,000  speedup is 19.57 (DRY RUN)";
+
+/// Returns canned stdout for dry runs and records the arguments it was given.
+struct CannedRsync {
+    stdout: String,
+    dry_run_args: RefCell<Vec<String>>,
+}
+
+impl CannedRsync {
+    fn new(stdout: &str) -> Self {
+        Self {
+            stdout: stdout.to_string(),
+            dry_run_args: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+impl RsyncClient for CannedRsync {
+    fn execute(&self, _args: &[String]) -> Result<RsyncResult, RsyncError> {
+        panic!("restore preview must not run a real transfer");
+    }
+
+    fn dry_run(&self, args: &[String]) -> Result<RsyncResult, RsyncError> {
+        *self.dry_run_args.borrow_mut() = args.to_vec();
+        Ok(RsyncResult {
+            exit_code: 0,
+            stdout: self.stdout.clone(),
+            stderr: String::new(),
+            command: format!("rsync {}", args.join(" ")),
+        })
+    }
+
+    fn version(&self) -> Result<String, RsyncError> {
+        Ok("rsync  version 3.2.7  protocol version 31".to_string())
+    }
+}
+
+fn paths(args: &[String]) -> (&str, &str) {
+    let n = args.len();
+    (&args[n - 2], &args[n - 1])
+}
+
+#[test]
+fn test_restore_command_swaps_source_and_destination() {
+    let job = create_mirror_job("/home/docs/", "/backups/docs/");
+    let args = build_restore_command(&job, None, true);
+
+    assert_eq!(paths(&args), ("/backups/docs/", "/home/docs/"));
+}
+
+#[test]
+fn test_restore_command_has_no_delete_flags() {
+    let mut job = create_test_job();
+    assert!(job.options.file_handling.delete);
+    job.options.file_handling.max_delete = Some(10);
+    job.options.advanced.custom_args = vec![
+        "--delete-after".to_string(),
+        "--del".to_string(),
+        "--remove-source-files".to_string(),
+        "--delay-updates".to_string(),
+    ];
+
+    let args = build_restore_command(&job, None, true);
+
+    assert!(!args
+        .iter()
+        .any(|a| a.starts_with("--del") && a != "--delay-updates"));
+    assert!(!args.iter().any(|a| a.starts_with("--max-delete")));
+    assert!(!args.contains(&"--remove-source-files".to_string()));
+    assert!(args.contains(&"--delay-updates".to_string()));
+}
+
+#[test]
+fn test_restore_command_reads_subdirectory_for_source_without_slash() {
+    let job = create_mirror_job("/home/docs", "/backups");
+    let args = build_restore_command(&job, None, false);
+
+    assert_eq!(paths(&args), ("/backups/docs/", "/home/docs/"));
+}
+
+#[test]
+fn test_restore_command_auto_trailing_slash_reads_destination_root() {
+    let job = create_mirror_job("/home/docs", "/backups/docs");
+    let args = build_restore_command(&job, None, true);
+
+    assert_eq!(paths(&args), ("/backups/docs/", "/home/docs/"));
+}
+
+#[test]
+fn test_restore_command_reads_from_snapshot() {
+    let job = create_mirror_job("/home/docs/", "/backups/");
+    let args = build_restore_command(&job, Some("/backups/2025-06-15_140000"), true);
+
+    assert_eq!(paths(&args), ("/backups/2025-06-15_140000/", "/home/docs/"));
+}
+
+#[test]
+fn test_restore_command_keeps_remote_side() {
+    let mut job = create_mirror_job("/home/docs/", "/unused");
+    job.transfer.destination = StorageLocation::RemoteSsh {
+        user: "backup".to_string(),
+        host: "nas.local".to_string(),
+        port: 22,
+        path: "/volume1/docs/".to_string(),
+        identity_file: None,
+    };
+
+    let args = build_restore_command(&job, None, true);
+
+    assert_eq!(
+        paths(&args),
+        ("backup@nas.local:/volume1/docs/", "/home/docs/")
+    );
+}
+
+#[test]
+fn test_preview_restore_dry_runs_and_reports_changes() {
+    let rsync = CannedRsync::new(RESTORE_DRY_RUN);
+    let job = create_mirror_job("/home/docs/", "/backups/docs/");
+
+    let preview = preview_restore(&job, &rsync, None, true).unwrap();
+
+    let args = rsync.dry_run_args.borrow();
+    assert!(args.contains(&"--dry-run".to_string()));
+    assert!(!args.contains(&"--delete".to_string()));
+    assert_eq!(paths(&args), ("/backups/docs/", "/home/docs/"));
+
+    let changed: Vec<&str> = preview.changes.iter().map(|c| c.path.as_str()).collect();
+    assert_eq!(changed, vec!["docs/report.pdf", "notes.txt"]);
+    assert_eq!(preview.truncated, 0);
+    assert_eq!(preview.files_to_restore, 2);
+    assert_eq!(preview.bytes_to_restore, 4_096);
+}
+
+#[test]
+fn test_preview_restore_command_is_not_a_dry_run() {
+    let rsync = CannedRsync::new(RESTORE_DRY_RUN);
+    let mut job = create_mirror_job("/home/docs/", "/backups/docs/");
+    job.options.core_transfer.dry_run = true;
+
+    let preview = preview_restore(&job, &rsync, None, true).unwrap();
+
+    assert!(preview.command.starts_with("rsync "));
+    assert!(!preview.command.contains("--dry-run"));
+    assert!(!preview.command.contains("--delete"));
+    assert!(preview.command.ends_with("/backups/docs/ /home/docs/"));
+}
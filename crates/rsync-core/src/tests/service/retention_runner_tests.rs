@@ -22,6 +22,9 @@ fn make_invocation(job_id: Uuid, age_days: i64) -> BackupInvocation {
         finished_at: Some(Utc::now() - Duration::days(age_days)),
         status: InvocationStatus::Succeeded,
         trigger: InvocationTrigger::Manual,
+        rerun_of: None,
+        resumed_from: None,
+        warning: None,
         transfer_stats: TransferStats::default(),
         execution_output: ExecutionOutput {
             command_executed: "rsync -a /src /dst".to_string(),
@@ -102,6 +105,9 @@ fn test_retention_runner_prunes_excess_per_job() {
         .set_retention_settings(&crate::models::settings::RetentionSettings {
             max_log_age_days: 365,
             max_history_per_job: 2,
+            max_success_per_job: None,
+            max_failure_per_job: None,
+            max_record_age_days: None,
         })
         .expect("set retention");
 
@@ -122,3 +128,153 @@ fn test_retention_runner_prunes_excess_per_job() {
         .expect("get history");
     assert_eq!(remaining.len(), 2);
 }
+
+#[test]
+fn test_retention_runner_deletes_rotated_and_structured_log_files() {
+    let (job_service, settings_service, _db) = setup_services();
+    let tmp = tempfile::tempdir().expect("tempdir");
+
+    let job = crate::tests::test_helpers::create_test_job();
+    let created = job_service.create_job(job).expect("create job");
+
+    let log_path = tmp.path().join("Nightly").join("2025-01-01.log");
+    std::fs::create_dir_all(log_path.parent().unwrap()).unwrap();
+    let log_str = log_path.to_str().unwrap().to_string();
+    std::fs::write(&log_path, "current").unwrap();
+    std::fs::write(format!("{}.1", log_str), "rotated").unwrap();
+    let jsonl_path = tmp.path().join("Nightly").join("2025-01-01.jsonl");
+    std::fs::write(&jsonl_path, "{}\n").unwrap();
+    let rsync_log = tmp.path().join("Nightly").join("2025-01-01.rsync.log");
+    std::fs::write(&rsync_log, "rsync").unwrap();
+
+    let mut old_inv = make_invocation(created.id, 100);
+    old_inv.execution_output.log_file_path = Some(log_str.clone());
+    job_service.record_invocation(&old_inv).expect("record inv");
+    // A newer success, so the old one is not protected as the latest
+    job_service
+        .record_invocation(&make_invocation(created.id, 1))
+        .expect("record inv");
+
+    let count = run_history_retention(&job_service, &settings_service);
+
+    assert_eq!(count, 1);
+    assert!(!log_path.exists());
+    assert!(!std::path::Path::new(&format!("{}.1", log_str)).exists());
+    assert!(!jsonl_path.exists());
+    assert!(!rsync_log.exists());
+}
+
+#[test]
+fn test_retention_runner_applies_split_limits() {
+    let (job_service, settings_service, _db) = setup_services();
+    let retention = crate::models::settings::RetentionSettings {
+        max_log_age_days: 365,
+        max_history_per_job: 2,
+        max_success_per_job: Some(1),
+        max_failure_per_job: Some(5),
+        max_record_age_days: None,
+    };
+    settings_service
+        .set_retention_settings(&retention)
+        .expect("set retention");
+    assert_eq!(
+        settings_service.get_retention_settings().unwrap(),
+        retention
+    );
+
+    let job = crate::tests::test_helpers::create_test_job();
+    let created = job_service.create_job(job).expect("create job");
+    for i in 0..6 {
+        let mut inv = make_invocation(created.id, i);
+        if i % 2 == 1 {
+            inv.status = InvocationStatus::Failed;
+        }
+        job_service.record_invocation(&inv).expect("record inv");
+    }
+
+    let count = run_history_retention(&job_service, &settings_service);
+    assert_eq!(count, 2); // two older successes
+
+    let remaining = job_service
+        .get_job_history(&created.id, 100)
+        .expect("get history");
+    let failures = remaining
+        .iter()
+        .filter(|inv| inv.status == InvocationStatus::Failed)
+        .count();
+    assert_eq!(remaining.len(), 4);
+    assert_eq!(failures, 3);
+}
+
+#[test]
+fn test_retention_runner_keeps_latest_success() {
+    let (job_service, settings_service, _db) = setup_services();
+
+    let job = crate::tests::test_helpers::create_test_job();
+    let created = job_service.create_job(job).expect("create job");
+
+    // Only success is 200 days old, well past the 90-day default
+    let old_success = make_invocation(created.id, 200);
+    job_service
+        .record_invocation(&old_success)
+        .expect("record inv");
+    let mut old_failure = make_invocation(created.id, 150);
+    old_failure.status = InvocationStatus::Failed;
+    job_service
+        .record_invocation(&old_failure)
+        .expect("record inv");
+
+    let count = run_history_retention(&job_service, &settings_service);
+    assert_eq!(count, 1);
+
+    let remaining = job_service
+        .get_job_history(&created.id, 100)
+        .expect("get history");
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].id, old_success.id);
+}
+
+#[test]
+fn test_retention_runner_clears_logs_before_deleting_records() {
+    let (job_service, settings_service, _db) = setup_services();
+    let tmp = tempfile::tempdir().expect("tempdir");
+    settings_service
+        .set_retention_settings(&crate::models::settings::RetentionSettings {
+            max_log_age_days: 30,
+            max_history_per_job: 100,
+            max_success_per_job: None,
+            max_failure_per_job: None,
+            max_record_age_days: Some(365),
+        })
+        .expect("set retention");
+
+    let job = crate::tests::test_helpers::create_test_job();
+    let created = job_service.create_job(job).expect("create job");
+    job_service
+        .record_invocation(&make_invocation(created.id, 1))
+        .expect("record inv");
+
+    let with_log = |age_days: i64, name: &str| {
+        let path = tmp.path().join(name);
+        std::fs::write(&path, "log").unwrap();
+        let mut inv = make_invocation(created.id, age_days);
+        inv.execution_output.log_file_path = Some(path.to_str().unwrap().to_string());
+        job_service.record_invocation(&inv).expect("record inv");
+        (inv.id, path)
+    };
+    let (middle_id, middle_log) = with_log(60, "middle.log");
+    let (old_id, old_log) = with_log(400, "old.log");
+
+    let count = run_history_retention(&job_service, &settings_service);
+
+    assert_eq!(count, 1);
+    assert!(job_service.get_invocation(&old_id).is_err());
+    assert!(!old_log.exists());
+
+    let middle = job_service.get_invocation(&middle_id).expect("record kept");
+    assert_eq!(middle.execution_output.log_file_path, None);
+    assert!(!middle_log.exists());
+
+    let retention = settings_service.get_retention_settings().unwrap();
+    assert_eq!(retention.max_record_age_days, Some(365));
+}
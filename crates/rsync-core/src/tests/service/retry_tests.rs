@@ -0,0 +1,140 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::database::sqlite::Database;
+use crate::models::backup::{
+    BackupInvocation, ExecutionOutput, InvocationStatus, InvocationTrigger, TransferStats,
+};
+use crate::models::itemize::ItemizedChange;
+use crate::models::progress::{JobStatusEvent, LogLine, ProgressUpdate};
+use crate::repository::sqlite::invocation::SqliteInvocationRepository;
+use crate::repository::sqlite::job::SqliteJobRepository;
+use crate::repository::sqlite::settings::SqliteSettingsRepository;
+use crate::repository::sqlite::snapshot::SqliteSnapshotRepository;
+use crate::repository::sqlite::statistics::SqliteStatisticsRepository;
+use crate::services::execution_handler::ExecutionEventHandler;
+use crate::services::job_executor::JobExecutor;
+use crate::services::job_service::JobService;
+use crate::services::rerun::job_for_retry;
+use crate::services::running_jobs::RunningJobs;
+use crate::services::settings_service::SettingsService;
+use crate::services::statistics_service::StatisticsService;
+use crate::tests::test_helpers::{create_mirror_job, create_test_job};
+
+struct NoopHandler;
+
+impl ExecutionEventHandler for NoopHandler {
+    fn on_log_line(&self, _line: LogLine) {}
+    fn on_progress(&self, _progress: &ProgressUpdate) {}
+    fn on_status_change(&self, _status: JobStatusEvent) {}
+    fn on_itemized_change(&self, _invocation_id: Uuid, _change: &ItemizedChange) {}
+}
+
+/// An executor in test mode, so a retry never writes even where rsync is installed.
+fn setup(log_dir: &str) -> (JobExecutor, Arc<JobService>, Database) {
+    let db = Database::in_memory().unwrap();
+    let conn = db.conn();
+    let job_service = Arc::new(JobService::new(
+        Arc::new(SqliteJobRepository::new(conn.clone())),
+        Arc::new(SqliteInvocationRepository::new(conn.clone())),
+        Arc::new(SqliteSnapshotRepository::new(conn.clone())),
+    ));
+    let settings_service = Arc::new(SettingsService::new(Arc::new(
+        SqliteSettingsRepository::new(conn.clone()),
+    )));
+    settings_service.set_test_mode(true).unwrap();
+    let executor = JobExecutor::new(
+        Arc::clone(&job_service),
+        Arc::new(StatisticsService::new(Arc::new(
+            SqliteStatisticsRepository::new(conn),
+        ))),
+        settings_service,
+        Arc::new(RunningJobs::new()),
+        log_dir.to_string(),
+    );
+    (executor, job_service, db)
+}
+
+fn record_run(svc: &JobService, job_id: Uuid, status: InvocationStatus) -> BackupInvocation {
+    let inv = BackupInvocation {
+        id: Uuid::new_v4(),
+        job_id,
+        started_at: Utc::now(),
+        finished_at: Some(Utc::now()),
+        status,
+        trigger: InvocationTrigger::Scheduled,
+        rerun_of: None,
+        resumed_from: None,
+        warning: None,
+        transfer_stats: TransferStats::default(),
+        execution_output: ExecutionOutput {
+            command_executed: "rsync -a /src/ /dst/".to_string(),
+            exit_code: Some(23),
+            snapshot_path: None,
+            log_file_path: None,
+        },
+    };
+    svc.record_invocation(&inv).unwrap();
+    inv
+}
+
+#[test]
+fn test_job_for_retry_forces_resume_flags_once() {
+    let job = create_test_job();
+    assert!(!job.options.core_transfer.partial);
+
+    let retry = job_for_retry(&job);
+    assert!(retry.options.core_transfer.partial);
+    assert_eq!(retry.options.advanced.custom_args, vec!["--append-verify"]);
+
+    let again = job_for_retry(&retry);
+    assert_eq!(again.options.advanced.custom_args, vec!["--append-verify"]);
+}
+
+#[test]
+fn test_retry_records_linked_invocation_with_resume_flags() {
+    let tmp = tempfile::tempdir().unwrap();
+    let src = tmp.path().join("src");
+    std::fs::create_dir(&src).unwrap();
+    let (executor, svc, _db) = setup(&tmp.path().join("logs").to_string_lossy());
+    let job = svc
+        .create_job(create_mirror_job(
+            &src.to_string_lossy(),
+            &tmp.path().join("dst").to_string_lossy(),
+        ))
+        .unwrap();
+    let original = record_run(&svc, job.id, InvocationStatus::Failed);
+
+    // Without rsync installed the spawn fails after the invocation is recorded
+    let _ = executor.retry(&original.id, Arc::new(NoopHandler));
+
+    let retry = svc
+        .get_job_history(&job.id, 10)
+        .unwrap()
+        .into_iter()
+        .find(|inv| inv.id != original.id)
+        .expect("retry invocation recorded");
+    assert_eq!(retry.rerun_of, Some(original.id));
+    assert_eq!(retry.trigger, InvocationTrigger::Manual);
+    let command = &retry.execution_output.command_executed;
+    assert!(command.contains("--partial"), "{}", command);
+    assert!(command.contains("--append-verify"), "{}", command);
+    executor.cancel_all();
+}
+
+#[test]
+fn test_retry_refuses_successful_run() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (executor, svc, _db) = setup(&tmp.path().to_string_lossy());
+    let job = svc.create_job(create_test_job()).unwrap();
+    let original = record_run(&svc, job.id, InvocationStatus::Succeeded);
+
+    let err = executor
+        .retry(&original.id, Arc::new(NoopHandler))
+        .unwrap_err();
+
+    assert!(err.contains("Only failed or interrupted runs can be retried"));
+    assert_eq!(svc.get_job_history(&job.id, 10).unwrap().len(), 1);
+}
@@ -0,0 +1,158 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::database::sqlite::Database;
+use crate::models::backup::InvocationTrigger;
+use crate::models::itemize::ItemizedChange;
+use crate::models::job::JobDefinition;
+use crate::models::progress::{JobStatusEvent, LogLine, ProgressUpdate};
+use crate::repository::sqlite::invocation::SqliteInvocationRepository;
+use crate::repository::sqlite::job::SqliteJobRepository;
+use crate::repository::sqlite::settings::SqliteSettingsRepository;
+use crate::repository::sqlite::snapshot::SqliteSnapshotRepository;
+use crate::repository::sqlite::statistics::SqliteStatisticsRepository;
+use crate::services::execution_handler::ExecutionEventHandler;
+use crate::services::job_executor::JobExecutor;
+use crate::services::job_service::JobService;
+use crate::services::run_conditions::{check_run_conditions, RunCondition};
+use crate::services::running_jobs::RunningJobs;
+use crate::services::settings_service::SettingsService;
+use crate::services::statistics_service::StatisticsService;
+use crate::tests::test_helpers::create_mirror_job;
+
+/// Mock environment that always reports a metered network connection.
+struct MeteredEnvironment {
+    checks: AtomicUsize,
+}
+
+impl MeteredEnvironment {
+    fn new() -> Self {
+        Self {
+            checks: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl RunCondition for MeteredEnvironment {
+    fn skip_reason(&self, _job: &JobDefinition) -> Option<String> {
+        self.checks.fetch_add(1, Ordering::SeqCst);
+        Some("network connection is metered".to_string())
+    }
+}
+
+struct NoopHandler;
+
+impl ExecutionEventHandler for NoopHandler {
+    fn on_log_line(&self, _line: LogLine) {}
+    fn on_progress(&self, _progress: &ProgressUpdate) {}
+    fn on_status_change(&self, _status: JobStatusEvent) {}
+    fn on_itemized_change(&self, _invocation_id: Uuid, _change: &ItemizedChange) {}
+}
+
+fn setup_executor(
+    condition: Arc<dyn RunCondition>,
+    log_dir: &str,
+) -> (JobExecutor, Arc<JobService>, Database) {
+    let db = Database::in_memory().unwrap();
+    let conn = db.conn();
+    let job_service = Arc::new(JobService::new(
+        Arc::new(SqliteJobRepository::new(conn.clone())),
+        Arc::new(SqliteInvocationRepository::new(conn.clone())),
+        Arc::new(SqliteSnapshotRepository::new(conn.clone())),
+    ));
+    let statistics_service = Arc::new(StatisticsService::new(Arc::new(
+        SqliteStatisticsRepository::new(conn.clone()),
+    )));
+    let settings_service = Arc::new(SettingsService::new(Arc::new(
+        SqliteSettingsRepository::new(conn),
+    )));
+
+    let executor = JobExecutor::new(
+        Arc::clone(&job_service),
+        statistics_service,
+        settings_service,
+        Arc::new(RunningJobs::new()),
+        log_dir.to_string(),
+    )
+    .with_run_condition(condition);
+
+    (executor, job_service, db)
+}
+
+#[test]
+fn test_check_run_conditions_manual_bypasses_guards() {
+    let job = create_mirror_job("/src/", "/dst/");
+    let env = Arc::new(MeteredEnvironment::new());
+    let conditions: Vec<Arc<dyn RunCondition>> = vec![env.clone()];
+
+    let reason = check_run_conditions(&job, &InvocationTrigger::Manual, &conditions);
+
+    assert!(reason.is_none());
+    assert_eq!(env.checks.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn test_check_run_conditions_scheduled_respects_guards() {
+    let job = create_mirror_job("/src/", "/dst/");
+    let env = Arc::new(MeteredEnvironment::new());
+    let conditions: Vec<Arc<dyn RunCondition>> = vec![env.clone()];
+
+    let reason = check_run_conditions(&job, &InvocationTrigger::Scheduled, &conditions);
+
+    assert_eq!(reason.as_deref(), Some("network connection is metered"));
+    assert_eq!(env.checks.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_check_run_conditions_scheduled_without_guards_runs() {
+    let job = create_mirror_job("/src/", "/dst/");
+    assert!(check_run_conditions(&job, &InvocationTrigger::Scheduled, &[]).is_none());
+}
+
+#[test]
+fn test_executor_skips_scheduled_run_when_metered() {
+    let tmp = tempfile::tempdir().unwrap();
+    let env = Arc::new(MeteredEnvironment::new());
+    let (executor, job_service, _db) = setup_executor(env.clone(), tmp.path().to_str().unwrap());
+    let job = job_service
+        .create_job(create_mirror_job("/src/", "/dst/"))
+        .unwrap();
+
+    let result = executor.execute(&job, InvocationTrigger::Scheduled, Arc::new(NoopHandler));
+
+    let err = result.unwrap_err();
+    assert!(err.contains("network connection is metered"));
+    assert_eq!(env.checks.load(Ordering::SeqCst), 1);
+    assert!(job_service.get_job_history(&job.id, 10).unwrap().is_empty());
+}
+
+#[test]
+fn test_executor_runs_manual_trigger_when_metered() {
+    let tmp = tempfile::tempdir().unwrap();
+    let src = tmp.path().join("src");
+    let dst = tmp.path().join("dst");
+    std::fs::create_dir_all(&src).unwrap();
+    let log_dir = tmp.path().join("logs");
+    let env = Arc::new(MeteredEnvironment::new());
+    let (executor, job_service, _db) = setup_executor(env.clone(), log_dir.to_str().unwrap());
+    let job = job_service
+        .create_job(create_mirror_job(
+            &format!("{}/", src.display()),
+            &format!("{}/", dst.display()),
+        ))
+        .unwrap();
+
+    // The spawn itself may fail where rsync isn't installed; what matters is
+    // that the guard was never consulted and an invocation was recorded.
+    let result = executor.execute(&job, InvocationTrigger::Manual, Arc::new(NoopHandler));
+
+    if let Err(e) = &result {
+        assert!(!e.contains("skipped"));
+    }
+    assert_eq!(env.checks.load(Ordering::SeqCst), 0);
+    let history = job_service.get_job_history(&job.id, 10).unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].trigger, InvocationTrigger::Manual);
+}
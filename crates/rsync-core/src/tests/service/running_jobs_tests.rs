@@ -1,8 +1,26 @@
 use std::process::Command;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 
 use uuid::Uuid;
 
-use crate::services::running_jobs::RunningJobs;
+use crate::database::sqlite::Database;
+use crate::models::backup::InvocationTrigger;
+use crate::models::job::{JobDefinition, JobStatus};
+use crate::models::progress::ProgressUpdate;
+use crate::repository::sqlite::invocation::SqliteInvocationRepository;
+use crate::repository::sqlite::job::SqliteJobRepository;
+use crate::repository::sqlite::settings::SqliteSettingsRepository;
+use crate::repository::sqlite::snapshot::SqliteSnapshotRepository;
+use crate::repository::sqlite::statistics::SqliteStatisticsRepository;
+use crate::services::job_executor::JobExecutor;
+use crate::services::job_service::JobService;
+use crate::services::running_jobs::{running_job_info, RunningJobs};
+use crate::services::settings_service::SettingsService;
+use crate::services::statistics_service::StatisticsService;
+use crate::tests::mock_executor::{mock_rsync_executor, StatusHandler};
+use crate::tests::test_helpers::create_test_job;
 
 fn spawn_sleep_child() -> std::process::Child {
     Command::new("sleep")
@@ -11,6 +29,47 @@ fn spawn_sleep_child() -> std::process::Child {
         .expect("failed to spawn sleep")
 }
 
+/// An executor over in-memory services sharing `running_jobs`, so tests can
+/// plant processes without starting real runs.
+fn executor_with(running_jobs: Arc<RunningJobs>) -> (JobExecutor, Database) {
+    let db = Database::in_memory().unwrap();
+    let conn = db.conn();
+    let job_service = Arc::new(JobService::new(
+        Arc::new(SqliteJobRepository::new(conn.clone())),
+        Arc::new(SqliteInvocationRepository::new(conn.clone())),
+        Arc::new(SqliteSnapshotRepository::new(conn.clone())),
+    ));
+    let statistics_service = Arc::new(StatisticsService::new(Arc::new(
+        SqliteStatisticsRepository::new(conn.clone()),
+    )));
+    let settings_service = Arc::new(SettingsService::new(Arc::new(
+        SqliteSettingsRepository::new(conn),
+    )));
+    let executor = JobExecutor::new(
+        job_service,
+        statistics_service,
+        settings_service,
+        running_jobs,
+        "/tmp/logs".to_string(),
+    );
+    (executor, db)
+}
+
+fn progress(rate: &str, bytes: u64, remaining: u64, total: u64, percentage: f64) -> ProgressUpdate {
+    ProgressUpdate {
+        invocation_id: Uuid::new_v4(),
+        bytes_transferred: bytes,
+        percentage,
+        transfer_rate: rate.to_string(),
+        elapsed: "0:00:05".to_string(),
+        files_transferred: total - remaining,
+        files_remaining: remaining,
+        files_total: total,
+        eta_secs: None,
+        current_file: None,
+    }
+}
+
 #[test]
 fn test_insert_and_is_running() {
     let rj = RunningJobs::new();
@@ -92,3 +151,170 @@ fn test_remove_nonexistent_returns_none() {
     let rj = RunningJobs::new();
     assert!(rj.remove(&Uuid::new_v4()).is_none());
 }
+
+#[test]
+fn test_aggregate_progress_empty() {
+    let rj = RunningJobs::new();
+    let agg = rj.aggregate_progress();
+    assert_eq!(agg.active_jobs, 0);
+    assert_eq!(agg.bytes_per_sec, 0.0);
+    assert_eq!(agg.percentage, 0.0);
+}
+
+#[test]
+fn test_aggregate_progress_sums_across_jobs() {
+    let rj = RunningJobs::new();
+    rj.update_progress(Uuid::new_v4(), progress("2.00MB/s", 1_000, 6, 10, 50.0));
+    rj.update_progress(Uuid::new_v4(), progress("500.00kB/s", 3_000, 4, 30, 80.0));
+
+    let agg = rj.aggregate_progress();
+
+    assert_eq!(agg.active_jobs, 2);
+    assert_eq!(agg.bytes_per_sec, 2_500_000.0);
+    assert_eq!(agg.bytes_transferred, 4_000);
+    assert_eq!(agg.files_done, 30);
+    assert_eq!(agg.files_total, 40);
+    assert_eq!(agg.percentage, 75.0);
+}
+
+#[test]
+fn test_aggregate_progress_uses_latest_snapshot_per_job() {
+    let rj = RunningJobs::new();
+    let id = Uuid::new_v4();
+    rj.update_progress(id, progress("1.00MB/s", 100, 9, 10, 10.0));
+    rj.update_progress(id, progress("3.00MB/s", 900, 1, 10, 90.0));
+
+    let agg = rj.aggregate_progress();
+
+    assert_eq!(agg.active_jobs, 1);
+    assert_eq!(agg.bytes_per_sec, 3_000_000.0);
+    assert_eq!(agg.files_done, 9);
+    assert_eq!(agg.percentage, 90.0);
+}
+
+#[test]
+fn test_aggregate_progress_averages_percentage_without_file_counts() {
+    let rj = RunningJobs::new();
+    rj.update_progress(Uuid::new_v4(), progress("1.00kB/s", 10, 0, 0, 20.0));
+    rj.update_progress(Uuid::new_v4(), progress("1.00kB/s", 10, 0, 0, 60.0));
+
+    let agg = rj.aggregate_progress();
+
+    assert_eq!(agg.files_total, 0);
+    assert_eq!(agg.percentage, 40.0);
+}
+
+#[test]
+fn test_remove_clears_progress() {
+    let rj = RunningJobs::new();
+    let id = Uuid::new_v4();
+    rj.insert(id, spawn_sleep_child());
+    rj.update_progress(id, progress("1.00MB/s", 100, 1, 2, 50.0));
+
+    let removed = rj.remove(&id);
+    assert_eq!(rj.aggregate_progress().active_jobs, 0);
+
+    if let Some(arc) = removed {
+        if let Ok(mut c) = arc.lock() {
+            let _ = c.kill();
+            let _ = c.wait();
+        }
+    }
+}
+
+#[test]
+fn test_cancel_all_stops_every_running_job() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (executor, svc, _db) = mock_rsync_executor(tmp.path(), "exec sleep 60");
+    let (tx, rx) = mpsc::channel();
+    let handler = Arc::new(StatusHandler(Mutex::new(tx)));
+    let jobs: Vec<JobDefinition> = (0..3)
+        .map(|_| svc.create_job(create_test_job()).unwrap())
+        .collect();
+    for job in &jobs {
+        executor
+            .execute(job, InvocationTrigger::Manual, handler.clone())
+            .unwrap();
+    }
+
+    assert_eq!(executor.cancel_all(), 3);
+
+    // Each executor thread records its run and then leaves the running set
+    for _ in &jobs {
+        let event = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+        assert_eq!(event.status, JobStatus::Cancelled);
+    }
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while !executor.running_job_ids().is_empty() && Instant::now() < deadline {
+        thread::sleep(Duration::from_millis(10));
+    }
+    for job in &jobs {
+        assert!(!executor.is_running(&job.id));
+    }
+    assert_eq!(executor.aggregate_progress().active_jobs, 0);
+}
+
+#[test]
+fn test_cancel_all_leaves_removal_to_the_running_job() {
+    let running_jobs = Arc::new(RunningJobs::new());
+    let (executor, _db) = executor_with(Arc::clone(&running_jobs));
+    let id = Uuid::new_v4();
+    let arc = running_jobs.insert(id, spawn_sleep_child());
+
+    assert_eq!(executor.cancel_all(), 1);
+
+    // Killed, but still held until its executor thread removes it
+    assert!(executor.is_running(&id));
+    let status = arc.lock().unwrap().wait().unwrap();
+    assert!(status.code().is_none());
+    running_jobs.remove(&id);
+    assert!(!executor.is_running(&id));
+}
+
+#[test]
+fn test_cancel_all_with_nothing_running() {
+    let (executor, _db) = executor_with(Arc::new(RunningJobs::new()));
+    assert_eq!(executor.cancel_all(), 0);
+    assert_eq!(executor.cancel_all(), 0);
+}
+
+#[test]
+fn test_running_job_info_maps_progress_snapshot() {
+    let id = Uuid::new_v4();
+    let mut snapshot = progress("2.00MB/s", 1_000, 6, 10, 42.5);
+    snapshot.current_file = Some("photos/img_0001.jpg".to_string());
+
+    let info = running_job_info(id, "Photos".to_string(), Some(&snapshot));
+
+    assert_eq!(info.id, id);
+    assert_eq!(info.name, "Photos");
+    assert_eq!(info.percentage, 42.5);
+    assert_eq!(info.current_file.as_deref(), Some("photos/img_0001.jpg"));
+    assert_eq!(info.bytes_per_sec, 2_000_000.0);
+}
+
+#[test]
+fn test_running_job_info_without_progress_yet() {
+    let info = running_job_info(Uuid::new_v4(), "Photos".to_string(), None);
+
+    assert_eq!(info.percentage, 0.0);
+    assert_eq!(info.current_file, None);
+    assert_eq!(info.bytes_per_sec, 0.0);
+}
+
+#[test]
+fn test_running_jobs_info_names_each_running_job() {
+    let running_jobs = Arc::new(RunningJobs::new());
+    let (executor, _db) = executor_with(Arc::clone(&running_jobs));
+    let unknown = Uuid::new_v4();
+    running_jobs.insert(unknown, spawn_sleep_child());
+    running_jobs.update_progress(unknown, progress("1.00MB/s", 100, 1, 2, 50.0));
+
+    let infos = executor.running_jobs_info();
+
+    assert_eq!(infos.len(), 1);
+    // A job missing from the database falls back to its id
+    assert_eq!(infos[0].name, unknown.to_string());
+    assert_eq!(infos[0].percentage, 50.0);
+    executor.cancel_all();
+}
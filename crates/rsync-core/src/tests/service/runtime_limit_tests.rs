@@ -0,0 +1,126 @@
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::services::runtime_limit::{
+    parse_rsync_version, stop_after_arg, stopped_by_limit, supports_stop_after, RuntimeWatchdog,
+    RSYNC_TIMEOUT_EXIT_CODE,
+};
+use crate::tests::test_helpers::create_test_job;
+
+const MIN: Duration = Duration::from_secs(60);
+
+#[test]
+fn test_stop_after_arg_uses_whole_minutes() {
+    assert_eq!(
+        stop_after_arg(Duration::from_secs(90 * 60)),
+        "--stop-after=90"
+    );
+    assert_eq!(stop_after_arg(Duration::from_secs(61)), "--stop-after=2");
+    assert_eq!(stop_after_arg(Duration::from_secs(5)), "--stop-after=1");
+}
+
+#[test]
+fn test_job_max_runtime_converts_minutes() {
+    let mut job = create_test_job();
+    assert_eq!(job.max_runtime(), None);
+    job.run_policy.max_runtime_mins = Some(45);
+    assert_eq!(job.max_runtime(), Some(Duration::from_secs(45 * 60)));
+}
+
+#[test]
+fn test_parse_rsync_version() {
+    assert_eq!(
+        parse_rsync_version("rsync  version 3.2.7  protocol version 31"),
+        Some((3, 2, 7))
+    );
+    assert_eq!(
+        parse_rsync_version("rsync  version v3.2.3  protocol version 31"),
+        None
+    );
+    assert_eq!(
+        parse_rsync_version("openrsync: protocol version 29\nrsync version 2.6.9 compatible"),
+        Some((2, 6, 9))
+    );
+    assert_eq!(parse_rsync_version("not rsync"), None);
+}
+
+#[test]
+fn test_supports_stop_after_from_3_2_3() {
+    assert!(supports_stop_after(
+        "rsync  version 3.2.3  protocol version 31"
+    ));
+    assert!(supports_stop_after(
+        "rsync  version 3.3.0  protocol version 32"
+    ));
+    assert!(!supports_stop_after(
+        "rsync  version 3.2.2  protocol version 31"
+    ));
+    assert!(!supports_stop_after(
+        "rsync  version 2.6.9  protocol version 29"
+    ));
+}
+
+#[test]
+fn test_stopped_by_limit_when_watchdog_fired() {
+    assert!(stopped_by_limit(true, false, None, MIN, 2 * MIN));
+}
+
+#[test]
+fn test_stopped_by_limit_when_rsync_timed_out_past_limit() {
+    let code = Some(RSYNC_TIMEOUT_EXIT_CODE);
+    assert!(stopped_by_limit(false, true, code, 2 * MIN, 2 * MIN));
+    // An I/O timeout before the limit is an ordinary failure
+    assert!(!stopped_by_limit(false, true, code, MIN, 2 * MIN));
+    // Without --stop-after, exit code 30 can only be an I/O timeout
+    assert!(!stopped_by_limit(false, false, code, 3 * MIN, 2 * MIN));
+    assert!(!stopped_by_limit(false, true, Some(23), 3 * MIN, 2 * MIN));
+}
+
+#[cfg(unix)]
+fn spawn_sleep(secs: &str) -> Arc<Mutex<std::process::Child>> {
+    let child = Command::new("sleep")
+        .arg(secs)
+        .stdout(Stdio::null())
+        .spawn()
+        .unwrap();
+    Arc::new(Mutex::new(child))
+}
+
+/// Wait for the child without holding its lock while it runs.
+#[cfg(unix)]
+fn wait_for_exit(child: &Arc<Mutex<std::process::Child>>) -> std::process::ExitStatus {
+    loop {
+        if let Some(status) = child.lock().unwrap().try_wait().unwrap() {
+            return status;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn test_watchdog_kills_process_past_deadline() {
+    let child = spawn_sleep("10");
+    let start = Instant::now();
+    let watchdog = RuntimeWatchdog::spawn(Arc::clone(&child), Duration::from_millis(100));
+
+    let status = wait_for_exit(&child);
+
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert_eq!(status.code(), None);
+    assert!(watchdog.finish());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_watchdog_leaves_finished_process_alone() {
+    let child = spawn_sleep("0");
+    let watchdog = RuntimeWatchdog::spawn(Arc::clone(&child), Duration::from_millis(200));
+
+    let status = wait_for_exit(&child);
+    std::thread::sleep(Duration::from_millis(300));
+
+    assert_eq!(status.code(), Some(0));
+    assert!(!watchdog.finish());
+}
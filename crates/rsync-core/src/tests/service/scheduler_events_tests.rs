@@ -0,0 +1,271 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::database::sqlite::Database;
+use crate::models::backup::{
+    BackupInvocation, ExecutionOutput, InvocationStatus, InvocationTrigger, TransferStats,
+};
+use crate::models::job::JobDefinition;
+use crate::models::schedule::{ScheduleConfig, ScheduleType, SchedulerDecision};
+use crate::repository::sqlite::invocation::SqliteInvocationRepository;
+use crate::repository::sqlite::job::SqliteJobRepository;
+use crate::repository::sqlite::snapshot::SqliteSnapshotRepository;
+use crate::services::job_service::JobService;
+use crate::services::scheduler_backend::{run_scheduler_cycle, ScheduledRunner};
+use crate::services::scheduler_events::SchedulerEventLog;
+use crate::tests::test_helpers::create_test_job;
+
+/// Runner that records which jobs it was asked to start instead of spawning rsync.
+#[derive(Default)]
+struct FakeRunner {
+    running: HashSet<Uuid>,
+    refuse_with: Option<String>,
+    started: Mutex<Vec<Uuid>>,
+}
+
+impl ScheduledRunner for FakeRunner {
+    fn is_running(&self, job_id: &Uuid) -> bool {
+        self.running.contains(job_id)
+    }
+
+    fn run_scheduled(&self, job: &JobDefinition) -> Result<Uuid, String> {
+        if let Some(reason) = &self.refuse_with {
+            return Err(reason.clone());
+        }
+        self.started.lock().unwrap().push(job.id);
+        Ok(Uuid::new_v4())
+    }
+}
+
+fn setup() -> JobService {
+    let db = Database::in_memory().unwrap();
+    let conn = db.conn();
+    JobService::new(
+        Arc::new(SqliteJobRepository::new(conn.clone())),
+        Arc::new(SqliteInvocationRepository::new(conn.clone())),
+        Arc::new(SqliteSnapshotRepository::new(conn)),
+    )
+}
+
+fn hourly_job(svc: &JobService) -> JobDefinition {
+    let mut job = create_test_job();
+    job.schedule = Some(ScheduleConfig {
+        schedule_type: ScheduleType::Interval { minutes: 60 },
+        enabled: true,
+    });
+    svc.create_job(job).unwrap()
+}
+
+fn record_run_at(svc: &JobService, job_id: Uuid, started_at: chrono::DateTime<Utc>) {
+    svc.record_invocation(&BackupInvocation {
+        id: Uuid::new_v4(),
+        job_id,
+        started_at,
+        finished_at: Some(started_at),
+        status: InvocationStatus::Succeeded,
+        trigger: InvocationTrigger::Scheduled,
+        rerun_of: None,
+        resumed_from: None,
+        warning: None,
+        transfer_stats: TransferStats {
+            bytes_transferred: 0,
+            files_transferred: 0,
+            total_files: 0,
+            files_deleted: 0,
+            detailed: None,
+        },
+        execution_output: ExecutionOutput {
+            command_executed: "rsync -a /src/ /dst/".to_string(),
+            exit_code: Some(0),
+            snapshot_path: None,
+            log_file_path: None,
+        },
+    })
+    .unwrap();
+}
+
+fn decisions(events: &SchedulerEventLog, job_id: &Uuid) -> Vec<SchedulerDecision> {
+    events
+        .scheduler_events(job_id)
+        .into_iter()
+        .map(|e| e.decision)
+        .collect()
+}
+
+#[test]
+fn test_not_due_job_is_logged_as_not_due() {
+    let svc = setup();
+    let job = hourly_job(&svc);
+    let now = Utc::now();
+    record_run_at(&svc, job.id, now - Duration::minutes(10));
+    let runner = FakeRunner::default();
+    let events = SchedulerEventLog::default();
+
+    run_scheduler_cycle(
+        std::slice::from_ref(&job),
+        &svc,
+        &runner,
+        false,
+        now,
+        &events,
+    );
+
+    assert_eq!(decisions(&events, &job.id), vec![SchedulerDecision::NotDue]);
+    assert!(runner.started.lock().unwrap().is_empty());
+}
+
+#[test]
+fn test_running_job_is_logged_as_already_running() {
+    let svc = setup();
+    let job = hourly_job(&svc);
+    let runner = FakeRunner {
+        running: HashSet::from([job.id]),
+        ..Default::default()
+    };
+    let events = SchedulerEventLog::default();
+
+    run_scheduler_cycle(
+        std::slice::from_ref(&job),
+        &svc,
+        &runner,
+        false,
+        Utc::now(),
+        &events,
+    );
+
+    assert_eq!(
+        decisions(&events, &job.id),
+        vec![SchedulerDecision::AlreadyRunning]
+    );
+    assert!(runner.started.lock().unwrap().is_empty());
+}
+
+#[test]
+fn test_due_job_is_executed_and_logged() {
+    let svc = setup();
+    let job = hourly_job(&svc);
+    let now = Utc::now();
+    record_run_at(&svc, job.id, now - Duration::hours(2));
+    let runner = FakeRunner::default();
+    let events = SchedulerEventLog::default();
+
+    run_scheduler_cycle(
+        std::slice::from_ref(&job),
+        &svc,
+        &runner,
+        false,
+        now,
+        &events,
+    );
+
+    assert_eq!(
+        decisions(&events, &job.id),
+        vec![SchedulerDecision::Executed]
+    );
+    assert_eq!(*runner.started.lock().unwrap(), vec![job.id]);
+}
+
+#[test]
+fn test_due_job_during_quiet_hours_is_held_back() {
+    let svc = setup();
+    let job = hourly_job(&svc);
+    let runner = FakeRunner::default();
+    let events = SchedulerEventLog::default();
+
+    run_scheduler_cycle(
+        std::slice::from_ref(&job),
+        &svc,
+        &runner,
+        true,
+        Utc::now(),
+        &events,
+    );
+
+    assert_eq!(
+        decisions(&events, &job.id),
+        vec![SchedulerDecision::QuietHours]
+    );
+    assert!(runner.started.lock().unwrap().is_empty());
+}
+
+#[test]
+fn test_refused_run_keeps_executor_message() {
+    let svc = setup();
+    let job = hourly_job(&svc);
+    let runner = FakeRunner {
+        refuse_with: Some("Scheduled run skipped: network connection is metered".to_string()),
+        ..Default::default()
+    };
+    let events = SchedulerEventLog::default();
+
+    run_scheduler_cycle(
+        std::slice::from_ref(&job),
+        &svc,
+        &runner,
+        false,
+        Utc::now(),
+        &events,
+    );
+
+    match &decisions(&events, &job.id)[..] {
+        [SchedulerDecision::Refused { message }] => assert!(message.contains("metered")),
+        other => panic!("Expected one Refused decision, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_disabled_and_unscheduled_jobs() {
+    let svc = setup();
+    let mut disabled = hourly_job(&svc);
+    disabled.enabled = false;
+    let unscheduled = svc.create_job(create_test_job()).unwrap();
+    let events = SchedulerEventLog::default();
+
+    run_scheduler_cycle(
+        &[disabled.clone(), unscheduled.clone()],
+        &svc,
+        &FakeRunner::default(),
+        false,
+        Utc::now(),
+        &events,
+    );
+
+    assert_eq!(
+        decisions(&events, &disabled.id),
+        vec![SchedulerDecision::Disabled]
+    );
+    assert!(decisions(&events, &unscheduled.id).is_empty());
+}
+
+#[test]
+fn test_event_log_drops_oldest_past_capacity() {
+    let events = SchedulerEventLog::new(2);
+    let job_id = Uuid::new_v4();
+    events.record(job_id, SchedulerDecision::NotDue);
+    events.record(job_id, SchedulerDecision::AlreadyRunning);
+    events.record(job_id, SchedulerDecision::Executed);
+
+    assert_eq!(events.len(), 2);
+    // Newest first
+    assert_eq!(
+        decisions(&events, &job_id),
+        vec![
+            SchedulerDecision::Executed,
+            SchedulerDecision::AlreadyRunning
+        ]
+    );
+}
+
+#[test]
+fn test_event_log_filters_by_job() {
+    let events = SchedulerEventLog::default();
+    let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+    events.record(a, SchedulerDecision::NotDue);
+    events.record(b, SchedulerDecision::Executed);
+
+    assert_eq!(decisions(&events, &a), vec![SchedulerDecision::NotDue]);
+    assert_eq!(decisions(&events, &b), vec![SchedulerDecision::Executed]);
+}
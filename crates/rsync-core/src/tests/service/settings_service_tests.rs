@@ -3,9 +3,16 @@ use std::sync::Arc;
 use chrono::Utc;
 
 use crate::database::sqlite::Database;
-use crate::models::job::{BackupMode, JobDefinition, RsyncOptions, StorageLocation, TransferConfig};
+use crate::models::job::{
+    BackupMode, JobAppearance, JobDefinition, JobRunPolicy, JobSafety, RsyncOptions,
+    StorageLocation, TransferConfig,
+};
 use crate::repository::sqlite::settings::SqliteSettingsRepository;
-use crate::models::settings::DryModeSettings;
+use crate::models::settings::{
+    AnomalySettings, DryModeSettings, LogFileSettings, QuietHours, SafeMirrorSettings,
+    DEFAULT_ITEMIZE_STORE_LIMIT, DEFAULT_LINK_DEST_COUNT,
+};
+use crate::services::command_builder::MAX_LINK_DEST_DIRS;
 use crate::services::settings_service::{apply_dry_mode_settings, SettingsService};
 
 fn setup() -> SettingsService {
@@ -31,6 +38,9 @@ fn make_job() -> JobDefinition {
         },
         options: RsyncOptions::default(),
         ssh_config: None,
+        appearance: JobAppearance::default(),
+        run_policy: JobRunPolicy::default(),
+        safety: JobSafety::default(),
         schedule: None,
         enabled: true,
         created_at: now,
@@ -161,3 +171,276 @@ fn set_and_get_show_output_options() {
     svc.set_show_output_options(true).unwrap();
     assert!(svc.get_show_output_options().unwrap());
 }
+
+#[test]
+fn test_monthly_data_budget_defaults_to_none() {
+    let svc = setup();
+    assert_eq!(svc.get_monthly_data_budget().unwrap(), None);
+}
+
+#[test]
+fn test_set_and_clear_monthly_data_budget() {
+    let svc = setup();
+    svc.set_monthly_data_budget(Some(1_000_000_000_000))
+        .unwrap();
+    assert_eq!(
+        svc.get_monthly_data_budget().unwrap(),
+        Some(1_000_000_000_000)
+    );
+
+    svc.set_monthly_data_budget(None).unwrap();
+    assert_eq!(svc.get_monthly_data_budget().unwrap(), None);
+}
+
+#[test]
+fn test_set_and_clear_pre_run_space_margin() {
+    let svc = setup();
+    assert_eq!(svc.get_pre_run_space_margin().unwrap(), None);
+
+    svc.set_pre_run_space_margin(Some(15)).unwrap();
+    assert_eq!(svc.get_pre_run_space_margin().unwrap(), Some(15));
+
+    svc.set_pre_run_space_margin(None).unwrap();
+    assert_eq!(svc.get_pre_run_space_margin().unwrap(), None);
+}
+
+#[test]
+fn test_set_get_and_delete_daemon_secret() {
+    let svc = setup();
+    assert_eq!(svc.get_daemon_secret("nas").unwrap(), None);
+
+    svc.set_daemon_secret("nas", "hunter2").unwrap();
+    assert_eq!(
+        svc.get_daemon_secret("nas").unwrap().as_deref(),
+        Some("hunter2")
+    );
+    assert_eq!(svc.get_daemon_secret("other").unwrap(), None);
+
+    svc.delete_daemon_secret("nas").unwrap();
+    assert_eq!(svc.get_daemon_secret("nas").unwrap(), None);
+}
+
+#[test]
+fn test_daemon_secret_requires_name() {
+    let svc = setup();
+    assert!(svc.set_daemon_secret("  ", "hunter2").is_err());
+}
+
+#[test]
+fn test_quiet_hours_defaults_to_none() {
+    let svc = setup();
+    assert_eq!(svc.get_quiet_hours().unwrap(), None);
+}
+
+#[test]
+fn test_set_and_clear_quiet_hours() {
+    let svc = setup();
+    let quiet = QuietHours {
+        start_minute: 22 * 60,
+        end_minute: 6 * 60 + 30,
+    };
+    svc.set_quiet_hours(Some(&quiet)).unwrap();
+    assert_eq!(svc.get_quiet_hours().unwrap(), Some(quiet));
+    assert_eq!(
+        svc.get_setting("quiet_hours").unwrap().as_deref(),
+        Some("22:00-06:30")
+    );
+
+    svc.set_quiet_hours(None).unwrap();
+    assert_eq!(svc.get_quiet_hours().unwrap(), None);
+}
+
+#[test]
+fn test_set_quiet_hours_rejects_out_of_range_time() {
+    let svc = setup();
+    let result = svc.set_quiet_hours(Some(&QuietHours {
+        start_minute: 24 * 60,
+        end_minute: 60,
+    }));
+    assert!(result.is_err());
+    assert_eq!(svc.get_quiet_hours().unwrap(), None);
+}
+
+#[test]
+fn test_log_file_settings_defaults() {
+    let svc = setup();
+    let settings = svc.get_log_file_settings().unwrap();
+    assert_eq!(settings.path_template, "{id}.log");
+    assert_eq!(settings.max_size_bytes, None);
+    assert!(!settings.structured);
+    assert_eq!(settings.max_line_bytes, 64 * 1024);
+    assert!(!settings.rsync_log_file);
+    assert_eq!(settings.rsync_log_format, "%i %n%L");
+}
+
+#[test]
+fn test_set_and_get_log_file_settings() {
+    let svc = setup();
+    svc.set_log_file_settings(&LogFileSettings {
+        path_template: "{job}/{date}.log".to_string(),
+        max_size_bytes: Some(10_485_760),
+        structured: true,
+        max_line_bytes: 4096,
+        rsync_log_file: true,
+        rsync_log_format: "%t %o %f %l".to_string(),
+    })
+    .unwrap();
+
+    let settings = svc.get_log_file_settings().unwrap();
+    assert_eq!(settings.path_template, "{job}/{date}.log");
+    assert_eq!(settings.max_size_bytes, Some(10_485_760));
+    assert!(settings.structured);
+    assert_eq!(settings.max_line_bytes, 4096);
+    assert!(settings.rsync_log_file);
+    assert_eq!(settings.rsync_log_format, "%t %o %f %l");
+}
+
+#[test]
+fn test_itemize_store_limit_default_and_set() {
+    let svc = setup();
+    assert_eq!(
+        svc.get_itemize_store_limit().unwrap(),
+        DEFAULT_ITEMIZE_STORE_LIMIT
+    );
+    svc.set_itemize_store_limit(250).unwrap();
+    assert_eq!(svc.get_itemize_store_limit().unwrap(), 250);
+}
+
+#[test]
+fn test_link_dest_count_default_and_set() {
+    let svc = setup();
+    assert_eq!(svc.get_link_dest_count().unwrap(), DEFAULT_LINK_DEST_COUNT);
+    svc.set_link_dest_count(5).unwrap();
+    assert_eq!(svc.get_link_dest_count().unwrap(), 5);
+}
+
+#[test]
+fn test_link_dest_count_rejects_out_of_range() {
+    let svc = setup();
+    assert!(svc.set_link_dest_count(0).is_err());
+    assert!(svc.set_link_dest_count(MAX_LINK_DEST_DIRS + 1).is_err());
+    svc.set_link_dest_count(MAX_LINK_DEST_DIRS).unwrap();
+    assert_eq!(svc.get_link_dest_count().unwrap(), MAX_LINK_DEST_DIRS);
+}
+
+#[test]
+fn test_anomaly_settings_defaults() {
+    let svc = setup();
+    assert_eq!(
+        svc.get_anomaly_settings().unwrap(),
+        AnomalySettings::default()
+    );
+}
+
+#[test]
+fn test_set_and_get_anomaly_settings() {
+    let svc = setup();
+    let settings = AnomalySettings {
+        baseline_runs: 5,
+        factor: 3.5,
+    };
+    svc.set_anomaly_settings(&settings).unwrap();
+    assert_eq!(svc.get_anomaly_settings().unwrap(), settings);
+}
+
+#[test]
+fn test_set_anomaly_settings_rejects_invalid_values() {
+    let svc = setup();
+    let no_runs = AnomalySettings {
+        baseline_runs: 0,
+        factor: 10.0,
+    };
+    let low_factor = AnomalySettings {
+        baseline_runs: 10,
+        factor: 1.0,
+    };
+    assert!(svc.set_anomaly_settings(&no_runs).is_err());
+    assert!(svc.set_anomaly_settings(&low_factor).is_err());
+    assert_eq!(
+        svc.get_anomaly_settings().unwrap(),
+        AnomalySettings::default()
+    );
+}
+
+#[test]
+fn test_safe_mirror_settings_default_to_disabled() {
+    let svc = setup();
+    let settings = svc.get_safe_mirror_settings().unwrap();
+    assert_eq!(settings, SafeMirrorSettings::default());
+    assert!(!settings.enabled);
+}
+
+#[test]
+fn test_set_and_get_safe_mirror_settings() {
+    let svc = setup();
+    let settings = SafeMirrorSettings {
+        enabled: true,
+        max_delete_percent: 25.0,
+    };
+    svc.set_safe_mirror_settings(&settings).unwrap();
+    assert_eq!(svc.get_safe_mirror_settings().unwrap(), settings);
+}
+
+#[test]
+fn test_set_safe_mirror_settings_rejects_out_of_range_percent() {
+    let svc = setup();
+    for max_delete_percent in [-1.0, 100.5, f64::NAN] {
+        let settings = SafeMirrorSettings {
+            enabled: true,
+            max_delete_percent,
+        };
+        assert!(svc.set_safe_mirror_settings(&settings).is_err());
+    }
+    assert_eq!(
+        svc.get_safe_mirror_settings().unwrap(),
+        SafeMirrorSettings::default()
+    );
+}
+
+#[test]
+fn test_known_hosts_path_defaults_to_none() {
+    let svc = setup();
+    assert_eq!(svc.get_known_hosts_path().unwrap(), None);
+}
+
+#[test]
+fn test_set_known_hosts_path() {
+    let svc = setup();
+    svc.set_known_hosts_path("/home/me/.ssh/known_hosts_backup")
+        .unwrap();
+    assert_eq!(
+        svc.get_known_hosts_path().unwrap().as_deref(),
+        Some("/home/me/.ssh/known_hosts_backup")
+    );
+}
+
+#[test]
+fn test_snapshot_trash_dir_set_and_clear() {
+    let svc = setup();
+    assert_eq!(svc.get_snapshot_trash_dir().unwrap(), None);
+
+    svc.set_snapshot_trash_dir("/backups/.trash").unwrap();
+    assert_eq!(
+        svc.get_snapshot_trash_dir().unwrap(),
+        Some("/backups/.trash".to_string())
+    );
+
+    svc.set_snapshot_trash_dir("  ").unwrap();
+    assert_eq!(svc.get_snapshot_trash_dir().unwrap(), None);
+
+    svc.set_snapshot_trash_dir("/backups/.trash").unwrap();
+    svc.clear_snapshot_trash_dir().unwrap();
+    assert_eq!(svc.get_snapshot_trash_dir().unwrap(), None);
+}
+
+#[test]
+fn relative_paths_defaults_to_off() {
+    let svc = setup();
+    assert!(!svc.get_relative_paths().unwrap());
+
+    svc.set_relative_paths(true).unwrap();
+    assert!(svc.get_relative_paths().unwrap());
+
+    svc.set_relative_paths(false).unwrap();
+    assert!(!svc.get_relative_paths().unwrap());
+}
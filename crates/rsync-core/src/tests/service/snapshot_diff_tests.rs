@@ -0,0 +1,142 @@
+use std::cell::RefCell;
+
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::backup::SnapshotRecord;
+use crate::rsync_client::{RsyncClient, RsyncError, RsyncResult};
+use crate::services::snapshot_diff::{diff_snapshots, latest_snapshot_pair};
+
+const ITEMIZED: &str = "\
+sending incremental file list
+.d..t...... ./
+*deleting   old-report.pdf
+>f+++++++++ photos/new.jpg
+cd+++++++++ photos/2026/
+>f.st...... notes.txt
+.f...p..... script.sh
+
+sent 1,234 bytes  received 56 bytes  2,580.00 bytes/sec
+total size is 9,876  speedup is 7.66 (DRY RUN)";
+
+/// Returns canned stdout for dry runs and records the arguments it was given.
+struct CannedRsync {
+    stdout: String,
+    dry_run_args: RefCell<Vec<String>>,
+}
+
+impl CannedRsync {
+    fn new(stdout: &str) -> Self {
+        Self {
+            stdout: stdout.to_string(),
+            dry_run_args: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+impl RsyncClient for CannedRsync {
+    fn execute(&self, _args: &[String]) -> Result<RsyncResult, RsyncError> {
+        panic!("a snapshot diff must not run a real transfer");
+    }
+
+    fn dry_run(&self, args: &[String]) -> Result<RsyncResult, RsyncError> {
+        *self.dry_run_args.borrow_mut() = args.to_vec();
+        Ok(RsyncResult {
+            exit_code: 0,
+            stdout: self.stdout.clone(),
+            stderr: String::new(),
+            command: format!("rsync {}", args.join(" ")),
+        })
+    }
+
+    fn version(&self) -> Result<String, RsyncError> {
+        Ok("rsync  version 3.2.7  protocol version 31".to_string())
+    }
+}
+
+fn snapshot(job_id: Uuid, path: &str, hours_ago: i64) -> SnapshotRecord {
+    SnapshotRecord {
+        id: Uuid::new_v4(),
+        job_id,
+        invocation_id: Uuid::new_v4(),
+        snapshot_path: path.to_string(),
+        link_dest_path: None,
+        created_at: Utc::now() - Duration::hours(hours_ago),
+        size_bytes: 0,
+        file_count: 0,
+        is_latest: false,
+        pinned: false,
+    }
+}
+
+fn paths(changes: &[crate::models::itemize::ItemizedChange]) -> Vec<&str> {
+    changes.iter().map(|c| c.path.as_str()).collect()
+}
+
+#[test]
+fn test_diff_groups_changes_by_type() {
+    let job_id = Uuid::new_v4();
+    let a = snapshot(job_id, "/backups/2026-10-14_020000", 24);
+    let b = snapshot(job_id, "/backups/2026-10-15_020000", 0);
+
+    let diff = diff_snapshots(&a, &b, &CannedRsync::new(ITEMIZED)).unwrap();
+
+    assert_eq!(diff.from_path, a.snapshot_path);
+    assert_eq!(diff.to_path, b.snapshot_path);
+    assert_eq!(paths(&diff.created), vec!["photos/new.jpg", "photos/2026/"]);
+    assert_eq!(paths(&diff.updated), vec!["notes.txt", "script.sh"]);
+    assert_eq!(paths(&diff.deleted), vec!["old-report.pdf"]);
+}
+
+#[test]
+fn test_diff_syncs_newer_snapshot_onto_older_without_transferring() {
+    let job_id = Uuid::new_v4();
+    let a = snapshot(job_id, "/backups/2026-10-14_020000/", 24);
+    let b = snapshot(job_id, "/backups/2026-10-15_020000", 0);
+    let rsync = CannedRsync::new("");
+
+    let diff = diff_snapshots(&a, &b, &rsync).unwrap();
+
+    assert_eq!(
+        *rsync.dry_run_args.borrow(),
+        vec![
+            "-ni",
+            "--dry-run",
+            "-a",
+            "--delete",
+            "/backups/2026-10-15_020000/",
+            "/backups/2026-10-14_020000/",
+        ]
+    );
+    assert!(diff.created.is_empty() && diff.updated.is_empty() && diff.deleted.is_empty());
+}
+
+#[test]
+fn test_diff_rejects_snapshots_of_different_jobs() {
+    let a = snapshot(Uuid::new_v4(), "/backups/a", 24);
+    let b = snapshot(Uuid::new_v4(), "/backups/b", 0);
+    let rsync = CannedRsync::new(ITEMIZED);
+
+    assert!(matches!(
+        diff_snapshots(&a, &b, &rsync),
+        Err(AppError::ValidationError(_))
+    ));
+    assert!(rsync.dry_run_args.borrow().is_empty());
+}
+
+#[test]
+fn test_latest_snapshot_pair_orders_oldest_first() {
+    let job_id = Uuid::new_v4();
+    let snapshots = vec![
+        snapshot(job_id, "/backups/middle", 24),
+        snapshot(job_id, "/backups/newest", 0),
+        snapshot(job_id, "/backups/oldest", 48),
+    ];
+
+    let (older, newer) = latest_snapshot_pair(&snapshots).unwrap();
+
+    assert_eq!(older.snapshot_path, "/backups/middle");
+    assert_eq!(newer.snapshot_path, "/backups/newest");
+    assert!(latest_snapshot_pair(&snapshots[..1]).is_none());
+}
@@ -0,0 +1,140 @@
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::models::backup::{
+    BackupInvocation, ExecutionOutput, InvocationStatus, InvocationTrigger, SnapshotRecord,
+    TransferStats,
+};
+use crate::models::job::StorageLocation;
+use crate::services::snapshot_resume::resumable_snapshot;
+use crate::tests::test_file_system::TestFileSystem;
+
+const DANGLING: &str = "/backups/2025-06-15_140000";
+
+fn local_destination() -> StorageLocation {
+    StorageLocation::Local {
+        path: "/backups".to_string(),
+    }
+}
+
+fn invocation(status: InvocationStatus, snapshot_path: Option<&str>) -> BackupInvocation {
+    BackupInvocation {
+        id: Uuid::new_v4(),
+        job_id: Uuid::new_v4(),
+        started_at: Utc::now(),
+        finished_at: None,
+        status,
+        trigger: InvocationTrigger::Scheduled,
+        rerun_of: None,
+        resumed_from: None,
+        warning: None,
+        transfer_stats: TransferStats::default(),
+        execution_output: ExecutionOutput {
+            command_executed: "rsync -a /src/ /backups/".to_string(),
+            exit_code: None,
+            snapshot_path: snapshot_path.map(str::to_string),
+            log_file_path: None,
+        },
+    }
+}
+
+fn snapshot(path: &str) -> SnapshotRecord {
+    SnapshotRecord {
+        id: Uuid::new_v4(),
+        job_id: Uuid::new_v4(),
+        invocation_id: Uuid::new_v4(),
+        snapshot_path: path.to_string(),
+        link_dest_path: None,
+        created_at: Utc::now(),
+        size_bytes: 0,
+        file_count: 0,
+        is_latest: true,
+        pinned: false,
+    }
+}
+
+#[test]
+fn test_dangling_dir_without_success_is_resumed() {
+    let fs = TestFileSystem::new().with_dir(DANGLING);
+    let history = vec![invocation(InvocationStatus::Running, Some(DANGLING))];
+
+    let resumed = resumable_snapshot(&local_destination(), &history, &[], &fs).unwrap();
+
+    assert_eq!(resumed.id, history[0].id);
+    assert_eq!(
+        resumed.execution_output.snapshot_path.as_deref(),
+        Some(DANGLING)
+    );
+}
+
+#[test]
+fn test_failed_run_dir_is_resumed() {
+    let fs = TestFileSystem::new().with_dir(DANGLING);
+    let history = vec![invocation(InvocationStatus::Failed, Some(DANGLING))];
+
+    assert!(resumable_snapshot(&local_destination(), &history, &[], &fs).is_some());
+}
+
+#[test]
+fn test_recorded_snapshot_is_not_resumed() {
+    let fs = TestFileSystem::new().with_dir(DANGLING);
+    let history = vec![invocation(InvocationStatus::Running, Some(DANGLING))];
+
+    let resumed = resumable_snapshot(&local_destination(), &history, &[snapshot(DANGLING)], &fs);
+
+    assert!(resumed.is_none());
+}
+
+#[test]
+fn test_success_without_record_is_not_resumed() {
+    // A dry run succeeds without recording a snapshot
+    let fs = TestFileSystem::new().with_dir(DANGLING);
+    let history = vec![invocation(InvocationStatus::Succeeded, Some(DANGLING))];
+
+    assert!(resumable_snapshot(&local_destination(), &history, &[], &fs).is_none());
+}
+
+#[test]
+fn test_missing_local_dir_is_not_resumed() {
+    let fs = TestFileSystem::new().with_dir("/backups");
+    let history = vec![invocation(InvocationStatus::Running, Some(DANGLING))];
+
+    assert!(resumable_snapshot(&local_destination(), &history, &[], &fs).is_none());
+}
+
+#[test]
+fn test_remote_destination_trusts_history() {
+    let fs = TestFileSystem::new();
+    let destination = StorageLocation::RemoteSsh {
+        user: "backup".to_string(),
+        host: "nas".to_string(),
+        port: 22,
+        path: "/backups".to_string(),
+        identity_file: None,
+    };
+    let history = vec![invocation(InvocationStatus::Cancelled, Some(DANGLING))];
+
+    assert!(resumable_snapshot(&destination, &history, &[], &fs).is_some());
+}
+
+#[test]
+fn test_only_latest_snapshot_run_decides() {
+    let older = "/backups/2025-06-14_140000";
+    let fs = TestFileSystem::new().with_dir(older).with_dir(DANGLING);
+    let mut interrupted = invocation(InvocationStatus::Failed, Some(older));
+    interrupted.started_at = Utc::now() - Duration::days(1);
+    let completed = invocation(InvocationStatus::Succeeded, Some(DANGLING));
+    let history = vec![completed, interrupted];
+
+    let resumed = resumable_snapshot(&local_destination(), &history, &[snapshot(DANGLING)], &fs);
+
+    assert!(resumed.is_none());
+}
+
+#[test]
+fn test_history_without_snapshot_runs_has_nothing_to_resume() {
+    let fs = TestFileSystem::new();
+    let history = vec![invocation(InvocationStatus::Failed, None)];
+
+    assert!(resumable_snapshot(&local_destination(), &history, &[], &fs).is_none());
+}
@@ -0,0 +1,288 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use chrono::{Duration, TimeZone, Utc};
+use uuid::Uuid;
+
+use crate::database::sqlite::Database;
+use crate::file_system::{FileSystem, FsError};
+use crate::models::backup::{
+    BackupInvocation, ExecutionOutput, InvocationStatus, InvocationTrigger, SnapshotRecord,
+    TransferStats,
+};
+use crate::models::job::RetentionPolicy;
+use crate::repository::sqlite::invocation::SqliteInvocationRepository;
+use crate::repository::sqlite::job::SqliteJobRepository;
+use crate::repository::sqlite::snapshot::SqliteSnapshotRepository;
+use crate::services::job_service::JobService;
+use crate::services::snapshot_trash::{
+    dispose_pruned_snapshot, empty_trash, move_to_trash, prepare_trash,
+};
+use crate::tests::test_file_system::TestFileSystem;
+use crate::tests::test_helpers::create_snapshot_job;
+
+fn snapshot_fs() -> TestFileSystem {
+    TestFileSystem::new()
+        .with_dir("/backups/2025-01-01")
+        .with_file("/backups/2025-01-01/docs/a.txt", "alpha")
+        .with_dir("/backups/2025-01-02")
+        .with_file("/backups/2025-01-02/docs/a.txt", "beta")
+}
+
+fn job_service() -> JobService {
+    let db = Database::in_memory().unwrap();
+    let conn = db.conn();
+    JobService::new(
+        Arc::new(SqliteJobRepository::new(conn.clone())),
+        Arc::new(SqliteInvocationRepository::new(conn.clone())),
+        Arc::new(SqliteSnapshotRepository::new(conn)),
+    )
+}
+
+fn make_invocation(job_id: Uuid) -> BackupInvocation {
+    BackupInvocation {
+        id: Uuid::new_v4(),
+        job_id,
+        started_at: Utc::now(),
+        finished_at: Some(Utc::now()),
+        status: InvocationStatus::Succeeded,
+        trigger: InvocationTrigger::Manual,
+        rerun_of: None,
+        resumed_from: None,
+        warning: None,
+        transfer_stats: TransferStats::default(),
+        execution_output: ExecutionOutput {
+            command_executed: "rsync -a /src/ /backups/".to_string(),
+            exit_code: Some(0),
+            snapshot_path: None,
+            log_file_path: None,
+        },
+    }
+}
+
+#[test]
+fn test_dispose_without_trash_deletes_snapshot() {
+    let fs = snapshot_fs();
+    let result = dispose_pruned_snapshot(&fs, Path::new("/backups/2025-01-01"), None).unwrap();
+
+    assert_eq!(result, None);
+    assert!(!fs.exists(Path::new("/backups/2025-01-01")));
+    assert!(fs.files_under("/backups/2025-01-01").is_empty());
+}
+
+#[test]
+fn test_move_to_trash_renames_snapshot_with_contents() {
+    let fs = snapshot_fs();
+    let now = Utc.with_ymd_and_hms(2025, 6, 15, 14, 30, 0).unwrap();
+
+    let target = move_to_trash(
+        &fs,
+        Path::new("/backups/2025-01-01"),
+        Path::new("/trash"),
+        now,
+    )
+    .unwrap();
+
+    assert_eq!(target, Path::new("/trash/20250615-143000-2025-01-01"));
+    assert!(!fs.exists(Path::new("/backups/2025-01-01")));
+    assert_eq!(
+        fs.file_content("/trash/20250615-143000-2025-01-01/docs/a.txt")
+            .as_deref(),
+        Some("alpha")
+    );
+    // Other snapshots are untouched
+    assert!(fs.exists(Path::new("/backups/2025-01-02/docs/a.txt")));
+}
+
+#[test]
+fn test_move_to_trash_does_not_overwrite_existing_entry() {
+    let fs = snapshot_fs().with_dir("/other/2025-01-01");
+    let now = Utc.with_ymd_and_hms(2025, 6, 15, 14, 30, 0).unwrap();
+
+    let first = move_to_trash(
+        &fs,
+        Path::new("/backups/2025-01-01"),
+        Path::new("/trash"),
+        now,
+    )
+    .unwrap();
+    let second = move_to_trash(
+        &fs,
+        Path::new("/other/2025-01-01"),
+        Path::new("/trash"),
+        now,
+    )
+    .unwrap();
+
+    assert_ne!(first, second);
+    assert_eq!(second, Path::new("/trash/20250615-143000-2025-01-01-1"));
+    assert!(fs.exists(Path::new("/trash/20250615-143000-2025-01-01/docs/a.txt")));
+}
+
+#[test]
+fn test_move_to_trash_missing_snapshot_is_error() {
+    let fs = snapshot_fs();
+    let now = Utc::now();
+    assert!(move_to_trash(&fs, Path::new("/backups/missing"), Path::new("/trash"), now).is_err());
+    assert!(!fs.exists(Path::new("/trash")));
+}
+
+#[test]
+fn test_move_to_trash_on_another_filesystem_is_refused() {
+    let fs = snapshot_fs().with_mount("/backups", Some("BACKUP"));
+    let now = Utc.with_ymd_and_hms(2025, 3, 1, 12, 0, 0).unwrap();
+
+    let result = move_to_trash(
+        &fs,
+        Path::new("/backups/2025-01-01"),
+        Path::new("/trash"),
+        now,
+    );
+
+    match result {
+        Err(FsError::CrossDevice(message)) => {
+            assert!(message.contains("/backups/2025-01-01"), "{}", message);
+            assert!(message.contains("/trash"), "{}", message);
+        }
+        other => panic!("expected a cross-device error, got {:?}", other),
+    }
+    assert!(fs.exists(Path::new("/backups/2025-01-01/docs/a.txt")));
+}
+
+#[test]
+fn test_prepare_trash_accepts_trash_on_the_backup_volume() {
+    let fs = snapshot_fs().with_mount("/backups", Some("BACKUP"));
+
+    prepare_trash(
+        &fs,
+        Path::new("/backups/2025-01-01"),
+        Path::new("/backups/.trash"),
+    )
+    .unwrap();
+
+    assert!(fs.is_dir(Path::new("/backups/.trash")));
+}
+
+#[test]
+fn test_empty_trash_removes_trashed_snapshots() {
+    let fs = snapshot_fs();
+    let trash = Path::new("/trash");
+    for path in ["/backups/2025-01-01", "/backups/2025-01-02"] {
+        dispose_pruned_snapshot(&fs, Path::new(path), Some(trash)).unwrap();
+    }
+
+    assert_eq!(empty_trash(&fs, trash).unwrap(), 2);
+    assert!(fs.read_dir(trash).unwrap().is_empty());
+    assert!(fs.files_under("/trash").is_empty());
+    // Nothing left to remove, and a missing trash is simply empty
+    assert_eq!(empty_trash(&fs, trash).unwrap(), 0);
+    assert_eq!(empty_trash(&fs, Path::new("/no-trash")).unwrap(), 0);
+}
+
+#[test]
+fn test_retention_pruned_snapshot_goes_to_trash() {
+    let svc = job_service();
+    let fs = snapshot_fs();
+    let policy = RetentionPolicy {
+        keep_daily: 0,
+        keep_weekly: 0,
+        keep_monthly: 0,
+    };
+    let job = svc
+        .create_job(create_snapshot_job("/src/", "/backups/", policy))
+        .unwrap();
+    let inv = make_invocation(job.id);
+    svc.record_invocation(&inv).unwrap();
+    let now = Utc::now();
+    for (path, hours_ago) in [("/backups/2025-01-01", 2), ("/backups/2025-01-02", 1)] {
+        svc.record_snapshot(&SnapshotRecord {
+            id: Uuid::new_v4(),
+            job_id: job.id,
+            invocation_id: inv.id,
+            snapshot_path: path.to_string(),
+            link_dest_path: None,
+            created_at: now - Duration::hours(hours_ago),
+            size_bytes: 5,
+            file_count: 1,
+            is_latest: hours_ago == 1,
+            pinned: false,
+        })
+        .unwrap();
+    }
+
+    let pruned = svc.apply_retention_policy(&job.id).unwrap();
+    assert_eq!(pruned, vec!["/backups/2025-01-01".to_string()]);
+    for path in &pruned {
+        let trashed = dispose_pruned_snapshot(&fs, Path::new(path), Some(Path::new("/trash")))
+            .unwrap()
+            .expect("moved to trash");
+        assert!(trashed.starts_with("/trash"));
+        assert_eq!(
+            fs.file_content(trashed.join("docs/a.txt").to_str().unwrap())
+                .as_deref(),
+            Some("alpha")
+        );
+    }
+
+    // Moved, not deleted: the pruned snapshot is gone from its old place only
+    assert!(!fs.exists(Path::new("/backups/2025-01-01")));
+    assert!(fs.exists(Path::new("/backups/2025-01-02/docs/a.txt")));
+    assert_eq!(fs.read_dir(Path::new("/trash")).unwrap().len(), 1);
+}
+
+#[test]
+fn test_pinned_snapshot_survives_retention() {
+    let svc = job_service();
+    let policy = RetentionPolicy {
+        keep_daily: 0,
+        keep_weekly: 0,
+        keep_monthly: 0,
+    };
+    let job = svc
+        .create_job(create_snapshot_job("/src/", "/backups/", policy))
+        .unwrap();
+    let inv = make_invocation(job.id);
+    svc.record_invocation(&inv).unwrap();
+    let now = Utc::now();
+    let mut ids = Vec::new();
+    for (path, hours_ago) in [
+        ("/backups/2025-01-01", 3),
+        ("/backups/2025-01-02", 2),
+        ("/backups/2025-01-03", 1),
+    ] {
+        let snapshot = SnapshotRecord {
+            id: Uuid::new_v4(),
+            job_id: job.id,
+            invocation_id: inv.id,
+            snapshot_path: path.to_string(),
+            link_dest_path: None,
+            created_at: now - Duration::hours(hours_ago),
+            size_bytes: 5,
+            file_count: 1,
+            is_latest: hours_ago == 1,
+            pinned: false,
+        };
+        svc.record_snapshot(&snapshot).unwrap();
+        ids.push(snapshot.id);
+    }
+    svc.set_snapshot_pinned(&ids[0], true).unwrap();
+
+    // Without the pin, the policy would prune both older snapshots
+    let pruned = svc.apply_retention_policy(&job.id).unwrap();
+    assert_eq!(pruned, vec!["/backups/2025-01-02".to_string()]);
+
+    let left: Vec<Uuid> = svc
+        .list_snapshots(&job.id)
+        .unwrap()
+        .iter()
+        .map(|s| s.id)
+        .collect();
+    assert_eq!(left, vec![ids[2], ids[0]]);
+
+    // Unpinned, the next pass prunes it
+    svc.set_snapshot_pinned(&ids[0], false).unwrap();
+    assert_eq!(
+        svc.apply_retention_policy(&job.id).unwrap(),
+        vec!["/backups/2025-01-01".to_string()]
+    );
+}
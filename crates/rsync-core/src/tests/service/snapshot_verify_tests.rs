@@ -0,0 +1,208 @@
+use std::sync::{Arc, Mutex};
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::database::sqlite::Database;
+use crate::models::backup::{
+    BackupInvocation, ExecutionOutput, InvocationStatus, InvocationTrigger, SnapshotRecord,
+    TransferStats,
+};
+use crate::models::job::{JobDefinition, RetentionPolicy, StorageLocation};
+use crate::repository::sqlite::invocation::SqliteInvocationRepository;
+use crate::repository::sqlite::job::SqliteJobRepository;
+use crate::repository::sqlite::snapshot::SqliteSnapshotRepository;
+use crate::rsync_client::{RsyncClient, RsyncError, RsyncResult};
+use crate::services::job_service::JobService;
+use crate::services::snapshot_verify::{
+    count_discrepancies, verification_args, verification_summary, verify_and_record,
+    verify_snapshot,
+};
+use crate::tests::test_helpers::create_snapshot_job;
+
+const SNAPSHOT: &str = "/backups/2025-06-15_140000";
+
+/// Answers every dry run with `stdout` and `exit_code`, keeping the arguments.
+struct MockRsync {
+    exit_code: i32,
+    stdout: &'static str,
+    calls: Mutex<Vec<Vec<String>>>,
+}
+
+impl MockRsync {
+    fn new(exit_code: i32, stdout: &'static str) -> Self {
+        Self {
+            exit_code,
+            stdout,
+            calls: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl RsyncClient for MockRsync {
+    fn execute(&self, args: &[String]) -> Result<RsyncResult, RsyncError> {
+        self.calls.lock().unwrap().push(args.to_vec());
+        Ok(RsyncResult {
+            exit_code: self.exit_code,
+            stdout: self.stdout.to_string(),
+            stderr: String::new(),
+            command: "rsync".to_string(),
+        })
+    }
+
+    fn dry_run(&self, args: &[String]) -> Result<RsyncResult, RsyncError> {
+        self.execute(args)
+    }
+
+    fn version(&self) -> Result<String, RsyncError> {
+        Ok("rsync version 3.2.7".to_string())
+    }
+}
+
+fn snapshot_job() -> JobDefinition {
+    let mut job = create_snapshot_job(
+        "/data/",
+        "/backups",
+        RetentionPolicy {
+            keep_daily: 7,
+            keep_weekly: 4,
+            keep_monthly: 6,
+        },
+    );
+    job.safety.verify_after_snapshot = true;
+    job.options.file_handling.max_delete = Some(10);
+    job.options.output.progress = true;
+    job
+}
+
+fn snapshot_location() -> StorageLocation {
+    StorageLocation::Local {
+        path: format!("{}/", SNAPSHOT),
+    }
+}
+
+/// A job service holding `job`, as saved, with a successful run that wrote
+/// a snapshot.
+fn service_with_snapshot(job: &JobDefinition) -> (JobService, JobDefinition, Uuid) {
+    let conn = Database::in_memory().unwrap().conn();
+    let svc = JobService::new(
+        Arc::new(SqliteJobRepository::new(conn.clone())),
+        Arc::new(SqliteInvocationRepository::new(conn.clone())),
+        Arc::new(SqliteSnapshotRepository::new(conn)),
+    );
+    let job = svc.create_job(job.clone()).unwrap();
+    let invocation = BackupInvocation {
+        id: Uuid::new_v4(),
+        job_id: job.id,
+        started_at: Utc::now(),
+        finished_at: Some(Utc::now()),
+        status: InvocationStatus::Succeeded,
+        trigger: InvocationTrigger::Manual,
+        rerun_of: None,
+        resumed_from: None,
+        warning: None,
+        transfer_stats: TransferStats::default(),
+        execution_output: ExecutionOutput {
+            command_executed: "rsync -a /data/ /backups/2025-06-15_140000/".to_string(),
+            exit_code: Some(0),
+            snapshot_path: Some(SNAPSHOT.to_string()),
+            log_file_path: None,
+        },
+    };
+    svc.record_invocation(&invocation).unwrap();
+    svc.record_snapshot(&SnapshotRecord {
+        id: Uuid::new_v4(),
+        job_id: job.id,
+        invocation_id: invocation.id,
+        snapshot_path: SNAPSHOT.to_string(),
+        link_dest_path: None,
+        created_at: Utc::now(),
+        size_bytes: 1024,
+        file_count: 3,
+        is_latest: true,
+        pinned: false,
+    })
+    .unwrap();
+    (svc, job, invocation.id)
+}
+
+#[test]
+fn test_verification_args_compare_source_with_snapshot_by_checksum() {
+    let args = verification_args(&snapshot_job(), &snapshot_location(), true, false);
+
+    for flag in ["--dry-run", "--checksum", "--delete", "--itemize-changes"] {
+        assert!(args.contains(&flag.to_string()), "missing {}", flag);
+    }
+    assert!(!args.iter().any(|arg| arg.starts_with("--max-delete")));
+    assert!(!args.iter().any(|arg| arg.starts_with("--link-dest")));
+    assert!(!args.contains(&"--progress".to_string()));
+    assert_eq!(
+        args[args.len() - 2..],
+        ["/data/", "/backups/2025-06-15_140000/"]
+    );
+}
+
+#[test]
+fn test_count_discrepancies_skips_root_and_other_output() {
+    let output = "sending incremental file list\n\
+                  .d..t...... ./\n\
+                  >fc.t...... docs/report.txt\n\
+                  *deleting   stray.tmp\n";
+
+    assert_eq!(count_discrepancies(output), 2);
+    assert_eq!(count_discrepancies(""), 0);
+}
+
+#[test]
+fn test_successful_snapshot_verification_is_stored() {
+    let (svc, job, invocation_id) = service_with_snapshot(&snapshot_job());
+    let rsync = MockRsync::new(0, ">fc.t...... docs/report.txt\n*deleting   stray.tmp\n");
+    let args = verification_args(&job, &snapshot_location(), true, false);
+
+    let verification = verify_and_record(&rsync, &svc, job.id, invocation_id, &args);
+
+    assert_eq!(rsync.calls.lock().unwrap()[0], args);
+    let stored = svc
+        .get_snapshot_verification(&invocation_id)
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored, verification);
+    assert!(!stored.passed);
+    assert_eq!(stored.discrepancies, 2);
+    assert_eq!(stored.error, None);
+    assert_eq!(
+        verification_summary(&stored),
+        "Snapshot verification found 2 differences from the source"
+    );
+}
+
+#[test]
+fn test_matching_snapshot_passes() {
+    let verification = verify_snapshot(&MockRsync::new(0, ""), Uuid::new_v4(), Uuid::new_v4(), &[]);
+
+    assert!(verification.passed);
+    assert_eq!(verification.discrepancies, 0);
+    assert_eq!(
+        verification_summary(&verification),
+        "Snapshot verified: it matches the source"
+    );
+}
+
+#[test]
+fn test_failed_verification_run_does_not_pass() {
+    let verification =
+        verify_snapshot(&MockRsync::new(23, ""), Uuid::new_v4(), Uuid::new_v4(), &[]);
+
+    assert!(!verification.passed);
+    assert_eq!(
+        verification.error.as_deref(),
+        Some("Verification exited with code 23")
+    );
+}
+
+#[test]
+fn test_unverified_run_has_no_result() {
+    let (svc, _job, invocation_id) = service_with_snapshot(&snapshot_job());
+
+    assert_eq!(svc.get_snapshot_verification(&invocation_id).unwrap(), None);
+}
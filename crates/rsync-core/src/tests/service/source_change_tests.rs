@@ -0,0 +1,230 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use uuid::Uuid;
+
+use crate::database::sqlite::Database;
+use crate::file_system::FsError;
+use crate::models::backup::{
+    BackupInvocation, ExecutionOutput, InvocationStatus, InvocationTrigger, TransferStats,
+};
+use crate::models::job::{JobDefinition, StorageLocation};
+use crate::repository::sqlite::invocation::SqliteInvocationRepository;
+use crate::repository::sqlite::job::SqliteJobRepository;
+use crate::repository::sqlite::snapshot::SqliteSnapshotRepository;
+use crate::services::job_service::JobService;
+use crate::services::run_conditions::{check_run_conditions, RunCondition};
+use crate::services::source_change::{source_max_mtime, unchanged_skip_reason, SourceUnchanged};
+use crate::tests::test_file_system::TestFileSystem;
+use crate::tests::test_helpers::create_mirror_job;
+
+fn at(hour: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 6, 1, hour, 0, 0).unwrap()
+}
+
+fn guarded_job(source: &str) -> JobDefinition {
+    let mut job = create_mirror_job(source, "/dst/");
+    job.safety.skip_if_unchanged = true;
+    job
+}
+
+/// A source tree whose newest entry was modified at `newest`.
+fn source_fs(newest: DateTime<Utc>) -> TestFileSystem {
+    TestFileSystem::new()
+        .with_file("/src/a.txt", "a")
+        .with_file("/src/nested/b.txt", "b")
+        .with_modified("/src", at(1))
+        .with_modified("/src/a.txt", at(2))
+        .with_modified("/src/nested", at(1))
+        .with_modified("/src/nested/b.txt", newest)
+}
+
+fn make_invocation(
+    job_id: Uuid,
+    started_at: DateTime<Utc>,
+    status: InvocationStatus,
+) -> BackupInvocation {
+    BackupInvocation {
+        id: Uuid::new_v4(),
+        job_id,
+        started_at,
+        finished_at: Some(started_at),
+        status,
+        trigger: InvocationTrigger::Scheduled,
+        rerun_of: None,
+        resumed_from: None,
+        warning: None,
+        transfer_stats: TransferStats::default(),
+        execution_output: ExecutionOutput {
+            command_executed: "rsync -a /src /dst".to_string(),
+            exit_code: Some(0),
+            snapshot_path: None,
+            log_file_path: None,
+        },
+    }
+}
+
+fn setup() -> (Arc<JobService>, Database) {
+    let db = Database::in_memory().unwrap();
+    let conn = db.conn();
+    let job_service = Arc::new(JobService::new(
+        Arc::new(SqliteJobRepository::new(conn.clone())),
+        Arc::new(SqliteInvocationRepository::new(conn.clone())),
+        Arc::new(SqliteSnapshotRepository::new(conn)),
+    ));
+    (job_service, db)
+}
+
+#[test]
+fn test_max_mtime_covers_nested_entries() {
+    let fs = source_fs(at(5));
+    assert_eq!(source_max_mtime(&fs, Path::new("/src")).unwrap(), at(5));
+}
+
+#[test]
+fn test_max_mtime_of_missing_source_is_not_found() {
+    let fs = TestFileSystem::new();
+    assert!(matches!(
+        source_max_mtime(&fs, Path::new("/src")),
+        Err(FsError::NotFound(_))
+    ));
+}
+
+#[test]
+fn test_source_older_than_last_run_is_skipped() {
+    let fs = source_fs(at(3));
+    let reason = unchanged_skip_reason(&fs, &guarded_job("/src"), Some(at(4)));
+    assert!(reason.unwrap().contains("has not changed"));
+}
+
+#[test]
+fn test_source_newer_than_last_run_runs() {
+    let fs = source_fs(at(6));
+    assert_eq!(
+        unchanged_skip_reason(&fs, &guarded_job("/src"), Some(at(4))),
+        None
+    );
+}
+
+#[test]
+fn test_guard_off_always_runs() {
+    let fs = source_fs(at(3));
+    let mut job = guarded_job("/src");
+    job.safety.skip_if_unchanged = false;
+    assert_eq!(unchanged_skip_reason(&fs, &job, Some(at(4))), None);
+}
+
+#[test]
+fn test_without_previous_success_runs() {
+    let fs = source_fs(at(3));
+    assert_eq!(unchanged_skip_reason(&fs, &guarded_job("/src"), None), None);
+}
+
+#[test]
+fn test_unreadable_source_runs() {
+    let fs = TestFileSystem::new();
+    assert_eq!(
+        unchanged_skip_reason(&fs, &guarded_job("/src"), Some(at(4))),
+        None
+    );
+}
+
+#[test]
+fn test_remote_source_is_not_checked() {
+    let fs = source_fs(at(3));
+    let mut job = guarded_job("/src");
+    job.transfer.source = StorageLocation::RemoteSsh {
+        host: "example.com".to_string(),
+        port: 22,
+        path: "/src".to_string(),
+        user: "backup".to_string(),
+        identity_file: None,
+    };
+    assert_eq!(unchanged_skip_reason(&fs, &job, Some(at(4))), None);
+}
+
+#[test]
+fn test_condition_uses_last_successful_run() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(tmp.path().join("file.txt"), "data").unwrap();
+    let (job_service, _db) = setup();
+    let job = job_service
+        .create_job(guarded_job(tmp.path().to_str().unwrap()))
+        .unwrap();
+    let condition = SourceUnchanged::new(Arc::clone(&job_service));
+
+    // Never succeeded: nothing to compare against.
+    assert_eq!(condition.skip_reason(&job), None);
+
+    // Succeeded before the file was written: the source changed since.
+    let earlier = make_invocation(
+        job.id,
+        Utc::now() - Duration::hours(1),
+        InvocationStatus::Succeeded,
+    );
+    job_service.record_invocation(&earlier).unwrap();
+    assert_eq!(condition.skip_reason(&job), None);
+
+    // A failed run afterwards doesn't count as a baseline.
+    let failed = make_invocation(
+        job.id,
+        Utc::now() + Duration::minutes(30),
+        InvocationStatus::Failed,
+    );
+    job_service.record_invocation(&failed).unwrap();
+    assert_eq!(condition.skip_reason(&job), None);
+
+    // Succeeded after every change: scheduled runs are skipped.
+    let later = make_invocation(
+        job.id,
+        Utc::now() + Duration::hours(1),
+        InvocationStatus::Succeeded,
+    );
+    job_service.record_invocation(&later).unwrap();
+    assert!(condition.skip_reason(&job).is_some());
+}
+
+#[test]
+fn test_condition_ignores_successful_dry_runs() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(tmp.path().join("file.txt"), "data").unwrap();
+    let (job_service, _db) = setup();
+    let job = job_service
+        .create_job(guarded_job(tmp.path().to_str().unwrap()))
+        .unwrap();
+    let condition = SourceUnchanged::new(Arc::clone(&job_service));
+
+    let mut dry_run = make_invocation(
+        job.id,
+        Utc::now() + Duration::hours(1),
+        InvocationStatus::Succeeded,
+    );
+    dry_run.execution_output.command_executed = "rsync -a --dry-run /src /dst".to_string();
+    job_service.record_invocation(&dry_run).unwrap();
+
+    assert_eq!(condition.skip_reason(&job), None);
+}
+
+#[test]
+fn test_manual_runs_proceed_when_unchanged() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(tmp.path().join("file.txt"), "data").unwrap();
+    let (job_service, _db) = setup();
+    let job = job_service
+        .create_job(guarded_job(tmp.path().to_str().unwrap()))
+        .unwrap();
+    let later = make_invocation(
+        job.id,
+        Utc::now() + Duration::hours(1),
+        InvocationStatus::Succeeded,
+    );
+    job_service.record_invocation(&later).unwrap();
+
+    let conditions: Vec<Arc<dyn RunCondition>> = vec![Arc::new(SourceUnchanged::new(job_service))];
+    assert!(check_run_conditions(&job, &InvocationTrigger::Scheduled, &conditions).is_some());
+    assert_eq!(
+        check_run_conditions(&job, &InvocationTrigger::Manual, &conditions),
+        None
+    );
+}
@@ -0,0 +1,87 @@
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+use uuid::Uuid;
+
+use crate::models::job::JobDefinition;
+use crate::services::scheduler_backend::ScheduledRunner;
+use crate::services::startup_runner::{run_startup_jobs, startup_jobs};
+use crate::tests::test_helpers::create_test_job;
+
+/// Runner that records which jobs it was asked to start instead of spawning rsync.
+#[derive(Default)]
+struct FakeRunner {
+    running: HashSet<Uuid>,
+    refuse: HashSet<Uuid>,
+    started: Mutex<Vec<Uuid>>,
+}
+
+impl ScheduledRunner for FakeRunner {
+    fn is_running(&self, job_id: &Uuid) -> bool {
+        self.running.contains(job_id)
+    }
+
+    fn run_scheduled(&self, job: &JobDefinition) -> Result<Uuid, String> {
+        if self.refuse.contains(&job.id) {
+            return Err("refused".to_string());
+        }
+        self.started.lock().unwrap().push(job.id);
+        Ok(Uuid::new_v4())
+    }
+}
+
+fn job(enabled: bool, run_on_startup: bool) -> JobDefinition {
+    let mut job = create_test_job();
+    job.enabled = enabled;
+    job.run_policy.run_on_startup = run_on_startup;
+    job
+}
+
+#[test]
+fn test_startup_jobs_selects_only_enabled_flagged_jobs() {
+    let jobs = vec![
+        job(true, true),
+        job(true, false),
+        job(false, true),
+        job(false, false),
+        job(true, true),
+    ];
+
+    let selected: Vec<Uuid> = startup_jobs(&jobs).iter().map(|j| j.id).collect();
+
+    assert_eq!(selected, vec![jobs[0].id, jobs[4].id]);
+}
+
+#[test]
+fn test_startup_jobs_empty_when_none_flagged() {
+    let jobs = vec![job(true, false), job(false, true)];
+    assert!(startup_jobs(&jobs).is_empty());
+}
+
+#[test]
+fn test_run_startup_jobs_skips_running_jobs() {
+    let jobs = vec![job(true, true), job(true, true)];
+    let runner = FakeRunner {
+        running: HashSet::from([jobs[0].id]),
+        ..Default::default()
+    };
+
+    let started = run_startup_jobs(&jobs, &runner);
+
+    assert_eq!(started.len(), 1);
+    assert_eq!(*runner.started.lock().unwrap(), vec![jobs[1].id]);
+}
+
+#[test]
+fn test_run_startup_jobs_continues_after_refusal() {
+    let jobs = vec![job(true, true), job(true, true), job(true, false)];
+    let runner = FakeRunner {
+        refuse: HashSet::from([jobs[0].id]),
+        ..Default::default()
+    };
+
+    let started = run_startup_jobs(&jobs, &runner);
+
+    assert_eq!(started.len(), 1);
+    assert_eq!(*runner.started.lock().unwrap(), vec![jobs[1].id]);
+}
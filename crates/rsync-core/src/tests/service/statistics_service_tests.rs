@@ -11,7 +11,11 @@ use crate::repository::invocation::InvocationRepository;
 use crate::repository::job::JobRepository;
 use crate::repository::sqlite::invocation::SqliteInvocationRepository;
 use crate::repository::sqlite::job::SqliteJobRepository;
+use crate::models::settings::AnomalySettings;
+use crate::repository::sqlite::snapshot::SqliteSnapshotRepository;
 use crate::repository::sqlite::statistics::SqliteStatisticsRepository;
+use crate::repository::statistics::StatisticsRepository;
+use crate::services::job_service::JobService;
 use crate::services::statistics_service::StatisticsService;
 use crate::tests::test_helpers::create_test_job;
 
@@ -39,10 +43,15 @@ fn make_completed_invocation(job_id: Uuid, bytes: u64, files: u64) -> BackupInvo
         finished_at: Some(Utc::now()),
         status: InvocationStatus::Succeeded,
         trigger: InvocationTrigger::Manual,
+        rerun_of: None,
+        resumed_from: None,
+        warning: None,
         transfer_stats: TransferStats {
             bytes_transferred: bytes,
             files_transferred: files,
             total_files: files,
+            files_deleted: 0,
+            detailed: None,
         },
         execution_output: ExecutionOutput {
             command_executed: "rsync -a /src/ /dst/".to_string(),
@@ -98,6 +107,34 @@ fn test_aggregate_for_job() {
     assert_eq!(agg_all.total_bytes_transferred, 1024 + 2048);
 }
 
+#[test]
+fn test_aggregate_sums_deleted_files() {
+    let (job_repo, inv_repo, stats_service) = setup();
+    let job = create_test_job();
+    job_repo.create_job(&job).unwrap();
+
+    for deleted in [3, 0, 12] {
+        let mut inv = make_completed_invocation(job.id, 1024, 5);
+        inv.transfer_stats.files_deleted = deleted;
+        inv_repo.create_invocation(&inv).unwrap();
+        stats_service.record(job.id, &inv, None).unwrap();
+    }
+
+    let stats = stats_service.list_all().unwrap();
+    assert_eq!(stats.iter().map(|s| s.files_deleted).max(), Some(12));
+    assert_eq!(
+        stats_service.get_aggregated().unwrap().total_files_deleted,
+        15
+    );
+    assert_eq!(
+        stats_service
+            .get_aggregated_for_job(&job.id)
+            .unwrap()
+            .total_files_deleted,
+        15
+    );
+}
+
 #[test]
 fn test_export_json() {
     let (job_repo, inv_repo, stats_service) = setup();
@@ -183,3 +220,236 @@ fn test_speedup_leq_one_no_time_saved() {
     let agg = stats_service.get_aggregated().unwrap();
     assert_eq!(agg.total_time_saved_secs, 0.0);
 }
+
+/// Record `count` similar runs of about 1 MB and 10 files each.
+fn record_baseline(
+    inv_repo: &SqliteInvocationRepository,
+    stats_service: &StatisticsService,
+    job_id: Uuid,
+    count: u64,
+) {
+    for i in 0..count {
+        let inv = make_completed_invocation(job_id, 1_000_000 + i * 10_000, 10 + i % 2);
+        inv_repo.create_invocation(&inv).unwrap();
+        stats_service.record(job_id, &inv, None).unwrap();
+    }
+}
+
+#[test]
+fn test_run_anomaly_flags_outlier() {
+    let (job_repo, inv_repo, stats_service) = setup();
+    let job = create_test_job();
+    job_repo.create_job(&job).unwrap();
+    record_baseline(&inv_repo, &stats_service, job.id, 5);
+
+    let outlier = make_completed_invocation(job.id, 50_000_000, 12);
+    let anomaly = stats_service
+        .run_anomaly(&job.id, &outlier, &AnomalySettings::default())
+        .unwrap()
+        .unwrap();
+
+    assert!(anomaly.is_anomaly);
+    assert_eq!(anomaly.baseline_runs, 5);
+    assert!(anomaly.bytes_ratio > 40.0);
+    assert!(anomaly.files_ratio < 2.0);
+}
+
+#[test]
+fn test_run_anomaly_accepts_typical_run() {
+    let (job_repo, inv_repo, stats_service) = setup();
+    let job = create_test_job();
+    job_repo.create_job(&job).unwrap();
+    record_baseline(&inv_repo, &stats_service, job.id, 5);
+
+    let typical = make_completed_invocation(job.id, 1_200_000, 11);
+    let anomaly = stats_service
+        .run_anomaly(&job.id, &typical, &AnomalySettings::default())
+        .unwrap()
+        .unwrap();
+
+    assert!(!anomaly.is_anomaly);
+}
+
+#[test]
+fn test_run_anomaly_respects_factor() {
+    let (job_repo, inv_repo, stats_service) = setup();
+    let job = create_test_job();
+    job_repo.create_job(&job).unwrap();
+    record_baseline(&inv_repo, &stats_service, job.id, 5);
+
+    let triple = make_completed_invocation(job.id, 3_000_000, 10);
+    let strict = AnomalySettings {
+        baseline_runs: 10,
+        factor: 2.0,
+    };
+    assert!(
+        stats_service
+            .run_anomaly(&job.id, &triple, &strict)
+            .unwrap()
+            .unwrap()
+            .is_anomaly
+    );
+    assert!(
+        !stats_service
+            .run_anomaly(&job.id, &triple, &AnomalySettings::default())
+            .unwrap()
+            .unwrap()
+            .is_anomaly
+    );
+}
+
+#[test]
+fn test_run_anomaly_needs_baseline() {
+    let (job_repo, inv_repo, stats_service) = setup();
+    let job = create_test_job();
+    job_repo.create_job(&job).unwrap();
+    record_baseline(&inv_repo, &stats_service, job.id, 2);
+
+    let outlier = make_completed_invocation(job.id, 50_000_000, 500);
+    let anomaly = stats_service
+        .run_anomaly(&job.id, &outlier, &AnomalySettings::default())
+        .unwrap();
+    assert_eq!(anomaly, None);
+}
+
+#[test]
+fn test_run_anomaly_excludes_the_run_itself() {
+    let (job_repo, inv_repo, stats_service) = setup();
+    let job = create_test_job();
+    job_repo.create_job(&job).unwrap();
+    record_baseline(&inv_repo, &stats_service, job.id, 3);
+
+    let outlier = make_completed_invocation(job.id, 50_000_000, 10);
+    inv_repo.create_invocation(&outlier).unwrap();
+    stats_service.record(job.id, &outlier, None).unwrap();
+    let anomaly = stats_service
+        .run_anomaly(&job.id, &outlier, &AnomalySettings::default())
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(anomaly.baseline_runs, 3);
+    assert!(anomaly.is_anomaly);
+}
+
+#[test]
+fn test_run_anomaly_uses_only_recent_runs() {
+    let (job_repo, inv_repo, stats_service) = setup();
+    let job = create_test_job();
+    job_repo.create_job(&job).unwrap();
+    // An old run of 100 MB followed by three recent runs of ~1 MB
+    let old = make_completed_invocation(job.id, 100_000_000, 10);
+    inv_repo.create_invocation(&old).unwrap();
+    stats_service.record(job.id, &old, None).unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(5));
+    record_baseline(&inv_repo, &stats_service, job.id, 3);
+
+    let outlier = make_completed_invocation(job.id, 50_000_000, 10);
+    let settings = AnomalySettings {
+        baseline_runs: 3,
+        factor: 10.0,
+    };
+    let anomaly = stats_service
+        .run_anomaly(&job.id, &outlier, &settings)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(anomaly.baseline_runs, 3);
+    assert!(anomaly.is_anomaly);
+}
+
+fn job_service_on(db: &Database) -> JobService {
+    let conn = db.conn();
+    JobService::new(
+        Arc::new(SqliteJobRepository::new(conn.clone())),
+        Arc::new(SqliteInvocationRepository::new(conn.clone())),
+        Arc::new(SqliteSnapshotRepository::new(conn)),
+    )
+}
+
+#[test]
+fn test_recompute_from_invocations_matches_recorded_stats() {
+    let db = Database::in_memory().unwrap();
+    let job_service = job_service_on(&db);
+    let stats_repo = Arc::new(SqliteStatisticsRepository::new(db.conn()));
+    let stats_service = StatisticsService::new(stats_repo.clone());
+    let tmp = tempfile::tempdir().unwrap();
+
+    let job = job_service.create_job(create_test_job()).unwrap();
+    let mut with_log = make_completed_invocation(job.id, 4096, 8);
+    let log_path = tmp.path().join("run.log");
+    std::fs::write(
+        &log_path,
+        "sent 4,096 bytes  received 35 bytes\ntotal size is 16,384  speedup is 4.00\n",
+    )
+    .unwrap();
+    with_log.execution_output.log_file_path = Some(log_path.to_str().unwrap().to_string());
+    let mut with_deletes = make_completed_invocation(job.id, 1024, 2);
+    with_deletes.transfer_stats.files_deleted = 3;
+    for inv in [&with_log, &with_deletes] {
+        job_service.record_invocation(inv).unwrap();
+    }
+    stats_service.record(job.id, &with_log, Some(4.0)).unwrap();
+    stats_service.record(job.id, &with_deletes, None).unwrap();
+    let expected = stats_service.get_aggregated().unwrap();
+
+    // Failed runs and dry runs never count
+    let mut failed = make_completed_invocation(job.id, 999, 9);
+    failed.status = InvocationStatus::Failed;
+    let mut dry = make_completed_invocation(job.id, 999, 9);
+    dry.execution_output.command_executed = "rsync -a --dry-run /src/ /dst/".to_string();
+    job_service.record_invocation(&failed).unwrap();
+    job_service.record_invocation(&dry).unwrap();
+
+    // Clear the statistics and leave a bogus one behind
+    stats_service.reset().unwrap();
+    stats_service.record(job.id, &failed, Some(100.0)).unwrap();
+
+    let recorded = stats_service
+        .recompute_from_invocations(&job_service)
+        .unwrap();
+
+    assert_eq!(recorded, 2);
+    let rebuilt = stats_service.get_aggregated().unwrap();
+    assert_eq!(rebuilt.total_jobs_run, expected.total_jobs_run);
+    assert_eq!(
+        rebuilt.total_files_transferred,
+        expected.total_files_transferred
+    );
+    assert_eq!(
+        rebuilt.total_bytes_transferred,
+        expected.total_bytes_transferred
+    );
+    assert_eq!(rebuilt.total_files_deleted, expected.total_files_deleted);
+    assert!((rebuilt.total_duration_secs - expected.total_duration_secs).abs() < 1e-9);
+    assert!((rebuilt.total_time_saved_secs - expected.total_time_saved_secs).abs() < 1e-9);
+    let speedups: Vec<Option<f64>> = stats_repo
+        .get_statistics_for_job(&job.id)
+        .unwrap()
+        .iter()
+        .map(|s| s.speedup)
+        .collect();
+    assert!(speedups.contains(&Some(4.0)));
+}
+
+#[test]
+fn test_recompute_with_missing_log_has_no_speedup() {
+    let db = Database::in_memory().unwrap();
+    let job_service = job_service_on(&db);
+    let stats_repo = Arc::new(SqliteStatisticsRepository::new(db.conn()));
+    let stats_service = StatisticsService::new(stats_repo);
+
+    let job = job_service.create_job(create_test_job()).unwrap();
+    let mut inv = make_completed_invocation(job.id, 10, 1);
+    inv.execution_output.log_file_path = Some("/nonexistent/run.log".to_string());
+    job_service.record_invocation(&inv).unwrap();
+
+    assert_eq!(
+        stats_service
+            .recompute_from_invocations(&job_service)
+            .unwrap(),
+        1
+    );
+    let agg = stats_service.get_aggregated().unwrap();
+    assert_eq!(agg.total_jobs_run, 1);
+    assert_eq!(agg.total_time_saved_secs, 0.0);
+}
@@ -0,0 +1,79 @@
+use crate::models::backup::InvocationStatus;
+use crate::models::job::JobStatus;
+use crate::models::status::{StatusColor, StatusDisplay};
+
+const INVOCATION_STATUSES: [InvocationStatus; 5] = [
+    InvocationStatus::Running,
+    InvocationStatus::Succeeded,
+    InvocationStatus::Failed,
+    InvocationStatus::Cancelled,
+    InvocationStatus::StoppedByLimit,
+];
+
+const JOB_STATUSES: [JobStatus; 5] = [
+    JobStatus::Idle,
+    JobStatus::Running,
+    JobStatus::Completed,
+    JobStatus::Failed,
+    JobStatus::Cancelled,
+];
+
+#[test]
+fn test_every_invocation_status_has_label_and_color() {
+    for status in &INVOCATION_STATUSES {
+        assert!(
+            !status.status_label().is_empty(),
+            "{:?} has no label",
+            status
+        );
+        let _: StatusColor = status.status_color_hint();
+    }
+}
+
+#[test]
+fn test_every_job_status_has_label_and_color() {
+    for status in &JOB_STATUSES {
+        assert!(
+            !status.status_label().is_empty(),
+            "{:?} has no label",
+            status
+        );
+        let _: StatusColor = status.status_color_hint();
+    }
+}
+
+#[test]
+fn test_job_and_run_states_read_alike() {
+    let pairs = [
+        (JobStatus::Running, InvocationStatus::Running),
+        (JobStatus::Completed, InvocationStatus::Succeeded),
+        (JobStatus::Failed, InvocationStatus::Failed),
+        (JobStatus::Cancelled, InvocationStatus::Cancelled),
+    ];
+    for (job, run) in pairs {
+        assert_eq!(job.status_label(), run.status_label());
+        assert_eq!(job.status_color_hint(), run.status_color_hint());
+    }
+}
+
+#[test]
+fn test_status_colors() {
+    assert_eq!(
+        InvocationStatus::Succeeded.status_color_hint(),
+        StatusColor::Success
+    );
+    assert_eq!(
+        InvocationStatus::Failed.status_color_hint(),
+        StatusColor::Failure
+    );
+    assert_eq!(
+        InvocationStatus::StoppedByLimit.status_color_hint(),
+        StatusColor::Warning
+    );
+    assert_eq!(JobStatus::Running.status_color_hint(), StatusColor::Active);
+    assert_eq!(JobStatus::Idle.status_color_hint(), StatusColor::Neutral);
+    assert_eq!(
+        InvocationStatus::StoppedByLimit.status_label(),
+        "Stopped at time limit"
+    );
+}
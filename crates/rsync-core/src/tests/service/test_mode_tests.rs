@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use crate::database::sqlite::Database;
+use crate::models::backup::InvocationStatus;
+use crate::models::job::RetentionPolicy;
+use crate::repository::sqlite::invocation::SqliteInvocationRepository;
+use crate::repository::sqlite::job::SqliteJobRepository;
+use crate::repository::sqlite::settings::SqliteSettingsRepository;
+use crate::repository::sqlite::snapshot::SqliteSnapshotRepository;
+use crate::repository::sqlite::statistics::SqliteStatisticsRepository;
+use crate::services::command_builder::build_rsync_args;
+use crate::services::job_executor::JobExecutor;
+use crate::services::job_service::JobService;
+use crate::services::running_jobs::RunningJobs;
+use crate::services::settings_service::SettingsService;
+use crate::services::statistics_service::StatisticsService;
+use crate::services::test_mode::{command_was_dry_run, job_for_run, records_run_results};
+use crate::tests::test_helpers::{create_snapshot_job, create_test_job};
+
+fn settings() -> SettingsService {
+    let db = Database::in_memory().unwrap();
+    SettingsService::new(Arc::new(SqliteSettingsRepository::new(db.conn())))
+}
+
+#[test]
+fn test_test_mode_defaults_off_and_round_trips() {
+    let svc = settings();
+    assert!(!svc.get_test_mode().unwrap());
+
+    svc.set_test_mode(true).unwrap();
+    assert!(svc.get_test_mode().unwrap());
+
+    svc.set_test_mode(false).unwrap();
+    assert!(!svc.get_test_mode().unwrap());
+}
+
+#[test]
+fn test_test_mode_forces_dry_run_and_records_no_snapshot() {
+    let job = create_snapshot_job("/src/", "/backups/", RetentionPolicy::default());
+    assert!(!job.options.core_transfer.dry_run);
+
+    let run = job_for_run(&job, true);
+    let args = build_rsync_args(
+        &run.transfer.source,
+        &run.transfer.destination,
+        &run.options,
+        run.ssh_config.as_ref(),
+        &[],
+        true,
+        false,
+    );
+
+    assert!(args.contains(&"--dry-run".to_string()));
+    assert!(!records_run_results(
+        &InvocationStatus::Succeeded,
+        run.options.core_transfer.dry_run
+    ));
+    // The stored job itself is left untouched
+    assert!(!job.options.core_transfer.dry_run);
+}
+
+#[test]
+fn test_job_runs_unchanged_with_test_mode_off() {
+    let job = create_test_job();
+
+    let run = job_for_run(&job, false);
+
+    assert_eq!(*run, job);
+    assert!(records_run_results(
+        &InvocationStatus::Succeeded,
+        run.options.core_transfer.dry_run
+    ));
+    assert!(!records_run_results(&InvocationStatus::Failed, false));
+}
+
+#[test]
+fn test_command_was_dry_run_detects_long_and_short_flags() {
+    assert!(command_was_dry_run("rsync -a --dry-run /src/ /dst/"));
+    assert!(command_was_dry_run("rsync -n -a /src/ /dst/"));
+    assert!(command_was_dry_run("rsync -avn /src/ /dst/"));
+    assert!(!command_was_dry_run("rsync -av /src/ /dst/"));
+    assert!(!command_was_dry_run(
+        "rsync -a --log-file-format='%i %n%L' /src/ /dst/"
+    ));
+    assert!(!command_was_dry_run("rsync -a /src/ /mnt/-n/"));
+}
+
+#[test]
+fn test_preview_command_reflects_test_mode() {
+    let db = Database::in_memory().unwrap();
+    let conn = db.conn();
+    let settings_service = Arc::new(SettingsService::new(Arc::new(
+        SqliteSettingsRepository::new(conn.clone()),
+    )));
+    let executor = JobExecutor::new(
+        Arc::new(JobService::new(
+            Arc::new(SqliteJobRepository::new(conn.clone())),
+            Arc::new(SqliteInvocationRepository::new(conn.clone())),
+            Arc::new(SqliteSnapshotRepository::new(conn.clone())),
+        )),
+        Arc::new(StatisticsService::new(Arc::new(
+            SqliteStatisticsRepository::new(conn),
+        ))),
+        Arc::clone(&settings_service),
+        Arc::new(RunningJobs::new()),
+        "/tmp/logs".to_string(),
+    );
+    let job = create_test_job();
+
+    let command = executor.preview_command(&job);
+    assert!(command.starts_with("rsync "));
+    assert!(!command.contains("--dry-run"));
+
+    settings_service.set_test_mode(true).unwrap();
+    assert!(executor.preview_command(&job).contains("--dry-run"));
+}
@@ -0,0 +1,252 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::models::itemize::ItemizedChange;
+use crate::models::job::JobStatus;
+use crate::models::progress::{JobStatusEvent, LogLine, ProgressUpdate};
+use crate::services::execution_handler::ExecutionEventHandler;
+use crate::services::log_writer::RotatingLogWriter;
+use crate::services::throttled_handler::ThrottledHandler;
+
+/// What reached the downstream handler, in order.
+#[derive(Debug, Clone, PartialEq)]
+enum Received {
+    Lines(Vec<String>),
+    Progress(f64),
+    Status(JobStatus),
+}
+
+#[derive(Default)]
+struct RecordingHandler {
+    received: Mutex<Vec<Received>>,
+}
+
+impl RecordingHandler {
+    fn received(&self) -> Vec<Received> {
+        self.received.lock().unwrap().clone()
+    }
+
+    fn progress_count(&self) -> usize {
+        self.received()
+            .iter()
+            .filter(|r| matches!(r, Received::Progress(_)))
+            .count()
+    }
+}
+
+impl ExecutionEventHandler for RecordingHandler {
+    fn on_log_line(&self, log_line: LogLine) {
+        self.on_log_lines(vec![log_line]);
+    }
+    fn on_log_lines(&self, log_lines: Vec<LogLine>) {
+        let lines = log_lines.into_iter().map(|l| l.line).collect();
+        self.received.lock().unwrap().push(Received::Lines(lines));
+    }
+    fn on_progress(&self, progress: &ProgressUpdate) {
+        self.received
+            .lock()
+            .unwrap()
+            .push(Received::Progress(progress.percentage));
+    }
+    fn on_status_change(&self, status: JobStatusEvent) {
+        self.received
+            .lock()
+            .unwrap()
+            .push(Received::Status(status.status));
+    }
+    fn on_itemized_change(&self, _invocation_id: Uuid, _change: &ItemizedChange) {}
+}
+
+fn throttled(interval: Duration) -> (ThrottledHandler, Arc<RecordingHandler>) {
+    let downstream = Arc::new(RecordingHandler::default());
+    let handler = ThrottledHandler::with_interval(downstream.clone(), interval);
+    (handler, downstream)
+}
+
+fn progress(invocation_id: Uuid, percentage: f64) -> ProgressUpdate {
+    ProgressUpdate {
+        invocation_id,
+        bytes_transferred: 0,
+        percentage,
+        transfer_rate: "1.00MB/s".to_string(),
+        elapsed: "0:00:01".to_string(),
+        files_transferred: 0,
+        files_remaining: 0,
+        files_total: 0,
+        eta_secs: None,
+        current_file: None,
+    }
+}
+
+fn log_line(invocation_id: Uuid, line: String) -> LogLine {
+    LogLine {
+        invocation_id,
+        timestamp: Utc::now(),
+        line,
+        is_stderr: false,
+    }
+}
+
+fn status(invocation_id: Uuid, status: JobStatus) -> JobStatusEvent {
+    JobStatusEvent {
+        job_id: Uuid::new_v4(),
+        invocation_id,
+        status,
+        exit_code: Some(0),
+        error_message: None,
+    }
+}
+
+#[test]
+fn test_progress_burst_is_coalesced_to_latest() {
+    let (handler, downstream) = throttled(Duration::from_secs(60));
+    let id = Uuid::new_v4();
+
+    for i in 0..1000 {
+        handler.on_progress(&progress(id, i as f64 / 10.0));
+    }
+    // Only the first update got through; the rest wait as one
+    assert_eq!(downstream.received(), vec![Received::Progress(0.0)]);
+
+    handler.on_status_change(status(id, JobStatus::Completed));
+    assert_eq!(
+        downstream.received(),
+        vec![
+            Received::Progress(0.0),
+            Received::Progress(99.9),
+            Received::Status(JobStatus::Completed),
+        ]
+    );
+}
+
+#[test]
+fn test_log_burst_is_batched_and_log_file_keeps_every_line() {
+    let tmp = tempfile::tempdir().unwrap();
+    let log_path = tmp.path().join("run.log");
+    let mut writer = RotatingLogWriter::open(&log_path, None).unwrap();
+    let (handler, downstream) = throttled(Duration::from_secs(60));
+    let id = Uuid::new_v4();
+
+    // Like the executor: write the file, then tell the handler
+    let lines: Vec<String> = (0..5000).map(|i| format!("file_{:04}.txt", i)).collect();
+    for line in &lines {
+        writer.write_line(line).unwrap();
+        handler.on_log_line(log_line(id, line.clone()));
+    }
+    writer.flush().unwrap();
+    handler.on_status_change(status(id, JobStatus::Completed));
+
+    let received = downstream.received();
+    assert_eq!(
+        received.len(),
+        3,
+        "one batch per interval plus the final flush"
+    );
+    let forwarded: Vec<String> = received
+        .iter()
+        .filter_map(|r| match r {
+            Received::Lines(lines) => Some(lines.clone()),
+            _ => None,
+        })
+        .flatten()
+        .collect();
+    assert_eq!(forwarded, lines);
+    assert_eq!(received[2], Received::Status(JobStatus::Completed));
+
+    let on_disk: Vec<String> = std::fs::read_to_string(&log_path)
+        .unwrap()
+        .lines()
+        .map(str::to_string)
+        .collect();
+    assert_eq!(on_disk, lines);
+}
+
+#[test]
+fn test_running_status_flushes_but_keeps_throttling() {
+    let (handler, downstream) = throttled(Duration::from_secs(60));
+    let id = Uuid::new_v4();
+
+    handler.on_progress(&progress(id, 1.0));
+    handler.on_progress(&progress(id, 2.0));
+    handler.on_status_change(status(id, JobStatus::Running));
+    handler.on_progress(&progress(id, 3.0));
+
+    assert_eq!(
+        downstream.received(),
+        vec![
+            Received::Progress(1.0),
+            Received::Progress(2.0),
+            Received::Status(JobStatus::Running),
+        ]
+    );
+}
+
+#[test]
+fn test_invocations_are_throttled_separately() {
+    let (handler, downstream) = throttled(Duration::from_secs(60));
+
+    handler.on_progress(&progress(Uuid::new_v4(), 10.0));
+    handler.on_progress(&progress(Uuid::new_v4(), 20.0));
+
+    assert_eq!(downstream.progress_count(), 2);
+}
+
+#[test]
+fn test_elapsed_interval_forwards_immediately() {
+    let (handler, downstream) = throttled(Duration::ZERO);
+    let id = Uuid::new_v4();
+
+    for i in 0..10 {
+        handler.on_progress(&progress(id, i as f64));
+    }
+
+    assert_eq!(downstream.progress_count(), 10);
+}
+
+#[test]
+fn test_held_events_are_flushed_without_further_events() {
+    let (handler, downstream) = throttled(Duration::from_millis(50));
+    let id = Uuid::new_v4();
+
+    handler.on_progress(&progress(id, 1.0));
+    handler.on_progress(&progress(id, 2.0));
+    handler.on_log_line(log_line(id, "first".to_string()));
+    handler.on_log_line(log_line(id, "second".to_string()));
+    assert_eq!(
+        downstream.received(),
+        vec![
+            Received::Progress(1.0),
+            Received::Lines(vec!["first".to_string()]),
+        ]
+    );
+
+    // Nothing else arrives, so the timer has to deliver the held events
+    std::thread::sleep(Duration::from_millis(500));
+    let received = downstream.received();
+    assert_eq!(received.len(), 4, "{:?}", received);
+    assert!(received.contains(&Received::Progress(2.0)));
+    assert!(received.contains(&Received::Lines(vec!["second".to_string()])));
+}
+
+#[test]
+fn test_flush_timer_leaves_finished_run_alone() {
+    let (handler, downstream) = throttled(Duration::from_millis(50));
+    let id = Uuid::new_v4();
+
+    handler.on_progress(&progress(id, 1.0));
+    handler.on_progress(&progress(id, 2.0));
+    handler.on_status_change(status(id, JobStatus::Completed));
+    std::thread::sleep(Duration::from_millis(300));
+
+    assert_eq!(
+        downstream.received(),
+        vec![
+            Received::Progress(1.0),
+            Received::Progress(2.0),
+            Received::Status(JobStatus::Completed),
+        ]
+    );
+}
@@ -0,0 +1,109 @@
+use std::cell::RefCell;
+
+use crate::models::statistics::TransferEstimate;
+use crate::rsync_client::{RsyncClient, RsyncError, RsyncResult};
+use crate::services::transfer_estimate::estimate_transfer;
+use crate::tests::test_helpers::create_test_job;
+
+const DRY_RUN_STATS: &str = "\
+sending incremental file list
+
+Number of files: 2,048 (reg: 2,000, dir: 48)
+Number of created files: 150 (reg: 150)
+Number of deleted files: 0
+Number of regular files transferred: 150
+Total file size: 52,428,800,000 bytes
+Total transferred file size: 3,221,225,472 bytes
+Literal data: 0 bytes
+Matched data: 0 bytes
+File list size: 0
+Total bytes sent: 65,432
+Total bytes received: 1,234
+
+sent 65,432 bytes  received 1,234 bytes  133,332.00 bytes/sec
+total size is 52,428,800,000  speedup is 786,432.00 (DRY RUN)";
+
+/// Returns canned stdout for dry runs and records the arguments it was given.
+struct CannedRsync {
+    stdout: String,
+    dry_run_args: RefCell<Vec<String>>,
+}
+
+impl CannedRsync {
+    fn new(stdout: &str) -> Self {
+        Self {
+            stdout: stdout.to_string(),
+            dry_run_args: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+impl RsyncClient for CannedRsync {
+    fn execute(&self, _args: &[String]) -> Result<RsyncResult, RsyncError> {
+        panic!("estimate must not run a real transfer");
+    }
+
+    fn dry_run(&self, args: &[String]) -> Result<RsyncResult, RsyncError> {
+        *self.dry_run_args.borrow_mut() = args.to_vec();
+        Ok(RsyncResult {
+            exit_code: 0,
+            stdout: self.stdout.clone(),
+            stderr: String::new(),
+            command: format!("rsync {}", args.join(" ")),
+        })
+    }
+
+    fn version(&self) -> Result<String, RsyncError> {
+        Ok("rsync  version 3.2.7  protocol version 31".to_string())
+    }
+}
+
+#[test]
+fn test_estimate_parses_dry_run_stats() {
+    let rsync = CannedRsync::new(DRY_RUN_STATS);
+    let estimate = estimate_transfer(&create_test_job(), &rsync, None, true, false).unwrap();
+
+    assert_eq!(
+        estimate,
+        TransferEstimate {
+            bytes_to_transfer: 3_221_225_472,
+            files_to_transfer: 150,
+            total_size_bytes: 52_428_800_000,
+            total_files: 2_048,
+        }
+    );
+}
+
+#[test]
+fn test_estimate_passes_dry_run_and_stats_flags() {
+    let rsync = CannedRsync::new(DRY_RUN_STATS);
+    let mut job = create_test_job();
+    job.options.output.progress = true;
+    job.options.output.itemize_changes = true;
+
+    estimate_transfer(&job, &rsync, None, true, false).unwrap();
+
+    let args = rsync.dry_run_args.borrow();
+    assert!(args.contains(&"--dry-run".to_string()));
+    assert!(args.contains(&"--stats".to_string()));
+    assert!(!args.contains(&"--progress".to_string()));
+    assert!(!args.contains(&"--itemize-changes".to_string()));
+}
+
+#[test]
+fn test_estimate_uses_link_dest_for_snapshots() {
+    let rsync = CannedRsync::new(DRY_RUN_STATS);
+    let link_dest = Some("/backups/2024-01-01");
+    estimate_transfer(&create_test_job(), &rsync, link_dest, true, false).unwrap();
+
+    let args = rsync.dry_run_args.borrow();
+    assert!(args
+        .iter()
+        .any(|a| a.contains("--link-dest") && a.contains("/backups/2024-01-01")));
+}
+
+#[test]
+fn test_estimate_fails_without_stats_block() {
+    let rsync = CannedRsync::new("sending incremental file list\n");
+    assert!(estimate_transfer(&create_test_job(), &rsync, None, true, false).is_err());
+}
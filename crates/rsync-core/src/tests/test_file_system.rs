@@ -1,6 +1,7 @@
-use std::cell::RefCell;
+use chrono::{DateTime, Utc};
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
 use crate::file_system::{FileSystem, FsError};
 
@@ -17,6 +18,13 @@ struct Inner {
     next_inode: u64,
     inode_paths: HashMap<u64, HashSet<PathBuf>>,
     available_space: u64,
+    read_only_dirs: Vec<PathBuf>,
+    /// Mounted volumes besides the root, with their labels.
+    mounts: Vec<(PathBuf, Option<String>)>,
+    /// Filesystem types by path prefix; unlisted paths have no known type.
+    filesystem_types: Vec<(PathBuf, String)>,
+    /// Modification times; nodes without an entry report the UNIX epoch.
+    modified_times: HashMap<PathBuf, DateTime<Utc>>,
 }
 
 impl Inner {
@@ -28,9 +36,30 @@ impl Inner {
             next_inode: 1,
             inode_paths: HashMap::new(),
             available_space: u64::MAX,
+            read_only_dirs: Vec::new(),
+            mounts: Vec::new(),
+            filesystem_types: Vec::new(),
+            modified_times: HashMap::new(),
         }
     }
 
+    fn check_writable(&self, path: &Path) -> Result<(), FsError> {
+        if self.read_only_dirs.iter().any(|dir| path.starts_with(dir)) {
+            return Err(FsError::PermissionDenied(path.display().to_string()));
+        }
+        Ok(())
+    }
+
+    /// The innermost mount containing `path`; the root is always mounted.
+    fn mount_for(&self, path: &Path) -> (PathBuf, Option<String>) {
+        self.mounts
+            .iter()
+            .filter(|(mount, _)| path.starts_with(mount))
+            .max_by_key(|(mount, _)| mount.as_os_str().len())
+            .cloned()
+            .unwrap_or_else(|| (PathBuf::from("/"), None))
+    }
+
     fn allocate_inode(&mut self) -> u64 {
         let inode = self.next_inode;
         self.next_inode += 1;
@@ -65,18 +94,18 @@ impl Inner {
 }
 
 pub struct TestFileSystem {
-    inner: RefCell<Inner>,
+    inner: Mutex<Inner>,
 }
 
 impl TestFileSystem {
     pub fn new() -> Self {
         Self {
-            inner: RefCell::new(Inner::new()),
+            inner: Mutex::new(Inner::new()),
         }
     }
 
     pub fn with_dir(self, path: &str) -> Self {
-        let mut inner = self.inner.borrow_mut();
+        let mut inner = self.inner.lock().unwrap();
         let path = PathBuf::from(path);
         inner.ensure_parents(&path);
         inner.nodes.insert(path, FsNode::Directory);
@@ -85,7 +114,7 @@ impl TestFileSystem {
     }
 
     pub fn with_file(self, path: &str, content: &str) -> Self {
-        let mut inner = self.inner.borrow_mut();
+        let mut inner = self.inner.lock().unwrap();
         let path = PathBuf::from(path);
         inner.ensure_parents(&path);
         let inode = inner.allocate_inode();
@@ -102,12 +131,53 @@ impl TestFileSystem {
     }
 
     pub fn with_available_space(self, bytes: u64) -> Self {
-        self.inner.borrow_mut().available_space = bytes;
+        self.inner.lock().unwrap().available_space = bytes;
+        self
+    }
+
+    /// Make writes, copies and renames into `path` fail with `PermissionDenied`.
+    pub fn with_read_only_dir(self, path: &str) -> Self {
+        self.inner
+            .lock()
+            .unwrap()
+            .read_only_dirs
+            .push(PathBuf::from(path));
+        self
+    }
+
+    /// Report `fs_type` as the filesystem type for `path` and everything under it.
+    pub fn with_filesystem_type(self, path: &str, fs_type: &str) -> Self {
+        self.inner
+            .lock()
+            .unwrap()
+            .filesystem_types
+            .push((PathBuf::from(path), fs_type.to_string()));
+        self
+    }
+
+    /// Report `time` as the modification time of the existing node at `path`.
+    pub fn with_modified(self, path: &str, time: DateTime<Utc>) -> Self {
+        self.inner
+            .lock()
+            .unwrap()
+            .modified_times
+            .insert(PathBuf::from(path), time);
+        self
+    }
+
+    /// Mount a volume at `path`, creating the mountpoint directory.
+    pub fn with_mount(self, path: &str, label: Option<&str>) -> Self {
+        let mut inner = self.inner.lock().unwrap();
+        let path = PathBuf::from(path);
+        inner.ensure_parents(&path);
+        inner.nodes.insert(path.clone(), FsNode::Directory);
+        inner.mounts.push((path, label.map(String::from)));
+        drop(inner);
         self
     }
 
     pub fn files_under(&self, path: &str) -> Vec<PathBuf> {
-        let inner = self.inner.borrow();
+        let inner = self.inner.lock().unwrap();
         let base = PathBuf::from(path);
         let mut result: Vec<PathBuf> = inner
             .nodes
@@ -122,7 +192,7 @@ impl TestFileSystem {
     }
 
     pub fn are_hard_linked(&self, path1: &str, path2: &str) -> bool {
-        let inner = self.inner.borrow();
+        let inner = self.inner.lock().unwrap();
         let p1 = PathBuf::from(path1);
         let p2 = PathBuf::from(path2);
 
@@ -136,7 +206,7 @@ impl TestFileSystem {
     }
 
     pub fn symlink_target(&self, path: &str) -> Option<PathBuf> {
-        let inner = self.inner.borrow();
+        let inner = self.inner.lock().unwrap();
         match inner.nodes.get(&PathBuf::from(path)) {
             Some(FsNode::Symlink { target }) => Some(target.clone()),
             _ => None,
@@ -144,7 +214,7 @@ impl TestFileSystem {
     }
 
     pub fn file_content(&self, path: &str) -> Option<String> {
-        let inner = self.inner.borrow();
+        let inner = self.inner.lock().unwrap();
         match inner.nodes.get(&PathBuf::from(path)) {
             Some(FsNode::File { content, .. }) => Some(content.clone()),
             _ => None,
@@ -160,23 +230,44 @@ impl Default for TestFileSystem {
 
 impl FileSystem for TestFileSystem {
     fn exists(&self, path: &Path) -> bool {
-        self.inner.borrow().nodes.contains_key(path)
+        self.inner.lock().unwrap().nodes.contains_key(path)
     }
 
     fn is_dir(&self, path: &Path) -> bool {
-        matches!(self.inner.borrow().nodes.get(path), Some(FsNode::Directory))
+        matches!(
+            self.inner.lock().unwrap().nodes.get(path),
+            Some(FsNode::Directory)
+        )
     }
 
     fn is_file(&self, path: &Path) -> bool {
-        matches!(self.inner.borrow().nodes.get(path), Some(FsNode::File { .. }))
+        matches!(
+            self.inner.lock().unwrap().nodes.get(path),
+            Some(FsNode::File { .. })
+        )
     }
 
     fn is_symlink(&self, path: &Path) -> bool {
-        matches!(self.inner.borrow().nodes.get(path), Some(FsNode::Symlink { .. }))
+        matches!(
+            self.inner.lock().unwrap().nodes.get(path),
+            Some(FsNode::Symlink { .. })
+        )
+    }
+
+    fn modified(&self, path: &Path) -> Result<DateTime<Utc>, FsError> {
+        let inner = self.inner.lock().unwrap();
+        if !inner.nodes.contains_key(path) {
+            return Err(FsError::NotFound(path.display().to_string()));
+        }
+        Ok(inner
+            .modified_times
+            .get(path)
+            .copied()
+            .unwrap_or(DateTime::<Utc>::UNIX_EPOCH))
     }
 
     fn create_dir_all(&self, path: &Path) -> Result<(), FsError> {
-        let mut inner = self.inner.borrow_mut();
+        let mut inner = self.inner.lock().unwrap();
         let mut current = PathBuf::new();
         for component in path.components() {
             current.push(component);
@@ -188,7 +279,7 @@ impl FileSystem for TestFileSystem {
     }
 
     fn remove_dir_all(&self, path: &Path) -> Result<(), FsError> {
-        let mut inner = self.inner.borrow_mut();
+        let mut inner = self.inner.lock().unwrap();
         if !inner.nodes.contains_key(path) {
             return Err(FsError::NotFound(path.display().to_string()));
         }
@@ -210,8 +301,29 @@ impl FileSystem for TestFileSystem {
         Ok(())
     }
 
+    fn remove_file(&self, path: &Path) -> Result<(), FsError> {
+        let mut inner = self.inner.lock().unwrap();
+        match inner.nodes.get(path) {
+            Some(FsNode::File { inode, .. }) => {
+                let inode = *inode;
+                inner.unregister_inode_path(inode, path);
+                inner.nodes.remove(path);
+                Ok(())
+            }
+            Some(FsNode::Symlink { .. }) => {
+                inner.nodes.remove(path);
+                Ok(())
+            }
+            Some(FsNode::Directory) => Err(FsError::IoError(format!(
+                "{} is a directory",
+                path.display()
+            ))),
+            None => Err(FsError::NotFound(path.display().to_string())),
+        }
+    }
+
     fn read_dir(&self, path: &Path) -> Result<Vec<PathBuf>, FsError> {
-        let inner = self.inner.borrow();
+        let inner = self.inner.lock().unwrap();
         if !matches!(inner.nodes.get(path), Some(FsNode::Directory)) {
             return Err(FsError::NotADirectory(path.display().to_string()));
         }
@@ -233,7 +345,7 @@ impl FileSystem for TestFileSystem {
     }
 
     fn read_to_string(&self, path: &Path) -> Result<String, FsError> {
-        let inner = self.inner.borrow();
+        let inner = self.inner.lock().unwrap();
         match inner.nodes.get(path) {
             Some(FsNode::File { content, .. }) => Ok(content.clone()),
             Some(_) => Err(FsError::IoError(format!(
@@ -245,7 +357,8 @@ impl FileSystem for TestFileSystem {
     }
 
     fn write(&self, path: &Path, content: &str) -> Result<(), FsError> {
-        let mut inner = self.inner.borrow_mut();
+        let mut inner = self.inner.lock().unwrap();
+        inner.check_writable(path)?;
         inner.ensure_parents(path);
 
         // If the file already exists, update content but keep inode
@@ -273,7 +386,7 @@ impl FileSystem for TestFileSystem {
     }
 
     fn create_symlink(&self, original: &Path, link: &Path) -> Result<(), FsError> {
-        let mut inner = self.inner.borrow_mut();
+        let mut inner = self.inner.lock().unwrap();
         inner.ensure_parents(link);
         inner.nodes.insert(
             link.to_path_buf(),
@@ -285,7 +398,7 @@ impl FileSystem for TestFileSystem {
     }
 
     fn read_link(&self, path: &Path) -> Result<PathBuf, FsError> {
-        let inner = self.inner.borrow();
+        let inner = self.inner.lock().unwrap();
         match inner.nodes.get(path) {
             Some(FsNode::Symlink { target }) => Ok(target.clone()),
             Some(_) => Err(FsError::IoError(format!(
@@ -297,7 +410,7 @@ impl FileSystem for TestFileSystem {
     }
 
     fn remove_symlink(&self, path: &Path) -> Result<(), FsError> {
-        let mut inner = self.inner.borrow_mut();
+        let mut inner = self.inner.lock().unwrap();
         match inner.nodes.get(path) {
             Some(FsNode::Symlink { .. }) => {
                 inner.nodes.remove(path);
@@ -312,11 +425,11 @@ impl FileSystem for TestFileSystem {
     }
 
     fn available_space(&self, _path: &Path) -> Result<u64, FsError> {
-        Ok(self.inner.borrow().available_space)
+        Ok(self.inner.lock().unwrap().available_space)
     }
 
     fn dir_size(&self, path: &Path) -> Result<u64, FsError> {
-        let inner = self.inner.borrow();
+        let inner = self.inner.lock().unwrap();
         if !matches!(inner.nodes.get(path), Some(FsNode::Directory)) {
             return Err(FsError::NotADirectory(path.display().to_string()));
         }
@@ -333,9 +446,30 @@ impl FileSystem for TestFileSystem {
         Ok(size)
     }
 
+    fn unique_dir_size(&self, path: &Path) -> Result<u64, FsError> {
+        let inner = self.inner.lock().unwrap();
+        if !matches!(inner.nodes.get(path), Some(FsNode::Directory)) {
+            return Err(FsError::NotADirectory(path.display().to_string()));
+        }
+
+        let mut seen = HashSet::new();
+        let size: u64 = inner
+            .nodes
+            .iter()
+            .filter(|(p, _)| p.starts_with(path) && *p != path)
+            .filter_map(|(_, node)| match node {
+                FsNode::File { content, inode } if seen.insert(*inode) => {
+                    Some(content.len() as u64)
+                }
+                _ => None,
+            })
+            .sum();
+        Ok(size)
+    }
+
     fn copy_file(&self, from: &Path, to: &Path) -> Result<(), FsError> {
         let content = {
-            let inner = self.inner.borrow();
+            let inner = self.inner.lock().unwrap();
             match inner.nodes.get(from) {
                 Some(FsNode::File { content, .. }) => content.clone(),
                 Some(_) => {
@@ -348,7 +482,8 @@ impl FileSystem for TestFileSystem {
             }
         };
 
-        let mut inner = self.inner.borrow_mut();
+        let mut inner = self.inner.lock().unwrap();
+        inner.check_writable(to)?;
         inner.ensure_parents(to);
         let inode = inner.allocate_inode();
         inner.register_inode_path(inode, to.to_path_buf());
@@ -358,8 +493,38 @@ impl FileSystem for TestFileSystem {
         Ok(())
     }
 
+    fn rename(&self, from: &Path, to: &Path) -> Result<(), FsError> {
+        let mut inner = self.inner.lock().unwrap();
+        inner.check_writable(to)?;
+        if !inner.nodes.contains_key(from) {
+            return Err(FsError::NotFound(from.display().to_string()));
+        }
+        // A directory takes everything below it along
+        let moved: Vec<PathBuf> = inner
+            .nodes
+            .keys()
+            .filter(|p| p.starts_with(from))
+            .cloned()
+            .collect();
+        inner.ensure_parents(to);
+        for old in moved {
+            let new = match old.strip_prefix(from) {
+                Ok(rest) if !rest.as_os_str().is_empty() => to.join(rest),
+                _ => to.to_path_buf(),
+            };
+            let node = inner.nodes.remove(&old).expect("listed above");
+            if let FsNode::File { inode, .. } = &node {
+                let inode = *inode;
+                inner.unregister_inode_path(inode, &old);
+                inner.register_inode_path(inode, new.clone());
+            }
+            inner.nodes.insert(new, node);
+        }
+        Ok(())
+    }
+
     fn hard_link(&self, original: &Path, link: &Path) -> Result<(), FsError> {
-        let mut inner = self.inner.borrow_mut();
+        let mut inner = self.inner.lock().unwrap();
         let (content, inode) = match inner.nodes.get(original) {
             Some(FsNode::File { content, inode }) => (content.clone(), *inode),
             Some(_) => {
@@ -380,7 +545,7 @@ impl FileSystem for TestFileSystem {
     }
 
     fn walk_dir(&self, path: &Path) -> Result<Vec<PathBuf>, FsError> {
-        let inner = self.inner.borrow();
+        let inner = self.inner.lock().unwrap();
         if !matches!(inner.nodes.get(path), Some(FsNode::Directory)) {
             return Err(FsError::NotADirectory(path.display().to_string()));
         }
@@ -395,7 +560,22 @@ impl FileSystem for TestFileSystem {
         Ok(result)
     }
 
-    fn filesystem_type(&self, _path: &Path) -> Option<String> {
-        None
+    fn filesystem_type(&self, path: &Path) -> Option<String> {
+        self.inner
+            .lock()
+            .unwrap()
+            .filesystem_types
+            .iter()
+            .filter(|(prefix, _)| path.starts_with(prefix))
+            .max_by_key(|(prefix, _)| prefix.as_os_str().len())
+            .map(|(_, fs_type)| fs_type.clone())
+    }
+
+    fn mount_point(&self, path: &Path) -> Option<PathBuf> {
+        Some(self.inner.lock().unwrap().mount_for(path).0)
+    }
+
+    fn volume_label(&self, path: &Path) -> Option<String> {
+        self.inner.lock().unwrap().mount_for(path).1
     }
 }
@@ -4,7 +4,8 @@ use chrono::Utc;
 use uuid::Uuid;
 
 use crate::models::job::{
-    BackupMode, JobDefinition, RetentionPolicy, RsyncOptions, StorageLocation, TransferConfig,
+    BackupMode, JobAppearance, JobDefinition, JobRunPolicy, JobSafety, RetentionPolicy,
+    RsyncOptions, StorageLocation, TransferConfig,
 };
 use crate::tests::test_file_system::TestFileSystem;
 use crate::tests::test_rsync_client::TestRsyncClient;
@@ -40,6 +41,9 @@ pub fn create_mirror_job(source: &str, dest: &str) -> JobDefinition {
             ..RsyncOptions::default()
         },
         ssh_config: None,
+        appearance: JobAppearance::default(),
+        run_policy: JobRunPolicy::default(),
+        safety: JobSafety::default(),
         schedule: None,
         enabled: true,
         created_at: now,
@@ -66,6 +70,9 @@ pub fn create_versioned_job(source: &str, dest: &str, backup_dir: &str) -> JobDe
         },
         options: RsyncOptions::default(),
         ssh_config: None,
+        appearance: JobAppearance::default(),
+        run_policy: JobRunPolicy::default(),
+        safety: JobSafety::default(),
         schedule: None,
         enabled: true,
         created_at: now,
@@ -96,6 +103,9 @@ pub fn create_snapshot_job(
         },
         options: RsyncOptions::default(),
         ssh_config: None,
+        appearance: JobAppearance::default(),
+        run_policy: JobRunPolicy::default(),
+        safety: JobSafety::default(),
         schedule: None,
         enabled: true,
         created_at: now,
@@ -1,12 +1,29 @@
+use rsync_core::models::audit::AuditEntry;
+use rsync_core::models::backup_set::{BackupSet, BackupSetRun};
 use rsync_core::models::command::{CommandExplanation, ParsedCommand};
-use rsync_core::models::execution::backup::{BackupInvocation, SnapshotRecord};
-use rsync_core::models::execution::itemize::ItemizedChange;
+use rsync_core::models::dashboard::DashboardSummary;
+use rsync_core::models::execution::backup::{BackupInvocation, SnapshotRecord, SnapshotVerification};
+use rsync_core::models::execution::itemize::{
+    ItemizedChange, RestorePreview, SnapshotDiff, StoredItemizedChanges,
+};
 use rsync_core::models::execution::log::LogEntry;
-use rsync_core::models::execution::progress::{JobStatusEvent, LogLine, ProgressUpdate};
-use rsync_core::models::execution::statistics::{AggregatedStats, RunStatistic};
-use rsync_core::models::job::{ExportData, JobDefinition};
+use rsync_core::models::execution::progress::{
+    AggregateProgress, JobStatusEvent, LogLine, ProgressUpdate, RunningJobInfo,
+};
+use rsync_core::models::execution::statistics::{
+    AggregatedStats, DestinationUsage, RunStatistic, TransferEstimate,
+};
+use rsync_core::models::job::{
+    ExportData, FullExportData, HostKey, ImportPreview, JobDefinition, RetargetResult,
+};
+use rsync_core::models::pattern::PatternTestEntry;
+use rsync_core::models::schedule::SchedulerEvent;
+use rsync_core::models::status::StatusColor;
 use rsync_core::models::scrubber::{ScrubApplyResult, ScrubScanResult};
-use rsync_core::models::settings::{DryModeSettings, RetentionSettings};
+use rsync_core::models::settings::{
+    AnomalySettings, DryModeSettings, LogFileSettings, LogRelocationResult, QuietHours,
+    RetentionSettings, SafeMirrorSettings, UndoPruneResult,
+};
 use rsync_core::models::validation::PreflightResult;
 use ts_rs::TS;
 
@@ -14,21 +31,46 @@ fn main() {
     // Root types — export_all() recursively exports all referenced types
     JobDefinition::export_all().expect("JobDefinition");
     ExportData::export_all().expect("ExportData");
+    FullExportData::export_all().expect("FullExportData");
+    ImportPreview::export_all().expect("ImportPreview");
+    HostKey::export_all().expect("HostKey");
+    RetargetResult::export_all().expect("RetargetResult");
     BackupInvocation::export_all().expect("BackupInvocation");
     SnapshotRecord::export_all().expect("SnapshotRecord");
+    SnapshotVerification::export_all().expect("SnapshotVerification");
     CommandExplanation::export_all().expect("CommandExplanation");
     ParsedCommand::export_all().expect("ParsedCommand");
     PreflightResult::export_all().expect("PreflightResult");
+    PatternTestEntry::export_all().expect("PatternTestEntry");
     ScrubScanResult::export_all().expect("ScrubScanResult");
     ScrubApplyResult::export_all().expect("ScrubApplyResult");
     RetentionSettings::export_all().expect("RetentionSettings");
     DryModeSettings::export_all().expect("DryModeSettings");
+    LogFileSettings::export_all().expect("LogFileSettings");
+    AnomalySettings::export_all().expect("AnomalySettings");
+    SafeMirrorSettings::export_all().expect("SafeMirrorSettings");
+    LogRelocationResult::export_all().expect("LogRelocationResult");
+    UndoPruneResult::export_all().expect("UndoPruneResult");
+    QuietHours::export_all().expect("QuietHours");
+    SchedulerEvent::export_all().expect("SchedulerEvent");
+    AuditEntry::export_all().expect("AuditEntry");
+    BackupSet::export_all().expect("BackupSet");
+    BackupSetRun::export_all().expect("BackupSetRun");
     ProgressUpdate::export_all().expect("ProgressUpdate");
+    AggregateProgress::export_all().expect("AggregateProgress");
+    RunningJobInfo::export_all().expect("RunningJobInfo");
     LogLine::export_all().expect("LogLine");
     JobStatusEvent::export_all().expect("JobStatusEvent");
+    StatusColor::export_all().expect("StatusColor");
     RunStatistic::export_all().expect("RunStatistic");
     AggregatedStats::export_all().expect("AggregatedStats");
+    TransferEstimate::export_all().expect("TransferEstimate");
+    DestinationUsage::export_all().expect("DestinationUsage");
     ItemizedChange::export_all().expect("ItemizedChange");
+    StoredItemizedChanges::export_all().expect("StoredItemizedChanges");
+    SnapshotDiff::export_all().expect("SnapshotDiff");
+    RestorePreview::export_all().expect("RestorePreview");
     LogEntry::export_all().expect("LogEntry");
+    DashboardSummary::export_all().expect("DashboardSummary");
     println!("TypeScript types exported successfully.");
 }
@@ -1,25 +1,60 @@
 use std::io::{BufRead, BufReader};
 use std::sync::Arc;
 
+use chrono::{DateTime, Local, Utc};
 use serde::Serialize;
-use tauri::State;
+use tauri::{Emitter, State};
 use uuid::Uuid;
 
 use rsync_core::rsync_client::process_rsync_client::ProcessRsyncClient;
+use rsync_core::ssh_client::process_ssh_client::ProcessSshClient;
 use rsync_core::file_system::real_file_system::RealFileSystem;
-use rsync_core::models::backup::{BackupInvocation, InvocationTrigger, SnapshotRecord};
-use rsync_core::models::job::JobDefinition;
-use rsync_core::models::statistics::AggregatedStats;
-use rsync_core::models::validation::PreflightResult;
+use rsync_core::models::audit::AuditEntry;
+use rsync_core::models::backup::{
+    BackupInvocation, InvocationTrigger, SnapshotRecord, SnapshotVerification,
+};
+use rsync_core::models::backup_set::BackupSet;
+use rsync_core::models::dashboard::DashboardSummary;
+use rsync_core::models::itemize::{RestorePreview, SnapshotDiff, StoredItemizedChanges};
+use rsync_core::models::job::{
+    BackupMode, FullExportData, HostKey, ImportPreview, JobDefinition, RetargetResult,
+    StorageLocation,
+};
+use rsync_core::models::progress::{AggregateProgress, RunningJobInfo};
+use rsync_core::models::statistics::{AggregatedStats, DestinationUsage, TransferEstimate};
+use rsync_core::models::validation::{self, PreflightResult, ValidationCheck};
 use rsync_core::models::command::CommandExplanation;
+use rsync_core::models::pattern::PatternTestEntry;
+use rsync_core::models::rsync_options::AdvancedOptions;
+use rsync_core::models::schedule::{ScheduleConfig, ScheduleType, SchedulerEvent};
 use rsync_core::models::scrubber::{ScrubApplyResult, ScrubScanResult};
-use rsync_core::models::settings::{DryModeSettings, RetentionSettings};
+use rsync_core::models::settings::{
+    AnomalySettings, DryModeSettings, LogFileSettings, LogRelocationResult, MirrorDeletionCheck,
+    QuietHours, RetentionSettings, SafeMirrorSettings, UndoPruneResult,
+};
 use rsync_core::services::command_explainer;
 use rsync_core::services::command_parser;
+use rsync_core::services::dashboard;
+use rsync_core::services::data_budget::DataBudget;
+use rsync_core::services::destination_usage;
 use rsync_core::services::export_import;
+use rsync_core::services::history_export;
+use rsync_core::services::invocation_report;
+use rsync_core::services::job_duplicates;
+use rsync_core::services::known_hosts;
+use rsync_core::services::log_relocation;
 use rsync_core::services::log_scrubber;
+use rsync_core::services::log_writer;
+use rsync_core::services::orphaned_logs;
+use rsync_core::services::pattern_tester;
 use rsync_core::services::preflight;
+use rsync_core::services::prune_recovery;
+use rsync_core::services::restore;
+use rsync_core::services::scheduler;
 use rsync_core::services::settings_service;
+use rsync_core::services::snapshot_diff;
+use rsync_core::services::snapshot_trash;
+use rsync_core::services::transfer_estimate;
 
 use crate::execution::TauriEventHandler;
 use crate::state::AppState;
@@ -53,6 +88,21 @@ pub fn update_job(job: JobDefinition, state: State<'_, AppState>) -> Result<JobD
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub fn retarget_job(
+    id: String,
+    destination: StorageLocation,
+    state: State<'_, AppState>,
+) -> Result<RetargetResult, String> {
+    let uuid = id
+        .parse::<Uuid>()
+        .map_err(|e| format!("Invalid job ID: {e}"))?;
+    state
+        .job_service
+        .retarget(&uuid, destination)
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn delete_job(id: String, state: State<'_, AppState>) -> Result<(), String> {
     let uuid = id
@@ -64,6 +114,48 @@ pub fn delete_job(id: String, state: State<'_, AppState>) -> Result<(), String>
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub fn find_duplicate_jobs(state: State<'_, AppState>) -> Result<Vec<Vec<String>>, String> {
+    let jobs = state.job_service.list_jobs().map_err(|e| e.to_string())?;
+    Ok(job_duplicates::find_duplicate_jobs(&jobs)
+        .into_iter()
+        .map(|group| group.iter().map(Uuid::to_string).collect())
+        .collect())
+}
+
+#[tauri::command]
+pub fn jobs_referencing_path(
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<JobDefinition>, String> {
+    state
+        .job_service
+        .jobs_referencing_path(&path)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn merge_jobs(
+    keep_id: String,
+    remove_ids: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let keep = keep_id
+        .parse::<Uuid>()
+        .map_err(|e| format!("Invalid job ID: {e}"))?;
+    let remove = remove_ids
+        .iter()
+        .map(|id| {
+            id.parse::<Uuid>()
+                .map_err(|e| format!("Invalid job ID: {e}"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    state
+        .job_service
+        .merge_jobs(&keep, &remove)
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn get_job_history(
     job_id: String,
@@ -79,6 +171,97 @@ pub fn get_job_history(
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub fn get_invocation(
+    invocation_id: String,
+    state: State<'_, AppState>,
+) -> Result<BackupInvocation, String> {
+    let uuid = invocation_id
+        .parse::<Uuid>()
+        .map_err(|e| format!("Invalid invocation ID: {e}"))?;
+    state
+        .job_service
+        .get_invocation(&uuid)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_scheduler_events(
+    job_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<SchedulerEvent>, String> {
+    let uuid = job_id
+        .parse::<Uuid>()
+        .map_err(|e| format!("Invalid job ID: {e}"))?;
+    Ok(state.scheduler_events.scheduler_events(&uuid))
+}
+
+/// The most recent destructive actions (deletions, purges, resets), newest first.
+#[tauri::command]
+pub fn list_audit_entries(
+    limit: usize,
+    state: State<'_, AppState>,
+) -> Result<Vec<AuditEntry>, String> {
+    state.audit_log.list(limit).map_err(|e| e.to_string())
+}
+
+/// The next `count` run times of a schedule from now, as if it were enabled,
+/// with runs inside quiet hours moved to where the scheduler catches up.
+#[tauri::command]
+pub fn preview_schedule(
+    schedule_type: ScheduleType,
+    count: usize,
+    state: State<'_, AppState>,
+) -> Result<Vec<DateTime<Utc>>, String> {
+    let quiet_hours = state
+        .settings_service
+        .get_quiet_hours()
+        .map_err(|e| e.to_string())?;
+    let schedule = ScheduleConfig {
+        schedule_type,
+        enabled: true,
+    };
+    Ok(scheduler::upcoming_runs_around_quiet_hours(
+        &schedule,
+        Utc::now(),
+        count,
+        quiet_hours.as_ref(),
+        &Local,
+    ))
+}
+
+/// Whether a schedule will ever start its job, counting current quiet hours.
+#[tauri::command]
+pub fn check_schedule(
+    schedule_type: ScheduleType,
+    state: State<'_, AppState>,
+) -> Result<ValidationCheck, String> {
+    let quiet_hours = state
+        .settings_service
+        .get_quiet_hours()
+        .map_err(|e| e.to_string())?;
+    Ok(validation::schedule_check(
+        &schedule_type,
+        quiet_hours.as_ref(),
+        Utc::now(),
+        &Local,
+    ))
+}
+
+#[tauri::command]
+pub fn get_itemized_changes(
+    invocation_id: String,
+    state: State<'_, AppState>,
+) -> Result<Option<StoredItemizedChanges>, String> {
+    let uuid = invocation_id
+        .parse::<Uuid>()
+        .map_err(|e| format!("Invalid invocation ID: {e}"))?;
+    state
+        .job_service
+        .get_itemized_changes(&uuid)
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn execute_job(
     job_id: String,
@@ -132,6 +315,97 @@ pub fn execute_job_dry_run(
         .map(|id| id.to_string())
 }
 
+#[tauri::command]
+pub fn list_backup_sets(state: State<'_, AppState>) -> Result<Vec<BackupSet>, String> {
+    state
+        .backup_set_service
+        .list_sets()
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn create_backup_set(
+    name: String,
+    job_ids: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<BackupSet, String> {
+    let job_ids = job_ids
+        .iter()
+        .map(|id| {
+            id.parse::<Uuid>()
+                .map_err(|e| format!("Invalid job ID: {e}"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    state
+        .backup_set_service
+        .create_set(&name, job_ids)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn update_backup_set(set: BackupSet, state: State<'_, AppState>) -> Result<BackupSet, String> {
+    state
+        .backup_set_service
+        .update_set(set)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn delete_backup_set(set_id: String, state: State<'_, AppState>) -> Result<(), String> {
+    let uuid = set_id
+        .parse::<Uuid>()
+        .map_err(|e| format!("Invalid backup set ID: {e}"))?;
+    state
+        .backup_set_service
+        .delete_set(&uuid)
+        .map_err(|e| e.to_string())
+}
+
+/// Start running a backup set's jobs one after another in the background.
+/// Each job emits the usual execution events; the per-job results are
+/// emitted as `backup-set-finished` when the set is done.
+#[tauri::command]
+pub fn run_backup_set(
+    set_id: String,
+    stop_on_failure: bool,
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let uuid = set_id
+        .parse::<Uuid>()
+        .map_err(|e| format!("Invalid backup set ID: {e}"))?;
+    let set = state
+        .backup_set_service
+        .get_set(&uuid)
+        .map_err(|e| e.to_string())?;
+
+    let executor = Arc::clone(&state.job_executor);
+    std::thread::spawn(move || {
+        let handler = Arc::new(TauriEventHandler::new(app.clone()));
+        let run = executor.run_set(&set, stop_on_failure, handler);
+        let _ = app.emit("backup-set-finished", &run);
+    });
+    Ok(())
+}
+
+/// Run a past invocation's stored command again in the background,
+/// returning the new invocation's ID.
+#[tauri::command]
+pub fn rerun_invocation(
+    invocation_id: String,
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let uuid = invocation_id
+        .parse::<Uuid>()
+        .map_err(|e| format!("Invalid invocation ID: {e}"))?;
+    let handler = Arc::new(TauriEventHandler::new(app));
+    state
+        .job_executor
+        .rerun(&uuid, handler)
+        .map(|id| id.to_string())
+}
+
 #[tauri::command]
 pub fn cancel_job(job_id: String, state: State<'_, AppState>) -> Result<(), String> {
     let uuid = job_id
@@ -144,14 +418,49 @@ pub fn cancel_job(job_id: String, state: State<'_, AppState>) -> Result<(), Stri
     }
 }
 
+/// Stop every running job. Returns how many were cancelled.
+#[tauri::command]
+pub fn cancel_all_jobs(state: State<'_, AppState>) -> Result<usize, String> {
+    Ok(state.job_executor.cancel_all())
+}
+
+/// Retry a failed run of a job with `--partial --append-verify` forced, so
+/// interrupted files resume. Returns the new invocation ID.
 #[tauri::command]
-pub fn get_running_jobs(state: State<'_, AppState>) -> Result<Vec<String>, String> {
-    Ok(state
+pub fn retry_invocation(
+    invocation_id: String,
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let uuid = invocation_id
+        .parse::<Uuid>()
+        .map_err(|e| format!("Invalid invocation ID: {e}"))?;
+    let handler = Arc::new(TauriEventHandler::new(app));
+    state
         .job_executor
-        .running_job_ids()
-        .iter()
+        .retry(&uuid, handler)
         .map(|id| id.to_string())
-        .collect())
+}
+
+#[tauri::command]
+pub fn get_running_jobs(state: State<'_, AppState>) -> Result<Vec<RunningJobInfo>, String> {
+    Ok(state.job_executor.running_jobs_info())
+}
+
+#[tauri::command]
+pub fn dashboard_summary(state: State<'_, AppState>) -> Result<DashboardSummary, String> {
+    dashboard::dashboard_summary(
+        &state.job_service,
+        &state.statistics_service,
+        state.job_executor.running_jobs_info(),
+        Utc::now(),
+    )
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_aggregate_progress(state: State<'_, AppState>) -> Result<AggregateProgress, String> {
+    Ok(state.job_executor.aggregate_progress())
 }
 
 #[tauri::command]
@@ -182,12 +491,88 @@ pub fn delete_snapshot(
         .map_err(|e| e.to_string())
 }
 
+/// Pin a snapshot so retention never prunes it, or unpin it.
+#[tauri::command]
+pub fn set_snapshot_pinned(
+    snapshot_id: String,
+    pinned: bool,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let uuid = snapshot_id
+        .parse::<Uuid>()
+        .map_err(|e| format!("Invalid snapshot ID: {e}"))?;
+    state
+        .job_service
+        .set_snapshot_pinned(&uuid, pinned)
+        .map_err(|e| e.to_string())
+}
+
+/// The post-run verification of the snapshot an invocation wrote, if one ran.
+#[tauri::command]
+pub fn get_snapshot_verification(
+    invocation_id: String,
+    state: State<'_, AppState>,
+) -> Result<Option<SnapshotVerification>, String> {
+    let uuid = invocation_id
+        .parse::<Uuid>()
+        .map_err(|e| format!("Invalid invocation ID: {e}"))?;
+    state
+        .job_service
+        .get_snapshot_verification(&uuid)
+        .map_err(|e| e.to_string())
+}
+
+/// What changed from snapshot `from_id` to the later snapshot `to_id` of a job.
+#[tauri::command]
+pub fn diff_snapshots(
+    job_id: String,
+    from_id: String,
+    to_id: String,
+    state: State<'_, AppState>,
+) -> Result<SnapshotDiff, String> {
+    let job_uuid = job_id
+        .parse::<Uuid>()
+        .map_err(|e| format!("Invalid job ID: {e}"))?;
+    let snapshots = state
+        .job_service
+        .list_snapshots(&job_uuid)
+        .map_err(|e| e.to_string())?;
+    let find = |id: &str| {
+        let uuid = id
+            .parse::<Uuid>()
+            .map_err(|e| format!("Invalid snapshot ID: {e}"))?;
+        snapshots
+            .iter()
+            .find(|s| s.id == uuid)
+            .ok_or_else(|| format!("Snapshot {id} not found"))
+    };
+    let (from, to) = (find(&from_id)?, find(&to_id)?);
+    snapshot_diff::diff_snapshots(from, to, &ProcessRsyncClient::new()).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn explain_command(command: String) -> Result<CommandExplanation, String> {
     let parsed = command_parser::parse_rsync_command(&command)?;
     Ok(command_explainer::explain_command(&parsed))
 }
 
+#[tauri::command]
+pub fn explain_trailing_slash(
+    source: StorageLocation,
+    destination: StorageLocation,
+    mode: BackupMode,
+    auto_slash: bool,
+) -> String {
+    command_explainer::explain_trailing_slash(&source, &destination, &mode, auto_slash)
+}
+
+/// The job form's note on its bandwidth schedule, as of the local time now;
+/// see `command_explainer::explain_bandwidth_schedule`.
+#[tauri::command]
+pub fn explain_bandwidth_schedule(advanced: AdvancedOptions) -> Option<String> {
+    command_explainer::explain_bandwidth_schedule(&advanced, Local::now().time())
+}
+
 #[tauri::command]
 pub fn parse_command_to_job(command: String) -> Result<JobDefinition, String> {
     let parsed = command_parser::parse_rsync_command(&command)?;
@@ -213,16 +598,227 @@ pub fn import_jobs(json: String, state: State<'_, AppState>) -> Result<usize, St
     Ok(count)
 }
 
+/// Preview an import without writing anything; see `export_import::validate_import`.
+#[tauri::command]
+pub fn validate_import(
+    json: String,
+    full_backup: bool,
+    state: State<'_, AppState>,
+) -> Result<ImportPreview, String> {
+    let existing = state.job_service.list_jobs().map_err(|e| e.to_string())?;
+    export_import::validate_import(&json, &existing, full_backup)
+}
+
+#[tauri::command]
+pub fn export_full(state: State<'_, AppState>) -> Result<String, String> {
+    export_import::export_full(&state.job_service, &state.statistics_service)
+}
+
+#[tauri::command]
+pub fn import_full(json: String, state: State<'_, AppState>) -> Result<FullExportData, String> {
+    export_import::import_full(&json, &state.job_service, &state.statistics_service)
+}
+
+#[tauri::command]
+pub fn run_preflight(job_id: String, state: State<'_, AppState>) -> Result<PreflightResult, String> {
+    let uuid = job_id
+        .parse::<Uuid>()
+        .map_err(|e| format!("Invalid job ID: {e}"))?;
+    let job = state.job_service.get_job(&uuid).map_err(|e| e.to_string())?;
+    let link_dest = state
+        .job_service
+        .get_latest_snapshot(&uuid)
+        .map_err(|e| e.to_string())?
+        .map(|snap| snap.snapshot_path);
+
+    let mirror_check = mirror_deletion_check(&state);
+    let fs = RealFileSystem::new();
+    let rsync = ProcessRsyncClient::new();
+    Ok(preflight::run_preflight(
+        &job,
+        link_dest.as_deref(),
+        mirror_check.as_ref(),
+        &fs,
+        &rsync,
+    ))
+}
+
+/// The deletion estimate preflight runs for mirror jobs, if it is enabled.
+fn mirror_deletion_check(state: &State<'_, AppState>) -> Option<MirrorDeletionCheck> {
+    let settings = state.settings_service.get_safe_mirror_settings().ok()?;
+    settings.enabled.then(|| MirrorDeletionCheck {
+        max_delete_percent: settings.max_delete_percent,
+        auto_trailing_slash: state
+            .settings_service
+            .get_auto_trailing_slash()
+            .unwrap_or(true),
+        relative_paths: state.settings_service.get_relative_paths().unwrap_or(false),
+    })
+}
+
+/// Dry-run the job with `--stats` to estimate how much the next run transfers.
+#[tauri::command]
+pub fn estimate_transfer(
+    job_id: String,
+    state: State<'_, AppState>,
+) -> Result<TransferEstimate, String> {
+    let uuid = job_id
+        .parse::<Uuid>()
+        .map_err(|e| format!("Invalid job ID: {e}"))?;
+    let job = state
+        .job_service
+        .get_job(&uuid)
+        .map_err(|e| e.to_string())?;
+    let link_dest = state
+        .job_service
+        .get_latest_snapshot(&uuid)
+        .map_err(|e| e.to_string())?
+        .map(|snap| snap.snapshot_path);
+    let auto_trailing_slash = state
+        .settings_service
+        .get_auto_trailing_slash()
+        .unwrap_or(true);
+    let relative_paths = state.settings_service.get_relative_paths().unwrap_or(false);
+
+    let rsync = ProcessRsyncClient::new();
+    transfer_estimate::estimate_transfer(
+        &job,
+        &rsync,
+        link_dest.as_deref(),
+        auto_trailing_slash,
+        relative_paths,
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Dry-run copying the job's backup (the latest snapshot for snapshot jobs)
+/// back onto its source. Never writes; the returned command is for the user
+/// to run once they have confirmed.
+#[tauri::command]
+pub fn preview_restore(
+    job_id: String,
+    state: State<'_, AppState>,
+) -> Result<RestorePreview, String> {
+    let uuid = job_id
+        .parse::<Uuid>()
+        .map_err(|e| format!("Invalid job ID: {e}"))?;
+    let job = state
+        .job_service
+        .get_job(&uuid)
+        .map_err(|e| e.to_string())?;
+    let snapshot_path = match &job.transfer.backup_mode {
+        BackupMode::Snapshot { .. } => Some(
+            state
+                .job_service
+                .get_latest_snapshot(&uuid)
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| "Job has no snapshot to restore from".to_string())?
+                .snapshot_path,
+        ),
+        _ => None,
+    };
+    let auto_trailing_slash = state
+        .settings_service
+        .get_auto_trailing_slash()
+        .unwrap_or(true);
+
+    let rsync = ProcessRsyncClient::new();
+    restore::preview_restore(&job, &rsync, snapshot_path.as_deref(), auto_trailing_slash)
+        .map_err(|e| e.to_string())
+}
+
+/// Space the job's destination occupies, counting hard-linked files once.
+/// `None` for remote or missing destinations.
+#[tauri::command]
+pub fn destination_usage(
+    job_id: String,
+    state: State<'_, AppState>,
+) -> Result<Option<DestinationUsage>, String> {
+    let uuid = job_id
+        .parse::<Uuid>()
+        .map_err(|e| format!("Invalid job ID: {e}"))?;
+    let job = state
+        .job_service
+        .get_job(&uuid)
+        .map_err(|e| e.to_string())?;
+    destination_usage::destination_usage(&job, &RealFileSystem::new()).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn scan_host_keys(job_id: String, state: State<'_, AppState>) -> Result<Vec<HostKey>, String> {
+    let uuid = job_id
+        .parse::<Uuid>()
+        .map_err(|e| format!("Invalid job ID: {e}"))?;
+    let job = state
+        .job_service
+        .get_job(&uuid)
+        .map_err(|e| e.to_string())?;
+    known_hosts::scan_host_keys(&job, &ProcessSshClient::new()).map_err(|e| e.to_string())
+}
+
+/// Add the host key whose fingerprint the user confirmed to the configured
+/// `known_hosts`, after scanning the host again. Returns false if it was
+/// already there.
+#[tauri::command]
+pub fn trust_host_key(
+    host: String,
+    port: u16,
+    fingerprint: String,
+    state: State<'_, AppState>,
+) -> Result<bool, String> {
+    let key = known_hosts::confirmed_host_key(&host, port, &fingerprint, &ProcessSshClient::new())
+        .map_err(|e| e.to_string())?;
+    let path = get_known_hosts_path(state)?;
+    known_hosts::trust_host_key(std::path::Path::new(&path), &key).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
-pub fn run_preflight(job_id: String, state: State<'_, AppState>) -> Result<PreflightResult, String> {
-    let uuid = job_id
-        .parse::<Uuid>()
-        .map_err(|e| format!("Invalid job ID: {e}"))?;
-    let job = state.job_service.get_job(&uuid).map_err(|e| e.to_string())?;
+pub fn get_known_hosts_path(state: State<'_, AppState>) -> Result<String, String> {
+    let configured = state
+        .settings_service
+        .get_known_hosts_path()
+        .map_err(|e| e.to_string())?;
+    configured
+        .or_else(|| known_hosts::default_known_hosts_path().map(|p| p.display().to_string()))
+        .ok_or_else(|| "No known_hosts file configured and HOME is not set".to_string())
+}
+
+#[tauri::command]
+pub fn set_known_hosts_path(path: String, state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .settings_service
+        .set_known_hosts_path(&path)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn run_preflight_all(state: State<'_, AppState>) -> Result<Vec<PreflightResult>, String> {
+    let jobs: Vec<_> = state
+        .job_service
+        .list_jobs()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|j| j.enabled)
+        .collect();
 
     let fs = RealFileSystem::new();
     let rsync = ProcessRsyncClient::new();
-    Ok(preflight::run_preflight(&job, &fs, &rsync))
+    let link_dest_for = |job: &JobDefinition| {
+        state
+            .job_service
+            .get_latest_snapshot(&job.id)
+            .ok()
+            .flatten()
+            .map(|snap| snap.snapshot_path)
+    };
+    let mirror_check = mirror_deletion_check(&state);
+    Ok(preflight::run_preflight_all(
+        &jobs,
+        link_dest_for,
+        mirror_check.as_ref(),
+        &fs,
+        &rsync,
+    ))
 }
 
 #[tauri::command]
@@ -257,9 +853,14 @@ pub fn export_statistics(state: State<'_, AppState>) -> Result<String, String> {
 
 #[tauri::command]
 pub fn reset_statistics(state: State<'_, AppState>) -> Result<(), String> {
+    state.statistics_service.reset().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn recompute_statistics(state: State<'_, AppState>) -> Result<usize, String> {
     state
         .statistics_service
-        .reset()
+        .recompute_from_invocations(&state.job_service)
         .map_err(|e| e.to_string())
 }
 
@@ -312,6 +913,21 @@ pub fn set_log_directory(path: String, state: State<'_, AppState>) -> Result<(),
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub fn relocate_logs(
+    new_dir: String,
+    state: State<'_, AppState>,
+) -> Result<LogRelocationResult, String> {
+    log_relocation::relocate_logs(
+        &RealFileSystem::new(),
+        &state.job_service,
+        &state.settings_service,
+        state.job_executor.default_log_dir(),
+        &new_dir,
+    )
+    .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn get_retention_settings(state: State<'_, AppState>) -> Result<RetentionSettings, String> {
     state
@@ -349,6 +965,284 @@ pub fn set_auto_trailing_slash(enabled: bool, state: State<'_, AppState>) -> Res
         .map_err(|e| e.to_string())
 }
 
+// --- Relative paths setting ---
+
+#[tauri::command]
+pub fn get_relative_paths(state: State<'_, AppState>) -> Result<bool, String> {
+    state
+        .settings_service
+        .get_relative_paths()
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn set_relative_paths(enabled: bool, state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .settings_service
+        .set_relative_paths(enabled)
+        .map_err(|e| e.to_string())
+}
+
+// --- Test mode ---
+
+#[tauri::command]
+pub fn get_test_mode(state: State<'_, AppState>) -> Result<bool, String> {
+    state
+        .settings_service
+        .get_test_mode()
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn set_test_mode(enabled: bool, state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .settings_service
+        .set_test_mode(enabled)
+        .map_err(|e| e.to_string())
+}
+
+// --- Log file layout ---
+
+#[tauri::command]
+pub fn get_log_file_settings(state: State<'_, AppState>) -> Result<LogFileSettings, String> {
+    state
+        .settings_service
+        .get_log_file_settings()
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn set_log_file_settings(
+    settings: LogFileSettings,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state
+        .settings_service
+        .set_log_file_settings(&settings)
+        .map_err(|e| e.to_string())
+}
+
+// --- Transfer anomaly detection ---
+
+#[tauri::command]
+pub fn get_anomaly_settings(state: State<'_, AppState>) -> Result<AnomalySettings, String> {
+    state
+        .settings_service
+        .get_anomaly_settings()
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn set_anomaly_settings(
+    settings: AnomalySettings,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state
+        .settings_service
+        .set_anomaly_settings(&settings)
+        .map_err(|e| e.to_string())
+}
+
+// --- Safe mirror deletion estimate ---
+
+#[tauri::command]
+pub fn get_safe_mirror_settings(state: State<'_, AppState>) -> Result<SafeMirrorSettings, String> {
+    state
+        .settings_service
+        .get_safe_mirror_settings()
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn set_safe_mirror_settings(
+    settings: SafeMirrorSettings,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state
+        .settings_service
+        .set_safe_mirror_settings(&settings)
+        .map_err(|e| e.to_string())
+}
+
+// --- Itemized change storage ---
+
+#[tauri::command]
+pub fn get_itemize_store_limit(state: State<'_, AppState>) -> Result<usize, String> {
+    state
+        .settings_service
+        .get_itemize_store_limit()
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn set_itemize_store_limit(limit: usize, state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .settings_service
+        .set_itemize_store_limit(limit)
+        .map_err(|e| e.to_string())
+}
+
+// --- Snapshot linking ---
+
+#[tauri::command]
+pub fn get_link_dest_count(state: State<'_, AppState>) -> Result<usize, String> {
+    state
+        .settings_service
+        .get_link_dest_count()
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn set_link_dest_count(count: usize, state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .settings_service
+        .set_link_dest_count(count)
+        .map_err(|e| e.to_string())
+}
+
+/// Directory pruned snapshots are moved to, or empty when they are deleted.
+#[tauri::command]
+pub fn get_snapshot_trash_dir(state: State<'_, AppState>) -> Result<String, String> {
+    state
+        .settings_service
+        .get_snapshot_trash_dir()
+        .map(Option::unwrap_or_default)
+        .map_err(|e| e.to_string())
+}
+
+/// An empty path goes back to deleting pruned snapshots outright.
+#[tauri::command]
+pub fn set_snapshot_trash_dir(path: String, state: State<'_, AppState>) -> Result<(), String> {
+    let path = path.trim();
+    let result = if path.is_empty() {
+        state.settings_service.clear_snapshot_trash_dir()
+    } else {
+        state.settings_service.set_snapshot_trash_dir(path)
+    };
+    result.map_err(|e| e.to_string())
+}
+
+/// Permanently delete the trashed snapshots. Returns how many were removed.
+#[tauri::command]
+pub fn empty_snapshot_trash(state: State<'_, AppState>) -> Result<usize, String> {
+    let Some(trash_dir) = state
+        .settings_service
+        .get_snapshot_trash_dir()
+        .map_err(|e| e.to_string())?
+    else {
+        return Ok(0);
+    };
+    snapshot_trash::empty_trash(&RealFileSystem::new(), std::path::Path::new(&trash_dir))
+        .map_err(|e| e.to_string())
+}
+
+/// Restore what the most recent retention prune removed, if it is still
+/// within the undo window. `None` when there is nothing to undo.
+#[tauri::command]
+pub fn undo_last_prune(state: State<'_, AppState>) -> Result<Option<UndoPruneResult>, String> {
+    prune_recovery::undo_last_prune(
+        &state.job_service,
+        &RealFileSystem::new(),
+        Utc::now(),
+    )
+    .map_err(|e| e.to_string())
+}
+
+// --- Monthly data budget ---
+
+#[tauri::command]
+pub fn get_monthly_data_budget(state: State<'_, AppState>) -> Result<Option<u64>, String> {
+    state
+        .settings_service
+        .get_monthly_data_budget()
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn set_monthly_data_budget(
+    bytes: Option<u64>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state
+        .settings_service
+        .set_monthly_data_budget(bytes)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_remaining_data_budget(state: State<'_, AppState>) -> Result<Option<u64>, String> {
+    DataBudget::new(
+        Arc::clone(&state.job_service),
+        Arc::clone(&state.settings_service),
+    )
+    .remaining_budget()
+    .map_err(|e| e.to_string())
+}
+
+// --- Pre-run free space check ---
+
+#[tauri::command]
+pub fn get_pre_run_space_margin(state: State<'_, AppState>) -> Result<Option<u32>, String> {
+    state
+        .settings_service
+        .get_pre_run_space_margin()
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn set_pre_run_space_margin(
+    percent: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state
+        .settings_service
+        .set_pre_run_space_margin(percent)
+        .map_err(|e| e.to_string())
+}
+
+// --- rsync daemon passwords ---
+
+#[tauri::command]
+pub fn set_daemon_secret(
+    name: String,
+    password: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state
+        .settings_service
+        .set_daemon_secret(&name, &password)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn delete_daemon_secret(name: String, state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .settings_service
+        .delete_daemon_secret(&name)
+        .map_err(|e| e.to_string())
+}
+
+// --- Quiet hours ---
+
+#[tauri::command]
+pub fn get_quiet_hours(state: State<'_, AppState>) -> Result<Option<QuietHours>, String> {
+    state
+        .settings_service
+        .get_quiet_hours()
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn set_quiet_hours(
+    quiet_hours: Option<QuietHours>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state
+        .settings_service
+        .set_quiet_hours(quiet_hours.as_ref())
+        .map_err(|e| e.to_string())
+}
+
 // --- Dry mode settings ---
 
 #[tauri::command]
@@ -387,10 +1281,12 @@ pub fn delete_invocation(
         .get_invocation(&uuid)
         .map_err(|e| e.to_string())?;
 
-    // Delete log file if it exists
+    // Delete log file (and its JSON Lines counterpart) if it exists
     if let Some(ref path) = inv.execution_output.log_file_path {
-        if std::path::Path::new(path).exists() {
-            let _ = std::fs::remove_file(path);
+        for file in [path.clone(), log_writer::structured_log_path(path)] {
+            if std::path::Path::new(&file).exists() {
+                let _ = std::fs::remove_file(&file);
+            }
         }
     }
 
@@ -417,8 +1313,10 @@ pub fn delete_invocations_for_job(
 
     for inv in &invocations {
         if let Some(ref path) = inv.execution_output.log_file_path {
-            if std::path::Path::new(path).exists() {
-                let _ = std::fs::remove_file(path);
+            for file in [path.clone(), log_writer::structured_log_path(path)] {
+                if std::path::Path::new(&file).exists() {
+                    let _ = std::fs::remove_file(&file);
+                }
             }
         }
     }
@@ -440,9 +1338,15 @@ pub fn count_invocations(state: State<'_, AppState>) -> Result<usize, String> {
 
 // --- Log scrubber commands ---
 
+#[tauri::command]
+pub fn validate_scrub_pattern(pattern: String) -> Result<Option<String>, String> {
+    log_scrubber::validate_scrub_pattern(&pattern)
+}
+
 #[tauri::command]
 pub fn scrub_scan_logs(
     pattern: String,
+    regex: bool,
     state: State<'_, AppState>,
 ) -> Result<Vec<ScrubScanResult>, String> {
     let log_dir = state
@@ -451,15 +1355,71 @@ pub fn scrub_scan_logs(
         .map(|opt| opt.unwrap_or_else(|| state.job_executor.default_log_dir().to_string()))
         .map_err(|e| e.to_string())?;
 
-    log_scrubber::scrub_scan(&log_dir, &pattern).map_err(|e| e.to_string())
+    log_scrubber::scrub_scan(&log_dir, &pattern, regex).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 pub fn scrub_apply_logs(
     pattern: String,
+    regex: bool,
     file_paths: Vec<String>,
 ) -> Result<Vec<ScrubApplyResult>, String> {
-    log_scrubber::scrub_apply(&pattern, &file_paths).map_err(|e| e.to_string())
+    log_scrubber::scrub_apply(&pattern, regex, &file_paths).map_err(|e| e.to_string())
+}
+
+// --- Orphaned logs ---
+
+/// The configured log directory, or the app default.
+fn current_log_dir(state: &State<'_, AppState>) -> Result<String, String> {
+    state
+        .settings_service
+        .get_log_directory()
+        .map(|opt| opt.unwrap_or_else(|| state.job_executor.default_log_dir().to_string()))
+        .map_err(|e| e.to_string())
+}
+
+/// Log files named after invocations that no longer have a record.
+#[tauri::command]
+pub fn find_orphaned_logs(state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    let log_dir = current_log_dir(&state)?;
+    orphaned_logs::find_orphaned_logs(
+        &RealFileSystem::new(),
+        std::path::Path::new(&log_dir),
+        &state.job_service,
+    )
+    .map(|paths| paths.iter().map(|p| p.display().to_string()).collect())
+    .map_err(|e| e.to_string())
+}
+
+/// Delete orphaned log files, returning the paths that were removed.
+#[tauri::command]
+pub fn clean_orphaned_logs(state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    let log_dir = current_log_dir(&state)?;
+    orphaned_logs::clean_orphaned_logs(
+        &RealFileSystem::new(),
+        std::path::Path::new(&log_dir),
+        &state.job_service,
+    )
+    .map(|paths| paths.iter().map(|p| p.display().to_string()).collect())
+    .map_err(|e| e.to_string())
+}
+
+// --- Pattern tester ---
+
+#[tauri::command]
+pub fn test_patterns(
+    source_dir: String,
+    include_patterns: Vec<String>,
+    exclude_patterns: Vec<String>,
+) -> Result<Vec<PatternTestEntry>, String> {
+    let fs = RealFileSystem::new();
+    pattern_tester::test_pattern_entries(
+        &fs,
+        std::path::Path::new(&source_dir),
+        &include_patterns,
+        &exclude_patterns,
+    )
+    .map_err(|e| e.to_string())
 }
 
 // --- NAS auto-detect setting ---
@@ -539,6 +1499,23 @@ pub fn detect_filesystem_type(path: String) -> Option<String> {
     fs.filesystem_type(std::path::Path::new(&path))
 }
 
+// --- Display formatting ---
+
+#[tauri::command]
+pub fn format_bytes(bytes: u64) -> String {
+    rsync_core::format::format_bytes(bytes)
+}
+
+#[tauri::command]
+pub fn format_rate(bytes_per_sec: f64) -> String {
+    rsync_core::format::format_rate(bytes_per_sec)
+}
+
+#[tauri::command]
+pub fn format_duration(secs: f64) -> String {
+    rsync_core::format::format_duration(secs)
+}
+
 // --- Log file commands ---
 
 #[tauri::command]
@@ -549,6 +1526,39 @@ pub fn read_log_file(path: String) -> Result<String, String> {
     std::fs::read_to_string(&path).map_err(|e| format!("Failed to read log file: {e}"))
 }
 
+#[tauri::command]
+pub fn export_invocation_report(
+    invocation_id: String,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let uuid = invocation_id
+        .parse::<Uuid>()
+        .map_err(|e| format!("Invalid invocation ID: {e}"))?;
+    invocation_report::invocation_report(&state.job_service, &uuid, &RealFileSystem::new())
+        .map_err(|e| e.to_string())
+}
+
+/// Write run history to `path` as newline-delimited JSON; see
+/// `history_export::export_history_ndjson`. Returns how many runs were written.
+#[tauri::command]
+pub fn export_history_ndjson(
+    path: String,
+    since: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<usize, String> {
+    let since = since
+        .map(|s| {
+            DateTime::parse_from_rfc3339(&s)
+                .map(|t| t.with_timezone(&Utc))
+                .map_err(|e| format!("Invalid since timestamp: {e}"))
+        })
+        .transpose()?;
+    let file = std::fs::File::create(&path).map_err(|e| format!("Failed to create {path}: {e}"))?;
+    let mut out = std::io::BufWriter::new(file);
+    history_export::export_history_ndjson(&state.job_service, since, &mut out)
+        .map_err(|e| e.to_string())
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct LogFileLine {
     pub text: String,
@@ -1,11 +1,14 @@
 use serde::Serialize;
-use tauri::Emitter;
+use tauri::{Emitter, Manager};
 use uuid::Uuid;
 
 use rsync_core::models::itemize::ItemizedChange;
 use rsync_core::models::progress::{JobStatusEvent, LogLine, ProgressUpdate};
 use rsync_core::services::execution_handler::ExecutionEventHandler;
 
+use crate::state::AppState;
+use crate::TRAY_ID;
+
 #[derive(Debug, Clone, Serialize)]
 struct ItemizedChangePayload {
     invocation_id: Uuid,
@@ -21,6 +24,27 @@ impl TauriEventHandler {
     pub fn new(app_handle: tauri::AppHandle) -> Self {
         Self { app_handle }
     }
+
+    /// Show overall progress of running jobs in the tray icon's tooltip.
+    fn refresh_tray_tooltip(&self) {
+        let Some(tray) = self.app_handle.tray_by_id(TRAY_ID) else {
+            return;
+        };
+        let progress = self
+            .app_handle
+            .state::<AppState>()
+            .job_executor
+            .aggregate_progress();
+        let tooltip = match progress.active_jobs {
+            0 => "Rsync Studio".to_string(),
+            1 => format!("Rsync Studio — 1 job running, {:.0}%", progress.percentage),
+            n => format!(
+                "Rsync Studio — {} jobs running, {:.0}%",
+                n, progress.percentage
+            ),
+        };
+        let _ = tray.set_tooltip(Some(tooltip));
+    }
 }
 
 impl ExecutionEventHandler for TauriEventHandler {
@@ -28,12 +52,18 @@ impl ExecutionEventHandler for TauriEventHandler {
         let _ = self.app_handle.emit("job-log", log_line);
     }
 
+    fn on_log_lines(&self, log_lines: Vec<LogLine>) {
+        let _ = self.app_handle.emit("job-log-batch", log_lines);
+    }
+
     fn on_progress(&self, progress: &ProgressUpdate) {
         let _ = self.app_handle.emit("job-progress", progress);
+        self.refresh_tray_tooltip();
     }
 
     fn on_status_change(&self, status: JobStatusEvent) {
         let _ = self.app_handle.emit("job-status", status);
+        self.refresh_tray_tooltip();
     }
 
     fn on_itemized_change(&self, invocation_id: Uuid, change: &ItemizedChange) {
@@ -5,17 +5,25 @@ use tauri::tray::TrayIconBuilder;
 use tauri::{Emitter, Manager, WindowEvent};
 
 use rsync_core::database::sqlite::Database;
+use rsync_core::repository::sqlite::audit::SqliteAuditLogRepository;
+use rsync_core::repository::sqlite::backup_set::SqliteBackupSetRepository;
 use rsync_core::repository::sqlite::invocation::SqliteInvocationRepository;
 use rsync_core::repository::sqlite::job::SqliteJobRepository;
 use rsync_core::repository::sqlite::settings::SqliteSettingsRepository;
 use rsync_core::repository::sqlite::snapshot::SqliteSnapshotRepository;
 use rsync_core::repository::sqlite::statistics::SqliteStatisticsRepository;
+use rsync_core::services::audit::AuditLog;
+use rsync_core::services::backup_set_service::BackupSetService;
+use rsync_core::services::data_budget::DataBudget;
+use rsync_core::services::source_change::SourceUnchanged;
 use rsync_core::services::job_executor::JobExecutor;
 use rsync_core::services::job_service::JobService;
 use rsync_core::services::retention_runner;
 use rsync_core::services::running_jobs::RunningJobs;
 use rsync_core::models::schedule::SchedulerConfig;
 use rsync_core::services::scheduler_backend::{InProcessScheduler, SchedulerBackend};
+use rsync_core::services::startup_runner;
+use rsync_core::services::scheduler_events::SchedulerEventLog;
 use rsync_core::services::settings_service::SettingsService;
 use rsync_core::services::statistics_service::StatisticsService;
 
@@ -113,27 +121,48 @@ pub fn run() {
             let invocations = Arc::new(SqliteInvocationRepository::new(conn.clone()));
             let snapshots = Arc::new(SqliteSnapshotRepository::new(conn.clone()));
             let statistics_repo = Arc::new(SqliteStatisticsRepository::new(conn.clone()));
+            let backup_set_repo = Arc::new(SqliteBackupSetRepository::new(conn.clone()));
+            let audit_repo = Arc::new(SqliteAuditLogRepository::new(conn.clone()));
             let settings_repo = Arc::new(SqliteSettingsRepository::new(conn));
 
-            let job_service = Arc::new(JobService::new(jobs, invocations, snapshots));
-            let statistics_service = Arc::new(StatisticsService::new(statistics_repo));
+            let audit_log = Arc::new(AuditLog::new(audit_repo).with_source("gui"));
+            let job_service = Arc::new(
+                JobService::new(jobs, invocations, snapshots)
+                    .with_audit_log(Arc::clone(&audit_log)),
+            );
+            let statistics_service = Arc::new(
+                StatisticsService::new(statistics_repo).with_audit_log(Arc::clone(&audit_log)),
+            );
             let settings_service = Arc::new(SettingsService::new(settings_repo));
+            let backup_set_service = Arc::new(BackupSetService::new(backup_set_repo));
             let running_jobs = Arc::new(RunningJobs::new());
 
-            let job_executor = Arc::new(JobExecutor::new(
-                Arc::clone(&job_service),
-                Arc::clone(&statistics_service),
-                Arc::clone(&settings_service),
-                Arc::clone(&running_jobs),
-                default_log_dir,
-            ));
+            let job_executor = Arc::new(
+                JobExecutor::new(
+                    Arc::clone(&job_service),
+                    Arc::clone(&statistics_service),
+                    Arc::clone(&settings_service),
+                    Arc::clone(&running_jobs),
+                    default_log_dir,
+                )
+                .with_run_condition(Arc::new(DataBudget::new(
+                    Arc::clone(&job_service),
+                    Arc::clone(&settings_service),
+                )))
+                .with_run_condition(Arc::new(SourceUnchanged::new(Arc::clone(&job_service)))),
+            );
+
+            let scheduler_events = Arc::new(SchedulerEventLog::default());
 
             app.manage(AppState {
                 _database: database,
                 job_service: Arc::clone(&job_service),
                 statistics_service: Arc::clone(&statistics_service),
                 settings_service: Arc::clone(&settings_service),
+                backup_set_service,
                 job_executor: Arc::clone(&job_executor),
+                scheduler_events: Arc::clone(&scheduler_events),
+                audit_log,
             });
 
             // --- Run history retention on startup ---
@@ -169,6 +198,14 @@ pub fn run() {
                     let _ = scheduler_app_handle2.emit("job-scheduled", &job_id.to_string());
                 });
 
+            // Jobs flagged to run on startup start in the background, not blocking setup
+            startup_runner::spawn_startup_runs(
+                Arc::clone(&job_executor),
+                Arc::clone(&job_service),
+                Arc::clone(&handler_factory),
+                Some(Arc::clone(&on_job_scheduled)),
+            );
+
             let in_process_scheduler = InProcessScheduler::new(
                 SchedulerConfig::default(),
                 Arc::clone(&job_executor),
@@ -176,7 +213,8 @@ pub fn run() {
                 Arc::clone(&settings_service),
                 handler_factory,
             )
-            .with_on_job_scheduled(on_job_scheduled);
+            .with_on_job_scheduled(on_job_scheduled)
+            .with_event_log(scheduler_events);
 
             // Start the scheduler — handle is intentionally leaked to keep the thread alive
             let _scheduler_handle = in_process_scheduler.start();
@@ -189,32 +227,97 @@ pub fn run() {
             commands::get_job,
             commands::create_job,
             commands::update_job,
+            commands::retarget_job,
             commands::delete_job,
+            commands::find_duplicate_jobs,
+            commands::jobs_referencing_path,
+            commands::merge_jobs,
             commands::get_job_history,
+            commands::get_invocation,
+            commands::get_scheduler_events,
+            commands::preview_schedule,
+            commands::check_schedule,
+            commands::list_audit_entries,
+            commands::get_itemized_changes,
             commands::execute_job,
             commands::execute_job_dry_run,
+            commands::list_backup_sets,
+            commands::create_backup_set,
+            commands::update_backup_set,
+            commands::delete_backup_set,
+            commands::run_backup_set,
+            commands::rerun_invocation,
+            commands::retry_invocation,
             commands::cancel_job,
+            commands::cancel_all_jobs,
             commands::get_running_jobs,
+            commands::dashboard_summary,
+            commands::get_aggregate_progress,
             commands::list_snapshots,
             commands::delete_snapshot,
+            commands::set_snapshot_pinned,
+            commands::get_snapshot_verification,
+            commands::diff_snapshots,
             commands::explain_command,
+            commands::explain_trailing_slash,
+            commands::explain_bandwidth_schedule,
             commands::parse_command_to_job,
             commands::export_jobs,
             commands::import_jobs,
+            commands::validate_import,
+            commands::export_full,
+            commands::import_full,
             commands::run_preflight,
+            commands::run_preflight_all,
+            commands::estimate_transfer,
+            commands::preview_restore,
+            commands::destination_usage,
+            commands::scan_host_keys,
+            commands::trust_host_key,
+            commands::get_known_hosts_path,
+            commands::set_known_hosts_path,
             commands::get_statistics,
             commands::get_statistics_for_job,
             commands::export_statistics,
             commands::reset_statistics,
             commands::reset_statistics_for_job,
+            commands::recompute_statistics,
             commands::get_setting,
             commands::set_setting,
             commands::get_log_directory,
             commands::set_log_directory,
+            commands::relocate_logs,
+            commands::get_log_file_settings,
+            commands::set_log_file_settings,
+            commands::get_anomaly_settings,
+            commands::set_anomaly_settings,
+            commands::get_safe_mirror_settings,
+            commands::set_safe_mirror_settings,
             commands::get_retention_settings,
             commands::set_retention_settings,
             commands::get_auto_trailing_slash,
             commands::set_auto_trailing_slash,
+            commands::get_relative_paths,
+            commands::set_relative_paths,
+            commands::get_test_mode,
+            commands::set_test_mode,
+            commands::get_itemize_store_limit,
+            commands::set_itemize_store_limit,
+            commands::get_link_dest_count,
+            commands::set_link_dest_count,
+            commands::get_snapshot_trash_dir,
+            commands::set_snapshot_trash_dir,
+            commands::empty_snapshot_trash,
+            commands::undo_last_prune,
+            commands::get_monthly_data_budget,
+            commands::set_monthly_data_budget,
+            commands::get_remaining_data_budget,
+            commands::get_pre_run_space_margin,
+            commands::set_pre_run_space_margin,
+            commands::set_daemon_secret,
+            commands::delete_daemon_secret,
+            commands::get_quiet_hours,
+            commands::set_quiet_hours,
             commands::get_dry_mode_settings,
             commands::set_dry_mode_settings,
             commands::delete_invocation,
@@ -222,9 +325,18 @@ pub fn run() {
             commands::count_invocations,
             commands::read_log_file,
             commands::read_log_file_lines,
+            commands::export_invocation_report,
+            commands::export_history_ndjson,
+            commands::validate_scrub_pattern,
             commands::scrub_scan_logs,
             commands::scrub_apply_logs,
+            commands::find_orphaned_logs,
+            commands::clean_orphaned_logs,
+            commands::test_patterns,
             commands::detect_filesystem_type,
+            commands::format_bytes,
+            commands::format_rate,
+            commands::format_duration,
             commands::get_nas_auto_detect,
             commands::set_nas_auto_detect,
             commands::get_show_file_handling_options,
@@ -238,13 +350,15 @@ pub fn run() {
         .expect("error while running tauri application");
 }
 
+pub(crate) const TRAY_ID: &str = "main";
+
 fn setup_tray(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
     let show = MenuItem::with_id(app, "show", "Show Window", true, None::<&str>)?;
     let hide = MenuItem::with_id(app, "hide", "Hide Window", true, None::<&str>)?;
     let quit = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
     let menu = Menu::with_items(app, &[&show, &hide, &quit])?;
 
-    let _tray = TrayIconBuilder::new()
+    let _tray = TrayIconBuilder::with_id(TRAY_ID)
         .icon(app.default_window_icon().cloned().expect("no app icon"))
         .icon_as_template(false) // full-color icon in menu bar
         .tooltip("Rsync Studio")
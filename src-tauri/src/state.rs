@@ -1,8 +1,11 @@
 use std::sync::Arc;
 
 use rsync_core::database::sqlite::Database;
+use rsync_core::services::audit::AuditLog;
+use rsync_core::services::backup_set_service::BackupSetService;
 use rsync_core::services::job_executor::JobExecutor;
 use rsync_core::services::job_service::JobService;
+use rsync_core::services::scheduler_events::SchedulerEventLog;
 use rsync_core::services::settings_service::SettingsService;
 use rsync_core::services::statistics_service::StatisticsService;
 
@@ -11,5 +14,8 @@ pub struct AppState {
     pub job_service: Arc<JobService>,
     pub statistics_service: Arc<StatisticsService>,
     pub settings_service: Arc<SettingsService>,
+    pub backup_set_service: Arc<BackupSetService>,
     pub job_executor: Arc<JobExecutor>,
+    pub scheduler_events: Arc<SchedulerEventLog>,
+    pub audit_log: Arc<AuditLog>,
 }
@@ -1,6 +1,7 @@
 import { useState } from "react";
 import type { JobDefinition } from "@/types/job";
 import { buildCommandString } from "@/lib/command-preview";
+import { useRelativePaths } from "@/hooks/use-relative-paths";
 import { Card, CardContent, CardHeader, CardTitle } from "@/components/ui/card";
 import { Button } from "@/components/ui/button";
 import { Copy, Check } from "lucide-react";
@@ -12,7 +13,8 @@ interface CommandPreviewProps {
 
 export function CommandPreview({ job, autoTrailingSlash = false }: CommandPreviewProps) {
   const [copied, setCopied] = useState(false);
-  const command = buildCommandString(job, autoTrailingSlash);
+  const relativePaths = useRelativePaths();
+  const command = buildCommandString(job, autoTrailingSlash, relativePaths);
 
   async function handleCopy() {
     await navigator.clipboard.writeText(command);
@@ -8,7 +8,9 @@ import { ProgressDisplay } from "./progress-display";
 import { ItemizedChangesTable } from "./itemized-changes-table";
 import { VirtualLogViewer } from "@/components/logs/virtual-log-viewer";
 import { buildCommandString } from "@/lib/command-preview";
+import { statusBadgeVariant, statusLabel } from "@/lib/status";
 import { useTrailingSlash } from "@/hooks/use-trailing-slash";
+import { useRelativePaths } from "@/hooks/use-relative-paths";
 
 interface ExecutionViewProps {
   job: JobDefinition;
@@ -24,21 +26,6 @@ interface ExecutionViewProps {
   onBack: () => void;
 }
 
-function statusBadgeVariant(status: JobStatus): "default" | "secondary" | "destructive" | "outline" {
-  switch (status) {
-    case "Running":
-      return "default";
-    case "Completed":
-      return "secondary";
-    case "Failed":
-      return "destructive";
-    case "Cancelled":
-      return "outline";
-    default:
-      return "secondary";
-  }
-}
-
 export function ExecutionView({
   job,
   status,
@@ -53,6 +40,7 @@ export function ExecutionView({
   onBack,
 }: ExecutionViewProps) {
   const autoTrailingSlash = useTrailingSlash();
+  const relativePaths = useRelativePaths();
 
   return (
     <div className="space-y-6">
@@ -69,7 +57,7 @@ export function ExecutionView({
           </div>
         </div>
         <div className="flex items-center gap-2">
-          <Badge variant={statusBadgeVariant(status)}>{status}</Badge>
+          <Badge variant={statusBadgeVariant(status)}>{statusLabel(status)}</Badge>
           {status === "Running" && (
             <Button variant="destructive" size="sm" onClick={onCancel}>
               <Square className="h-3 w-3 mr-1" />
@@ -82,7 +70,7 @@ export function ExecutionView({
       <div className="space-y-2">
         <h3 className="text-sm font-medium">Command</h3>
         <pre className="text-xs font-mono whitespace-pre-wrap bg-muted/50 rounded-md border p-3">
-          {buildCommandString(job, autoTrailingSlash)}
+          {buildCommandString(job, autoTrailingSlash, relativePaths)}
         </pre>
       </div>
 
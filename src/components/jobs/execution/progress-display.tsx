@@ -2,6 +2,13 @@ import type { ProgressUpdate } from "@/types/execution/progress";
 import type { JobStatus } from "@/types/job";
 import { Progress } from "@/components/ui/progress";
 
+function formatEta(secs: number): string {
+  const h = Math.floor(secs / 3600);
+  const m = Math.floor((secs % 3600) / 60);
+  const s = secs % 60;
+  return `${h}:${String(m).padStart(2, "0")}:${String(s).padStart(2, "0")}`;
+}
+
 interface ProgressDisplayProps {
   progress: ProgressUpdate | null;
   status?: JobStatus;
@@ -44,7 +51,13 @@ export function ProgressDisplay({ progress, status }: ProgressDisplayProps) {
         <span>{Math.round(overallPercent)}%</span>
         <span>{progress.transfer_rate}</span>
         <span>{progress.elapsed}</span>
+        {progress.eta_secs !== null && <span>ETA {formatEta(progress.eta_secs)}</span>}
       </div>
+      {progress.current_file && (
+        <p className="truncate text-xs text-muted-foreground" title={progress.current_file}>
+          Copying {progress.current_file}
+        </p>
+      )}
       {progress.files_total > 0 && (
         <p className="text-xs text-muted-foreground">
           {progress.files_transferred} of {progress.files_total} files transferred
@@ -0,0 +1,76 @@
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
+import { JOB_ICONS, JobIcon } from "../job-icon";
+
+interface AppearanceFieldProps {
+  color: string | null;
+  icon: string | null;
+  onColorChange: (color: string | null) => void;
+  onIconChange: (icon: string | null) => void;
+  error?: string;
+}
+
+export function AppearanceField({
+  color,
+  icon,
+  onColorChange,
+  onIconChange,
+  error,
+}: AppearanceFieldProps) {
+  return (
+    <div className="space-y-2">
+      <Label>Appearance</Label>
+      <p className="text-xs text-muted-foreground">
+        Color and icon used for this job in the job list and the terminal UI.
+      </p>
+      <div className="flex items-center gap-2">
+        <input
+          type="color"
+          aria-label="Job color"
+          value={color ?? "#000000"}
+          onChange={(e) => onColorChange(e.target.value)}
+          className="h-9 w-12 cursor-pointer rounded border bg-transparent"
+        />
+        <Input
+          value={color ?? ""}
+          onChange={(e) => onColorChange(e.target.value.trim() || null)}
+          placeholder="No color"
+          className="w-32"
+        />
+        {color && (
+          <Button variant="ghost" size="sm" onClick={() => onColorChange(null)}>
+            Clear
+          </Button>
+        )}
+        <Select
+          value={icon ?? "none"}
+          onValueChange={(value) => onIconChange(value === "none" ? null : value)}
+        >
+          <SelectTrigger className="w-40">
+            <SelectValue />
+          </SelectTrigger>
+          <SelectContent>
+            <SelectItem value="none">No icon</SelectItem>
+            {Object.keys(JOB_ICONS).map((name) => (
+              <SelectItem key={name} value={name}>
+                <span className="flex items-center gap-2">
+                  <JobIcon name={name} color={null} className="h-4 w-4" />
+                  {name}
+                </span>
+              </SelectItem>
+            ))}
+          </SelectContent>
+        </Select>
+      </div>
+      {error && <p className="text-sm text-destructive">{error}</p>}
+    </div>
+  );
+}
@@ -0,0 +1,78 @@
+import type { ChangeAlarm } from "@/types/job";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import { Switch } from "@/components/ui/switch";
+
+interface ChangeAlarmFieldProps {
+  value: ChangeAlarm | null;
+  onChange: (value: ChangeAlarm | null) => void;
+}
+
+function parsePercent(input: string): number | null {
+  const percent = parseFloat(input);
+  return isNaN(percent) || percent < 0 || percent > 100 ? null : percent;
+}
+
+export function ChangeAlarmField({ value, onChange }: ChangeAlarmFieldProps) {
+  const alarm: ChangeAlarm = value ?? {
+    max_changed_percent: null,
+    max_deleted_percent: null,
+    notify: false,
+  };
+
+  function update(next: ChangeAlarm) {
+    const unset =
+      next.max_changed_percent === null && next.max_deleted_percent === null && !next.notify;
+    onChange(unset ? null : next);
+  }
+
+  return (
+    <div className="space-y-2">
+      <Label>Change Alarm</Label>
+      <p className="text-xs text-muted-foreground">
+        Flag a successful run that changed or deleted more than this share of files, e.g. after
+        ransomware or a wrong mount. Leave empty to skip a check.
+      </p>
+      <div className="flex items-center gap-4">
+        <div className="space-y-1">
+          <Label htmlFor="alarm-changed" className="text-xs">Changed above (%)</Label>
+          <Input
+            id="alarm-changed"
+            type="number"
+            min={0}
+            max={100}
+            value={alarm.max_changed_percent ?? ""}
+            onChange={(e) =>
+              update({ ...alarm, max_changed_percent: parsePercent(e.target.value) })
+            }
+            placeholder="Off"
+            className="w-32"
+          />
+        </div>
+        <div className="space-y-1">
+          <Label htmlFor="alarm-deleted" className="text-xs">Deleted above (%)</Label>
+          <Input
+            id="alarm-deleted"
+            type="number"
+            min={0}
+            max={100}
+            value={alarm.max_deleted_percent ?? ""}
+            onChange={(e) =>
+              update({ ...alarm, max_deleted_percent: parsePercent(e.target.value) })
+            }
+            placeholder="Off"
+            className="w-32"
+          />
+        </div>
+      </div>
+      <div className="flex items-center gap-2">
+        <Switch
+          id="alarm-notify"
+          checked={alarm.notify}
+          onCheckedChange={(notify) => update({ ...alarm, notify })}
+        />
+        <Label htmlFor="alarm-notify">Notify when the alarm trips</Label>
+      </div>
+    </div>
+  );
+}
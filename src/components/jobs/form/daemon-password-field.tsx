@@ -0,0 +1,84 @@
+import { useState } from "react";
+import { setDaemonSecret } from "@/lib/tauri";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+
+interface DaemonPasswordFieldProps {
+  secretName: string | null;
+  onSecretNameChange: (name: string | null) => void;
+}
+
+/**
+ * Names the stored rsync daemon password a location uses and lets the user
+ * store (or replace) it. The password itself is never read back.
+ */
+export function DaemonPasswordField({
+  secretName,
+  onSecretNameChange,
+}: DaemonPasswordFieldProps) {
+  const [password, setPassword] = useState("");
+  const [status, setStatus] = useState<{
+    type: "success" | "error";
+    message: string;
+  } | null>(null);
+
+  async function handleSave() {
+    if (!secretName) return;
+    setStatus(null);
+    try {
+      await setDaemonSecret(secretName, password);
+      setPassword("");
+      setStatus({ type: "success", message: "Password stored." });
+    } catch (err) {
+      setStatus({
+        type: "error",
+        message: err instanceof Error ? err.message : String(err),
+      });
+    }
+  }
+
+  return (
+    <div className="col-span-2 grid grid-cols-2 gap-2">
+      <div className="space-y-1">
+        <Label className="text-xs text-muted-foreground">Password name</Label>
+        <Input
+          value={secretName ?? ""}
+          onChange={(e) => onSecretNameChange(e.target.value || null)}
+          placeholder="No password"
+        />
+      </div>
+      <div className="space-y-1">
+        <Label className="text-xs text-muted-foreground">Password</Label>
+        <div className="flex gap-2">
+          <Input
+            type="password"
+            value={password}
+            onChange={(e) => setPassword(e.target.value)}
+            disabled={!secretName}
+            placeholder="Unchanged"
+          />
+          <Button
+            type="button"
+            variant="outline"
+            onClick={handleSave}
+            disabled={!secretName || !password}
+          >
+            Store
+          </Button>
+        </div>
+      </div>
+      {status && (
+        <p
+          className={`col-span-2 text-xs ${
+            status.type === "success"
+              ? "text-green-600 dark:text-green-400"
+              : "text-destructive"
+          }`}
+        >
+          {status.message}
+        </p>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,88 @@
+import { useState } from "react";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import { X, Plus } from "lucide-react";
+
+interface EnvVarsFieldProps {
+  value: Record<string, string>;
+  onChange: (env: Record<string, string>) => void;
+}
+
+export function EnvVarsField({ value, onChange }: EnvVarsFieldProps) {
+  const [name, setName] = useState("");
+  const [val, setVal] = useState("");
+
+  function handleAdd() {
+    const key = name.trim();
+    if (key && !/[=\s]/.test(key)) {
+      onChange({ ...value, [key]: val });
+      setName("");
+      setVal("");
+    }
+  }
+
+  function handleRemove(key: string) {
+    const next = { ...value };
+    delete next[key];
+    onChange(next);
+  }
+
+  function handleKeyDown(e: React.KeyboardEvent) {
+    if (e.key === "Enter") {
+      e.preventDefault();
+      handleAdd();
+    }
+  }
+
+  const entries = Object.entries(value).sort(([a], [b]) => a.localeCompare(b));
+
+  return (
+    <div className="space-y-2">
+      <Label>Environment Variables</Label>
+      <p className="text-xs text-muted-foreground">
+        Passed to the rsync process, e.g. SSH_AUTH_SOCK or RSYNC_PASSWORD.
+      </p>
+      <div className="flex gap-2">
+        <Input
+          value={name}
+          onChange={(e) => setName(e.target.value)}
+          onKeyDown={handleKeyDown}
+          placeholder="NAME"
+          className="w-1/3 font-mono"
+        />
+        <Input
+          value={val}
+          onChange={(e) => setVal(e.target.value)}
+          onKeyDown={handleKeyDown}
+          placeholder="value"
+          className="flex-1 font-mono"
+        />
+        <Button type="button" variant="outline" size="icon" onClick={handleAdd}>
+          <Plus className="h-4 w-4" />
+        </Button>
+      </div>
+      {entries.length > 0 && (
+        <div className="flex flex-wrap gap-1">
+          {entries.map(([key, v]) => (
+            <span
+              key={key}
+              className="inline-flex items-center gap-1 rounded-md bg-secondary px-2 py-1 text-sm"
+            >
+              <code>
+                {key}={v}
+              </code>
+              <button
+                type="button"
+                onClick={() => handleRemove(key)}
+                className="text-muted-foreground hover:text-foreground"
+              >
+                <X className="h-3 w-3" />
+              </button>
+            </span>
+          ))}
+        </div>
+      )}
+    </div>
+  );
+}
@@ -1,4 +1,6 @@
+import { useEffect, useState } from "react";
 import type { JobDefinition } from "@/types/job";
+import { explainTrailingSlash } from "@/lib/tauri";
 import { Input } from "@/components/ui/input";
 import { Label } from "@/components/ui/label";
 import { Switch } from "@/components/ui/switch";
@@ -12,6 +14,10 @@ interface JobFormGeneralProps {
   onEnabledChange: (enabled: boolean) => void;
   onSourceChange: (source: JobDefinition["transfer"]["source"]) => void;
   onDestinationChange: (destination: JobDefinition["transfer"]["destination"]) => void;
+  onCreateDestinationChange: (createDestination: boolean) => void;
+  onRunOnStartupChange: (runOnStartup: boolean) => void;
+  onSkipIfUnchangedChange: (skipIfUnchanged: boolean) => void;
+  onExpectNonemptyDestinationChange: (expectNonemptyDestination: boolean) => void;
   errors: Record<string, string>;
   autoTrailingSlash?: boolean;
 }
@@ -23,9 +29,30 @@ export function JobFormGeneral({
   onEnabledChange,
   onSourceChange,
   onDestinationChange,
+  onCreateDestinationChange,
+  onRunOnStartupChange,
+  onSkipIfUnchangedChange,
+  onExpectNonemptyDestinationChange,
   errors,
   autoTrailingSlash,
 }: JobFormGeneralProps) {
+  const destinationType = job.transfer.destination.type;
+  const isRemote = destinationType === "RemoteSsh" || destinationType === "RemoteRsync";
+  const [slashEffect, setSlashEffect] = useState<string | null>(null);
+
+  useEffect(() => {
+    const { source, destination, backup_mode } = job.transfer;
+    if (!source.path || !destination.path) {
+      setSlashEffect(null);
+      return;
+    }
+    let cancelled = false;
+    explainTrailingSlash(source, destination, backup_mode, autoTrailingSlash ?? false)
+      .then((text) => { if (!cancelled) setSlashEffect(text); })
+      .catch(() => { if (!cancelled) setSlashEffect(null); });
+    return () => { cancelled = true; };
+  }, [job.transfer, autoTrailingSlash]);
+
   return (
     <div className="space-y-6">
       <div className="flex items-center justify-between">
@@ -81,6 +108,49 @@ export function JobFormGeneral({
       {errors.destination && (
         <p className="text-sm text-destructive">{errors.destination}</p>
       )}
+      {slashEffect && (
+        <p className="text-xs text-muted-foreground">{slashEffect}</p>
+      )}
+      {!isRemote && (
+        <div className="flex items-center gap-2">
+          <Switch
+            id="job-expect-nonempty-destination"
+            checked={job.safety.expect_nonempty_destination}
+            onCheckedChange={onExpectNonemptyDestinationChange}
+          />
+          <Label htmlFor="job-expect-nonempty-destination">
+            Abort if the destination is unexpectedly empty
+          </Label>
+        </div>
+      )}
+      {isRemote && (
+        <div className="flex items-center gap-2">
+          <Switch
+            id="job-create-destination"
+            checked={job.run_policy.create_destination}
+            onCheckedChange={onCreateDestinationChange}
+          />
+          <Label htmlFor="job-create-destination">Create destination if missing</Label>
+        </div>
+      )}
+      <div className="flex items-center gap-2">
+        <Switch
+          id="job-run-on-startup"
+          checked={job.run_policy.run_on_startup}
+          onCheckedChange={onRunOnStartupChange}
+        />
+        <Label htmlFor="job-run-on-startup">Run on app startup</Label>
+      </div>
+      <div className="flex items-center gap-2">
+        <Switch
+          id="job-skip-if-unchanged"
+          checked={job.safety.skip_if_unchanged}
+          onCheckedChange={onSkipIfUnchangedChange}
+        />
+        <Label htmlFor="job-skip-if-unchanged">
+          Skip scheduled runs when the source hasn't changed
+        </Label>
+      </div>
     </div>
   );
 }
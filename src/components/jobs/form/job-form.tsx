@@ -4,16 +4,33 @@ import { useNasAutoDetect } from "@/hooks/use-nas-auto-detect";
 import { useShowFileHandlingOptions } from "@/hooks/use-show-file-handling-options";
 import { useShowMetadataOptions } from "@/hooks/use-show-metadata-options";
 import { useShowOutputOptions } from "@/hooks/use-show-output-options";
-import { detectFilesystemType } from "@/lib/tauri";
-import type { JobDefinition, StorageLocation, SshConfig } from "@/types/job";
+import { checkSchedule, detectFilesystemType } from "@/lib/tauri";
+import type {
+  ChangeAlarm,
+  JobDefinition,
+  NotifyOn,
+  ProcessPriority,
+  StorageLocation,
+  SshConfig,
+} from "@/types/job";
 import type { ScheduleConfig } from "@/types/schedule";
 import { Button } from "@/components/ui/button";
 import { Tabs, TabsContent, TabsList, TabsTrigger } from "@/components/ui/tabs";
 import { ScrollArea } from "@/components/ui/scroll-area";
+import { Switch } from "@/components/ui/switch";
+import { Label } from "@/components/ui/label";
 import { JobFormGeneral } from "./job-form-general";
 import { BackupModeField } from "./backup-mode-field";
 import { RsyncOptionsField } from "./rsync-options-field";
 import { SshConfigField } from "./ssh-config-field";
+import { EnvVarsField } from "./env-vars-field";
+import { RuntimeLimitField } from "./runtime-limit-field";
+import { ParallelSplitField } from "./parallel-split-field";
+import { SuccessExitCodesField } from "./success-exit-codes-field";
+import { NotifyOnField } from "./notify-on-field";
+import { ProcessPriorityField } from "./process-priority-field";
+import { ChangeAlarmField } from "./change-alarm-field";
+import { AppearanceField } from "./appearance-field";
 import { ScheduleField } from "./schedule-field";
 import { CommandPreview } from "../command-preview";
 
@@ -28,6 +45,20 @@ type Action =
   | { type: "SET_BACKUP_MODE"; mode: JobDefinition["transfer"]["backup_mode"] }
   | { type: "SET_OPTIONS"; options: JobDefinition["options"] }
   | { type: "SET_SSH_CONFIG"; ssh_config: SshConfig }
+  | { type: "SET_ENV"; env: Record<string, string> }
+  | { type: "SET_MAX_RUNTIME"; max_runtime_mins: number | null }
+  | { type: "SET_PARALLEL_SPLIT"; parallel_split: number | null }
+  | { type: "SET_VERIFY_AFTER_SNAPSHOT"; verify_after_snapshot: boolean }
+  | { type: "SET_SUCCESS_EXIT_CODES"; success_exit_codes: number[] }
+  | { type: "SET_NOTIFY_ON"; notify_on: NotifyOn }
+  | { type: "SET_PROCESS_PRIORITY"; process_priority: ProcessPriority | null }
+  | { type: "SET_CREATE_DESTINATION"; create_destination: boolean }
+  | { type: "SET_RUN_ON_STARTUP"; run_on_startup: boolean }
+  | { type: "SET_SKIP_IF_UNCHANGED"; skip_if_unchanged: boolean }
+  | { type: "SET_EXPECT_NONEMPTY_DESTINATION"; expect_nonempty_destination: boolean }
+  | { type: "SET_CHANGE_ALARM"; change_alarm: ChangeAlarm | null }
+  | { type: "SET_COLOR"; color: string | null }
+  | { type: "SET_ICON"; icon: string | null }
   | { type: "SET_SCHEDULE"; schedule: ScheduleConfig | null }
   | { type: "ENABLE_NAS_MODE" };
 
@@ -35,11 +66,18 @@ function needsSshConfig(source: StorageLocation, destination: StorageLocation):
   return source.type === "RemoteSsh" || destination.type === "RemoteSsh";
 }
 
+function isLocalPath(loc: StorageLocation): boolean {
+  return loc.type === "Local" || loc.type === "Mount";
+}
+
 function defaultSshConfig(): SshConfig {
   return {
     port: 22,
     identity_file: null,
     strict_host_key_checking: true,
+    connect_timeout: null,
+    server_alive_interval: null,
+    jump_host: null,
     custom_ssh_command: null,
   };
 }
@@ -70,6 +108,58 @@ function jobReducer(state: JobDefinition, action: Action): JobDefinition {
       return { ...state, options: action.options };
     case "SET_SSH_CONFIG":
       return { ...state, ssh_config: action.ssh_config };
+    case "SET_ENV":
+      return { ...state, run_policy: { ...state.run_policy, env: action.env } };
+    case "SET_MAX_RUNTIME":
+      return {
+        ...state,
+        run_policy: { ...state.run_policy, max_runtime_mins: action.max_runtime_mins },
+      };
+    case "SET_PARALLEL_SPLIT":
+      return {
+        ...state,
+        run_policy: { ...state.run_policy, parallel_split: action.parallel_split },
+      };
+    case "SET_VERIFY_AFTER_SNAPSHOT":
+      return {
+        ...state,
+        safety: { ...state.safety, verify_after_snapshot: action.verify_after_snapshot },
+      };
+    case "SET_SUCCESS_EXIT_CODES":
+      return {
+        ...state,
+        run_policy: { ...state.run_policy, success_exit_codes: action.success_exit_codes },
+      };
+    case "SET_NOTIFY_ON":
+      return { ...state, run_policy: { ...state.run_policy, notify_on: action.notify_on } };
+    case "SET_PROCESS_PRIORITY":
+      return {
+        ...state,
+        run_policy: { ...state.run_policy, process_priority: action.process_priority },
+      };
+    case "SET_CREATE_DESTINATION":
+      return {
+        ...state,
+        run_policy: { ...state.run_policy, create_destination: action.create_destination },
+      };
+    case "SET_RUN_ON_STARTUP":
+      return {
+        ...state,
+        run_policy: { ...state.run_policy, run_on_startup: action.run_on_startup },
+      };
+    case "SET_SKIP_IF_UNCHANGED":
+      return { ...state, safety: { ...state.safety, skip_if_unchanged: action.skip_if_unchanged } };
+    case "SET_EXPECT_NONEMPTY_DESTINATION":
+      return {
+        ...state,
+        safety: { ...state.safety, expect_nonempty_destination: action.expect_nonempty_destination },
+      };
+    case "SET_CHANGE_ALARM":
+      return { ...state, safety: { ...state.safety, change_alarm: action.change_alarm } };
+    case "SET_COLOR":
+      return { ...state, appearance: { ...state.appearance, color: action.color } };
+    case "SET_ICON":
+      return { ...state, appearance: { ...state.appearance, icon: action.icon } };
     case "SET_SCHEDULE":
       return { ...state, schedule: action.schedule };
     case "ENABLE_NAS_MODE": {
@@ -94,12 +184,22 @@ function validate(job: JobDefinition): Record<string, string> {
   if (!job.name.trim()) {
     errors.name = "Name is required";
   }
-  if (job.transfer.source.type === "Local" && !job.transfer.source.path.trim()) {
+  if (isLocalPath(job.transfer.source) && !job.transfer.source.path.trim()) {
     errors.source = "Source path is required";
   }
-  if (job.transfer.destination.type === "Local" && !job.transfer.destination.path.trim()) {
+  if (isLocalPath(job.transfer.destination) && !job.transfer.destination.path.trim()) {
     errors.destination = "Destination path is required";
   }
+  if (job.transfer.source.type === "Mount" && !job.transfer.source.mountpoint.trim()) {
+    errors.source = "Mountpoint is required";
+  }
+  if (job.transfer.destination.type === "Mount" && !job.transfer.destination.mountpoint.trim()) {
+    errors.destination = "Mountpoint is required";
+  }
+  const { color } = job.appearance;
+  if (color !== null && !/^#([0-9a-fA-F]{3}|[0-9a-fA-F]{6})$/.test(color)) {
+    errors.color = "Color must be a hex value like #3b82f6";
+  }
   return errors;
 }
 
@@ -132,7 +232,7 @@ export function JobForm({ initialJob, onSave, onCancel, title }: JobFormProps) {
         if (!cancelled) setNetworkFs(null);
         return;
       }
-      if (job.transfer.source.type === "Local" && job.transfer.source.path) {
+      if (isLocalPath(job.transfer.source) && job.transfer.source.path) {
         try {
           const fsType = await detectFilesystemType(job.transfer.source.path);
           if (!cancelled && fsType && NETWORK_FS_TYPES.includes(fsType)) {
@@ -142,7 +242,7 @@ export function JobForm({ initialJob, onSave, onCancel, title }: JobFormProps) {
           }
         } catch { /* ignore */ }
       }
-      if (job.transfer.destination.type === "Local" && job.transfer.destination.path) {
+      if (isLocalPath(job.transfer.destination) && job.transfer.destination.path) {
         try {
           const fsType = await detectFilesystemType(job.transfer.destination.path);
           if (!cancelled && fsType && NETWORK_FS_TYPES.includes(fsType)) {
@@ -163,6 +263,21 @@ export function JobForm({ initialJob, onSave, onCancel, title }: JobFormProps) {
     setErrors(validationErrors);
     if (Object.keys(validationErrors).length > 0) return;
 
+    // Never-firing schedules are rejected on save; a schedule that only runs
+    // inside quiet hours is allowed after confirming
+    if (job.schedule?.enabled) {
+      try {
+        const check = await checkSchedule(job.schedule.schedule_type);
+        if (
+          !check.passed &&
+          check.severity === "Warning" &&
+          !confirm(`${check.message}.\n\nSave anyway?`)
+        ) {
+          return;
+        }
+      } catch { /* the save reports schedule errors */ }
+    }
+
     setSaving(true);
     try {
       await onSave({ ...job, updated_at: new Date().toISOString() });
@@ -219,9 +334,31 @@ export function JobForm({ initialJob, onSave, onCancel, title }: JobFormProps) {
                   onDestinationChange={(destination) =>
                     dispatch({ type: "SET_DESTINATION", destination })
                   }
+                  onCreateDestinationChange={(create_destination) =>
+                    dispatch({ type: "SET_CREATE_DESTINATION", create_destination })
+                  }
+                  onRunOnStartupChange={(run_on_startup) =>
+                    dispatch({ type: "SET_RUN_ON_STARTUP", run_on_startup })
+                  }
+                  onSkipIfUnchangedChange={(skip_if_unchanged) =>
+                    dispatch({ type: "SET_SKIP_IF_UNCHANGED", skip_if_unchanged })
+                  }
+                  onExpectNonemptyDestinationChange={(expect_nonempty_destination) =>
+                    dispatch({
+                      type: "SET_EXPECT_NONEMPTY_DESTINATION",
+                      expect_nonempty_destination,
+                    })
+                  }
                   errors={errors}
                   autoTrailingSlash={autoTrailingSlash}
                 />
+                <AppearanceField
+                  color={job.appearance.color}
+                  icon={job.appearance.icon}
+                  onColorChange={(color) => dispatch({ type: "SET_COLOR", color })}
+                  onIconChange={(icon) => dispatch({ type: "SET_ICON", icon })}
+                  error={errors.color}
+                />
               </TabsContent>
 
               <TabsContent value="options" className="space-y-6 mt-4">
@@ -231,6 +368,20 @@ export function JobForm({ initialJob, onSave, onCancel, title }: JobFormProps) {
                     dispatch({ type: "SET_BACKUP_MODE", mode })
                   }
                 />
+                {job.transfer.backup_mode.type === "Snapshot" && (
+                  <div className="flex items-center gap-2">
+                    <Switch
+                      id="job-verify-after-snapshot"
+                      checked={job.safety.verify_after_snapshot}
+                      onCheckedChange={(verify_after_snapshot) =>
+                        dispatch({ type: "SET_VERIFY_AFTER_SNAPSHOT", verify_after_snapshot })
+                      }
+                    />
+                    <Label htmlFor="job-verify-after-snapshot">
+                      Verify each snapshot against the source by checksum
+                    </Label>
+                  </div>
+                )}
                 <RsyncOptionsField
                   value={job.options}
                   onChange={(options) =>
@@ -249,6 +400,44 @@ export function JobForm({ initialJob, onSave, onCancel, title }: JobFormProps) {
                     }
                   />
                 )}
+                <EnvVarsField
+                  value={job.run_policy.env}
+                  onChange={(env) => dispatch({ type: "SET_ENV", env })}
+                />
+                <RuntimeLimitField
+                  value={job.run_policy.max_runtime_mins}
+                  onChange={(max_runtime_mins) =>
+                    dispatch({ type: "SET_MAX_RUNTIME", max_runtime_mins })
+                  }
+                />
+                <ParallelSplitField
+                  value={job.run_policy.parallel_split}
+                  onChange={(parallel_split) =>
+                    dispatch({ type: "SET_PARALLEL_SPLIT", parallel_split })
+                  }
+                />
+                <SuccessExitCodesField
+                  value={job.run_policy.success_exit_codes}
+                  onChange={(success_exit_codes) =>
+                    dispatch({ type: "SET_SUCCESS_EXIT_CODES", success_exit_codes })
+                  }
+                />
+                <NotifyOnField
+                  value={job.run_policy.notify_on}
+                  onChange={(notify_on) => dispatch({ type: "SET_NOTIFY_ON", notify_on })}
+                />
+                <ProcessPriorityField
+                  value={job.run_policy.process_priority}
+                  onChange={(process_priority) =>
+                    dispatch({ type: "SET_PROCESS_PRIORITY", process_priority })
+                  }
+                />
+                <ChangeAlarmField
+                  value={job.safety.change_alarm}
+                  onChange={(change_alarm) =>
+                    dispatch({ type: "SET_CHANGE_ALARM", change_alarm })
+                  }
+                />
                 <ScheduleField
                   value={job.schedule}
                   onChange={(schedule) =>
@@ -0,0 +1,35 @@
+import type { NotifyOn } from "@/types/job";
+import { Label } from "@/components/ui/label";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
+
+interface NotifyOnFieldProps {
+  value: NotifyOn;
+  onChange: (value: NotifyOn) => void;
+}
+
+export function NotifyOnField({ value, onChange }: NotifyOnFieldProps) {
+  return (
+    <div className="space-y-2">
+      <Label>Notifications</Label>
+      <p className="text-xs text-muted-foreground">
+        Which finished runs of this job send a notification.
+      </p>
+      <Select value={value} onValueChange={(v) => onChange(v as NotifyOn)}>
+        <SelectTrigger className="w-40">
+          <SelectValue />
+        </SelectTrigger>
+        <SelectContent>
+          <SelectItem value="Never">Never</SelectItem>
+          <SelectItem value="Failure">On failure</SelectItem>
+          <SelectItem value="Always">Always</SelectItem>
+        </SelectContent>
+      </Select>
+    </div>
+  );
+}
@@ -0,0 +1,33 @@
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+
+interface ParallelSplitFieldProps {
+  value: number | null;
+  onChange: (value: number | null) => void;
+}
+
+export function ParallelSplitField({ value, onChange }: ParallelSplitFieldProps) {
+  return (
+    <div className="space-y-2">
+      <Label htmlFor="parallel-split">Parallel rsync Processes</Label>
+      <p className="text-xs text-muted-foreground">
+        Split a local source's top-level folders and files across up to this many rsync
+        processes run at once. Snapshot jobs and jobs that delete extraneous files always run a
+        single rsync. Leave empty to run a single rsync.
+      </p>
+      <Input
+        id="parallel-split"
+        type="number"
+        min={2}
+        max={16}
+        value={value ?? ""}
+        onChange={(e) => {
+          const parts = parseInt(e.target.value, 10);
+          onChange(isNaN(parts) || parts < 2 ? null : Math.min(parts, 16));
+        }}
+        placeholder="1"
+        className="w-40"
+      />
+    </div>
+  );
+}
@@ -0,0 +1,39 @@
+import type { ProcessPriority } from "@/types/job";
+import { Label } from "@/components/ui/label";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
+
+interface ProcessPriorityFieldProps {
+  value: ProcessPriority | null;
+  onChange: (value: ProcessPriority | null) => void;
+}
+
+export function ProcessPriorityField({ value, onChange }: ProcessPriorityFieldProps) {
+  return (
+    <div className="space-y-2">
+      <Label>Process Priority</Label>
+      <p className="text-xs text-muted-foreground">
+        CPU and disk priority of rsync while this job runs. High usually needs
+        administrator rights and otherwise runs at normal priority.
+      </p>
+      <Select
+        value={value ?? "Normal"}
+        onValueChange={(v) => onChange(v === "Normal" ? null : (v as ProcessPriority))}
+      >
+        <SelectTrigger className="w-40">
+          <SelectValue />
+        </SelectTrigger>
+        <SelectContent>
+          <SelectItem value="Low">Low</SelectItem>
+          <SelectItem value="Normal">Normal</SelectItem>
+          <SelectItem value="High">High</SelectItem>
+        </SelectContent>
+      </Select>
+    </div>
+  );
+}
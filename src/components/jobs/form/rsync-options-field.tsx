@@ -1,7 +1,17 @@
-import type { RsyncOptions } from "@/types/job";
-import { Info } from "lucide-react";
+import { useEffect, useState } from "react";
+import type { BandwidthWindow, RsyncOptions } from "@/types/job";
+import { Info, Plus, X } from "lucide-react";
+import { explainBandwidthSchedule } from "@/lib/tauri";
+import { Button } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { Label } from "@/components/ui/label";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
 import { Switch } from "@/components/ui/switch";
 import { Textarea } from "@/components/ui/textarea";
 import { PatternListField } from "./pattern-list-field";
@@ -15,6 +25,20 @@ interface RsyncOptionsFieldProps {
   showOutput?: boolean;
 }
 
+const COMPRESS_CHOICES = ["zstd", "lz4", "zlibx", "zlib", "none"];
+
+const CHECKSUM_CHOICES = ["xxh128", "xxh3", "xxh64", "md5", "md4"];
+
+function minuteToTime(minute: number): string {
+  const pad = (n: number) => String(n).padStart(2, "0");
+  return `${pad(Math.floor(minute / 60))}:${pad(minute % 60)}`;
+}
+
+function timeToMinute(time: string): number {
+  const [h, m] = time.split(":").map(Number);
+  return (h || 0) * 60 + (m || 0);
+}
+
 type OptionGroup = "core_transfer" | "file_handling" | "metadata" | "output";
 
 const BOOLEAN_FLAGS: { group: OptionGroup; key: string; label: string; description: string }[] = [
@@ -48,6 +72,26 @@ const OUTPUT_FLAGS: { group: OptionGroup; key: string; label: string; descriptio
 ];
 
 export function RsyncOptionsField({ value, onChange, networkFs, showFileHandling, showMetadata, showOutput }: RsyncOptionsFieldProps) {
+  const [bandwidthNote, setBandwidthNote] = useState<string | null>(null);
+
+  useEffect(() => {
+    let cancelled = false;
+    explainBandwidthSchedule(value.advanced)
+      .then((note) => { if (!cancelled) setBandwidthNote(note); })
+      .catch(() => { if (!cancelled) setBandwidthNote(null); });
+    return () => { cancelled = true; };
+  }, [value.advanced]);
+
+  function setBandwidthSchedule(schedule: BandwidthWindow[]) {
+    onChange({ ...value, advanced: { ...value.advanced, bandwidth_schedule: schedule } });
+  }
+
+  function updateBandwidthWindow(index: number, patch: Partial<BandwidthWindow>) {
+    setBandwidthSchedule(
+      value.advanced.bandwidth_schedule.map((w, i) => (i === index ? { ...w, ...patch } : w)),
+    );
+  }
+
   function toggleFlag(group: OptionGroup, key: string) {
     // eslint-disable-next-line @typescript-eslint/no-explicit-any
     const groupObj = value[group] as any;
@@ -82,6 +126,35 @@ export function RsyncOptionsField({ value, onChange, networkFs, showFileHandling
     );
   }
 
+  // Coarse timestamps on the destination make every file look changed, so the
+  // tolerance moves next to the network banner when the destination is a mount
+  const modifyWindowProminent = networkFs?.location === "destination";
+  const modifyWindowField = (
+    <div className="space-y-2">
+      <Label>Modify Window (seconds)</Label>
+      <p className="text-xs text-muted-foreground">
+        Treat modification times this many seconds apart as equal (--modify-window). FAT/exFAT
+        and SMB store times with 2-second precision; set 2 there to stop rsync re-transferring
+        unchanged files.
+      </p>
+      <Input
+        type="number"
+        min={0}
+        value={value.file_handling.modify_window ?? ""}
+        onChange={(e) =>
+          onChange({
+            ...value,
+            file_handling: {
+              ...value.file_handling,
+              modify_window: e.target.value ? parseInt(e.target.value) : null,
+            },
+          })
+        }
+        placeholder={modifyWindowProminent ? "2" : "Exact"}
+      />
+    </div>
+  );
+
   return (
     <div className="space-y-6">
       <div className="space-y-4">
@@ -135,6 +208,8 @@ export function RsyncOptionsField({ value, onChange, networkFs, showFileHandling
             </div>
           </div>
         )}
+
+        {modifyWindowProminent && modifyWindowField}
       </div>
 
       {showFileHandling && renderFlagGroup("File Handling", FILE_HANDLING_FLAGS)}
@@ -148,6 +223,22 @@ export function RsyncOptionsField({ value, onChange, networkFs, showFileHandling
         placeholder="e.g. *.tmp"
       />
 
+      <div className="flex items-center justify-between rounded-md border p-3">
+        <div>
+          <p className="text-sm font-medium">Exclude System Files</p>
+          <p className="text-xs text-muted-foreground">
+            Skip OS and NAS junk such as .DS_Store, Thumbs.db and @eaDir. Added to the command
+            at run time; your exclude patterns stay as they are.
+          </p>
+        </div>
+        <Switch
+          checked={value.advanced.exclude_system_files}
+          onCheckedChange={(checked) =>
+            onChange({ ...value, advanced: { ...value.advanced, exclude_system_files: checked } })
+          }
+        />
+      </div>
+
       <PatternListField
         label="Include Patterns"
         patterns={value.advanced.include_patterns}
@@ -174,6 +265,192 @@ export function RsyncOptionsField({ value, onChange, networkFs, showFileHandling
         />
       </div>
 
+      <div className="space-y-2">
+        <div className="flex items-center justify-between">
+          <Label>Bandwidth Schedule</Label>
+          <Button
+            type="button"
+            variant="outline"
+            size="sm"
+            onClick={() =>
+              setBandwidthSchedule([
+                ...value.advanced.bandwidth_schedule,
+                { start_minute: 8 * 60, end_minute: 18 * 60, limit: null },
+              ])
+            }
+          >
+            <Plus className="mr-1 h-3 w-3" />
+            Add window
+          </Button>
+        </div>
+        <p className="text-xs text-muted-foreground">
+          Use a different limit (KB/s) at certain times of day; outside every window the limit
+          above applies. The first matching window wins, and a run keeps the limit it started with.
+        </p>
+        {value.advanced.bandwidth_schedule.map((w, i) => (
+          <div key={i} className="flex items-center gap-2">
+            <Input
+              type="time"
+              value={minuteToTime(w.start_minute)}
+              onChange={(e) => updateBandwidthWindow(i, { start_minute: timeToMinute(e.target.value) })}
+            />
+            <span className="text-xs text-muted-foreground">to</span>
+            <Input
+              type="time"
+              value={minuteToTime(w.end_minute)}
+              onChange={(e) => updateBandwidthWindow(i, { end_minute: timeToMinute(e.target.value) })}
+            />
+            <Input
+              type="number"
+              min={0}
+              value={w.limit ?? ""}
+              onChange={(e) =>
+                updateBandwidthWindow(i, { limit: e.target.value ? parseInt(e.target.value) : null })
+              }
+              placeholder="Unlimited"
+            />
+            <Button
+              type="button"
+              variant="ghost"
+              size="icon"
+              onClick={() =>
+                setBandwidthSchedule(value.advanced.bandwidth_schedule.filter((_, j) => j !== i))
+              }
+            >
+              <X className="h-4 w-4" />
+            </Button>
+          </div>
+        ))}
+        {bandwidthNote && <p className="text-xs text-muted-foreground">{bandwidthNote}</p>}
+      </div>
+
+      <div className="space-y-2">
+        <Label>Max Deletions</Label>
+        <p className="text-xs text-muted-foreground">
+          Stop deleting once this many files have been removed (--max-delete). The run fails
+          with "delete limit reached" instead of wiping the destination.
+        </p>
+        <Input
+          type="number"
+          min={0}
+          value={value.file_handling.max_delete ?? ""}
+          onChange={(e) =>
+            onChange({
+              ...value,
+              file_handling: {
+                ...value.file_handling,
+                max_delete: e.target.value ? parseInt(e.target.value) : null,
+              },
+            })
+          }
+          placeholder="Unlimited"
+        />
+      </div>
+
+      {!modifyWindowProminent && modifyWindowField}
+
+      <div className="space-y-2">
+        <Label>Checksum Algorithm</Label>
+        <p className="text-xs text-muted-foreground">
+          Hash used for delta transfers and --checksum comparisons (--checksum-choice). The xxh
+          family is much faster but needs rsync 3.2 or newer on both ends; preflight warns when
+          the local rsync lacks it.
+        </p>
+        <Select
+          value={value.file_handling.checksum_choice ?? "default"}
+          onValueChange={(choice) =>
+            onChange({
+              ...value,
+              file_handling: {
+                ...value.file_handling,
+                checksum_choice: choice === "default" ? null : choice,
+              },
+            })
+          }
+        >
+          <SelectTrigger>
+            <SelectValue />
+          </SelectTrigger>
+          <SelectContent>
+            <SelectItem value="default">rsync default</SelectItem>
+            {CHECKSUM_CHOICES.map((choice) => (
+              <SelectItem key={choice} value={choice}>
+                {choice}
+              </SelectItem>
+            ))}
+          </SelectContent>
+        </Select>
+      </div>
+
+      <div className="space-y-2">
+        <Label>Partial Directory</Label>
+        <p className="text-xs text-muted-foreground">
+          Keep partially transferred files here until they are complete (--partial-dir, implies
+          --partial), so a half-copied file is never mistaken for a finished one. A relative name
+          is created inside each destination directory.
+        </p>
+        <Input
+          value={value.core_transfer.partial_dir ?? ""}
+          onChange={(e) =>
+            onChange({
+              ...value,
+              core_transfer: {
+                ...value.core_transfer,
+                partial_dir: e.target.value ? e.target.value : null,
+              },
+            })
+          }
+          placeholder=".rsync-partial"
+        />
+      </div>
+
+      <div className="grid grid-cols-2 gap-3">
+        <div className="space-y-2">
+          <Label>Compression Algorithm</Label>
+          <Select
+            value={value.advanced.compress_choice ?? "default"}
+            onValueChange={(choice) =>
+              onChange({
+                ...value,
+                advanced: {
+                  ...value.advanced,
+                  compress_choice: choice === "default" ? null : choice,
+                },
+              })
+            }
+          >
+            <SelectTrigger>
+              <SelectValue />
+            </SelectTrigger>
+            <SelectContent>
+              <SelectItem value="default">rsync default</SelectItem>
+              {COMPRESS_CHOICES.map((choice) => (
+                <SelectItem key={choice} value={choice}>
+                  {choice}
+                </SelectItem>
+              ))}
+            </SelectContent>
+          </Select>
+        </div>
+        <div className="space-y-2">
+          <Label>Compression Level</Label>
+          <Input
+            type="number"
+            value={value.advanced.compress_level ?? ""}
+            onChange={(e) =>
+              onChange({
+                ...value,
+                advanced: {
+                  ...value.advanced,
+                  compress_level: e.target.value ? parseInt(e.target.value) : null,
+                },
+              })
+            }
+            placeholder="Default"
+          />
+        </div>
+      </div>
+
       <div className="space-y-2">
         <Label>Custom Arguments</Label>
         <Textarea
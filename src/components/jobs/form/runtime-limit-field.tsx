@@ -0,0 +1,30 @@
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+
+interface RuntimeLimitFieldProps {
+  value: number | null;
+  onChange: (value: number | null) => void;
+}
+
+export function RuntimeLimitField({ value, onChange }: RuntimeLimitFieldProps) {
+  return (
+    <div className="space-y-2">
+      <Label htmlFor="max-runtime">Maximum Run Time (minutes)</Label>
+      <p className="text-xs text-muted-foreground">
+        Stop the run once it has been going this long. Leave empty for no limit.
+      </p>
+      <Input
+        id="max-runtime"
+        type="number"
+        min={1}
+        value={value ?? ""}
+        onChange={(e) => {
+          const mins = parseInt(e.target.value, 10);
+          onChange(isNaN(mins) || mins <= 0 ? null : mins);
+        }}
+        placeholder="No limit"
+        className="w-40"
+      />
+    </div>
+  );
+}
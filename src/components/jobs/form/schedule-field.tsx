@@ -1,4 +1,7 @@
+import { useState } from "react";
 import type { ScheduleConfig, ScheduleType } from "@/types/schedule";
+import { previewSchedule } from "@/lib/tauri";
+import { Button } from "@/components/ui/button";
 import { Label } from "@/components/ui/label";
 import { Switch } from "@/components/ui/switch";
 import { Input } from "@/components/ui/input";
@@ -15,6 +18,8 @@ interface ScheduleFieldProps {
   onChange: (schedule: ScheduleConfig | null) => void;
 }
 
+const PREVIEW_COUNT = 10;
+
 function defaultSchedule(): ScheduleConfig {
   return {
     schedule_type: { type: "Interval", minutes: 60 },
@@ -25,6 +30,19 @@ function defaultSchedule(): ScheduleConfig {
 export function ScheduleField({ value, onChange }: ScheduleFieldProps) {
   const hasSchedule = value !== null;
   const enabled = value?.enabled ?? false;
+  const [preview, setPreview] = useState<string[] | null>(null);
+  const [previewError, setPreviewError] = useState<string | null>(null);
+
+  async function handlePreview() {
+    if (!value) return;
+    try {
+      setPreview(await previewSchedule(value.schedule_type, PREVIEW_COUNT));
+      setPreviewError(null);
+    } catch (err) {
+      setPreview(null);
+      setPreviewError(String(err));
+    }
+  }
 
   function handleToggle(checked: boolean) {
     if (checked) {
@@ -137,6 +155,25 @@ export function ScheduleField({ value, onChange }: ScheduleFieldProps) {
               </p>
             </div>
           )}
+
+          <div className="space-y-2">
+            <Button type="button" variant="outline" size="sm" onClick={handlePreview}>
+              Preview next {PREVIEW_COUNT} runs
+            </Button>
+            {previewError && <p className="text-xs text-destructive">{previewError}</p>}
+            {preview && preview.length === 0 && (
+              <p className="text-xs text-muted-foreground">
+                This schedule never runs. Check the cron expression.
+              </p>
+            )}
+            {preview && preview.length > 0 && (
+              <ul className="space-y-1 text-xs text-muted-foreground">
+                {preview.map((run) => (
+                  <li key={run}>{new Date(run).toLocaleString()}</li>
+                ))}
+              </ul>
+            )}
+          </div>
         </div>
       )}
     </div>
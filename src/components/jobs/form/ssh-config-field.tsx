@@ -3,6 +3,11 @@ import { Input } from "@/components/ui/input";
 import { Label } from "@/components/ui/label";
 import { Switch } from "@/components/ui/switch";
 
+function parseSeconds(input: string): number | null {
+  const secs = parseInt(input, 10);
+  return isNaN(secs) || secs <= 0 ? null : secs;
+}
+
 interface SshConfigFieldProps {
   value: SshConfig;
   onChange: (value: SshConfig) => void;
@@ -37,6 +42,43 @@ export function SshConfigField({ value, onChange }: SshConfigFieldProps) {
             placeholder="~/.ssh/id_rsa"
           />
         </div>
+        <div className="space-y-1">
+          <Label className="text-xs text-muted-foreground">Connect Timeout (s)</Label>
+          <Input
+            type="number"
+            min={1}
+            value={value.connect_timeout ?? ""}
+            onChange={(e) =>
+              onChange({ ...value, connect_timeout: parseSeconds(e.target.value) })
+            }
+            placeholder="System default"
+          />
+        </div>
+        <div className="space-y-1">
+          <Label className="text-xs text-muted-foreground">Keepalive Interval (s)</Label>
+          <Input
+            type="number"
+            min={1}
+            value={value.server_alive_interval ?? ""}
+            onChange={(e) =>
+              onChange({
+                ...value,
+                server_alive_interval: parseSeconds(e.target.value),
+              })
+            }
+            placeholder="Off"
+          />
+        </div>
+        <div className="col-span-2 space-y-1">
+          <Label className="text-xs text-muted-foreground">Jump Host</Label>
+          <Input
+            value={value.jump_host ?? ""}
+            onChange={(e) =>
+              onChange({ ...value, jump_host: e.target.value || null })
+            }
+            placeholder="user@bastion:22"
+          />
+        </div>
       </div>
 
       <div className="flex items-center justify-between rounded-md border p-3">
@@ -64,7 +106,7 @@ export function SshConfigField({ value, onChange }: SshConfigFieldProps) {
               custom_ssh_command: e.target.value || null,
             })
           }
-          placeholder="e.g. ssh -o ConnectTimeout=10"
+          placeholder="e.g. ssh -F ~/.ssh/backup_config"
         />
       </div>
     </div>
@@ -11,6 +11,7 @@ import {
   SelectTrigger,
   SelectValue,
 } from "@/components/ui/select";
+import { DaemonPasswordField } from "./daemon-password-field";
 
 interface StorageLocationFieldProps {
   label: string;
@@ -32,6 +33,14 @@ export function StorageLocationField({
       case "Local":
         onChange({ type: "Local", path: "" });
         break;
+      case "Mount":
+        onChange({
+          type: "Mount",
+          mountpoint: "",
+          path: "",
+          expected_fs_label: null,
+        });
+        break;
       case "RemoteSsh":
         onChange({
           type: "RemoteSsh",
@@ -48,6 +57,8 @@ export function StorageLocationField({
           host: "",
           module: "",
           path: "",
+          username: null,
+          password_secret: null,
         });
         break;
     }
@@ -62,6 +73,7 @@ export function StorageLocationField({
         </SelectTrigger>
         <SelectContent>
           <SelectItem value="Local">Local</SelectItem>
+          <SelectItem value="Mount">Mounted volume</SelectItem>
           <SelectItem value="RemoteSsh">Remote (SSH)</SelectItem>
           <SelectItem value="RemoteRsync">Remote (rsync daemon)</SelectItem>
         </SelectContent>
@@ -108,6 +120,50 @@ export function StorageLocationField({
         </div>
       )}
 
+      {value.type === "Mount" && (
+        <div className="space-y-1.5">
+          <div className="grid grid-cols-2 gap-2">
+            <div className="space-y-1">
+              <Label className="text-xs text-muted-foreground">Mountpoint</Label>
+              <Input
+                value={value.mountpoint}
+                onChange={(e) => onChange({ ...value, mountpoint: e.target.value })}
+                placeholder="/Volumes/Backup"
+              />
+            </div>
+            <div className="space-y-1">
+              <Label className="text-xs text-muted-foreground">Volume label</Label>
+              <Input
+                value={value.expected_fs_label ?? ""}
+                onChange={(e) =>
+                  onChange({ ...value, expected_fs_label: e.target.value || null })
+                }
+                placeholder="Any"
+              />
+            </div>
+            <div className="col-span-2 space-y-1">
+              <Label className="text-xs text-muted-foreground">Path</Label>
+              <div className="relative">
+                <Input
+                  className={showSlashIndicator ? "pr-6" : ""}
+                  value={value.path}
+                  onChange={(e) => onChange({ ...value, path: e.target.value })}
+                  placeholder="/Volumes/Backup/laptop"
+                />
+                {showSlashIndicator && (
+                  <span className="absolute right-3 top-1/2 -translate-y-1/2 text-muted-foreground pointer-events-none text-sm">
+                    /
+                  </span>
+                )}
+              </div>
+            </div>
+          </div>
+          <p className="text-xs text-muted-foreground">
+            Runs are refused when nothing is mounted at the mountpoint.
+          </p>
+        </div>
+      )}
+
       {value.type === "RemoteSsh" && (
         <div className="space-y-1.5">
           <div className="grid grid-cols-2 gap-2">
@@ -183,6 +239,26 @@ export function StorageLocationField({
                 placeholder="backup"
               />
             </div>
+            <div className="col-span-2 space-y-1">
+              <Label className="text-xs text-muted-foreground">
+                Username (optional)
+              </Label>
+              <Input
+                value={value.username ?? ""}
+                onChange={(e) =>
+                  onChange({ ...value, username: e.target.value || null })
+                }
+                placeholder="Anonymous"
+              />
+            </div>
+            {value.username && (
+              <DaemonPasswordField
+                secretName={value.password_secret}
+                onSecretNameChange={(password_secret) =>
+                  onChange({ ...value, password_secret })
+                }
+              />
+            )}
             <div className="col-span-2 space-y-1">
               <Label className="text-xs text-muted-foreground">Path</Label>
               <div className="relative">
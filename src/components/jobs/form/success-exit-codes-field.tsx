@@ -0,0 +1,42 @@
+import { useState } from "react";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+
+interface SuccessExitCodesFieldProps {
+  value: number[];
+  onChange: (value: number[]) => void;
+}
+
+function parseCodes(text: string): number[] {
+  const codes = text
+    .split(",")
+    .map((part) => parseInt(part.trim(), 10))
+    .filter((code) => !isNaN(code) && code >= 0);
+  return codes.length > 0 ? Array.from(new Set(codes)) : [0];
+}
+
+export function SuccessExitCodesField({ value, onChange }: SuccessExitCodesFieldProps) {
+  const [text, setText] = useState(value.join(", "));
+
+  return (
+    <div className="space-y-2">
+      <Label htmlFor="success-exit-codes">Success Exit Codes</Label>
+      <p className="text-xs text-muted-foreground">
+        rsync exit codes that count as a successful run, separated by commas.
+        Add 24 to accept files that vanished during the transfer.
+      </p>
+      <Input
+        id="success-exit-codes"
+        value={text}
+        onChange={(e) => setText(e.target.value)}
+        onBlur={() => {
+          const codes = parseCodes(text);
+          setText(codes.join(", "));
+          onChange(codes);
+        }}
+        placeholder="0"
+        className="w-40"
+      />
+    </div>
+  );
+}
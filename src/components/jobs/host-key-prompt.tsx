@@ -0,0 +1,48 @@
+import type { HostKey } from "@/types/job";
+import { Button } from "@/components/ui/button";
+
+interface HostKeyPromptProps {
+  keys: HostKey[];
+  trusted: string[];
+  onTrust: (key: HostKey) => void;
+  onDismiss: () => void;
+}
+
+/**
+ * Lists the host keys a server offered so the user can compare fingerprints
+ * before trusting one. Nothing is added to known_hosts without a click.
+ */
+export function HostKeyPrompt({ keys, trusted, onTrust, onDismiss }: HostKeyPromptProps) {
+  const target = keys[0]?.port === 22 ? keys[0]?.host : `${keys[0]?.host}:${keys[0]?.port}`;
+
+  return (
+    <div className="mt-3 space-y-2 border-t pt-2">
+      <div className="text-sm font-medium">Host keys for {target}</div>
+      <p className="text-xs text-muted-foreground">
+        Check these fingerprints against the server (e.g. <code>ssh-keygen -lf</code> on the host)
+        before trusting one.
+      </p>
+      {keys.map((key) => {
+        const isTrusted = trusted.includes(key.known_hosts_line);
+        return (
+          <div key={key.known_hosts_line} className="flex items-center gap-2 text-xs">
+            <span className="shrink-0 text-muted-foreground">{key.key_type}</span>
+            <code className="truncate">{key.fingerprint}</code>
+            <Button
+              variant="outline"
+              size="sm"
+              className="ml-auto h-6 text-xs"
+              onClick={() => onTrust(key)}
+              disabled={isTrusted}
+            >
+              {isTrusted ? "Trusted" : "Trust"}
+            </Button>
+          </div>
+        );
+      })}
+      <Button variant="ghost" size="sm" className="h-6 text-xs" onClick={onDismiss}>
+        Close
+      </Button>
+    </div>
+  );
+}
@@ -1,5 +1,5 @@
 import { useState } from "react";
-import type { JobDefinition, JobStatus } from "@/types/job";
+import type { HostKey, JobDefinition, JobStatus } from "@/types/job";
 import type { PreflightResult } from "@/types/validation";
 import * as api from "@/lib/tauri";
 import {
@@ -11,10 +11,14 @@ import {
 } from "@/components/ui/card";
 import { Badge } from "@/components/ui/badge";
 import { Button } from "@/components/ui/button";
-import { Pencil, Trash2, ArrowRight, ShieldCheck, FlaskConical, Terminal } from "lucide-react";
+import { Pencil, Trash2, ArrowRight, ShieldCheck, FlaskConical, Terminal, KeyRound, ServerCog, HardDrive } from "lucide-react";
+import { HostKeyPrompt } from "./host-key-prompt";
+import { JobIcon } from "./job-icon";
 import { JobRunButton } from "./job-run-button";
+import { RetargetPanel } from "./retarget-panel";
 import { ScheduleBadge } from "./schedule-badge";
-import { locationSummary, statusBadgeVariant } from "./job-formatting";
+import { statusBadgeVariant, statusLabel } from "@/lib/status";
+import { locationSummary } from "./job-formatting";
 
 interface JobCardProps {
   job: JobDefinition;
@@ -25,12 +29,21 @@ interface JobCardProps {
   onDryRun: () => void;
   onCancel: () => void;
   onViewExecution: () => void;
+  onRetargeted: () => void;
 }
 
-export function JobCard({ job, status, onEdit, onDelete, onRun, onDryRun, onCancel, onViewExecution }: JobCardProps) {
+export function JobCard({ job, status, onEdit, onDelete, onRun, onDryRun, onCancel, onViewExecution, onRetargeted }: JobCardProps) {
   const isRunning = status === "Running";
   const [preflight, setPreflight] = useState<PreflightResult | null>(null);
   const [preflightLoading, setPreflightLoading] = useState(false);
+  const [hostKeys, setHostKeys] = useState<HostKey[] | null>(null);
+  const [trustedKeys, setTrustedKeys] = useState<string[]>([]);
+  const [hostKeyError, setHostKeyError] = useState<string | null>(null);
+  const [retargeting, setRetargeting] = useState(false);
+  const [usageMessage, setUsageMessage] = useState<string | null>(null);
+  const [usageError, setUsageError] = useState<string | null>(null);
+  const isRemote =
+    job.transfer.source.type === "RemoteSsh" || job.transfer.destination.type === "RemoteSsh";
 
   async function handlePreflight() {
     setPreflightLoading(true);
@@ -45,11 +58,59 @@ export function JobCard({ job, status, onEdit, onDelete, onRun, onDryRun, onCanc
     }
   }
 
+  async function handleScanHostKeys() {
+    setHostKeyError(null);
+    setHostKeys(null);
+    try {
+      setHostKeys(await api.scanHostKeys(job.id));
+    } catch (err) {
+      setHostKeyError(String(err));
+    }
+  }
+
+  async function handleDestinationUsage() {
+    setUsageError(null);
+    setUsageMessage(null);
+    try {
+      const usage = await api.getDestinationUsage(job.id);
+      if (!usage) {
+        setUsageMessage("Destination size unknown (remote, unmounted or not created yet)");
+        return;
+      }
+      const physical = await api.formatBytes(usage.physical_bytes);
+      if (usage.physical_bytes === usage.logical_bytes) {
+        setUsageMessage(`Destination uses ${physical}`);
+      } else {
+        const logical = await api.formatBytes(usage.logical_bytes);
+        setUsageMessage(
+          `Destination uses ${physical} on disk (${logical} before hard-link sharing)`
+        );
+      }
+    } catch (err) {
+      setUsageError(String(err));
+    }
+  }
+
+  async function handleTrustHostKey(key: HostKey) {
+    try {
+      await api.trustHostKey(key);
+      setTrustedKeys((prev) => [...prev, key.known_hosts_line]);
+    } catch (err) {
+      setHostKeyError(String(err));
+    }
+  }
+
+  const { color, icon } = job.appearance;
+
   return (
-    <Card className={isRunning ? "border-primary/50" : undefined}>
+    <Card
+      className={isRunning ? "border-primary/50" : undefined}
+      style={color ? { borderLeftColor: color, borderLeftWidth: 4 } : undefined}
+    >
       <CardHeader className="pb-2">
         <div className="space-y-2">
           <div className="flex items-center gap-2">
+            <JobIcon name={icon} color={color} className="h-4 w-4 shrink-0" />
             <CardTitle className="text-base truncate">{job.name}</CardTitle>
             {!job.enabled && (
               <Badge variant="secondary" className="text-xs shrink-0">
@@ -73,6 +134,27 @@ export function JobCard({ job, status, onEdit, onDelete, onRun, onDryRun, onCanc
             >
               <ShieldCheck className="h-4 w-4" />
             </Button>
+            {isRemote && (
+              <Button
+                variant="ghost"
+                size="icon"
+                className="h-8 w-8"
+                onClick={handleScanHostKeys}
+                disabled={isRunning}
+                title="Check SSH host key"
+              >
+                <KeyRound className="h-4 w-4" />
+              </Button>
+            )}
+            <Button
+              variant="ghost"
+              size="icon"
+              className="h-8 w-8"
+              onClick={handleDestinationUsage}
+              title="Destination size on disk"
+            >
+              <HardDrive className="h-4 w-4" />
+            </Button>
             <Button
               variant="ghost"
               size="icon"
@@ -95,6 +177,16 @@ export function JobCard({ job, status, onEdit, onDelete, onRun, onDryRun, onCanc
                 <Terminal className="h-4 w-4" />
               </Button>
             )}
+            <Button
+              variant="ghost"
+              size="icon"
+              className="h-8 w-8"
+              onClick={() => setRetargeting(true)}
+              disabled={isRunning}
+              title="Change destination"
+            >
+              <ServerCog className="h-4 w-4" />
+            </Button>
             <Button variant="ghost" size="icon" className="h-8 w-8" onClick={onEdit} disabled={isRunning}>
               <Pencil className="h-4 w-4" />
             </Button>
@@ -125,10 +217,36 @@ export function JobCard({ job, status, onEdit, onDelete, onRun, onDryRun, onCanc
           <ScheduleBadge schedule={job.schedule} />
           {status !== "Idle" && (
             <Badge variant={statusBadgeVariant(status)} className="text-xs">
-              {status}
+              {statusLabel(status)}
             </Badge>
           )}
         </div>
+        {retargeting && (
+          <RetargetPanel
+            job={job}
+            onClose={(changed) => {
+              setRetargeting(false);
+              if (changed) onRetargeted();
+            }}
+          />
+        )}
+        {hostKeys && (
+          <HostKeyPrompt
+            keys={hostKeys}
+            trusted={trustedKeys}
+            onTrust={handleTrustHostKey}
+            onDismiss={() => setHostKeys(null)}
+          />
+        )}
+        {usageMessage && (
+          <p className="mt-2 text-xs text-muted-foreground">{usageMessage}</p>
+        )}
+        {usageError && (
+          <p className="mt-2 text-xs text-destructive">{usageError}</p>
+        )}
+        {hostKeyError && (
+          <p className="mt-2 text-xs text-destructive">{hostKeyError}</p>
+        )}
         {preflight && (
           <div className="mt-3 space-y-1 border-t pt-2">
             <div className="flex items-center gap-2 text-sm font-medium">
@@ -0,0 +1,44 @@
+import {
+  Archive,
+  Briefcase,
+  Camera,
+  Cloud,
+  Code,
+  Database,
+  FileText,
+  Film,
+  HardDrive,
+  Home,
+  Music,
+  Server,
+  type LucideIcon,
+} from "lucide-react";
+
+/** Icons a job can be given; the key is the name stored on the job. */
+export const JOB_ICONS: Record<string, LucideIcon> = {
+  archive: Archive,
+  briefcase: Briefcase,
+  camera: Camera,
+  cloud: Cloud,
+  code: Code,
+  database: Database,
+  "file-text": FileText,
+  film: Film,
+  "hard-drive": HardDrive,
+  home: Home,
+  music: Music,
+  server: Server,
+};
+
+interface JobIconProps {
+  name: string | null;
+  color: string | null;
+  className?: string;
+}
+
+/** The job's icon tinted with its color; renders nothing for unknown names. */
+export function JobIcon({ name, color, className }: JobIconProps) {
+  const Icon = name ? JOB_ICONS[name] : undefined;
+  if (!Icon) return null;
+  return <Icon className={className} style={color ? { color } : undefined} />;
+}
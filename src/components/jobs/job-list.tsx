@@ -2,7 +2,7 @@ import { useState } from "react";
 import type { JobDefinition, JobStatus } from "@/types/job";
 import type { ProgressUpdate, LogLine } from "@/types/execution/progress";
 import { Button } from "@/components/ui/button";
-import { Plus, LayoutGrid, List } from "lucide-react";
+import { Plus, LayoutGrid, List, Merge } from "lucide-react";
 import { JobCard } from "./job-card";
 import { JobTable } from "./job-table";
 
@@ -19,6 +19,8 @@ interface JobListProps {
   onDryRun: (jobId: string) => void;
   onCancel: (jobId: string) => void;
   onViewExecution: (jobId: string) => void;
+  onRetargeted: () => void;
+  onMergeDuplicates: () => void;
   getStatus: (jobId: string) => JobStatus;
   getLogs: (jobId: string) => LogLine[];
   getProgress: (jobId: string) => ProgressUpdate | null;
@@ -36,6 +38,8 @@ export function JobList({
   onDryRun,
   onCancel,
   onViewExecution,
+  onRetargeted,
+  onMergeDuplicates,
   getStatus,
   getLogs,
   getProgress,
@@ -73,6 +77,15 @@ export function JobList({
               <List className="h-4 w-4" />
             </Button>
           </div>
+          <Button
+            variant="outline"
+            size="icon"
+            className="h-8 w-8"
+            onClick={onMergeDuplicates}
+            title="Find and merge duplicate jobs"
+          >
+            <Merge className="h-4 w-4" />
+          </Button>
           <Button onClick={onCreate}>
             <Plus className="h-4 w-4 mr-2" />
             Create Job
@@ -113,6 +126,7 @@ export function JobList({
               onDryRun={() => onDryRun(job.id)}
               onCancel={() => onCancel(job.id)}
               onViewExecution={() => onViewExecution(job.id)}
+              onRetargeted={onRetargeted}
             />
           ))}
         </div>
@@ -6,9 +6,11 @@ import * as api from "@/lib/tauri";
 import { Badge } from "@/components/ui/badge";
 import { Button } from "@/components/ui/button";
 import { Pencil, Trash2, ShieldCheck, ChevronDown, ChevronRight, FlaskConical, ExternalLink, Terminal } from "lucide-react";
+import { JobIcon } from "./job-icon";
 import { JobRunButton } from "./job-run-button";
 import { ScheduleBadge } from "./schedule-badge";
-import { locationSummary, statusBadgeVariant } from "./job-formatting";
+import { statusBadgeVariant, statusLabel } from "@/lib/status";
+import { locationSummary } from "./job-formatting";
 
 interface JobTableProps {
   jobs: JobDefinition[];
@@ -89,12 +91,15 @@ function JobTableRow({
     }
   }
 
+  const { color, icon } = job.appearance;
+
   return (
     <>
       <tr className="border-b hover:bg-muted/50 transition-colors">
         <td className="px-4 py-3 text-sm font-medium">
           <div className="flex items-center gap-2">
-            <span>{job.name}</span>
+            <JobIcon name={icon} color={color} className="h-4 w-4 shrink-0" />
+            <span style={color ? { color } : undefined}>{job.name}</span>
             {!job.enabled && (
               <Badge variant="secondary" className="text-xs">
                 Disabled
@@ -119,7 +124,7 @@ function JobTableRow({
         <td className="px-4 py-3">
           {status !== "Idle" && (
             <Badge variant={statusBadgeVariant(status)} className="text-xs">
-              {status}
+              {statusLabel(status)}
             </Badge>
           )}
         </td>
@@ -220,7 +225,7 @@ function JobTableRow({
               )}
               <span>Execution Output</span>
               <Badge variant={statusBadgeVariant(status)} className="text-xs ml-1">
-                {status}
+                {statusLabel(status)}
               </Badge>
             </button>
             {logsOpen && (
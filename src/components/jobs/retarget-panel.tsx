@@ -0,0 +1,98 @@
+import { useState } from "react";
+import type { JobDefinition, RetargetResult, StorageLocation } from "@/types/job";
+import * as api from "@/lib/tauri";
+import { Button } from "@/components/ui/button";
+import { StorageLocationField } from "./form/storage-location-field";
+
+interface RetargetPanelProps {
+  job: JobDefinition;
+  /** Called when the panel closes; `changed` is true if the job was retargeted. */
+  onClose: (changed: boolean) => void;
+}
+
+function initialDestination(current: StorageLocation): StorageLocation {
+  // Offer SSH with the same path, the usual next step for a local prototype
+  if (current.type === "Local" || current.type === "Mount") {
+    return {
+      type: "RemoteSsh",
+      user: "",
+      host: "",
+      port: 22,
+      path: current.path,
+      identity_file: null,
+    };
+  }
+  return current;
+}
+
+/**
+ * Points an existing job at a new destination and reports what changed:
+ * destination warnings, preflight checks that now apply differently, and
+ * snapshots left behind at the old destination.
+ */
+export function RetargetPanel({ job, onClose }: RetargetPanelProps) {
+  const [destination, setDestination] = useState<StorageLocation>(() =>
+    initialDestination(job.transfer.destination)
+  );
+  const [result, setResult] = useState<RetargetResult | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [saving, setSaving] = useState(false);
+
+  async function handleApply() {
+    setSaving(true);
+    setError(null);
+    try {
+      setResult(await api.retargetJob(job.id, destination));
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setSaving(false);
+    }
+  }
+
+  const warnings = result?.checks.filter((c) => !c.passed) ?? [];
+
+  return (
+    <div className="mt-3 space-y-3 border-t pt-2">
+      <div className="text-sm font-medium">Change destination</div>
+      {result ? (
+        <div className="space-y-1 text-xs">
+          <p>Destination updated.</p>
+          {warnings.map((check, i) => (
+            <p key={i} className="text-amber-600 dark:text-amber-400">
+              {check.message}
+            </p>
+          ))}
+          {result.forgotten_snapshots.length > 0 && (
+            <p className="text-muted-foreground">
+              {result.forgotten_snapshots.length} snapshot(s) at the old
+              destination are no longer tracked; the next run is a full copy.
+              Their directories were left in place.
+            </p>
+          )}
+        </div>
+      ) : (
+        <StorageLocationField
+          label="New destination"
+          value={destination}
+          onChange={setDestination}
+        />
+      )}
+      {error && <p className="text-xs text-destructive">{error}</p>}
+      <div className="flex gap-2">
+        {!result && (
+          <Button size="sm" onClick={handleApply} disabled={saving}>
+            Apply
+          </Button>
+        )}
+        <Button
+          variant="ghost"
+          size="sm"
+          onClick={() => onClose(result !== null)}
+        >
+          {result ? "Done" : "Cancel"}
+        </Button>
+      </div>
+    </div>
+  );
+}
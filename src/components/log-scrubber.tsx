@@ -1,8 +1,10 @@
-import { useState } from "react";
+import { useEffect, useState } from "react";
 import type { ScrubScanResult, ScrubApplyResult } from "@/types/scrubber";
 import * as api from "@/lib/tauri";
 import { Button } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import { Switch } from "@/components/ui/switch";
 import {
   Card,
   CardContent,
@@ -26,18 +28,40 @@ type Phase = "input" | "review" | "complete";
 
 export function LogScrubber() {
   const [pattern, setPattern] = useState("");
+  const [regex, setRegex] = useState(false);
+  const [patternError, setPatternError] = useState<string | null>(null);
+  const [patternWarning, setPatternWarning] = useState<string | null>(null);
   const [phase, setPhase] = useState<Phase>("input");
   const [scanResults, setScanResults] = useState<ScrubScanResult[]>([]);
   const [applyResults, setApplyResults] = useState<ScrubApplyResult[]>([]);
   const [error, setError] = useState<string | null>(null);
   const [loading, setLoading] = useState(false);
 
+  // Check a regular expression as it is typed, before any log is read
+  useEffect(() => {
+    setPatternError(null);
+    setPatternWarning(null);
+    if (!regex || !pattern) return;
+    let cancelled = false;
+    api
+      .validateScrubPattern(pattern)
+      .then((warning) => {
+        if (!cancelled) setPatternWarning(warning);
+      })
+      .catch((err) => {
+        if (!cancelled) setPatternError(err instanceof Error ? err.message : String(err));
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [pattern, regex]);
+
   async function handleScan() {
-    if (!pattern.trim()) return;
+    if (!pattern.trim() || patternError) return;
     setLoading(true);
     setError(null);
     try {
-      const results = await api.scrubScanLogs(pattern);
+      const results = await api.scrubScanLogs(pattern, regex);
       setScanResults(results);
       if (results.length === 0) {
         setError("No matches found in any log files.");
@@ -56,7 +80,7 @@ export function LogScrubber() {
     setError(null);
     try {
       const filePaths = scanResults.map((r) => r.file_path);
-      const results = await api.scrubApplyLogs(pattern, filePaths);
+      const results = await api.scrubApplyLogs(pattern, regex, filePaths);
       setApplyResults(results);
       setPhase("complete");
     } catch (err) {
@@ -68,6 +92,7 @@ export function LogScrubber() {
 
   function handleReset() {
     setPattern("");
+    setRegex(false);
     setPhase("input");
     setScanResults([]);
     setApplyResults([]);
@@ -96,15 +121,27 @@ export function LogScrubber() {
           <Input
             value={pattern}
             onChange={(e) => setPattern(e.target.value)}
-            placeholder="Enter text to find and scrub..."
+            placeholder={
+              regex ? "Enter a regular expression to scrub..." : "Enter text to find and scrub..."
+            }
             className="font-mono text-sm"
             onKeyDown={(e) => {
               if (e.key === "Enter") handleScan();
             }}
           />
+          <div className="flex items-center gap-2">
+            <Switch id="scrub-regex" checked={regex} onCheckedChange={setRegex} />
+            <Label htmlFor="scrub-regex" className="text-sm font-normal">
+              Regular expression
+            </Label>
+          </div>
+          {patternError && <p className="text-sm text-destructive">{patternError}</p>}
+          {patternWarning && (
+            <p className="text-sm text-amber-600 dark:text-amber-400">{patternWarning}</p>
+          )}
           <Button
             onClick={handleScan}
-            disabled={loading || !pattern.trim()}
+            disabled={loading || !pattern.trim() || patternError !== null}
           >
             {loading ? "Scanning..." : "Scan Logs"}
           </Button>
@@ -3,7 +3,7 @@ import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 import type { ProgressUpdate, LogLine, JobStatusEvent } from "@/types/execution/progress";
 import type { ItemizedChange } from "@/types/itemize";
 import type { JobStatus } from "@/types/job";
-import { executeJob as invokeExecute, executeDryRun as invokeDryRun, cancelJob as invokeCancel, getRunningJobs, getMaxItemizedChanges, getLogDirectory } from "@/lib/tauri";
+import { executeJob as invokeExecute, executeDryRun as invokeDryRun, cancelJob as invokeCancel, getRunningJobs, getMaxItemizedChanges, getInvocation } from "@/lib/tauri";
 
 const MAX_LOG_LINES = 10_000;
 
@@ -57,28 +57,38 @@ export function useJobExecution() {
   useEffect(() => {
     let cancelled = false;
 
+    // Lines of one invocation, appended in a single state update
+    const appendLogs = (lines: LogLine[]) => {
+      if (lines.length === 0) return;
+      setJobs((prev) => {
+        const next = new Map(prev);
+        const jobEntries = Array.from(next.entries());
+        const match = jobEntries.find(
+          ([, state]) => state.invocationId === lines[0].invocation_id
+        );
+        if (match) {
+          const [jobId, state] = match;
+          const newLogs = [...state.logs, ...lines];
+          next.set(jobId, {
+            ...state,
+            logs: newLogs.length > MAX_LOG_LINES
+              ? newLogs.slice(newLogs.length - MAX_LOG_LINES)
+              : newLogs,
+          });
+        }
+        return next;
+      });
+    };
+
     // Subscribe to Tauri events
     const setupListeners = async () => {
       const unlistenLog = await listen<LogLine>("job-log", (event) => {
-        const log = event.payload;
-        setJobs((prev) => {
-          const next = new Map(prev);
-          const jobEntries = Array.from(next.entries());
-          const match = jobEntries.find(
-            ([, state]) => state.invocationId === log.invocation_id
-          );
-          if (match) {
-            const [jobId, state] = match;
-            const newLogs = [...state.logs, log];
-            next.set(jobId, {
-              ...state,
-              logs: newLogs.length > MAX_LOG_LINES
-                ? newLogs.slice(newLogs.length - MAX_LOG_LINES)
-                : newLogs,
-            });
-          }
-          return next;
-        });
+        appendLogs([event.payload]);
+      });
+
+      // The executor throttles its events and sends most lines in batches
+      const unlistenLogBatch = await listen<LogLine[]>("job-log-batch", (event) => {
+        appendLogs(event.payload);
       });
 
       const unlistenProgress = await listen<ProgressUpdate>("job-progress", (event) => {
@@ -134,21 +144,28 @@ export function useJobExecution() {
 
       if (cancelled) {
         unlistenLog();
+        unlistenLogBatch();
         unlistenProgress();
         unlistenItemize();
         unlistenStatus();
         return;
       }
 
-      unlistenRefs.current = [unlistenLog, unlistenProgress, unlistenItemize, unlistenStatus];
+      unlistenRefs.current = [
+        unlistenLog,
+        unlistenLogBatch,
+        unlistenProgress,
+        unlistenItemize,
+        unlistenStatus,
+      ];
     };
 
     setupListeners();
 
     // Load initially running jobs
-    getRunningJobs().then((ids) => {
+    getRunningJobs().then((running) => {
       if (cancelled) return;
-      for (const id of ids) {
+      for (const { id } of running) {
         updateJob(id, { status: "Running" });
       }
     });
@@ -176,8 +193,9 @@ export function useJobExecution() {
       });
       try {
         const invocationId = await invokeExecute(jobId);
-        const logDir = await getLogDirectory();
-        updateJob(jobId, { invocationId, logFilePath: `${logDir}/${invocationId}.log` });
+        // The run settles its log file, which may sit outside the log directory
+        const invocation = await getInvocation(invocationId);
+        updateJob(jobId, { invocationId, logFilePath: invocation.execution_output.log_file_path });
       } catch (err) {
         updateJob(jobId, {
           status: "Failed",
@@ -203,8 +221,9 @@ export function useJobExecution() {
       });
       try {
         const invocationId = await invokeDryRun(jobId);
-        const logDir = await getLogDirectory();
-        updateJob(jobId, { invocationId, logFilePath: `${logDir}/${invocationId}.log` });
+        // The run settles its log file, which may sit outside the log directory
+        const invocation = await getInvocation(invocationId);
+        updateJob(jobId, { invocationId, logFilePath: invocation.execution_output.log_file_path });
       } catch (err) {
         updateJob(jobId, {
           status: "Failed",
@@ -45,9 +45,17 @@ export function useJobs(): UseJobsResult {
 
   const handleUpdate = useCallback(
     async (job: JobDefinition): Promise<JobDefinition> => {
-      const updated = await api.updateJob(job);
-      await refresh();
-      return updated;
+      try {
+        const updated = await api.updateJob(job);
+        await refresh();
+        return updated;
+      } catch (err) {
+        // Saved elsewhere since it was loaded: pick up the newer version
+        if (String(err).startsWith("Conflict")) {
+          await refresh();
+        }
+        throw err;
+      }
     },
     [refresh]
   );
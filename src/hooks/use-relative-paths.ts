@@ -0,0 +1,10 @@
+import { useState, useEffect } from "react";
+import * as api from "@/lib/tauri";
+
+export function useRelativePaths() {
+  const [enabled, setEnabled] = useState(false);
+  useEffect(() => {
+    api.getRelativePaths().then(setEnabled).catch(console.error);
+  }, []);
+  return enabled;
+}
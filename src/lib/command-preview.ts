@@ -9,14 +9,28 @@ function ensureTrailingSlash(path: string): string {
   return path.endsWith("/") ? path : `${path}/`;
 }
 
+// Anchor a source path for --relative so only its last component is
+// recreated under the destination. Mirrors relative_source_path() in Rust.
+export function relativeSourcePath(path: string): string {
+  if (path.includes("/./")) return path;
+  const trimmed = path.replace(/\/+$/, "");
+  const idx = trimmed.lastIndexOf("/");
+  if (idx < 0 || idx + 1 >= trimmed.length) return path;
+  return `${trimmed.slice(0, idx)}/./${trimmed.slice(idx + 1)}`;
+}
+
 function storageLocationToRsyncPath(loc: StorageLocation): string {
   switch (loc.type) {
     case "Local":
       return loc.path;
+    case "Mount":
+      return loc.path;
     case "RemoteSsh":
       return `${loc.user}@${loc.host}:${loc.path}`;
     case "RemoteRsync":
-      return `rsync://${loc.host}/${loc.module}/${loc.path}`;
+      return loc.username
+        ? `rsync://${loc.username}@${loc.host}/${loc.module}/${loc.path}`
+        : `rsync://${loc.host}/${loc.module}/${loc.path}`;
   }
 }
 
@@ -35,10 +49,22 @@ function buildSshArgs(ssh: SshConfig): string[] {
     sshParts.push(`-i ${ssh.identity_file}`);
   }
 
+  if (ssh.jump_host) {
+    sshParts.push(`-J ${ssh.jump_host}`);
+  }
+
   if (!ssh.strict_host_key_checking) {
     sshParts.push("-o StrictHostKeyChecking=no");
   }
 
+  if (ssh.connect_timeout !== null) {
+    sshParts.push(`-o ConnectTimeout=${ssh.connect_timeout}`);
+  }
+
+  if (ssh.server_alive_interval !== null) {
+    sshParts.push(`-o ServerAliveInterval=${ssh.server_alive_interval}`);
+  }
+
   if (sshParts.length > 1) {
     return ["-e", sshParts.join(" ")];
   }
@@ -52,13 +78,19 @@ export function buildRsyncArgs(
   options: RsyncOptions,
   sshConfig: SshConfig | null,
   autoTrailingSlash: boolean = false,
+  relativePaths: boolean = false,
 ): string[] {
   const args: string[] = [];
 
   // Core transfer
   if (options.core_transfer.archive) args.push("-a");
   if (options.core_transfer.compress) args.push("-z");
-  if (options.core_transfer.partial) args.push("--partial");
+  // --partial-dir implies --partial
+  if (options.core_transfer.partial_dir) {
+    args.push(`--partial-dir=${options.core_transfer.partial_dir}`);
+  } else if (options.core_transfer.partial) {
+    args.push("--partial");
+  }
   if (options.core_transfer.dry_run) args.push("--dry-run");
   // File handling
   if (options.file_handling.delete) args.push("--delete");
@@ -68,6 +100,15 @@ export function buildRsyncArgs(
   if (options.file_handling.whole_file) args.push("--whole-file");
   if (options.file_handling.ignore_existing) args.push("--ignore-existing");
   if (options.file_handling.one_file_system) args.push("--one-file-system");
+  if (options.file_handling.max_delete !== null) {
+    args.push(`--max-delete=${options.file_handling.max_delete}`);
+  }
+  if (options.file_handling.modify_window !== null) {
+    args.push(`--modify-window=${options.file_handling.modify_window}`);
+  }
+  if (options.file_handling.checksum_choice) {
+    args.push(`--checksum-choice=${options.file_handling.checksum_choice}`);
+  }
   // Metadata
   if (options.metadata.hard_links) args.push("--hard-links");
   if (options.metadata.acls) args.push("--acls");
@@ -92,10 +133,20 @@ export function buildRsyncArgs(
     args.push(`--bwlimit=${options.advanced.bandwidth_limit}`);
   }
 
+  if (options.advanced.compress_choice) {
+    args.push(`--compress-choice=${options.advanced.compress_choice}`);
+  }
+
+  if (options.advanced.compress_level !== null) {
+    args.push(`--compress-level=${options.advanced.compress_level}`);
+  }
+
   if (sshConfig) {
     args.push(...buildSshArgs(sshConfig));
   }
 
+  if (relativePaths) args.push("--relative");
+
   for (const arg of options.advanced.custom_args) {
     args.push(arg);
   }
@@ -103,20 +154,23 @@ export function buildRsyncArgs(
   const sourcePath = storageLocationToRsyncPath(source);
   const destPath = storageLocationToRsyncPath(destination);
 
-  if (autoTrailingSlash) {
+  if (relativePaths) {
+    args.push(relativeSourcePath(sourcePath));
+  } else if (autoTrailingSlash) {
     args.push(ensureTrailingSlash(sourcePath));
-    args.push(ensureTrailingSlash(destPath));
   } else {
     args.push(sourcePath);
-    args.push(destPath);
   }
 
+  args.push(autoTrailingSlash ? ensureTrailingSlash(destPath) : destPath);
+
   return args;
 }
 
 export function buildCommandString(
   job: JobDefinition,
   autoTrailingSlash: boolean = false,
+  relativePaths: boolean = false,
 ): string {
   const args = buildRsyncArgs(
     job.transfer.source,
@@ -124,6 +178,7 @@ export function buildCommandString(
     job.options,
     job.ssh_config,
     autoTrailingSlash,
+    relativePaths,
   );
   return `rsync ${args.join(" ")}`;
 }
@@ -16,6 +16,7 @@ export function createDefaultJob(): JobDefinition {
         archive: true,
         compress: false,
         partial: false,
+        partial_dir: null,
         dry_run: false,
       },
       file_handling: {
@@ -26,6 +27,9 @@ export function createDefaultJob(): JobDefinition {
         whole_file: false,
         ignore_existing: false,
         one_file_system: false,
+        max_delete: null,
+        modify_window: null,
+        checksum_choice: null,
       },
       metadata: {
         hard_links: false,
@@ -42,12 +46,36 @@ export function createDefaultJob(): JobDefinition {
       },
       advanced: {
         exclude_patterns: [],
+        exclude_system_files: false,
         include_patterns: [],
         bandwidth_limit: null,
+        bandwidth_schedule: [],
+        compress_choice: null,
+        compress_level: null,
         custom_args: [],
       },
     },
     ssh_config: null,
+    appearance: {
+      color: null,
+      icon: null,
+    },
+    run_policy: {
+      env: {},
+      max_runtime_mins: null,
+      success_exit_codes: [0],
+      notify_on: "Failure",
+      process_priority: null,
+      create_destination: false,
+      run_on_startup: false,
+      parallel_split: null,
+    },
+    safety: {
+      expect_nonempty_destination: false,
+      change_alarm: null,
+      skip_if_unchanged: false,
+      verify_after_snapshot: false,
+    },
     schedule: null,
     enabled: true,
     created_at: now,
@@ -0,0 +1,51 @@
+import type { InvocationStatus } from "@/types/execution/backup";
+import type { StatusColor } from "@/types/execution/status";
+import type { JobStatus } from "@/types/job";
+
+type AnyStatus = InvocationStatus | JobStatus;
+
+// Mirrors rsync-core's `StatusDisplay`, so the GUI words and colors every
+// state the same way the TUI does.
+const LABELS: Record<AnyStatus, string> = {
+  Idle: "Idle",
+  Running: "Running",
+  Succeeded: "Succeeded",
+  Completed: "Succeeded",
+  Failed: "Failed",
+  Cancelled: "Cancelled",
+  StoppedByLimit: "Stopped at time limit",
+};
+
+const COLOR_HINTS: Record<AnyStatus, StatusColor> = {
+  Idle: "Neutral",
+  Running: "Active",
+  Succeeded: "Success",
+  Completed: "Success",
+  Failed: "Failure",
+  Cancelled: "Warning",
+  StoppedByLimit: "Warning",
+};
+
+export function statusLabel(status: AnyStatus): string {
+  return LABELS[status];
+}
+
+export function statusColorHint(status: AnyStatus): StatusColor {
+  return COLOR_HINTS[status];
+}
+
+export function statusBadgeVariant(
+  status: AnyStatus
+): "default" | "secondary" | "destructive" | "outline" {
+  switch (statusColorHint(status)) {
+    case "Active":
+      return "default";
+    case "Failure":
+      return "destructive";
+    case "Warning":
+      return "outline";
+    case "Success":
+    case "Neutral":
+      return "secondary";
+  }
+}
@@ -1,12 +1,45 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { JobDefinition } from "@/types/job";
-import type { BackupInvocation, SnapshotRecord } from "@/types/execution/backup";
+import type {
+  AdvancedOptions,
+  BackupMode,
+  FullExportData,
+  HostKey,
+  ImportPreview,
+  JobDefinition,
+  RetargetResult,
+  StorageLocation,
+} from "@/types/job";
+import type {
+  BackupInvocation,
+  SnapshotRecord,
+  SnapshotVerification,
+} from "@/types/execution/backup";
 import type { CommandExplanation } from "@/types/command";
-import type { AggregatedStats } from "@/types/execution/statistics";
-import type { PreflightResult } from "@/types/validation";
+import type {
+  AggregatedStats,
+  DestinationUsage,
+  TransferEstimate,
+} from "@/types/execution/statistics";
+import type { AggregateProgress, RunningJobInfo } from "@/types/execution/progress";
+import type { PreflightResult, ValidationCheck } from "@/types/validation";
 import type { LogFileChunk } from "@/types/execution/log-file";
 import type { ScrubScanResult, ScrubApplyResult } from "@/types/scrubber";
-import type { RetentionSettings, DryModeSettings } from "@/types/settings";
+import type { PatternTestEntry } from "@/types/pattern";
+import type { RestorePreview, SnapshotDiff, StoredItemizedChanges } from "@/types/itemize";
+import type { ScheduleType, SchedulerEvent } from "@/types/schedule";
+import type { BackupSet } from "@/types/backup-set";
+import type { AuditEntry } from "@/types/audit";
+import type { DashboardSummary } from "@/types/dashboard";
+import type {
+  RetentionSettings,
+  DryModeSettings,
+  LogFileSettings,
+  LogRelocationResult,
+  UndoPruneResult,
+  AnomalySettings,
+  SafeMirrorSettings,
+  QuietHours,
+} from "@/types/settings";
 
 export async function listJobs(): Promise<JobDefinition[]> {
   return invoke<JobDefinition[]>("list_jobs");
@@ -24,10 +57,29 @@ export async function updateJob(job: JobDefinition): Promise<JobDefinition> {
   return invoke<JobDefinition>("update_job", { job });
 }
 
+export async function retargetJob(
+  id: string,
+  destination: StorageLocation
+): Promise<RetargetResult> {
+  return invoke<RetargetResult>("retarget_job", { id, destination });
+}
+
 export async function deleteJob(id: string): Promise<void> {
   return invoke<void>("delete_job", { id });
 }
 
+export async function findDuplicateJobs(): Promise<string[][]> {
+  return invoke<string[][]>("find_duplicate_jobs");
+}
+
+export async function jobsReferencingPath(path: string): Promise<JobDefinition[]> {
+  return invoke<JobDefinition[]>("jobs_referencing_path", { path });
+}
+
+export async function mergeJobs(keepId: string, removeIds: string[]): Promise<void> {
+  return invoke<void>("merge_jobs", { keepId, removeIds });
+}
+
 export async function getJobHistory(
   jobId: string,
   limit: number
@@ -35,6 +87,37 @@ export async function getJobHistory(
   return invoke<BackupInvocation[]>("get_job_history", { jobId, limit });
 }
 
+export async function getInvocation(invocationId: string): Promise<BackupInvocation> {
+  return invoke<BackupInvocation>("get_invocation", { invocationId });
+}
+
+export async function getSchedulerEvents(
+  jobId: string
+): Promise<SchedulerEvent[]> {
+  return invoke<SchedulerEvent[]>("get_scheduler_events", { jobId });
+}
+
+export async function listAuditEntries(limit: number): Promise<AuditEntry[]> {
+  return invoke<AuditEntry[]>("list_audit_entries", { limit });
+}
+
+export async function previewSchedule(
+  scheduleType: ScheduleType,
+  count: number
+): Promise<string[]> {
+  return invoke<string[]>("preview_schedule", { scheduleType, count });
+}
+
+export async function checkSchedule(scheduleType: ScheduleType): Promise<ValidationCheck> {
+  return invoke<ValidationCheck>("check_schedule", { scheduleType });
+}
+
+export async function getItemizedChanges(
+  invocationId: string
+): Promise<StoredItemizedChanges | null> {
+  return invoke<StoredItemizedChanges | null>("get_itemized_changes", { invocationId });
+}
+
 export async function executeJob(jobId: string): Promise<string> {
   return invoke<string>("execute_job", { jobId });
 }
@@ -47,8 +130,20 @@ export async function cancelJob(jobId: string): Promise<void> {
   return invoke<void>("cancel_job", { jobId });
 }
 
-export async function getRunningJobs(): Promise<string[]> {
-  return invoke<string[]>("get_running_jobs");
+export async function cancelAllJobs(): Promise<number> {
+  return invoke<number>("cancel_all_jobs");
+}
+
+export async function getRunningJobs(): Promise<RunningJobInfo[]> {
+  return invoke<RunningJobInfo[]>("get_running_jobs");
+}
+
+export async function getDashboardSummary(): Promise<DashboardSummary> {
+  return invoke<DashboardSummary>("dashboard_summary");
+}
+
+export async function getAggregateProgress(): Promise<AggregateProgress> {
+  return invoke<AggregateProgress>("get_aggregate_progress");
 }
 
 export async function listSnapshots(jobId: string): Promise<SnapshotRecord[]> {
@@ -59,12 +154,43 @@ export async function deleteSnapshot(snapshotId: string): Promise<void> {
   return invoke<void>("delete_snapshot", { snapshotId });
 }
 
+export async function setSnapshotPinned(snapshotId: string, pinned: boolean): Promise<void> {
+  return invoke<void>("set_snapshot_pinned", { snapshotId, pinned });
+}
+
+export async function getSnapshotVerification(
+  invocationId: string
+): Promise<SnapshotVerification | null> {
+  return invoke<SnapshotVerification | null>("get_snapshot_verification", { invocationId });
+}
+
+export async function diffSnapshots(
+  jobId: string,
+  fromId: string,
+  toId: string
+): Promise<SnapshotDiff> {
+  return invoke<SnapshotDiff>("diff_snapshots", { jobId, fromId, toId });
+}
+
 export async function explainCommand(
   command: string
 ): Promise<CommandExplanation> {
   return invoke<CommandExplanation>("explain_command", { command });
 }
 
+export async function explainTrailingSlash(
+  source: StorageLocation,
+  destination: StorageLocation,
+  mode: BackupMode,
+  autoSlash: boolean
+): Promise<string> {
+  return invoke<string>("explain_trailing_slash", { source, destination, mode, autoSlash });
+}
+
+export async function explainBandwidthSchedule(advanced: AdvancedOptions): Promise<string | null> {
+  return invoke<string | null>("explain_bandwidth_schedule", { advanced });
+}
+
 export async function parseCommandToJob(
   command: string
 ): Promise<JobDefinition> {
@@ -79,10 +205,86 @@ export async function importJobs(json: string): Promise<number> {
   return invoke<number>("import_jobs", { json });
 }
 
+export async function validateImport(
+  json: string,
+  fullBackup: boolean
+): Promise<ImportPreview> {
+  return invoke<ImportPreview>("validate_import", { json, fullBackup });
+}
+
+export async function exportFull(): Promise<string> {
+  return invoke<string>("export_full");
+}
+
+export async function importFull(json: string): Promise<FullExportData> {
+  return invoke<FullExportData>("import_full", { json });
+}
+
 export async function runPreflight(jobId: string): Promise<PreflightResult> {
   return invoke<PreflightResult>("run_preflight", { jobId });
 }
 
+export async function runPreflightAll(): Promise<PreflightResult[]> {
+  return invoke<PreflightResult[]>("run_preflight_all");
+}
+
+export async function listBackupSets(): Promise<BackupSet[]> {
+  return invoke<BackupSet[]>("list_backup_sets");
+}
+
+export async function createBackupSet(name: string, jobIds: string[]): Promise<BackupSet> {
+  return invoke<BackupSet>("create_backup_set", { name, jobIds });
+}
+
+export async function updateBackupSet(set: BackupSet): Promise<BackupSet> {
+  return invoke<BackupSet>("update_backup_set", { set });
+}
+
+export async function deleteBackupSet(setId: string): Promise<void> {
+  return invoke("delete_backup_set", { setId });
+}
+
+export async function runBackupSet(setId: string, stopOnFailure: boolean): Promise<void> {
+  return invoke("run_backup_set", { setId, stopOnFailure });
+}
+
+export async function rerunInvocation(invocationId: string): Promise<string> {
+  return invoke<string>("rerun_invocation", { invocationId });
+}
+
+export async function retryInvocation(invocationId: string): Promise<string> {
+  return invoke<string>("retry_invocation", { invocationId });
+}
+
+export async function estimateTransfer(jobId: string): Promise<TransferEstimate> {
+  return invoke<TransferEstimate>("estimate_transfer", { jobId });
+}
+
+export async function previewRestore(jobId: string): Promise<RestorePreview> {
+  return invoke<RestorePreview>("preview_restore", { jobId });
+}
+
+export async function getDestinationUsage(jobId: string): Promise<DestinationUsage | null> {
+  return invoke<DestinationUsage | null>("destination_usage", { jobId });
+}
+
+export async function scanHostKeys(jobId: string): Promise<HostKey[]> {
+  return invoke<HostKey[]>("scan_host_keys", { jobId });
+}
+
+export async function trustHostKey(key: HostKey): Promise<boolean> {
+  const { host, port, fingerprint } = key;
+  return invoke<boolean>("trust_host_key", { host, port, fingerprint });
+}
+
+export async function getKnownHostsPath(): Promise<string> {
+  return invoke<string>("get_known_hosts_path");
+}
+
+export async function setKnownHostsPath(path: string): Promise<void> {
+  return invoke("set_known_hosts_path", { path });
+}
+
 export async function getStatistics(): Promise<AggregatedStats> {
   return invoke<AggregatedStats>("get_statistics");
 }
@@ -105,6 +307,10 @@ export async function resetStatisticsForJob(jobId: string): Promise<void> {
   return invoke<void>("reset_statistics_for_job", { jobId });
 }
 
+export async function recomputeStatistics(): Promise<number> {
+  return invoke<number>("recompute_statistics");
+}
+
 // --- Settings ---
 
 export async function getSetting(key: string): Promise<string | null> {
@@ -123,6 +329,10 @@ export async function setLogDirectory(path: string): Promise<void> {
   return invoke<void>("set_log_directory", { path });
 }
 
+export async function relocateLogs(newDir: string): Promise<LogRelocationResult> {
+  return invoke<LogRelocationResult>("relocate_logs", { newDir });
+}
+
 export async function getRetentionSettings(): Promise<RetentionSettings> {
   return invoke<RetentionSettings>("get_retention_settings");
 }
@@ -143,6 +353,143 @@ export async function setAutoTrailingSlash(enabled: boolean): Promise<void> {
   return invoke<void>("set_auto_trailing_slash", { enabled });
 }
 
+export async function getRelativePaths(): Promise<boolean> {
+  return invoke<boolean>("get_relative_paths");
+}
+
+export async function setRelativePaths(enabled: boolean): Promise<void> {
+  return invoke<void>("set_relative_paths", { enabled });
+}
+
+// --- Test mode ---
+
+export async function getTestMode(): Promise<boolean> {
+  return invoke<boolean>("get_test_mode");
+}
+
+export async function setTestMode(enabled: boolean): Promise<void> {
+  return invoke<void>("set_test_mode", { enabled });
+}
+
+// --- Log file layout ---
+
+export async function getLogFileSettings(): Promise<LogFileSettings> {
+  return invoke<LogFileSettings>("get_log_file_settings");
+}
+
+export async function setLogFileSettings(
+  settings: LogFileSettings
+): Promise<void> {
+  return invoke<void>("set_log_file_settings", { settings });
+}
+
+// --- Itemized change storage ---
+
+export async function getItemizeStoreLimit(): Promise<number> {
+  return invoke<number>("get_itemize_store_limit");
+}
+
+export async function setItemizeStoreLimit(limit: number): Promise<void> {
+  return invoke<void>("set_itemize_store_limit", { limit });
+}
+
+export async function getLinkDestCount(): Promise<number> {
+  return invoke<number>("get_link_dest_count");
+}
+
+export async function setLinkDestCount(count: number): Promise<void> {
+  return invoke<void>("set_link_dest_count", { count });
+}
+
+export async function getSnapshotTrashDir(): Promise<string> {
+  return invoke<string>("get_snapshot_trash_dir");
+}
+
+export async function setSnapshotTrashDir(path: string): Promise<void> {
+  return invoke<void>("set_snapshot_trash_dir", { path });
+}
+
+export async function emptySnapshotTrash(): Promise<number> {
+  return invoke<number>("empty_snapshot_trash");
+}
+
+export async function undoLastPrune(): Promise<UndoPruneResult | null> {
+  return invoke<UndoPruneResult | null>("undo_last_prune");
+}
+
+// --- Transfer anomaly detection ---
+
+export async function getAnomalySettings(): Promise<AnomalySettings> {
+  return invoke<AnomalySettings>("get_anomaly_settings");
+}
+
+export async function setAnomalySettings(
+  settings: AnomalySettings
+): Promise<void> {
+  return invoke<void>("set_anomaly_settings", { settings });
+}
+
+// --- Safe mirror deletion estimate ---
+
+export async function getSafeMirrorSettings(): Promise<SafeMirrorSettings> {
+  return invoke<SafeMirrorSettings>("get_safe_mirror_settings");
+}
+
+export async function setSafeMirrorSettings(
+  settings: SafeMirrorSettings
+): Promise<void> {
+  return invoke<void>("set_safe_mirror_settings", { settings });
+}
+
+// --- Monthly data budget ---
+
+export async function getMonthlyDataBudget(): Promise<number | null> {
+  return invoke<number | null>("get_monthly_data_budget");
+}
+
+export async function setMonthlyDataBudget(bytes: number | null): Promise<void> {
+  return invoke<void>("set_monthly_data_budget", { bytes });
+}
+
+export async function getRemainingDataBudget(): Promise<number | null> {
+  return invoke<number | null>("get_remaining_data_budget");
+}
+
+// --- Pre-run free space check ---
+
+export async function getPreRunSpaceMargin(): Promise<number | null> {
+  return invoke<number | null>("get_pre_run_space_margin");
+}
+
+export async function setPreRunSpaceMargin(percent: number | null): Promise<void> {
+  return invoke<void>("set_pre_run_space_margin", { percent });
+}
+
+// --- rsync daemon passwords ---
+
+export async function setDaemonSecret(
+  name: string,
+  password: string
+): Promise<void> {
+  return invoke<void>("set_daemon_secret", { name, password });
+}
+
+export async function deleteDaemonSecret(name: string): Promise<void> {
+  return invoke<void>("delete_daemon_secret", { name });
+}
+
+// --- Quiet hours ---
+
+export async function getQuietHours(): Promise<QuietHours | null> {
+  return invoke<QuietHours | null>("get_quiet_hours");
+}
+
+export async function setQuietHours(
+  quietHours: QuietHours | null
+): Promise<void> {
+  return invoke<void>("set_quiet_hours", { quietHours });
+}
+
 // --- Max itemized changes ---
 
 const DEFAULT_MAX_ITEMIZED = 50_000;
@@ -188,6 +535,14 @@ export async function readLogFile(path: string): Promise<string> {
   return invoke<string>("read_log_file", { path });
 }
 
+export async function exportInvocationReport(invocationId: string): Promise<string> {
+  return invoke<string>("export_invocation_report", { invocationId });
+}
+
+export async function exportHistoryNdjson(path: string, since: string | null): Promise<number> {
+  return invoke<number>("export_history_ndjson", { path, since });
+}
+
 export async function readLogFileLines(
   path: string,
   offset: number,
@@ -240,17 +595,59 @@ export async function detectFilesystemType(
   return invoke<string | null>("detect_filesystem_type", { path });
 }
 
+export async function formatBytes(bytes: number): Promise<string> {
+  return invoke<string>("format_bytes", { bytes });
+}
+
+export async function formatRate(bytesPerSec: number): Promise<string> {
+  return invoke<string>("format_rate", { bytesPerSec });
+}
+
+export async function formatDuration(secs: number): Promise<string> {
+  return invoke<string>("format_duration", { secs });
+}
+
+// --- Pattern tester ---
+
+export async function testPatterns(
+  sourceDir: string,
+  includePatterns: string[],
+  excludePatterns: string[]
+): Promise<PatternTestEntry[]> {
+  return invoke<PatternTestEntry[]>("test_patterns", {
+    sourceDir,
+    includePatterns,
+    excludePatterns,
+  });
+}
+
 // --- Log scrubber ---
 
+export async function validateScrubPattern(pattern: string): Promise<string | null> {
+  return invoke<string | null>("validate_scrub_pattern", { pattern });
+}
+
 export async function scrubScanLogs(
-  pattern: string
+  pattern: string,
+  regex: boolean
 ): Promise<ScrubScanResult[]> {
-  return invoke<ScrubScanResult[]>("scrub_scan_logs", { pattern });
+  return invoke<ScrubScanResult[]>("scrub_scan_logs", { pattern, regex });
 }
 
 export async function scrubApplyLogs(
   pattern: string,
+  regex: boolean,
   filePaths: string[]
 ): Promise<ScrubApplyResult[]> {
-  return invoke<ScrubApplyResult[]>("scrub_apply_logs", { pattern, filePaths });
+  return invoke<ScrubApplyResult[]>("scrub_apply_logs", { pattern, regex, filePaths });
+}
+
+// --- Orphaned logs ---
+
+export async function findOrphanedLogs(): Promise<string[]> {
+  return invoke<string[]>("find_orphaned_logs");
+}
+
+export async function cleanOrphanedLogs(): Promise<string[]> {
+  return invoke<string[]>("clean_orphaned_logs");
 }
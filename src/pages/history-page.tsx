@@ -1,8 +1,19 @@
 import { useState, useEffect, useCallback } from "react";
-import { Trash2, FileText, X } from "lucide-react";
+import {
+  Trash2,
+  FileText,
+  FileDown,
+  RotateCcw,
+  Play,
+  X,
+  GitCompare,
+  Pin,
+  PinOff,
+} from "lucide-react";
 import type { JobDefinition } from "@/types/job";
 import type { BackupInvocation, SnapshotRecord } from "@/types/execution/backup";
 import * as api from "@/lib/tauri";
+import { statusBadgeVariant, statusLabel } from "@/lib/status";
 import { Badge } from "@/components/ui/badge";
 import { Button } from "@/components/ui/button";
 import {
@@ -22,23 +33,6 @@ import {
 import { ScrollArea } from "@/components/ui/scroll-area";
 import { HistoricalLogViewer } from "@/components/logs/historical-log-viewer";
 
-function statusVariant(
-  status: string
-): "default" | "secondary" | "destructive" | "outline" {
-  switch (status) {
-    case "Succeeded":
-      return "secondary";
-    case "Failed":
-      return "destructive";
-    case "Cancelled":
-      return "outline";
-    case "Running":
-      return "default";
-    default:
-      return "secondary";
-  }
-}
-
 function formatBytes(bytes: number): string {
   if (bytes === 0) return "0 B";
   const units = ["B", "KB", "MB", "GB", "TB"];
@@ -113,6 +107,69 @@ export function HistoryPage() {
     }
   }
 
+  /** The snapshot taken just before `snap`, if any. */
+  function previousSnapshot(snap: SnapshotRecord): SnapshotRecord | undefined {
+    return snapshots
+      .filter((s) => s.created_at < snap.created_at)
+      .sort((a, b) => b.created_at.localeCompare(a.created_at))[0];
+  }
+
+  async function handleCompareSnapshot(snap: SnapshotRecord) {
+    const previous = previousSnapshot(snap);
+    if (!selectedJobId || !previous) return;
+    try {
+      const diff = await api.diffSnapshots(selectedJobId, previous.id, snap.id);
+      alert(
+        `Changes since ${formatDate(previous.created_at)}:\n\n` +
+          `${diff.created.length} created\n` +
+          `${diff.updated.length} updated\n` +
+          `${diff.deleted.length} deleted`
+      );
+    } catch (err) {
+      console.error("Failed to compare snapshots:", err);
+    }
+  }
+
+  async function handleTogglePin(snap: SnapshotRecord) {
+    try {
+      await api.setSnapshotPinned(snap.id, !snap.pinned);
+      if (selectedJobId) {
+        await loadHistory(selectedJobId);
+      }
+    } catch (err) {
+      console.error("Failed to pin snapshot:", err);
+    }
+  }
+
+  async function handleRerunInvocation(inv: BackupInvocation) {
+    if (
+      !confirm(
+        `Run this exact command again?\n\n${inv.execution_output.command_executed}`
+      )
+    ) {
+      return;
+    }
+    try {
+      await api.rerunInvocation(inv.id);
+    } catch (err) {
+      console.error("Failed to re-run invocation:", err);
+    }
+    if (selectedJobId) {
+      await loadHistory(selectedJobId);
+    }
+  }
+
+  async function handleRetryInvocation(inv: BackupInvocation) {
+    try {
+      await api.retryInvocation(inv.id);
+    } catch (err) {
+      console.error("Failed to retry invocation:", err);
+    }
+    if (selectedJobId) {
+      await loadHistory(selectedJobId);
+    }
+  }
+
   async function handleClearAllHistory() {
     if (!selectedJobId) return;
     if (
@@ -132,6 +189,23 @@ export function HistoryPage() {
     }
   }
 
+  async function handleExportReport(inv: BackupInvocation) {
+    try {
+      const report = await api.exportInvocationReport(inv.id);
+      const blob = new Blob([report], { type: "text/markdown" });
+      const url = URL.createObjectURL(blob);
+      const a = document.createElement("a");
+      a.href = url;
+      a.download = `rsync-studio-run-${inv.started_at.slice(0, 10)}-${inv.id.slice(0, 8)}.md`;
+      document.body.appendChild(a);
+      a.click();
+      document.body.removeChild(a);
+      URL.revokeObjectURL(url);
+    } catch (err) {
+      console.error("Failed to export run report:", err);
+    }
+  }
+
   function handleViewLog(inv: BackupInvocation) {
     if (!inv.execution_output.log_file_path) return;
     setViewingLogId(inv.id);
@@ -259,6 +333,39 @@ export function HistoryPage() {
                             <FileText className="h-3.5 w-3.5" />
                           </Button>
                         )}
+                        <Button
+                          variant="ghost"
+                          size="icon"
+                          className="h-7 w-7"
+                          title="Export run report (secrets redacted)"
+                          onClick={() => handleExportReport(inv)}
+                        >
+                          <FileDown className="h-3.5 w-3.5" />
+                        </Button>
+                        {(inv.status === "Failed" ||
+                          inv.status === "Cancelled" ||
+                          inv.status === "StoppedByLimit") && (
+                          <Button
+                            variant="ghost"
+                            size="icon"
+                            className="h-7 w-7"
+                            title="Retry, resuming partially transferred files"
+                            onClick={() => handleRetryInvocation(inv)}
+                          >
+                            <Play className="h-3.5 w-3.5" />
+                          </Button>
+                        )}
+                        {inv.status !== "Running" && (
+                          <Button
+                            variant="ghost"
+                            size="icon"
+                            className="h-7 w-7"
+                            title="Re-run this exact command"
+                            onClick={() => handleRerunInvocation(inv)}
+                          >
+                            <RotateCcw className="h-3.5 w-3.5" />
+                          </Button>
+                        )}
                         <Button
                           variant="ghost"
                           size="icon"
@@ -269,13 +376,22 @@ export function HistoryPage() {
                           <Trash2 className="h-3.5 w-3.5" />
                         </Button>
                         <Badge variant="outline" className="text-xs">
-                          {inv.trigger}
+                          {inv.rerun_of ? "Rerun" : inv.trigger}
                         </Badge>
+                        {inv.resumed_from && (
+                          <Badge
+                            variant="outline"
+                            className="text-xs"
+                            title="Resumed the snapshot an interrupted run left unfinished"
+                          >
+                            Resumed
+                          </Badge>
+                        )}
                         <Badge
-                          variant={statusVariant(inv.status)}
+                          variant={statusBadgeVariant(inv.status)}
                           className="text-xs"
                         >
-                          {inv.status}
+                          {statusLabel(inv.status)}
                         </Badge>
                       </div>
                     </div>
@@ -293,17 +409,43 @@ export function HistoryPage() {
                         {inv.transfer_stats.total_files > 0 && `/${inv.transfer_stats.total_files}`}
                       </span>
                       <span>Transferred: {formatBytes(inv.transfer_stats.bytes_transferred)}</span>
+                      {inv.transfer_stats.files_deleted > 0 && (
+                        <span>Deleted: {inv.transfer_stats.files_deleted}</span>
+                      )}
                       {inv.execution_output.snapshot_path && (
                         <span className="truncate max-w-[200px]" title={inv.execution_output.snapshot_path}>
                           Snapshot: {inv.execution_output.snapshot_path}
                         </span>
                       )}
                     </div>
+                    {inv.transfer_stats.detailed && (
+                      <div className="mt-1 flex gap-4 text-xs text-muted-foreground">
+                        {inv.transfer_stats.detailed.matched_data !== null && (
+                          <span>Matched: {formatBytes(inv.transfer_stats.detailed.matched_data)}</span>
+                        )}
+                        {inv.transfer_stats.detailed.literal_data !== null && (
+                          <span>Literal: {formatBytes(inv.transfer_stats.detailed.literal_data)}</span>
+                        )}
+                        {inv.transfer_stats.detailed.file_list_generation_secs !== null && (
+                          <span>
+                            File list: {inv.transfer_stats.detailed.file_list_generation_secs.toFixed(3)}s
+                          </span>
+                        )}
+                        {inv.transfer_stats.detailed.bytes_per_sec !== null && (
+                          <span>
+                            Speed: {formatBytes(Math.round(inv.transfer_stats.detailed.bytes_per_sec))}/s
+                          </span>
+                        )}
+                      </div>
+                    )}
                     <div className="mt-1">
                       <code className="text-xs text-muted-foreground break-all">
                         {inv.execution_output.command_executed}
                       </code>
                     </div>
+                    {inv.warning && (
+                      <p className="mt-1 text-xs text-amber-600 dark:text-amber-400">{inv.warning}</p>
+                    )}
                   </CardContent>
                 </Card>
               ))
@@ -331,6 +473,35 @@ export function HistoryPage() {
                             Latest
                           </Badge>
                         )}
+                        {snap.pinned && (
+                          <Badge variant="outline" className="text-xs">
+                            Pinned
+                          </Badge>
+                        )}
+                        <Button
+                          variant="ghost"
+                          size="icon"
+                          className="h-7 w-7"
+                          title={snap.pinned ? "Unpin (allow retention to prune)" : "Pin (keep through retention)"}
+                          onClick={() => handleTogglePin(snap)}
+                        >
+                          {snap.pinned ? (
+                            <PinOff className="h-3.5 w-3.5" />
+                          ) : (
+                            <Pin className="h-3.5 w-3.5" />
+                          )}
+                        </Button>
+                        {previousSnapshot(snap) && (
+                          <Button
+                            variant="ghost"
+                            size="icon"
+                            className="h-7 w-7"
+                            title="Compare with previous snapshot"
+                            onClick={() => handleCompareSnapshot(snap)}
+                          >
+                            <GitCompare className="h-3.5 w-3.5" />
+                          </Button>
+                        )}
                       </div>
                     </div>
                     <CardDescription className="text-xs">
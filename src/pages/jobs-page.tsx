@@ -3,6 +3,7 @@ import type { JobDefinition } from "@/types/job";
 import { useJobs } from "@/hooks/use-jobs";
 import { useJobExecution } from "@/hooks/use-job-execution";
 import { createDefaultJob } from "@/lib/defaults";
+import { findDuplicateJobs, mergeJobs } from "@/lib/tauri";
 import { JobList } from "@/components/jobs/job-list";
 import { JobForm } from "@/components/jobs/form/job-form";
 import { DeleteJobDialog } from "@/components/jobs/delete-job-dialog";
@@ -15,7 +16,7 @@ type View =
   | { view: "running"; jobId: string };
 
 export function JobsPage() {
-  const { jobs, loading, error, handleCreate, handleUpdate, handleDelete } =
+  const { jobs, loading, error, refresh, handleCreate, handleUpdate, handleDelete } =
     useJobs();
   const execution = useJobExecution();
   const [currentView, setCurrentView] = useState<View>({ view: "list" });
@@ -38,6 +39,31 @@ export function JobsPage() {
     }
   }
 
+  async function onMergeDuplicates() {
+    try {
+      const groups = await findDuplicateJobs();
+      if (groups.length === 0) {
+        alert("No duplicate jobs found.");
+        return;
+      }
+      for (const [keepId, ...removeIds] of groups) {
+        const name = (id: string) => jobs.find((j) => j.id === id)?.name ?? id;
+        const duplicates = removeIds.map((id) => `"${name(id)}"`).join(", ");
+        if (
+          confirm(
+            `Merge ${duplicates} into "${name(keepId)}"? Their history and snapshots move to "${name(keepId)}" and the duplicates are deleted.`
+          )
+        ) {
+          await mergeJobs(keepId, removeIds);
+        }
+      }
+    } catch (err) {
+      alert(`Failed to merge jobs: ${err}`);
+    } finally {
+      await refresh();
+    }
+  }
+
   function handleRun(jobId: string) {
     execution.runJob(jobId);
   }
@@ -109,6 +135,8 @@ export function JobsPage() {
         onDryRun={handleDryRun}
         onCancel={(jobId) => execution.cancelJob(jobId)}
         onViewExecution={(jobId) => setCurrentView({ view: "running", jobId })}
+        onRetargeted={refresh}
+        onMergeDuplicates={onMergeDuplicates}
         getStatus={(jobId) => execution.getStatus(jobId)}
         getLogs={(jobId) => execution.getLogs(jobId)}
         getProgress={(jobId) => execution.getProgress(jobId)}
@@ -2,6 +2,7 @@ import { useRef, useState, useEffect } from "react";
 import { FolderOpen, Sun, Moon, Monitor } from "lucide-react";
 import { open } from "@tauri-apps/plugin-dialog";
 import * as api from "@/lib/tauri";
+import type { ImportPreview } from "@/types/job";
 import { themes, type AppearanceMode } from "@/lib/themes";
 import { useTheme } from "@/hooks/use-theme";
 import { Button } from "@/components/ui/button";
@@ -17,6 +18,25 @@ import {
   CardTitle,
 } from "@/components/ui/card";
 
+const GB = 1024 ** 3;
+
+/** Blank or invalid input means "no limit of its own". */
+function parseOptionalCount(value: string): number | null {
+  const n = parseInt(value);
+  return value.trim() === "" || isNaN(n) || n < 0 ? null : n;
+}
+
+function minutesToTime(minutes: number): string {
+  const pad = (n: number) => String(n).padStart(2, "0");
+  return `${pad(Math.floor(minutes / 60))}:${pad(minutes % 60)}`;
+}
+
+/** Parse an `HH:MM` time input; blank means "not set". */
+function timeToMinutes(value: string): number | null {
+  const [h, m] = value.split(":").map((p) => parseInt(p, 10));
+  return isNaN(h) || isNaN(m) ? null : h * 60 + m;
+}
+
 export function SettingsPage() {
   const { theme, setTheme, appearance, setAppearance } = useTheme();
   const [status, setStatus] = useState<{
@@ -25,9 +45,14 @@ export function SettingsPage() {
   } | null>(null);
   const [loading, setLoading] = useState(false);
   const fileInputRef = useRef<HTMLInputElement>(null);
+  const fullFileInputRef = useRef<HTMLInputElement>(null);
 
   // Trailing slash state
   const [autoTrailingSlash, setAutoTrailingSlashState] = useState(true);
+  const [relativePaths, setRelativePathsState] = useState(false);
+
+  // Test mode state
+  const [testMode, setTestModeState] = useState(false);
 
   // NAS auto-detect state
   const [nasAutoDetect, setNasAutoDetectState] = useState(true);
@@ -49,17 +74,104 @@ export function SettingsPage() {
     message: string;
   } | null>(null);
 
+  // Log file layout state
+  const [logTemplate, setLogTemplate] = useState("{id}.log");
+  const [logMaxSizeMb, setLogMaxSizeMb] = useState("");
+  const [logStructured, setLogStructured] = useState(false);
+  const [logMaxLineKb, setLogMaxLineKb] = useState("64");
+  const [logRsyncLogFile, setLogRsyncLogFile] = useState(false);
+  const [logRsyncLogFormat, setLogRsyncLogFormat] = useState("%i %n%L");
+
   // Retention state
   const [maxAgeDays, setMaxAgeDays] = useState(90);
   const [maxPerJob, setMaxPerJob] = useState(15);
+  const [maxSuccessPerJob, setMaxSuccessPerJob] = useState("");
+  const [maxFailurePerJob, setMaxFailurePerJob] = useState("");
+  const [maxRecordAgeDays, setMaxRecordAgeDays] = useState("");
   const [invocationCount, setInvocationCount] = useState(0);
   const [retentionStatus, setRetentionStatus] = useState<{
     type: "success" | "error";
     message: string;
   } | null>(null);
 
+  // Transfer anomaly detection state
+  const [anomalyRuns, setAnomalyRuns] = useState("");
+  const [anomalyFactor, setAnomalyFactor] = useState("");
+  const [anomalyStatus, setAnomalyStatus] = useState<{
+    type: "success" | "error";
+    message: string;
+  } | null>(null);
+
+  // Safe mirror deletion estimate state
+  const [safeMirrorEnabled, setSafeMirrorEnabled] = useState(false);
+  const [safeMirrorPercent, setSafeMirrorPercent] = useState("");
+  const [safeMirrorStatus, setSafeMirrorStatus] = useState<{
+    type: "success" | "error";
+    message: string;
+  } | null>(null);
+
+  // Snapshot linking state
+  const [linkDestCount, setLinkDestCountState] = useState("");
+  const [linkDestStatus, setLinkDestStatus] = useState<{
+    type: "success" | "error";
+    message: string;
+  } | null>(null);
+
+  // Snapshot trash state
+  const [trashDir, setTrashDir] = useState("");
+  const [trashStatus, setTrashStatus] = useState<{
+    type: "success" | "error";
+    message: string;
+  } | null>(null);
+
+  // Orphaned logs state
+  const [orphanedLogs, setOrphanedLogs] = useState<string[] | null>(null);
+  const [orphanStatus, setOrphanStatus] = useState<{
+    type: "success" | "error";
+    message: string;
+  } | null>(null);
+
+  // Itemized change storage state
+  const [itemizeLimit, setItemizeLimit] = useState("");
+  const [itemizeStatus, setItemizeStatus] = useState<{
+    type: "success" | "error";
+    message: string;
+  } | null>(null);
+
+  // SSH known_hosts state
+  const [knownHostsPath, setKnownHostsPathState] = useState("");
+  const [knownHostsStatus, setKnownHostsStatus] = useState<{
+    type: "success" | "error";
+    message: string;
+  } | null>(null);
+
+  // Monthly data budget state (edited in GB, stored in bytes)
+  const [budgetGb, setBudgetGb] = useState("");
+  const [remainingBudget, setRemainingBudget] = useState<number | null>(null);
+  const [budgetStatus, setBudgetStatus] = useState<{
+    type: "success" | "error";
+    message: string;
+  } | null>(null);
+
+  // Pre-run free space check (margin in percent, empty when off)
+  const [spaceMargin, setSpaceMargin] = useState("");
+  const [spaceMarginStatus, setSpaceMarginStatus] = useState<{
+    type: "success" | "error";
+    message: string;
+  } | null>(null);
+
+  // Quiet hours state (edited as HH:MM, stored as minutes after midnight)
+  const [quietStart, setQuietStart] = useState("");
+  const [quietEnd, setQuietEnd] = useState("");
+  const [quietStatus, setQuietStatus] = useState<{
+    type: "success" | "error";
+    message: string;
+  } | null>(null);
+
   useEffect(() => {
     api.getAutoTrailingSlash().then(setAutoTrailingSlashState).catch(console.error);
+    api.getRelativePaths().then(setRelativePathsState).catch(console.error);
+    api.getTestMode().then(setTestModeState).catch(console.error);
     api.getNasAutoDetect().then(setNasAutoDetectState).catch(console.error);
     api.getShowFileHandlingOptions().then(setShowFileHandling).catch(console.error);
     api.getShowMetadataOptions().then(setShowMetadata).catch(console.error);
@@ -73,14 +185,76 @@ export function SettingsPage() {
       .catch(console.error);
     api.getMaxItemizedChanges().then(setMaxItemized).catch(console.error);
     api.getLogDirectory().then(setLogDir).catch(console.error);
+    api
+      .getLogFileSettings()
+      .then((s) => {
+        setLogTemplate(s.path_template);
+        setLogMaxSizeMb(
+          s.max_size_bytes === null ? "" : String(s.max_size_bytes / (1024 * 1024))
+        );
+        setLogStructured(s.structured);
+        setLogMaxLineKb(String(s.max_line_bytes / 1024));
+        setLogRsyncLogFile(s.rsync_log_file);
+        setLogRsyncLogFormat(s.rsync_log_format);
+      })
+      .catch(console.error);
     api
       .getRetentionSettings()
       .then((s) => {
         setMaxAgeDays(s.max_log_age_days);
         setMaxPerJob(s.max_history_per_job);
+        setMaxSuccessPerJob(
+          s.max_success_per_job === null ? "" : String(s.max_success_per_job)
+        );
+        setMaxFailurePerJob(
+          s.max_failure_per_job === null ? "" : String(s.max_failure_per_job)
+        );
+        setMaxRecordAgeDays(
+          s.max_record_age_days === null ? "" : String(s.max_record_age_days)
+        );
       })
       .catch(console.error);
     api.countInvocations().then(setInvocationCount).catch(console.error);
+    api
+      .getAnomalySettings()
+      .then((s) => {
+        setAnomalyRuns(String(s.baseline_runs));
+        setAnomalyFactor(String(s.factor));
+      })
+      .catch(console.error);
+    api
+      .getSafeMirrorSettings()
+      .then((s) => {
+        setSafeMirrorEnabled(s.enabled);
+        setSafeMirrorPercent(String(s.max_delete_percent));
+      })
+      .catch(console.error);
+    api
+      .getItemizeStoreLimit()
+      .then((limit) => setItemizeLimit(String(limit)))
+      .catch(console.error);
+    api
+      .getLinkDestCount()
+      .then((count) => setLinkDestCountState(String(count)))
+      .catch(console.error);
+    api.getSnapshotTrashDir().then(setTrashDir).catch(console.error);
+    api.getKnownHostsPath().then(setKnownHostsPathState).catch(console.error);
+    api
+      .getMonthlyDataBudget()
+      .then((b) => setBudgetGb(b === null ? "" : String(b / GB)))
+      .catch(console.error);
+    api.getRemainingDataBudget().then(setRemainingBudget).catch(console.error);
+    api
+      .getPreRunSpaceMargin()
+      .then((p) => setSpaceMargin(p === null ? "" : String(p)))
+      .catch(console.error);
+    api
+      .getQuietHours()
+      .then((q) => {
+        setQuietStart(q ? minutesToTime(q.start_minute) : "");
+        setQuietEnd(q ? minutesToTime(q.end_minute) : "");
+      })
+      .catch(console.error);
   }, []);
 
   async function handleExport() {
@@ -108,6 +282,32 @@ export function SettingsPage() {
     }
   }
 
+  /** Show what an import would do and ask before writing; false to abort. */
+  function confirmImport(preview: ImportPreview): boolean {
+    const blocking = preview.jobs.filter((j) =>
+      j.failed_checks.some((c) => c.severity === "Error")
+    );
+    if (blocking.length > 0) {
+      const details = blocking
+        .map((j) => {
+          const messages = j.failed_checks.map((c) => c.message).join("; ");
+          return `${j.name || "(unnamed)"}: ${messages}`;
+        })
+        .join("\n");
+      alert(`Nothing was imported. Fix these jobs first:\n\n${details}`);
+      return false;
+    }
+    const creates = preview.jobs.filter((j) => j.action === "Create").length;
+    const updates = preview.jobs.length - creates;
+    const warnings = preview.jobs.filter((j) => j.failed_checks.length > 0).length;
+    return confirm(
+      `This will create ${creates} job${creates !== 1 ? "s" : ""}` +
+        (updates > 0 ? ` and update ${updates}` : "") +
+        (warnings > 0 ? ` (${warnings} with warnings)` : "") +
+        ". Continue?"
+    );
+  }
+
   function handleImportClick() {
     fileInputRef.current?.click();
   }
@@ -120,6 +320,7 @@ export function SettingsPage() {
     setStatus(null);
     try {
       const json = await file.text();
+      if (!confirmImport(await api.validateImport(json, false))) return;
       const count = await api.importJobs(json);
       setStatus({
         type: "success",
@@ -138,13 +339,79 @@ export function SettingsPage() {
     }
   }
 
+  async function handleExportFull() {
+    setLoading(true);
+    setStatus(null);
+    try {
+      const json = await api.exportFull();
+      const blob = new Blob([json], { type: "application/json" });
+      const url = URL.createObjectURL(blob);
+      const a = document.createElement("a");
+      a.href = url;
+      a.download = `rsync-studio-full-${new Date().toISOString().slice(0, 10)}.json`;
+      document.body.appendChild(a);
+      a.click();
+      document.body.removeChild(a);
+      URL.revokeObjectURL(url);
+      setStatus({ type: "success", message: "Full backup exported successfully." });
+    } catch (err) {
+      setStatus({
+        type: "error",
+        message: err instanceof Error ? err.message : String(err),
+      });
+    } finally {
+      setLoading(false);
+    }
+  }
+
+  async function handleFullFileSelected(e: React.ChangeEvent<HTMLInputElement>) {
+    const file = e.target.files?.[0];
+    if (!file) return;
+
+    setLoading(true);
+    setStatus(null);
+    try {
+      const json = await file.text();
+      if (!confirmImport(await api.validateImport(json, true))) return;
+      const restored = await api.importFull(json);
+      setStatus({
+        type: "success",
+        message: `Restored ${restored.jobs.length} job${restored.jobs.length !== 1 ? "s" : ""} and ${restored.invocations.length} run${restored.invocations.length !== 1 ? "s" : ""}.`,
+      });
+    } catch (err) {
+      setStatus({
+        type: "error",
+        message: err instanceof Error ? err.message : String(err),
+      });
+    } finally {
+      setLoading(false);
+      if (fullFileInputRef.current) {
+        fullFileInputRef.current.value = "";
+      }
+    }
+  }
+
   async function handleSaveLogDir() {
     setLogDirStatus(null);
     try {
-      await api.setLogDirectory(logDir);
+      const moved = await api.relocateLogs(logDir);
+      const mb = parseFloat(logMaxSizeMb);
+      const lineKb = parseFloat(logMaxLineKb);
+      await api.setLogFileSettings({
+        path_template: logTemplate,
+        max_size_bytes:
+          logMaxSizeMb.trim() === "" || isNaN(mb) ? null : Math.round(mb * 1024 * 1024),
+        structured: logStructured,
+        max_line_bytes: isNaN(lineKb) || lineKb <= 0 ? 64 * 1024 : Math.round(lineKb * 1024),
+        rsync_log_file: logRsyncLogFile,
+        rsync_log_format: logRsyncLogFormat.trim() === "" ? "%i %n%L" : logRsyncLogFormat,
+      });
       setLogDirStatus({
         type: "success",
-        message: "Log directory saved.",
+        message:
+          moved.updated > 0
+            ? `Log directory saved. Moved ${moved.moved} log file${moved.moved !== 1 ? "s" : ""}${moved.missing > 0 ? ` (${moved.missing} missing)` : ""}.`
+            : "Log directory saved.",
       });
     } catch (err) {
       setLogDirStatus({
@@ -160,6 +427,9 @@ export function SettingsPage() {
       await api.setRetentionSettings({
         max_log_age_days: maxAgeDays,
         max_history_per_job: maxPerJob,
+        max_success_per_job: parseOptionalCount(maxSuccessPerJob),
+        max_failure_per_job: parseOptionalCount(maxFailurePerJob),
+        max_record_age_days: parseOptionalCount(maxRecordAgeDays),
       });
       setRetentionStatus({
         type: "success",
@@ -173,6 +443,253 @@ export function SettingsPage() {
     }
   }
 
+  async function handleSaveAnomaly() {
+    setAnomalyStatus(null);
+    try {
+      await api.setAnomalySettings({
+        baseline_runs: parseInt(anomalyRuns, 10),
+        factor: parseFloat(anomalyFactor),
+      });
+      setAnomalyStatus({ type: "success", message: "Anomaly detection saved." });
+    } catch (err) {
+      setAnomalyStatus({
+        type: "error",
+        message: err instanceof Error ? err.message : String(err),
+      });
+    }
+  }
+
+  async function handleSaveSafeMirror() {
+    setSafeMirrorStatus(null);
+    try {
+      await api.setSafeMirrorSettings({
+        enabled: safeMirrorEnabled,
+        max_delete_percent: parseFloat(safeMirrorPercent),
+      });
+      setSafeMirrorStatus({ type: "success", message: "Deletion estimate saved." });
+    } catch (err) {
+      setSafeMirrorStatus({
+        type: "error",
+        message: err instanceof Error ? err.message : String(err),
+      });
+    }
+  }
+
+  async function handleSaveLinkDestCount() {
+    setLinkDestStatus(null);
+    const count = parseInt(linkDestCount, 10);
+    if (isNaN(count) || count < 1 || count > 20) {
+      setLinkDestStatus({ type: "error", message: "Enter a number from 1 to 20." });
+      return;
+    }
+    try {
+      await api.setLinkDestCount(count);
+      setLinkDestStatus({ type: "success", message: "Snapshot linking saved." });
+    } catch (err) {
+      setLinkDestStatus({
+        type: "error",
+        message: err instanceof Error ? err.message : String(err),
+      });
+    }
+  }
+
+  async function handleSaveTrashDir() {
+    setTrashStatus(null);
+    try {
+      await api.setSnapshotTrashDir(trashDir);
+      setTrashStatus({
+        type: "success",
+        message: trashDir.trim()
+          ? "Pruned snapshots will be moved to the trash."
+          : "Pruned snapshots will be deleted.",
+      });
+    } catch (err) {
+      setTrashStatus({
+        type: "error",
+        message: err instanceof Error ? err.message : String(err),
+      });
+    }
+  }
+
+  async function handleEmptyTrash() {
+    if (!confirm("Permanently delete every trashed snapshot? This cannot be undone.")) {
+      return;
+    }
+    setTrashStatus(null);
+    try {
+      const removed = await api.emptySnapshotTrash();
+      setTrashStatus({
+        type: "success",
+        message: `Removed ${removed} trashed snapshot${removed !== 1 ? "s" : ""}.`,
+      });
+    } catch (err) {
+      setTrashStatus({
+        type: "error",
+        message: err instanceof Error ? err.message : String(err),
+      });
+    }
+  }
+
+  async function handleUndoLastPrune() {
+    setTrashStatus(null);
+    try {
+      const result = await api.undoLastPrune();
+      if (!result) {
+        setTrashStatus({ type: "success", message: "No recent prune to undo." });
+        return;
+      }
+      const restored = result.invocations_restored + result.snapshots_restored;
+      const missing = result.missing_directories.length;
+      setTrashStatus({
+        type: missing > 0 ? "error" : "success",
+        message:
+          `Restored ${restored} record${restored !== 1 ? "s" : ""} and ` +
+          `${result.directories_restored} snapshot folder${result.directories_restored !== 1 ? "s" : ""}.` +
+          (missing > 0
+            ? ` ${missing} snapshot folder${missing !== 1 ? "s were" : " was"} already deleted: ${result.missing_directories.join(", ")}`
+            : ""),
+      });
+    } catch (err) {
+      setTrashStatus({
+        type: "error",
+        message: err instanceof Error ? err.message : String(err),
+      });
+    }
+  }
+
+  async function handleFindOrphanedLogs() {
+    setOrphanStatus(null);
+    try {
+      const found = await api.findOrphanedLogs();
+      setOrphanedLogs(found);
+      setOrphanStatus({
+        type: "success",
+        message:
+          found.length === 0
+            ? "No orphaned log files found."
+            : `Found ${found.length} orphaned log file${found.length !== 1 ? "s" : ""}.`,
+      });
+    } catch (err) {
+      setOrphanStatus({
+        type: "error",
+        message: err instanceof Error ? err.message : String(err),
+      });
+    }
+  }
+
+  async function handleCleanOrphanedLogs() {
+    if (!confirm("Delete every orphaned log file? This cannot be undone.")) {
+      return;
+    }
+    setOrphanStatus(null);
+    try {
+      const removed = await api.cleanOrphanedLogs();
+      setOrphanedLogs([]);
+      setOrphanStatus({
+        type: "success",
+        message: `Removed ${removed.length} orphaned log file${removed.length !== 1 ? "s" : ""}.`,
+      });
+    } catch (err) {
+      setOrphanStatus({
+        type: "error",
+        message: err instanceof Error ? err.message : String(err),
+      });
+    }
+  }
+
+  async function handleSaveItemizeLimit() {
+    setItemizeStatus(null);
+    const limit = parseInt(itemizeLimit, 10);
+    if (isNaN(limit) || limit < 0) {
+      setItemizeStatus({ type: "error", message: "Enter a whole number of changes." });
+      return;
+    }
+    try {
+      await api.setItemizeStoreLimit(limit);
+      setItemizeStatus({ type: "success", message: "Itemized change limit saved." });
+    } catch (err) {
+      setItemizeStatus({
+        type: "error",
+        message: err instanceof Error ? err.message : String(err),
+      });
+    }
+  }
+
+  async function handleSaveKnownHosts() {
+    setKnownHostsStatus(null);
+    if (!knownHostsPath.trim()) {
+      setKnownHostsStatus({ type: "error", message: "Enter a file path." });
+      return;
+    }
+    try {
+      await api.setKnownHostsPath(knownHostsPath.trim());
+      setKnownHostsStatus({ type: "success", message: "known_hosts file saved." });
+    } catch (err) {
+      setKnownHostsStatus({
+        type: "error",
+        message: err instanceof Error ? err.message : String(err),
+      });
+    }
+  }
+
+  async function handleSaveBudget() {
+    setBudgetStatus(null);
+    try {
+      const gb = parseFloat(budgetGb);
+      await api.setMonthlyDataBudget(
+        budgetGb.trim() === "" || isNaN(gb) ? null : Math.round(gb * GB)
+      );
+      setRemainingBudget(await api.getRemainingDataBudget());
+      setBudgetStatus({ type: "success", message: "Data budget saved." });
+    } catch (err) {
+      setBudgetStatus({
+        type: "error",
+        message: err instanceof Error ? err.message : String(err),
+      });
+    }
+  }
+
+  async function handleSaveSpaceMargin() {
+    setSpaceMarginStatus(null);
+    try {
+      const percent = parseInt(spaceMargin, 10);
+      await api.setPreRunSpaceMargin(
+        spaceMargin.trim() === "" || isNaN(percent) ? null : Math.max(0, percent)
+      );
+      setSpaceMarginStatus({ type: "success", message: "Free space check saved." });
+    } catch (err) {
+      setSpaceMarginStatus({
+        type: "error",
+        message: err instanceof Error ? err.message : String(err),
+      });
+    }
+  }
+
+  async function handleSaveQuietHours() {
+    setQuietStatus(null);
+    try {
+      const start = timeToMinutes(quietStart);
+      const end = timeToMinutes(quietEnd);
+      await api.setQuietHours(
+        start === null || end === null
+          ? null
+          : { start_minute: start, end_minute: end }
+      );
+      setQuietStatus({
+        type: "success",
+        message:
+          start === null || end === null
+            ? "Quiet hours cleared."
+            : "Quiet hours saved.",
+      });
+    } catch (err) {
+      setQuietStatus({
+        type: "error",
+        message: err instanceof Error ? err.message : String(err),
+      });
+    }
+  }
+
   return (
     <div className="space-y-4">
       <div>
@@ -283,30 +800,82 @@ export function SettingsPage() {
         </CardContent>
       </Card>
 
-      {/* NAS Auto-Detection */}
+      {/* Relative Paths */}
       <Card>
         <CardHeader>
-          <CardTitle>NAS / Network Filesystem</CardTitle>
+          <CardTitle>Relative Paths</CardTitle>
           <CardDescription>
-            Automatically detect when a job source or destination is on a
-            network filesystem (SMB, NFS, AFP) and suggest compatibility flags
-            that prevent unnecessary re-transfers.
+            Pass --relative (-R) and anchor the source at its last folder, e.g.
+            /home/me/./docs, so the destination receives docs/ instead of the
+            full source path. The source's trailing slash no longer matters in
+            this mode. Applies to the command preview and to runs.
           </CardDescription>
         </CardHeader>
         <CardContent>
           <div className="flex items-center justify-between">
-            <div>
-              <Label htmlFor="nas-auto-detect">Auto-detect network filesystems</Label>
-              <p className="text-xs text-muted-foreground">
-                When enabled, the job form will check paths and auto-enable
-                --size-only for network mounts.
-              </p>
-            </div>
+            <Label htmlFor="relative-paths">Use relative source paths</Label>
             <Switch
-              id="nas-auto-detect"
-              checked={nasAutoDetect}
+              id="relative-paths"
+              checked={relativePaths}
               onCheckedChange={async (checked) => {
-                setNasAutoDetectState(checked);
+                setRelativePathsState(checked);
+                await api.setRelativePaths(checked);
+              }}
+            />
+          </div>
+        </CardContent>
+      </Card>
+
+      {/* Test Mode */}
+      <Card>
+        <CardHeader>
+          <CardTitle>Test Mode</CardTitle>
+          <CardDescription>
+            Force every run to be a dry run, whatever each job is configured
+            to do, so nothing is ever written. Runs are marked with a banner in
+            their log, and no statistics or snapshots are recorded. Re-running
+            past invocations is disabled while test mode is on.
+          </CardDescription>
+        </CardHeader>
+        <CardContent>
+          <div className="flex items-center justify-between">
+            <Label htmlFor="test-mode">Enable test mode</Label>
+            <Switch
+              id="test-mode"
+              checked={testMode}
+              onCheckedChange={async (checked) => {
+                setTestModeState(checked);
+                await api.setTestMode(checked);
+              }}
+            />
+          </div>
+        </CardContent>
+      </Card>
+
+      {/* NAS Auto-Detection */}
+      <Card>
+        <CardHeader>
+          <CardTitle>NAS / Network Filesystem</CardTitle>
+          <CardDescription>
+            Automatically detect when a job source or destination is on a
+            network filesystem (SMB, NFS, AFP) and suggest compatibility flags
+            that prevent unnecessary re-transfers.
+          </CardDescription>
+        </CardHeader>
+        <CardContent>
+          <div className="flex items-center justify-between">
+            <div>
+              <Label htmlFor="nas-auto-detect">Auto-detect network filesystems</Label>
+              <p className="text-xs text-muted-foreground">
+                When enabled, the job form will check paths and auto-enable
+                --size-only for network mounts.
+              </p>
+            </div>
+            <Switch
+              id="nas-auto-detect"
+              checked={nasAutoDetect}
+              onCheckedChange={async (checked) => {
+                setNasAutoDetectState(checked);
                 await api.setNasAutoDetect(checked);
               }}
             />
@@ -475,7 +1044,8 @@ export function SettingsPage() {
         <CardHeader>
           <CardTitle>Log Directory</CardTitle>
           <CardDescription>
-            Directory where job execution logs are stored.
+            Directory where job execution logs are stored, and how log files
+            are named and rotated.
           </CardDescription>
         </CardHeader>
         <CardContent className="space-y-3">
@@ -502,8 +1072,80 @@ export function SettingsPage() {
             >
               <FolderOpen className="h-4 w-4" />
             </Button>
-            <Button onClick={handleSaveLogDir}>Save</Button>
           </div>
+          <div className="grid grid-cols-2 gap-4">
+            <div className="space-y-1">
+              <Label className="text-sm">File name template</Label>
+              <Input
+                value={logTemplate}
+                onChange={(e) => setLogTemplate(e.target.value)}
+                placeholder="{job}/{date}.log"
+              />
+              <p className="text-xs text-muted-foreground">
+                Placeholders: {"{job}"}, {"{date}"}, {"{id}"}
+              </p>
+            </div>
+            <div className="space-y-1">
+              <Label className="text-sm">Rotate after (MB, empty for never)</Label>
+              <Input
+                type="number"
+                min={1}
+                value={logMaxSizeMb}
+                onChange={(e) => setLogMaxSizeMb(e.target.value)}
+              />
+            </div>
+            <div className="space-y-1">
+              <Label className="text-sm">Truncate lines longer than (KB)</Label>
+              <Input
+                type="number"
+                min={1}
+                value={logMaxLineKb}
+                onChange={(e) => setLogMaxLineKb(e.target.value)}
+              />
+            </div>
+          </div>
+          <div className="flex items-center justify-between">
+            <div className="space-y-0.5">
+              <Label htmlFor="log-structured">Also write JSON Lines logs</Label>
+              <p className="text-xs text-muted-foreground">
+                Writes each output line and progress update as JSON to a .jsonl
+                file next to the log, for scripts and log shippers.
+              </p>
+            </div>
+            <Switch
+              id="log-structured"
+              checked={logStructured}
+              onCheckedChange={setLogStructured}
+            />
+          </div>
+          <div className="flex items-center justify-between">
+            <div className="space-y-0.5">
+              <Label htmlFor="log-rsync-log-file">Also keep rsync's own log</Label>
+              <p className="text-xs text-muted-foreground">
+                Passes --log-file to rsync so it writes a .rsync.log file next to
+                the captured log. Pruned together with it.
+              </p>
+            </div>
+            <Switch
+              id="log-rsync-log-file"
+              checked={logRsyncLogFile}
+              onCheckedChange={setLogRsyncLogFile}
+            />
+          </div>
+          {logRsyncLogFile && (
+            <div className="space-y-1">
+              <Label className="text-sm">rsync log format</Label>
+              <Input
+                value={logRsyncLogFormat}
+                onChange={(e) => setLogRsyncLogFormat(e.target.value)}
+                placeholder="%i %n%L"
+              />
+              <p className="text-xs text-muted-foreground">
+                Passed as --log-file-format; see "log format" in rsyncd.conf(5).
+              </p>
+            </div>
+          )}
+          <Button onClick={handleSaveLogDir}>Save</Button>
           {logDirStatus && (
             <p
               className={`text-sm ${
@@ -518,6 +1160,51 @@ export function SettingsPage() {
         </CardContent>
       </Card>
 
+      {/* Orphaned Logs */}
+      <Card>
+        <CardHeader>
+          <CardTitle>Orphaned Logs</CardTitle>
+          <CardDescription>
+            Log files named after runs that no longer have a history record,
+            such as logs left by a crashed run. Retention never removes them.
+          </CardDescription>
+        </CardHeader>
+        <CardContent className="space-y-3">
+          <div className="flex gap-2">
+            <Button variant="outline" onClick={handleFindOrphanedLogs}>
+              Scan
+            </Button>
+            <Button
+              variant="outline"
+              onClick={handleCleanOrphanedLogs}
+              disabled={!orphanedLogs || orphanedLogs.length === 0}
+            >
+              Delete Orphaned Logs
+            </Button>
+          </div>
+          {orphanedLogs && orphanedLogs.length > 0 && (
+            <ul className="max-h-40 overflow-y-auto rounded-md border p-2 text-xs font-mono">
+              {orphanedLogs.map((path) => (
+                <li key={path} className="truncate">
+                  {path}
+                </li>
+              ))}
+            </ul>
+          )}
+          {orphanStatus && (
+            <p
+              className={`text-sm ${
+                orphanStatus.type === "success"
+                  ? "text-green-600 dark:text-green-400"
+                  : "text-destructive"
+              }`}
+            >
+              {orphanStatus.message}
+            </p>
+          )}
+        </CardContent>
+      </Card>
+
       {/* Retention */}
       <Card>
         <CardHeader>
@@ -546,7 +1233,45 @@ export function SettingsPage() {
                 onChange={(e) => setMaxPerJob(parseInt(e.target.value) || 15)}
               />
             </div>
+            <div className="space-y-1">
+              <Label className="text-sm">Successful runs to keep</Label>
+              <Input
+                type="number"
+                min={0}
+                placeholder="Same as max runs"
+                value={maxSuccessPerJob}
+                onChange={(e) => setMaxSuccessPerJob(e.target.value)}
+              />
+            </div>
+            <div className="space-y-1">
+              <Label className="text-sm">Failed runs to keep</Label>
+              <Input
+                type="number"
+                min={0}
+                placeholder="Same as max runs"
+                value={maxFailurePerJob}
+                onChange={(e) => setMaxFailurePerJob(e.target.value)}
+              />
+            </div>
+          </div>
+          <div className="space-y-1">
+            <Label className="text-sm">Keep run records (days)</Label>
+            <Input
+              type="number"
+              min={1}
+              placeholder="Same as max log age"
+              value={maxRecordAgeDays}
+              onChange={(e) => setMaxRecordAgeDays(e.target.value)}
+            />
+            <p className="text-xs text-muted-foreground">
+              Past the log age only the log file is deleted; the run's date,
+              status and transfer stats stay until this age.
+            </p>
           </div>
+          <p className="text-xs text-muted-foreground">
+            Set either split limit to count successful and failed (or cancelled)
+            runs separately, e.g. keep 5 successes but 30 failures for debugging.
+          </p>
           <p className="text-xs text-muted-foreground">
             Currently saved: {invocationCount} invocation
             {invocationCount !== 1 ? "s" : ""}
@@ -566,13 +1291,368 @@ export function SettingsPage() {
         </CardContent>
       </Card>
 
+      {/* Monthly data budget */}
+      <Card>
+        <CardHeader>
+          <CardTitle>Monthly Data Budget</CardTitle>
+          <CardDescription>
+            Cap the bytes transferred per calendar month. Scheduled runs are
+            skipped once the budget would be exceeded; manual runs only warn.
+          </CardDescription>
+        </CardHeader>
+        <CardContent className="space-y-3">
+          <div className="space-y-1 max-w-xs">
+            <Label className="text-sm">Budget (GB, empty for unlimited)</Label>
+            <Input
+              type="number"
+              min={0}
+              value={budgetGb}
+              onChange={(e) => setBudgetGb(e.target.value)}
+            />
+          </div>
+          {remainingBudget !== null && (
+            <p className="text-xs text-muted-foreground">
+              Remaining this month: {(remainingBudget / GB).toFixed(2)} GB
+            </p>
+          )}
+          <Button onClick={handleSaveBudget}>Save</Button>
+          {budgetStatus && (
+            <p
+              className={`text-sm ${
+                budgetStatus.type === "success"
+                  ? "text-green-600 dark:text-green-400"
+                  : "text-destructive"
+              }`}
+            >
+              {budgetStatus.message}
+            </p>
+          )}
+        </CardContent>
+      </Card>
+
+      {/* Pre-run free space check */}
+      <Card>
+        <CardHeader>
+          <CardTitle>Free Space Before Each Run</CardTitle>
+          <CardDescription>
+            Re-check a local destination's free space right before rsync
+            starts, since it may have filled up since preflight. The run fails
+            if the source size plus this margin does not fit.
+          </CardDescription>
+        </CardHeader>
+        <CardContent className="space-y-3">
+          <div className="space-y-1 max-w-xs">
+            <Label className="text-sm">Margin (%, empty to skip the check)</Label>
+            <Input
+              type="number"
+              min={0}
+              value={spaceMargin}
+              onChange={(e) => setSpaceMargin(e.target.value)}
+            />
+          </div>
+          <Button onClick={handleSaveSpaceMargin}>Save</Button>
+          {spaceMarginStatus && (
+            <p
+              className={`text-sm ${
+                spaceMarginStatus.type === "success"
+                  ? "text-green-600 dark:text-green-400"
+                  : "text-destructive"
+              }`}
+            >
+              {spaceMarginStatus.message}
+            </p>
+          )}
+        </CardContent>
+      </Card>
+
+      {/* Quiet hours */}
+      <Card>
+        <CardHeader>
+          <CardTitle>Quiet Hours</CardTitle>
+          <CardDescription>
+            A daily period (local time) when no scheduled runs start. Jobs that
+            fall due run once it ends; manual runs are not affected. Leave
+            either time empty to turn quiet hours off.
+          </CardDescription>
+        </CardHeader>
+        <CardContent className="space-y-3">
+          <div className="grid grid-cols-2 gap-4 max-w-md">
+            <div className="space-y-1">
+              <Label className="text-sm">From</Label>
+              <Input
+                type="time"
+                value={quietStart}
+                onChange={(e) => setQuietStart(e.target.value)}
+              />
+            </div>
+            <div className="space-y-1">
+              <Label className="text-sm">Until</Label>
+              <Input
+                type="time"
+                value={quietEnd}
+                onChange={(e) => setQuietEnd(e.target.value)}
+              />
+            </div>
+          </div>
+          <Button onClick={handleSaveQuietHours}>Save</Button>
+          {quietStatus && (
+            <p
+              className={`text-sm ${
+                quietStatus.type === "success"
+                  ? "text-green-600 dark:text-green-400"
+                  : "text-destructive"
+              }`}
+            >
+              {quietStatus.message}
+            </p>
+          )}
+        </CardContent>
+      </Card>
+
+      {/* Transfer Anomalies */}
+      <Card>
+        <CardHeader>
+          <CardTitle>Transfer Anomalies</CardTitle>
+          <CardDescription>
+            Warn in the run log when a job transfers far more bytes or files
+            than the average of its recent successful runs.
+          </CardDescription>
+        </CardHeader>
+        <CardContent className="space-y-3">
+          <div className="grid grid-cols-2 gap-4 max-w-md">
+            <div className="space-y-1">
+              <Label className="text-sm">Baseline (runs)</Label>
+              <Input
+                type="number"
+                min={1}
+                value={anomalyRuns}
+                onChange={(e) => setAnomalyRuns(e.target.value)}
+              />
+            </div>
+            <div className="space-y-1">
+              <Label className="text-sm">Warn above (× average)</Label>
+              <Input
+                type="number"
+                min={1}
+                step={0.5}
+                value={anomalyFactor}
+                onChange={(e) => setAnomalyFactor(e.target.value)}
+              />
+            </div>
+          </div>
+          <Button onClick={handleSaveAnomaly}>Save</Button>
+          {anomalyStatus && (
+            <p
+              className={`text-sm ${
+                anomalyStatus.type === "success"
+                  ? "text-green-600 dark:text-green-400"
+                  : "text-destructive"
+              }`}
+            >
+              {anomalyStatus.message}
+            </p>
+          )}
+        </CardContent>
+      </Card>
+
+      {/* Safe Mirror */}
+      <Card>
+        <CardHeader>
+          <CardTitle>Safe Mirror</CardTitle>
+          <CardDescription>
+            During preflight, dry-run mirror jobs that use --delete and warn
+            when they would remove a large share of the destination.
+          </CardDescription>
+        </CardHeader>
+        <CardContent className="space-y-3">
+          <div className="flex items-center gap-2">
+            <Switch
+              id="safe-mirror-enabled"
+              checked={safeMirrorEnabled}
+              onCheckedChange={setSafeMirrorEnabled}
+            />
+            <Label htmlFor="safe-mirror-enabled">Estimate deletions in preflight</Label>
+          </div>
+          <div className="space-y-1 max-w-[12rem]">
+            <Label className="text-sm">Warn above (% of destination)</Label>
+            <Input
+              type="number"
+              min={0}
+              max={100}
+              value={safeMirrorPercent}
+              onChange={(e) => setSafeMirrorPercent(e.target.value)}
+            />
+          </div>
+          <Button onClick={handleSaveSafeMirror}>Save</Button>
+          {safeMirrorStatus && (
+            <p
+              className={`text-sm ${
+                safeMirrorStatus.type === "success"
+                  ? "text-green-600 dark:text-green-400"
+                  : "text-destructive"
+              }`}
+            >
+              {safeMirrorStatus.message}
+            </p>
+          )}
+        </CardContent>
+      </Card>
+
+      {/* Snapshot Linking */}
+      <Card>
+        <CardHeader>
+          <CardTitle>Snapshot Linking</CardTitle>
+          <CardDescription>
+            Recent snapshots each snapshot run hard-links unchanged files
+            against (--link-dest). Linking against more than the latest one
+            finds files that changed and changed back. rsync allows up to 20.
+          </CardDescription>
+        </CardHeader>
+        <CardContent className="space-y-3">
+          <div className="space-y-1 max-w-xs">
+            <Label className="text-sm">Snapshots to link against</Label>
+            <Input
+              type="number"
+              min={1}
+              max={20}
+              value={linkDestCount}
+              onChange={(e) => setLinkDestCountState(e.target.value)}
+            />
+          </div>
+          <Button onClick={handleSaveLinkDestCount}>Save</Button>
+          {linkDestStatus && (
+            <p
+              className={`text-sm ${
+                linkDestStatus.type === "success"
+                  ? "text-green-600 dark:text-green-400"
+                  : "text-destructive"
+              }`}
+            >
+              {linkDestStatus.message}
+            </p>
+          )}
+        </CardContent>
+      </Card>
+
+      {/* Snapshot Trash */}
+      <Card>
+        <CardHeader>
+          <CardTitle>Snapshot Trash</CardTitle>
+          <CardDescription>
+            Move snapshots pruned by retention into this directory instead of
+            deleting them, so a bad retention change can be undone. Use a
+            directory on the same drive as the snapshots. Leave empty to
+            delete pruned snapshots.
+          </CardDescription>
+        </CardHeader>
+        <CardContent className="space-y-3">
+          <div className="space-y-1">
+            <Label className="text-sm">Trash directory</Label>
+            <Input
+              value={trashDir}
+              onChange={(e) => setTrashDir(e.target.value)}
+              placeholder="/Volumes/Backup/.snapshot-trash"
+            />
+          </div>
+          <div className="flex gap-2">
+            <Button onClick={handleSaveTrashDir}>Save</Button>
+            <Button variant="outline" onClick={handleEmptyTrash}>
+              Empty Trash
+            </Button>
+            <Button variant="outline" onClick={handleUndoLastPrune}>
+              Undo Last Prune
+            </Button>
+          </div>
+          {trashStatus && (
+            <p
+              className={`text-sm ${
+                trashStatus.type === "success"
+                  ? "text-green-600 dark:text-green-400"
+                  : "text-destructive"
+              }`}
+            >
+              {trashStatus.message}
+            </p>
+          )}
+        </CardContent>
+      </Card>
+
+      {/* Itemized Change Storage */}
+      <Card>
+        <CardHeader>
+          <CardTitle>Itemized Change Storage</CardTitle>
+          <CardDescription>
+            Itemized changes kept per run. Changes past the limit are counted
+            in the run summary but not stored.
+          </CardDescription>
+        </CardHeader>
+        <CardContent className="space-y-3">
+          <div className="space-y-1 max-w-xs">
+            <Label className="text-sm">Maximum changes per run</Label>
+            <Input
+              type="number"
+              min={0}
+              value={itemizeLimit}
+              onChange={(e) => setItemizeLimit(e.target.value)}
+            />
+          </div>
+          <Button onClick={handleSaveItemizeLimit}>Save</Button>
+          {itemizeStatus && (
+            <p
+              className={`text-sm ${
+                itemizeStatus.type === "success"
+                  ? "text-green-600 dark:text-green-400"
+                  : "text-destructive"
+              }`}
+            >
+              {itemizeStatus.message}
+            </p>
+          )}
+        </CardContent>
+      </Card>
+
+      {/* SSH Host Keys */}
+      <Card>
+        <CardHeader>
+          <CardTitle>SSH Host Keys</CardTitle>
+          <CardDescription>
+            Host keys you trust from a job&apos;s host key check are appended to
+            this file. Keys are only added when you confirm them.
+          </CardDescription>
+        </CardHeader>
+        <CardContent className="space-y-3">
+          <div className="space-y-1">
+            <Label className="text-sm">known_hosts file</Label>
+            <Input
+              value={knownHostsPath}
+              onChange={(e) => setKnownHostsPathState(e.target.value)}
+              placeholder="~/.ssh/known_hosts"
+            />
+          </div>
+          <Button onClick={handleSaveKnownHosts}>Save</Button>
+          {knownHostsStatus && (
+            <p
+              className={`text-sm ${
+                knownHostsStatus.type === "success"
+                  ? "text-green-600 dark:text-green-400"
+                  : "text-destructive"
+              }`}
+            >
+              {knownHostsStatus.message}
+            </p>
+          )}
+        </CardContent>
+      </Card>
+
       {/* Export & Import */}
       <Card>
         <CardHeader>
           <CardTitle>Export &amp; Import</CardTitle>
           <CardDescription>
             Export all jobs to a JSON file for backup or transfer. Import jobs
-            from a previously exported file.
+            from a previously exported file. A full backup also includes run
+            history, snapshots and statistics, and can be restored on another
+            machine without duplicating existing records.
           </CardDescription>
         </CardHeader>
         <CardContent className="space-y-3">
@@ -595,6 +1675,25 @@ export function SettingsPage() {
               onChange={handleFileSelected}
             />
           </div>
+          <div className="flex gap-2">
+            <Button onClick={handleExportFull} disabled={loading}>
+              Export Full Backup
+            </Button>
+            <Button
+              variant="outline"
+              onClick={() => fullFileInputRef.current?.click()}
+              disabled={loading}
+            >
+              Restore Full Backup
+            </Button>
+            <input
+              ref={fullFileInputRef}
+              type="file"
+              accept=".json"
+              className="hidden"
+              onChange={handleFullFileSelected}
+            />
+          </div>
           {status && (
             <p
               className={`text-sm ${
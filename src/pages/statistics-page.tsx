@@ -19,27 +19,17 @@ import {
   AlertDialogTitle,
   AlertDialogTrigger,
 } from "@/components/ui/alert-dialog";
-import { Download, RotateCcw } from "lucide-react";
+import { Download, RefreshCw, RotateCcw } from "lucide-react";
 
-function formatBytes(bytes: number): string {
-  if (bytes === 0) return "0 B";
-  const units = ["B", "KB", "MB", "GB", "TB"];
-  const i = Math.floor(Math.log(bytes) / Math.log(1024));
-  return `${(bytes / Math.pow(1024, i)).toFixed(1)} ${units[i]}`;
-}
-
-function formatDuration(secs: number): string {
-  if (secs <= 0) return "0s";
-  const hours = Math.floor(secs / 3600);
-  const mins = Math.floor((secs % 3600) / 60);
-  const remainSecs = Math.floor(secs % 60);
-  if (hours > 0) return `${hours}h ${mins}m ${remainSecs}s`;
-  if (mins > 0) return `${mins}m ${remainSecs}s`;
-  return `${remainSecs}s`;
+interface FormattedStats {
+  bytes: string;
+  duration: string;
+  timeSaved: string;
 }
 
 export function StatisticsPage() {
   const [stats, setStats] = useState<AggregatedStats | null>(null);
+  const [formatted, setFormatted] = useState<FormattedStats | null>(null);
   const [loading, setLoading] = useState(true);
   const [error, setError] = useState<string | null>(null);
 
@@ -48,7 +38,13 @@ export function StatisticsPage() {
     setError(null);
     try {
       const data = await api.getStatistics();
+      const [bytes, duration, timeSaved] = await Promise.all([
+        api.formatBytes(data.total_bytes_transferred),
+        api.formatDuration(data.total_duration_secs),
+        api.formatDuration(data.total_time_saved_secs),
+      ]);
       setStats(data);
+      setFormatted({ bytes, duration, timeSaved });
     } catch (e) {
       setError(String(e));
     } finally {
@@ -75,6 +71,15 @@ export function StatisticsPage() {
     }
   }
 
+  async function handleRecompute() {
+    try {
+      await api.recomputeStatistics();
+      await loadStats();
+    } catch (e) {
+      setError(String(e));
+    }
+  }
+
   async function handleReset() {
     try {
       await api.resetStatistics();
@@ -106,6 +111,30 @@ export function StatisticsPage() {
             <Download className="h-4 w-4 mr-2" />
             Export
           </Button>
+          <AlertDialog>
+            <AlertDialogTrigger asChild>
+              <Button variant="outline" size="sm">
+                <RefreshCw className="h-4 w-4 mr-2" />
+                Recompute
+              </Button>
+            </AlertDialogTrigger>
+            <AlertDialogContent>
+              <AlertDialogHeader>
+                <AlertDialogTitle>Recompute statistics from history?</AlertDialogTitle>
+                <AlertDialogDescription>
+                  All recorded statistics are replaced with ones rebuilt from
+                  the stored successful runs. Runs whose history was deleted
+                  are no longer counted.
+                </AlertDialogDescription>
+              </AlertDialogHeader>
+              <AlertDialogFooter>
+                <AlertDialogCancel>Cancel</AlertDialogCancel>
+                <AlertDialogAction onClick={handleRecompute}>
+                  Recompute
+                </AlertDialogAction>
+              </AlertDialogFooter>
+            </AlertDialogContent>
+          </AlertDialog>
           <AlertDialog>
             <AlertDialogTrigger asChild>
               <Button variant="outline" size="sm">
@@ -162,6 +191,18 @@ export function StatisticsPage() {
               </p>
             </CardContent>
           </Card>
+          <Card>
+            <CardHeader className="pb-2">
+              <CardTitle className="text-sm font-medium text-muted-foreground">
+                Files Deleted
+              </CardTitle>
+            </CardHeader>
+            <CardContent>
+              <p className="text-3xl font-bold">
+                {stats.total_files_deleted.toLocaleString()}
+              </p>
+            </CardContent>
+          </Card>
           <Card>
             <CardHeader className="pb-2">
               <CardTitle className="text-sm font-medium text-muted-foreground">
@@ -170,7 +211,7 @@ export function StatisticsPage() {
             </CardHeader>
             <CardContent>
               <p className="text-3xl font-bold">
-                {formatBytes(stats.total_bytes_transferred)}
+                {formatted?.bytes}
               </p>
             </CardContent>
           </Card>
@@ -182,7 +223,7 @@ export function StatisticsPage() {
             </CardHeader>
             <CardContent>
               <p className="text-3xl font-bold">
-                {formatDuration(stats.total_duration_secs)}
+                {formatted?.duration}
               </p>
             </CardContent>
           </Card>
@@ -194,7 +235,7 @@ export function StatisticsPage() {
             </CardHeader>
             <CardContent>
               <p className="text-3xl font-bold">
-                {formatDuration(stats.total_time_saved_secs)}
+                {formatted?.timeSaved}
               </p>
               <p className="text-xs text-muted-foreground mt-1">
                 via rsync speedup
@@ -0,0 +1,2 @@
+export type { AuditAction } from "./generated/audit/AuditAction";
+export type { AuditEntry } from "./generated/audit/AuditEntry";
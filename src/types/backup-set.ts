@@ -0,0 +1,4 @@
+export type { BackupSet } from "./generated/backup_set/BackupSet";
+export type { BackupSetRun } from "./generated/backup_set/BackupSetRun";
+export type { SetJobResult } from "./generated/backup_set/SetJobResult";
+export type { SetJobOutcome } from "./generated/backup_set/SetJobOutcome";
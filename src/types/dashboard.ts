@@ -0,0 +1,2 @@
+export type { DashboardSummary } from "./generated/dashboard/DashboardSummary";
+export type { OverdueJob } from "./generated/dashboard/OverdueJob";
@@ -1,6 +1,8 @@
 export type { TransferStats } from "../generated/execution/TransferStats";
+export type { DetailedStats } from "../generated/execution/DetailedStats";
 export type { ExecutionOutput } from "../generated/execution/ExecutionOutput";
 export type { BackupInvocation } from "../generated/execution/BackupInvocation";
 export type { InvocationStatus } from "../generated/execution/InvocationStatus";
 export type { InvocationTrigger } from "../generated/execution/InvocationTrigger";
 export type { SnapshotRecord } from "../generated/execution/SnapshotRecord";
+export type { SnapshotVerification } from "../generated/execution/SnapshotVerification";
@@ -1,3 +1,5 @@
 export type { ProgressUpdate } from "../generated/execution/ProgressUpdate";
 export type { LogLine } from "../generated/execution/LogLine";
 export type { JobStatusEvent } from "../generated/execution/JobStatusEvent";
+export type { AggregateProgress } from "../generated/execution/AggregateProgress";
+export type { RunningJobInfo } from "../generated/execution/RunningJobInfo";
@@ -1,2 +1,4 @@
 export type { RunStatistic } from "../generated/execution/RunStatistic";
 export type { AggregatedStats } from "../generated/execution/AggregatedStats";
+export type { TransferEstimate } from "../generated/execution/TransferEstimate";
+export type { DestinationUsage } from "../generated/execution/DestinationUsage";
@@ -0,0 +1 @@
+export type { StatusColor } from "../generated/execution/StatusColor";
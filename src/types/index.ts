@@ -3,13 +3,29 @@ export type {
   BackupMode,
   RetentionPolicy,
   SshConfig,
+  HostKey,
+  RetargetResult,
   RsyncOptions,
   TransferConfig,
   JobDefinition,
   JobStatus,
+  NotifyOn,
+  ProcessPriority,
+  ChangeAlarm,
 } from "./job";
 
-export type { ScheduleConfig, ScheduleType } from "./schedule";
+export type {
+  ScheduleConfig,
+  ScheduleType,
+  SchedulerDecision,
+  SchedulerEvent,
+} from "./schedule";
+
+export type { BackupSet, BackupSetRun, SetJobResult, SetJobOutcome } from "./backup-set";
+
+export type { AuditAction, AuditEntry } from "./audit";
+
+export type { DashboardSummary, OverdueJob } from "./dashboard";
 
 export type { LogLevel, LogEntry } from "./execution/log";
 
@@ -22,13 +38,23 @@ export type {
   SnapshotRecord,
 } from "./execution/backup";
 
-export type { ProgressUpdate, LogLine, JobStatusEvent } from "./execution/progress";
+export type {
+  ProgressUpdate,
+  LogLine,
+  JobStatusEvent,
+  AggregateProgress,
+  RunningJobInfo,
+} from "./execution/progress";
 
 export type {
   ItemizedChange,
   TransferType,
   FileType,
   DifferenceKind,
+  ItemizeSummary,
+  StoredItemizedChanges,
+  SnapshotDiff,
+  RestorePreview,
 } from "./itemize";
 
 export type {
@@ -38,4 +64,4 @@ export type {
   CheckSeverity,
 } from "./validation";
 
-export type { RetentionSettings, DryModeSettings } from "./settings";
+export type { RetentionSettings, DryModeSettings, LogFileSettings, AnomalySettings, SafeMirrorSettings, QuietHours } from "./settings";
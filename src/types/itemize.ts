@@ -2,3 +2,7 @@ export type { ItemizedChange } from "./generated/execution/ItemizedChange";
 export type { TransferType } from "./generated/execution/TransferType";
 export type { FileType } from "./generated/execution/FileType";
 export type { DifferenceKind } from "./generated/execution/DifferenceKind";
+export type { ItemizeSummary } from "./generated/execution/ItemizeSummary";
+export type { StoredItemizedChanges } from "./generated/execution/StoredItemizedChanges";
+export type { SnapshotDiff } from "./generated/execution/SnapshotDiff";
+export type { RestorePreview } from "./generated/execution/RestorePreview";
@@ -2,13 +2,26 @@ export type { BackupMode } from "./generated/job/BackupMode";
 export type { RetentionPolicy } from "./generated/job/RetentionPolicy";
 export type { StorageLocation } from "./generated/job/StorageLocation";
 export type { SshConfig } from "./generated/job/SshConfig";
+export type { HostKey } from "./generated/job/HostKey";
+export type { RetargetResult } from "./generated/job/RetargetResult";
 export type { TransferConfig } from "./generated/job/TransferConfig";
 export type { JobDefinition } from "./generated/job/JobDefinition";
+export type { JobAppearance } from "./generated/job/JobAppearance";
+export type { JobRunPolicy } from "./generated/job/JobRunPolicy";
+export type { JobSafety } from "./generated/job/JobSafety";
+export type { NotifyOn } from "./generated/job/NotifyOn";
+export type { ProcessPriority } from "./generated/job/ProcessPriority";
+export type { ChangeAlarm } from "./generated/job/ChangeAlarm";
 export type { JobStatus } from "./generated/job/JobStatus";
 export type { ExportData } from "./generated/job/ExportData";
+export type { FullExportData } from "./generated/job/FullExportData";
+export type { ImportPreview } from "./generated/job/ImportPreview";
+export type { ImportPreviewJob } from "./generated/job/ImportPreviewJob";
+export type { ImportAction } from "./generated/job/ImportAction";
 export type { CoreTransferOptions } from "./generated/job/CoreTransferOptions";
 export type { FileHandlingOptions } from "./generated/job/FileHandlingOptions";
 export type { MetadataOptions } from "./generated/job/MetadataOptions";
 export type { OutputOptions } from "./generated/job/OutputOptions";
 export type { AdvancedOptions } from "./generated/job/AdvancedOptions";
+export type { BandwidthWindow } from "./generated/job/BandwidthWindow";
 export type { RsyncOptions } from "./generated/job/RsyncOptions";
@@ -0,0 +1,2 @@
+export type { Matched } from "./generated/pattern/Matched";
+export type { PatternTestEntry } from "./generated/pattern/PatternTestEntry";
@@ -1,2 +1,4 @@
 export type { ScheduleConfig } from "./generated/schedule/ScheduleConfig";
 export type { ScheduleType } from "./generated/schedule/ScheduleType";
+export type { SchedulerDecision } from "./generated/schedule/SchedulerDecision";
+export type { SchedulerEvent } from "./generated/schedule/SchedulerEvent";
@@ -1,2 +1,8 @@
 export type { RetentionSettings } from "./generated/settings/RetentionSettings";
 export type { DryModeSettings } from "./generated/settings/DryModeSettings";
+export type { LogFileSettings } from "./generated/settings/LogFileSettings";
+export type { AnomalySettings } from "./generated/settings/AnomalySettings";
+export type { SafeMirrorSettings } from "./generated/settings/SafeMirrorSettings";
+export type { LogRelocationResult } from "./generated/settings/LogRelocationResult";
+export type { UndoPruneResult } from "./generated/settings/UndoPruneResult";
+export type { QuietHours } from "./generated/settings/QuietHours";